@@ -0,0 +1,11 @@
+#!/bin/sh
+# Builds the live-USB image: the headless binary first, then the disk image
+# (needs mkosi 20 or later). Write the result to a stick with
+#   dd if=mkosi.output/quickscan-live.raw of=/dev/sdX bs=4M conv=fsync
+set -eu
+cd "$(dirname "$0")"
+
+cargo build --release --features headless --bin quickscan-headless --manifest-path ../src-tauri/Cargo.toml
+install -D -m 755 ../src-tauri/target/release/quickscan-headless mkosi.extra/usr/bin/quickscan-headless
+mkosi --force build
+echo "image: mkosi.output/quickscan-live.raw"
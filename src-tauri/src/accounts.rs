@@ -0,0 +1,232 @@
+// Personal accounts the machine is signed in to, for `get_signed_in_accounts`,
+// and what syncs through each. A machine handed in still signed in to the
+// seller's Apple ID keeps syncing their photos, keychain and Find My, and
+// can't be erased and set up again without their password. A Windows
+// machine registered with a company's Entra ID stays tied to it through a
+// reset: Autopilot hands it back to the company at the next setup.
+//
+// Only a masked form of each account leaves this module: enough for the
+// seller to recognise their own, not enough to identify them from a report.
+
+use crate::error::QuickscanError;
+use crate::probe_scheduler::ProbeScheduler;
+use serde::Serialize;
+
+#[derive(Serialize, Default)]
+pub struct SignedInAccounts {
+    pub signed_in: bool,
+    pub accounts: Vec<SignedInAccount>,
+}
+
+#[derive(Serialize)]
+pub struct SignedInAccount {
+    pub service: &'static str, // "apple_id", "microsoft" or "work_school"
+    pub account: String, // masked: "j•••e@icloud.com"; a work account without one is its organisation
+    pub services: Vec<String>, // turned on for it: "find_my", "icloud_drive", "onedrive", "entra_joined"…
+}
+
+#[tauri::command]
+pub async fn get_signed_in_accounts(probes: tauri::State<'_, ProbeScheduler>) -> Result<SignedInAccounts, QuickscanError> {
+    probes.run(signed_in_accounts).await
+}
+
+pub fn signed_in_accounts() -> Result<SignedInAccounts, QuickscanError> {
+    #[cfg(target_os = "macos")]
+    {
+        let accounts = macos::accounts()?;
+        Ok(SignedInAccounts { signed_in: !accounts.is_empty(), accounts })
+    }
+    #[cfg(target_os = "windows")]
+    {
+        let accounts = win32::accounts();
+        Ok(SignedInAccounts { signed_in: !accounts.is_empty(), accounts })
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        Err(QuickscanError::unsupported("signed_in_accounts"))
+    }
+}
+
+// "jane.doe@icloud.com" -> "j•••e@icloud.com"; a phone number keeps its
+// first two characters and its last two
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn mask(account: &str) -> String {
+    let hide = |text: &str, keep_start: usize, keep_end: usize| {
+        let chars: Vec<char> = text.chars().collect();
+        if chars.len() <= keep_start + keep_end {
+            return format!("{}•••", chars.first().map(char::to_string).unwrap_or_default());
+        }
+        let start: String = chars[..keep_start].iter().collect();
+        let end: String = chars[chars.len() - keep_end..].iter().collect();
+        format!("{}•••{}", start, end)
+    };
+    match account.split_once('@') {
+        Some((local, domain)) => format!("{}@{}", hide(local, 1, 1), domain),
+        None => hide(account, 2, 2),
+    }
+}
+
+// The seller's Apple ID as the session running the scan is signed in to it.
+// MobileMeAccounts lists each account with the data classes it syncs.
+#[cfg(target_os = "macos")]
+mod macos {
+    use super::SignedInAccount;
+    use crate::error::QuickscanError;
+    use crate::process;
+    use std::process::Command;
+
+    // Data class names as MobileMeAccounts stores them; others are passed on lowercased
+    const SERVICES: &[(&str, &str)] = &[
+        ("FIND_MY_MAC", "find_my"),
+        ("MOBILE_DOCUMENTS", "icloud_drive"),
+        ("CLOUDDESKTOP", "desktop_documents"),
+        ("KEYCHAIN_SYNC", "keychain"),
+        ("MAIL_AND_NOTES", "mail"),
+        ("CONTACTS", "contacts"),
+        ("CALENDAR", "calendars"),
+        ("BOOKMARKS", "safari"),
+    ];
+
+    type Dict = Vec<(String, String)>;
+
+    fn value<'a>(dict: &'a Dict, key: &str) -> Option<&'a str> {
+        dict.iter().find(|(name, _)| name == key).map(|(_, value)| value.as_str())
+    }
+
+    // The old-style plist `defaults` prints: each `{ … }` is a dict, nested
+    // ones (an account's services) close before the one holding them
+    fn parse(plist: &str) -> Vec<SignedInAccount> {
+        let mut stack: Vec<Dict> = vec![];
+        let mut services: Vec<String> = vec![];
+        let mut accounts = vec![];
+        for line in plist.lines().map(str::trim) {
+            if line.ends_with('{') {
+                stack.push(vec![]);
+            } else if line.starts_with('}') {
+                let Some(dict) = stack.pop() else {
+                    continue;
+                };
+                if let Some(account) = value(&dict, "AccountID") {
+                    let signed_in = value(&dict, "LoggedIn") != Some("0");
+                    if signed_in && !account.is_empty() {
+                        accounts.push(SignedInAccount {
+                            service: "apple_id",
+                            account: super::mask(account),
+                            services: std::mem::take(&mut services),
+                        });
+                    }
+                    services.clear();
+                } else if let Some(name) = value(&dict, "Name").filter(|_| value(&dict, "Enabled") == Some("1")) {
+                    let service = SERVICES
+                        .iter()
+                        .find(|(class, _)| *class == name)
+                        .map_or_else(|| name.to_lowercase(), |(_, service)| service.to_string());
+                    services.push(service);
+                }
+            } else if let (Some(dict), Some((name, value))) = (stack.last_mut(), line.split_once(" = ")) {
+                dict.push((name.trim().to_string(), value.trim_end_matches(';').trim_matches('"').to_string()));
+            }
+        }
+        accounts
+    }
+
+    pub fn accounts() -> Result<Vec<SignedInAccount>, QuickscanError> {
+        let output = process::run_cmd(Command::new("defaults").args(["read", "MobileMeAccounts", "Accounts"]))?;
+        // Never signed in: the domain doesn't exist and defaults exits non-zero
+        if !output.status.success() {
+            return Ok(vec![]);
+        }
+        Ok(parse(&String::from_utf8_lossy(&output.stdout)))
+    }
+}
+
+// The Microsoft account Windows is signed in with, every OneDrive account
+// syncing a folder, and the device's join to or registration with a work or
+// school directory (Entra ID), all from the registry
+#[cfg(target_os = "windows")]
+mod win32 {
+    use super::SignedInAccount;
+    use crate::wmi_provider::{registry_string, registry_subkeys, user_registry_string, user_registry_subkeys};
+
+    // One subkey per Microsoft account the user signed in with, named by its address
+    const MSA_ACCOUNTS: &str = "Software\\Microsoft\\IdentityCRL\\UserExtendedProperties";
+    // "Personal", "Business1", "Business2"…
+    const ONEDRIVE_ACCOUNTS: &str = "Software\\Microsoft\\OneDrive\\Accounts";
+    // Joined: the whole machine belongs to the directory (HKLM). Registered:
+    // a work account added under "Access work or school" (HKCU). One subkey
+    // per certificate, with UserEmail and TenantId.
+    const JOIN_INFO: &str = "SYSTEM\\CurrentControlSet\\Control\\CloudDomainJoin\\JoinInfo";
+    const TENANT_INFO: &str = "SYSTEM\\CurrentControlSet\\Control\\CloudDomainJoin\\TenantInfo";
+    const WORKPLACE_JOIN_INFO: &str = "Software\\Microsoft\\Windows NT\\CurrentVersion\\WorkplaceJoin\\JoinInfo";
+
+    type Found = Vec<(&'static str, String, Vec<String>)>;
+
+    fn add(found: &mut Found, service: &'static str, account: String, syncing: Option<&str>) {
+        let index = match found.iter().position(|(kind, name, _)| *kind == service && name.eq_ignore_ascii_case(&account)) {
+            Some(index) => index,
+            None => {
+                found.push((service, account, vec![]));
+                found.len() - 1
+            }
+        };
+        let services = &mut found[index].2;
+        if let Some(syncing) = syncing.filter(|syncing| !services.iter().any(|service| service == syncing)) {
+            services.push(syncing.to_string());
+        }
+    }
+
+    pub fn accounts() -> Vec<SignedInAccount> {
+        // Found by address before masking, so OneDrive joins the account it belongs to
+        let mut found: Found = vec![];
+        for address in user_registry_subkeys(MSA_ACCOUNTS).into_iter().filter(|name| name.contains('@')) {
+            add(&mut found, "microsoft", address, None);
+        }
+
+        // A personal OneDrive belongs to a Microsoft account, a business one
+        // to a work or school account; only those syncing a folder count
+        for name in user_registry_subkeys(ONEDRIVE_ACCOUNTS) {
+            let key = format!("{}\\{}", ONEDRIVE_ACCOUNTS, name);
+            let Some(address) = user_registry_string(&key, "UserEmail") else {
+                continue;
+            };
+            if user_registry_string(&key, "UserFolder").is_none() {
+                continue;
+            }
+            let service = if name == "Personal" { "microsoft" } else { "work_school" };
+            add(&mut found, service, address, Some("onedrive"));
+        }
+
+        // The account that joined, or failing that the organisation's name
+        let tenant_name = || {
+            registry_subkeys(TENANT_INFO)
+                .into_iter()
+                .find_map(|tenant| registry_string(&format!("{}\\{}", TENANT_INFO, tenant), "DisplayName"))
+        };
+        for join in registry_subkeys(JOIN_INFO) {
+            let key = format!("{}\\{}", JOIN_INFO, join);
+            let account = registry_string(&key, "UserEmail")
+                .filter(|email| email.contains('@'))
+                .or_else(tenant_name)
+                .or_else(|| registry_string(&key, "TenantId"))
+                .unwrap_or(join);
+            add(&mut found, "work_school", account, Some("entra_joined"));
+        }
+        for registration in user_registry_subkeys(WORKPLACE_JOIN_INFO) {
+            let key = format!("{}\\{}", WORKPLACE_JOIN_INFO, registration);
+            let account = user_registry_string(&key, "UserEmail")
+                .or_else(|| user_registry_string(&key, "TenantId"))
+                .unwrap_or(registration);
+            add(&mut found, "work_school", account, Some("entra_registered"));
+        }
+
+        found
+            .into_iter()
+            .map(|(service, account, services)| SignedInAccount {
+                service,
+                // Organisation names and tenant IDs aren't personal
+                account: if account.contains('@') { super::mask(&account) } else { account },
+                services,
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,53 @@
+// Find My / Activation Lock (`check_activation_lock`). A Mac that is still
+// tied to the seller's Apple ID can't be set up again after a wipe, so this
+// is the first thing to check on a used Mac.
+
+use crate::error::QuickscanError;
+use crate::probe_scheduler::ProbeScheduler;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct ActivationLockStatus {
+    pub find_my_enabled: bool, // Find My Mac token present in NVRAM
+    pub activation_lock_enabled: Option<bool>, // None on Macs without a T2 or Apple silicon
+    pub may_be_locked: bool,
+}
+
+#[tauri::command]
+pub async fn check_activation_lock(probes: tauri::State<'_, ProbeScheduler>) -> Result<ActivationLockStatus, QuickscanError> {
+    probes.run(activation_lock_status).await
+}
+
+pub fn activation_lock_status() -> Result<ActivationLockStatus, QuickscanError> {
+    #[cfg(target_os = "macos")]
+    {
+        activation_lock_status_macos()
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        Err(QuickscanError::unsupported("activation_lock"))
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn activation_lock_status_macos() -> Result<ActivationLockStatus, QuickscanError> {
+    use std::process::Command;
+
+    // "activation_lock_enabled" / "activation_lock_disabled"
+    let activation_lock_enabled = crate::apple_overview::section("SPHardwareDataType")?
+        .first()
+        .and_then(|hardware| hardware.get("activation_lock_status"))
+        .and_then(|v| v.as_str())
+        .map(|status| status.ends_with("_enabled"));
+
+    // nvram exits non-zero when the variable isn't set
+    let find_my_enabled = crate::process::run_cmd(Command::new("nvram").arg("fmm-mobileme-token-FMM"))?
+        .status
+        .success();
+
+    Ok(ActivationLockStatus {
+        find_my_enabled,
+        activation_lock_enabled,
+        may_be_locked: find_my_enabled || activation_lock_enabled == Some(true),
+    })
+}
@@ -0,0 +1,240 @@
+// One system_profiler run for the data types most checks need
+// (`get_apple_system_overview`). Each system_profiler launch costs seconds
+// before it prints anything, so the hardware, power, NVMe, memory and Wi-Fi
+// sections are asked for together and kept for the rest of the scan; the
+// Wi-Fi, activation lock, component serial and refurbishment checks read
+// their sections from here instead of starting their own.
+
+use crate::error::QuickscanError;
+use crate::probe_scheduler::ProbeScheduler;
+use serde::Serialize;
+
+#[derive(Serialize, Default)]
+pub struct AppleSystemOverview {
+    pub model_name: Option<String>,       // "MacBook Pro"
+    pub model_identifier: Option<String>, // "Mac14,9"
+    pub model_number: Option<String>,     // "MPHE3LL/A"
+    pub chip: Option<String>,             // "Apple M2 Pro", or the CPU on Intel Macs
+    pub cores: Option<String>,            // "10 (6 performance and 4 efficiency)"
+    pub memory: Option<String>,           // "16 GB"
+    pub serial_number: Option<String>,
+    pub firmware: Option<String>,
+    pub activation_lock: Option<bool>,
+    pub battery: Option<AppleBattery>,
+    pub storage: Vec<AppleStorage>,
+    pub memory_details: Option<AppleMemory>,
+    pub wifi: Option<AppleWifi>,
+}
+
+#[derive(Serialize)]
+pub struct AppleBattery {
+    pub serial: Option<String>,
+    pub cycle_count: Option<u64>,
+    pub condition: Option<String>, // "Good", "Service Recommended"
+    pub maximum_capacity_percent: Option<u32>,
+    pub charger_watts: Option<u32>, // connected charger, if any
+}
+
+#[derive(Serialize)]
+pub struct AppleStorage {
+    pub model: String,
+    pub serial: Option<String>,
+    pub size_bytes: Option<u64>,
+    pub smart_status: Option<String>, // "Verified"
+    pub trim: Option<bool>,
+}
+
+#[derive(Serialize)]
+pub struct AppleMemory {
+    pub kind: Option<String>, // "LPDDR5"
+    pub manufacturer: Option<String>,
+    pub upgradeable: Option<bool>,
+}
+
+#[derive(Serialize)]
+pub struct AppleWifi {
+    pub card_type: Option<String>, // "Wi-Fi  (0x14E4, 0x4387)"
+    pub firmware: Option<String>,
+    pub phy_modes: Option<String>, // "802.11 a/b/g/n/ac/ax"
+}
+
+// Always runs system_profiler afresh; the UI calls it as a scan starts so the
+// checks after it share the result
+#[tauri::command]
+pub async fn get_apple_system_overview(probes: tauri::State<'_, ProbeScheduler>) -> Result<AppleSystemOverview, QuickscanError> {
+    probes.run(apple_system_overview).await
+}
+
+pub fn apple_system_overview() -> Result<AppleSystemOverview, QuickscanError> {
+    #[cfg(target_os = "macos")]
+    {
+        let json = macos::refresh()?;
+        Ok(macos::parse(&json))
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        Err(QuickscanError::unsupported("apple_system_overview"))
+    }
+}
+
+#[cfg(target_os = "macos")]
+pub use macos::section;
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use super::{AppleBattery, AppleMemory, AppleStorage, AppleSystemOverview, AppleWifi};
+    use crate::error::QuickscanError;
+    use serde_json::Value;
+    use std::process::Command;
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, Instant};
+
+    pub const DATA_TYPES: [&str; 5] =
+        ["SPHardwareDataType", "SPPowerDataType", "SPNVMeDataType", "SPMemoryDataType", "SPAirPortDataType"];
+
+    // Long enough to cover one scan, stress and memory tests included
+    const MAX_AGE: Duration = Duration::from_secs(10 * 60);
+    // Five data types in one run take longer than the default timeout allows
+    const TIMEOUT: Duration = Duration::from_secs(45);
+
+    // Held while system_profiler runs, so checks that ask at the same time
+    // wait for one run instead of starting several
+    static CACHE: Mutex<Option<(Instant, Arc<Value>)>> = Mutex::new(None);
+
+    pub fn refresh() -> Result<Arc<Value>, QuickscanError> {
+        let mut cache = CACHE.lock().unwrap();
+        let json = Arc::new(run()?);
+        *cache = Some((Instant::now(), json.clone()));
+        Ok(json)
+    }
+
+    fn cached() -> Result<Arc<Value>, QuickscanError> {
+        let mut cache = CACHE.lock().unwrap();
+        if let Some((at, json)) = cache.as_ref() {
+            if at.elapsed() < MAX_AGE {
+                return Ok(json.clone());
+            }
+        }
+        let json = Arc::new(run()?);
+        *cache = Some((Instant::now(), json.clone()));
+        Ok(json)
+    }
+
+    fn run() -> Result<Value, QuickscanError> {
+        let mut command = Command::new("system_profiler");
+        command.args(DATA_TYPES).arg("-json");
+        let output = crate::process::run_probe(&mut command, TIMEOUT)?;
+        if !output.status.success() {
+            return Err(QuickscanError::command_failed(
+                "system_profiler",
+                String::from_utf8_lossy(&output.stderr).trim(),
+            ));
+        }
+        serde_json::from_slice(&output.stdout).map_err(|e| QuickscanError::parse("system_profiler", e))
+    }
+
+    // The entries of one of the overview's data types, e.g.
+    // section("SPAirPortDataType"); empty when the Mac has none
+    pub fn section(data_type: &str) -> Result<Vec<Value>, QuickscanError> {
+        debug_assert!(DATA_TYPES.contains(&data_type));
+        let json = cached()?;
+        Ok(json.get(data_type).and_then(|v| v.as_array()).cloned().unwrap_or_default())
+    }
+
+    fn text(value: &Value, key: &str) -> Option<String> {
+        value.get(key).and_then(|v| v.as_str()).map(|s| s.trim().to_string()).filter(|s| !s.is_empty())
+    }
+
+    // "proc 10:6:4" on Apple silicon, a plain count on Intel
+    fn cores(value: &Value) -> Option<String> {
+        match value.get("number_processors")? {
+            Value::Number(count) => Some(count.to_string()),
+            Value::String(text) => {
+                let counts: Vec<&str> = text.trim_start_matches("proc ").split(':').collect();
+                match counts.as_slice() {
+                    [total, performance, efficiency] => {
+                        Some(format!("{} ({} performance and {} efficiency)", total, performance, efficiency))
+                    }
+                    _ => Some(text.clone()),
+                }
+            }
+            _ => None,
+        }
+    }
+
+    pub fn parse(json: &Value) -> AppleSystemOverview {
+        let items = |data_type: &str| json.get(data_type).and_then(|v| v.as_array()).cloned().unwrap_or_default();
+        let mut overview = AppleSystemOverview::default();
+
+        // {"SPHardwareDataType": [{"machine_name": "MacBook Pro", "machine_model": "Mac14,9",
+        //   "chip_type": "Apple M2 Pro", "number_processors": "proc 10:6:4", "physical_memory": "16 GB",
+        //   "serial_number": "...", "boot_rom_version": "10151.1.1", "activation_lock_status": "activation_lock_disabled"}]}
+        if let Some(hardware) = items("SPHardwareDataType").first() {
+            overview.model_name = text(hardware, "machine_name");
+            overview.model_identifier = text(hardware, "machine_model");
+            overview.model_number = text(hardware, "model_number");
+            overview.chip = text(hardware, "chip_type").or_else(|| text(hardware, "cpu_type"));
+            overview.cores = cores(hardware);
+            overview.memory = text(hardware, "physical_memory");
+            overview.serial_number = text(hardware, "serial_number");
+            overview.firmware = text(hardware, "boot_rom_version");
+            overview.activation_lock = text(hardware, "activation_lock_status").map(|s| s.ends_with("_enabled"));
+        }
+
+        // The battery and the charger are separate entries
+        let power = items("SPPowerDataType");
+        if let Some(battery) = power.iter().find(|item| item.get("sppower_battery_health_info").is_some()) {
+            let health = &battery["sppower_battery_health_info"];
+            let model = &battery["sppower_battery_model_info"];
+            overview.battery = Some(AppleBattery {
+                serial: text(model, "sppower_battery_serial_number"),
+                cycle_count: health.get("sppower_battery_cycle_count").and_then(|v| v.as_u64()),
+                condition: text(health, "sppower_battery_health"),
+                maximum_capacity_percent: text(health, "sppower_battery_health_maximum_capacity")
+                    .and_then(|s| s.trim_end_matches('%').parse().ok()),
+                charger_watts: power
+                    .iter()
+                    .find_map(|item| text(item, "sppower_ac_charger_watts"))
+                    .and_then(|watts| watts.parse().ok()),
+            });
+        }
+
+        // {"SPNVMeDataType": [{"_items": [{"_name": "APPLE SSD AP0512Z", "device_serial": "...",
+        //   "size_in_bytes": 500277792768, "smart_status": "Verified", "spnvme_trim_support": "Yes"}]}]}
+        overview.storage = items("SPNVMeDataType")
+            .iter()
+            .filter_map(|controller| controller.get("_items")?.as_array())
+            .flatten()
+            .map(|drive| AppleStorage {
+                model: text(drive, "device_model").or_else(|| text(drive, "_name")).unwrap_or_default(),
+                serial: text(drive, "device_serial"),
+                size_bytes: drive.get("size_in_bytes").and_then(|v| v.as_u64()),
+                smart_status: text(drive, "smart_status"),
+                trim: text(drive, "spnvme_trim_support").map(|s| s == "Yes"),
+            })
+            .collect();
+
+        // Apple silicon: one entry for the whole package; Intel: a bank list with a DIMM per _items entry
+        if let Some(memory) = items("SPMemoryDataType").first() {
+            let module = memory.get("_items").and_then(|v| v.as_array()).and_then(|dimms| dimms.first()).unwrap_or(memory);
+            overview.memory_details = Some(AppleMemory {
+                kind: text(module, "dimm_type"),
+                manufacturer: text(module, "dimm_manufacturer"),
+                upgradeable: text(memory, "is_memory_upgradeable").map(|s| s == "Yes"),
+            });
+        }
+
+        let wifi = items("SPAirPortDataType")
+            .iter()
+            .filter_map(|group| group.get("spairport_airport_interfaces")?.as_array())
+            .flatten()
+            .find(|interface| interface.get("spairport_card_type").is_some())
+            .cloned();
+        overview.wifi = wifi.map(|interface| AppleWifi {
+            card_type: text(&interface, "spairport_card_type"),
+            firmware: text(&interface, "spairport_firmware_version"),
+            phy_modes: text(&interface, "spairport_supported_phymodes"),
+        });
+        overview
+    }
+}
@@ -0,0 +1,619 @@
+// Audio devices (`get_audio_devices`) and a speaker-to-microphone loopback
+// test (`run_audio_test`). The loopback plays a short run of tones on each
+// output channel in turn and listens for them on the default input, so a
+// blown speaker, a dead channel or a dead microphone shows up as a missing
+// tone without anyone having to listen.
+
+use crate::error::QuickscanError;
+use crate::probe_scheduler::ProbeScheduler;
+use serde::Serialize;
+use std::f64::consts::PI;
+use std::time::Duration;
+
+const SAMPLE_RATE: u32 = 48_000;
+// Each channel gets its own frequencies, so the recording can be searched for
+// them without knowing the output latency. The lower tones are the ones a
+// torn speaker cone loses first.
+const CHANNEL_TONES: [(&str, [u32; 4]); 2] = [("left", [500, 1000, 2000, 4000]), ("right", [630, 1250, 2500, 5000])];
+const TONE_SECONDS: f64 = 0.25;
+const TONE_AMPLITUDE: f64 = 0.5; // -6 dBFS; loud enough to reach the mic, not enough to clip small speakers
+const LEAD_SECONDS: f64 = 0.5;
+const RECORD_DURATION: Duration = Duration::from_secs(5);
+// 50 ms windows resolve 20 Hz, well inside the spacing of the tones
+const WINDOW: usize = SAMPLE_RATE as usize / 20;
+// A tone counts as heard this far above the noise at its frequency
+const DETECTION_MARGIN_DB: f64 = 10.0;
+
+#[derive(Serialize)]
+pub struct AudioDevices {
+    pub outputs: Vec<AudioDevice>,
+    pub inputs: Vec<AudioDevice>,
+    // Some(true) with headphones plugged in, Some(false) for an empty jack,
+    // None where the OS doesn't report the jack
+    pub headphone_jack: Option<bool>,
+}
+
+#[derive(Serialize)]
+pub struct AudioDevice {
+    pub name: String,
+    pub built_in: Option<bool>, // None where the OS doesn't say
+    pub channels: Option<u32>,
+}
+
+#[derive(Serialize)]
+pub struct AudioTestResult {
+    pub channels: Vec<ChannelResult>,
+}
+
+#[derive(Serialize)]
+pub struct ChannelResult {
+    pub channel: &'static str,
+    pub tones: Vec<ToneLevel>,
+    pub level_dbfs: f64, // mean over the tones
+    pub detected: bool, // most of its tones were heard
+}
+
+#[derive(Serialize)]
+pub struct ToneLevel {
+    pub frequency_hz: u32,
+    pub level_dbfs: f64, // loudest 50 ms of the recording at this frequency
+    pub noise_dbfs: f64, // typical level at this frequency while the tone isn't playing
+    pub heard: bool,
+}
+
+#[tauri::command]
+pub async fn get_audio_devices(probes: tauri::State<'_, ProbeScheduler>) -> Result<AudioDevices, QuickscanError> {
+    probes.run(audio_devices).await
+}
+
+// Plays through the default output and records the default input, which are
+// the built-in speakers and microphone unless something else is plugged in
+#[tauri::command]
+pub async fn run_audio_test(app: tauri::AppHandle, probes: tauri::State<'_, ProbeScheduler>) -> Result<AudioTestResult, QuickscanError> {
+    crate::consent::require(&app, crate::consent::ConsentScope::Microphone)?;
+    probes.run(|| {
+        let recording = play_and_record(&test_signal())?;
+        // Exact digital silence is a blocked microphone, not a quiet room
+        if recording.iter().all(|&s| s == 0) {
+            return Err(QuickscanError::PermissionDenied {
+                source: "microphone".to_string(),
+                message: "the recording is silent; check that microphone access is allowed".to_string(),
+            });
+        }
+        Ok(analyze(&recording))
+    })
+    .await
+}
+
+pub fn audio_devices() -> Result<AudioDevices, QuickscanError> {
+    #[cfg(target_os = "macos")]
+    {
+        audio_devices_macos()
+    }
+    #[cfg(target_os = "windows")]
+    {
+        Ok(winmm::devices())
+    }
+    #[cfg(target_os = "linux")]
+    {
+        Ok(audio_devices_linux())
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        Err(QuickscanError::unsupported("audio_devices"))
+    }
+}
+
+// Interleaved stereo: a lead-in of silence, then each channel's tones in turn
+fn test_signal() -> Vec<i16> {
+    let tone_samples = (TONE_SECONDS * SAMPLE_RATE as f64) as usize;
+    let lead_samples = (LEAD_SECONDS * SAMPLE_RATE as f64) as usize;
+    // 10 ms fades keep the tone edges from clicking across the whole spectrum
+    let fade = SAMPLE_RATE as usize / 100;
+
+    let mut signal = vec![0i16; lead_samples * 2];
+    for (channel, (_, tones)) in CHANNEL_TONES.iter().enumerate() {
+        for &frequency in tones {
+            for n in 0..tone_samples {
+                let envelope = (n.min(tone_samples - n) as f64 / fade as f64).min(1.0);
+                let value = TONE_AMPLITUDE * envelope * (2.0 * PI * frequency as f64 * n as f64 / SAMPLE_RATE as f64).sin();
+                let mut frame = [0i16; 2];
+                frame[channel] = (value * i16::MAX as f64) as i16;
+                signal.extend_from_slice(&frame);
+            }
+        }
+        signal.resize(signal.len() + lead_samples * 2, 0);
+    }
+    signal
+}
+
+fn analyze(recording: &[i16]) -> AudioTestResult {
+    let samples: Vec<f64> = recording.iter().map(|&s| s as f64 / i16::MAX as f64).collect();
+    let windows: Vec<&[f64]> = samples.windows(WINDOW).step_by(WINDOW / 2).collect();
+    let round = |db: f64| (db * 10.0).round() / 10.0;
+
+    let channels = CHANNEL_TONES
+        .iter()
+        .map(|(channel, tones)| {
+            let tones: Vec<ToneLevel> = tones
+                .iter()
+                .map(|&frequency| {
+                    let mut levels: Vec<f64> = windows.iter().map(|w| tone_dbfs(w, frequency as f64)).collect();
+                    levels.sort_by(|a, b| a.total_cmp(b));
+                    // The tone fills a few windows out of ~200, so the median is the noise
+                    let level = levels.last().copied().unwrap_or(-140.0);
+                    let noise = levels.get(levels.len() / 2).copied().unwrap_or(-140.0);
+                    ToneLevel {
+                        frequency_hz: frequency,
+                        level_dbfs: round(level),
+                        noise_dbfs: round(noise),
+                        heard: level - noise >= DETECTION_MARGIN_DB,
+                    }
+                })
+                .collect();
+            let level = tones.iter().map(|t| t.level_dbfs).sum::<f64>() / tones.len() as f64;
+            let heard = tones.iter().filter(|t| t.heard).count();
+            ChannelResult { channel, level_dbfs: round(level), detected: heard * 2 > tones.len(), tones }
+        })
+        .collect();
+    AudioTestResult { channels }
+}
+
+// Goertzel: the amplitude at one frequency, relative to a full-scale sine
+fn tone_dbfs(samples: &[f64], frequency: f64) -> f64 {
+    let coefficient = 2.0 * (2.0 * PI * frequency / SAMPLE_RATE as f64).cos();
+    let (mut s1, mut s2) = (0.0, 0.0);
+    for &x in samples {
+        let s0 = x + coefficient * s1 - s2;
+        s2 = s1;
+        s1 = s0;
+    }
+    let power = (s1 * s1 + s2 * s2 - coefficient * s1 * s2).max(0.0);
+    let amplitude = 2.0 * power.sqrt() / samples.len() as f64;
+    20.0 * amplitude.max(1e-7).log10()
+}
+
+fn play_and_record(signal: &[i16]) -> Result<Vec<i16>, QuickscanError> {
+    #[cfg(target_os = "macos")]
+    {
+        play_and_record_macos(signal)
+    }
+    #[cfg(target_os = "windows")]
+    {
+        winmm::play_and_record(signal, RECORD_DURATION)
+    }
+    #[cfg(target_os = "linux")]
+    {
+        play_and_record_linux(signal)
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        let _ = signal;
+        Err(QuickscanError::unsupported("audio_test"))
+    }
+}
+
+// afplay and aplay both take a plain 16-bit PCM WAV file
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn write_wav(signal: &[i16]) -> Result<std::path::PathBuf, QuickscanError> {
+    let data_len = (signal.len() * 2) as u32;
+    let mut wav = Vec::with_capacity(44 + data_len as usize);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&2u16.to_le_bytes());
+    wav.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    wav.extend_from_slice(&(SAMPLE_RATE * 4).to_le_bytes());
+    wav.extend_from_slice(&4u16.to_le_bytes());
+    wav.extend_from_slice(&16u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    for sample in signal {
+        wav.extend_from_slice(&sample.to_le_bytes());
+    }
+
+    let path = std::env::temp_dir().join(format!("quickscan-audio-test-{}.wav", std::process::id()));
+    std::fs::write(&path, wav).map_err(|e| QuickscanError::io("audio_test", e))?;
+    Ok(path)
+}
+
+#[cfg(target_os = "macos")]
+fn audio_devices_macos() -> Result<AudioDevices, QuickscanError> {
+    use std::process::Command;
+
+    // {"SPAudioDataType": [{"_items": [{"_name": "MacBook Pro Speakers",
+    //   "coreaudio_device_output": 2, "coreaudio_device_transport": "coreaudio_device_type_builtin", ...}]}]}
+    let output = crate::process::run_cmd_checked(Command::new("system_profiler").args(["SPAudioDataType", "-json"]))?;
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).map_err(|e| QuickscanError::parse("system_profiler", e))?;
+    let items = json
+        .get("SPAudioDataType")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|group| group.get("_items")?.as_array())
+        .flatten();
+
+    let mut devices = AudioDevices { outputs: vec![], inputs: vec![], headphone_jack: None };
+    for item in items {
+        let field = |key: &str| item.get(key).and_then(|v| v.as_str());
+        let name = field("_name").unwrap_or("Audio device").to_string();
+        let built_in = field("coreaudio_device_transport").map(|t| t == "coreaudio_device_type_builtin");
+        // Intel Macs switch the built-in output's source to "Headphones";
+        // Apple silicon adds an "External Headphones" device
+        if built_in == Some(true) && item.get("coreaudio_device_output").is_some() {
+            let source = field("coreaudio_output_source").unwrap_or_default();
+            let plugged_in = name.contains("Headphones") || source.contains("Headphones");
+            devices.headphone_jack = Some(devices.headphone_jack.unwrap_or(false) || plugged_in);
+        }
+        let channels = |key: &str| item.get(key).and_then(|v| v.as_u64()).map(|n| n as u32);
+        if let Some(count) = channels("coreaudio_device_output") {
+            devices.outputs.push(AudioDevice { name: name.clone(), built_in, channels: Some(count) });
+        }
+        if let Some(count) = channels("coreaudio_device_input") {
+            devices.inputs.push(AudioDevice { name, built_in, channels: Some(count) });
+        }
+    }
+    Ok(devices)
+}
+
+// afplay plays the tones while an Audio Queue records; there's no recording tool
+// in the base system
+#[cfg(target_os = "macos")]
+fn play_and_record_macos(signal: &[i16]) -> Result<Vec<i16>, QuickscanError> {
+    let path = write_wav(signal)?;
+    let player = {
+        let path = path.clone();
+        std::thread::spawn(move || {
+            // Give the input queue a moment to start so the lead-in is recorded
+            std::thread::sleep(Duration::from_millis(300));
+            crate::process::run_cmd_with_timeout(std::process::Command::new("afplay").arg(&path), crate::process::DEFAULT_TIMEOUT)
+        })
+    };
+    let recording = audio_queue::record(SAMPLE_RATE, RECORD_DURATION);
+    let played = player.join().unwrap_or_else(|e| std::panic::resume_unwind(e));
+    let _ = std::fs::remove_file(&path);
+
+    let output = played?;
+    if !output.status.success() {
+        return Err(QuickscanError::command_failed("afplay", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    recording
+}
+
+// Audio Queue Services, the plain C recording API in AudioToolbox
+#[cfg(target_os = "macos")]
+mod audio_queue {
+    use crate::error::QuickscanError;
+    use std::ffi::c_void;
+    use std::ptr;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    type AudioQueueRef = *mut c_void;
+    type InputCallback = extern "C" fn(*mut c_void, AudioQueueRef, *mut AudioQueueBuffer, *const c_void, u32, *const c_void);
+
+    #[repr(C)]
+    struct AudioStreamBasicDescription {
+        sample_rate: f64,
+        format_id: u32,
+        format_flags: u32,
+        bytes_per_packet: u32,
+        frames_per_packet: u32,
+        bytes_per_frame: u32,
+        channels_per_frame: u32,
+        bits_per_channel: u32,
+        reserved: u32,
+    }
+
+    #[repr(C)]
+    struct AudioQueueBuffer {
+        audio_data_bytes_capacity: u32,
+        audio_data: *mut c_void,
+        audio_data_byte_size: u32,
+        user_data: *mut c_void,
+        packet_description_capacity: u32,
+        packet_descriptions: *mut c_void,
+        packet_description_count: u32,
+    }
+
+    #[link(name = "AudioToolbox", kind = "framework")]
+    extern "C" {
+        fn AudioQueueNewInput(
+            format: *const AudioStreamBasicDescription,
+            callback: InputCallback,
+            user_data: *mut c_void,
+            run_loop: *const c_void,
+            run_loop_mode: *const c_void,
+            flags: u32,
+            queue: *mut AudioQueueRef,
+        ) -> i32;
+        fn AudioQueueAllocateBuffer(queue: AudioQueueRef, byte_size: u32, buffer: *mut *mut AudioQueueBuffer) -> i32;
+        fn AudioQueueEnqueueBuffer(queue: AudioQueueRef, buffer: *mut AudioQueueBuffer, packets: u32, descriptions: *const c_void) -> i32;
+        fn AudioQueueStart(queue: AudioQueueRef, start_time: *const c_void) -> i32;
+        fn AudioQueueStop(queue: AudioQueueRef, immediate: u8) -> i32;
+        fn AudioQueueDispose(queue: AudioQueueRef, immediate: u8) -> i32;
+    }
+
+    const FORMAT_LINEAR_PCM: u32 = u32::from_be_bytes(*b"lpcm");
+    const FLAGS_SIGNED_PACKED: u32 = 0x4 | 0x8;
+    const BUFFERS: usize = 3;
+    const BUFFER_FRAMES: u32 = 4800; // 100 ms
+
+    // Runs on the queue's own thread; hands the buffer straight back for refilling
+    extern "C" fn on_input(
+        user_data: *mut c_void,
+        queue: AudioQueueRef,
+        buffer: *mut AudioQueueBuffer,
+        _start_time: *const c_void,
+        _packets: u32,
+        _descriptions: *const c_void,
+    ) {
+        unsafe {
+            let samples = &*(user_data as *const Mutex<Vec<i16>>);
+            let buffer_ref = &*buffer;
+            let data = std::slice::from_raw_parts(buffer_ref.audio_data as *const i16, buffer_ref.audio_data_byte_size as usize / 2);
+            samples.lock().unwrap_or_else(|e| e.into_inner()).extend_from_slice(data);
+            AudioQueueEnqueueBuffer(queue, buffer, 0, ptr::null());
+        }
+    }
+
+    fn check(status: i32, call: &str) -> Result<(), QuickscanError> {
+        if status == 0 {
+            Ok(())
+        } else {
+            Err(QuickscanError::command_failed("AudioToolbox", format!("{} failed ({})", call, status)))
+        }
+    }
+
+    // Mono 16-bit from the default input for `duration`
+    pub fn record(sample_rate: u32, duration: Duration) -> Result<Vec<i16>, QuickscanError> {
+        let format = AudioStreamBasicDescription {
+            sample_rate: sample_rate as f64,
+            format_id: FORMAT_LINEAR_PCM,
+            format_flags: FLAGS_SIGNED_PACKED,
+            bytes_per_packet: 2,
+            frames_per_packet: 1,
+            bytes_per_frame: 2,
+            channels_per_frame: 1,
+            bits_per_channel: 16,
+            reserved: 0,
+        };
+        // Boxed so its address stays put while the queue thread writes into it
+        let samples: Box<Mutex<Vec<i16>>> = Box::default();
+        unsafe {
+            let mut queue: AudioQueueRef = ptr::null_mut();
+            let user_data = &*samples as *const Mutex<Vec<i16>> as *mut c_void;
+            check(AudioQueueNewInput(&format, on_input, user_data, ptr::null(), ptr::null(), 0, &mut queue), "AudioQueueNewInput")?;
+            let result = (|| {
+                for _ in 0..BUFFERS {
+                    let mut buffer = ptr::null_mut();
+                    check(AudioQueueAllocateBuffer(queue, BUFFER_FRAMES * 2, &mut buffer), "AudioQueueAllocateBuffer")?;
+                    check(AudioQueueEnqueueBuffer(queue, buffer, 0, ptr::null()), "AudioQueueEnqueueBuffer")?;
+                }
+                check(AudioQueueStart(queue, ptr::null()), "AudioQueueStart")?;
+                std::thread::sleep(duration);
+                check(AudioQueueStop(queue, 1), "AudioQueueStop")
+            })();
+            // Disposing also frees the buffers; no callbacks run after it returns
+            AudioQueueDispose(queue, 1);
+            result?;
+        }
+        Ok(samples.into_inner().unwrap_or_else(|e| e.into_inner()))
+    }
+}
+
+// The waveIn/waveOut API: old, but it needs neither COM nor a callback, and
+// the wave mapper is the default device
+#[cfg(target_os = "windows")]
+mod winmm {
+    use super::{AudioDevice, AudioDevices, SAMPLE_RATE};
+    use crate::error::QuickscanError;
+    use std::mem::size_of;
+    use std::time::{Duration, Instant};
+    use windows::core::PSTR;
+    use windows::Win32::Media::Audio::{
+        waveInAddBuffer, waveInClose, waveInGetDevCapsW, waveInGetNumDevs, waveInOpen, waveInPrepareHeader,
+        waveInReset, waveInStart, waveInUnprepareHeader, waveOutClose, waveOutGetDevCapsW, waveOutGetNumDevs,
+        waveOutOpen, waveOutPrepareHeader, waveOutReset, waveOutUnprepareHeader, waveOutWrite, CALLBACK_NULL,
+        HWAVEIN, HWAVEOUT, WAVEFORMATEX, WAVEHDR, WAVEINCAPSW, WAVEOUTCAPSW, WAVE_FORMAT_PCM, WAVE_MAPPER,
+        WHDR_DONE,
+    };
+    use windows::Win32::Media::MMSYSERR_NOERROR;
+
+    const POLL_INTERVAL: Duration = Duration::from_millis(25);
+
+    fn check(result: u32, call: &str) -> Result<(), QuickscanError> {
+        if result == MMSYSERR_NOERROR {
+            Ok(())
+        } else {
+            Err(QuickscanError::command_failed("winmm", format!("{} failed ({})", call, result)))
+        }
+    }
+
+    // Device names are cut off at 31 characters
+    fn name(units: &[u16]) -> String {
+        let len = units.iter().position(|&c| c == 0).unwrap_or(units.len());
+        String::from_utf16_lossy(&units[..len])
+    }
+
+    pub fn devices() -> AudioDevices {
+        let mut outputs = vec![];
+        let mut inputs = vec![];
+        unsafe {
+            for id in 0..waveOutGetNumDevs() as usize {
+                let mut caps = WAVEOUTCAPSW::default();
+                if waveOutGetDevCapsW(id, &mut caps, size_of::<WAVEOUTCAPSW>() as u32) == MMSYSERR_NOERROR {
+                    let pname = caps.szPname; // packed struct
+                    outputs.push(AudioDevice { name: name(&pname), built_in: None, channels: Some(caps.wChannels as u32) });
+                }
+            }
+            for id in 0..waveInGetNumDevs() as usize {
+                let mut caps = WAVEINCAPSW::default();
+                if waveInGetDevCapsW(id, &mut caps, size_of::<WAVEINCAPSW>() as u32) == MMSYSERR_NOERROR {
+                    let pname = caps.szPname;
+                    inputs.push(AudioDevice { name: name(&pname), built_in: None, channels: Some(caps.wChannels as u32) });
+                }
+            }
+        }
+        AudioDevices { outputs, inputs, headphone_jack: None }
+    }
+
+    fn format(channels: u16) -> WAVEFORMATEX {
+        WAVEFORMATEX {
+            wFormatTag: WAVE_FORMAT_PCM as u16,
+            nChannels: channels,
+            nSamplesPerSec: SAMPLE_RATE,
+            nAvgBytesPerSec: SAMPLE_RATE * 2 * channels as u32,
+            nBlockAlign: 2 * channels,
+            wBitsPerSample: 16,
+            cbSize: 0,
+        }
+    }
+
+    // Reset returns any queued buffer, which close needs
+    struct WaveIn(HWAVEIN);
+    struct WaveOut(HWAVEOUT);
+
+    impl Drop for WaveIn {
+        fn drop(&mut self) {
+            unsafe {
+                waveInReset(self.0);
+                waveInClose(self.0);
+            }
+        }
+    }
+
+    impl Drop for WaveOut {
+        fn drop(&mut self) {
+            unsafe {
+                waveOutReset(self.0);
+                waveOutClose(self.0);
+            }
+        }
+    }
+
+    // One input buffer covers the whole recording; it is done once full
+    pub fn play_and_record(signal: &[i16], duration: Duration) -> Result<Vec<i16>, QuickscanError> {
+        let header_size = size_of::<WAVEHDR>() as u32;
+        let mut recording = vec![0i16; (SAMPLE_RATE as f64 * duration.as_secs_f64()) as usize];
+        let mut playback = signal.to_vec();
+        unsafe {
+            let mut handle = HWAVEIN::default();
+            check(waveInOpen(Some(&mut handle), WAVE_MAPPER, &format(1), None, None, CALLBACK_NULL), "waveInOpen")?;
+            let wave_in = WaveIn(handle);
+            let mut in_header = WAVEHDR {
+                lpData: PSTR(recording.as_mut_ptr() as *mut u8),
+                dwBufferLength: (recording.len() * 2) as u32,
+                ..Default::default()
+            };
+            check(waveInPrepareHeader(wave_in.0, &mut in_header, header_size), "waveInPrepareHeader")?;
+            check(waveInAddBuffer(wave_in.0, &mut in_header, header_size), "waveInAddBuffer")?;
+            check(waveInStart(wave_in.0), "waveInStart")?;
+
+            let mut handle = HWAVEOUT::default();
+            check(waveOutOpen(Some(&mut handle), WAVE_MAPPER, &format(2), None, None, CALLBACK_NULL), "waveOutOpen")?;
+            let wave_out = WaveOut(handle);
+            let mut out_header = WAVEHDR {
+                lpData: PSTR(playback.as_mut_ptr() as *mut u8),
+                dwBufferLength: (playback.len() * 2) as u32,
+                ..Default::default()
+            };
+            check(waveOutPrepareHeader(wave_out.0, &mut out_header, header_size), "waveOutPrepareHeader")?;
+            check(waveOutWrite(wave_out.0, &mut out_header, header_size), "waveOutWrite")?;
+
+            // The driver sets WHDR_DONE from its own thread; the header is packed
+            let deadline = Instant::now() + duration + Duration::from_secs(2);
+            let flags = std::ptr::addr_of!(in_header.dwFlags);
+            while flags.read_unaligned() & WHDR_DONE == 0 && Instant::now() < deadline {
+                std::thread::sleep(POLL_INTERVAL);
+            }
+            waveOutReset(wave_out.0);
+            waveOutUnprepareHeader(wave_out.0, &mut out_header, header_size);
+            waveInReset(wave_in.0);
+            waveInUnprepareHeader(wave_in.0, &mut in_header, header_size);
+            recording.truncate(in_header.dwBytesRecorded as usize / 2);
+        }
+        Ok(recording)
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn audio_devices_linux() -> AudioDevices {
+    use std::process::Command;
+
+    // "00-03: HDMI 0 : HDMI 0 : playback 1"
+    let pcm = std::fs::read_to_string("/proc/asound/pcm").unwrap_or_default();
+    let mut devices = AudioDevices { outputs: vec![], inputs: vec![], headphone_jack: None };
+    let mut cards = vec![];
+    for line in pcm.lines() {
+        let fields: Vec<&str> = line.split(" : ").map(|f| f.trim()).collect();
+        let Some((card, name)) = fields.first().and_then(|f| f.split_once(": ")) else {
+            continue;
+        };
+        let card = card.split('-').next().and_then(|c| c.parse::<u32>().ok()).unwrap_or(0);
+        // USB audio cards have a usbid; HDMI outputs go to a monitor
+        let external =
+            std::path::Path::new(&format!("/proc/asound/card{}/usbid", card)).exists() || name.contains("HDMI");
+        let device = || AudioDevice { name: name.to_string(), built_in: Some(!external), channels: None };
+        if fields.iter().any(|f| f.starts_with("playback")) {
+            devices.outputs.push(device());
+        }
+        if fields.iter().any(|f| f.starts_with("capture")) {
+            devices.inputs.push(device());
+        }
+        if !cards.contains(&card) {
+            cards.push(card);
+        }
+    }
+
+    // HDA codecs expose jack sensing as a read-only control:
+    // "numid=18,iface=CARD,name='Headphone Jack'" then "  : values=off"
+    for card in cards {
+        let mut command = Command::new("amixer");
+        let Ok(output) = crate::process::run_cmd(command.args(["-c", &card.to_string(), "contents"])) else {
+            continue;
+        };
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let mut lines = stdout.lines().skip_while(|line| !line.contains("name='Headphone Jack'"));
+        if let Some(value) = lines.find_map(|line| line.trim().strip_prefix(": values=")) {
+            devices.headphone_jack = Some(devices.headphone_jack.unwrap_or(false) || value == "on");
+        }
+    }
+    devices
+}
+
+// alsa-utils: arecord captures the default input while aplay plays the tones
+#[cfg(target_os = "linux")]
+fn play_and_record_linux(signal: &[i16]) -> Result<Vec<i16>, QuickscanError> {
+    use std::process::Command;
+
+    let missing_tool = |e: QuickscanError| match e {
+        QuickscanError::CommandFailed { .. } => QuickscanError::unsupported("audio_test (install alsa-utils)"),
+        other => other,
+    };
+    let path = write_wav(signal)?;
+    let recorder = std::thread::spawn(|| {
+        let seconds = RECORD_DURATION.as_secs().to_string();
+        let mut command = Command::new("arecord");
+        command.args(["-q", "-t", "raw", "-f", "S16_LE", "-r", &SAMPLE_RATE.to_string(), "-c", "1", "-d", &seconds]);
+        crate::process::run_cmd_with_timeout(&mut command, crate::process::DEFAULT_TIMEOUT)
+    });
+    // Give arecord a moment to open the device so the lead-in is recorded
+    std::thread::sleep(Duration::from_millis(300));
+    let played = crate::process::run_cmd_with_timeout(Command::new("aplay").args(["-q"]).arg(&path), crate::process::DEFAULT_TIMEOUT);
+    let recorded = recorder.join().unwrap_or_else(|e| std::panic::resume_unwind(e));
+    let _ = std::fs::remove_file(&path);
+
+    let played = played.map_err(missing_tool)?;
+    if !played.status.success() {
+        return Err(QuickscanError::command_failed("aplay", String::from_utf8_lossy(&played.stderr).trim()));
+    }
+    let recorded = recorded.map_err(missing_tool)?;
+    if !recorded.status.success() || recorded.stdout.is_empty() {
+        return Err(QuickscanError::command_failed("arecord", String::from_utf8_lossy(&recorded.stderr).trim()));
+    }
+    Ok(recorded.stdout.chunks_exact(2).map(|pair| i16::from_le_bytes([pair[0], pair[1]])).collect())
+}
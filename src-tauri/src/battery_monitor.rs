@@ -0,0 +1,292 @@
+// Battery drain/charge measurement (`start_battery_monitor`, then
+// `stop_battery_monitor`). A health percentage says how much the battery
+// holds, not how fast the machine empties it: a battery at 90% health can
+// still be flat in 90 minutes. Sampling charge, voltage and current over a
+// few minutes of real use answers that.
+
+use crate::error::QuickscanError;
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+use tauri::Emitter;
+
+pub const SAMPLE_EVENT: &str = "battery://sample";
+
+const MIN_INTERVAL_SECS: u64 = 1;
+const MAX_INTERVAL_SECS: u64 = 300;
+// How often the sampling thread checks whether it was stopped
+const STOP_POLL: Duration = Duration::from_millis(200);
+
+static MONITOR: Mutex<Option<Monitor>> = Mutex::new(None);
+
+struct Monitor {
+    stop: Arc<AtomicBool>,
+    started: Instant,
+    thread: JoinHandle<Vec<BatterySample>>,
+}
+
+#[derive(Serialize, Clone, Default)]
+pub struct BatterySample {
+    pub elapsed_secs: f64,
+    pub charge_percent: Option<f64>,
+    pub voltage: Option<f64>, // volts
+    pub amperage: Option<f64>, // amps; negative while discharging
+    pub temperature: Option<f64>, // degrees Celsius
+    pub remaining_wh: Option<f64>,
+    pub full_charge_wh: Option<f64>,
+}
+
+impl BatterySample {
+    // Signed like amperage: negative while discharging
+    fn watts(&self) -> Option<f64> {
+        Some(self.voltage? * self.amperage?)
+    }
+}
+
+#[derive(Serialize)]
+pub struct BatteryMonitorResult {
+    pub duration_secs: f64,
+    pub discharge_watts: Option<f64>, // averaged over the samples on battery
+    pub charge_watts: Option<f64>, // averaged over the samples while charging
+    pub percent_per_hour: Option<f64>, // change in charge level; negative while draining
+    pub estimated_runtime_hours: Option<f64>, // full charge at the measured discharge rate
+    pub samples: Vec<BatterySample>,
+}
+
+// Starts sampling every `interval_secs`; returns the first sample, or None
+// (and starts nothing) on a machine without a battery. A running monitor is
+// replaced.
+#[tauri::command]
+pub async fn start_battery_monitor(app: tauri::AppHandle, interval_secs: u64) -> Result<Option<BatterySample>, QuickscanError> {
+    crate::process::blocking(move || {
+        let Some(first) = read_battery() else {
+            return Ok(None);
+        };
+        if let Some(previous) = MONITOR.lock().unwrap_or_else(|e| e.into_inner()).take() {
+            previous.stop.store(true, Ordering::Relaxed);
+            let _ = previous.thread.join();
+        }
+
+        let interval = Duration::from_secs(interval_secs.clamp(MIN_INTERVAL_SECS, MAX_INTERVAL_SECS));
+        let stop = Arc::new(AtomicBool::new(false));
+        let started = Instant::now();
+        let thread = {
+            let stop = stop.clone();
+            let first = first.clone();
+            thread::spawn(move || sample_loop(&app, first, interval, started, &stop))
+        };
+        *MONITOR.lock().unwrap_or_else(|e| e.into_inner()) = Some(Monitor { stop, started, thread });
+        Ok(Some(first))
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn stop_battery_monitor() -> Result<BatteryMonitorResult, QuickscanError> {
+    crate::process::blocking(|| {
+        let monitor = MONITOR
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+            .ok_or_else(|| QuickscanError::command_failed("battery_monitor", "not running"))?;
+        monitor.stop.store(true, Ordering::Relaxed);
+        let duration_secs = monitor.started.elapsed().as_secs_f64();
+        let samples = monitor
+            .thread
+            .join()
+            .map_err(|_| QuickscanError::command_failed("battery_monitor", "sampling thread panicked"))?;
+        Ok(summarize(duration_secs, samples))
+    })
+    .await
+}
+
+fn sample_loop(
+    app: &tauri::AppHandle,
+    first: BatterySample,
+    interval: Duration,
+    started: Instant,
+    stop: &AtomicBool,
+) -> Vec<BatterySample> {
+    let mut samples = vec![first];
+    let mut next = started + interval;
+    while !stop.load(Ordering::Relaxed) {
+        if Instant::now() < next {
+            thread::sleep(STOP_POLL.min(next - Instant::now()));
+            continue;
+        }
+        next += interval;
+        if let Some(mut sample) = read_battery() {
+            sample.elapsed_secs = started.elapsed().as_secs_f64();
+            // The UI charts these live; a closed window just stops listening
+            let _ = app.emit(SAMPLE_EVENT, sample.clone());
+            samples.push(sample);
+        }
+    }
+    samples
+}
+
+fn summarize(duration_secs: f64, samples: Vec<BatterySample>) -> BatteryMonitorResult {
+    let average = |values: Vec<f64>| (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64);
+    let round = |value: f64| (value * 10.0).round() / 10.0;
+    // Change per hour between the first and last sample that report a value
+    let per_hour = |value: fn(&BatterySample) -> Option<f64>| {
+        let mut known = samples.iter().filter_map(|s| Some((s.elapsed_secs, value(s)?)));
+        let (start_secs, start) = known.next()?;
+        let (end_secs, end) = known.next_back()?;
+        (end_secs > start_secs).then(|| (end - start) / ((end_secs - start_secs) / 3600.0))
+    };
+
+    // The battery's own current is the most direct figure; without it the
+    // remaining energy between the first and last sample still tells
+    let measured = average(samples.iter().filter_map(|s| s.watts()).filter(|w| *w < 0.0).map(|w| -w).collect());
+    let from_energy = per_hour(|s| s.remaining_wh).filter(|wh| *wh < 0.0).map(|wh| -wh);
+    let discharge_watts = measured.or(from_energy);
+    let charge_watts = average(samples.iter().filter_map(|s| s.watts()).filter(|w| *w > 0.0).collect());
+
+    let percent_per_hour = per_hour(|s| s.charge_percent);
+    let full_charge_wh = samples.iter().rev().find_map(|s| s.full_charge_wh);
+    let estimated_runtime_hours = match (full_charge_wh, discharge_watts) {
+        (Some(wh), Some(watts)) if watts > 0.0 => Some(round(wh / watts)),
+        _ => None,
+    };
+
+    BatteryMonitorResult {
+        duration_secs: round(duration_secs),
+        discharge_watts: discharge_watts.map(round),
+        charge_watts: charge_watts.map(round),
+        percent_per_hour: percent_per_hour.map(round),
+        estimated_runtime_hours,
+        samples,
+    }
+}
+
+fn read_battery() -> Option<BatterySample> {
+    #[cfg(target_os = "macos")]
+    {
+        read_battery_macos()
+    }
+    #[cfg(target_os = "windows")]
+    {
+        read_battery_windows()
+    }
+    #[cfg(target_os = "linux")]
+    {
+        read_battery_linux()
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        None
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn read_battery_macos() -> Option<BatterySample> {
+    let battery = crate::iokit::service_properties("AppleSmartBattery")?;
+    let number = |key: &str| battery.get(key).and_then(|v| v.as_i64());
+
+    // Capacities in mAh, voltage in mV, amperage in signed mA
+    let voltage = number("Voltage").filter(|mv| *mv > 0).map(|mv| mv as f64 / 1000.0);
+    let current = number("AppleRawCurrentCapacity").or_else(|| number("CurrentCapacity"));
+    let full = number("AppleRawMaxCapacity").or_else(|| number("MaxCapacity"));
+    Some(BatterySample {
+        charge_percent: match (current, full) {
+            (Some(current), Some(full)) if full > 0 => Some((current as f64 / full as f64 * 100.0).min(100.0)),
+            _ => None,
+        },
+        voltage,
+        amperage: number("InstantAmperage").or_else(|| number("Amperage")).map(|ma| ma as f64 / 1000.0),
+        // Hundredths of a degree Celsius
+        temperature: number("Temperature").map(|t| t as f64 / 100.0),
+        remaining_wh: current.zip(voltage).map(|(mah, volts)| mah as f64 * volts / 1000.0),
+        full_charge_wh: full.zip(voltage).map(|(mah, volts)| mah as f64 * volts / 1000.0),
+        ..Default::default()
+    })
+}
+
+#[cfg(target_os = "windows")]
+fn read_battery_windows() -> Option<BatterySample> {
+    use crate::wmi_provider::{WmiProvider, WMI};
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct BatteryStatus {
+        voltage: Option<u32>, // mV
+        remaining_capacity: Option<u32>, // mWh
+        charge_rate: Option<i32>, // mW
+        discharge_rate: Option<i32>,
+        charging: Option<bool>,
+        discharging: Option<bool>,
+    }
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct BatteryFullChargedCapacity {
+        full_charged_capacity: u32, // mWh
+    }
+
+    let wmi = WmiProvider::global();
+    let status = wmi.query_first::<BatteryStatus>(
+        WMI,
+        "SELECT Voltage, RemainingCapacity, ChargeRate, DischargeRate, Charging, Discharging FROM BatteryStatus",
+    )?;
+    let full = wmi
+        .query_first::<BatteryFullChargedCapacity>(WMI, "SELECT FullChargedCapacity FROM BatteryFullChargedCapacity")
+        .map(|b| b.full_charged_capacity as f64 / 1000.0);
+    let remaining = status.remaining_capacity.map(|mwh| mwh as f64 / 1000.0);
+    let voltage = status.voltage.filter(|mv| *mv > 0).map(|mv| mv as f64 / 1000.0);
+
+    // Windows reports power, not current; derive the current from the voltage
+    let watts = if status.discharging == Some(true) {
+        status.discharge_rate.filter(|mw| *mw > 0).map(|mw| -(mw as f64) / 1000.0)
+    } else if status.charging == Some(true) {
+        status.charge_rate.filter(|mw| *mw > 0).map(|mw| mw as f64 / 1000.0)
+    } else {
+        Some(0.0)
+    };
+    Some(BatterySample {
+        charge_percent: match (remaining, full) {
+            (Some(remaining), Some(full)) if full > 0.0 => Some((remaining / full * 100.0).min(100.0)),
+            _ => None,
+        },
+        voltage,
+        amperage: watts.zip(voltage).map(|(watts, volts)| watts / volts),
+        temperature: None,
+        remaining_wh: remaining,
+        full_charge_wh: full,
+        ..Default::default()
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn read_battery_linux() -> Option<BatterySample> {
+    let battery = std::fs::read_dir("/sys/class/power_supply").ok()?.flatten().map(|entry| entry.path()).find(|path| {
+        std::fs::read_to_string(path.join("type")).is_ok_and(|t| t.trim() == "Battery")
+    })?;
+    let read = |name: &str| std::fs::read_to_string(battery.join(name)).ok().and_then(|s| s.trim().parse::<f64>().ok());
+
+    // Micro-units throughout; drivers report energy (µWh, µW) or charge (µAh, µA)
+    let voltage = read("voltage_now").filter(|uv| *uv > 0.0).map(|uv| uv / 1e6);
+    let discharging = std::fs::read_to_string(battery.join("status")).is_ok_and(|s| s.trim() == "Discharging");
+    let sign = if discharging { -1.0 } else { 1.0 };
+    let amperage = read("current_now")
+        .map(|ua| ua.abs() / 1e6)
+        .or_else(|| Some(read("power_now")?.abs() / 1e6 / voltage?))
+        .map(|amps| sign * amps);
+    let design_voltage = read("voltage_min_design").map(|uv| uv / 1e6).or(voltage);
+    let energy = |energy: &str, charge: &str| {
+        read(energy).map(|uwh| uwh / 1e6).or_else(|| Some(read(charge)? / 1e6 * design_voltage?))
+    };
+
+    Some(BatterySample {
+        charge_percent: read("capacity"),
+        voltage,
+        amperage,
+        // Tenths of a degree Celsius, where the driver reports it at all
+        temperature: read("temp").map(|t| t / 10.0),
+        remaining_wh: energy("energy_now", "charge_now"),
+        full_charge_wh: energy("energy_full", "charge_full"),
+        ..Default::default()
+    })
+}
@@ -0,0 +1,49 @@
+// Who made a battery pack and when, to tell the original from a replacement.
+// Laptop makers buy their packs from a handful of cell and pack suppliers and
+// a genuine pack reports one of them, or the laptop maker itself; cheap
+// replacement packs report whatever their gauge was flashed with ("OEM",
+// "Li-ion", a factory code). A pack made well after the machine was
+// replaced it, genuine or not.
+
+use chrono::NaiveDate;
+
+// Pack makers that supply Apple, Dell, HP, Lenovo and the rest, by the first
+// word of the name the gauge reports ("SMP", "Samsung SDI", "Hewlett-Packard")
+const PACK_MAKERS: &[&str] = &[
+    "smp", "simplo", "dsy", "desay", "swd", "sunwoda", "atl", "lgc", "lg", "sdi", "samsung", "sny", "sony", "sanyo",
+    "panasonic", "dp", "dyn", "dynapack", "celxpert", "cpt", "cosmx", "byd", "scud", "lishen", "murata", "apple",
+    "dell", "hp", "hewlett", "lenovo", "microsoft", "asus", "asustek", "acer", "toshiba", "dynabook", "fujitsu",
+    "framework", "msi",
+];
+
+// A pack newer than the machine by more than this was fitted later
+pub const REPLACEMENT_MONTHS: i64 = 12;
+
+fn first_word(name: &str) -> String {
+    name.trim().split(|c: char| !c.is_ascii_alphanumeric()).next().unwrap_or_default().to_lowercase()
+}
+
+// A maker that isn't a known supplier nor the machine's own maker; None when
+// the pack doesn't say
+pub fn third_party_maker(manufacturer: &str, system_vendor: Option<&str>) -> Option<bool> {
+    let maker = first_word(manufacturer);
+    if maker.is_empty() || maker == "unknown" {
+        return None;
+    }
+    let own = system_vendor.is_some_and(|vendor| first_word(vendor) == maker);
+    Some(!own && !PACK_MAKERS.contains(&maker.as_str()))
+}
+
+// Smart Battery ManufactureDate: (year - 1980) * 512 + month * 32 + day
+#[cfg(target_os = "macos")]
+pub fn sbs_date(raw: u64) -> Option<String> {
+    let (year, month, day) = (1980 + (raw >> 9), (raw >> 5) & 0xF, raw & 0x1F);
+    NaiveDate::from_ymd_opt(year as i32, month as u32, day as u32).map(|date| date.format("%Y-%m-%d").to_string())
+}
+
+// Whole months from `chassis` to `battery`, both YYYY-MM-DD
+pub fn months_newer(battery: &str, chassis: &str) -> Option<i64> {
+    let parse = |date: &str| NaiveDate::parse_from_str(date.get(..10)?, "%Y-%m-%d").ok();
+    let (battery, chassis) = (parse(battery)?, parse(chassis)?);
+    Some((battery - chassis).num_days() / 30)
+}
@@ -0,0 +1,5 @@
+// Scans the machine from the command line, without a window or display
+// server: the entry point of the live-USB image
+fn main() -> std::process::ExitCode {
+    quickscan_lib::headless::run()
+}
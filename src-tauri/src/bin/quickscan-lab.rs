@@ -0,0 +1,5 @@
+// Runs every probe and sends the raw tool outputs with the parsed results to
+// the hardware-lab corpus
+fn main() -> std::process::ExitCode {
+    quickscan_lib::lab::run()
+}
@@ -0,0 +1,448 @@
+// The Bluetooth controller and the devices paired with it, for
+// `get_bluetooth_info` and the Bluetooth half of `get_network_info`. Pairings
+// that outlived a wipe name the previous owner's headphones or phone, so the
+// refurbishment check reports them as a data remnant.
+
+use crate::error::QuickscanError;
+use crate::probe_scheduler::ProbeScheduler;
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct BluetoothInfo {
+    pub available: bool, // a controller is present
+    pub enabled: bool,   // and powered on
+    pub controller: Option<String>, // chipset or adapter name: "BCM_4387", "Intel(R) Wireless Bluetooth(R)"
+    pub vendor: Option<String>,
+    pub address: Option<String>,
+    pub version: Option<&'static str>, // core spec the controller implements, "5.3"
+    pub firmware: Option<String>,
+    pub paired_devices: Vec<PairedDevice>,
+}
+
+#[derive(Serialize)]
+pub struct PairedDevice {
+    pub name: String,
+    pub address: Option<String>,
+    pub kind: Option<String>, // as the OS describes it: "Headphones", "audio-headset"
+    pub connected: bool,
+}
+
+impl BluetoothInfo {
+    fn none() -> Self {
+        BluetoothInfo {
+            available: false,
+            enabled: false,
+            controller: None,
+            vendor: None,
+            address: None,
+            version: None,
+            firmware: None,
+            paired_devices: vec![],
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn get_bluetooth_info(probes: tauri::State<'_, ProbeScheduler>) -> Result<BluetoothInfo, QuickscanError> {
+    probes.run(bluetooth_info).await
+}
+
+pub fn bluetooth_info() -> Result<BluetoothInfo, QuickscanError> {
+    #[cfg(target_os = "macos")]
+    let info = bluetooth_info_macos();
+    #[cfg(target_os = "windows")]
+    let info = bluetooth_info_windows();
+    #[cfg(target_os = "linux")]
+    let info = bluetooth_info_linux();
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    let info = Err(QuickscanError::unsupported("bluetooth_info"));
+    info
+}
+
+// HCI/LMP version number to the core specification it stands for
+fn core_version(version: u8) -> Option<&'static str> {
+    const VERSIONS: [&str; 15] =
+        ["1.0b", "1.1", "1.2", "2.0", "2.1", "3.0", "4.0", "4.1", "4.2", "5.0", "5.1", "5.2", "5.3", "5.4", "6.0"];
+    VERSIONS.get(version as usize).copied()
+}
+
+// "5.0 (0x9)" or "0x9"
+#[cfg(not(target_os = "windows"))]
+fn parse_version(text: &str) -> Option<&'static str> {
+    let hex = match text.find("(0x") {
+        Some(start) => text[start + 1..].split(')').next()?,
+        None => text.split_whitespace().next()?,
+    };
+    u8::from_str_radix(hex.trim().trim_start_matches("0x"), 16).ok().and_then(core_version)
+}
+
+#[cfg(target_os = "macos")]
+fn bluetooth_info_macos() -> Result<BluetoothInfo, QuickscanError> {
+    use std::process::Command;
+
+    // {"SPBluetoothDataType": [{"controller_properties": {"controller_address": "F8:4D:89:00:00:00",
+    //   "controller_chipset": "BCM_4387", "controller_firmwareVersion": "22.1.534.1304",
+    //   "controller_state": "attrib_on", "controller_vendorID": "0x004C (Apple)"},
+    //   "device_connected": [{"Magic Mouse": {"device_address": "...", "device_minorType": "Mouse"}}],
+    //   "device_not_connected": [{"AirPods Pro": {"device_address": "...", "device_minorType": "Headphones"}}]}]}
+    //
+    // Before macOS 12 the controller is "local_device_title" with general_*
+    // keys, including the HCI version that newer releases no longer list,
+    // and the devices are one "device_title" list
+    let output = crate::process::run_cmd_checked(Command::new("system_profiler").args(["SPBluetoothDataType", "-json"]))?;
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).map_err(|e| QuickscanError::parse("system_profiler", e))?;
+    let Some(entry) = json.get("SPBluetoothDataType").and_then(|v| v.as_array()).and_then(|a| a.first()) else {
+        return Ok(BluetoothInfo::none());
+    };
+    let field = |value: &serde_json::Value, key: &str| value.get(key).and_then(|v| v.as_str()).map(|s| s.trim().to_string());
+    // [{"name": {...}}, ...]
+    let devices = |key: &str| -> Vec<(String, serde_json::Value)> {
+        entry
+            .get(key)
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|item| item.as_object())
+            .flat_map(|item| item.iter().map(|(name, props)| (name.clone(), props.clone())))
+            .collect()
+    };
+
+    if let Some(controller) = entry.get("controller_properties") {
+        let mut paired_devices = vec![];
+        for (key, connected) in [("device_connected", true), ("device_not_connected", false)] {
+            paired_devices.extend(devices(key).into_iter().map(|(name, props)| PairedDevice {
+                name,
+                address: field(&props, "device_address"),
+                kind: field(&props, "device_minorType"),
+                connected,
+            }));
+        }
+        // "0x004C (Apple)"
+        let vendor = field(controller, "controller_vendorID")
+            .map(|id| id.split_once('(').map(|(_, name)| name.trim_end_matches(')').to_string()).unwrap_or(id));
+        return Ok(BluetoothInfo {
+            available: true,
+            enabled: field(controller, "controller_state").as_deref() == Some("attrib_on"),
+            controller: field(controller, "controller_chipset"),
+            vendor,
+            address: field(controller, "controller_address"),
+            version: None,
+            firmware: field(controller, "controller_firmwareVersion"),
+            paired_devices,
+        });
+    }
+
+    let Some(local) = entry.get("local_device_title") else {
+        return Ok(BluetoothInfo::none());
+    };
+    let yes = |props: &serde_json::Value, key: &str| field(props, key).is_some_and(|v| v.eq_ignore_ascii_case("attrib_yes"));
+    let paired_devices = devices("device_title")
+        .into_iter()
+        .filter(|(_, props)| yes(props, "device_ispaired"))
+        .map(|(name, props)| PairedDevice {
+            address: field(&props, "device_addr"),
+            kind: field(&props, "device_minorClassOfDevice_string"),
+            connected: yes(&props, "device_isconnected"),
+            name,
+        })
+        .collect();
+    Ok(BluetoothInfo {
+        available: true,
+        enabled: field(local, "general_power").is_some_and(|v| v.eq_ignore_ascii_case("attrib_on")),
+        controller: field(local, "general_chipset"),
+        vendor: field(local, "general_mfg"),
+        address: field(local, "general_address"),
+        version: field(local, "general_lmp_version").or_else(|| field(local, "general_hci_version")).and_then(|v| parse_version(&v)),
+        firmware: field(local, "general_fw_version"),
+        paired_devices,
+    })
+}
+
+// Bluetooth SIG company identifiers of the usual controller makers
+#[cfg(target_os = "windows")]
+fn company_name(id: u16) -> Option<&'static str> {
+    let companies = [
+        (0x0002, "Intel"),
+        (0x000A, "Qualcomm (CSR)"),
+        (0x000F, "Broadcom"),
+        (0x001D, "Qualcomm"),
+        (0x0046, "MediaTek"),
+        (0x0048, "Marvell"),
+        (0x004C, "Apple"),
+        (0x005D, "Realtek"),
+    ];
+    companies.iter().find(|(company, _)| *company == id).map(|(_, name)| *name)
+}
+
+// Class of Device: the major class, and for peripherals what kind of input
+#[cfg(target_os = "windows")]
+fn device_kind(class_of_device: u32) -> Option<&'static str> {
+    match (class_of_device >> 8) & 0x1F {
+        1 => Some("Computer"),
+        2 => Some("Phone"),
+        3 => Some("Network"),
+        4 => Some("Audio"),
+        5 => match (class_of_device >> 6) & 0x3 {
+            1 => Some("Keyboard"),
+            2 => Some("Mouse"),
+            3 => Some("Keyboard and mouse"),
+            _ => Some("Peripheral"),
+        },
+        6 => Some("Imaging"),
+        7 => Some("Wearable"),
+        8 => Some("Toy"),
+        9 => Some("Health"),
+        _ => None, // Low Energy devices often send none
+    }
+}
+
+// WMI names the adapter, the Bluetooth API reads the radio and its pairings,
+// and the radio driver's IOCTL_BTH_GET_LOCAL_INFO has the LMP version
+#[cfg(target_os = "windows")]
+fn bluetooth_info_windows() -> Result<BluetoothInfo, QuickscanError> {
+    use crate::wmi_provider::{WmiProvider, CIMV2};
+    use serde::Deserialize;
+    use std::mem::size_of;
+    use windows::Win32::Devices::Bluetooth::*;
+    use windows::Win32::Foundation::{CloseHandle, ERROR_SUCCESS, HANDLE};
+    use windows::Win32::System::IO::DeviceIoControl;
+
+    // From bthioctl.h, which the windows crate doesn't carry
+    const IOCTL_BTH_GET_LOCAL_INFO: u32 = 0x0041_0000;
+    #[repr(C)]
+    #[derive(Default)]
+    struct BthRadioInfo {
+        lmp_supported_features: u64,
+        mfg: u16,
+        lmp_subversion: u16,
+        lmp_version: u8,
+    }
+    #[repr(C)]
+    #[derive(Default)]
+    struct BthLocalRadioInfo {
+        local_info: BTH_DEVICE_INFO,
+        flags: u32,
+        hci_revision: u16,
+        hci_version: u8,
+        radio_info: BthRadioInfo,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct PnpEntity {
+        name: Option<String>,
+        #[serde(rename = "PNPDeviceID")]
+        pnp_device_id: Option<String>,
+    }
+
+    struct Radio(HANDLE);
+    impl Drop for Radio {
+        fn drop(&mut self) {
+            unsafe {
+                let _ = CloseHandle(self.0);
+            }
+        }
+    }
+
+    fn address(address: &BLUETOOTH_ADDRESS) -> String {
+        // Least significant byte first
+        let bytes = unsafe { address.Anonymous.rgBytes };
+        bytes.iter().rev().map(|b| format!("{:02X}", b)).collect::<Vec<_>>().join(":")
+    }
+
+    fn wide(text: &[u16]) -> String {
+        let length = text.iter().position(|&c| c == 0).unwrap_or(text.len());
+        String::from_utf16_lossy(&text[..length])
+    }
+
+    // Paired devices and the stack's own enumerators share the Bluetooth
+    // class; the radio itself hangs off USB, PCI or a UART
+    let adapter = WmiProvider::global()
+        .query::<PnpEntity>(CIMV2, "SELECT Name, PNPDeviceID FROM Win32_PnPEntity WHERE PNPClass = 'Bluetooth'")?
+        .into_iter()
+        .find(|entity| {
+            entity
+                .pnp_device_id
+                .as_deref()
+                .is_some_and(|id| !id.starts_with("BTH") && !id.starts_with("SWD\\") && !id.starts_with("ROOT\\"))
+        })
+        .and_then(|entity| entity.name);
+
+    let params = BLUETOOTH_FIND_RADIO_PARAMS { dwSize: size_of::<BLUETOOTH_FIND_RADIO_PARAMS>() as u32 };
+    let mut handle = HANDLE::default();
+    // No radio: the adapter is missing or disabled in Device Manager
+    let Ok(find) = (unsafe { BluetoothFindFirstRadio(&params, &mut handle) }) else {
+        return Ok(BluetoothInfo { available: adapter.is_some(), controller: adapter, ..BluetoothInfo::none() });
+    };
+    unsafe {
+        let _ = BluetoothFindRadioClose(find);
+    }
+    let radio = Radio(handle);
+
+    let mut info = BLUETOOTH_RADIO_INFO { dwSize: size_of::<BLUETOOTH_RADIO_INFO>() as u32, ..Default::default() };
+    let status = unsafe { BluetoothGetRadioInfo(radio.0, &mut info) };
+    if status != ERROR_SUCCESS.0 {
+        return Err(QuickscanError::command_failed("bluetoothapis", format!("BluetoothGetRadioInfo failed ({})", status)));
+    }
+    // Switched off in Settings, the radio is still found but won't accept connections
+    let enabled = unsafe { BluetoothIsConnectable(Some(radio.0)) }.as_bool();
+
+    let mut local = BthLocalRadioInfo::default();
+    let mut returned = 0u32;
+    let version = unsafe {
+        DeviceIoControl(
+            radio.0,
+            IOCTL_BTH_GET_LOCAL_INFO,
+            None,
+            0,
+            Some(&mut local as *mut BthLocalRadioInfo as *mut std::ffi::c_void),
+            size_of::<BthLocalRadioInfo>() as u32,
+            Some(&mut returned),
+            None,
+        )
+    }
+    .ok()
+    .and_then(|_| core_version(local.radio_info.lmp_version));
+
+    let search = BLUETOOTH_DEVICE_SEARCH_PARAMS {
+        dwSize: size_of::<BLUETOOTH_DEVICE_SEARCH_PARAMS>() as u32,
+        fReturnAuthenticated: true.into(),
+        fReturnRemembered: true.into(),
+        hRadio: radio.0,
+        ..Default::default()
+    };
+    let mut paired_devices = vec![];
+    let mut device = BLUETOOTH_DEVICE_INFO { dwSize: size_of::<BLUETOOTH_DEVICE_INFO>() as u32, ..Default::default() };
+    if let Ok(find) = unsafe { BluetoothFindFirstDevice(&search, &mut device) } {
+        loop {
+            if device.fRemembered.as_bool() || device.fAuthenticated.as_bool() {
+                paired_devices.push(PairedDevice {
+                    name: wide(&device.szName),
+                    address: Some(address(&device.Address)),
+                    kind: device_kind(device.ulClassofDevice).map(str::to_string),
+                    connected: device.fConnected.as_bool(),
+                });
+            }
+            device = BLUETOOTH_DEVICE_INFO { dwSize: size_of::<BLUETOOTH_DEVICE_INFO>() as u32, ..Default::default() };
+            if unsafe { BluetoothFindNextDevice(find, &mut device) }.is_err() {
+                break;
+            }
+        }
+        unsafe {
+            let _ = BluetoothFindDeviceClose(find);
+        }
+    }
+
+    Ok(BluetoothInfo {
+        available: true,
+        enabled,
+        controller: adapter,
+        vendor: company_name(info.manufacturer).map(str::to_string),
+        address: Some(address(&info.address)),
+        version,
+        firmware: Some(format!("{:#06x}", info.lmpSubversion)),
+        paired_devices,
+    })
+}
+
+// sysfs for the adapter and its rfkill switch, `hciconfig` for the version
+// and firmware, `bluetoothctl` for power and the paired devices
+#[cfg(target_os = "linux")]
+fn bluetooth_info_linux() -> Result<BluetoothInfo, QuickscanError> {
+    use std::path::Path;
+    use std::process::Command;
+    use std::time::Duration;
+
+    // bluetoothctl waits for bluetoothd indefinitely when it isn't running
+    const BLUETOOTHCTL_TIMEOUT: Duration = Duration::from_secs(5);
+
+    let hci = std::fs::read_dir("/sys/class/bluetooth")
+        .ok()
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| name.starts_with("hci") && !name.contains(':'))
+        .min();
+    let Some(hci) = hci else {
+        return Ok(BluetoothInfo::none());
+    };
+    let sys = Path::new("/sys/class/bluetooth").join(&hci);
+    let read = |path: &Path| std::fs::read_to_string(path).ok().map(|s| s.trim().to_string());
+
+    // A USB adapter's product string, else "btusb (8087:0033)"
+    let driver = std::fs::read_link(sys.join("device/driver"))
+        .ok()
+        .and_then(|path| path.file_name().map(|name| name.to_string_lossy().to_string()));
+    let usb_id = read(&sys.join("device/../idVendor")).zip(read(&sys.join("device/../idProduct")));
+    let controller = read(&sys.join("device/../product")).or_else(|| match (driver, usb_id) {
+        (Some(driver), Some((vendor, product))) => Some(format!("{} ({}:{})", driver, vendor, product)),
+        (driver, usb_id) => driver.or(usb_id.map(|(vendor, product)| format!("{}:{}", vendor, product))),
+    });
+
+    let blocked = std::fs::read_dir(&sys)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|entry| entry.file_name().to_string_lossy().starts_with("rfkill"))
+                .any(|entry| read(&entry.path().join("soft")).as_deref() == Some("1") || read(&entry.path().join("hard")).as_deref() == Some("1"))
+        })
+        .unwrap_or(false);
+
+    // hciconfig comes from bluez's deprecated tools, which not every distro installs
+    let (mut address, mut vendor, mut version, mut firmware) = (None, None, None, None);
+    if let Ok(output) = crate::process::run_cmd(Command::new("hciconfig").args(["-a", &hci])) {
+        let text = String::from_utf8_lossy(&output.stdout);
+        let field = |key: &str| {
+            text.lines().find_map(|line| {
+                let start = line.find(key)? + key.len();
+                Some(line[start..].split("  ").next().unwrap_or_default().trim().to_string())
+            })
+        };
+        address = field("BD Address:");
+        // "Intel Corp. (2)"
+        vendor = field("Manufacturer:").map(|name| name.rsplit_once(" (").map(|(name, _)| name.to_string()).unwrap_or(name));
+        version = field("LMP Version:").and_then(|v| parse_version(&v));
+        firmware = field("Subversion:");
+    }
+
+    let missing_tool = |e: QuickscanError| match e {
+        QuickscanError::CommandFailed { .. } => QuickscanError::unsupported("bluetooth_info (install bluez)"),
+        other => other,
+    };
+    let bluetoothctl = |args: &[&str]| -> Result<String, QuickscanError> {
+        let output = crate::process::run_probe(Command::new("bluetoothctl").args(args), BLUETOOTHCTL_TIMEOUT)
+            .map_err(missing_tool)?;
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    };
+    let value = |text: &str, key: &str| text.lines().find_map(|line| line.trim().strip_prefix(key)).map(|v| v.trim().to_string());
+
+    let show = bluetoothctl(&["show"])?;
+    let enabled = !blocked && value(&show, "Powered:").as_deref() == Some("yes");
+    // "Controller AA:BB:CC:DD:EE:FF (public)"
+    if address.is_none() {
+        address = show.lines().find_map(|line| line.strip_prefix("Controller ")).and_then(|rest| rest.split_whitespace().next()).map(str::to_string);
+    }
+
+    // "Device AA:BB:CC:DD:EE:FF AirPods"; BlueZ before 5.65 only knows paired-devices
+    let mut listing = bluetoothctl(&["devices", "Paired"])?;
+    if !listing.lines().any(|line| line.starts_with("Device ")) {
+        listing = bluetoothctl(&["paired-devices"])?;
+    }
+    let mut paired_devices = vec![];
+    for line in listing.lines() {
+        let Some((device_address, name)) = line.strip_prefix("Device ").and_then(|rest| rest.split_once(' ')) else {
+            continue;
+        };
+        let info = bluetoothctl(&["info", device_address]).unwrap_or_default();
+        paired_devices.push(PairedDevice {
+            name: name.trim().to_string(),
+            address: Some(device_address.to_string()),
+            kind: value(&info, "Icon:"),
+            connected: value(&info, "Connected:").as_deref() == Some("yes"),
+        });
+    }
+
+    Ok(BluetoothInfo { available: true, enabled, controller, vendor, address, version, firmware, paired_devices })
+}
@@ -0,0 +1,238 @@
+// Logic board / motherboard identity and firmware for `get_board_info`. The
+// board serial is also compared with the serials the firmware reports for the
+// machine: a replacement board ships with a blank system serial until someone
+// programs it, and a board pulled from a donor keeps the donor's. Intel Macs
+// also keep a copy in NVRAM that has to agree. Either way the board no longer
+// belongs with the chassis, a sign of a board swap or a machine put together
+// from several that `check_refurbishment` reports.
+
+use crate::component_serials::clean;
+use crate::error::QuickscanError;
+use crate::probe_scheduler::ProbeScheduler;
+use serde::Serialize;
+
+#[derive(Serialize, Default)]
+pub struct BoardInfo {
+    pub manufacturer: Option<String>,
+    pub model: Option<String>, // "Z790 AORUS ELITE", "0K2W4M"; the board ID on Macs ("Mac-827FB448E656EC26", "J314sAP")
+    pub version: Option<String>,
+    pub serial: Option<String>,
+    pub system_serial: Option<String>, // the machine's serial, as the firmware reports it
+    pub chassis_serial: Option<String>, // the enclosure's, where SMBIOS keeps one apart (not on Macs)
+    pub nvram_serial: Option<String>, // Intel Macs keep the serial in NVRAM as well
+    pub firmware_vendor: Option<String>,
+    pub firmware_version: Option<String>, // BIOS / UEFI version, or the Mac's boot ROM
+    pub firmware_date: Option<String>, // YYYY-MM-DD
+}
+
+#[tauri::command]
+pub async fn get_board_info(probes: tauri::State<'_, ProbeScheduler>) -> Result<BoardInfo, QuickscanError> {
+    probes.run(board_info).await
+}
+
+pub fn board_info() -> Result<BoardInfo, QuickscanError> {
+    #[cfg(target_os = "macos")]
+    {
+        Ok(board_info_macos())
+    }
+    #[cfg(target_os = "windows")]
+    {
+        board_info_windows()
+    }
+    #[cfg(target_os = "linux")]
+    {
+        Ok(board_info_linux())
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        Err(QuickscanError::unsupported("board_info"))
+    }
+}
+
+// Makers that program a system serial into every board they ship in a
+// machine. Retail boards (ASUS, Gigabyte, MSI…) leave it unset, so a blank
+// one there says nothing.
+const SYSTEM_BOARD_VENDORS: &[&str] = &["apple", "dell", "hp", "hewlett-packard", "lenovo", "microsoft", "framework"];
+
+pub struct SerialMismatch {
+    pub description: String, // indicator description, e.g. "chassis_serial_mismatch:C02XK0ABJG5J / C02XL1CDJG5J"
+    pub severity: &'static str,
+}
+
+// Why the board doesn't belong with this machine; None when the serials agree
+// or there isn't enough to compare. Serials that disagree mean parts of two
+// machines were put together and are critical; a board that was never
+// programmed is a repair done without the maker's tools, a warning.
+pub fn serial_mismatch(board: &BoardInfo) -> Option<SerialMismatch> {
+    let mismatch = |description: String| Some(SerialMismatch { description, severity: "critical" });
+    let board_serial = board.serial.as_deref()?;
+    let vendor = board.manufacturer.as_deref()?.to_lowercase();
+    if !SYSTEM_BOARD_VENDORS.iter().any(|known| vendor.starts_with(known)) {
+        return None;
+    }
+    let Some(system_serial) = board.system_serial.as_deref() else {
+        return Some(SerialMismatch { description: "board_serial_unprogrammed".to_string(), severity: "warning" });
+    };
+    if let Some(nvram_serial) = board.nvram_serial.as_deref() {
+        if !nvram_serial.eq_ignore_ascii_case(system_serial) {
+            return mismatch(format!("nvram_serial_mismatch:{} / {}", system_serial, nvram_serial));
+        }
+    }
+    if let Some(chassis_serial) = board.chassis_serial.as_deref() {
+        if !chassis_serial.eq_ignore_ascii_case(system_serial) {
+            return mismatch(format!("chassis_serial_mismatch:{} / {}", system_serial, chassis_serial));
+        }
+    }
+    // Dell board serials start with the service tag they were made for: "/7XKQ2Y2/CN1296385G0123/"
+    let board_tag = board_serial.split('/').find(|part| !part.is_empty())?;
+    if vendor.starts_with("dell")
+        && board_serial.starts_with('/')
+        && board_tag.len() == 7
+        && system_serial.len() == 7
+        && !board_tag.eq_ignore_ascii_case(system_serial)
+    {
+        return mismatch(format!("board_service_tag_mismatch:{} / {}", board_tag, system_serial));
+    }
+    None
+}
+
+// Intel Macs before the T2 name the build date in the boot ROM version:
+// "MBP114.88Z.0177.B00.1708080033" was built on 2017-08-08
+#[cfg(target_os = "macos")]
+fn boot_rom_date(version: &str) -> Option<String> {
+    let build = version.rsplit('.').next().filter(|_| version.contains(".88Z."))?;
+    let digits = build.get(..6).filter(|d| d.bytes().all(|b| b.is_ascii_digit()))?;
+    Some(format!("20{}-{}-{}", &digits[..2], &digits[2..4], &digits[4..6]))
+}
+
+// "4D1EDE05-38C7-4A6A-9CC6-4BCCA8B38C14:SSN\tC02XK0ABJG5J"; Apple silicon Macs don't have it
+#[cfg(target_os = "macos")]
+fn nvram_serial_macos() -> Option<String> {
+    let mut command = std::process::Command::new("nvram");
+    let output = crate::process::run_cmd(command.arg("4D1EDE05-38C7-4A6A-9CC6-4BCCA8B38C14:SSN")).ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    clean(stdout.split('\t').nth(1)?)
+}
+
+#[cfg(target_os = "macos")]
+fn board_info_macos() -> BoardInfo {
+    use crate::iokit;
+
+    let platform = iokit::service_properties("IOPlatformExpertDevice").unwrap_or_default();
+    // Apple silicon: "compatible" lists the board first ("J314sAP\0MacBookPro18,3\0AppleARM");
+    // Intel Macs carry a board-id
+    let model = iokit::string_property(&platform, "board-id").or_else(|| {
+        let compatible = iokit::string_property(&platform, "compatible")?;
+        compatible.split('\0').next().map(str::to_string).filter(|board| !board.is_empty())
+    });
+    let firmware_version = crate::apple_overview::section("SPHardwareDataType")
+        .ok()
+        .and_then(|items| items.first()?.get("boot_rom_version")?.as_str().map(|s| s.trim().to_string()))
+        .filter(|version| !version.is_empty());
+
+    BoardInfo {
+        manufacturer: Some("Apple Inc.".to_string()),
+        model,
+        version: None,
+        serial: crate::component_serials::logic_board_serial_macos(),
+        system_serial: iokit::string_property(&platform, "IOPlatformSerialNumber").and_then(|s| clean(&s)),
+        chassis_serial: None,
+        nvram_serial: nvram_serial_macos(),
+        firmware_vendor: Some("Apple Inc.".to_string()),
+        firmware_date: firmware_version.as_deref().and_then(boot_rom_date),
+        firmware_version,
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn board_info_windows() -> Result<BoardInfo, QuickscanError> {
+    use crate::wmi_provider::{self, WmiProvider, CIMV2};
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct BaseBoard {
+        manufacturer: Option<String>,
+        product: Option<String>,
+        version: Option<String>,
+        serial_number: Option<String>,
+    }
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct Bios {
+        manufacturer: Option<String>,
+        #[serde(rename = "SMBIOSBIOSVersion")]
+        smbios_bios_version: Option<String>,
+        release_date: Option<String>, // "20230412000000.000000+000"
+        serial_number: Option<String>,
+    }
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct SystemEnclosure {
+        serial_number: Option<String>,
+    }
+
+    let wmi = WmiProvider::global();
+    let board = wmi
+        .query::<BaseBoard>(CIMV2, "SELECT Manufacturer, Product, Version, SerialNumber FROM Win32_BaseBoard")?
+        .into_iter()
+        .next();
+    let bios = wmi.query_first::<Bios>(
+        CIMV2,
+        "SELECT Manufacturer, SMBIOSBIOSVersion, ReleaseDate, SerialNumber FROM Win32_BIOS",
+    );
+    let enclosure = wmi.query_first::<SystemEnclosure>(CIMV2, "SELECT SerialNumber FROM Win32_SystemEnclosure");
+
+    let field = |value: Option<String>| value.and_then(|v| clean(&v));
+    let (manufacturer, model, version, serial) = match board {
+        Some(board) => (field(board.manufacturer), field(board.product), field(board.version), field(board.serial_number)),
+        None => (None, None, None, None),
+    };
+    let (firmware_vendor, firmware_version, firmware_date, system_serial) = match bios {
+        Some(bios) => (
+            field(bios.manufacturer),
+            field(bios.smbios_bios_version),
+            bios.release_date
+                .as_deref()
+                .and_then(wmi_provider::format_datetime)
+                .map(|date| date[..10].to_string()),
+            field(bios.serial_number),
+        ),
+        None => (None, None, None, None),
+    };
+    Ok(BoardInfo {
+        manufacturer,
+        model,
+        version,
+        serial,
+        system_serial,
+        chassis_serial: enclosure.and_then(|e| field(e.serial_number)),
+        nvram_serial: None,
+        firmware_vendor,
+        firmware_version,
+        firmware_date,
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn board_info_linux() -> BoardInfo {
+    // The serials are readable by root only on most distributions
+    let read = |name: &str| std::fs::read_to_string(format!("/sys/class/dmi/id/{}", name)).ok().and_then(|s| clean(&s));
+    // "04/12/2023"
+    let firmware_date = read("bios_date").map(|date| match date.split('/').collect::<Vec<_>>().as_slice() {
+        [month, day, year] if year.len() == 4 => format!("{}-{:0>2}-{:0>2}", year, month, day),
+        _ => date.clone(),
+    });
+    BoardInfo {
+        manufacturer: read("board_vendor"),
+        model: read("board_name"),
+        version: read("board_version"),
+        serial: read("board_serial"),
+        system_serial: read("product_serial"),
+        chassis_serial: read("chassis_serial"),
+        nvram_serial: None,
+        firmware_vendor: read("bios_vendor"),
+        firmware_version: read("bios_version"),
+        firmware_date,
+    }
+}
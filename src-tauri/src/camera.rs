@@ -0,0 +1,677 @@
+// Cameras (`get_camera_info`) and a native frame grab (`test_camera_capture`).
+// The webview's getUserMedia can fail for reasons that have nothing to do with
+// the hardware, and a webcam whose cable wasn't reconnected after a screen
+// replacement simply disappears. Grabbing a frame through AVFoundation or
+// Media Foundation shows the technician the camera itself works.
+
+use crate::error::QuickscanError;
+use crate::probe_scheduler::ProbeScheduler;
+use base64::prelude::{Engine, BASE64_STANDARD};
+use serde::Serialize;
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+use std::time::Duration;
+
+// Big enough to judge focus and a dusty lens, small enough for the IPC bridge
+const MAX_CAPTURE_WIDTH: u32 = 1280;
+const JPEG_QUALITY: u8 = 85;
+// Mean luma (0-255) below which the frame is black: a covered lens, a
+// privacy shutter or a sensor that streams nothing
+const DARK_BRIGHTNESS: f64 = 12.0;
+// Frames from the first moments come out dark while auto-exposure settles
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+const WARMUP_FRAMES: u32 = 15;
+// Long enough for the user to answer the macOS camera permission prompt
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+const CAPTURE_TIMEOUT: Duration = Duration::from_secs(20);
+
+#[derive(Serialize)]
+pub struct CameraInfo {
+    pub name: String,
+    pub model: Option<String>,
+    pub built_in: Option<bool>, // None where the OS doesn't say
+    pub connection: Option<String>, // "built-in", "usb", "thunderbolt", "virtual", ...
+    pub max_resolution: Option<String>, // e.g. "1920x1080"
+}
+
+#[derive(Serialize)]
+pub struct CameraCapture {
+    pub camera: String,
+    pub width: u32, // of the encoded image, after scaling down
+    pub height: u32,
+    pub image_base64: String, // JPEG
+    pub mean_brightness: f64,
+    pub dark: bool,
+}
+
+// A raw frame as tightly packed RGB rows
+struct Frame {
+    width: u32,
+    height: u32,
+    rgb: Vec<u8>,
+}
+
+#[tauri::command]
+pub async fn get_camera_info(probes: tauri::State<'_, ProbeScheduler>) -> Result<Vec<CameraInfo>, QuickscanError> {
+    probes.run(camera_info).await
+}
+
+// `index` is the position in get_camera_info's list; the first camera by default
+#[tauri::command]
+pub async fn test_camera_capture(app: tauri::AppHandle, probes: tauri::State<'_, ProbeScheduler>, index: Option<usize>) -> Result<CameraCapture, QuickscanError> {
+    crate::consent::require(&app, crate::consent::ConsentScope::Camera)?;
+    probes.run(move || {
+        let index = index.unwrap_or(0);
+        let camera = camera_info()?
+            .into_iter()
+            .nth(index)
+            .ok_or_else(|| QuickscanError::command_failed("camera", format!("no camera at index {}", index)))?;
+        encode(camera.name, capture(index)?)
+    })
+    .await
+}
+
+pub fn camera_info() -> Result<Vec<CameraInfo>, QuickscanError> {
+    #[cfg(target_os = "macos")]
+    {
+        Ok(avfoundation::cameras())
+    }
+    #[cfg(target_os = "windows")]
+    {
+        media_foundation::cameras()
+    }
+    #[cfg(target_os = "linux")]
+    {
+        Ok(cameras_linux())
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        Err(QuickscanError::unsupported("camera_info"))
+    }
+}
+
+fn capture(index: usize) -> Result<Frame, QuickscanError> {
+    #[cfg(target_os = "macos")]
+    {
+        avfoundation::capture(index, CAPTURE_TIMEOUT)
+    }
+    #[cfg(target_os = "windows")]
+    {
+        media_foundation::capture(index, CAPTURE_TIMEOUT)
+    }
+    #[cfg(target_os = "linux")]
+    {
+        capture_linux(index)
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        let _ = index;
+        Err(QuickscanError::unsupported("camera_capture"))
+    }
+}
+
+fn encode(camera: String, frame: Frame) -> Result<CameraCapture, QuickscanError> {
+    let image = image::RgbImage::from_raw(frame.width, frame.height, frame.rgb)
+        .ok_or_else(|| QuickscanError::parse("camera", "frame smaller than its dimensions"))?;
+    let image = if image.width() > MAX_CAPTURE_WIDTH {
+        let height = image.height() * MAX_CAPTURE_WIDTH / image.width();
+        image::imageops::resize(&image, MAX_CAPTURE_WIDTH, height, image::imageops::FilterType::Triangle)
+    } else {
+        image
+    };
+
+    let pixels = (image.width() as u64 * image.height() as u64).max(1) as f64;
+    let luma: f64 = image.pixels().map(|p| 0.299 * p[0] as f64 + 0.587 * p[1] as f64 + 0.114 * p[2] as f64).sum();
+    let mean_brightness = (luma / pixels * 10.0).round() / 10.0;
+
+    let mut jpeg = Vec::new();
+    image::codecs::jpeg::JpegEncoder::new_with_quality(&mut jpeg, JPEG_QUALITY)
+        .encode_image(&image)
+        .map_err(|e| QuickscanError::command_failed("camera", e))?;
+    Ok(CameraCapture {
+        camera,
+        width: image.width(),
+        height: image.height(),
+        image_base64: BASE64_STANDARD.encode(jpeg),
+        mean_brightness,
+        dark: mean_brightness < DARK_BRIGHTNESS,
+    })
+}
+
+// AVFoundation through the Objective-C runtime. There is no C API for
+// capture, and a handful of messages doesn't justify a bindings crate.
+#[cfg(target_os = "macos")]
+mod avfoundation {
+    use super::{CameraInfo, Frame, WARMUP_FRAMES};
+    use crate::error::QuickscanError;
+    use core_foundation::base::TCFType;
+    use core_foundation::dictionary::CFDictionary;
+    use core_foundation::number::CFNumber;
+    use core_foundation::string::CFString;
+    use core_foundation_sys::string::CFStringRef;
+    use std::ffi::{c_char, c_void, CStr, CString};
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::{Condvar, Mutex, OnceLock};
+    use std::time::Duration;
+
+    type Id = *mut c_void;
+    type Sel = *const c_void;
+
+    #[link(name = "objc")]
+    extern "C" {
+        fn objc_getClass(name: *const c_char) -> Id;
+        fn sel_registerName(name: *const c_char) -> Sel;
+        fn objc_msgSend();
+        fn objc_allocateClassPair(superclass: Id, name: *const c_char, extra_bytes: usize) -> Id;
+        fn objc_registerClassPair(class: Id);
+        fn class_addMethod(class: Id, name: Sel, imp: *const c_void, types: *const c_char) -> bool;
+        fn objc_autoreleasePoolPush() -> *mut c_void;
+        fn objc_autoreleasePoolPop(pool: *mut c_void);
+    }
+
+    #[link(name = "AVFoundation", kind = "framework")]
+    extern "C" {
+        static AVMediaTypeVideo: Id;
+    }
+
+    #[repr(C)]
+    struct CMVideoDimensions {
+        width: i32,
+        height: i32,
+    }
+
+    #[link(name = "CoreMedia", kind = "framework")]
+    extern "C" {
+        fn CMSampleBufferGetImageBuffer(sample_buffer: *const c_void) -> *const c_void;
+        fn CMVideoFormatDescriptionGetDimensions(description: *const c_void) -> CMVideoDimensions;
+    }
+
+    #[link(name = "CoreVideo", kind = "framework")]
+    extern "C" {
+        static kCVPixelBufferPixelFormatTypeKey: CFStringRef;
+        fn CVPixelBufferLockBaseAddress(buffer: *const c_void, flags: u64) -> i32;
+        fn CVPixelBufferUnlockBaseAddress(buffer: *const c_void, flags: u64) -> i32;
+        fn CVPixelBufferGetBaseAddress(buffer: *const c_void) -> *const u8;
+        fn CVPixelBufferGetBytesPerRow(buffer: *const c_void) -> usize;
+        fn CVPixelBufferGetWidth(buffer: *const c_void) -> usize;
+        fn CVPixelBufferGetHeight(buffer: *const c_void) -> usize;
+    }
+
+    extern "C" {
+        fn dispatch_queue_create(label: *const c_char, attr: *const c_void) -> *mut c_void;
+        fn dispatch_release(object: *mut c_void);
+    }
+
+    const PIXEL_FORMAT_32BGRA: i32 = 0x4247_5241; // 'BGRA'
+    const LOCK_READ_ONLY: u64 = 1;
+    const AUTHORIZATION_RESTRICTED: isize = 1;
+    const AUTHORIZATION_DENIED: isize = 2;
+
+    // The delegate runs on a dispatch queue; it hands the first settled frame over here
+    static FRAME: Mutex<Option<Frame>> = Mutex::new(None);
+    static FRAME_READY: Condvar = Condvar::new();
+    static FRAMES_SEEN: AtomicU32 = AtomicU32::new(0);
+    // One capture at a time: the slot above is shared
+    static CAPTURING: Mutex<()> = Mutex::new(());
+
+    fn sel(name: &str) -> Sel {
+        let name = CString::new(name).unwrap_or_default();
+        unsafe { sel_registerName(name.as_ptr()) }
+    }
+
+    fn class(name: &str) -> Id {
+        let name = CString::new(name).unwrap_or_default();
+        unsafe { objc_getClass(name.as_ptr()) }
+    }
+
+    // objc_msgSend must be called through a pointer of the method's exact type
+    unsafe fn send<R>(receiver: Id, selector: &str) -> R {
+        let f = std::mem::transmute::<unsafe extern "C" fn(), unsafe extern "C" fn(Id, Sel) -> R>(objc_msgSend);
+        f(receiver, sel(selector))
+    }
+
+    unsafe fn send1<A, R>(receiver: Id, selector: &str, a: A) -> R {
+        let f = std::mem::transmute::<unsafe extern "C" fn(), unsafe extern "C" fn(Id, Sel, A) -> R>(objc_msgSend);
+        f(receiver, sel(selector), a)
+    }
+
+    unsafe fn send2<A, B, R>(receiver: Id, selector: &str, a: A, b: B) -> R {
+        let f = std::mem::transmute::<unsafe extern "C" fn(), unsafe extern "C" fn(Id, Sel, A, B) -> R>(objc_msgSend);
+        f(receiver, sel(selector), a, b)
+    }
+
+    unsafe fn string(ns_string: Id) -> Option<String> {
+        if ns_string.is_null() {
+            return None;
+        }
+        let utf8: *const c_char = send(ns_string, "UTF8String");
+        (!utf8.is_null()).then(|| CStr::from_ptr(utf8).to_string_lossy().trim().to_string()).filter(|s| !s.is_empty())
+    }
+
+    unsafe fn video_devices() -> Vec<Id> {
+        let devices: Id = send1(class("AVCaptureDevice"), "devicesWithMediaType:", AVMediaTypeVideo);
+        let count: usize = send(devices, "count");
+        (0..count).map(|i| send1(devices, "objectAtIndex:", i)).collect()
+    }
+
+    // transportType is a four-character code: 'bltn', 'usb ', 'thun', 'virt'
+    fn connection(transport: i32) -> Option<String> {
+        let code = String::from_utf8_lossy(&transport.to_be_bytes()).trim().to_string();
+        match code.as_str() {
+            "" => None,
+            "bltn" => Some("built-in".to_string()),
+            "thun" => Some("thunderbolt".to_string()),
+            "virt" => Some("virtual".to_string()),
+            _ => Some(code),
+        }
+    }
+
+    pub fn cameras() -> Vec<CameraInfo> {
+        unsafe {
+            let pool = objc_autoreleasePoolPush();
+            let cameras = video_devices()
+                .into_iter()
+                .map(|device| {
+                    let transport: i32 = send(device, "transportType");
+                    let formats: Id = send(device, "formats");
+                    let count: usize = send(formats, "count");
+                    let largest = (0..count)
+                        .map(|i| {
+                            let format: Id = send1(formats, "objectAtIndex:", i);
+                            CMVideoFormatDescriptionGetDimensions(send(format, "formatDescription"))
+                        })
+                        .max_by_key(|d| d.width as i64 * d.height as i64);
+                    let connection = connection(transport);
+                    CameraInfo {
+                        name: string(send(device, "localizedName")).unwrap_or_else(|| "Camera".to_string()),
+                        model: string(send(device, "modelID")),
+                        built_in: connection.as_ref().map(|c| c == "built-in"),
+                        connection,
+                        max_resolution: largest.map(|d| format!("{}x{}", d.width, d.height)),
+                    }
+                })
+                .collect();
+            objc_autoreleasePoolPop(pool);
+            cameras
+        }
+    }
+
+    // -captureOutput:didOutputSampleBuffer:fromConnection:
+    extern "C" fn did_output(_this: Id, _cmd: Sel, _output: Id, sample_buffer: *const c_void, _connection: Id) {
+        if FRAMES_SEEN.fetch_add(1, Ordering::Relaxed) < WARMUP_FRAMES {
+            return;
+        }
+        let mut slot = FRAME.lock().unwrap_or_else(|e| e.into_inner());
+        if slot.is_some() {
+            return;
+        }
+        unsafe {
+            let pixels = CMSampleBufferGetImageBuffer(sample_buffer);
+            if pixels.is_null() || CVPixelBufferLockBaseAddress(pixels, LOCK_READ_ONLY) != 0 {
+                return;
+            }
+            let (width, height) = (CVPixelBufferGetWidth(pixels), CVPixelBufferGetHeight(pixels));
+            let stride = CVPixelBufferGetBytesPerRow(pixels);
+            let base = CVPixelBufferGetBaseAddress(pixels);
+            if !base.is_null() {
+                let bgra = std::slice::from_raw_parts(base, stride * height);
+                let rgb = bgra
+                    .chunks(stride)
+                    .flat_map(|row| row[..width * 4].chunks(4).flat_map(|p| [p[2], p[1], p[0]]))
+                    .collect();
+                *slot = Some(Frame { width: width as u32, height: height as u32, rgb });
+                FRAME_READY.notify_all();
+            }
+            CVPixelBufferUnlockBaseAddress(pixels, LOCK_READ_ONLY);
+        }
+    }
+
+    fn delegate_class() -> Id {
+        static CLASS: OnceLock<usize> = OnceLock::new();
+        *CLASS.get_or_init(|| unsafe {
+            let class = objc_allocateClassPair(class("NSObject"), c"QuickscanFrameGrabber".as_ptr(), 0);
+            class_addMethod(
+                class,
+                sel("captureOutput:didOutputSampleBuffer:fromConnection:"),
+                did_output as *const c_void,
+                c"v@:@@@".as_ptr(),
+            );
+            objc_registerClassPair(class);
+            class as usize
+        }) as Id
+    }
+
+    pub fn capture(index: usize, timeout: Duration) -> Result<Frame, QuickscanError> {
+        let _capturing = CAPTURING.lock().unwrap_or_else(|e| e.into_inner());
+        unsafe {
+            let status: isize = send1(class("AVCaptureDevice"), "authorizationStatusForMediaType:", AVMediaTypeVideo);
+            if status == AUTHORIZATION_DENIED || status == AUTHORIZATION_RESTRICTED {
+                return Err(QuickscanError::PermissionDenied {
+                    source: "AVFoundation".to_string(),
+                    message: "camera access is turned off in Privacy & Security settings".to_string(),
+                });
+            }
+            let pool = objc_autoreleasePoolPush();
+            let frame = capture_in_pool(index, timeout);
+            objc_autoreleasePoolPop(pool);
+            frame
+        }
+    }
+
+    unsafe fn capture_in_pool(index: usize, timeout: Duration) -> Result<Frame, QuickscanError> {
+        let device = *video_devices()
+            .get(index)
+            .ok_or_else(|| QuickscanError::command_failed("AVFoundation", format!("no camera at index {}", index)))?;
+        // Asks for camera permission the first time
+        let mut error: Id = std::ptr::null_mut();
+        let input: Id = send2(class("AVCaptureDeviceInput"), "deviceInputWithDevice:error:", device, &mut error as *mut Id);
+        if input.is_null() {
+            let message = string(send(error, "localizedDescription")).unwrap_or_else(|| "camera unavailable".to_string());
+            return Err(QuickscanError::command_failed("AVFoundation", message));
+        }
+
+        let session: Id = send(send::<Id>(class("AVCaptureSession"), "alloc"), "init");
+        let output: Id = send(send::<Id>(class("AVCaptureVideoDataOutput"), "alloc"), "init");
+        let settings = CFDictionary::from_CFType_pairs(&[(
+            CFString::wrap_under_get_rule(kCVPixelBufferPixelFormatTypeKey),
+            CFNumber::from(PIXEL_FORMAT_32BGRA),
+        )]);
+        send1::<Id, ()>(output, "setVideoSettings:", settings.as_concrete_TypeRef() as Id);
+        send1::<bool, ()>(output, "setAlwaysDiscardsLateVideoFrames:", true);
+        let delegate: Id = send(send::<Id>(delegate_class(), "alloc"), "init");
+        let queue = dispatch_queue_create(c"quickscan.camera".as_ptr(), std::ptr::null());
+        send2::<Id, *mut c_void, ()>(output, "setSampleBufferDelegate:queue:", delegate, queue);
+
+        *FRAME.lock().unwrap_or_else(|e| e.into_inner()) = None;
+        FRAMES_SEEN.store(0, Ordering::Relaxed);
+        let ready = send1::<Id, bool>(session, "canAddInput:", input) && send1::<Id, bool>(session, "canAddOutput:", output);
+        let frame = if ready {
+            send1::<Id, ()>(session, "addInput:", input);
+            send1::<Id, ()>(session, "addOutput:", output);
+            send::<()>(session, "startRunning");
+            let slot = FRAME.lock().unwrap_or_else(|e| e.into_inner());
+            let (mut slot, _) =
+                FRAME_READY.wait_timeout_while(slot, timeout, |frame| frame.is_none()).unwrap_or_else(|e| e.into_inner());
+            let frame = slot.take();
+            drop(slot);
+            send::<()>(session, "stopRunning");
+            frame.ok_or(QuickscanError::Timeout { source: "AVFoundation".to_string(), seconds: timeout.as_secs() })
+        } else {
+            // Another app holds the camera exclusively
+            Err(QuickscanError::command_failed("AVFoundation", "camera is in use"))
+        };
+
+        send2::<Id, *mut c_void, ()>(output, "setSampleBufferDelegate:queue:", std::ptr::null_mut(), std::ptr::null_mut());
+        send::<()>(session, "release");
+        send::<()>(output, "release");
+        send::<()>(delegate, "release");
+        dispatch_release(queue);
+        frame
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod media_foundation {
+    use super::{CameraInfo, Frame, WARMUP_FRAMES};
+    use crate::error::QuickscanError;
+    use std::time::{Duration, Instant};
+    use windows::core::{GUID, PWSTR};
+    use windows::Win32::Media::MediaFoundation::{
+        IMFActivate, IMFAttributes, IMFMediaSource, IMFMediaType, IMFSample, IMFSourceReader, MFCreateAttributes,
+        MFCreateMediaType, MFCreateSourceReaderFromMediaSource, MFEnumDeviceSources, MFMediaType_Video, MFShutdown,
+        MFStartup, MFVideoFormat_RGB32, MFSTARTUP_FULL, MF_DEVSOURCE_ATTRIBUTE_FRIENDLY_NAME,
+        MF_DEVSOURCE_ATTRIBUTE_SOURCE_TYPE, MF_DEVSOURCE_ATTRIBUTE_SOURCE_TYPE_VIDCAP_GUID,
+        MF_DEVSOURCE_ATTRIBUTE_SOURCE_TYPE_VIDCAP_SYMBOLIC_LINK, MF_MT_DEFAULT_STRIDE, MF_MT_FRAME_SIZE,
+        MF_MT_MAJOR_TYPE, MF_MT_SUBTYPE, MF_SOURCE_READER_ENABLE_VIDEO_PROCESSING, MF_SOURCE_READER_FIRST_VIDEO_STREAM,
+        MF_VERSION,
+    };
+    use windows::Win32::System::Com::{CoInitializeEx, CoTaskMemFree, COINIT_MULTITHREADED};
+
+    const SOURCE: &str = "Media Foundation";
+
+    fn failed(error: windows::core::Error) -> QuickscanError {
+        QuickscanError::command_failed(SOURCE, error.message())
+    }
+
+    // COM and Media Foundation for the lifetime of one probe on this thread
+    struct Session;
+
+    impl Session {
+        fn start() -> Result<Session, QuickscanError> {
+            unsafe {
+                // Already initialized on a reused pool thread is fine
+                let _ = CoInitializeEx(None, COINIT_MULTITHREADED);
+                MFStartup(MF_VERSION, MFSTARTUP_FULL).map_err(failed)?;
+            }
+            Ok(Session)
+        }
+    }
+
+    impl Drop for Session {
+        fn drop(&mut self) {
+            let _ = unsafe { MFShutdown() };
+        }
+    }
+
+    fn devices() -> Result<Vec<IMFActivate>, QuickscanError> {
+        unsafe {
+            let mut attributes: Option<IMFAttributes> = None;
+            MFCreateAttributes(&mut attributes, 1).map_err(failed)?;
+            let attributes = attributes.ok_or_else(|| QuickscanError::command_failed(SOURCE, "no attribute store"))?;
+            attributes
+                .SetGUID(&MF_DEVSOURCE_ATTRIBUTE_SOURCE_TYPE, &MF_DEVSOURCE_ATTRIBUTE_SOURCE_TYPE_VIDCAP_GUID)
+                .map_err(failed)?;
+
+            let mut list: *mut Option<IMFActivate> = std::ptr::null_mut();
+            let mut count = 0u32;
+            MFEnumDeviceSources(&attributes, &mut list, &mut count).map_err(failed)?;
+            if list.is_null() {
+                return Ok(vec![]);
+            }
+            // Take ownership of each entry, then free the array itself
+            let devices = (0..count as usize).filter_map(|i| std::ptr::read(list.add(i))).collect();
+            CoTaskMemFree(Some(list as *const _));
+            Ok(devices)
+        }
+    }
+
+    fn string(device: &IMFActivate, key: &GUID) -> Option<String> {
+        unsafe {
+            let mut value = PWSTR::null();
+            let mut len = 0u32;
+            device.GetAllocatedString(key, &mut value, &mut len).ok()?;
+            let string = value.to_string().ok();
+            CoTaskMemFree(Some(value.0 as *const _));
+            string
+        }
+    }
+
+    fn reader(device: &IMFActivate) -> Result<IMFSourceReader, QuickscanError> {
+        unsafe {
+            let source: IMFMediaSource = device.ActivateObject().map_err(failed)?;
+            // Lets the reader convert whatever the camera streams into RGB32
+            let mut attributes: Option<IMFAttributes> = None;
+            MFCreateAttributes(&mut attributes, 1).map_err(failed)?;
+            if let Some(attributes) = &attributes {
+                attributes.SetUINT32(&MF_SOURCE_READER_ENABLE_VIDEO_PROCESSING, 1).map_err(failed)?;
+            }
+            MFCreateSourceReaderFromMediaSource(&source, attributes.as_ref()).map_err(failed)
+        }
+    }
+
+    fn frame_size(media_type: &IMFMediaType) -> Option<(u32, u32)> {
+        // Width in the high 32 bits, height in the low
+        let size = unsafe { media_type.GetUINT64(&MF_MT_FRAME_SIZE) }.ok()?;
+        Some(((size >> 32) as u32, size as u32))
+    }
+
+    pub fn cameras() -> Result<Vec<CameraInfo>, QuickscanError> {
+        let _session = Session::start()?;
+        let stream = MF_SOURCE_READER_FIRST_VIDEO_STREAM.0 as u32;
+        Ok(devices()?
+            .iter()
+            .map(|device| {
+                let name = string(device, &MF_DEVSOURCE_ATTRIBUTE_FRIENDLY_NAME).unwrap_or_else(|| "Camera".to_string());
+                // "\\?\usb#vid_04f2&pid_b6dd&mi_00#..."
+                let link = string(device, &MF_DEVSOURCE_ATTRIBUTE_SOURCE_TYPE_VIDCAP_SYMBOLIC_LINK).unwrap_or_default().to_lowercase();
+                let model = link
+                    .find("vid_")
+                    .and_then(|start| link.get(start..start + 17))
+                    .map(|ids| ids.replace("vid_", "").replace("&pid_", ":"));
+                // Only the resolutions the camera streams natively
+                let largest = reader(device).ok().and_then(|reader| {
+                    (0..)
+                        .map_while(|i| unsafe { reader.GetNativeMediaType(stream, i) }.ok())
+                        .filter_map(|media_type| frame_size(&media_type))
+                        .max_by_key(|(w, h)| *w as u64 * *h as u64)
+                });
+                let _ = unsafe { device.ShutdownObject() };
+                // Media Foundation doesn't say whether a camera is built in; laptops
+                // name theirs "Integrated" or "Internal"
+                let lower = name.to_lowercase();
+                let built_in = (lower.contains("integrated") || lower.contains("internal") || lower.contains("built-in"))
+                    .then_some(true);
+                CameraInfo {
+                    connection: link.starts_with(r"\\?\usb#").then(|| "usb".to_string()),
+                    built_in,
+                    model,
+                    max_resolution: largest.map(|(w, h)| format!("{}x{}", w, h)),
+                    name,
+                }
+            })
+            .collect())
+    }
+
+    pub fn capture(index: usize, timeout: Duration) -> Result<Frame, QuickscanError> {
+        let _session = Session::start()?;
+        let devices = devices()?;
+        let device = devices
+            .get(index)
+            .ok_or_else(|| QuickscanError::command_failed(SOURCE, format!("no camera at index {}", index)))?;
+        let reader = reader(device)?;
+        let stream = MF_SOURCE_READER_FIRST_VIDEO_STREAM.0 as u32;
+        let frame = unsafe {
+            let rgb32 = MFCreateMediaType().map_err(failed)?;
+            rgb32.SetGUID(&MF_MT_MAJOR_TYPE, &MFMediaType_Video).map_err(failed)?;
+            rgb32.SetGUID(&MF_MT_SUBTYPE, &MFVideoFormat_RGB32).map_err(failed)?;
+            reader.SetCurrentMediaType(stream, None, &rgb32).map_err(failed)?;
+            read_frame(&reader, stream, timeout)
+        };
+        let _ = unsafe { device.ShutdownObject() };
+        frame
+    }
+
+    unsafe fn read_frame(reader: &IMFSourceReader, stream: u32, timeout: Duration) -> Result<Frame, QuickscanError> {
+        let media_type = reader.GetCurrentMediaType(stream).map_err(failed)?;
+        let (width, height) = frame_size(&media_type).ok_or_else(|| QuickscanError::parse(SOURCE, "no frame size"))?;
+        // Negative for bottom-up rows
+        let stride = media_type.GetUINT32(&MF_MT_DEFAULT_STRIDE).map(|s| s as i32).unwrap_or(width as i32 * 4);
+
+        let started = Instant::now();
+        let mut frames = 0;
+        loop {
+            if started.elapsed() > timeout {
+                return Err(QuickscanError::Timeout { source: SOURCE.to_string(), seconds: timeout.as_secs() });
+            }
+            let mut sample: Option<IMFSample> = None;
+            let mut flags = 0u32;
+            reader.ReadSample(stream, 0, None, Some(&mut flags), None, Some(&mut sample)).map_err(failed)?;
+            // Gaps in the stream come back as empty reads
+            let Some(sample) = sample else {
+                continue;
+            };
+            frames += 1;
+            if frames <= WARMUP_FRAMES {
+                continue;
+            }
+
+            let buffer = sample.ConvertToContiguousBuffer().map_err(failed)?;
+            let mut data: *mut u8 = std::ptr::null_mut();
+            let mut len = 0u32;
+            buffer.Lock(&mut data, None, Some(&mut len)).map_err(failed)?;
+            let row_len = stride.unsigned_abs() as usize;
+            let rgb = if data.is_null() || (len as usize) < row_len * height as usize {
+                None
+            } else {
+                let bytes = std::slice::from_raw_parts(data, row_len * height as usize);
+                let mut rows: Vec<&[u8]> = bytes.chunks(row_len).collect();
+                if stride < 0 {
+                    rows.reverse();
+                }
+                // BGRX
+                Some(rows.iter().flat_map(|row| row[..width as usize * 4].chunks(4).flat_map(|p| [p[2], p[1], p[0]])).collect())
+            };
+            let _ = buffer.Unlock();
+            return rgb
+                .map(|rgb| Frame { width, height, rgb })
+                .ok_or_else(|| QuickscanError::parse(SOURCE, "frame buffer smaller than its dimensions"));
+        }
+    }
+}
+
+// V4L2 devices from sysfs; each camera also registers a metadata node
+// (index 1) that streams no pictures
+#[cfg(target_os = "linux")]
+fn video_nodes() -> Vec<std::path::PathBuf> {
+    let mut nodes: Vec<_> = std::fs::read_dir("/sys/class/video4linux")
+        .map(|entries| entries.flatten().map(|entry| entry.path()).collect())
+        .unwrap_or_default();
+    nodes.retain(|node| std::fs::read_to_string(node.join("index")).map_or(true, |i| i.trim() == "0"));
+    nodes.sort();
+    nodes
+}
+
+#[cfg(target_os = "linux")]
+fn cameras_linux() -> Vec<CameraInfo> {
+    let read = |path: std::path::PathBuf| std::fs::read_to_string(path).ok().map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+    video_nodes()
+        .into_iter()
+        .map(|node| {
+            // device -> the USB interface; its parent is the USB device
+            let device = std::fs::canonicalize(node.join("device")).ok();
+            let usb_device = device.as_ref().and_then(|d| d.parent()).filter(|d| d.join("idVendor").exists());
+            let model = usb_device.and_then(|d| Some(format!("{}:{}", read(d.join("idVendor"))?, read(d.join("idProduct"))?)));
+            // "fixed" for a port wired to something inside the case
+            let built_in = usb_device.and_then(|d| match read(d.join("removable")).as_deref() {
+                Some("fixed") => Some(true),
+                Some("removable") => Some(false),
+                _ => None,
+            });
+            let connection = device.map(|d| if d.to_string_lossy().contains("/usb") { "usb" } else { "platform" }.to_string());
+            CameraInfo {
+                name: read(node.join("name")).unwrap_or_else(|| "Camera".to_string()),
+                model,
+                built_in,
+                connection,
+                max_resolution: None,
+            }
+        })
+        .collect()
+}
+
+// There's no capture library to lean on here; ffmpeg is on most distributions
+// that ship a desktop
+#[cfg(target_os = "linux")]
+fn capture_linux(index: usize) -> Result<Frame, QuickscanError> {
+    let node = video_nodes()
+        .into_iter()
+        .nth(index)
+        .ok_or_else(|| QuickscanError::command_failed("camera", format!("no camera at index {}", index)))?;
+    let device = format!("/dev/{}", node.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default());
+
+    // Skip the frames taken while auto-exposure settles
+    let mut command = std::process::Command::new("ffmpeg");
+    command.args(["-hide_banner", "-loglevel", "error", "-f", "v4l2", "-i", &device]);
+    command.args(["-vf", r"select=gte(n\,15)", "-frames:v", "1", "-f", "image2pipe", "-vcodec", "mjpeg", "-"]);
+    let output = crate::process::run_cmd(&mut command).map_err(|e| match e {
+        QuickscanError::CommandFailed { .. } => QuickscanError::unsupported("camera_capture (install ffmpeg)"),
+        other => other,
+    })?;
+    if !output.status.success() || output.stdout.is_empty() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(QuickscanError::command_failed("ffmpeg", stderr));
+    }
+    let image = image::load_from_memory_with_format(&output.stdout, image::ImageFormat::Jpeg)
+        .map_err(|e| QuickscanError::parse("ffmpeg", e))?
+        .to_rgb8();
+    Ok(Frame { width: image.width(), height: image.height(), rgb: image.into_raw() })
+}
@@ -0,0 +1,105 @@
+// Whether the system clock can be trusted, for `check_clock`. Several
+// indicators compare dates: an install "two weeks ago", a battery made years
+// after the chassis. On a machine whose clock is wrong, a flat RTC cell
+// (rtc_battery.rs) or one set by hand, those comparisons say nothing, so
+// `check_refurbishment` notes the clock's state on them and weighs them less.
+//
+// The clock is wrong when it reads a date the machine can't have seen: the
+// 1970 epoch or a firmware default from before the firmware was released, or
+// one past January 2038 where 32-bit time wraps. When a time server answers,
+// anything more than a day from its time is wrong too. A scan run offline
+// only has the first kind to go on.
+
+use chrono::{Datelike, Local, NaiveDate};
+use serde::Serialize;
+use std::net::{ToSocketAddrs, UdpSocket};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const NTP_SERVERS: [&str; 3] = ["time.apple.com:123", "time.windows.com:123", "pool.ntp.org:123"];
+const NTP_TIMEOUT: Duration = Duration::from_secs(2);
+// Seconds from 1900, where NTP counts from, to 1970
+const NTP_EPOCH_OFFSET: i64 = 2_208_988_800;
+// Dates are compared by the day; minutes of drift don't matter
+const MAX_OFFSET_SECS: i64 = 24 * 3600;
+
+// The refurbishment indicators that go by dates
+pub const DATE_INDICATORS: [&str; 2] = ["recent_os_install", "battery_newer_than_chassis"];
+// Before any machine this app runs on was made
+const EPOCH_YEARS_BEFORE: i32 = 2000;
+
+#[derive(Serialize)]
+pub struct ClockCheck {
+    pub system_time: String, // "YYYY-MM-DD HH:MM:SS" local time
+    pub ntp_server: Option<String>, // the one that answered; None offline
+    pub ntp_offset_seconds: Option<i64>, // the server's time minus the system clock
+    pub problem: Option<String>, // "epoch", "before_firmware", "y2038" or "ntp_offset"
+    pub reliable: bool,
+}
+
+#[tauri::command]
+pub async fn check_clock() -> ClockCheck {
+    crate::process::blocking(clock_check).await
+}
+
+pub fn clock_check() -> ClockCheck {
+    let now = Local::now();
+    let today = now.date_naive();
+    let ntp = NTP_SERVERS.iter().find_map(|server| ntp_offset(server).map(|offset| (server.to_string(), offset)));
+
+    let before_firmware = crate::rtc_battery::earliest_possible()
+        .and_then(|earliest| NaiveDate::parse_from_str(&earliest, "%Y-%m-%d").ok())
+        .is_some_and(|earliest| today < earliest);
+    let problem = if today.year() < EPOCH_YEARS_BEFORE {
+        Some("epoch")
+    } else if before_firmware {
+        Some("before_firmware")
+    } else if now.timestamp() >= i32::MAX as i64 {
+        Some("y2038")
+    } else if ntp.as_ref().is_some_and(|(_, offset)| offset.abs() > MAX_OFFSET_SECS) {
+        Some("ntp_offset")
+    } else {
+        None
+    };
+
+    ClockCheck {
+        system_time: now.format("%Y-%m-%d %H:%M:%S").to_string(),
+        ntp_offset_seconds: ntp.as_ref().map(|(_, offset)| *offset),
+        ntp_server: ntp.map(|(server, _)| server),
+        reliable: problem.is_none(),
+        problem: problem.map(str::to_string),
+    }
+}
+
+// One SNTP exchange: the server's transmit time against the middle of the
+// round trip on the system clock. None when the server doesn't answer.
+fn ntp_offset(server: &str) -> Option<i64> {
+    let address = server.to_socket_addrs().ok()?.next()?;
+    let socket = UdpSocket::bind(if address.is_ipv4() { "0.0.0.0:0" } else { "[::]:0" }).ok()?;
+    socket.set_read_timeout(Some(NTP_TIMEOUT)).ok()?;
+
+    // Version 4, client mode
+    let mut request = [0u8; 48];
+    request[0] = 0x23;
+    let sent = unix_seconds(SystemTime::now());
+    socket.send_to(&request, address).ok()?;
+    let mut response = [0u8; 48];
+    let (length, _) = socket.recv_from(&mut response).ok()?;
+    let received = unix_seconds(SystemTime::now());
+
+    // Server mode, and not a kiss-o'-death (stratum 0)
+    if length < 48 || response[0] & 0x07 != 4 || response[1] == 0 {
+        return None;
+    }
+    let seconds = u32::from_be_bytes(response[40..44].try_into().ok()?) as i64;
+    let fraction = u32::from_be_bytes(response[44..48].try_into().ok()?) as f64 / u32::MAX as f64;
+    let server_time = (seconds - NTP_EPOCH_OFFSET) as f64 + fraction;
+    Some((server_time - (sent + received) / 2.0).round() as i64)
+}
+
+// Seconds since 1970 on the system clock; negative when it reads earlier
+fn unix_seconds(time: SystemTime) -> f64 {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(since) => since.as_secs_f64(),
+        Err(e) => -e.duration().as_secs_f64(),
+    }
+}
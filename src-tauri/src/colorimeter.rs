@@ -0,0 +1,211 @@
+// Display measurement through an ArgyllCMS-supported colorimeter (X-Rite,
+// Datacolor, ...). The frontend shows a full-screen patch, calls
+// `read_colorimeter` for each one (white, black, red, green, blue) and then
+// `compute_display_measurement` to turn the readings into panel figures.
+
+use serde::{Deserialize, Serialize};
+use crate::error::QuickscanError;
+use crate::process::run_cmd_with_timeout;
+use std::process::Command;
+use std::time::Duration;
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub struct ColorimeterReading {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub luminance: f64, // cd/m², equal to Y in emissive mode
+    pub chromaticity_x: f64,
+    pub chromaticity_y: f64,
+}
+
+#[derive(Deserialize)]
+pub struct PatchReadings {
+    pub white: ColorimeterReading,
+    pub black: ColorimeterReading,
+    pub red: Option<ColorimeterReading>,
+    pub green: Option<ColorimeterReading>,
+    pub blue: Option<ColorimeterReading>,
+}
+
+#[derive(Serialize)]
+pub struct DisplayMeasurement {
+    pub brightness_nits: f64,
+    pub black_level_nits: f64,
+    pub contrast_ratio: Option<f64>,
+    pub white_point_cct: Option<f64>,
+    pub srgb_coverage: Option<f64>,
+    pub dci_p3_coverage: Option<f64>,
+}
+
+// spotread waits for the instrument to settle; a reading rarely takes more than a few seconds
+const READ_TIMEOUT: Duration = Duration::from_secs(60);
+
+const SRGB_PRIMARIES: [(f64, f64); 3] = [(0.640, 0.330), (0.300, 0.600), (0.150, 0.060)];
+const DCI_P3_PRIMARIES: [(f64, f64); 3] = [(0.680, 0.320), (0.265, 0.690), (0.150, 0.060)];
+
+// Takes a single emissive reading of whatever is currently on screen.
+#[tauri::command]
+pub async fn read_colorimeter() -> Result<ColorimeterReading, QuickscanError> {
+    crate::process::blocking(take_reading).await
+}
+
+fn take_reading() -> Result<ColorimeterReading, QuickscanError> {
+    // A spotread that couldn't be started isn't installed; a timeout or a
+    // permission error is reported as it is
+    let output = run_cmd_with_timeout(Command::new("spotread").args(["-e", "-O"]), READ_TIMEOUT).map_err(|e| match e {
+        QuickscanError::CommandFailed { .. } => QuickscanError::unsupported("colorimeter (install ArgyllCMS)"),
+        other => other,
+    })?;
+
+    // No instrument, or it couldn't read the patch: spotread says why
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    parse_spotread_output(&stdout).ok_or_else(|| {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let message = stderr.lines().chain(stdout.lines()).find(|l| !l.trim().is_empty());
+        QuickscanError::command_failed("spotread", message.unwrap_or("no reading").trim())
+    })
+}
+
+#[tauri::command]
+pub fn compute_display_measurement(readings: PatchReadings) -> DisplayMeasurement {
+    let white = readings.white;
+    let black = readings.black;
+    let contrast_ratio = (black.luminance > 0.0).then(|| white.luminance / black.luminance);
+
+    let gamut = match (readings.red, readings.green, readings.blue) {
+        (Some(r), Some(g), Some(b)) => Some([
+            (r.chromaticity_x, r.chromaticity_y),
+            (g.chromaticity_x, g.chromaticity_y),
+            (b.chromaticity_x, b.chromaticity_y),
+        ]),
+        _ => None,
+    };
+
+    DisplayMeasurement {
+        brightness_nits: white.luminance,
+        black_level_nits: black.luminance,
+        contrast_ratio,
+        white_point_cct: correlated_color_temperature(white.chromaticity_x, white.chromaticity_y),
+        srgb_coverage: gamut.and_then(|g| gamut_coverage(&g, &SRGB_PRIMARIES)),
+        dci_p3_coverage: gamut.and_then(|g| gamut_coverage(&g, &DCI_P3_PRIMARIES)),
+    }
+}
+
+// spotread prints e.g. "Result is XYZ: 95.049741 100.000000 108.891011, D50 Lab: ..."
+fn parse_spotread_output(stdout: &str) -> Option<ColorimeterReading> {
+    let line = stdout.lines().rev().find(|l| l.contains("Result is XYZ:"))?;
+    let values: Vec<f64> = line
+        .split("XYZ:")
+        .nth(1)?
+        .split(',')
+        .next()?
+        .split_whitespace()
+        .filter_map(|v| v.parse().ok())
+        .collect();
+    let [x, y, z] = values[..] else {
+        return None;
+    };
+
+    let sum = x + y + z;
+    let (chromaticity_x, chromaticity_y) = if sum > 0.0 { (x / sum, y / sum) } else { (0.0, 0.0) };
+    Some(ColorimeterReading {
+        x,
+        y,
+        z,
+        luminance: y,
+        chromaticity_x,
+        chromaticity_y,
+    })
+}
+
+// McCamy's approximation, valid roughly between 2000K and 12500K
+fn correlated_color_temperature(x: f64, y: f64) -> Option<f64> {
+    if y <= 0.0 || (0.1858 - y).abs() < f64::EPSILON {
+        return None;
+    }
+    let n = (x - 0.3320) / (0.1858 - y);
+    let cct = 449.0 * n.powi(3) + 3525.0 * n.powi(2) + 6823.3 * n + 5520.33;
+    (1000.0..=25000.0).contains(&cct).then_some(cct)
+}
+
+// Below this the measured primaries are collinear (or repeated), and the clipping
+// would treat every reference vertex as inside the zero-area triangle
+const MIN_GAMUT_AREA: f64 = 1e-6;
+
+// Share of the reference triangle (CIE 1931 xy) covered by the measured one, in
+// percent; None when the measured primaries don't span a triangle
+fn gamut_coverage(measured: &[(f64, f64); 3], reference: &[(f64, f64); 3]) -> Option<f64> {
+    if polygon_area(measured) < MIN_GAMUT_AREA {
+        return None;
+    }
+    let reference_area = polygon_area(reference);
+    if reference_area <= 0.0 {
+        return Some(0.0);
+    }
+    let overlap = clip_polygon(reference, measured);
+    Some((polygon_area(&overlap) / reference_area * 100.0).min(100.0))
+}
+
+fn polygon_area(points: &[(f64, f64)]) -> f64 {
+    let n = points.len();
+    if n < 3 {
+        return 0.0;
+    }
+    let twice_area: f64 = (0..n)
+        .map(|i| {
+            let (x1, y1) = points[i];
+            let (x2, y2) = points[(i + 1) % n];
+            x1 * y2 - x2 * y1
+        })
+        .sum();
+    twice_area.abs() / 2.0
+}
+
+// Sutherland–Hodgman clipping of `subject` against the convex `clip` triangle
+fn clip_polygon(subject: &[(f64, f64)], clip: &[(f64, f64); 3]) -> Vec<(f64, f64)> {
+    let orientation = signed_area(clip).signum();
+    let mut output: Vec<(f64, f64)> = subject.to_vec();
+
+    for i in 0..clip.len() {
+        let edge_start = clip[i];
+        let edge_end = clip[(i + 1) % clip.len()];
+        let inside = |p: (f64, f64)| cross(edge_start, edge_end, p) * orientation >= 0.0;
+
+        let input = std::mem::take(&mut output);
+        for j in 0..input.len() {
+            let current = input[j];
+            let previous = input[(j + input.len() - 1) % input.len()];
+            match (inside(current), inside(previous)) {
+                (true, true) => output.push(current),
+                (true, false) => {
+                    output.push(intersection(previous, current, edge_start, edge_end));
+                    output.push(current);
+                }
+                (false, true) => output.push(intersection(previous, current, edge_start, edge_end)),
+                (false, false) => {}
+            }
+        }
+        if output.is_empty() {
+            break;
+        }
+    }
+    output
+}
+
+fn signed_area(points: &[(f64, f64); 3]) -> f64 {
+    cross(points[0], points[1], points[2])
+}
+
+fn cross(a: (f64, f64), b: (f64, f64), p: (f64, f64)) -> f64 {
+    (b.0 - a.0) * (p.1 - a.1) - (b.1 - a.1) * (p.0 - a.0)
+}
+
+fn intersection(p1: (f64, f64), p2: (f64, f64), q1: (f64, f64), q2: (f64, f64)) -> (f64, f64) {
+    let d = (p1.0 - p2.0) * (q1.1 - q2.1) - (p1.1 - p2.1) * (q1.0 - q2.0);
+    if d.abs() < f64::EPSILON {
+        return p2;
+    }
+    let t = ((p1.0 - q1.0) * (q1.1 - q2.1) - (p1.1 - q1.1) * (q1.0 - q2.0)) / d;
+    (p1.0 + t * (p2.0 - p1.0), p1.1 + t * (p2.1 - p1.1))
+}
@@ -0,0 +1,149 @@
+// Per-model manifest of components a Mac ships with. Detected hardware is
+// diffed against it so anything removed or dead shows up as "expected but not
+// detected" instead of silently missing from the report.
+
+use crate::process::run_cmd;
+use std::process::Command;
+
+pub struct ExpectedComponents {
+    pub camera: bool,
+    pub touch_id: bool,
+    pub touch_bar: bool,
+    pub backlit_keyboard: bool,
+    pub speakers: u8,
+    pub thunderbolt_ports: u8,
+}
+
+const fn expect(touch_id: bool, touch_bar: bool, speakers: u8, thunderbolt_ports: u8) -> ExpectedComponents {
+    ExpectedComponents {
+        camera: true,
+        touch_id,
+        touch_bar,
+        backlit_keyboard: true,
+        speakers,
+        thunderbolt_ports,
+    }
+}
+
+// Keyed by model identifier (sysctl hw.model)
+const MANIFEST: &[(&str, ExpectedComponents)] = &[
+    // MacBook Air
+    ("MacBookAir8,1", expect(true, false, 2, 2)),
+    ("MacBookAir8,2", expect(true, false, 2, 2)),
+    ("MacBookAir9,1", expect(true, false, 2, 2)),
+    ("MacBookAir10,1", expect(true, false, 2, 2)),
+    ("Mac14,2", expect(true, false, 4, 2)),
+    ("Mac14,15", expect(true, false, 6, 2)),
+    // 13" MacBook Pro
+    ("MacBookPro13,1", expect(false, false, 2, 2)),
+    ("MacBookPro13,2", expect(true, true, 2, 4)),
+    ("MacBookPro14,1", expect(false, false, 2, 2)),
+    ("MacBookPro14,2", expect(true, true, 2, 4)),
+    ("MacBookPro15,2", expect(true, true, 2, 4)),
+    ("MacBookPro15,4", expect(true, true, 2, 2)),
+    ("MacBookPro16,2", expect(true, true, 2, 4)),
+    ("MacBookPro16,3", expect(true, true, 2, 2)),
+    ("MacBookPro17,1", expect(true, true, 2, 2)),
+    ("Mac14,7", expect(true, true, 2, 2)),
+    // 15" / 16" MacBook Pro
+    ("MacBookPro13,3", expect(true, true, 2, 4)),
+    ("MacBookPro14,3", expect(true, true, 2, 4)),
+    ("MacBookPro15,1", expect(true, true, 2, 4)),
+    ("MacBookPro15,3", expect(true, true, 2, 4)),
+    ("MacBookPro16,1", expect(true, true, 6, 4)),
+    ("MacBookPro16,4", expect(true, true, 6, 4)),
+    // 14" / 16" MacBook Pro (Apple silicon)
+    ("MacBookPro18,1", expect(true, false, 6, 3)),
+    ("MacBookPro18,2", expect(true, false, 6, 3)),
+    ("MacBookPro18,3", expect(true, false, 6, 3)),
+    ("MacBookPro18,4", expect(true, false, 6, 3)),
+];
+
+pub fn expected_components(model_identifier: &str) -> Option<&'static ExpectedComponents> {
+    MANIFEST
+        .iter()
+        .find(|(model, _)| *model == model_identifier)
+        .map(|(_, expected)| expected)
+}
+
+pub fn model_identifier() -> Option<String> {
+    let output = run_cmd(Command::new("sysctl").args(["-n", "hw.model"])).ok()?;
+    let model = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!model.is_empty()).then_some(model)
+}
+
+// Components listed in the manifest that the system doesn't report, e.g.
+// "camera" or "thunderbolt_ports 2/4"
+pub fn missing_components(expected: &ExpectedComponents) -> Vec<String> {
+    let mut missing = vec![];
+    let ioreg = command_stdout("ioreg", &["-l"]).unwrap_or_default();
+
+    if expected.camera && !has_builtin_camera() {
+        missing.push("camera".to_string());
+    }
+    if expected.touch_id && !ioreg.contains("AppleMesa") {
+        missing.push("touch_id".to_string());
+    }
+    // TouchBarServer only runs when the Touch Bar is present and working
+    if expected.touch_bar && !command_succeeds("pgrep", &["-x", "TouchBarServer"]) {
+        missing.push("touch_bar".to_string());
+    }
+    if expected.backlit_keyboard && !ioreg.contains("KeyboardBacklight") {
+        missing.push("backlit_keyboard".to_string());
+    }
+    if expected.speakers > 0 && !has_builtin_speakers() {
+        missing.push("speakers".to_string());
+    }
+    if let Some(ports) = thunderbolt_port_count() {
+        if ports < expected.thunderbolt_ports {
+            missing.push(format!("thunderbolt_ports {}/{}", ports, expected.thunderbolt_ports));
+        }
+    }
+    missing
+}
+
+fn command_stdout(program: &str, args: &[&str]) -> Option<String> {
+    let output = run_cmd(Command::new(program).args(args)).ok()?;
+    Some(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+fn command_succeeds(program: &str, args: &[&str]) -> bool {
+    run_cmd(Command::new(program).args(args)).is_ok_and(|o| o.status.success())
+}
+
+fn system_profiler_items(data_type: &str) -> Option<Vec<serde_json::Value>> {
+    let stdout = command_stdout("system_profiler", &[data_type, "-json"])?;
+    let json = serde_json::from_str::<serde_json::Value>(&stdout).ok()?;
+    json.get(data_type)?.as_array().cloned()
+}
+
+fn has_builtin_camera() -> bool {
+    system_profiler_items("SPCameraDataType").is_some_and(|items| !items.is_empty())
+}
+
+fn has_builtin_speakers() -> bool {
+    let Some(items) = system_profiler_items("SPAudioDataType") else {
+        return false;
+    };
+    items
+        .iter()
+        .filter_map(|item| item.get("_items").and_then(|v| v.as_array()))
+        .flatten()
+        .any(|device| {
+            let builtin = device.get("coreaudio_device_transport").and_then(|v| v.as_str())
+                == Some("coreaudio_device_type_builtin");
+            builtin && device.get("coreaudio_device_output").is_some()
+        })
+}
+
+// Each Thunderbolt bus lists its ports as receptacle_<n>_tag entries
+fn thunderbolt_port_count() -> Option<u8> {
+    let items = system_profiler_items("SPThunderboltDataType").filter(|items| !items.is_empty())?;
+    let count = items
+        .iter()
+        .filter_map(|item| item.as_object())
+        .flat_map(|bus| bus.keys())
+        .filter(|key| key.starts_with("receptacle_") && key.ends_with("_tag"))
+        .count();
+    Some(count.min(u8::MAX as usize) as u8)
+}
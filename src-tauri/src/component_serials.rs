@@ -0,0 +1,281 @@
+// Serial numbers of the major components (`get_component_serials`): logic
+// board, battery, disks, internal panel and Wi-Fi MAC. Gives ITADs a parts
+// traceability record, and two scans of the same machine can be compared to
+// see what was swapped in between.
+
+use crate::error::QuickscanError;
+use crate::probe_scheduler::ProbeScheduler;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct ComponentSerials {
+    pub logic_board: Option<String>,
+    pub battery: Option<String>,
+    pub storage: Vec<StorageSerial>,
+    pub display: Option<String>, // internal panel, from its EDID
+    pub wifi_mac: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct StorageSerial {
+    pub model: String,
+    pub serial: String,
+}
+
+#[tauri::command]
+pub async fn get_component_serials(probes: tauri::State<'_, ProbeScheduler>) -> Result<ComponentSerials, QuickscanError> {
+    probes.run(component_serials).await
+}
+
+pub fn component_serials() -> Result<ComponentSerials, QuickscanError> {
+    #[cfg(target_os = "macos")]
+    {
+        Ok(component_serials_macos())
+    }
+    #[cfg(target_os = "windows")]
+    {
+        Ok(component_serials_windows())
+    }
+    #[cfg(target_os = "linux")]
+    {
+        Ok(component_serials_linux())
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        Err(QuickscanError::unsupported("component_serials"))
+    }
+}
+
+// Firmware fills unset serials with placeholders; those identify nothing
+pub fn clean(serial: &str) -> Option<String> {
+    let serial = serial.trim().trim_matches('\0').trim();
+    let placeholder = serial.is_empty()
+        || serial.chars().all(|c| c == '0' || c == ' ' || c == ':')
+        || [
+            "to be filled by o.e.m.",
+            "default string",
+            "not applicable",
+            "none",
+            "unknown",
+            "n/a",
+            "invalid",
+            "system serial number",
+            "chassis serial number",
+        ]
+            .contains(&serial.to_lowercase().as_str());
+    (!placeholder).then(|| serial.to_string())
+}
+
+// Apple silicon keeps the board serial in the device tree; Intel Macs in NVRAM
+#[cfg(target_os = "macos")]
+pub fn logic_board_serial_macos() -> Option<String> {
+    crate::iokit::service_properties("IOPlatformExpertDevice")
+        .and_then(|platform| crate::iokit::string_property(&platform, "mlb-serial-number"))
+        .and_then(|serial| clean(&serial))
+        .or_else(|| {
+            // "4D1EDE05-38C7-4A6A-9CC6-4BCCA8B38C14:MLB\tC02912345ABCDEF1"
+            let mut command = std::process::Command::new("nvram");
+            let output = crate::process::run_cmd(command.arg("4D1EDE05-38C7-4A6A-9CC6-4BCCA8B38C14:MLB")).ok()?;
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            clean(stdout.split('\t').nth(1)?)
+        })
+}
+
+#[cfg(target_os = "macos")]
+fn component_serials_macos() -> ComponentSerials {
+    use crate::iokit;
+    use std::process::Command;
+
+    let logic_board = logic_board_serial_macos();
+
+    let battery = iokit::service_properties("AppleSmartBattery").and_then(|battery| {
+        iokit::string_property(&battery, "Serial")
+            .or_else(|| iokit::string_property(&battery, "BatterySerialNumber"))
+            .or_else(|| battery.get("BatteryData")?.get("Serial")?.as_str().map(|s| s.to_string()))
+            .and_then(|serial| clean(&serial))
+    });
+
+    // {"SPNVMeDataType": [{"_items": [{"_name": "APPLE SSD AP0512Q", "device_serial": "..."}]}]}
+    let drives = |controllers: &[serde_json::Value]| -> Vec<StorageSerial> {
+        controllers
+            .iter()
+            .filter_map(|controller| controller.get("_items")?.as_array())
+            .flatten()
+            .filter_map(|drive| {
+                let field = |key: &str| drive.get(key).and_then(|v| v.as_str());
+                let serial = field("device_serial").and_then(clean)?;
+                let model = field("device_model").or(field("_name")).unwrap_or_default().trim().to_string();
+                Some(StorageSerial { model, serial })
+            })
+            .collect()
+    };
+    let mut storage = drives(&crate::apple_overview::section("SPNVMeDataType").unwrap_or_default());
+    // Only Macs from before NVMe have SATA drives, so the extra run is skipped on the rest
+    if storage.is_empty() {
+        let mut command = Command::new("system_profiler");
+        if let Ok(output) = crate::process::run_cmd(command.args(["SPSerialATADataType", "-json"])) {
+            let json = serde_json::from_slice::<serde_json::Value>(&output.stdout).unwrap_or_default();
+            storage = drives(json.get("SPSerialATADataType").and_then(|v| v.as_array()).map(Vec::as_slice).unwrap_or_default());
+        }
+    }
+
+    // Apple silicon panels report their EDID serial under ProductAttributes;
+    // Intel Macs on the AppleDisplay service
+    let display = iokit::service_properties("AppleCLCD2")
+        .and_then(|panel| {
+            let product = panel.get("DisplayAttributes")?.get("ProductAttributes")?;
+            let serial = product.get("AlphanumericSerialNumber").and_then(|v| v.as_str()).map(|s| s.to_string());
+            serial.or_else(|| product.get("SerialNumber")?.as_i64().map(|n| n.to_string()))
+        })
+        .or_else(|| {
+            let display = iokit::service_properties("AppleDisplay")?;
+            display.get("DisplaySerialNumber")?.as_i64().map(|n| n.to_string())
+        })
+        .and_then(|serial| clean(&serial));
+
+    let wifi_mac = crate::get_wifi_mac_macos().and_then(|mac| clean(&mac));
+    ComponentSerials { logic_board, battery, storage, display, wifi_mac }
+}
+
+#[cfg(target_os = "windows")]
+fn component_serials_windows() -> ComponentSerials {
+    use crate::wmi_provider::{WmiProvider, CIMV2, STANDARD_CIMV2, STORAGE, WMI};
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct SerialNumber {
+        serial_number: Option<String>,
+    }
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct PhysicalDisk {
+        friendly_name: Option<String>,
+        serial_number: Option<String>,
+    }
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct MonitorId {
+        instance_name: String,
+        serial_number_id: Option<Vec<u16>>,
+    }
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct MonitorConnection {
+        instance_name: String,
+        video_output_technology: u32,
+    }
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct NetAdapter {
+        permanent_address: Option<String>,
+    }
+
+    let wmi = WmiProvider::global();
+    let logic_board = wmi
+        .query_first::<SerialNumber>(CIMV2, "SELECT SerialNumber FROM Win32_BaseBoard")
+        .and_then(|board| clean(&board.serial_number?));
+    let battery = wmi
+        .query_first::<SerialNumber>(WMI, "SELECT SerialNumber FROM BatteryStaticData")
+        .and_then(|battery| clean(&battery.serial_number?));
+    let storage = wmi
+        .query::<PhysicalDisk>(STORAGE, "SELECT FriendlyName, SerialNumber FROM MSFT_PhysicalDisk")
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|disk| {
+            Some(StorageSerial {
+                serial: clean(&disk.serial_number?)?,
+                model: disk.friendly_name.unwrap_or_default().trim().to_string(),
+            })
+        })
+        .collect();
+
+    // The internal panel is the one on LVDS, eDP or an internal connector
+    let internal: Vec<String> = wmi
+        .query::<MonitorConnection>(WMI, "SELECT InstanceName, VideoOutputTechnology FROM WmiMonitorConnectionParams")
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|c| matches!(c.video_output_technology, 6 | 11 | 13 | 0x8000_0000))
+        .map(|c| c.instance_name)
+        .collect();
+    let display = wmi
+        .query::<MonitorId>(WMI, "SELECT InstanceName, SerialNumberID FROM WmiMonitorID")
+        .unwrap_or_default()
+        .into_iter()
+        .find(|monitor| internal.contains(&monitor.instance_name))
+        .and_then(|monitor| {
+            // NUL-padded UTF-16 code units
+            let units = monitor.serial_number_id.unwrap_or_default();
+            let len = units.iter().position(|&c| c == 0).unwrap_or(units.len());
+            clean(&String::from_utf16_lossy(&units[..len]))
+        });
+
+    // "A1B2C3D4E5F6" -> "a1:b2:c3:d4:e5:f6", the way macOS and Linux print it
+    let wifi_mac = wmi
+        .query_first::<NetAdapter>(
+            STANDARD_CIMV2,
+            "SELECT PermanentAddress FROM MSFT_NetAdapter WHERE NdisPhysicalMedium = 9 AND ConnectorPresent = TRUE",
+        )
+        .and_then(|adapter| adapter.permanent_address)
+        .filter(|mac| mac.len() == 12)
+        .map(|mac| {
+            mac.as_bytes()
+                .chunks(2)
+                .map(|pair| String::from_utf8_lossy(pair).to_lowercase())
+                .collect::<Vec<_>>()
+                .join(":")
+        })
+        .and_then(|mac| clean(&mac));
+    ComponentSerials { logic_board, battery, storage, display, wifi_mac }
+}
+
+#[cfg(target_os = "linux")]
+fn component_serials_linux() -> ComponentSerials {
+    use std::path::PathBuf;
+
+    let read = |path: PathBuf| std::fs::read_to_string(path).ok().and_then(|s| clean(&s));
+    let entries = |dir: &str| {
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)
+            .map(|entries| entries.flatten().map(|entry| entry.path()).collect())
+            .unwrap_or_default();
+        paths.sort();
+        paths
+    };
+
+    // Readable by root only on most distributions
+    let logic_board = read(PathBuf::from("/sys/class/dmi/id/board_serial"));
+    let battery = entries("/sys/class/power_supply")
+        .into_iter()
+        .filter(|path| std::fs::read_to_string(path.join("type")).is_ok_and(|t| t.trim() == "Battery"))
+        .find_map(|battery| read(battery.join("serial_number")));
+
+    let mut storage = Vec::new();
+    // {"blockdevices": [{"name": "nvme0n1", "model": "...", "serial": "...", "type": "disk"}]}
+    let mut command = std::process::Command::new("lsblk");
+    if let Ok(output) = crate::process::run_cmd(command.args(["-J", "-d", "-o", "NAME,MODEL,SERIAL,TYPE"])) {
+        let json = serde_json::from_slice::<serde_json::Value>(&output.stdout).unwrap_or_default();
+        let devices = json.get("blockdevices").and_then(|v| v.as_array()).into_iter().flatten();
+        for device in devices.filter(|d| d.get("type").and_then(|t| t.as_str()) == Some("disk")) {
+            let field = |key: &str| device.get(key).and_then(|v| v.as_str());
+            if let Some(serial) = field("serial").and_then(clean) {
+                let model = field("model").or(field("name")).unwrap_or_default().trim().to_string();
+                storage.push(StorageSerial { model, serial });
+            }
+        }
+    }
+
+    let display = entries("/sys/class/drm")
+        .into_iter()
+        .filter(|connector| {
+            let name = connector.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            ["-eDP-", "-LVDS-", "-DSI-"].iter().any(|kind| name.contains(kind))
+        })
+        .find_map(|connector| crate::display::edid_serial(&std::fs::read(connector.join("edid")).ok()?))
+        .and_then(|serial| clean(&serial));
+
+    let wifi_mac = entries("/sys/class/net")
+        .into_iter()
+        .filter(|interface| interface.join("wireless").exists() || interface.join("phy80211").exists())
+        .find_map(|interface| read(interface.join("address")));
+    ComponentSerials { logic_board, battery, storage, display, wifi_mac }
+}
@@ -0,0 +1,132 @@
+// Consent for checks that see private things: the camera, the microphone and
+// the system logs. Each needs the operator's yes once per app session; a
+// command run without it fails with ConsentRequired so the UI can ask.
+//
+// For unattended runs a shop pre-approves scopes in `consent.json` in the
+// app config directory:
+//
+// { "preapproved": ["camera", "microphone", "logs"] }
+//
+// Every decision, including a pre-approval being relied on, is appended to
+// audit.jsonl in the app data directory, as is every sealed report (seal.rs).
+
+use crate::error::QuickscanError;
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use tauri::Manager;
+
+pub const CONSENT_FILE: &str = "consent.json";
+pub const AUDIT_LOG_FILE: &str = "audit.jsonl";
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ConsentScope {
+    Camera,     // capturing frames
+    Microphone, // recording sound
+    Logs,       // reading the power and system logs
+}
+
+impl ConsentScope {
+    fn name(self) -> &'static str {
+        match self {
+            ConsentScope::Camera => "camera",
+            ConsentScope::Microphone => "microphone",
+            ConsentScope::Logs => "logs",
+        }
+    }
+}
+
+#[derive(Deserialize, Default)]
+pub struct ConsentConfig {
+    #[serde(default)]
+    pub preapproved: Vec<ConsentScope>,
+}
+
+#[derive(Serialize)]
+struct AuditEntry {
+    at: i64,
+    scope: ConsentScope,
+    decision: &'static str, // "granted", "denied" or "preapproved"
+}
+
+// Scopes allowed so far this session
+static GRANTED: Mutex<Option<HashSet<ConsentScope>>> = Mutex::new(None);
+
+pub fn load_config(path: &Path) -> Result<ConsentConfig, String> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(ConsentConfig::default()),
+        Err(e) => return Err(e.to_string()),
+    };
+    serde_json::from_str(&content).map_err(|e| e.to_string())
+}
+
+// Whether `scope` may run without asking
+#[tauri::command]
+pub fn check_consent(app: tauri::AppHandle, scope: ConsentScope) -> Result<bool, QuickscanError> {
+    match require(&app, scope) {
+        Ok(()) => Ok(true),
+        Err(QuickscanError::ConsentRequired { .. }) => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+// The operator's answer to the prompt. A "no" only holds until the next ask.
+#[tauri::command]
+pub fn record_consent(app: tauri::AppHandle, scope: ConsentScope, granted: bool) -> Result<(), QuickscanError> {
+    audit(&app, scope, if granted { "granted" } else { "denied" })
+        .map_err(|e| QuickscanError::command_failed(AUDIT_LOG_FILE, e))?;
+    let mut session = session();
+    let session = session.get_or_insert_with(HashSet::new);
+    if granted {
+        session.insert(scope);
+    } else {
+        session.remove(&scope);
+    }
+    Ok(())
+}
+
+// Called by the gated commands before they touch anything. Fails closed: a
+// consent file that can't be read or an audit log that can't be written
+// stops the check.
+pub fn require(app: &tauri::AppHandle, scope: ConsentScope) -> Result<(), QuickscanError> {
+    if session().as_ref().is_some_and(|granted| granted.contains(&scope)) {
+        return Ok(());
+    }
+
+    // The lock isn't held while reading the config or writing the log
+    let config_dir = app.path().app_config_dir().map_err(|e| QuickscanError::command_failed(CONSENT_FILE, e))?;
+    let config = load_config(&config_dir.join(CONSENT_FILE)).map_err(|e| QuickscanError::parse(CONSENT_FILE, e))?;
+    if !config.preapproved.contains(&scope) {
+        return Err(QuickscanError::ConsentRequired { scope: scope.name().to_string() });
+    }
+    audit(app, scope, "preapproved").map_err(|e| QuickscanError::command_failed(AUDIT_LOG_FILE, e))?;
+    session().get_or_insert_with(HashSet::new).insert(scope);
+    Ok(())
+}
+
+// The set is still sound if a thread panicked while holding the lock
+fn session() -> MutexGuard<'static, Option<HashSet<ConsentScope>>> {
+    GRANTED.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn audit(app: &tauri::AppHandle, scope: ConsentScope, decision: &'static str) -> Result<(), String> {
+    let data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    append_audit(&data_dir, &AuditEntry { at: Local::now().timestamp(), scope, decision })
+}
+
+// Appends one line to the audit log; sealed reports record themselves here too
+pub fn append_audit(data_dir: &Path, entry: &impl Serialize) -> Result<(), String> {
+    std::fs::create_dir_all(data_dir).map_err(|e| e.to_string())?;
+    let line = serde_json::to_string(entry).map_err(|e| e.to_string())?;
+    let mut log = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(data_dir.join(AUDIT_LOG_FILE))
+        .map_err(|e| e.to_string())?;
+    writeln!(log, "{}", line).map_err(|e| e.to_string())
+}
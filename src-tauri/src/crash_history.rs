@@ -0,0 +1,342 @@
+// The last year of system crashes for `get_crash_history`: kernel panics on
+// macOS, bug checks and fatal hardware errors on Windows. One crash says
+// little, but the same one coming back every few weeks is a failing GPU,
+// memory or SSD that passes every check run once in a shop, so each crash is
+// put down to the component it points at.
+//
+// macOS keeps a report per panic in /Library/Logs/DiagnosticReports (moved to
+// Retired once sent to Apple), named after when it happened. Windows logs a
+// bug check on the boot after it (WER-SystemErrorReporting 1001, with the
+// stop code), and WHEA-Logger 18 for the fatal hardware error behind a 0x124,
+// naming the failing part.
+
+use crate::error::QuickscanError;
+use crate::probe_scheduler::ProbeScheduler;
+use chrono::{Datelike, Months};
+use serde::Serialize;
+
+const HISTORY_MONTHS: u32 = 12;
+// This many in the window is a pattern rather than bad luck
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+const REPEATED_CRASHES: u32 = 3;
+
+#[derive(Serialize)]
+pub struct Crash {
+    pub at: String, // "YYYY-MM-DD HH:MM:SS", local time
+    pub kind: &'static str, // "panic", "bug_check" or "hardware_error"
+    pub component: &'static str, // "gpu", "kernel", "driver", "memory", "cpu", "storage", "pcie" or "unknown"
+    pub detail: Option<String>, // the panic string's first line, the stop code, or the WHEA component
+}
+
+#[derive(Serialize)]
+pub struct CrashMonth {
+    pub month: String, // "YYYY-MM"
+    pub count: u32,
+}
+
+#[derive(Serialize)]
+pub struct CrashHistory {
+    pub months_covered: u32,
+    pub total: u32,
+    pub by_month: Vec<CrashMonth>, // every month in the window, oldest first
+    pub crashes: Vec<Crash>, // newest first
+    pub top_component: Option<&'static str>, // the most common known one
+    pub repeated: bool,
+}
+
+#[tauri::command]
+pub async fn get_crash_history(app: tauri::AppHandle, probes: tauri::State<'_, ProbeScheduler>) -> Result<CrashHistory, QuickscanError> {
+    crate::consent::require(&app, crate::consent::ConsentScope::Logs)?;
+    probes.run(crash_history).await
+}
+
+pub fn crash_history() -> Result<CrashHistory, QuickscanError> {
+    let today = chrono::Local::now().date_naive();
+    let first_month = today.with_day(1).and_then(|first| first.checked_sub_months(Months::new(HISTORY_MONTHS - 1)));
+    let first_month = first_month.unwrap_or(today);
+
+    #[cfg(target_os = "macos")]
+    {
+        Ok(summarize(macos::crashes(first_month), first_month))
+    }
+    #[cfg(target_os = "windows")]
+    {
+        win32::crashes(first_month).map(|crashes| summarize(crashes, first_month))
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        let _ = first_month;
+        Err(QuickscanError::unsupported("crash_history"))
+    }
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn summarize(mut crashes: Vec<Crash>, first_month: chrono::NaiveDate) -> CrashHistory {
+    let since = first_month.format("%Y-%m-%d").to_string();
+    crashes.retain(|crash| crash.at >= since);
+    crashes.sort_by(|a, b| b.at.cmp(&a.at));
+
+    let by_month: Vec<CrashMonth> = (0..HISTORY_MONTHS)
+        .filter_map(|offset| first_month.checked_add_months(Months::new(offset)))
+        .map(|month| {
+            let month = month.format("%Y-%m").to_string();
+            let count = crashes.iter().filter(|crash| crash.at.starts_with(&month)).count() as u32;
+            CrashMonth { month, count }
+        })
+        .collect();
+
+    let mut counts: Vec<(&'static str, usize)> = vec![];
+    for crash in crashes.iter().filter(|crash| crash.component != "unknown") {
+        match counts.iter_mut().find(|(component, _)| *component == crash.component) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((crash.component, 1)),
+        }
+    }
+    // Ties go to the component seen most recently, listed first
+    let top_component = counts.iter().rev().max_by_key(|(_, count)| *count).map(|(component, _)| *component);
+
+    let total = crashes.len() as u32;
+    CrashHistory {
+        months_covered: HISTORY_MONTHS,
+        total,
+        by_month,
+        crashes,
+        top_component,
+        repeated: total >= REPEATED_CRASHES,
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use super::Crash;
+    use chrono::NaiveDate;
+    use std::path::Path;
+
+    const REPORT_DIRS: &[&str] = &["/Library/Logs/DiagnosticReports", "/Library/Logs/DiagnosticReports/Retired"];
+
+    // What a panic string names, matched lowercased and in this order: the
+    // GPU drivers of every generation, the SSD and its file system, memory
+    const MARKERS: &[(&str, &str)] = &[
+        ("agx", "gpu"),
+        ("iogpu", "gpu"),
+        ("amdradeon", "gpu"),
+        ("nvda", "gpu"),
+        ("geforce", "gpu"),
+        ("appleintelframebuffer", "gpu"),
+        ("applegpuwrangler", "gpu"),
+        ("appleans", "storage"),
+        ("nvme", "storage"),
+        ("apfs", "storage"),
+        ("dram", "memory"),
+        ("ecc error", "memory"),
+        ("machine check", "cpu"),
+    ];
+
+    // "Kernel-2019-05-16-114523_MacBook-Pro.panic", "panic-full-2024-01-05-101010.000.ips"
+    fn report_time(name: &str) -> Option<String> {
+        let bytes = name.as_bytes();
+        (0..bytes.len().saturating_sub(16)).find_map(|start| {
+            let candidate = name.get(start..start + 17)?;
+            let shape = candidate.bytes().enumerate().all(|(i, b)| match i {
+                4 | 7 | 10 => b == b'-',
+                _ => b.is_ascii_digit(),
+            });
+            if !shape {
+                return None;
+            }
+            NaiveDate::parse_from_str(&candidate[..10], "%Y-%m-%d").ok()?;
+            Some(format!("{} {}:{}:{}", &candidate[..10], &candidate[11..13], &candidate[13..15], &candidate[15..17]))
+        })
+    }
+
+    fn is_panic_report(name: &str) -> bool {
+        name.ends_with(".panic") || (name.starts_with("panic") && name.ends_with(".ips"))
+    }
+
+    // A kext outside Apple's in "Kernel Extensions in backtrace:" is what was
+    // running when the kernel gave up
+    fn third_party_kext(text: &str) -> Option<String> {
+        let backtrace = &text[text.find("Kernel Extensions in backtrace")?..];
+        backtrace
+            .lines()
+            .skip(1)
+            .take_while(|line| !line.trim().is_empty())
+            .map(str::trim)
+            .find(|line| line.starts_with("com.") && !line.starts_with("com.apple."))
+            .map(|line| line.split(['(', '[']).next().unwrap_or(line).to_string())
+    }
+
+    fn classify(text: &str) -> (&'static str, Option<String>) {
+        // .ips reports are JSON with the panic text in an escaped string
+        let text = text.replace("\\n", "\n").replace("\\\"", "\"");
+        let detail = text.find("panic(").map(|start| {
+            let line = text[start..].lines().next().unwrap_or_default().trim_end_matches(['"', ',']);
+            line.chars().take(160).collect::<String>()
+        });
+        let lower = detail.as_deref().unwrap_or(&text).to_lowercase();
+        if let Some((_, component)) = MARKERS.iter().find(|(marker, _)| lower.contains(marker)) {
+            return (component, detail);
+        }
+        if let Some(kext) = third_party_kext(&text) {
+            return ("driver", detail.or(Some(kext)));
+        }
+        ("kernel", detail)
+    }
+
+    pub fn crashes(first_month: NaiveDate) -> Vec<Crash> {
+        let since = first_month.format("%Y-%m-%d").to_string();
+        let mut crashes: Vec<Crash> = vec![];
+        for dir in REPORT_DIRS {
+            let Ok(entries) = std::fs::read_dir(dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                if !is_panic_report(&name) {
+                    continue;
+                }
+                let Some(at) = report_time(&name).filter(|at| *at >= since) else {
+                    continue;
+                };
+                // Reports are readable by admins only; one that isn't still counts
+                let (component, detail) = match std::fs::read_to_string(Path::new(dir).join(&name)) {
+                    Ok(text) => classify(&text),
+                    Err(_) => ("unknown", None),
+                };
+                // A report that was sent sits in Retired and sometimes still in the folder above
+                if crashes.iter().any(|crash| crash.at == at) {
+                    continue;
+                }
+                crashes.push(Crash { at, kind: "panic", component, detail });
+            }
+        }
+        crashes
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod win32 {
+    use super::Crash;
+    use crate::error::QuickscanError;
+    use crate::wmi_provider::{format_datetime, wmi_datetime, WmiProvider, CIMV2};
+    use chrono::{NaiveDate, NaiveDateTime};
+    use serde::Deserialize;
+
+    // WHEA records the error as the system comes back up, around when the
+    // bug check is logged
+    const WHEA_MATCH_SECS: i64 = 600;
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct LogEvent {
+        message: Option<String>,
+        insertion_strings: Option<Vec<String>>,
+        time_generated: Option<String>,
+    }
+
+    // Stop codes by what they usually come down to
+    fn bug_check_component(code: u32) -> &'static str {
+        match code {
+            0x116 | 0x117 | 0x119 | 0x10E => "gpu", // VIDEO_TDR_FAILURE, VIDEO_SCHEDULER_INTERNAL_ERROR…
+            0x124 => "cpu", // WHEA_UNCORRECTABLE_ERROR, unless WHEA says otherwise
+            0x1A | 0x50 => "memory", // MEMORY_MANAGEMENT, PAGE_FAULT_IN_NONPAGED_AREA
+            0x7A | 0x77 | 0xF4 | 0x7B | 0xED => "storage", // KERNEL_DATA_INPAGE_ERROR, UNMOUNTABLE_BOOT_VOLUME…
+            0xD1 | 0x9F | 0xC4 | 0xC9 | 0x7E | 0x3B => "driver", // DRIVER_IRQL_NOT_LESS_OR_EQUAL…
+            _ => "kernel",
+        }
+    }
+
+    // "Component: Processor Core", "Component: PCI Express Root Port", "Component: Memory"
+    fn whea_component(message: &str) -> Option<(&'static str, String)> {
+        let value = message.lines().find_map(|line| line.trim().strip_prefix("Component:"))?.trim();
+        let component = if value.starts_with("Processor") {
+            "cpu"
+        } else if value.starts_with("Memory") {
+            "memory"
+        } else if value.starts_with("PCI Express") {
+            "pcie"
+        } else {
+            "unknown"
+        };
+        Some((component, value.to_string()))
+    }
+
+    fn seconds(at: &str) -> Option<i64> {
+        NaiveDateTime::parse_from_str(at, "%Y-%m-%d %H:%M:%S").ok().map(|time| time.and_utc().timestamp())
+    }
+
+    fn events(wmi: &WmiProvider, after: &str, filter: &str) -> Result<Vec<LogEvent>, QuickscanError> {
+        wmi.query::<LogEvent>(
+            CIMV2,
+            &format!(
+                "SELECT Message, InsertionStrings, TimeGenerated FROM Win32_NTLogEvent WHERE Logfile = 'System' AND {} \
+                 AND TimeGenerated >= '{}'",
+                filter, after
+            ),
+        )
+    }
+
+    pub fn crashes(first_month: NaiveDate) -> Result<Vec<Crash>, QuickscanError> {
+        let wmi = WmiProvider::global();
+        let since = first_month.and_hms_opt(0, 0, 0).map(|time| time.and_utc().timestamp().max(0) as u64).unwrap_or(0);
+        let after = wmi_datetime(since);
+
+        // "0x00000116 (0xffffc08f..., 0xfffff806..., 0x..., 0x...)" leads the
+        // insertion strings; Windows 7 logs it under the BugCheck source
+        let bug_checks = events(
+            wmi,
+            &after,
+            "(SourceName = 'Microsoft-Windows-WER-SystemErrorReporting' OR SourceName = 'BugCheck') AND EventCode = 1001",
+        )?;
+        let mut whea: Vec<(i64, &'static str, String, String)> = events(
+            wmi,
+            &after,
+            "SourceName = 'Microsoft-Windows-WHEA-Logger' AND EventCode = 18",
+        )
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|event| {
+            let at = format_datetime(event.time_generated.as_deref()?)?;
+            let (component, detail) = whea_component(event.message.as_deref()?)?;
+            Some((seconds(&at)?, component, detail, at))
+        })
+        .collect();
+
+        let mut crashes: Vec<Crash> = vec![];
+        for event in bug_checks {
+            let Some(at) = event.time_generated.as_deref().and_then(format_datetime) else {
+                continue;
+            };
+            let text = event
+                .insertion_strings
+                .as_ref()
+                .and_then(|strings| strings.first().cloned())
+                .or(event.message)
+                .unwrap_or_default();
+            let code = text.find("0x").and_then(|start| {
+                let hex: String = text[start + 2..].chars().take_while(char::is_ascii_hexdigit).collect();
+                u32::from_str_radix(&hex, 16).ok()
+            });
+            let mut component = code.map_or("unknown", bug_check_component);
+            // The fatal error WHEA logged alongside says which part it was
+            if code == Some(0x124) {
+                let when = seconds(&at).unwrap_or(0);
+                if let Some(index) = whea.iter().position(|(time, ..)| (time - when).abs() <= WHEA_MATCH_SECS) {
+                    component = whea.remove(index).1;
+                }
+            }
+            if crashes.iter().any(|crash| crash.at == at) {
+                continue;
+            }
+            crashes.push(Crash { at, kind: "bug_check", component, detail: code.map(|code| format!("0x{:08X}", code)) });
+        }
+        // Fatal errors without a bug check: the dump couldn't be written
+        crashes.extend(whea.into_iter().map(|(_, component, detail, at)| Crash {
+            at,
+            kind: "hardware_error",
+            component,
+            detail: Some(detail),
+        }));
+        Ok(crashes)
+    }
+}
@@ -0,0 +1,400 @@
+// What a previous owner left behind, for `check_data_remnants`: other users'
+// home folders, signed-in iCloud and Microsoft accounts, saved Wi-Fi
+// networks, browser profiles, and a join to a company domain or Entra ID.
+// Buyers check it to confirm the machine was actually wiped; refurbishers
+// keep it as proof before they certify one.
+//
+// The user running the scan is the tester, so their own home folder and
+// browser profiles don't count, and neither does the network the machine is
+// on right now. Looking inside other users' folders needs administrator
+// rights, so their browser profiles and accounts are best effort.
+
+use crate::error::QuickscanError;
+use crate::probe_scheduler::ProbeScheduler;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+#[derive(Serialize, Default)]
+pub struct DataRemnants {
+    pub other_users: Vec<String>, // home folders besides the current user's
+    pub accounts: Vec<CloudAccount>,
+    pub saved_networks: Vec<String>, // SSIDs, the connected one left out
+    pub browser_profiles: Vec<BrowserProfile>,
+    pub directory_join: Option<DirectoryJoin>,
+    pub wiped: bool, // none of the above
+}
+
+#[derive(Serialize)]
+pub struct CloudAccount {
+    pub service: &'static str, // "icloud" or "microsoft"
+    pub account: String, // "someone@icloud.com"
+}
+
+#[derive(Serialize)]
+pub struct BrowserProfile {
+    pub browser: &'static str,
+    pub user: String,
+    pub profile: String, // "Default", "Profile 2", "x1y2z3.default-release"
+}
+
+#[derive(Serialize)]
+pub struct DirectoryJoin {
+    pub kind: &'static str, // "domain" (Active Directory, Kerberos realm) or "entra_id"
+    pub name: String,
+}
+
+// How a browser keeps its profiles
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+enum Layout {
+    Chromium, // "Default" and "Profile N" folders
+    Firefox, // one folder per profile
+    #[cfg(target_os = "macos")]
+    Safari, // a single profile, there once History.db is
+}
+
+// Relative to the home folder
+#[cfg(target_os = "macos")]
+const BROWSERS: &[(&str, &str, Layout)] = &[
+    ("Safari", "Library/Safari", Layout::Safari),
+    ("Chrome", "Library/Application Support/Google/Chrome", Layout::Chromium),
+    ("Edge", "Library/Application Support/Microsoft Edge", Layout::Chromium),
+    ("Brave", "Library/Application Support/BraveSoftware/Brave-Browser", Layout::Chromium),
+    ("Firefox", "Library/Application Support/Firefox/Profiles", Layout::Firefox),
+];
+#[cfg(target_os = "windows")]
+const BROWSERS: &[(&str, &str, Layout)] = &[
+    ("Edge", "AppData\\Local\\Microsoft\\Edge\\User Data", Layout::Chromium),
+    ("Chrome", "AppData\\Local\\Google\\Chrome\\User Data", Layout::Chromium),
+    ("Brave", "AppData\\Local\\BraveSoftware\\Brave-Browser\\User Data", Layout::Chromium),
+    ("Firefox", "AppData\\Roaming\\Mozilla\\Firefox\\Profiles", Layout::Firefox),
+];
+#[cfg(target_os = "linux")]
+const BROWSERS: &[(&str, &str, Layout)] = &[
+    ("Firefox", ".mozilla/firefox", Layout::Firefox),
+    ("Chrome", ".config/google-chrome", Layout::Chromium),
+    ("Chromium", ".config/chromium", Layout::Chromium),
+    ("Edge", ".config/microsoft-edge", Layout::Chromium),
+    ("Brave", ".config/BraveSoftware/Brave-Browser", Layout::Chromium),
+];
+
+// Folders under the homes root that belong to no one
+const SYSTEM_HOMES: &[&str] = &["Shared", "Guest", "Public", "Default", "Default User", "All Users", "defaultuser0", "lost+found"];
+
+#[tauri::command]
+pub async fn check_data_remnants(probes: tauri::State<'_, ProbeScheduler>) -> Result<DataRemnants, QuickscanError> {
+    probes.run(data_remnants).await
+}
+
+pub fn data_remnants() -> Result<DataRemnants, QuickscanError> {
+    #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+    {
+        let others = other_homes();
+        let mut remnants = DataRemnants {
+            other_users: others.iter().map(|(user, _)| user.clone()).collect(),
+            browser_profiles: others.iter().flat_map(|(user, home)| browser_profiles(user, home)).collect(),
+            ..Default::default()
+        };
+        #[cfg(target_os = "macos")]
+        macos::fill(&mut remnants, &others);
+        #[cfg(target_os = "windows")]
+        win32::fill(&mut remnants);
+        #[cfg(target_os = "linux")]
+        linux::fill(&mut remnants);
+
+        let connected = crate::wifi::wifi_info().ok().and_then(|wifi| wifi.connection?.ssid);
+        remnants.saved_networks.retain(|ssid| Some(ssid) != connected.as_ref());
+        remnants.saved_networks.dedup();
+        remnants.wiped = remnants.other_users.is_empty()
+            && remnants.accounts.is_empty()
+            && remnants.saved_networks.is_empty()
+            && remnants.browser_profiles.is_empty()
+            && remnants.directory_join.is_none();
+        Ok(remnants)
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        Err(QuickscanError::unsupported("data_remnants"))
+    }
+}
+
+// What the indicator in `check_refurbishment` lists: accounts and users
+// first, since those are what a buyer has to get removed
+pub fn evidence(remnants: &DataRemnants) -> Vec<String> {
+    let mut evidence: Vec<String> = remnants.other_users.clone();
+    evidence.extend(remnants.accounts.iter().map(|account| account.account.clone()));
+    evidence.extend(remnants.directory_join.iter().map(|join| join.name.clone()));
+    evidence.extend(remnants.saved_networks.iter().cloned());
+    for profile in &remnants.browser_profiles {
+        let entry = format!("{} ({})", profile.browser, profile.user);
+        if !evidence.contains(&entry) {
+            evidence.push(entry);
+        }
+    }
+    evidence
+}
+
+// Accounts, users or a directory join mean the machine was never wiped;
+// leftover networks or browser data alone may just be a careless wipe
+pub fn severity(remnants: &DataRemnants) -> &'static str {
+    if !remnants.other_users.is_empty() || !remnants.accounts.is_empty() || remnants.directory_join.is_some() {
+        "warning"
+    } else {
+        "info"
+    }
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+fn homes_root() -> PathBuf {
+    #[cfg(target_os = "macos")]
+    {
+        PathBuf::from("/Users")
+    }
+    #[cfg(target_os = "windows")]
+    {
+        let system_drive = std::env::var("SystemDrive").unwrap_or_else(|_| "C:".to_string());
+        PathBuf::from(format!("{}\\Users", system_drive))
+    }
+    #[cfg(target_os = "linux")]
+    {
+        PathBuf::from("/home")
+    }
+}
+
+// (user, home) for every home folder but the current user's, by name
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+fn other_homes() -> Vec<(String, PathBuf)> {
+    let current = std::env::var_os(if cfg!(target_os = "windows") { "USERPROFILE" } else { "HOME" }).map(PathBuf::from);
+    let Ok(entries) = std::fs::read_dir(homes_root()) else {
+        return vec![];
+    };
+    let mut homes: Vec<(String, PathBuf)> = entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_dir()))
+        .map(|entry| (entry.file_name().to_string_lossy().to_string(), entry.path()))
+        .filter(|(user, home)| {
+            !user.starts_with('.') && !SYSTEM_HOMES.contains(&user.as_str()) && Some(home) != current.as_ref()
+        })
+        .collect();
+    homes.sort();
+    homes
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+fn browser_profiles(user: &str, home: &Path) -> Vec<BrowserProfile> {
+    let mut profiles = vec![];
+    for (browser, path, layout) in BROWSERS {
+        let dir = home.join(path);
+        let folders: Vec<String> = match layout {
+            #[cfg(target_os = "macos")]
+            Layout::Safari => {
+                if dir.join("History.db").exists() {
+                    profiles.push(BrowserProfile { browser, user: user.to_string(), profile: "Default".to_string() });
+                }
+                continue;
+            }
+            _ => std::fs::read_dir(&dir)
+                .map(|entries| {
+                    entries
+                        .flatten()
+                        .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_dir()))
+                        .map(|entry| entry.file_name().to_string_lossy().to_string())
+                        .collect()
+                })
+                .unwrap_or_default(),
+        };
+        let mut names: Vec<String> = folders
+            .into_iter()
+            .filter(|name| match layout {
+                Layout::Chromium => name == "Default" || name.starts_with("Profile "),
+                // Linux keeps crash reports next to the "<id>.<name>" profiles
+                _ => name.contains('.'),
+            })
+            .collect();
+        names.sort();
+        profiles.extend(names.into_iter().map(|profile| BrowserProfile { browser, user: user.to_string(), profile }));
+    }
+    profiles
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use super::{CloudAccount, DataRemnants, DirectoryJoin};
+    use crate::process;
+    use std::path::PathBuf;
+    use std::process::Command;
+
+    // `AccountID = "someone@icloud.com";` in each home's MobileMeAccounts
+    fn icloud_accounts(home: &std::path::Path) -> Vec<String> {
+        let plist = home.join("Library/Preferences/MobileMeAccounts");
+        let mut command = Command::new("defaults");
+        let Ok(output) = process::run_cmd(command.arg("read").arg(&plist).arg("Accounts")) else {
+            return vec![];
+        };
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| {
+                let (name, value) = line.trim().split_once(" = ")?;
+                (name == "AccountID").then(|| value.trim_end_matches(';').trim_matches('"').to_string())
+            })
+            .filter(|account| !account.is_empty())
+            .collect()
+    }
+
+    // "Hardware Port: Wi-Fi\nDevice: en0"
+    fn wifi_device() -> Option<String> {
+        let output = process::run_cmd(Command::new("networksetup").arg("-listallhardwareports")).ok()?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let mut lines = stdout.lines();
+        lines.find(|line| line.trim() == "Hardware Port: Wi-Fi")?;
+        lines.next()?.strip_prefix("Device:").map(|device| device.trim().to_string())
+    }
+
+    pub fn fill(remnants: &mut DataRemnants, others: &[(String, PathBuf)]) {
+        let current = std::env::var_os("HOME").map(PathBuf::from);
+        for home in current.iter().chain(others.iter().map(|(_, home)| home)) {
+            for account in icloud_accounts(home) {
+                remnants.accounts.push(CloudAccount { service: "icloud", account });
+            }
+        }
+
+        // "Preferred networks on en0:\n\tShop\n\tHome"
+        if let Some(device) = wifi_device() {
+            if let Ok(output) = process::run_cmd(Command::new("networksetup").args(["-listpreferredwirelessnetworks", &device])) {
+                remnants.saved_networks = String::from_utf8_lossy(&output.stdout)
+                    .lines()
+                    .skip(1)
+                    .map(|line| line.trim().to_string())
+                    .filter(|ssid| !ssid.is_empty())
+                    .collect();
+            }
+        }
+
+        // "Active Directory Domain          = corp.example.com"
+        if let Ok(output) = process::run_cmd(Command::new("dsconfigad").arg("-show")) {
+            remnants.directory_join = String::from_utf8_lossy(&output.stdout).lines().find_map(|line| {
+                let (name, value) = line.split_once('=')?;
+                (name.trim() == "Active Directory Domain" && !value.trim().is_empty())
+                    .then(|| DirectoryJoin { kind: "domain", name: value.trim().to_string() })
+            });
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod win32 {
+    use super::{CloudAccount, DataRemnants, DirectoryJoin};
+    use crate::wmi_provider::{registry_string, registry_subkeys};
+    use std::ffi::c_void;
+    use windows::core::PWSTR;
+    use windows::Win32::Foundation::{ERROR_SUCCESS, HANDLE};
+    use windows::Win32::NetworkManagement::NetManagement::{NetApiBufferFree, NetGetJoinInformation, NetSetupDomainName, NETSETUP_JOIN_STATUS};
+    use windows::Win32::NetworkManagement::WiFi::{
+        WlanCloseHandle, WlanEnumInterfaces, WlanFreeMemory, WlanGetProfileList, WlanOpenHandle, WLAN_INTERFACE_INFO_LIST,
+        WLAN_PROFILE_INFO_LIST,
+    };
+
+    // Every account that has signed in to Windows, keyed by SID
+    const IDENTITY_CACHE: &str = "SOFTWARE\\Microsoft\\IdentityStore\\Cache";
+    const JOIN_INFO: &str = "SYSTEM\\CurrentControlSet\\Control\\CloudDomainJoin\\JoinInfo";
+    const TENANT_INFO: &str = "SYSTEM\\CurrentControlSet\\Control\\CloudDomainJoin\\TenantInfo";
+
+    fn wide(buffer: &[u16]) -> String {
+        let length = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+        String::from_utf16_lossy(&buffer[..length])
+    }
+
+    // Profiles of every Wi-Fi interface, for all users
+    fn saved_networks() -> Vec<String> {
+        let mut networks = vec![];
+        unsafe {
+            let mut version = 0u32;
+            let mut handle = HANDLE::default();
+            if WlanOpenHandle(2, None, &mut version, &mut handle) != ERROR_SUCCESS.0 {
+                return networks;
+            }
+            let mut interfaces: *mut WLAN_INTERFACE_INFO_LIST = std::ptr::null_mut();
+            if WlanEnumInterfaces(handle, None, &mut interfaces) == ERROR_SUCCESS.0 && !interfaces.is_null() {
+                let list = std::slice::from_raw_parts((*interfaces).InterfaceInfo.as_ptr(), (*interfaces).dwNumberOfItems as usize);
+                for interface in list {
+                    let mut profiles: *mut WLAN_PROFILE_INFO_LIST = std::ptr::null_mut();
+                    if WlanGetProfileList(handle, &interface.InterfaceGuid, None, &mut profiles) == ERROR_SUCCESS.0 && !profiles.is_null() {
+                        let entries = std::slice::from_raw_parts((*profiles).ProfileInfo.as_ptr(), (*profiles).dwNumberOfItems as usize);
+                        networks.extend(entries.iter().map(|profile| wide(&profile.strProfileName)));
+                        WlanFreeMemory(profiles as *const c_void);
+                    }
+                }
+                WlanFreeMemory(interfaces as *const c_void);
+            }
+            WlanCloseHandle(handle, None);
+        }
+        networks
+    }
+
+    // The Active Directory domain, when the machine is joined to one
+    fn domain() -> Option<String> {
+        let mut name = PWSTR::null();
+        let mut status = NETSETUP_JOIN_STATUS::default();
+        unsafe {
+            if NetGetJoinInformation(None, &mut name, &mut status) != ERROR_SUCCESS.0 {
+                return None;
+            }
+            let domain = name.to_string().ok();
+            NetApiBufferFree(Some(name.0 as *const c_void));
+            domain.filter(|_| status == NetSetupDomainName)
+        }
+    }
+
+    pub fn fill(remnants: &mut DataRemnants) {
+        // Local accounts have no cache entry; Microsoft and work accounts sign in with an address
+        for sid in registry_subkeys(IDENTITY_CACHE) {
+            let cache = format!("{}\\{}\\IdentityCache", IDENTITY_CACHE, sid);
+            for identity in registry_subkeys(&cache) {
+                if let Some(account) = registry_string(&format!("{}\\{}", cache, identity), "UserName").filter(|name| name.contains('@')) {
+                    if !remnants.accounts.iter().any(|known| known.account == account) {
+                        remnants.accounts.push(CloudAccount { service: "microsoft", account });
+                    }
+                }
+            }
+        }
+
+        remnants.saved_networks = saved_networks();
+
+        remnants.directory_join = domain().map(|name| DirectoryJoin { kind: "domain", name }).or_else(|| {
+            let join = registry_subkeys(JOIN_INFO).into_iter().next()?;
+            let tenant = registry_subkeys(TENANT_INFO)
+                .into_iter()
+                .find_map(|tenant| registry_string(&format!("{}\\{}", TENANT_INFO, tenant), "DisplayName"))
+                .or_else(|| registry_string(&format!("{}\\{}", JOIN_INFO, join), "TenantId"))?;
+            Some(DirectoryJoin { kind: "entra_id", name: tenant })
+        });
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::{DataRemnants, DirectoryJoin};
+    use crate::process;
+    use std::process::Command;
+
+    pub fn fill(remnants: &mut DataRemnants) {
+        // "Shop:802-11-wireless"; colons in names come escaped as "\:"
+        if let Ok(output) = process::run_cmd(Command::new("nmcli").args(["-t", "-f", "NAME,TYPE", "connection", "show"])) {
+            remnants.saved_networks = String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .filter_map(|line| {
+                    let (name, kind) = line.rsplit_once(':')?;
+                    (kind == "802-11-wireless").then(|| name.replace("\\:", ":"))
+                })
+                .collect();
+        }
+
+        // One realm per line, "corp.example.com"
+        if let Ok(output) = process::run_cmd(Command::new("realm").args(["list", "--name-only"])) {
+            remnants.directory_join = String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .map(str::trim)
+                .find(|realm| !realm.is_empty())
+                .map(|realm| DirectoryJoin { kind: "domain", name: realm.to_string() });
+        }
+    }
+}
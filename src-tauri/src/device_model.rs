@@ -0,0 +1,191 @@
+// Hardware model identifier -> marketing name (`get_device_model`), so a
+// listing's "MacBook Pro 2021 M1 Pro" can be checked against what the machine
+// reports about itself.
+
+use crate::error::QuickscanError;
+use crate::probe_scheduler::ProbeScheduler;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct DeviceModel {
+    pub identifier: String, // hw.model on macOS, the firmware model string elsewhere
+    pub manufacturer: Option<String>,
+    pub marketing_name: Option<String>,
+    pub release_year: Option<u16>,
+    pub spec_options: Vec<String>, // configurations the model originally shipped in
+}
+
+struct ModelSpec {
+    name: &'static str,
+    year: u16,
+    options: &'static [&'static str],
+}
+
+const fn spec(name: &'static str, year: u16, options: &'static [&'static str]) -> ModelSpec {
+    ModelSpec { name, year, options }
+}
+
+// Keyed by Mac model identifier, or by the model string Windows and Linux read
+// from the firmware. Several years can share one identifier.
+const MODELS: &[(&str, ModelSpec)] = &[
+    // MacBook
+    ("MacBook9,1", spec("MacBook (Retina, 12-inch, Early 2016)", 2016, &["Core m3 1.1GHz", "Core m5 1.2GHz", "Core m7 1.3GHz", "8GB"])),
+    ("MacBook10,1", spec("MacBook (Retina, 12-inch, 2017)", 2017, &["Core m3 1.2GHz", "Core i5 1.3GHz", "Core i7 1.4GHz", "8GB", "16GB"])),
+    // MacBook Air
+    ("MacBookAir7,2", spec("MacBook Air (13-inch, Early 2015 / 2017)", 2015, &["Core i5 1.6GHz", "Core i5 1.8GHz", "Core i7 2.2GHz", "8GB"])),
+    ("MacBookAir8,1", spec("MacBook Air (Retina, 13-inch, 2018)", 2018, &["Core i5 1.6GHz", "8GB", "16GB"])),
+    ("MacBookAir8,2", spec("MacBook Air (Retina, 13-inch, 2019)", 2019, &["Core i5 1.6GHz", "8GB", "16GB"])),
+    ("MacBookAir9,1", spec("MacBook Air (Retina, 13-inch, 2020)", 2020, &["Core i3 1.1GHz", "Core i5 1.1GHz", "Core i7 1.2GHz", "8GB", "16GB"])),
+    ("MacBookAir10,1", spec("MacBook Air (M1, 2020)", 2020, &["M1 7-core GPU", "M1 8-core GPU", "8GB", "16GB"])),
+    ("Mac14,2", spec("MacBook Air (M2, 2022)", 2022, &["M2 8-core GPU", "M2 10-core GPU", "8GB", "16GB", "24GB"])),
+    ("Mac14,15", spec("MacBook Air (15-inch, M2, 2023)", 2023, &["M2 10-core GPU", "8GB", "16GB", "24GB"])),
+    ("Mac15,12", spec("MacBook Air (13-inch, M3, 2024)", 2024, &["M3 8-core GPU", "M3 10-core GPU", "8GB", "16GB", "24GB"])),
+    ("Mac15,13", spec("MacBook Air (15-inch, M3, 2024)", 2024, &["M3 10-core GPU", "8GB", "16GB", "24GB"])),
+    // 13" MacBook Pro
+    ("MacBookPro13,1", spec("MacBook Pro (13-inch, 2016, Two Thunderbolt 3 ports)", 2016, &["Core i5 2.0GHz", "Core i7 2.4GHz", "8GB", "16GB"])),
+    ("MacBookPro13,2", spec("MacBook Pro (13-inch, 2016, Four Thunderbolt 3 ports)", 2016, &["Core i5 2.9GHz", "Core i5 3.1GHz", "Core i7 3.3GHz", "8GB", "16GB"])),
+    ("MacBookPro14,1", spec("MacBook Pro (13-inch, 2017, Two Thunderbolt 3 ports)", 2017, &["Core i5 2.3GHz", "Core i7 2.5GHz", "8GB", "16GB"])),
+    ("MacBookPro14,2", spec("MacBook Pro (13-inch, 2017, Four Thunderbolt 3 ports)", 2017, &["Core i5 3.1GHz", "Core i5 3.3GHz", "Core i7 3.5GHz", "8GB", "16GB"])),
+    ("MacBookPro15,2", spec("MacBook Pro (13-inch, 2018 / 2019, Four Thunderbolt 3 ports)", 2018, &["Core i5 2.3GHz", "Core i5 2.4GHz", "Core i7 2.7GHz", "Core i7 2.8GHz", "8GB", "16GB"])),
+    ("MacBookPro15,4", spec("MacBook Pro (13-inch, 2019, Two Thunderbolt 3 ports)", 2019, &["Core i5 1.4GHz", "Core i7 1.7GHz", "8GB", "16GB"])),
+    ("MacBookPro16,2", spec("MacBook Pro (13-inch, 2020, Four Thunderbolt 3 ports)", 2020, &["Core i5 2.0GHz", "Core i7 2.3GHz", "16GB", "32GB"])),
+    ("MacBookPro16,3", spec("MacBook Pro (13-inch, 2020, Two Thunderbolt 3 ports)", 2020, &["Core i5 1.4GHz", "Core i7 1.7GHz", "8GB", "16GB"])),
+    ("MacBookPro17,1", spec("MacBook Pro (13-inch, M1, 2020)", 2020, &["M1 8-core GPU", "8GB", "16GB"])),
+    ("Mac14,7", spec("MacBook Pro (13-inch, M2, 2022)", 2022, &["M2 10-core GPU", "8GB", "16GB", "24GB"])),
+    // 15" / 16" MacBook Pro
+    ("MacBookPro13,3", spec("MacBook Pro (15-inch, 2016)", 2016, &["Core i7 2.6GHz", "Core i7 2.7GHz", "Core i7 2.9GHz", "16GB", "Radeon Pro 450", "Radeon Pro 455", "Radeon Pro 460"])),
+    ("MacBookPro14,3", spec("MacBook Pro (15-inch, 2017)", 2017, &["Core i7 2.8GHz", "Core i7 2.9GHz", "Core i7 3.1GHz", "16GB", "Radeon Pro 555", "Radeon Pro 560"])),
+    ("MacBookPro15,1", spec("MacBook Pro (15-inch, 2018 / 2019)", 2018, &["Core i7 2.2GHz", "Core i7 2.6GHz", "Core i9 2.3GHz", "Core i9 2.4GHz", "16GB", "32GB", "Radeon Pro 555X", "Radeon Pro 560X"])),
+    ("MacBookPro15,3", spec("MacBook Pro (15-inch, 2018 / 2019, Radeon Pro Vega)", 2018, &["Core i7 2.6GHz", "Core i9 2.9GHz", "16GB", "32GB", "Radeon Pro Vega 16", "Radeon Pro Vega 20"])),
+    ("MacBookPro16,1", spec("MacBook Pro (16-inch, 2019)", 2019, &["Core i7 2.6GHz", "Core i9 2.3GHz", "Core i9 2.4GHz", "16GB", "32GB", "64GB", "Radeon Pro 5300M", "Radeon Pro 5500M"])),
+    ("MacBookPro16,4", spec("MacBook Pro (16-inch, 2019, Radeon Pro 5600M)", 2019, &["Core i7 2.6GHz", "Core i9 2.3GHz", "Core i9 2.4GHz", "16GB", "32GB", "64GB", "Radeon Pro 5600M"])),
+    // 14" / 16" MacBook Pro (Apple silicon)
+    ("MacBookPro18,1", spec("MacBook Pro (16-inch, 2021)", 2021, &["M1 Pro 10-core CPU / 16-core GPU", "16GB", "32GB"])),
+    ("MacBookPro18,2", spec("MacBook Pro (16-inch, 2021)", 2021, &["M1 Max 10-core CPU / 24-core GPU", "M1 Max 10-core CPU / 32-core GPU", "32GB", "64GB"])),
+    ("MacBookPro18,3", spec("MacBook Pro (14-inch, 2021)", 2021, &["M1 Pro 8-core CPU / 14-core GPU", "M1 Pro 10-core CPU / 14-core GPU", "M1 Pro 10-core CPU / 16-core GPU", "16GB", "32GB"])),
+    ("MacBookPro18,4", spec("MacBook Pro (14-inch, 2021)", 2021, &["M1 Max 10-core CPU / 24-core GPU", "M1 Max 10-core CPU / 32-core GPU", "32GB", "64GB"])),
+    ("Mac14,5", spec("MacBook Pro (14-inch, 2023)", 2023, &["M2 Max 12-core CPU / 30-core GPU", "M2 Max 12-core CPU / 38-core GPU", "32GB", "64GB", "96GB"])),
+    ("Mac14,9", spec("MacBook Pro (14-inch, 2023)", 2023, &["M2 Pro 10-core CPU / 16-core GPU", "M2 Pro 12-core CPU / 19-core GPU", "16GB", "32GB"])),
+    ("Mac14,6", spec("MacBook Pro (16-inch, 2023)", 2023, &["M2 Max 12-core CPU / 30-core GPU", "M2 Max 12-core CPU / 38-core GPU", "32GB", "64GB", "96GB"])),
+    ("Mac14,10", spec("MacBook Pro (16-inch, 2023)", 2023, &["M2 Pro 12-core CPU / 19-core GPU", "16GB", "32GB"])),
+    ("Mac15,3", spec("MacBook Pro (14-inch, M3, Nov 2023)", 2023, &["M3 8-core CPU / 10-core GPU", "8GB", "16GB", "24GB"])),
+    // Desktops
+    ("Macmini8,1", spec("Mac mini (2018)", 2018, &["Core i3 3.6GHz", "Core i5 3.0GHz", "Core i7 3.2GHz", "8GB", "16GB", "32GB", "64GB"])),
+    ("Macmini9,1", spec("Mac mini (M1, 2020)", 2020, &["M1 8-core GPU", "8GB", "16GB"])),
+    ("Mac14,3", spec("Mac mini (2023)", 2023, &["M2 10-core GPU", "8GB", "16GB", "24GB"])),
+    ("Mac14,12", spec("Mac mini (2023)", 2023, &["M2 Pro 10-core CPU / 16-core GPU", "M2 Pro 12-core CPU / 19-core GPU", "16GB", "32GB"])),
+    ("iMac20,1", spec("iMac (Retina 5K, 27-inch, 2020)", 2020, &["Core i5 3.1GHz", "Core i5 3.3GHz", "Core i7 3.8GHz", "8GB", "16GB", "32GB", "64GB", "128GB"])),
+    ("iMac20,2", spec("iMac (Retina 5K, 27-inch, 2020)", 2020, &["Core i9 3.6GHz", "8GB", "16GB", "32GB", "64GB", "128GB"])),
+    ("iMac21,1", spec("iMac (24-inch, M1, 2021)", 2021, &["M1 8-core GPU", "8GB", "16GB"])),
+    ("iMac21,2", spec("iMac (24-inch, M1, 2021)", 2021, &["M1 7-core GPU", "8GB", "16GB"])),
+    // Surface
+    ("Surface Laptop 3", spec("Surface Laptop 3", 2019, &["Core i5-1035G7", "Core i7-1065G7", "Ryzen 5 3580U", "Ryzen 7 3780U", "8GB", "16GB", "32GB"])),
+    ("Surface Laptop 4", spec("Surface Laptop 4", 2021, &["Core i5-1135G7", "Core i7-1185G7", "Ryzen 5 4680U", "Ryzen 7 4980U", "8GB", "16GB", "32GB"])),
+    ("Surface Laptop 5", spec("Surface Laptop 5", 2022, &["Core i5-1235U", "Core i7-1255U", "8GB", "16GB", "32GB"])),
+    ("Surface Pro 7", spec("Surface Pro 7", 2019, &["Core i3-1005G1", "Core i5-1035G4", "Core i7-1065G7", "4GB", "8GB", "16GB"])),
+    ("Surface Pro 8", spec("Surface Pro 8", 2021, &["Core i3-1115G4", "Core i5-1135G7", "Core i7-1185G7", "8GB", "16GB", "32GB"])),
+    ("Surface Pro 9", spec("Surface Pro 9", 2022, &["Core i5-1235U", "Core i7-1255U", "Microsoft SQ3", "8GB", "16GB", "32GB"])),
+    ("Surface Book 3", spec("Surface Book 3", 2020, &["Core i5-1035G7", "Core i7-1065G7", "GeForce GTX 1650", "GeForce GTX 1660 Ti", "8GB", "16GB", "32GB"])),
+];
+
+fn lookup(identifier: &str) -> Option<&'static ModelSpec> {
+    MODELS.iter().find(|(key, _)| key.eq_ignore_ascii_case(identifier)).map(|(_, spec)| spec)
+}
+
+// Fills in the marketing name and options from the table; firmware model
+// strings that aren't listed are usually readable names already
+fn resolve(identifier: String, manufacturer: Option<String>, fallback_name: Option<String>) -> DeviceModel {
+    let spec = lookup(&identifier).or_else(|| fallback_name.as_deref().and_then(lookup));
+    DeviceModel {
+        marketing_name: spec.map(|s| s.name.to_string()).or(fallback_name),
+        release_year: spec.map(|s| s.year),
+        spec_options: spec.map(|s| s.options.iter().map(|o| o.to_string()).collect()).unwrap_or_default(),
+        identifier,
+        manufacturer,
+    }
+}
+
+#[tauri::command]
+pub async fn get_device_model(probes: tauri::State<'_, ProbeScheduler>) -> Result<DeviceModel, QuickscanError> {
+    probes.run(device_model).await
+}
+
+pub fn device_model() -> Result<DeviceModel, QuickscanError> {
+    #[cfg(target_os = "macos")]
+    {
+        let identifier = crate::component_manifest::model_identifier()
+            .ok_or_else(|| QuickscanError::command_failed("sysctl", "hw.model is empty"))?;
+        Ok(resolve(identifier, Some("Apple".to_string()), None))
+    }
+    #[cfg(target_os = "windows")]
+    {
+        get_device_model_windows()
+    }
+    #[cfg(target_os = "linux")]
+    {
+        get_device_model_linux()
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        Err(QuickscanError::unsupported("device_model"))
+    }
+}
+
+// Lenovo puts the machine type ("20L5CTO1WW") in the model field and the
+// product name ("ThinkPad T480") in the version field
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+fn readable_model(manufacturer: &str, model: String, version: Option<String>) -> String {
+    if manufacturer.eq_ignore_ascii_case("lenovo") {
+        if let Some(version) = version.filter(|v| !v.trim().is_empty()) {
+            return version.trim().to_string();
+        }
+    }
+    model
+}
+
+#[cfg(target_os = "windows")]
+fn get_device_model_windows() -> Result<DeviceModel, QuickscanError> {
+    use crate::wmi_provider::{WmiProvider, CIMV2};
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct ComputerSystem {
+        manufacturer: Option<String>,
+        model: Option<String>,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct ComputerSystemProduct {
+        version: Option<String>,
+    }
+
+    let provider = WmiProvider::global();
+    let system = provider
+        .query::<ComputerSystem>(CIMV2, "SELECT Manufacturer, Model FROM Win32_ComputerSystem")?
+        .into_iter()
+        .next()
+        .ok_or_else(|| QuickscanError::parse("Win32_ComputerSystem", "no instance"))?;
+    let manufacturer = system.manufacturer.map(|m| m.trim().to_string()).unwrap_or_default();
+    let model = system.model.map(|m| m.trim().to_string()).unwrap_or_default();
+    let version = provider
+        .query_first::<ComputerSystemProduct>(CIMV2, "SELECT Version FROM Win32_ComputerSystemProduct")
+        .and_then(|product| product.version);
+
+    let name = readable_model(&manufacturer, model.clone(), version);
+    Ok(resolve(model, Some(manufacturer).filter(|m| !m.is_empty()), Some(name)))
+}
+
+#[cfg(target_os = "linux")]
+fn get_device_model_linux() -> Result<DeviceModel, QuickscanError> {
+    let read = |name: &str| {
+        let path = format!("/sys/class/dmi/id/{}", name);
+        std::fs::read_to_string(&path).map(|s| s.trim().to_string()).map_err(|e| QuickscanError::io(&path, e))
+    };
+    let model = read("product_name")?;
+    let manufacturer = read("sys_vendor").ok().filter(|m| !m.is_empty());
+    let version = read("product_version").ok();
+
+    let name = readable_model(manufacturer.as_deref().unwrap_or(""), model.clone(), version);
+    Ok(resolve(model, manufacturer, Some(name)))
+}
@@ -0,0 +1,272 @@
+// Daily digest email for shop managers: how many machines were scanned, which
+// failed a check and so were held back from sale, and which only had
+// warnings. Optional; it runs only when `digest.json` exists in the app
+// config directory:
+//
+// {
+//   "smtp_url": "smtps://smtp.example.com:465",
+//   "username": "quickscan@example.com",
+//   "password": "app-password",
+//   "from": "quickscan@example.com",
+//   "to": ["manager@example.com"],
+//   "send_at": "18:00"
+// }
+//
+// Every finished scan is appended to `scan_log.jsonl` in the app data
+// directory (`record_scan`), and each digest covers the scans logged since
+// the previous one. Mail goes out through curl, which ships with macOS,
+// Windows 10 and later and most Linux desktops, and speaks SMTP over TLS.
+
+use crate::error::QuickscanError;
+use chrono::{DateTime, Local, NaiveTime, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::Path;
+use std::process::Command;
+use std::thread;
+use std::time::Duration;
+use tauri::Manager;
+
+pub const DIGEST_FILE: &str = "digest.json";
+pub const SCAN_LOG_FILE: &str = "scan_log.jsonl";
+// When the last digest went out, and why the last attempt failed if it did
+const STATE_FILE: &str = "digest_state.json";
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+// A mail server that is down isn't asked again every minute
+const RETRY_INTERVAL_SECS: i64 = 60 * 60;
+const SEND_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Deserialize)]
+pub struct DigestConfig {
+    pub smtp_url: String, // smtps://host:465, or smtp://host:587 for STARTTLS
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub password: Option<String>,
+    pub from: String,
+    pub to: Vec<String>,
+    #[serde(default = "default_send_at")]
+    pub send_at: String, // local time, "HH:MM"
+}
+
+fn default_send_at() -> String {
+    "18:00".to_string()
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ScanLogEntry {
+    #[serde(default)]
+    pub scanned_at: i64, // Unix seconds, stamped when logged
+    pub report_id: String,
+    pub device: String,
+    #[serde(default)]
+    pub serial: Option<String>,
+    // Categories of the failed checks; any one holds the machine back from sale
+    #[serde(default)]
+    pub failures: Vec<String>,
+    #[serde(default)]
+    pub warnings: u32,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct DigestState {
+    #[serde(default)]
+    last_sent: Option<i64>,
+    #[serde(default)]
+    last_attempt: Option<i64>,
+    #[serde(default)]
+    last_error: Option<String>,
+}
+
+#[tauri::command]
+pub async fn record_scan(app: tauri::AppHandle, entry: ScanLogEntry) -> Result<(), QuickscanError> {
+    crate::process::blocking(move || append_scan(&app, entry)).await
+}
+
+fn append_scan(app: &tauri::AppHandle, mut entry: ScanLogEntry) -> Result<(), QuickscanError> {
+    let data_dir = app.path().app_data_dir().map_err(|e| QuickscanError::command_failed(SCAN_LOG_FILE, e))?;
+    let io = |e| QuickscanError::io(SCAN_LOG_FILE, e);
+    std::fs::create_dir_all(&data_dir).map_err(io)?;
+    // The digest then names machines by fingerprint
+    if entry.serial.is_some() && !crate::preferences::store_raw_identifiers(app) {
+        let fingerprint = crate::fingerprint::device_fingerprint(&data_dir).map_err(|e| QuickscanError::command_failed("fingerprint", e))?;
+        entry.serial = Some(fingerprint);
+    }
+    entry.scanned_at = Local::now().timestamp();
+    let line = serde_json::to_string(&entry).map_err(|e| QuickscanError::parse(SCAN_LOG_FILE, e))?;
+    let mut log = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(data_dir.join(SCAN_LOG_FILE))
+        .map_err(io)?;
+    writeln!(log, "{}", line).map_err(io)
+}
+
+pub fn load_config(path: &Path) -> Result<Option<DigestConfig>, String> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.to_string()),
+    };
+    serde_json::from_str(&content).map(Some).map_err(|e| e.to_string())
+}
+
+// Checks once a minute whether today's digest is due. Edits to digest.json
+// take effect without a restart.
+pub fn start_scheduler(app: tauri::AppHandle) {
+    thread::spawn(move || loop {
+        thread::sleep(CHECK_INTERVAL);
+        let (Ok(config_dir), Ok(data_dir)) = (app.path().app_config_dir(), app.path().app_data_dir()) else {
+            continue;
+        };
+        send_if_due(&config_dir, &data_dir, Local::now());
+    });
+}
+
+fn send_if_due(config_dir: &Path, data_dir: &Path, now: DateTime<Local>) {
+    let state_path = data_dir.join(STATE_FILE);
+    let mut state: DigestState = std::fs::read_to_string(&state_path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+
+    let config = match load_config(&config_dir.join(DIGEST_FILE)) {
+        Ok(Some(config)) => config,
+        Ok(None) => return,
+        Err(e) => return config_error(&state_path, &mut state, e),
+    };
+    let Ok(send_at) = NaiveTime::parse_from_str(&config.send_at, "%H:%M") else {
+        return config_error(&state_path, &mut state, format!("send_at \"{}\" is not HH:MM", config.send_at));
+    };
+
+    let local_date = |timestamp: i64| Local.timestamp_opt(timestamp, 0).single().map(|t| t.date_naive());
+    let sent_today = state.last_sent.and_then(local_date) == Some(now.date_naive());
+    let retrying = state.last_attempt.is_some_and(|t| now.timestamp() - t < RETRY_INTERVAL_SECS);
+    if now.time() < send_at || sent_today || retrying {
+        return;
+    }
+
+    // The first digest covers the day before it
+    let since = state.last_sent.unwrap_or(now.timestamp() - 24 * 60 * 60);
+    let scans: Vec<ScanLogEntry> = std::fs::read_to_string(data_dir.join(SCAN_LOG_FILE))
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| serde_json::from_str::<ScanLogEntry>(line).ok())
+        .filter(|entry| entry.scanned_at > since)
+        .collect();
+
+    state.last_attempt = Some(now.timestamp());
+    match send(&config, &compose(&config, &scans, now)) {
+        Ok(()) => {
+            state.last_sent = Some(now.timestamp());
+            state.last_attempt = None;
+            state.last_error = None;
+        }
+        Err(e) => state.last_error = Some(e),
+    }
+    save_state(&state_path, &state);
+}
+
+// Recorded once per broken edit rather than rewritten every minute
+fn config_error(state_path: &Path, state: &mut DigestState, error: String) {
+    let error = format!("{}: {}", DIGEST_FILE, error);
+    if state.last_error.as_deref() != Some(error.as_str()) {
+        state.last_error = Some(error);
+        save_state(state_path, state);
+    }
+}
+
+fn save_state(path: &Path, state: &DigestState) {
+    if let Ok(content) = serde_json::to_string_pretty(state) {
+        let _ = std::fs::write(path, content);
+    }
+}
+
+// A plain-text RFC 5322 message
+fn compose(config: &DigestConfig, scans: &[ScanLogEntry], now: DateTime<Local>) -> String {
+    let gated: Vec<&ScanLogEntry> = scans.iter().filter(|s| !s.failures.is_empty()).collect();
+    let warned = scans.iter().filter(|s| s.failures.is_empty() && s.warnings > 0).count();
+    let clean = scans.len() - gated.len() - warned;
+    let date = now.format("%Y-%m-%d");
+
+    let mut body = format!("QuickScan daily digest for {}\n\n", date);
+    body += &format!("Scans performed:      {}\n", scans.len());
+    body += &format!("Held back from sale:  {} (failed at least one check)\n", gated.len());
+    body += &format!("Warnings only:        {}\n", warned);
+    body += &format!("Passed clean:         {}\n", clean);
+    if !gated.is_empty() {
+        body += "\nHeld back from sale:\n";
+        for scan in &gated {
+            let serial = scan.serial.as_deref().map(|s| format!(" ({})", s)).unwrap_or_default();
+            body += &format!("  {}{}: {}\n", scan.device, serial, scan.failures.join(", "));
+        }
+    }
+
+    let headers = [
+        format!("From: {}", config.from),
+        format!("To: {}", config.to.join(", ")),
+        format!("Subject: QuickScan daily digest {}: {} scans, {} held back", date, scans.len(), gated.len()),
+        format!("Date: {}", now.to_rfc2822()),
+        "MIME-Version: 1.0".to_string(),
+        "Content-Type: text/plain; charset=utf-8".to_string(),
+        "Content-Transfer-Encoding: 8bit".to_string(),
+    ];
+    format!("{}\r\n\r\n{}", headers.join("\r\n"), body.replace('\n', "\r\n"))
+}
+
+// The credentials go in a curl config file rather than on the command line,
+// where any local user could read them from the process list
+fn send(config: &DigestConfig, message: &str) -> Result<(), String> {
+    if config.to.is_empty() {
+        return Err(format!("{}: no recipients in \"to\"", DIGEST_FILE));
+    }
+    let temp = |extension: &str| std::env::temp_dir().join(format!("quickscan-digest-{}.{}", std::process::id(), extension));
+    let message_path = temp("eml");
+    let config_path = temp("curlrc");
+
+    let quote = |value: &str| format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""));
+    let mut curl_config = vec![
+        format!("url = {}", quote(&config.smtp_url)),
+        format!("mail-from = {}", quote(&config.from)),
+        format!("upload-file = {}", quote(&message_path.to_string_lossy())),
+        "ssl-reqd".to_string(),
+        "silent".to_string(),
+        "show-error".to_string(),
+    ];
+    curl_config.extend(config.to.iter().map(|to| format!("mail-rcpt = {}", quote(to))));
+    if let Some(username) = &config.username {
+        let password = config.password.as_deref().unwrap_or_default();
+        curl_config.push(format!("user = {}", quote(&format!("{}:{}", username, password))));
+    }
+
+    let result = write_private(&message_path, message)
+        .and_then(|()| write_private(&config_path, &(curl_config.join("\n") + "\n")))
+        .and_then(|()| {
+            let mut command = Command::new("curl");
+            command.arg("--config").arg(&config_path);
+            crate::process::run_cmd_with_timeout(&mut command, SEND_TIMEOUT).map_err(|e| e.to_string())
+        })
+        .and_then(|output| {
+            if output.status.success() {
+                Ok(())
+            } else {
+                Err(format!("curl: {}", String::from_utf8_lossy(&output.stderr).trim()))
+            }
+        });
+    let _ = std::fs::remove_file(&message_path);
+    let _ = std::fs::remove_file(&config_path);
+    result
+}
+
+pub fn write_private(path: &Path, content: &str) -> Result<(), String> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path).map_err(|e| e.to_string())?;
+    file.write_all(content.as_bytes()).map_err(|e| e.to_string())
+}
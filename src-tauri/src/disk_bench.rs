@@ -0,0 +1,210 @@
+// Disk benchmark (`run_disk_benchmark`). A worn SSD can pass SMART and still
+// crawl once its spare blocks run out, so this measures it directly:
+// sequential throughput in 1 MiB chunks and random 4 KiB IOPS, bypassing the
+// page cache where the platform allows.
+
+use crate::error::QuickscanError;
+use crate::probe_scheduler::ProbeScheduler;
+use serde::Serialize;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+const FILE_NAME: &str = "quickscan-disk-bench.tmp";
+const SEQUENTIAL_CHUNK: usize = 1024 * 1024;
+const RANDOM_BLOCK: usize = 4096;
+const RANDOM_PHASE: Duration = Duration::from_secs(3);
+const MIN_SIZE_MB: u64 = 64;
+const MAX_SIZE_MB: u64 = 4096;
+
+#[derive(Serialize)]
+pub struct DiskBenchmark {
+    pub path: String,
+    pub disk: Option<String>, // device or volume the file landed on
+    pub size_mb: u64,
+    pub direct_io: bool, // false when the filesystem refused it; reads may then come from cache
+    pub sequential_write_mbps: f64,
+    pub sequential_read_mbps: f64,
+    pub random_write_iops: f64,
+    pub random_read_iops: f64,
+}
+
+// Direct I/O needs buffers aligned to the device's logical block size
+#[repr(C, align(4096))]
+#[derive(Clone, Copy)]
+struct Block([u8; RANDOM_BLOCK]);
+
+struct AlignedBuffer(Vec<Block>);
+
+impl AlignedBuffer {
+    fn new(len: usize) -> Self {
+        // Incompressible contents, so controllers that compress can't cheat
+        let mut seed = 0x9E37_79B9_7F4A_7C15u64;
+        let blocks = (0..len / RANDOM_BLOCK)
+            .map(|_| {
+                let mut block = [0u8; RANDOM_BLOCK];
+                for chunk in block.chunks_mut(8) {
+                    seed = xorshift(seed);
+                    chunk.copy_from_slice(&seed.to_le_bytes());
+                }
+                Block(block)
+            })
+            .collect();
+        AlignedBuffer(blocks)
+    }
+
+    fn bytes(&self) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(self.0.as_ptr() as *const u8, self.0.len() * RANDOM_BLOCK) }
+    }
+
+    fn bytes_mut(&mut self) -> &mut [u8] {
+        unsafe { std::slice::from_raw_parts_mut(self.0.as_mut_ptr() as *mut u8, self.0.len() * RANDOM_BLOCK) }
+    }
+}
+
+// Removes the test file however the benchmark ends
+struct TestFile(PathBuf);
+
+impl Drop for TestFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+#[tauri::command]
+pub async fn run_disk_benchmark(probes: tauri::State<'_, ProbeScheduler>, target_path: Option<String>, size_mb: u64) -> Result<DiskBenchmark, QuickscanError> {
+    probes.run(move || {
+        let dir = target_path.map(PathBuf::from).unwrap_or_else(std::env::temp_dir);
+        disk_benchmark(&dir, size_mb)
+    })
+    .await
+}
+
+fn disk_benchmark(dir: &Path, size_mb: u64) -> Result<DiskBenchmark, QuickscanError> {
+    let size_mb = size_mb.clamp(MIN_SIZE_MB, MAX_SIZE_MB);
+    let test_file = TestFile(dir.join(FILE_NAME));
+    let source = test_file.0.to_string_lossy().to_string();
+    let io_error = |e| QuickscanError::io(&source, e);
+
+    let (mut file, direct_io) = open(&test_file.0).map_err(io_error)?;
+    let chunks = size_mb as usize * 1024 * 1024 / SEQUENTIAL_CHUNK;
+    let mut buffer = AlignedBuffer::new(SEQUENTIAL_CHUNK);
+
+    let started = Instant::now();
+    for _ in 0..chunks {
+        file.write_all(buffer.bytes()).map_err(io_error)?;
+    }
+    file.sync_all().map_err(io_error)?;
+    let sequential_write_mbps = size_mb as f64 / started.elapsed().as_secs_f64();
+
+    file.seek(SeekFrom::Start(0)).map_err(io_error)?;
+    let started = Instant::now();
+    for _ in 0..chunks {
+        file.read_exact(buffer.bytes_mut()).map_err(io_error)?;
+    }
+    let sequential_read_mbps = size_mb as f64 / started.elapsed().as_secs_f64();
+
+    let blocks = (chunks * SEQUENTIAL_CHUNK / RANDOM_BLOCK) as u64;
+    let mut block = AlignedBuffer::new(RANDOM_BLOCK);
+    let random_write_iops = random_phase(&mut file, blocks, |file| file.write_all(block.bytes())).map_err(io_error)?;
+    file.sync_all().map_err(io_error)?;
+    let random_read_iops = random_phase(&mut file, blocks, |file| file.read_exact(block.bytes_mut())).map_err(io_error)?;
+
+    Ok(DiskBenchmark {
+        path: dir.to_string_lossy().to_string(),
+        disk: disk_for_path(dir),
+        size_mb,
+        direct_io,
+        sequential_write_mbps: round(sequential_write_mbps),
+        sequential_read_mbps: round(sequential_read_mbps),
+        random_write_iops: round(random_write_iops),
+        random_read_iops: round(random_read_iops),
+    })
+}
+
+// Runs one 4 KiB operation at a time at random aligned offsets for
+// RANDOM_PHASE and returns operations per second
+fn random_phase(
+    file: &mut File,
+    blocks: u64,
+    mut operation: impl FnMut(&mut File) -> std::io::Result<()>,
+) -> std::io::Result<f64> {
+    let mut seed = 0x2545_F491_4F6C_DD1Du64;
+    let mut count = 0u64;
+    let started = Instant::now();
+    while started.elapsed() < RANDOM_PHASE {
+        seed = xorshift(seed);
+        file.seek(SeekFrom::Start(seed % blocks * RANDOM_BLOCK as u64))?;
+        operation(file)?;
+        count += 1;
+    }
+    Ok(count as f64 / started.elapsed().as_secs_f64())
+}
+
+fn xorshift(mut x: u64) -> u64 {
+    x ^= x << 13;
+    x ^= x >> 7;
+    x ^= x << 17;
+    x
+}
+
+fn round(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+// Opens the test file bypassing the page cache, falling back to buffered I/O
+// on filesystems that refuse it (tmpfs, some network shares)
+fn open(path: &Path) -> std::io::Result<(File, bool)> {
+    let mut options = OpenOptions::new();
+    options.read(true).write(true).create(true).truncate(true);
+
+    #[cfg(target_os = "linux")]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        if let Ok(file) = options.clone().custom_flags(libc::O_DIRECT).open(path) {
+            return Ok((file, true));
+        }
+    }
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::fs::OpenOptionsExt;
+        const FILE_FLAG_NO_BUFFERING: u32 = 0x2000_0000;
+        const FILE_FLAG_WRITE_THROUGH: u32 = 0x8000_0000;
+        if let Ok(file) = options.clone().custom_flags(FILE_FLAG_NO_BUFFERING | FILE_FLAG_WRITE_THROUGH).open(path) {
+            return Ok((file, true));
+        }
+    }
+
+    let file = options.open(path)?;
+    // macOS has no O_DIRECT; F_NOCACHE turns caching off per descriptor
+    #[cfg(target_os = "macos")]
+    let direct = {
+        use std::os::unix::io::AsRawFd;
+        unsafe { libc::fcntl(file.as_raw_fd(), libc::F_NOCACHE, 1) != -1 }
+    };
+    #[cfg(not(target_os = "macos"))]
+    let direct = false;
+    Ok((file, direct))
+}
+
+fn disk_for_path(dir: &Path) -> Option<String> {
+    #[cfg(target_os = "windows")]
+    {
+        // "C:\Users\..." -> "C:"
+        use std::path::Component;
+        match std::path::absolute(dir).ok()?.components().next()? {
+            Component::Prefix(prefix) => Some(prefix.as_os_str().to_string_lossy().to_string()),
+            _ => None,
+        }
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        // df -P prints "Filesystem 1024-blocks Used Available Capacity Mounted on"
+        // then e.g. "/dev/disk3s1 ..." for the volume holding the path
+        let output = crate::process::run_cmd(std::process::Command::new("df").arg("-P").arg(dir)).ok()?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let device = stdout.lines().nth(1)?.split_whitespace().next()?;
+        Some(device.trim_start_matches("/dev/").to_string())
+    }
+}
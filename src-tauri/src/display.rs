@@ -0,0 +1,371 @@
+// Attached display panels (`get_display_info`). Vendors are EDID PnP IDs
+// mapped to names where known, so a replaced internal panel shows up as
+// "LG Display" or "BOE" instead of Apple or the laptop maker.
+
+use crate::error::QuickscanError;
+use crate::probe_scheduler::ProbeScheduler;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct DisplayInfo {
+    pub name: String,
+    pub vendor: String,
+    pub native_resolution: Option<String>, // "2560x1600"
+    pub refresh_rate_hz: Option<f64>,
+    pub color_depth: Option<u32>, // bits per pixel
+    pub connection_type: String,
+    pub is_internal: bool,
+}
+
+const PNP_VENDORS: &[(&str, &str)] = &[
+    ("ACR", "Acer"),
+    ("APP", "Apple"),
+    ("AUO", "AU Optronics"),
+    ("BOE", "BOE"),
+    ("CMN", "Innolux"),
+    ("CSO", "CSOT"),
+    ("DEL", "Dell"),
+    ("GSM", "LG Electronics"),
+    ("HWP", "HP"),
+    ("IVO", "InfoVision"),
+    ("LEN", "Lenovo"),
+    ("LGD", "LG Display"),
+    ("SAM", "Samsung"),
+    ("SDC", "Samsung Display"),
+    ("SHP", "Sharp"),
+];
+
+pub fn pnp_vendor_name(pnp_id: &str) -> String {
+    PNP_VENDORS
+        .iter()
+        .find(|(id, _)| id.eq_ignore_ascii_case(pnp_id))
+        .map(|(_, name)| name.to_string())
+        .unwrap_or_else(|| pnp_id.to_string())
+}
+
+// EDID packs the manufacturer as three 5-bit letters, 'A' = 1
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn decode_pnp_id(id: u16) -> Option<String> {
+    let letters = [(id >> 10) & 0x1f, (id >> 5) & 0x1f, id & 0x1f];
+    letters
+        .iter()
+        .map(|&l| (1..=26).contains(&l).then(|| (b'A' + l as u8 - 1) as char))
+        .collect()
+}
+
+#[tauri::command]
+pub async fn get_display_info(probes: tauri::State<'_, ProbeScheduler>) -> Result<Vec<DisplayInfo>, QuickscanError> {
+    probes.run(display_info).await
+}
+
+pub fn display_info() -> Result<Vec<DisplayInfo>, QuickscanError> {
+    #[cfg(target_os = "macos")]
+    {
+        get_display_info_macos()
+    }
+    #[cfg(target_os = "windows")]
+    {
+        get_display_info_windows()
+    }
+    #[cfg(target_os = "linux")]
+    {
+        get_display_info_linux()
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        Err(QuickscanError::unsupported("display_info"))
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn get_display_info_macos() -> Result<Vec<DisplayInfo>, QuickscanError> {
+    let mut command = std::process::Command::new("system_profiler");
+    let output = crate::process::run_cmd(command.args(["SPDisplaysDataType", "-json"]))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json = serde_json::from_str::<serde_json::Value>(&stdout)
+        .map_err(|e| QuickscanError::parse("system_profiler", e))?;
+
+    let field = |item: &serde_json::Value, key: &str| item.get(key).and_then(|v| v.as_str()).map(|s| s.to_string());
+    // Panels are listed under the GPU driving them
+    let displays = json.get("SPDisplaysDataType")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|gpu| gpu.get("spdisplays_ndrvs").and_then(|v| v.as_array()))
+        .flatten()
+        .map(|panel| {
+            // Vendor id is hex without prefix, e.g. "610" for Apple
+            let vendor = field(panel, "_spdisplays_display-vendor-id")
+                .and_then(|id| u16::from_str_radix(&id, 16).ok())
+                .and_then(decode_pnp_id)
+                .map(|pnp| pnp_vendor_name(&pnp))
+                .unwrap_or_default();
+            let connection_type = match field(panel, "spdisplays_connection_type") {
+                Some(connection) => connection.trim_start_matches("spdisplays_").to_string(),
+                None if field(panel, "spdisplays_display_type").is_some_and(|t| t.contains("built-in")) => {
+                    "internal".to_string()
+                }
+                None => "external".to_string(),
+            };
+            DisplayInfo {
+                name: field(panel, "_name").unwrap_or_default(),
+                vendor,
+                native_resolution: field(panel, "_spdisplays_pixels")
+                    .or_else(|| field(panel, "spdisplays_pixelresolution"))
+                    .and_then(|r| parse_resolution(&r)),
+                // "1728 x 1117 @ 120.00Hz"
+                refresh_rate_hz: field(panel, "_spdisplays_resolution")
+                    .or_else(|| field(panel, "spdisplays_resolution"))
+                    .and_then(|r| r.split('@').nth(1).map(|hz| hz.trim().trim_end_matches("Hz").to_string()))
+                    .and_then(|hz| hz.parse().ok()),
+                color_depth: field(panel, "spdisplays_depth").and_then(|depth| match depth.as_str() {
+                    "CGSThirtytwoBitColor" => Some(32),
+                    "CGSThirtyBitColor" => Some(30),
+                    "CGSSixteenBitColor" => Some(16),
+                    _ => None,
+                }),
+                is_internal: connection_type == "internal",
+                connection_type,
+            }
+        })
+        .collect();
+    Ok(displays)
+}
+
+// "3456 x 2234", "spdisplays_2880x1800Retina" -> "3456x2234", "2880x1800"
+#[cfg(target_os = "macos")]
+fn parse_resolution(value: &str) -> Option<String> {
+    let compact: String = value.chars().filter(|c| !c.is_whitespace()).collect();
+    let start = compact.find(|c: char| c.is_ascii_digit())?;
+    let rest = &compact[start..];
+    let (width, rest) = rest.split_once('x')?;
+    let height: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+    (!height.is_empty()).then(|| format!("{}x{}", width, height))
+}
+
+#[cfg(target_os = "windows")]
+fn get_display_info_windows() -> Result<Vec<DisplayInfo>, QuickscanError> {
+    use crate::wmi_provider::{WmiProvider, CIMV2, WMI};
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct MonitorId {
+        instance_name: String,
+        manufacturer_name: Option<Vec<u16>>,
+        user_friendly_name: Option<Vec<u16>>,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct MonitorConnection {
+        instance_name: String,
+        video_output_technology: u32,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct SourceMode {
+        horizontal_active_pixels: u16,
+        vertical_active_pixels: u16,
+        vertical_refresh_rate_numerator: u32,
+        vertical_refresh_rate_denominator: u32,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct MonitorModes {
+        instance_name: String,
+        preferred_monitor_source_mode_index: u16,
+        monitor_source_modes: Vec<SourceMode>,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct VideoController {
+        current_bits_per_pixel: Option<u32>,
+    }
+
+    let provider = WmiProvider::global();
+    let monitors =
+        provider.query::<MonitorId>(WMI, "SELECT InstanceName, ManufacturerName, UserFriendlyName FROM WmiMonitorID")?;
+    let connections: Vec<MonitorConnection> = provider
+        .query(WMI, "SELECT InstanceName, VideoOutputTechnology FROM WmiMonitorConnectionParams")
+        .unwrap_or_default();
+    let modes: Vec<MonitorModes> = provider
+        .query(
+            WMI,
+            "SELECT InstanceName, PreferredMonitorSourceModeIndex, MonitorSourceModes FROM WmiMonitorListedSupportedSourceModes",
+        )
+        .unwrap_or_default();
+    // WMI only exposes the desktop color depth per adapter
+    let color_depth = provider
+        .query_first::<VideoController>(CIMV2, "SELECT CurrentBitsPerPixel FROM Win32_VideoController")
+        .and_then(|controller| controller.current_bits_per_pixel);
+
+    // Names are NUL-padded UTF-16 code units
+    let decode = |units: Option<Vec<u16>>| {
+        let units = units.unwrap_or_default();
+        let len = units.iter().position(|&c| c == 0).unwrap_or(units.len());
+        String::from_utf16_lossy(&units[..len]).trim().to_string()
+    };
+
+    let displays = monitors
+        .into_iter()
+        .map(|monitor| {
+            let technology = connections
+                .iter()
+                .find(|c| c.instance_name == monitor.instance_name)
+                .map(|c| c.video_output_technology);
+            // D3DKMDT_VIDEO_OUTPUT_TECHNOLOGY values
+            let (connection_type, is_internal) = match technology {
+                Some(0) => ("VGA", false),
+                Some(4) => ("DVI", false),
+                Some(5) => ("HDMI", false),
+                Some(6) => ("LVDS", true),
+                Some(10) => ("DisplayPort", false),
+                Some(11) => ("eDP", true),
+                Some(13) => ("UDI", true),
+                Some(15) => ("Miracast", false),
+                Some(0x8000_0000) => ("internal", true),
+                _ => ("unknown", false),
+            };
+            let preferred = modes
+                .iter()
+                .find(|m| m.instance_name == monitor.instance_name)
+                .and_then(|m| m.monitor_source_modes.get(m.preferred_monitor_source_mode_index as usize));
+            DisplayInfo {
+                name: decode(monitor.user_friendly_name),
+                vendor: pnp_vendor_name(&decode(monitor.manufacturer_name)),
+                native_resolution: preferred
+                    .map(|mode| format!("{}x{}", mode.horizontal_active_pixels, mode.vertical_active_pixels)),
+                refresh_rate_hz: preferred
+                    .filter(|mode| mode.vertical_refresh_rate_denominator > 0)
+                    .map(|mode| {
+                        mode.vertical_refresh_rate_numerator as f64 / mode.vertical_refresh_rate_denominator as f64
+                    }),
+                color_depth,
+                connection_type: connection_type.to_string(),
+                is_internal,
+            }
+        })
+        .collect();
+    Ok(displays)
+}
+
+#[cfg(target_os = "linux")]
+fn get_display_info_linux() -> Result<Vec<DisplayInfo>, QuickscanError> {
+    let entries = std::fs::read_dir("/sys/class/drm").map_err(|e| QuickscanError::io("/sys/class/drm", e))?;
+    let mut connectors: Vec<_> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            std::fs::read_to_string(path.join("status")).is_ok_and(|status| status.trim() == "connected")
+        })
+        .collect();
+    connectors.sort();
+
+    let displays = connectors
+        .into_iter()
+        .map(|connector| {
+            // card0-eDP-1 -> eDP
+            let name = connector.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            let connection_type = name
+                .split_once('-')
+                .map(|(_, rest)| rest.rsplit_once('-').map_or(rest, |(kind, _)| kind))
+                .unwrap_or("unknown")
+                .to_string();
+            let is_internal = ["eDP", "LVDS", "DSI"].contains(&connection_type.as_str());
+            let edid = std::fs::read(connector.join("edid")).ok().and_then(|bytes| parse_edid(&bytes));
+
+            DisplayInfo {
+                name: edid.as_ref().and_then(|e| e.name.clone()).unwrap_or_else(|| name.clone()),
+                vendor: edid.as_ref().map(|e| pnp_vendor_name(&e.vendor)).unwrap_or_default(),
+                native_resolution: edid.as_ref().and_then(|e| e.native_resolution.clone()),
+                refresh_rate_hz: edid.as_ref().and_then(|e| e.refresh_rate_hz),
+                color_depth: edid.as_ref().and_then(|e| e.bits_per_channel).map(|bits| bits * 3),
+                connection_type,
+                is_internal,
+            }
+        })
+        .collect();
+    Ok(displays)
+}
+
+// The panel's serial: the 0xFF display descriptor if present, otherwise the
+// 32-bit number in bytes 12-15
+#[cfg(target_os = "linux")]
+pub fn edid_serial(bytes: &[u8]) -> Option<String> {
+    if bytes.len() < 128 || bytes[..8] != [0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00] {
+        return None;
+    }
+    let text = bytes[54..126].chunks(18).find(|d| d[..3] == [0, 0, 0] && d[3] == 0xff).map(|d| {
+        String::from_utf8_lossy(&d[5..]).split('\n').next().unwrap_or("").trim().to_string()
+    });
+    let number = u32::from_le_bytes([bytes[12], bytes[13], bytes[14], bytes[15]]);
+    text.filter(|s| !s.is_empty()).or_else(|| (number != 0).then(|| number.to_string()))
+}
+
+// The panel's part number ("ATNA40YK04-0", "LP140WF9-SPF1"): laptop panels
+// carry it in the 0xFE alphanumeric descriptor, the last one that has text
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+pub fn edid_panel_model(bytes: &[u8]) -> Option<String> {
+    if bytes.len() < 128 || bytes[..8] != [0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00] {
+        return None;
+    }
+    bytes[54..126]
+        .chunks(18)
+        .rev()
+        .filter(|d| d[..3] == [0, 0, 0] && d[3] == 0xfe)
+        .map(|d| String::from_utf8_lossy(&d[5..]).split('\n').next().unwrap_or("").trim().to_string())
+        .find(|text| !text.is_empty())
+}
+
+#[cfg(target_os = "linux")]
+struct Edid {
+    vendor: String,
+    name: Option<String>,
+    native_resolution: Option<String>,
+    refresh_rate_hz: Option<f64>,
+    bits_per_channel: Option<u32>,
+}
+
+#[cfg(target_os = "linux")]
+fn parse_edid(bytes: &[u8]) -> Option<Edid> {
+    if bytes.len() < 128 || bytes[..8] != [0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00] {
+        return None;
+    }
+    let vendor = decode_pnp_id(u16::from_be_bytes([bytes[8], bytes[9]]))?;
+
+    // Digital inputs (EDID 1.4) encode bits per channel in byte 20
+    let bits_per_channel = match (bytes[20] & 0x80 != 0, (bytes[20] >> 4) & 0x07) {
+        (true, depth @ 1..=6) => Some(4 + 2 * depth as u32),
+        _ => None,
+    };
+
+    // The first detailed timing descriptor is the preferred (native) mode
+    let timing = &bytes[54..72];
+    let pixel_clock = u16::from_le_bytes([timing[0], timing[1]]) as f64 * 10_000.0;
+    let (native_resolution, refresh_rate_hz) = if pixel_clock > 0.0 {
+        let h_active = timing[2] as u32 | ((timing[4] as u32 >> 4) << 8);
+        let h_blank = timing[3] as u32 | ((timing[4] as u32 & 0x0f) << 8);
+        let v_active = timing[5] as u32 | ((timing[7] as u32 >> 4) << 8);
+        let v_blank = timing[6] as u32 | ((timing[7] as u32 & 0x0f) << 8);
+        let total = ((h_active + h_blank) * (v_active + v_blank)) as f64;
+        let refresh = (total > 0.0).then(|| (pixel_clock / total * 100.0).round() / 100.0);
+        (Some(format!("{}x{}", h_active, v_active)), refresh)
+    } else {
+        (None, None)
+    };
+
+    // Monitor name lives in a display descriptor tagged 0xFC
+    let name = bytes[54..126].chunks(18).find(|d| d[..3] == [0, 0, 0] && d[3] == 0xfc).map(|d| {
+        String::from_utf8_lossy(&d[5..])
+            .split('\n')
+            .next()
+            .unwrap_or("")
+            .trim()
+            .to_string()
+    });
+
+    Some(Edid { vendor, name, native_resolution, refresh_rate_hz, bits_per_channel })
+}
@@ -0,0 +1,131 @@
+// System volume encryption (`check_disk_encryption`). A drive still encrypted
+// to the previous owner's credentials can't be reused without their password
+// or recovery key.
+
+use crate::error::QuickscanError;
+use crate::probe_scheduler::ProbeScheduler;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct EncryptionStatus {
+    pub encrypted: bool,
+    pub method: Option<String>, // "FileVault", "BitLocker" or "LUKS"
+    pub in_progress: bool, // encrypting or decrypting right now
+    pub recovery_key_escrowed: Option<bool>, // None when the platform doesn't say
+}
+
+#[tauri::command]
+pub async fn check_disk_encryption(probes: tauri::State<'_, ProbeScheduler>) -> Result<EncryptionStatus, QuickscanError> {
+    probes.run(encryption_status).await
+}
+
+pub fn encryption_status() -> Result<EncryptionStatus, QuickscanError> {
+    #[cfg(target_os = "macos")]
+    {
+        encryption_status_macos()
+    }
+    #[cfg(target_os = "windows")]
+    {
+        encryption_status_windows()
+    }
+    #[cfg(target_os = "linux")]
+    {
+        encryption_status_linux()
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        Err(QuickscanError::unsupported("disk_encryption"))
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn encryption_status_macos() -> Result<EncryptionStatus, QuickscanError> {
+    use std::process::Command;
+
+    // "FileVault is On." / "FileVault is Off." / "Encryption in progress: ..."
+    let output = crate::process::run_cmd_checked(Command::new("fdesetup").arg("status"))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let in_progress = stdout.contains("in progress");
+    let encrypted = stdout.contains("FileVault is On") || stdout.contains("Encryption in progress");
+
+    // Personal key shown at setup, or an institutional key from a keychain/MDM
+    let has_key = |kind: &str| {
+        crate::process::run_cmd(Command::new("fdesetup").arg(kind))
+            .ok()
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim() == "true")
+    };
+    let recovery_key_escrowed = if encrypted {
+        match (has_key("haspersonalrecoverykey"), has_key("hasinstitutionalrecoverykey")) {
+            (None, None) => None,
+            (personal, institutional) => Some(personal == Some(true) || institutional == Some(true)),
+        }
+    } else {
+        None
+    };
+
+    Ok(EncryptionStatus {
+        encrypted,
+        method: encrypted.then(|| "FileVault".to_string()),
+        in_progress,
+        recovery_key_escrowed,
+    })
+}
+
+#[cfg(target_os = "windows")]
+fn encryption_status_windows() -> Result<EncryptionStatus, QuickscanError> {
+    use crate::wmi_provider::{WmiProvider, VOLUME_ENCRYPTION};
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct EncryptableVolume {
+        protection_status: Option<u32>,
+        conversion_status: Option<u32>,
+    }
+
+    // Only readable from an elevated process; anything else is access denied
+    let drive = std::env::var("SystemDrive").unwrap_or_else(|_| "C:".to_string());
+    let volume = WmiProvider::global()
+        .query::<EncryptableVolume>(
+            VOLUME_ENCRYPTION,
+            &format!(
+                "SELECT ProtectionStatus, ConversionStatus FROM Win32_EncryptableVolume WHERE DriveLetter = '{}'",
+                drive
+            ),
+        )?
+        .into_iter()
+        .next();
+    let Some(volume) = volume else {
+        return Ok(EncryptionStatus::default());
+    };
+
+    // ConversionStatus: 0 fully decrypted, 1 fully encrypted, 2-5 converting or paused
+    let conversion = volume.conversion_status.unwrap_or(0);
+    let encrypted = volume.protection_status == Some(1) || conversion != 0;
+    Ok(EncryptionStatus {
+        encrypted,
+        method: encrypted.then(|| "BitLocker".to_string()),
+        in_progress: (2..=5).contains(&conversion),
+        // Key protectors are only exposed through a WMI method call
+        recovery_key_escrowed: None,
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn encryption_status_linux() -> Result<EncryptionStatus, QuickscanError> {
+    use std::process::Command;
+
+    let output = crate::process::run_cmd(Command::new("findmnt").args(["-n", "-o", "SOURCE", "/"]))?;
+    let source = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if source.is_empty() {
+        return Err(QuickscanError::parse("findmnt", "no source for /"));
+    }
+    // -s walks from the root filesystem down through LVM and dm-crypt layers
+    let output = crate::process::run_cmd(Command::new("lsblk").args(["-n", "-s", "-o", "TYPE", &source]))?;
+    let encrypted = String::from_utf8_lossy(&output.stdout).lines().any(|line| line.trim() == "crypt");
+    Ok(EncryptionStatus {
+        encrypted,
+        method: encrypted.then(|| "LUKS".to_string()),
+        in_progress: false,
+        recovery_key_escrowed: None,
+    })
+}
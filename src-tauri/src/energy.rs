@@ -0,0 +1,192 @@
+// Energy report (`get_energy_report`): what the machine draws with only the
+// scan running, and the runtime that works out to on a full charge at a
+// standard display brightness. Buyers care about hours, not mAh.
+
+use crate::error::QuickscanError;
+use crate::probe_scheduler::ProbeScheduler;
+use serde::Serialize;
+
+// Typical laptop panel draw at the ends of the brightness range. The
+// backlight is most of it and scales roughly linearly with the setting.
+const PANEL_MIN_WATTS: f64 = 0.5;
+const PANEL_MAX_WATTS: f64 = 5.0;
+// About 150 nits on the usual 300-500 nit laptop panel
+const STANDARD_BRIGHTNESS: f64 = 0.5;
+// Below this the reading is noise or the machine is still settling
+const MIN_SYSTEM_WATTS: f64 = 1.0;
+
+#[derive(Serialize, Default)]
+pub struct EnergyReport {
+    pub on_battery: bool,
+    pub system_power_watts: Option<f64>, // None on AC power unless the platform reports system load
+    pub display_brightness: Option<f64>, // 0.0-1.0
+    pub display_power_watts: Option<f64>, // estimated at the current brightness
+    pub full_charge_wh: Option<f64>,
+    pub estimated_runtime_hours: Option<f64>, // at STANDARD_BRIGHTNESS
+}
+
+#[tauri::command]
+pub async fn get_energy_report(probes: tauri::State<'_, ProbeScheduler>) -> Result<EnergyReport, QuickscanError> {
+    probes.run(energy_report).await
+}
+
+pub fn energy_report() -> Result<EnergyReport, QuickscanError> {
+    #[cfg(target_os = "macos")]
+    {
+        Ok(estimate(energy_report_macos()))
+    }
+    #[cfg(target_os = "windows")]
+    {
+        energy_report_windows().map(estimate)
+    }
+    #[cfg(target_os = "linux")]
+    {
+        Ok(estimate(energy_report_linux()))
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        Err(QuickscanError::unsupported("energy_report"))
+    }
+}
+
+fn panel_watts(brightness: f64) -> f64 {
+    PANEL_MIN_WATTS + (PANEL_MAX_WATTS - PANEL_MIN_WATTS) * brightness.clamp(0.0, 1.0)
+}
+
+// Swaps the panel's share of the measured draw for what it would be at the
+// standard brightness; without a brightness reading the draw is taken as is
+fn estimate(mut report: EnergyReport) -> EnergyReport {
+    report.display_power_watts = report.display_brightness.map(panel_watts);
+    let standardized = report.system_power_watts.filter(|w| *w >= MIN_SYSTEM_WATTS).map(|watts| {
+        let display = report.display_power_watts.unwrap_or(panel_watts(STANDARD_BRIGHTNESS));
+        (watts - display + panel_watts(STANDARD_BRIGHTNESS)).max(MIN_SYSTEM_WATTS)
+    });
+    report.estimated_runtime_hours = match (report.full_charge_wh, standardized) {
+        (Some(wh), Some(watts)) if wh > 0.0 => Some((wh / watts * 10.0).round() / 10.0),
+        _ => None,
+    };
+    report
+}
+
+#[cfg(target_os = "macos")]
+fn energy_report_macos() -> EnergyReport {
+    use crate::iokit;
+
+    let mut report = EnergyReport::default();
+    if let Some(battery) = iokit::service_properties("AppleSmartBattery") {
+        let number = |key: &str| battery.get(key).and_then(|v| v.as_i64());
+        report.on_battery = battery.get("ExternalConnected").and_then(|v| v.as_bool()) == Some(false);
+
+        let millivolts = number("Voltage").unwrap_or(0) as f64;
+        report.full_charge_wh = number("AppleRawMaxCapacity")
+            .or_else(|| number("NominalChargeCapacity"))
+            .filter(|_| millivolts > 0.0)
+            .map(|mah| mah as f64 * millivolts / 1e6);
+
+        // T2 and Apple silicon Macs report the system's own load (mW) even on
+        // AC; otherwise only the battery's discharge current tells
+        let system_load = battery
+            .get("PowerTelemetryData")
+            .and_then(|t| t.get("SystemLoad"))
+            .and_then(|v| v.as_i64())
+            .filter(|mw| *mw > 0)
+            .map(|mw| mw as f64 / 1000.0);
+        let discharge = number("InstantAmperage")
+            .or_else(|| number("Amperage"))
+            .filter(|ma| *ma < 0 && report.on_battery)
+            .map(|ma| -ma as f64 * millivolts / 1e6);
+        report.system_power_watts = system_load.or(discharge);
+    }
+
+    // IODisplayParameters: { "brightness": { "min": 0, "max": 65536, "value": 32768 } }
+    report.display_brightness = ["AppleARMBacklight", "IODisplayConnect"].iter().find_map(|class| {
+        let properties = iokit::service_properties(class)?;
+        let brightness = properties.get("IODisplayParameters")?.get("brightness")?;
+        let value = brightness.get("value")?.as_f64()?;
+        let min = brightness.get("min").and_then(|v| v.as_f64()).unwrap_or(0.0);
+        let max = brightness.get("max")?.as_f64()?;
+        (max > min).then(|| (value - min) / (max - min))
+    });
+    report
+}
+
+#[cfg(target_os = "windows")]
+fn energy_report_windows() -> Result<EnergyReport, QuickscanError> {
+    use crate::wmi_provider::{WmiProvider, WMI};
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct BatteryStatus {
+        discharge_rate: Option<i32>, // mW
+        power_online: Option<bool>,
+    }
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct BatteryFullChargedCapacity {
+        full_charged_capacity: u32, // mWh
+    }
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct MonitorBrightness {
+        current_brightness: u8, // percent
+    }
+
+    let wmi = WmiProvider::global();
+    let mut report = EnergyReport::default();
+    // A desktop has no BatteryStatus rows; that is an answer, not an error
+    if let Some(status) = wmi.query::<BatteryStatus>(WMI, "SELECT DischargeRate, PowerOnline FROM BatteryStatus")?.into_iter().next() {
+        report.on_battery = status.power_online == Some(false);
+        report.system_power_watts = status
+            .discharge_rate
+            .filter(|mw| *mw > 0 && report.on_battery)
+            .map(|mw| mw as f64 / 1000.0);
+        report.full_charge_wh = wmi
+            .query_first::<BatteryFullChargedCapacity>(WMI, "SELECT FullChargedCapacity FROM BatteryFullChargedCapacity")
+            .map(|b| b.full_charged_capacity as f64 / 1000.0);
+    }
+    // Only internal panels implement WmiMonitorBrightness
+    report.display_brightness = wmi
+        .query_first::<MonitorBrightness>(WMI, "SELECT CurrentBrightness FROM WmiMonitorBrightness")
+        .map(|b| b.current_brightness as f64 / 100.0);
+    Ok(report)
+}
+
+#[cfg(target_os = "linux")]
+fn energy_report_linux() -> EnergyReport {
+    use std::path::Path;
+
+    let read = |dir: &Path, name: &str| {
+        std::fs::read_to_string(dir.join(name)).ok().and_then(|s| s.trim().parse::<f64>().ok())
+    };
+    let devices = |class: &str| {
+        let mut paths: Vec<_> = std::fs::read_dir(class)
+            .map(|entries| entries.flatten().map(|entry| entry.path()).collect())
+            .unwrap_or_default();
+        paths.sort();
+        paths
+    };
+
+    let mut report = EnergyReport::default();
+    let battery = devices("/sys/class/power_supply").into_iter().find(|path| {
+        std::fs::read_to_string(path.join("type")).is_ok_and(|t| t.trim() == "Battery")
+    });
+    if let Some(battery) = battery {
+        report.on_battery = std::fs::read_to_string(battery.join("status")).is_ok_and(|s| s.trim() == "Discharging");
+        // Drivers report energy (µWh, µW) or charge (µAh, µA) with a voltage (µV)
+        let voltage = read(&battery, "voltage_now").or_else(|| read(&battery, "voltage_min_design"));
+        report.full_charge_wh = read(&battery, "energy_full")
+            .map(|uwh| uwh / 1e6)
+            .or_else(|| Some(read(&battery, "charge_full")? * read(&battery, "voltage_min_design")? / 1e12));
+        report.system_power_watts = read(&battery, "power_now")
+            .map(|uw| uw / 1e6)
+            .or_else(|| Some(read(&battery, "current_now")? * voltage? / 1e12))
+            .filter(|_| report.on_battery);
+    }
+
+    report.display_brightness = devices("/sys/class/backlight").into_iter().find_map(|backlight| {
+        let max = read(&backlight, "max_brightness").filter(|max| *max > 0.0)?;
+        Some(read(&backlight, "brightness")? / max)
+    });
+    report
+}
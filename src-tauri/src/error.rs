@@ -0,0 +1,62 @@
+// Errors returned by probe commands. Serialized with a `kind` tag, e.g.
+// {"kind": "timeout", "source": "system_profiler", "seconds": 15}, so the UI
+// can tell "retry" or "run as admin" apart from hardware that simply isn't
+// there, which commands report as Ok(None) or an empty list.
+
+use serde::Serialize;
+
+// Every probe has a macOS implementation, so nothing is Unsupported there
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+#[cfg_attr(target_os = "macos", allow(dead_code))]
+pub enum QuickscanError {
+    // `source` is the program, WMI class or file the probe read
+    CommandFailed { source: String, message: String },
+    ParseError { source: String, message: String },
+    Unsupported { feature: String },
+    PermissionDenied { source: String, message: String },
+    Timeout { source: String, seconds: u64 },
+    // Killed by the watchdog; `resource` is "memory" or "cpu"
+    ResourceLimit { source: String, resource: String },
+    // A privacy-sensitive check the operator hasn't allowed yet (see consent.rs)
+    ConsentRequired { scope: String },
+}
+
+impl QuickscanError {
+    pub fn command_failed(source: &str, message: impl ToString) -> Self {
+        QuickscanError::CommandFailed { source: source.to_string(), message: message.to_string() }
+    }
+
+    pub fn parse(source: &str, message: impl ToString) -> Self {
+        QuickscanError::ParseError { source: source.to_string(), message: message.to_string() }
+    }
+
+    #[cfg_attr(target_os = "macos", allow(dead_code))]
+    pub fn unsupported(feature: &str) -> Self {
+        QuickscanError::Unsupported { feature: feature.to_string() }
+    }
+
+    pub fn io(source: &str, error: std::io::Error) -> Self {
+        if error.kind() == std::io::ErrorKind::PermissionDenied {
+            QuickscanError::PermissionDenied { source: source.to_string(), message: error.to_string() }
+        } else {
+            QuickscanError::command_failed(source, error)
+        }
+    }
+}
+
+impl std::fmt::Display for QuickscanError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QuickscanError::CommandFailed { source, message } => write!(f, "{}: {}", source, message),
+            QuickscanError::ParseError { source, message } => write!(f, "{}: unexpected output: {}", source, message),
+            QuickscanError::Unsupported { feature } => write!(f, "{} is not supported on this platform", feature),
+            QuickscanError::PermissionDenied { source, message } => write!(f, "{}: permission denied: {}", source, message),
+            QuickscanError::Timeout { source, seconds } => write!(f, "{}: timed out after {}s", source, seconds),
+            QuickscanError::ResourceLimit { source, resource } => write!(f, "{}: killed for excessive {} use", source, resource),
+            QuickscanError::ConsentRequired { scope } => write!(f, "{} access needs the operator's consent", scope),
+        }
+    }
+}
+
+impl std::error::Error for QuickscanError {}
@@ -0,0 +1,176 @@
+// Evidence mode: the raw output of every external command and WMI query a
+// scan ran, kept gzip-compressed next to the scan in
+// evidence/<report id>.json.gz in the app data directory. A disputed or
+// surprising indicator can then be re-derived from what the tools really
+// printed (ioreg text, smartctl JSON, the WMI rows) without the machine.
+//
+// Off unless the shop sets "evidence_mode": true in preferences.json: the
+// outputs carry serials, account names and network names. The frontend calls
+// `start_evidence` before the first check and `save_evidence` with the report
+// id once the report exists. The lab binary collects through the same
+// recorder.
+
+use crate::error::QuickscanError;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::Serialize;
+use serde_json::Value;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::Manager;
+
+pub const EVIDENCE_DIR: &str = "evidence";
+
+#[derive(Serialize)]
+pub struct RawOutput {
+    pub source: &'static str, // "command" or "wmi"
+    pub invocation: String, // "system_profiler -json SPHardwareDataType", or "ROOT\CIMV2: SELECT …"
+    pub duration_ms: u64,
+    pub exit_code: Option<i32>,
+    pub output: Value, // stdout as text, or the WMI rows with every property
+    pub stderr: Option<String>,
+    pub error: Option<Value>, // the QuickscanError when it didn't run or the query failed
+}
+
+#[derive(Serialize)]
+struct EvidenceBundle {
+    report_id: String,
+    app_version: &'static str,
+    saved_at: String,
+    raw: Vec<RawOutput>, // in the order they ran
+}
+
+// Some while a scan or a lab run is collecting
+static CAPTURES: Mutex<Option<Vec<RawOutput>>> = Mutex::new(None);
+
+fn record(capture: RawOutput) {
+    if let Some(captures) = CAPTURES.lock().unwrap().as_mut() {
+        captures.push(capture);
+    }
+}
+
+pub fn recording() -> bool {
+    CAPTURES.lock().unwrap().is_some()
+}
+
+// Drops whatever an earlier scan left uncollected
+pub fn start() {
+    *CAPTURES.lock().unwrap() = Some(vec![]);
+}
+
+// None when nothing was recording
+pub fn take() -> Option<Vec<RawOutput>> {
+    CAPTURES.lock().unwrap().take()
+}
+
+// Called by process::run_cmd_with_timeout for every command it runs
+pub fn record_command(command: &Command, elapsed: Duration, result: &Result<Output, QuickscanError>) {
+    if !recording() {
+        return;
+    }
+    let mut invocation = command.get_program().to_string_lossy().to_string();
+    for arg in command.get_args() {
+        invocation.push(' ');
+        invocation.push_str(&arg.to_string_lossy());
+    }
+    let (exit_code, output, stderr, error) = match result {
+        Ok(output) => (
+            output.status.code(),
+            Value::String(String::from_utf8_lossy(&output.stdout).to_string()),
+            Some(String::from_utf8_lossy(&output.stderr).to_string()).filter(|stderr| !stderr.is_empty()),
+            None,
+        ),
+        Err(e) => (None, Value::Null, None, serde_json::to_value(e).ok()),
+    };
+    record(RawOutput { source: "command", invocation, duration_ms: elapsed.as_millis() as u64, exit_code, output, stderr, error });
+}
+
+// Called by the WMI worker with every property of the rows a query returned,
+// before the probe picks out the ones it wants
+#[cfg(target_os = "windows")]
+pub fn record_wmi(namespace: &str, wql: &str, elapsed: Duration, rows: Result<Value, QuickscanError>) {
+    let (output, error) = match rows {
+        Ok(rows) => (rows, None),
+        Err(e) => (Value::Null, serde_json::to_value(e).ok()),
+    };
+    record(RawOutput {
+        source: "wmi",
+        invocation: format!("{}: {}", namespace, wql),
+        duration_ms: elapsed.as_millis() as u64,
+        exit_code: None,
+        output,
+        stderr: None,
+        error,
+    });
+}
+
+// The id becomes a file name
+fn evidence_path(app: &tauri::AppHandle, report_id: &str) -> Result<PathBuf, QuickscanError> {
+    if report_id.is_empty() || !report_id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err(QuickscanError::parse(EVIDENCE_DIR, format!("invalid report id: {}", report_id)));
+    }
+    let data_dir = app.path().app_data_dir().map_err(|e| QuickscanError::command_failed(EVIDENCE_DIR, e))?;
+    Ok(data_dir.join(EVIDENCE_DIR).join(format!("{}.json.gz", report_id)))
+}
+
+fn write_bundle(path: &Path, report_id: &str, raw: Vec<RawOutput>) -> Result<(), QuickscanError> {
+    let bundle = EvidenceBundle {
+        report_id: report_id.to_string(),
+        app_version: env!("CARGO_PKG_VERSION"),
+        saved_at: chrono::Local::now().to_rfc3339(),
+        raw,
+    };
+    let content = serde_json::to_vec(&bundle).map_err(|e| QuickscanError::parse(EVIDENCE_DIR, e))?;
+    let io = |e| QuickscanError::io(EVIDENCE_DIR, e);
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+    encoder.write_all(&content).map_err(io)?;
+    let compressed = encoder.finish().map_err(io)?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(io)?;
+    }
+    std::fs::write(path, compressed).map_err(io)
+}
+
+// None when the scan wasn't recorded
+fn read_bundle(path: &Path) -> Result<Option<Value>, QuickscanError> {
+    let file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(QuickscanError::io(EVIDENCE_DIR, e)),
+    };
+    let mut content = String::new();
+    GzDecoder::new(file).read_to_string(&mut content).map_err(|e| QuickscanError::parse(EVIDENCE_DIR, e))?;
+    serde_json::from_str(&content).map(Some).map_err(|e| QuickscanError::parse(EVIDENCE_DIR, e))
+}
+
+// Whether this scan is recorded, as the shop's preferences say
+#[tauri::command]
+pub fn start_evidence(app: tauri::AppHandle) -> bool {
+    if !crate::preferences::evidence_mode(&app) {
+        let _ = take();
+        return false;
+    }
+    start();
+    true
+}
+
+// Where the scan's evidence went, or None when it wasn't recorded
+#[tauri::command]
+pub fn save_evidence(app: tauri::AppHandle, report_id: String) -> Result<Option<PathBuf>, QuickscanError> {
+    let path = evidence_path(&app, &report_id)?;
+    let Some(raw) = take() else {
+        return Ok(None);
+    };
+    write_bundle(&path, &report_id, raw)?;
+    Ok(Some(path))
+}
+
+// The stored bundle, decompressed; None for a scan without evidence
+#[tauri::command]
+pub fn get_evidence(app: tauri::AppHandle, report_id: String) -> Result<Option<Value>, QuickscanError> {
+    read_bundle(&evidence_path(&app, &report_id)?)
+}
@@ -0,0 +1,144 @@
+// The full scan as one self-describing JSON document (`export_report`), for
+// inventory systems that would otherwise call every command and stitch the
+// results together themselves. The envelope is versioned and described by a
+// JSON Schema published with the download page (docs/schema/); each check
+// sits under `checks` by name with its result, or {"error": {...}} as the
+// commands return it, so a check that failed doesn't cost the rest.
+//
+// The checks that read the system logs are only run when the operator has
+// allowed it; otherwise they carry the consent_required error. The machine is
+// identified by its serial number and by the fingerprint the scan log keys it
+// by when the shop doesn't store raw identifiers (fingerprint.rs), so either
+// kind of inventory can match it.
+//
+// The probe lists are shared with the headless and lab binaries.
+
+use crate::consent::ConsentScope;
+use crate::error::QuickscanError;
+use crate::probe_scheduler::ProbeScheduler;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use tauri::Manager;
+
+// Bumped whenever a field of the envelope changes meaning; a check's own
+// fields follow the app version
+pub const SCHEMA_VERSION: u32 = 1;
+const SCHEMA_URL: &str = "https://f82525086-droid.github.io/quickscan/schema/report-v1.json";
+
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    Json, // indented
+    JsonCompact, // one line, for appending to a log of scans
+}
+
+#[derive(Serialize)]
+struct Machine {
+    serial_number: Option<String>,
+    model_identifier: Option<String>,
+    fingerprint: Option<String>,
+}
+
+#[derive(Serialize)]
+pub(crate) struct ExportDocument {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    schema_version: u32,
+    app_version: &'static str,
+    scanned_at: String,
+    machine: Machine,
+    checks: Value,
+}
+
+// Each probe's result under its name, or {"error": {...}} when it failed
+pub(crate) fn probe<T: Serialize>(report: &mut Map<String, Value>, name: &str, result: Result<T, QuickscanError>) {
+    let value = match result {
+        Ok(value) => serde_json::to_value(value).unwrap_or(Value::Null),
+        Err(error) => json!({ "error": error }),
+    };
+    report.insert(name.to_string(), value);
+}
+
+// What the machine is built from; all the headless scan can trust
+pub(crate) fn hardware(report: &mut Map<String, Value>) {
+    probe(report, "hardware", Ok(crate::hardware_info()));
+    probe(report, "model", crate::device_model::device_model());
+    probe(report, "board", crate::board::board_info());
+    probe(report, "component_serials", crate::component_serials::component_serials());
+    probe(report, "memory_modules", crate::memory_modules::memory_modules());
+    probe(report, "storage", crate::storage_health());
+    probe(report, "battery", crate::battery_info());
+    probe(report, "gpus", crate::gpu::gpu_info());
+    probe(report, "displays", crate::display::display_info());
+    probe(report, "input_devices", crate::input_devices::input_devices());
+    probe(report, "network_adapters", crate::network_adapters::network_adapters());
+    probe(report, "genuine_parts", Ok(crate::genuine_parts::genuine_parts()));
+    probe(report, "clock", Ok(crate::clock::clock_check()));
+}
+
+// What the installed system knows about the machine and its previous owner,
+// short of its logs
+pub(crate) fn installed_system(report: &mut Map<String, Value>) {
+    probe(report, "apple_overview", crate::apple_overview::apple_system_overview());
+    probe(report, "os_install", crate::os_install::os_install_history());
+    probe(report, "region", crate::region::region_info());
+    probe(report, "mdm", crate::mdm::mdm_status());
+    probe(report, "security", crate::security::security_info());
+    probe(report, "encryption", crate::encryption::encryption_status());
+    probe(report, "activation_lock", crate::activation_lock::activation_lock_status());
+    probe(report, "windows_activation", crate::windows_activation::windows_activation());
+    probe(report, "accounts", crate::accounts::signed_in_accounts());
+    probe(report, "data_remnants", crate::data_remnants::data_remnants());
+    probe(report, "bluetooth", crate::bluetooth::bluetooth_info());
+    probe(report, "wifi", crate::wifi::wifi_info());
+    probe(report, "usb", crate::usb::usb_devices());
+    probe(report, "hardware_devices", crate::hardware_watch::hardware_devices());
+    probe(report, "audio", crate::audio::audio_devices());
+    probe(report, "cameras", crate::camera::camera_info());
+    probe(report, "touch_bar", crate::touch_bar::touch_bar_info());
+    probe(report, "thermal", crate::thermal::thermal_info());
+    probe(report, "energy", crate::energy::energy_report());
+    probe(report, "usage", crate::usage_estimate::usage_estimate());
+}
+
+#[tauri::command]
+pub async fn export_report(app: tauri::AppHandle, probes: tauri::State<'_, ProbeScheduler>, format: ExportFormat) -> Result<String, QuickscanError> {
+    probes.run(move || {
+        let document = collect(&app);
+        match format {
+            ExportFormat::Json => serde_json::to_string_pretty(&document),
+            ExportFormat::JsonCompact => serde_json::to_string(&document),
+        }
+        .map_err(|e| QuickscanError::parse("export_report", e))
+    })
+    .await
+}
+
+pub(crate) fn collect(app: &tauri::AppHandle) -> ExportDocument {
+    let scanned_at = chrono::Local::now().to_rfc3339();
+    let mut checks = Map::new();
+    hardware(&mut checks);
+    installed_system(&mut checks);
+    let logs = || crate::consent::require(app, ConsentScope::Logs);
+    probe(&mut checks, "crash_history", logs().and_then(|()| crate::crash_history::crash_history()));
+    probe(&mut checks, "rtc_battery", logs().and_then(|()| crate::rtc_battery::rtc_battery()));
+    probe(&mut checks, "power_events", logs().and_then(|()| crate::sleep::power_events()));
+    probe(&mut checks, "refurbishment", Ok(crate::refurbishment_check(app, None)));
+
+    let model_identifier = checks.get("model").and_then(|model| model.get("identifier")).and_then(Value::as_str).map(str::to_string);
+    let serial = crate::get_serial_number();
+    let machine = Machine {
+        serial_number: (serial != "Unknown").then_some(serial),
+        model_identifier,
+        fingerprint: app.path().app_data_dir().ok().and_then(|data_dir| crate::fingerprint::device_fingerprint(&data_dir).ok()),
+    };
+    ExportDocument {
+        schema: SCHEMA_URL,
+        schema_version: SCHEMA_VERSION,
+        app_version: env!("CARGO_PKG_VERSION"),
+        scanned_at,
+        machine,
+        checks: Value::Object(checks),
+    }
+}
+
@@ -0,0 +1,88 @@
+// A stable stand-in for the device serial, for shops that set
+// "store_raw_identifiers": false in preferences.json. The part history and
+// the scan log then key machines by a salted SHA-256 of the serial number,
+// and keep component serials only as hashes. A re-scan still finds the
+// earlier record and a swapped part still shows up, but no serial is written
+// to disk.
+//
+// The key leaves the parts out on purpose: with the logic board serial or the
+// Wi-Fi MAC in it, swapping either would give the machine a new key, the
+// earlier record wouldn't be found and the swap would go unreported. Only
+// when the firmware has no serial does the key fall back to the enclosure's
+// or the board's own.
+//
+// The salt is generated on first use in `fingerprint_salt` in the app data
+// directory. Stations that should recognise each other's machines share it.
+
+use crate::component_serials::{ComponentSerials, StorageSerial};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use std::sync::OnceLock;
+
+pub const SALT_FILE: &str = "fingerprint_salt";
+
+// Hex digits kept from each hash; 128 bits is plenty to tell machines apart
+const HASH_LENGTH: usize = 32;
+
+// Reading the serial goes through WMI on Windows, so once per session
+static FINGERPRINT: OnceLock<String> = OnceLock::new();
+
+pub fn load_salt(data_dir: &Path) -> Result<String, String> {
+    let path = data_dir.join(SALT_FILE);
+    match std::fs::read_to_string(&path) {
+        Ok(salt) if salt.trim().is_empty() => return Err(format!("{} is empty", SALT_FILE)),
+        Ok(salt) => return Ok(salt.trim().to_string()),
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.to_string()),
+        Err(_) => {}
+    }
+    let mut bytes = [0u8; 32];
+    getrandom::getrandom(&mut bytes).map_err(|e| e.to_string())?;
+    let salt = hex(&bytes);
+    std::fs::create_dir_all(data_dir).map_err(|e| e.to_string())?;
+    std::fs::write(&path, &salt).map_err(|e| e.to_string())?;
+    Ok(salt)
+}
+
+// Case-insensitive, like the serial comparisons in part_history
+pub fn hash(salt: &str, value: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(salt.as_bytes());
+    hasher.update([0]);
+    hasher.update(value.trim().to_uppercase().as_bytes());
+    let mut digest = hex(&hasher.finalize());
+    digest.truncate(HASH_LENGTH);
+    digest
+}
+
+// This machine's key, in place of its serial number
+pub fn device_fingerprint(data_dir: &Path) -> Result<String, String> {
+    if let Some(fingerprint) = FINGERPRINT.get() {
+        return Ok(fingerprint.clone());
+    }
+    let salt = load_salt(data_dir)?;
+    let serial = Some(crate::get_serial_number())
+        .filter(|serial| serial != "Unknown")
+        .or_else(|| crate::board::board_info().ok().and_then(|board| board.chassis_serial.or(board.serial)))
+        .ok_or_else(|| "no serial number to key the machine by".to_string())?;
+    Ok(FINGERPRINT.get_or_init(|| hash(&salt, &serial)).clone())
+}
+
+// The same serials, each replaced by its hash; disk models stay readable
+pub fn hash_serials(salt: &str, serials: ComponentSerials) -> ComponentSerials {
+    let hash_optional = |value: Option<String>| value.map(|value| hash(salt, &value));
+    ComponentSerials {
+        logic_board: hash_optional(serials.logic_board),
+        battery: hash_optional(serials.battery),
+        storage: serials
+            .storage
+            .into_iter()
+            .map(|disk| StorageSerial { serial: hash(salt, &disk.serial), model: disk.model })
+            .collect(),
+        display: hash_optional(serials.display),
+        wifi_mac: hash_optional(serials.wifi_mac),
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
@@ -0,0 +1,101 @@
+// Whether the storage, display, battery and keyboard are the ones the machine
+// shipped with, for `check_genuine_parts` and the third-party indicators in
+// `check_refurbishment`. Each verdict comes with its evidence, the model or
+// maker the part reports, so a buyer can check it. Only Macs narrow their
+// parts down to one supplier; on other machines storage and panels come from
+// many makers, so those stay unknown and just show what is fitted.
+
+use crate::error::QuickscanError;
+use crate::probe_scheduler::ProbeScheduler;
+use serde::Serialize;
+
+#[derive(Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Genuine,
+    ThirdParty,
+    Unknown,
+}
+
+#[derive(Serialize)]
+pub struct PartVerdict {
+    pub part: &'static str, // "storage", "display", "battery" or "keyboard"
+    pub verdict: Verdict,
+    pub evidence: String, // "SAMSUNG SSD 970 EVO", "BOE", "SMP"; empty when the part says nothing
+}
+
+#[tauri::command]
+pub async fn check_genuine_parts(probes: tauri::State<'_, ProbeScheduler>) -> Result<Vec<PartVerdict>, QuickscanError> {
+    probes.run(|| Ok(genuine_parts())).await
+}
+
+// One entry per internal disk and panel; a part that wasn't found is left out
+pub fn genuine_parts() -> Vec<PartVerdict> {
+    let apple = cfg!(target_os = "macos");
+    let mut parts = vec![];
+
+    let disks = crate::storage_health().unwrap_or_default();
+    for disk in disks.into_iter().filter(|disk| disk.is_internal) {
+        let verdict = if !apple || disk.model == "Unknown" {
+            Verdict::Unknown
+        } else if ["APPLE SSD", "Apple SSD", "AP", "Macintosh"].iter().any(|name| disk.model.contains(name)) {
+            Verdict::Genuine
+        } else {
+            Verdict::ThirdParty
+        };
+        parts.push(PartVerdict { part: "storage", verdict, evidence: disk.model });
+    }
+
+    let panels = crate::display::display_info().unwrap_or_default();
+    for panel in panels.into_iter().filter(|panel| panel.is_internal) {
+        let verdict = match panel.vendor.as_str() {
+            _ if !apple => Verdict::Unknown,
+            "" => Verdict::Unknown,
+            "Apple" => Verdict::Genuine,
+            _ => Verdict::ThirdParty,
+        };
+        parts.push(PartVerdict { part: "display", verdict, evidence: panel.vendor });
+    }
+
+    if let Ok(Some(battery)) = crate::battery_info() {
+        let system_vendor = crate::device_model::device_model().ok().and_then(|model| model.manufacturer);
+        let manufacturer = battery.manufacturer.unwrap_or_default();
+        let verdict = match crate::battery_pack::third_party_maker(&manufacturer, system_vendor.as_deref()) {
+            Some(true) => Verdict::ThirdParty,
+            Some(false) => Verdict::Genuine,
+            None => Verdict::Unknown,
+        };
+        parts.push(PartVerdict { part: "battery", verdict, evidence: manufacturer });
+    }
+
+    if let Some(keyboard) = internal_keyboard() {
+        parts.push(keyboard);
+    }
+    parts
+}
+
+// A Mac's built-in keyboard sits behind Apple's own controller, vendor 0x05AC
+#[cfg(target_os = "macos")]
+fn internal_keyboard() -> Option<PartVerdict> {
+    const APPLE_VENDOR_ID: i64 = 0x05AC;
+    let keyboard = crate::iokit::all_service_properties("IOHIDDevice").into_iter().find(|device| {
+        let number = |key: &str| device.get(key).and_then(|v| v.as_i64());
+        device.get("Built-In").and_then(|v| v.as_bool()) == Some(true)
+            && number("PrimaryUsagePage") == Some(1)
+            && number("PrimaryUsage") == Some(6)
+    })?;
+    let verdict = match keyboard.get("VendorID").and_then(|v| v.as_i64()) {
+        Some(APPLE_VENDOR_ID) => Verdict::Genuine,
+        Some(_) => Verdict::ThirdParty,
+        None => Verdict::Unknown,
+    };
+    let evidence = crate::iokit::string_property(&keyboard, "Product").unwrap_or_default();
+    Some(PartVerdict { part: "keyboard", verdict, evidence })
+}
+
+#[cfg(not(target_os = "macos"))]
+fn internal_keyboard() -> Option<PartVerdict> {
+    let devices = crate::input_devices::input_devices().ok()?;
+    let keyboard = devices.devices.into_iter().find(|device| device.kind == "keyboard" && device.built_in == Some(true))?;
+    Some(PartVerdict { part: "keyboard", verdict: Verdict::Unknown, evidence: keyboard.name })
+}
@@ -0,0 +1,270 @@
+// GPU inventory (`get_gpu_info`) plus the reference data used to spot failing
+// or misconfigured graphics.
+
+use crate::error::QuickscanError;
+use crate::probe_scheduler::ProbeScheduler;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct GpuInfo {
+    pub model: String,
+    pub vendor: String,
+    pub vram_mb: Option<u64>, // None for unified memory (Apple silicon) or when not reported
+    pub driver_version: Option<String>,
+    pub api_version: Option<String>, // Metal family on macOS
+    pub is_integrated: bool,
+}
+
+// Smallest VRAM configuration each GPU shipped with, in MB. Matched by the
+// longest key contained in the reported model name.
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+const MIN_VRAM_MB: &[(&str, u64)] = &[
+    // Mac discrete GPUs
+    ("Radeon Pro 450", 2048),
+    ("Radeon Pro 455", 2048),
+    ("Radeon Pro 460", 4096),
+    ("Radeon Pro 555", 2048),
+    ("Radeon Pro 560", 4096),
+    ("Radeon Pro 555X", 4096),
+    ("Radeon Pro 560X", 4096),
+    ("Radeon Pro Vega 16", 4096),
+    ("Radeon Pro Vega 20", 4096),
+    ("Radeon Pro 5300M", 4096),
+    ("Radeon Pro 5500M", 4096),
+    ("Radeon Pro 5600M", 8192),
+    ("GeForce GT 750M", 2048),
+    // Common Windows laptop GPUs
+    ("GeForce GTX 1050", 2048),
+    ("GeForce GTX 1050 Ti", 4096),
+    ("GeForce GTX 1650", 4096),
+    ("GeForce GTX 1660 Ti", 6144),
+    ("GeForce RTX 2060", 6144),
+    ("GeForce RTX 2070", 8192),
+    ("GeForce RTX 3050", 4096),
+    ("GeForce RTX 3060", 6144),
+    ("GeForce RTX 3070", 8192),
+    ("GeForce RTX 4050", 6144),
+    ("GeForce RTX 4060", 8192),
+    ("GeForce RTX 4070", 8192),
+];
+
+// Windows falls back to these when the vendor driver is missing or has crashed
+#[cfg(target_os = "windows")]
+const FALLBACK_ADAPTERS: &[&str] = &[
+    "Microsoft Basic Display Adapter",
+    "Microsoft Basic Render Driver",
+    "Standard VGA Graphics Adapter",
+];
+
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+pub fn expected_min_vram_mb(model: &str) -> Option<u64> {
+    let model = model.to_lowercase();
+    MIN_VRAM_MB
+        .iter()
+        .filter(|(name, _)| model.contains(&name.to_lowercase()))
+        .max_by_key(|(name, _)| name.len())
+        .map(|(_, vram)| *vram)
+}
+
+#[cfg(target_os = "windows")]
+pub fn is_fallback_adapter(name: &str) -> bool {
+    FALLBACK_ADAPTERS.iter().any(|fallback| name.contains(fallback))
+}
+
+// Parses system_profiler sizes such as "8 GB" or "1536 MB"
+#[cfg(target_os = "macos")]
+pub fn parse_vram_mb(value: &str) -> Option<u64> {
+    let mut parts = value.split_whitespace();
+    let amount: u64 = parts.next()?.parse().ok()?;
+    match parts.next()?.to_uppercase().as_str() {
+        "GB" => Some(amount * 1024),
+        "MB" => Some(amount),
+        _ => None,
+    }
+}
+
+// Integrated parts share system memory; names are the only portable hint
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+fn looks_integrated(vendor: &str, model: &str) -> bool {
+    let vendor = vendor.to_lowercase();
+    let model = model.to_lowercase();
+    if vendor.contains("intel") {
+        return !model.contains("arc");
+    }
+    // AMD APUs report e.g. "AMD Radeon(TM) Graphics" or "Radeon Vega 8 Graphics"
+    vendor.contains("amd")
+        && (model.ends_with("radeon(tm) graphics")
+            || model.ends_with("radeon graphics")
+            || model.contains("vega 8")
+            || model.contains("vega 11"))
+}
+
+#[tauri::command]
+pub async fn get_gpu_info(probes: tauri::State<'_, ProbeScheduler>) -> Result<Vec<GpuInfo>, QuickscanError> {
+    probes.run(gpu_info).await
+}
+
+pub fn gpu_info() -> Result<Vec<GpuInfo>, QuickscanError> {
+    #[cfg(target_os = "macos")]
+    {
+        get_gpu_info_macos()
+    }
+    #[cfg(target_os = "windows")]
+    {
+        get_gpu_info_windows()
+    }
+    #[cfg(target_os = "linux")]
+    {
+        get_gpu_info_linux()
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        Err(QuickscanError::unsupported("gpu_info"))
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn get_gpu_info_macos() -> Result<Vec<GpuInfo>, QuickscanError> {
+    let mut command = std::process::Command::new("system_profiler");
+    let output = crate::process::run_cmd(command.args(["SPDisplaysDataType", "-json"]))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json = serde_json::from_str::<serde_json::Value>(&stdout)
+        .map_err(|e| QuickscanError::parse("system_profiler", e))?;
+
+    let field = |item: &serde_json::Value, key: &str| item.get(key).and_then(|v| v.as_str()).map(|s| s.to_string());
+    let gpus = json.get("SPDisplaysDataType")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .map(|item| {
+            // Vendor looks like "sppci_vendor_Apple" or "AMD (0x1002)"
+            let vendor = field(item, "spdisplays_vendor").unwrap_or_default();
+            let vendor = vendor.trim_start_matches("sppci_vendor_").split(" (").next().unwrap_or("").to_string();
+            let bus = field(item, "sppci_bus").unwrap_or_default();
+            GpuInfo {
+                model: field(item, "sppci_model").unwrap_or_default(),
+                vendor,
+                vram_mb: field(item, "spdisplays_vram")
+                    .or_else(|| field(item, "spdisplays_vram_shared"))
+                    .and_then(|v| parse_vram_mb(&v)),
+                // GPU drivers ship with the OS and have no separate version
+                driver_version: None,
+                // e.g. "spdisplays_metal3" -> "Metal 3"
+                api_version: field(item, "spdisplays_mtlgpufamilysupport")
+                    .map(|v| v.trim_start_matches("spdisplays_").replace("metal", "Metal ")),
+                is_integrated: bus.contains("builtin"),
+            }
+        })
+        .collect();
+    Ok(gpus)
+}
+
+#[cfg(target_os = "windows")]
+fn get_gpu_info_windows() -> Result<Vec<GpuInfo>, QuickscanError> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct VideoController {
+        name: Option<String>,
+        adapter_compatibility: Option<String>,
+        adapter_ram: Option<u32>,
+        driver_version: Option<String>,
+    }
+
+    let gpus = crate::wmi_provider::WmiProvider::global()
+        .query::<VideoController>(
+            crate::wmi_provider::CIMV2,
+            "SELECT Name, AdapterCompatibility, AdapterRAM, DriverVersion FROM Win32_VideoController",
+        )?
+        .into_iter()
+        .map(|controller| {
+            let model = controller.name.unwrap_or_default();
+            let vendor = controller.adapter_compatibility.unwrap_or_default();
+            GpuInfo {
+                is_integrated: looks_integrated(&vendor, &model),
+                model,
+                vendor,
+                // 32-bit field: cards with 4GB or more report about 4GB
+                vram_mb: controller.adapter_ram.map(|bytes| bytes as u64 / (1024 * 1024)),
+                driver_version: controller.driver_version,
+                api_version: None,
+            }
+        })
+        .collect();
+    Ok(gpus)
+}
+
+#[cfg(target_os = "linux")]
+fn get_gpu_info_linux() -> Result<Vec<GpuInfo>, QuickscanError> {
+    let entries = std::fs::read_dir("/sys/class/drm").map_err(|e| QuickscanError::io("/sys/class/drm", e))?;
+    let mut cards: Vec<_> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            // card0, card1, ... but not connectors like card0-eDP-1
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .and_then(|n| n.strip_prefix("card"))
+                .is_some_and(|n| n.chars().all(|c| c.is_ascii_digit()))
+        })
+        .collect();
+    cards.sort();
+
+    let gpus = cards
+        .into_iter()
+        .filter_map(|card| {
+            let device = card.join("device");
+            let read = |name: &str| std::fs::read_to_string(device.join(name)).ok().map(|s| s.trim().to_string());
+            let vendor_id = read("vendor")?;
+            let vendor = match vendor_id.as_str() {
+                "0x8086" => "Intel",
+                "0x1002" => "AMD",
+                "0x10de" => "NVIDIA",
+                "0x1414" => "Microsoft",
+                "0x15ad" => "VMware",
+                "0x1af4" => "Red Hat",
+                other => other,
+            }
+            .to_string();
+            let driver = std::fs::read_link(device.join("driver"))
+                .ok()
+                .and_then(|link| link.file_name().map(|n| n.to_string_lossy().to_string()));
+            let slot = std::fs::canonicalize(&device)
+                .ok()
+                .and_then(|path| path.file_name().map(|n| n.to_string_lossy().to_string()));
+
+            // lspci has the marketing name; fall back to the PCI device id
+            let model = slot
+                .as_deref()
+                .and_then(lspci_device_name)
+                .or_else(|| read("device").map(|id| format!("{} {}", vendor, id)))
+                .unwrap_or_default();
+
+            Some(GpuInfo {
+                is_integrated: looks_integrated(&vendor, &model),
+                vram_mb: read("mem_info_vram_total")
+                    .and_then(|bytes| bytes.parse::<u64>().ok())
+                    .map(|bytes| bytes / (1024 * 1024)),
+                driver_version: driver.as_deref().and_then(|d| {
+                    std::fs::read_to_string(format!("/sys/module/{}/version", d))
+                        .ok()
+                        .map(|v| format!("{} {}", d, v.trim()))
+                        .or_else(|| Some(d.to_string()))
+                }),
+                api_version: None,
+                model,
+                vendor,
+            })
+        })
+        .collect();
+    Ok(gpus)
+}
+
+#[cfg(target_os = "linux")]
+fn lspci_device_name(slot: &str) -> Option<String> {
+    let output = crate::process::run_cmd(std::process::Command::new("lspci").args(["-vmm", "-s", slot])).ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    stdout
+        .lines()
+        .find_map(|line| line.strip_prefix("Device:"))
+        .map(|name| name.trim().to_string())
+}
@@ -0,0 +1,363 @@
+// A letter grade for a finished report (`compute_device_grade`), so that two
+// refurbishers looking at the same machine put it in the same bin. Four
+// categories are scored out of 100 and weighted into one score:
+//
+//   battery             health, cycles, drain while asleep
+//   storage             SMART status, wear, reallocated sectors
+//   cosmetic_proxy      what use leaves visible: dead pixels, failed keys,
+//                       trackpad faults, panel wear, heavy use
+//   hardware_integrity  refurbishment indicators, memory errors, repeated
+//                       crashes, stalled fans
+//
+// Every deduction is a rule with an id the frontend translates, so the grade
+// can always be explained. Some findings also cap the grade whatever the
+// score: a failing drive or a machine still locked to someone can't be an A.
+// The thresholds and weights are the shop's (scan_config.rs); custom scripts
+// can add deductions and caps of their own (scripting.rs).
+
+use crate::scan_config::{BatteryThresholds, ScanConfig, StorageThresholds};
+use crate::scripting::{GradeModifier, GRADE_CATEGORIES};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Grade {
+    A,
+    B,
+    C,
+    D,
+}
+
+#[derive(Serialize)]
+pub struct DeviceGrade {
+    pub grade: Grade,
+    pub score: u32, // 0-100
+    pub categories: Vec<CategoryScore>,
+    pub rules: Vec<FiredRule>,
+}
+
+#[derive(Serialize)]
+pub struct CategoryScore {
+    pub category: &'static str,
+    pub score: u32,
+    pub weight: u32,
+}
+
+#[derive(Serialize)]
+pub struct FiredRule {
+    pub id: &'static str, // "battery_wear", "storage_smart_failing"…
+    pub category: &'static str,
+    pub points: u32, // taken off the category's 100
+    pub cap: Option<Grade>, // the best grade the machine can still get
+    pub detail: Option<String>, // the figure behind it, "72%", or an indicator's description
+}
+
+// The parts of the frontend's DetectionReport the grade reads
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct GradeInput {
+    battery: Battery,
+    storage: Storage,
+    power_events: Option<PowerEvents>,
+    interactive: Interactive,
+    panel_aging: Option<PanelAging>,
+    usage: Option<Usage>,
+    refurbishment: Option<Refurbishment>,
+    memory_test: Option<MemoryTest>,
+    crash_history: Option<CrashHistory>,
+    thermal: Option<Thermal>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct Battery {
+    health: f64,
+    #[serde(alias = "cycle_count")]
+    cycle_count: u64,
+    #[serde(alias = "design_capacity")]
+    design_capacity: f64,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct Storage {
+    smart_status: String,
+    reallocated_sectors: Option<u64>,
+    percentage_used: Option<f64>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct PowerEvents {
+    #[serde(alias = "chronic_drain")]
+    chronic_drain: bool,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct Interactive {
+    screen: ScreenTest,
+    keyboard: KeyboardTest,
+    trackpad: TrackpadTest,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct ScreenTest {
+    tested: bool,
+    has_dead_pixel: bool,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct KeyboardTest {
+    tested: bool,
+    failed_keys: Vec<String>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct TrackpadTest {
+    tested: bool,
+    click_working: bool,
+    drag_working: bool,
+    gesture_working: bool,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct PanelAging {
+    score: Option<f64>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct Usage {
+    usage: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct Refurbishment {
+    indicators: Vec<Indicator>,
+    #[serde(alias = "grade_modifiers")]
+    grade_modifiers: Vec<GradeModifier>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct Indicator {
+    detected: bool,
+    description: String, // "key:value", translated by the frontend
+    severity: String,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct MemoryTest {
+    fault_count: u64,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct CrashHistory {
+    total: u64,
+    repeated: bool,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct Thermal {
+    fans: Vec<Fan>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct Fan {
+    stalled: bool,
+}
+
+// The aliases above let the backend's own results fill in the fields whose
+// names differ from the frontend report's
+impl GradeInput {
+    // A full scan's checks (export.rs), which have no interactive tests or
+    // memory test to grade
+    pub fn from_checks(checks: &Value) -> GradeInput {
+        fn check<T: serde::de::DeserializeOwned>(checks: &Value, name: &str) -> Option<T> {
+            let value = checks.get(name).filter(|value| value.get("error").is_none())?;
+            serde_json::from_value(value.clone()).ok()
+        }
+        let disks: Vec<Value> = check(checks, "storage").unwrap_or_default();
+        let storage = disks
+            .iter()
+            .find(|disk| disk["is_boot"] == Value::Bool(true))
+            .or(disks.first())
+            .map(|disk| Storage {
+                smart_status: disk["smart_status"].as_str().unwrap_or_default().to_lowercase(),
+                reallocated_sectors: disk["smart"]["reallocated_sectors"].as_u64(),
+                percentage_used: disk["smart"]["percentage_used"].as_f64(),
+            })
+            .unwrap_or_default();
+        GradeInput {
+            battery: check(checks, "battery").unwrap_or_default(),
+            storage,
+            power_events: check(checks, "power_events"),
+            usage: check(checks, "usage"),
+            refurbishment: check(checks, "refurbishment"),
+            crash_history: check(checks, "crash_history"),
+            thermal: check(checks, "thermal"),
+            ..GradeInput::default()
+        }
+    }
+}
+
+#[tauri::command]
+pub fn compute_device_grade(report: GradeInput) -> DeviceGrade {
+    let config = crate::scan_config::current();
+    let mut rules = vec![];
+    // Desktops report a zero design capacity
+    let has_battery = report.battery.design_capacity > 0.0;
+    if has_battery {
+        battery_rules(&report, &config.battery, &mut rules);
+    }
+    storage_rules(&report.storage, &config.storage, &mut rules);
+    cosmetic_rules(&report, &mut rules);
+    integrity_rules(&report, &mut rules);
+    script_rules(&report, &mut rules);
+
+    let categories: Vec<CategoryScore> = weights(&config)
+        .into_iter()
+        .filter(|(category, _)| has_battery || *category != "battery")
+        .map(|(category, weight)| {
+            let lost: u32 = rules.iter().filter(|rule| rule.category == category).map(|rule| rule.points).sum();
+            CategoryScore { category, score: 100u32.saturating_sub(lost), weight }
+        })
+        .collect();
+    let total_weight: u32 = categories.iter().map(|category| category.weight).sum();
+    let score = categories.iter().map(|category| f64::from(category.score * category.weight)).sum::<f64>()
+        / f64::from(total_weight.max(1));
+
+    let by_score = if score >= config.grade.a {
+        Grade::A
+    } else if score >= config.grade.b {
+        Grade::B
+    } else if score >= config.grade.c {
+        Grade::C
+    } else {
+        Grade::D
+    };
+    let grade = rules.iter().filter_map(|rule| rule.cap).fold(by_score, Grade::max);
+    DeviceGrade { grade, score: score.round() as u32, categories, rules }
+}
+
+fn weights(config: &ScanConfig) -> [(&'static str, u32); 4] {
+    let weights = &config.grade.weights;
+    [
+        ("battery", weights.battery),
+        ("storage", weights.storage),
+        ("cosmetic_proxy", weights.cosmetic_proxy),
+        ("hardware_integrity", weights.hardware_integrity),
+    ]
+}
+
+fn fire(rules: &mut Vec<FiredRule>, id: &'static str, category: &'static str, points: u32, cap: Option<Grade>, detail: Option<String>) {
+    rules.push(FiredRule { id, category, points: points.min(100), cap, detail });
+}
+
+fn battery_rules(report: &GradeInput, thresholds: &BatteryThresholds, rules: &mut Vec<FiredRule>) {
+    let battery = &report.battery;
+    // A few percent is normal for any battery that has been charged at all
+    if battery.health < thresholds.wear_free_percent {
+        let cap = (battery.health < thresholds.fair_percent).then_some(Grade::C);
+        let detail = Some(format!("{}%", battery.health.round()));
+        fire(rules, "battery_wear", "battery", (100.0 - battery.health).round().max(0.0) as u32, cap, detail);
+    }
+    if battery.cycle_count > u64::from(thresholds.cycle_limit) {
+        fire(rules, "battery_cycles_high", "battery", 10, None, Some(battery.cycle_count.to_string()));
+    }
+    if report.power_events.as_ref().is_some_and(|events| events.chronic_drain) {
+        fire(rules, "battery_sleep_drain", "battery", 10, None, None);
+    }
+}
+
+fn storage_rules(storage: &Storage, thresholds: &StorageThresholds, rules: &mut Vec<FiredRule>) {
+    match storage.smart_status.as_str() {
+        "failing" => fire(rules, "storage_smart_failing", "storage", 80, Some(Grade::D), None),
+        "warning" => fire(rules, "storage_smart_warning", "storage", 40, Some(Grade::C), None),
+        _ => {}
+    }
+    if let Some(used) = storage.percentage_used.filter(|used| *used >= thresholds.wear_warning_percent) {
+        let points = if used >= thresholds.wear_limit_percent { 30 } else { 15 };
+        fire(rules, "storage_wear", "storage", points, None, Some(format!("{}%", used.round())));
+    }
+    if let Some(sectors) = storage.reallocated_sectors.filter(|sectors| *sectors > 0) {
+        fire(rules, "storage_reallocated", "storage", 20, None, Some(sectors.to_string()));
+    }
+}
+
+fn cosmetic_rules(report: &GradeInput, rules: &mut Vec<FiredRule>) {
+    let interactive = &report.interactive;
+    if interactive.screen.tested && interactive.screen.has_dead_pixel {
+        fire(rules, "dead_pixels", "cosmetic_proxy", 30, Some(Grade::B), None);
+    }
+    let failed_keys = interactive.keyboard.failed_keys.len() as u32;
+    if interactive.keyboard.tested && failed_keys > 0 {
+        fire(rules, "keys_failed", "cosmetic_proxy", (failed_keys * 10).min(40), Some(Grade::C), Some(failed_keys.to_string()));
+    }
+    let trackpad = &interactive.trackpad;
+    if trackpad.tested && !(trackpad.click_working && trackpad.drag_working && trackpad.gesture_working) {
+        fire(rules, "trackpad_faults", "cosmetic_proxy", 25, Some(Grade::C), None);
+    }
+    if let Some(score) = report.panel_aging.as_ref().and_then(|panel| panel.score).filter(|score| *score < 90.0) {
+        fire(rules, "panel_wear", "cosmetic_proxy", ((100.0 - score) / 2.0).round() as u32, None, Some(format!("{}/100", score.round())));
+    }
+    if report.usage.as_ref().and_then(|usage| usage.usage.as_deref()) == Some("heavy") {
+        fire(rules, "heavy_use", "cosmetic_proxy", 15, None, None);
+    }
+}
+
+fn integrity_rules(report: &GradeInput, rules: &mut Vec<FiredRule>) {
+    let indicators = report.refurbishment.as_ref().map(|refurb| refurb.indicators.as_slice()).unwrap_or_default();
+    for indicator in indicators.iter().filter(|indicator| indicator.detected) {
+        match indicator.severity.as_str() {
+            // A lock, a foreign account, a swapped part someone hid
+            "critical" => fire(rules, "critical_indicator", "hardware_integrity", 40, Some(Grade::D), Some(indicator.description.clone())),
+            "warning" => fire(rules, "warning_indicator", "hardware_integrity", 10, None, Some(indicator.description.clone())),
+            _ => {}
+        }
+    }
+    if let Some(faults) = report.memory_test.as_ref().map(|test| test.fault_count).filter(|faults| *faults > 0) {
+        fire(rules, "memory_faults", "hardware_integrity", 50, Some(Grade::D), Some(faults.to_string()));
+    }
+    if let Some(crashes) = report.crash_history.as_ref().filter(|history| history.repeated) {
+        fire(rules, "repeated_crashes", "hardware_integrity", 25, Some(Grade::C), Some(crashes.total.to_string()));
+    }
+    let stalled = report.thermal.as_ref().map_or(0, |thermal| thermal.fans.iter().filter(|fan| fan.stalled).count());
+    if stalled > 0 {
+        fire(rules, "fan_stalled", "hardware_integrity", 20, Some(Grade::C), Some(stalled.to_string()));
+    }
+    if report.usage.as_ref().and_then(|usage| usage.usage.as_deref()) == Some("exceeds_age") {
+        fire(rules, "usage_exceeds_age", "hardware_integrity", 10, None, None);
+    }
+}
+
+// The shop's own deductions; the detail names the script and its reason
+fn script_rules(report: &GradeInput, rules: &mut Vec<FiredRule>) {
+    let modifiers = report.refurbishment.as_ref().map(|refurb| refurb.grade_modifiers.as_slice()).unwrap_or_default();
+    // Like the built-in battery rules, nothing about a battery a desktop doesn't have
+    let has_battery = report.battery.design_capacity > 0.0;
+    for modifier in modifiers.iter().filter(|modifier| has_battery || modifier.category != "battery") {
+        let Some(category) = GRADE_CATEGORIES.into_iter().find(|category| *category == modifier.category) else {
+            continue;
+        };
+        let cap = match modifier.cap.as_deref() {
+            Some("A") => Some(Grade::A),
+            Some("B") => Some(Grade::B),
+            Some("C") => Some(Grade::C),
+            Some("D") => Some(Grade::D),
+            _ => None,
+        };
+        let detail = Some(format!("{}: {}", modifier.script, modifier.reason));
+        fire(rules, "script_modifier", category, modifier.points, cap, detail);
+    }
+}
@@ -0,0 +1,430 @@
+// Devices coming and going while the app is open (`watch_hardware_changes`,
+// then `stop_hardware_watch`): USB and Thunderbolt devices, displays and
+// storage volumes. The port test counts the ports a test device was plugged
+// into; the live view lets a buyer plug in their own accessories and watch
+// them appear.
+//
+// The OS says when something was plugged in or out (IOKit matching
+// notifications, Configuration Manager device-interface notifications,
+// kernel uevents), and the watch then lists every category again and emits
+// what changed. It also lists them every few seconds without a notification,
+// for what none arrives for (displays on Apple silicon Macs).
+
+use crate::error::QuickscanError;
+use crate::probe_scheduler::ProbeScheduler;
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use tauri::Emitter;
+
+pub const CHANGED_EVENT: &str = "hardware://changed";
+
+const RESCAN_INTERVAL: Duration = Duration::from_secs(5);
+
+static WATCH: Mutex<Option<Watch>> = Mutex::new(None);
+
+struct Watch {
+    stop: Arc<AtomicBool>,
+    thread: JoinHandle<()>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct HardwareDevice {
+    pub id: String, // unique across categories, stable while the device stays connected
+    pub category: &'static str, // "usb", "thunderbolt", "display" or "storage"
+    pub name: String,
+    pub vendor: Option<String>,
+    pub port: Option<String>, // USB and Thunderbolt: the machine's own port, through any hubs
+    pub speed_mbps: Option<f64>, // USB and Thunderbolt, negotiated
+    pub resolution: Option<String>, // displays, "2560x1600"
+    pub capacity_bytes: Option<u64>, // storage
+    pub removable: Option<bool>, // storage
+}
+
+#[derive(Serialize, Clone)]
+pub struct HardwareEvent {
+    pub action: &'static str, // "added" or "removed"
+    pub device: HardwareDevice,
+    pub at: String, // RFC 3339
+}
+
+#[derive(Serialize, Clone)]
+pub struct HardwareChange {
+    pub events: Vec<HardwareEvent>,
+    pub devices: Vec<HardwareDevice>, // everything connected now
+}
+
+// Starts watching and returns what is connected now; CHANGED_EVENT follows
+// each change. A running watch is replaced.
+#[tauri::command]
+pub async fn watch_hardware_changes(app: tauri::AppHandle, probes: tauri::State<'_, ProbeScheduler>) -> Result<Vec<HardwareDevice>, QuickscanError> {
+    // The watch's rescans queue with the commands' probes
+    let scheduler = probes.inner().clone();
+    probes.run(move || {
+        let devices = hardware_devices()?;
+        stop_watch();
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread = {
+            let stop = stop.clone();
+            let devices = devices.clone();
+            thread::spawn(move || scheduler.enter(|| watch_loop(&app, devices, &stop)))
+        };
+        *WATCH.lock().unwrap_or_else(|e| e.into_inner()) = Some(Watch { stop, thread });
+        Ok(devices)
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn stop_hardware_watch() -> Result<(), QuickscanError> {
+    crate::process::blocking(|| {
+        stop_watch();
+        Ok(())
+    })
+    .await
+}
+
+fn stop_watch() {
+    if let Some(watch) = WATCH.lock().unwrap_or_else(|e| e.into_inner()).take() {
+        watch.stop.store(true, Ordering::Relaxed);
+        let _ = watch.thread.join();
+    }
+}
+
+fn watch_loop(app: &tauri::AppHandle, mut known: Vec<HardwareDevice>, stop: &AtomicBool) {
+    // Without notifications the watch still rescans, just later
+    let listener = platform::Listener::open();
+    // Waits are short so a stop doesn't wait out the rescan interval
+    let wait = Duration::from_millis(500);
+    let mut waited = Duration::ZERO;
+    while !stop.load(Ordering::Relaxed) {
+        let notified = match &listener {
+            Some(listener) => listener.wait(wait),
+            None => {
+                thread::sleep(wait);
+                false
+            }
+        };
+        waited += wait;
+        if !notified && waited < RESCAN_INTERVAL {
+            continue;
+        }
+        waited = Duration::ZERO;
+        // A failed listing (WMI busy, command timed out) just waits for the next one
+        let Ok(devices) = hardware_devices() else {
+            continue;
+        };
+        let at = chrono::Local::now().to_rfc3339();
+        let missing_from = |list: &[HardwareDevice], device: &HardwareDevice| !list.iter().any(|d| d.id == device.id);
+        let event = |action, device: &HardwareDevice| HardwareEvent { action, device: device.clone(), at: at.clone() };
+        let events: Vec<HardwareEvent> = devices
+            .iter()
+            .filter(|d| missing_from(&known, d))
+            .map(|d| event("added", d))
+            .chain(known.iter().filter(|d| missing_from(&devices, d)).map(|d| event("removed", d)))
+            .collect();
+        if !events.is_empty() {
+            // A closed window just stops listening
+            let _ = app.emit(CHANGED_EVENT, HardwareChange { events, devices: devices.clone() });
+        }
+        known = devices;
+    }
+}
+
+// USB is what ports are tested with, so a failure there fails the listing;
+// displays and volumes are left out when they can't be read
+pub fn hardware_devices() -> Result<Vec<HardwareDevice>, QuickscanError> {
+    let mut devices: Vec<HardwareDevice> = crate::usb::usb_devices()?
+        .into_iter()
+        .map(|device| HardwareDevice {
+            id: format!("{}:{}", device.kind, device.id),
+            category: device.kind,
+            name: device.name,
+            vendor: device.vendor,
+            port: device.port,
+            speed_mbps: device.speed_mbps,
+            resolution: None,
+            capacity_bytes: None,
+            removable: None,
+        })
+        .collect();
+
+    // Two identical monitors only differ by their place in the list
+    let displays = crate::display::display_info().unwrap_or_default();
+    for (index, display) in displays.into_iter().enumerate() {
+        devices.push(HardwareDevice {
+            id: format!("display:{}:{}:{}", display.name, display.connection_type, index),
+            category: "display",
+            name: display.name,
+            vendor: Some(display.vendor).filter(|vendor| !vendor.is_empty()),
+            port: Some(display.connection_type).filter(|connection| !connection.is_empty()),
+            speed_mbps: None,
+            resolution: display.native_resolution,
+            capacity_bytes: None,
+            removable: None,
+        });
+    }
+
+    let volumes = sysinfo::Disks::new_with_refreshed_list();
+    for volume in volumes.list() {
+        let mount_point = volume.mount_point().to_string_lossy().to_string();
+        let label = volume.name().to_string_lossy().to_string();
+        devices.push(HardwareDevice {
+            id: format!("storage:{}", mount_point),
+            category: "storage",
+            name: if label.is_empty() { mount_point } else { label },
+            vendor: None,
+            port: None,
+            speed_mbps: None,
+            resolution: None,
+            capacity_bytes: Some(volume.total_space()),
+            removable: Some(volume.is_removable()),
+        });
+    }
+    Ok(devices)
+}
+
+// Matching notifications for the classes behind each category, read straight
+// off the notification port's Mach port so no run loop is needed
+#[cfg(target_os = "macos")]
+mod platform {
+    use io_kit_sys::types::io_iterator_t;
+    use io_kit_sys::{
+        kIOFirstMatchNotification, kIOMasterPortDefault, kIOTerminatedNotification, IOIteratorNext,
+        IONotificationPortCreate, IONotificationPortDestroy, IONotificationPortGetMachPort, IONotificationPortRef,
+        IOObjectRelease, IOServiceAddMatchingNotification, IOServiceMatching,
+    };
+    use mach2::message::{mach_msg, mach_msg_header_t, MACH_MSG_SUCCESS, MACH_RCV_MSG, MACH_RCV_TIMEOUT};
+    use mach2::port::MACH_PORT_NULL;
+    use std::ffi::{c_void, CStr};
+    use std::time::Duration;
+
+    const CLASSES: &[&CStr] = &[c"IOUSBHostDevice", c"IOThunderboltSwitch", c"IOMedia", c"IODisplayConnect"];
+
+    pub struct Listener {
+        port: IONotificationPortRef,
+        iterators: Vec<io_iterator_t>,
+    }
+
+    // The callback never runs: messages are taken off the port directly
+    unsafe extern "C" fn ignore(_refcon: *mut c_void, _iterator: io_iterator_t) {}
+
+    // IOKit only sends the next notification once the iterator is read to the end
+    fn drain(iterator: io_iterator_t) {
+        unsafe {
+            loop {
+                let service = IOIteratorNext(iterator);
+                if service == 0 {
+                    break;
+                }
+                IOObjectRelease(service);
+            }
+        }
+    }
+
+    impl Listener {
+        pub fn open() -> Option<Listener> {
+            unsafe {
+                let port = IONotificationPortCreate(kIOMasterPortDefault);
+                if port.is_null() {
+                    return None;
+                }
+                let mut listener = Listener { port, iterators: vec![] };
+                for class in CLASSES {
+                    for kind in [kIOFirstMatchNotification, kIOTerminatedNotification] {
+                        // Each registration consumes its matching dictionary
+                        let matching = IOServiceMatching(class.as_ptr());
+                        let mut iterator: io_iterator_t = 0;
+                        if IOServiceAddMatchingNotification(port, kind as *mut _, matching as _, ignore, std::ptr::null_mut(), &mut iterator)
+                            == 0
+                        {
+                            drain(iterator);
+                            listener.iterators.push(iterator);
+                        }
+                    }
+                }
+                Some(listener)
+            }
+        }
+
+        // True when a notification arrived before the timeout
+        pub fn wait(&self, timeout: Duration) -> bool {
+            let mach_port = unsafe { IONotificationPortGetMachPort(self.port) };
+            // Notification messages are small; u32s keep the header aligned
+            let mut buffer = [0u32; 1024];
+            let receive = |timeout_ms: u32, buffer: &mut [u32]| unsafe {
+                mach_msg(
+                    buffer.as_mut_ptr() as *mut mach_msg_header_t,
+                    MACH_RCV_MSG | MACH_RCV_TIMEOUT,
+                    0,
+                    (buffer.len() * 4) as u32,
+                    mach_port,
+                    timeout_ms,
+                    MACH_PORT_NULL,
+                ) == MACH_MSG_SUCCESS
+            };
+            if !receive(timeout.as_millis() as u32, &mut buffer) {
+                return false;
+            }
+            // A plug-in often sends several at once
+            while receive(0, &mut buffer) {}
+            self.iterators.iter().for_each(|iterator| drain(*iterator));
+            true
+        }
+    }
+
+    impl Drop for Listener {
+        fn drop(&mut self) {
+            unsafe {
+                for iterator in &self.iterators {
+                    IOObjectRelease(*iterator);
+                }
+                IONotificationPortDestroy(self.port);
+            }
+        }
+    }
+}
+
+// Device-interface arrivals and removals of every class: USB devices,
+// monitors and volumes each register one
+#[cfg(target_os = "windows")]
+mod platform {
+    use std::ffi::c_void;
+    use std::sync::mpsc::{channel, Receiver, Sender};
+    use std::time::Duration;
+    use windows::Win32::Devices::DeviceAndDriverInstallation::{
+        CM_Register_Notification, CM_Unregister_Notification, CM_NOTIFY_ACTION, CM_NOTIFY_ACTION_DEVICEINTERFACEARRIVAL,
+        CM_NOTIFY_ACTION_DEVICEINTERFACEREMOVAL, CM_NOTIFY_EVENT_DATA, CM_NOTIFY_FILTER,
+        CM_NOTIFY_FILTER_FLAG_ALL_INTERFACE_CLASSES, CM_NOTIFY_FILTER_TYPE_DEVICEINTERFACE, CR_SUCCESS, HCMNOTIFICATION,
+    };
+
+    pub struct Listener {
+        notification: HCMNOTIFICATION,
+        receiver: Receiver<()>,
+        // The callback's context; it has to outlive the registration
+        _sender: Box<Sender<()>>,
+    }
+
+    // Runs on a system thread pool
+    unsafe extern "system" fn notify(
+        _notification: HCMNOTIFICATION,
+        context: *const c_void,
+        action: CM_NOTIFY_ACTION,
+        _data: *const CM_NOTIFY_EVENT_DATA,
+        _size: u32,
+    ) -> u32 {
+        if action == CM_NOTIFY_ACTION_DEVICEINTERFACEARRIVAL || action == CM_NOTIFY_ACTION_DEVICEINTERFACEREMOVAL {
+            let _ = unsafe { &*(context as *const Sender<()>) }.send(());
+        }
+        0
+    }
+
+    impl Listener {
+        pub fn open() -> Option<Listener> {
+            let (sender, receiver) = channel();
+            let sender = Box::new(sender);
+            let filter = CM_NOTIFY_FILTER {
+                cbSize: std::mem::size_of::<CM_NOTIFY_FILTER>() as u32,
+                Flags: CM_NOTIFY_FILTER_FLAG_ALL_INTERFACE_CLASSES,
+                FilterType: CM_NOTIFY_FILTER_TYPE_DEVICEINTERFACE,
+                ..Default::default()
+            };
+            let mut notification = HCMNOTIFICATION::default();
+            let context = &*sender as *const Sender<()> as *const c_void;
+            let result = unsafe { CM_Register_Notification(&filter, Some(context), Some(notify), &mut notification) };
+            (result == CR_SUCCESS).then_some(Listener { notification, receiver, _sender: sender })
+        }
+
+        // True when a notification arrived before the timeout
+        pub fn wait(&self, timeout: Duration) -> bool {
+            if self.receiver.recv_timeout(timeout).is_err() {
+                return false;
+            }
+            while self.receiver.try_recv().is_ok() {}
+            true
+        }
+    }
+
+    impl Drop for Listener {
+        fn drop(&mut self) {
+            // Waits for running callbacks, so the sender is still there for them
+            unsafe { CM_Unregister_Notification(self.notification) };
+        }
+    }
+}
+
+// The kernel's uevents on a netlink socket, the same ones udev reads
+#[cfg(target_os = "linux")]
+mod platform {
+    use std::time::Duration;
+
+    const SUBSYSTEMS: &[&[u8]] = &[b"SUBSYSTEM=usb", b"SUBSYSTEM=thunderbolt", b"SUBSYSTEM=drm", b"SUBSYSTEM=block"];
+
+    pub struct Listener(libc::c_int);
+
+    impl Listener {
+        pub fn open() -> Option<Listener> {
+            unsafe {
+                let fd = libc::socket(libc::AF_NETLINK, libc::SOCK_DGRAM | libc::SOCK_CLOEXEC, libc::NETLINK_KOBJECT_UEVENT);
+                if fd < 0 {
+                    return None;
+                }
+                let mut address: libc::sockaddr_nl = std::mem::zeroed();
+                address.nl_family = libc::AF_NETLINK as libc::sa_family_t;
+                address.nl_groups = 1; // the kernel's group, not udev's
+                let size = std::mem::size_of::<libc::sockaddr_nl>() as libc::socklen_t;
+                if libc::bind(fd, &address as *const libc::sockaddr_nl as *const libc::sockaddr, size) < 0 {
+                    libc::close(fd);
+                    return None;
+                }
+                Some(Listener(fd))
+            }
+        }
+
+        // True when a device of a watched subsystem came, went or changed
+        // (a monitor plugged into a connector) before the timeout
+        pub fn wait(&self, timeout: Duration) -> bool {
+            let mut poll = libc::pollfd { fd: self.0, events: libc::POLLIN, revents: 0 };
+            if unsafe { libc::poll(&mut poll, 1, timeout.as_millis() as libc::c_int) } <= 0 {
+                return false;
+            }
+            // "add@/devices/...\0ACTION=add\0DEVPATH=...\0SUBSYSTEM=usb\0..."
+            let mut buffer = [0u8; 8192];
+            let mut relevant = false;
+            loop {
+                let length =
+                    unsafe { libc::recv(self.0, buffer.as_mut_ptr() as *mut libc::c_void, buffer.len(), libc::MSG_DONTWAIT) };
+                if length <= 0 {
+                    break;
+                }
+                relevant |= buffer[..length as usize].split(|&b| b == 0).any(|field| SUBSYSTEMS.contains(&field));
+            }
+            relevant
+        }
+    }
+
+    impl Drop for Listener {
+        fn drop(&mut self) {
+            unsafe { libc::close(self.0) };
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+mod platform {
+    use std::time::Duration;
+
+    pub struct Listener;
+
+    impl Listener {
+        pub fn open() -> Option<Listener> {
+            None
+        }
+
+        pub fn wait(&self, _timeout: Duration) -> bool {
+            false
+        }
+    }
+}
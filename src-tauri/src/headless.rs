@@ -0,0 +1,73 @@
+// The headless scan behind the `quickscan-headless` binary, built with the
+// `headless` feature for the live-USB image (see live-usb/). It runs the
+// hardware probes straight from the command line, with no window, webview or
+// display server, and writes one JSON report.
+//
+// The live system isn't the machine's own, so everything that describes the
+// running OS (install history, pairings, region settings, MDM) is left out;
+// the claimed origin is only checked against the keyboard.
+//
+//   quickscan-headless [--output report.json] [--claimed-region JP]
+
+use crate::export::{hardware, probe};
+use serde_json::{json, Map, Value};
+use std::process::ExitCode;
+
+const USAGE: &str = "usage: quickscan-headless [--output <file>] [--claimed-region <ISO 3166 code>]";
+
+pub fn scan(claimed_region: Option<&str>) -> Value {
+    let mut report = Map::new();
+    report.insert("generated_at".to_string(), json!(chrono::Local::now().to_rfc3339()));
+    report.insert("serial_number".to_string(), json!(crate::get_serial_number()));
+    hardware(&mut report);
+
+    let mut check = crate::hardware_refurbishment_check();
+    if let Some(claimed_region) = claimed_region {
+        crate::apply_claimed_origin(&crate::region::hardware_region_info(), claimed_region, &mut check);
+        crate::score_refurbishment(&mut check);
+    }
+    for indicator in &mut check.indicators {
+        indicator.help = crate::indicator_help::lookup(&indicator.name);
+    }
+    probe(&mut report, "refurbishment", Ok(check));
+    Value::Object(report)
+}
+
+pub fn run() -> ExitCode {
+    let mut output: Option<String> = None;
+    let mut claimed_region: Option<String> = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--output" | "-o" => output = args.next(),
+            "--claimed-region" => claimed_region = args.next().filter(|region| !region.trim().is_empty()),
+            "--help" | "-h" => {
+                println!("{}", USAGE);
+                return ExitCode::SUCCESS;
+            }
+            _ => {
+                eprintln!("{}", USAGE);
+                return ExitCode::from(2);
+            }
+        }
+    }
+
+    let report = scan(claimed_region.as_deref());
+    let content = serde_json::to_string_pretty(&report).unwrap_or_default();
+    match output {
+        Some(path) => match std::fs::write(&path, content + "\n") {
+            Ok(()) => {
+                eprintln!("report written to {}", path);
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!("{}: {}", path, e);
+                ExitCode::FAILURE
+            }
+        },
+        None => {
+            println!("{}", content);
+            ExitCode::SUCCESS
+        }
+    }
+}
@@ -0,0 +1,156 @@
+// Scan-event hooks configured in `hooks.json` in the app config directory:
+//
+// { "post_export": [{ "command": "/usr/local/bin/print-report", "args": ["--copies", "2"] }] }
+//
+// Each hook runs with the report path (when there is one) appended as its last
+// argument and QUICKSCAN_HOOK_STAGE set in its environment.
+
+use crate::error::QuickscanError;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+use tauri::Manager;
+
+pub const HOOKS_FILE: &str = "hooks.json";
+// Under the app data directory, for post-scan hooks
+const REPORTS_DIR: &str = "reports";
+
+// Hooks print or upload reports, so they get longer than the probe commands
+const HOOK_TIMEOUT: Duration = Duration::from_secs(120);
+
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum HookStage {
+    PreScan,
+    PostScan,
+    PostExport,
+}
+
+impl HookStage {
+    fn as_str(&self) -> &'static str {
+        match self {
+            HookStage::PreScan => "pre_scan",
+            HookStage::PostScan => "post_scan",
+            HookStage::PostExport => "post_export",
+        }
+    }
+}
+
+#[derive(Deserialize, Default)]
+pub struct HookConfig {
+    #[serde(default)]
+    pub pre_scan: Vec<Hook>,
+    #[serde(default)]
+    pub post_scan: Vec<Hook>,
+    #[serde(default)]
+    pub post_export: Vec<Hook>,
+}
+
+#[derive(Deserialize)]
+pub struct Hook {
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct HookResult {
+    pub command: String,
+    pub success: bool,
+    pub exit_code: Option<i32>,
+    pub output: String,
+}
+
+impl HookConfig {
+    fn hooks_for(&self, stage: HookStage) -> &[Hook] {
+        match stage {
+            HookStage::PreScan => &self.pre_scan,
+            HookStage::PostScan => &self.post_scan,
+            HookStage::PostExport => &self.post_export,
+        }
+    }
+}
+
+pub fn load_hooks(path: &Path) -> Result<HookConfig, QuickscanError> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(HookConfig::default()),
+        Err(e) => return Err(QuickscanError::io(HOOKS_FILE, e)),
+    };
+    serde_json::from_str(&content).map_err(|e| QuickscanError::parse(HOOKS_FILE, e))
+}
+
+// Runs the hooks for a stage. Post-scan hooks receive the report written to the
+// app data directory; post-export hooks receive the exported file path.
+#[tauri::command]
+pub async fn run_scan_hook(
+    app: tauri::AppHandle,
+    stage: HookStage,
+    report: Option<serde_json::Value>,
+    report_path: Option<String>,
+) -> Result<Vec<HookResult>, QuickscanError> {
+    let config_dir = app.path().app_config_dir().map_err(|e| QuickscanError::command_failed(HOOKS_FILE, e))?;
+    let config = load_hooks(&config_dir.join(HOOKS_FILE))?;
+    let hooks = config.hooks_for(stage);
+    if hooks.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let report_path = match (report, report_path) {
+        (_, Some(path)) => Some(PathBuf::from(path)),
+        (Some(report), None) => {
+            let data_dir = app.path().app_data_dir().map_err(|e| QuickscanError::command_failed(REPORTS_DIR, e))?;
+            Some(write_report(&data_dir, &report)?)
+        }
+        (None, None) => None,
+    };
+
+    Ok(hooks
+        .iter()
+        .map(|hook| run_hook(hook, stage, report_path.as_deref()))
+        .collect())
+}
+
+fn write_report(data_dir: &Path, report: &serde_json::Value) -> Result<PathBuf, QuickscanError> {
+    let reports_dir = data_dir.join(REPORTS_DIR);
+    let io = |e| QuickscanError::io(REPORTS_DIR, e);
+    std::fs::create_dir_all(&reports_dir).map_err(io)?;
+
+    let id = report
+        .get("id")
+        .and_then(|v| v.as_str())
+        .filter(|id| id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'))
+        .unwrap_or("latest");
+    let path = reports_dir.join(format!("report-{}.json", id));
+    let content = serde_json::to_string_pretty(report).map_err(|e| QuickscanError::parse("report", e))?;
+    std::fs::write(&path, content).map_err(io)?;
+    Ok(path)
+}
+
+fn run_hook(hook: &Hook, stage: HookStage, report_path: Option<&Path>) -> HookResult {
+    let mut command = Command::new(&hook.command);
+    command.args(&hook.args).env("QUICKSCAN_HOOK_STAGE", stage.as_str());
+    if let Some(path) = report_path {
+        command.arg(path);
+    }
+
+    match crate::process::run_cmd_with_timeout(&mut command, HOOK_TIMEOUT) {
+        Ok(output) => {
+            let mut text = String::from_utf8_lossy(&output.stdout).to_string();
+            text.push_str(&String::from_utf8_lossy(&output.stderr));
+            HookResult {
+                command: hook.command.clone(),
+                success: output.status.success(),
+                exit_code: output.status.code(),
+                output: text.trim().to_string(),
+            }
+        }
+        Err(e) => HookResult {
+            command: hook.command.clone(),
+            success: false,
+            exit_code: None,
+            output: e.to_string(),
+        },
+    }
+}
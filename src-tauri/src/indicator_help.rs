@@ -0,0 +1,62 @@
+// Plain-language help for refurbishment indicators. Each entry points at
+// i18n keys under `refurbishment.help.<topic>` so the frontend and exports can
+// explain what an indicator means, why it usually happens and what to do.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct IndicatorHelp {
+    pub explanation: String,
+    pub causes: String,
+    pub action: String,
+}
+
+// Indicator name -> help topic
+const TOPICS: &[(&str, &str)] = &[
+    ("serial_refurb", "certified_refurbished"),
+    ("ioreg_refurb", "firmware_refurb_flag"),
+    ("bios_refurb", "firmware_refurb_flag"),
+    ("oem_refurb", "oem_refurb_flag"),
+    ("refurbisher_license", "refurbisher_license"),
+    ("enterprise_managed", "enterprise_managed"),
+    ("third_party_storage", "third_party_storage"),
+    ("third_party_display", "third_party_display"),
+    ("third_party_wifi", "third_party_wifi"),
+    ("wifi_vendor_mismatch", "third_party_wifi"),
+    ("wifi_mac_locally_administered", "wifi_mac_locally_administered"),
+    ("gpu_vram_below_spec", "gpu_vram_below_spec"),
+    ("gpu_generic_driver", "gpu_generic_driver"),
+    ("component_missing", "component_missing"),
+    ("sip_disabled", "sip_disabled"),
+    ("activation_lock", "activation_lock"),
+    ("secure_boot_lowered", "secure_boot_lowered"),
+    ("part_swapped", "part_swapped"),
+    ("logic_board_mismatch", "logic_board_mismatch"),
+    ("bluetooth_pairings", "bluetooth_pairings"),
+    ("origin_mismatch", "origin_mismatch"),
+    ("third_party_battery", "third_party_battery"),
+    ("third_party_keyboard", "third_party_keyboard"),
+    ("battery_newer_than_chassis", "battery_replaced"),
+    ("recent_os_install", "recent_os_install"),
+    ("clock_unreliable", "clock_unreliable"),
+    ("previous_owner_data", "previous_owner_data"),
+    ("personal_account", "personal_account"),
+    ("work_account", "work_account"),
+];
+
+pub fn lookup(indicator_name: &str) -> Option<IndicatorHelp> {
+    let topic = TOPICS
+        .iter()
+        .find(|(name, _)| *name == indicator_name)
+        .map(|(_, topic)| *topic)
+        .or_else(|| {
+            let is_custom = indicator_name.starts_with("rule:") || indicator_name.starts_with("script:");
+            is_custom.then_some("custom_check")
+        })?;
+
+    Some(IndicatorHelp {
+        explanation: format!("refurbishment.help.{}.explanation", topic),
+        causes: format!("refurbishment.help.{}.causes", topic),
+        action: format!("refurbishment.help.{}.action", topic),
+    })
+}
@@ -0,0 +1,791 @@
+// Keyboards and pointing devices (`get_input_devices`) and a raw key capture
+// for the press-every-key test (`start_keyboard_test`, then
+// `stop_keyboard_test`). Replaced and partly dead keyboards are common on
+// resold laptops. The webview never sees keys the OS keeps for itself (media
+// keys on the function row, ⌘Q, the Windows key), so the capture reads them
+// below it and emits KEY_EVENT with the same `code` names as KeyboardEvent.code.
+
+use crate::error::QuickscanError;
+use serde::Serialize;
+use std::sync::Mutex;
+
+pub const KEY_EVENT: &str = "keyboard://key";
+
+static CAPTURE: Mutex<Option<platform::Capture>> = Mutex::new(None);
+
+#[derive(Serialize)]
+pub struct InputDevices {
+    pub devices: Vec<InputDevice>,
+    // None where built-in devices can't be told apart from plugged-in ones
+    pub internal_keyboard: Option<bool>,
+    pub internal_trackpad: Option<bool>,
+    pub physical_layout: Option<&'static str>, // built-in keyboard: "ansi", "iso" or "jis"
+}
+
+#[derive(Serialize)]
+pub struct InputDevice {
+    pub name: String,
+    pub kind: &'static str, // "keyboard", "trackpad" or "mouse"
+    pub built_in: Option<bool>,
+    pub connection: Option<String>, // "spi", "i2c", "ps2", "usb", "bluetooth"
+    pub force_touch: Option<bool>, // pressure-sensing trackpad
+    pub haptic: Option<bool>, // clicks come from an actuator rather than a switch
+}
+
+#[derive(Serialize, Clone)]
+pub struct KeyEvent {
+    pub code: Option<&'static str>, // KeyboardEvent.code, e.g. "KeyA", "F5", "MetaLeft"
+    pub raw: u32, // the platform's own key number, for keys without a code
+    pub pressed: bool,
+}
+
+#[tauri::command]
+pub async fn get_input_devices() -> Result<InputDevices, QuickscanError> {
+    crate::process::blocking(input_devices).await
+}
+
+// A running capture is replaced
+#[tauri::command]
+pub async fn start_keyboard_test(app: tauri::AppHandle) -> Result<(), QuickscanError> {
+    crate::process::blocking(move || {
+        stop_capture();
+        let capture = platform::start(app)?;
+        *CAPTURE.lock().unwrap_or_else(|e| e.into_inner()) = Some(capture);
+        Ok(())
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn stop_keyboard_test() -> Result<(), QuickscanError> {
+    crate::process::blocking(|| {
+        stop_capture();
+        Ok(())
+    })
+    .await
+}
+
+fn stop_capture() {
+    if let Some(capture) = CAPTURE.lock().unwrap_or_else(|e| e.into_inner()).take() {
+        capture.stop();
+    }
+}
+
+#[allow(dead_code)] // each platform uses some of these
+fn emit(app: &tauri::AppHandle, code: Option<&'static str>, raw: u32, pressed: bool) {
+    use tauri::Emitter;
+    // A closed window just stops listening
+    let _ = app.emit(KEY_EVENT, KeyEvent { code, raw, pressed });
+}
+
+pub fn input_devices() -> Result<InputDevices, QuickscanError> {
+    #[cfg(target_os = "macos")]
+    let devices = input_devices_macos();
+    #[cfg(target_os = "windows")]
+    let devices = input_devices_windows()?;
+    #[cfg(target_os = "linux")]
+    let devices = input_devices_linux();
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    let devices: Vec<InputDevice> = return Err(QuickscanError::unsupported("input_devices"));
+
+    let internal = |kind: &str| {
+        let of_kind: Vec<&InputDevice> = devices.iter().filter(|d| d.kind == kind).collect();
+        if of_kind.iter().any(|d| d.built_in == Some(true)) {
+            Some(true)
+        } else if of_kind.iter().all(|d| d.built_in.is_some()) {
+            Some(false)
+        } else {
+            None
+        }
+    };
+    Ok(InputDevices {
+        internal_keyboard: internal("keyboard"),
+        internal_trackpad: internal("trackpad"),
+        physical_layout: physical_layout(),
+        devices,
+    })
+}
+
+// ANSI, ISO or JIS: which market a keyboard was made for, and what a listing
+// has to say. The software layout can be anything; this is the key shape.
+pub fn physical_layout() -> Option<&'static str> {
+    #[cfg(target_os = "macos")]
+    {
+        physical_layout_macos()
+    }
+    #[cfg(target_os = "windows")]
+    {
+        physical_layout_windows()
+    }
+    #[cfg(target_os = "linux")]
+    {
+        physical_layout_linux()
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        None
+    }
+}
+
+// HID bCountryCode: 33 US (ANSI), 13 international (ISO), 15 Japan (JIS).
+// 16 and 30 are the Korean and Taiwanese keyboards, ANSI-shaped; the other
+// national codes are ISO ones. 0 means the keyboard doesn't say.
+#[allow(dead_code)] // not on Windows
+fn country_code_layout(code: u64) -> Option<&'static str> {
+    match code {
+        15 => Some("jis"),
+        16 | 30 | 33 => Some("ansi"),
+        1..=35 => Some("iso"),
+        _ => None,
+    }
+}
+
+// Apple's built-in keyboards carry their country code in the registry
+#[cfg(target_os = "macos")]
+fn physical_layout_macos() -> Option<&'static str> {
+    let keyboard = crate::iokit::all_service_properties("IOHIDDevice").into_iter().find(|device| {
+        let number = |key: &str| device.get(key).and_then(|v| v.as_i64());
+        device.get("Built-In").and_then(|v| v.as_bool()) == Some(true)
+            && number("PrimaryUsagePage") == Some(1)
+            && number("PrimaryUsage") == Some(6)
+    })?;
+    country_code_layout(keyboard.get("CountryCode")?.as_u64()?)
+}
+
+// Laptop keyboards hang off the PS/2 controller, which has no country code;
+// Windows only knows the Japanese 106/109-key type (7) apart
+#[cfg(target_os = "windows")]
+fn physical_layout_windows() -> Option<&'static str> {
+    use windows::Win32::UI::Input::KeyboardAndMouse::GetKeyboardType;
+    (unsafe { GetKeyboardType(0) } == 7).then_some("jis")
+}
+
+// The country code of a USB keyboard, from the HID descriptor that follows its
+// interface descriptor; Macs before 2016 wire the built-in keyboard over USB
+#[cfg(target_os = "linux")]
+fn physical_layout_linux() -> Option<&'static str> {
+    let entries = std::fs::read_dir("/sys/bus/usb/devices").ok()?;
+    for entry in entries.flatten() {
+        let Ok(descriptors) = std::fs::read(entry.path().join("descriptors")) else {
+            continue;
+        };
+        let mut keyboard = false;
+        let mut rest = descriptors.as_slice();
+        while let [length, kind, ..] = *rest {
+            let length = length as usize;
+            if length < 2 || length > rest.len() {
+                break;
+            }
+            let descriptor = &rest[..length];
+            match kind {
+                // Interface: class 3 (HID), protocol 1 (keyboard)
+                4 if length >= 9 => keyboard = descriptor[5] == 3 && descriptor[7] == 1,
+                // HID: bcdHID, then bCountryCode
+                0x21 if keyboard && length >= 6 => {
+                    if let Some(layout) = country_code_layout(descriptor[4] as u64) {
+                        return Some(layout);
+                    }
+                }
+                _ => {}
+            }
+            rest = &rest[length..];
+        }
+    }
+    None
+}
+
+// Scan code set 1, which Windows reports and Linux evdev numbers match up to 88
+#[allow(dead_code)]
+fn set1_code(scan: u32) -> Option<&'static str> {
+    const CODES: [&str; 89] = [
+        "", "Escape", "Digit1", "Digit2", "Digit3", "Digit4", "Digit5", "Digit6", "Digit7", "Digit8", "Digit9",
+        "Digit0", "Minus", "Equal", "Backspace", "Tab", "KeyQ", "KeyW", "KeyE", "KeyR", "KeyT", "KeyY", "KeyU",
+        "KeyI", "KeyO", "KeyP", "BracketLeft", "BracketRight", "Enter", "ControlLeft", "KeyA", "KeyS", "KeyD",
+        "KeyF", "KeyG", "KeyH", "KeyJ", "KeyK", "KeyL", "Semicolon", "Quote", "Backquote", "ShiftLeft",
+        "Backslash", "KeyZ", "KeyX", "KeyC", "KeyV", "KeyB", "KeyN", "KeyM", "Comma", "Period", "Slash",
+        "ShiftRight", "NumpadMultiply", "AltLeft", "Space", "CapsLock", "F1", "F2", "F3", "F4", "F5", "F6", "F7",
+        "F8", "F9", "F10", "NumLock", "ScrollLock", "Numpad7", "Numpad8", "Numpad9", "NumpadSubtract", "Numpad4",
+        "Numpad5", "Numpad6", "NumpadAdd", "Numpad1", "Numpad2", "Numpad3", "Numpad0", "NumpadDecimal", "", "",
+        "IntlBackslash", "F11", "F12",
+    ];
+    CODES.get(scan as usize).copied().filter(|code| !code.is_empty())
+}
+
+#[cfg(target_os = "macos")]
+fn input_devices_macos() -> Vec<InputDevice> {
+    use crate::iokit;
+
+    // Force Touch trackpads carry a pressure sensor and a Taptic Engine
+    // actuator; the multitouch driver says which
+    let multitouch = iokit::all_service_properties("AppleMultitouchDevice");
+    let flag = |key: &str| multitouch.iter().find_map(|device| device.get(key)?.as_bool());
+    let force_touch = flag("ForceSupported");
+    let haptic = flag("ActuationSupported");
+
+    let mut devices = vec![];
+    for device in iokit::all_service_properties("IOHIDDevice") {
+        let number = |key: &str| device.get(key).and_then(|v| v.as_i64());
+        // Generic desktop page: 2 mouse, 6 keyboard. Internal trackpads
+        // present as mice with a multitouch interface alongside.
+        let kind = match (number("PrimaryUsagePage"), number("PrimaryUsage")) {
+            (Some(1), Some(6)) => "keyboard",
+            (Some(1), Some(2)) => "mouse",
+            _ => continue,
+        };
+        let name = iokit::string_property(&device, "Product").unwrap_or_else(|| "HID device".to_string());
+        let kind = if kind == "mouse" && name.to_lowercase().contains("trackpad") { "trackpad" } else { kind };
+        let built_in = device.get("Built-In").and_then(|v| v.as_bool());
+        // "SPI", "USB", "Bluetooth", "Bluetooth Low Energy", "FIFO" (Apple silicon's internal bus)
+        let connection = iokit::string_property(&device, "Transport").map(|transport| {
+            let transport = transport.to_lowercase();
+            if transport.starts_with("bluetooth") {
+                "bluetooth".to_string()
+            } else if transport == "fifo" {
+                "spi".to_string()
+            } else {
+                transport
+            }
+        });
+        let trackpad = kind == "trackpad" && built_in == Some(true);
+        // The same keyboard shows up once per HID interface
+        if devices.iter().any(|d: &InputDevice| d.name == name && d.kind == kind) {
+            continue;
+        }
+        devices.push(InputDevice {
+            name,
+            kind,
+            built_in,
+            connection,
+            force_touch: if trackpad { force_touch } else { None },
+            haptic: if trackpad { haptic } else { None },
+        });
+    }
+    devices
+}
+
+#[cfg(target_os = "windows")]
+fn input_devices_windows() -> Result<Vec<InputDevice>, QuickscanError> {
+    use crate::wmi_provider::{WmiProvider, CIMV2};
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct Device {
+        name: Option<String>,
+        description: Option<String>,
+        #[serde(rename = "PNPDeviceID")]
+        pnp_device_id: Option<String>,
+    }
+
+    // The device ID says how it is attached: "ACPI\..." for a laptop's PS/2
+    // keyboard, "HID\VEN_..." for an I2C touchpad, "HID\VID_..." for USB and
+    // a Bluetooth HID service GUID for wireless ones
+    let attachment = |id: &str| {
+        let id = id.to_uppercase();
+        if id.starts_with("BTH") || id.contains("{00001124-") || id.contains("{00001812-") {
+            (Some("bluetooth"), Some(false))
+        } else if id.starts_with("ACPI\\") {
+            (Some("ps2"), Some(true))
+        } else if id.starts_with("HID\\VEN_") {
+            (Some("i2c"), Some(true))
+        } else if id.starts_with("USB\\") || id.starts_with("HID\\VID_") {
+            (Some("usb"), None) // a detachable keyboard cover is USB too
+        } else {
+            (None, None)
+        }
+    };
+
+    let wmi = WmiProvider::global();
+    let mut devices = vec![];
+    for (class, default_kind) in [("Win32_Keyboard", "keyboard"), ("Win32_PointingDevice", "mouse")] {
+        let query = format!("SELECT Name, Description, PNPDeviceID FROM {}", class);
+        for device in wmi.query::<Device>(CIMV2, &query)? {
+            let name = device.name.or(device.description).unwrap_or_else(|| "Input device".to_string());
+            let lower = name.to_lowercase();
+            let kind = if default_kind == "mouse" && (lower.contains("touchpad") || lower.contains("trackpad")) {
+                "trackpad"
+            } else {
+                default_kind
+            };
+            let (connection, built_in) = attachment(device.pnp_device_id.as_deref().unwrap_or_default());
+            devices.push(InputDevice {
+                name,
+                kind,
+                built_in,
+                connection: connection.map(|c| c.to_string()),
+                force_touch: None,
+                haptic: None,
+            });
+        }
+    }
+    Ok(devices)
+}
+
+// One block per device in /proc/bus/input/devices
+#[cfg(target_os = "linux")]
+struct ProcInputDevice {
+    name: String,
+    bus: u32,
+    handlers: Vec<String>,
+    properties: u64, // INPUT_PROP_* bits
+    events: u64,     // EV_* bits
+}
+
+#[cfg(target_os = "linux")]
+fn proc_input_devices() -> Vec<ProcInputDevice> {
+    let content = std::fs::read_to_string("/proc/bus/input/devices").unwrap_or_default();
+    content
+        .split("\n\n")
+        .filter(|block| !block.trim().is_empty())
+        .map(|block| {
+            let field = |prefix: &str| block.lines().find_map(|line| line.strip_prefix(prefix)).unwrap_or_default();
+            let hex = |value: &str| u64::from_str_radix(value.trim(), 16).unwrap_or(0);
+            // "I: Bus=0011 Vendor=0001 Product=0001 Version=ab83"
+            let bus = field("I: ").split_whitespace().find_map(|f| f.strip_prefix("Bus=")).unwrap_or("0");
+            ProcInputDevice {
+                name: field("N: Name=").trim_matches('"').to_string(),
+                bus: hex(bus) as u32,
+                handlers: field("H: Handlers=").split_whitespace().map(|h| h.to_string()).collect(),
+                properties: hex(field("B: PROP=")),
+                events: hex(field("B: EV=")),
+            }
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+const EV_REP: u64 = 1 << 0x14; // auto-repeat: real keyboards, not power buttons
+
+#[cfg(target_os = "linux")]
+fn input_devices_linux() -> Vec<InputDevice> {
+    const INPUT_PROP_BUTTONPAD: u64 = 1 << 2;
+    const INPUT_PROP_PRESSUREPAD: u64 = 1 << 5;
+
+    proc_input_devices()
+        .into_iter()
+        .filter_map(|device| {
+            let lower = device.name.to_lowercase();
+            let kind = if device.handlers.iter().any(|h| h == "kbd") && device.events & EV_REP != 0 {
+                "keyboard"
+            } else if device.properties & INPUT_PROP_BUTTONPAD != 0 || lower.contains("touchpad") || lower.contains("trackpad") {
+                "trackpad"
+            } else if device.handlers.iter().any(|h| h.starts_with("mouse")) {
+                "mouse"
+            } else {
+                return None;
+            };
+            // BUS_I8042, BUS_I2C, BUS_HOST and BUS_SPI are inside the case
+            let (connection, built_in) = match device.bus {
+                0x03 => (Some("usb"), Some(false)),
+                0x05 => (Some("bluetooth"), Some(false)),
+                0x11 => (Some("ps2"), Some(true)),
+                0x18 => (Some("i2c"), Some(true)),
+                0x19 => (None, Some(true)),
+                0x1c => (Some("spi"), Some(true)),
+                _ => (None, None),
+            };
+            let trackpad = kind == "trackpad";
+            Some(InputDevice {
+                name: device.name,
+                kind,
+                built_in,
+                connection: connection.map(|c| c.to_string()),
+                force_touch: trackpad.then_some(device.properties & INPUT_PROP_PRESSUREPAD != 0),
+                // Pressure pads click through a haptic actuator
+                haptic: trackpad.then_some(device.properties & INPUT_PROP_PRESSUREPAD != 0),
+            })
+        })
+        .collect()
+}
+
+// IOHIDManager on a thread of its own with its own run loop. Reading HID
+// values needs the Input Monitoring permission; the first start asks for it.
+#[cfg(target_os = "macos")]
+mod platform {
+    use super::emit;
+    use crate::error::QuickscanError;
+    use core_foundation::base::TCFType;
+    use core_foundation::dictionary::CFDictionary;
+    use core_foundation::number::CFNumber;
+    use core_foundation::string::CFString;
+    use core_foundation_sys::base::{kCFAllocatorDefault, CFAllocatorRef, CFRelease};
+    use core_foundation_sys::dictionary::CFDictionaryRef;
+    use core_foundation_sys::runloop::{kCFRunLoopDefaultMode, CFRunLoopGetCurrent, CFRunLoopRef, CFRunLoopRun, CFRunLoopStop};
+    use core_foundation_sys::string::CFStringRef;
+    use std::ffi::c_void;
+    use std::sync::mpsc;
+    use std::thread::{self, JoinHandle};
+
+    type IOHIDManagerRef = *mut c_void;
+    type IOHIDValueRef = *mut c_void;
+    type IOHIDElementRef = *mut c_void;
+    type ValueCallback = extern "C" fn(context: *mut c_void, result: i32, sender: *mut c_void, value: IOHIDValueRef);
+
+    #[link(name = "IOKit", kind = "framework")]
+    extern "C" {
+        fn IOHIDManagerCreate(allocator: CFAllocatorRef, options: u32) -> IOHIDManagerRef;
+        fn IOHIDManagerSetDeviceMatching(manager: IOHIDManagerRef, matching: CFDictionaryRef);
+        fn IOHIDManagerRegisterInputValueCallback(manager: IOHIDManagerRef, callback: ValueCallback, context: *mut c_void);
+        fn IOHIDManagerScheduleWithRunLoop(manager: IOHIDManagerRef, run_loop: CFRunLoopRef, mode: CFStringRef);
+        fn IOHIDManagerOpen(manager: IOHIDManagerRef, options: u32) -> i32;
+        fn IOHIDManagerClose(manager: IOHIDManagerRef, options: u32) -> i32;
+        fn IOHIDValueGetElement(value: IOHIDValueRef) -> IOHIDElementRef;
+        fn IOHIDValueGetIntegerValue(value: IOHIDValueRef) -> isize;
+        fn IOHIDElementGetUsagePage(element: IOHIDElementRef) -> u32;
+        fn IOHIDElementGetUsage(element: IOHIDElementRef) -> u32;
+        fn IOHIDRequestAccess(request_type: u32) -> bool;
+    }
+
+    const REQUEST_LISTEN_EVENT: u32 = 1;
+    const PAGE_KEYBOARD: u32 = 0x07;
+    const PAGE_APPLE_TOP_CASE: u32 = 0xff; // the Fn key on Apple keyboards
+    const USAGE_TOP_CASE_FN: u32 = 0x03;
+
+    pub struct Capture {
+        run_loop: usize, // CFRunLoopRef of the capture thread
+        thread: JoinHandle<()>,
+    }
+
+    impl Capture {
+        pub fn stop(self) {
+            unsafe { CFRunLoopStop(self.run_loop as CFRunLoopRef) };
+            let _ = self.thread.join();
+        }
+    }
+
+    // Keyboard usage page (0x07) to KeyboardEvent.code
+    fn hid_code(usage: u32) -> Option<&'static str> {
+        const LETTERS: [&str; 26] = [
+            "KeyA", "KeyB", "KeyC", "KeyD", "KeyE", "KeyF", "KeyG", "KeyH", "KeyI", "KeyJ", "KeyK", "KeyL", "KeyM",
+            "KeyN", "KeyO", "KeyP", "KeyQ", "KeyR", "KeyS", "KeyT", "KeyU", "KeyV", "KeyW", "KeyX", "KeyY", "KeyZ",
+        ];
+        const DIGITS: [&str; 10] = ["Digit1", "Digit2", "Digit3", "Digit4", "Digit5", "Digit6", "Digit7", "Digit8", "Digit9", "Digit0"];
+        const FUNCTION: [&str; 12] = ["F1", "F2", "F3", "F4", "F5", "F6", "F7", "F8", "F9", "F10", "F11", "F12"];
+        let code = match usage {
+            0x04..=0x1d => LETTERS[(usage - 0x04) as usize],
+            0x1e..=0x27 => DIGITS[(usage - 0x1e) as usize],
+            0x28 => "Enter",
+            0x29 => "Escape",
+            0x2a => "Backspace",
+            0x2b => "Tab",
+            0x2c => "Space",
+            0x2d => "Minus",
+            0x2e => "Equal",
+            0x2f => "BracketLeft",
+            0x30 => "BracketRight",
+            0x31 => "Backslash",
+            0x33 => "Semicolon",
+            0x34 => "Quote",
+            0x35 => "Backquote",
+            0x36 => "Comma",
+            0x37 => "Period",
+            0x38 => "Slash",
+            0x39 => "CapsLock",
+            0x3a..=0x45 => FUNCTION[(usage - 0x3a) as usize],
+            0x4a => "Home",
+            0x4b => "PageUp",
+            0x4c => "Delete",
+            0x4d => "End",
+            0x4e => "PageDown",
+            0x4f => "ArrowRight",
+            0x50 => "ArrowLeft",
+            0x51 => "ArrowDown",
+            0x52 => "ArrowUp",
+            0x64 => "IntlBackslash",
+            0xe0 => "ControlLeft",
+            0xe1 => "ShiftLeft",
+            0xe2 => "AltLeft",
+            0xe3 => "MetaLeft",
+            0xe4 => "ControlRight",
+            0xe5 => "ShiftRight",
+            0xe6 => "AltRight",
+            0xe7 => "MetaRight",
+            _ => return None,
+        };
+        Some(code)
+    }
+
+    extern "C" fn on_value(context: *mut c_void, _result: i32, _sender: *mut c_void, value: IOHIDValueRef) {
+        unsafe {
+            let app = &*(context as *const tauri::AppHandle);
+            let element = IOHIDValueGetElement(value);
+            let (page, usage) = (IOHIDElementGetUsagePage(element), IOHIDElementGetUsage(element));
+            let pressed = IOHIDValueGetIntegerValue(value) != 0;
+            match (page, usage) {
+                // Below 0x04 are rollover and error codes, not keys
+                (PAGE_KEYBOARD, 0x04..=0xe7) => emit(app, hid_code(usage), usage, pressed),
+                (PAGE_APPLE_TOP_CASE, USAGE_TOP_CASE_FN) => emit(app, Some("Fn"), usage | (page << 16), pressed),
+                _ => {}
+            }
+        }
+    }
+
+    pub fn start(app: tauri::AppHandle) -> Result<Capture, QuickscanError> {
+        if !unsafe { IOHIDRequestAccess(REQUEST_LISTEN_EVENT) } {
+            return Err(QuickscanError::PermissionDenied {
+                source: "IOHIDManager".to_string(),
+                message: "Input Monitoring access is turned off in Privacy & Security settings".to_string(),
+            });
+        }
+
+        let (started, result) = mpsc::channel::<Result<usize, QuickscanError>>();
+        let thread = thread::spawn(move || unsafe {
+            // Freed once the run loop has stopped and no callback can run
+            let context = Box::into_raw(Box::new(app));
+            let manager = IOHIDManagerCreate(kCFAllocatorDefault, 0);
+            // Generic desktop page, keyboard usage
+            let matching = CFDictionary::from_CFType_pairs(&[
+                (CFString::new("DeviceUsagePage"), CFNumber::from(1i32)),
+                (CFString::new("DeviceUsage"), CFNumber::from(6i32)),
+            ]);
+            IOHIDManagerSetDeviceMatching(manager, matching.as_concrete_TypeRef());
+            IOHIDManagerRegisterInputValueCallback(manager, on_value, context as *mut c_void);
+            IOHIDManagerScheduleWithRunLoop(manager, CFRunLoopGetCurrent(), kCFRunLoopDefaultMode);
+            let status = IOHIDManagerOpen(manager, 0);
+            if status == 0 {
+                let _ = started.send(Ok(CFRunLoopGetCurrent() as usize));
+                CFRunLoopRun();
+                IOHIDManagerClose(manager, 0);
+            } else {
+                let _ = started.send(Err(QuickscanError::command_failed("IOHIDManager", format!("open failed ({:#x})", status))));
+            }
+            CFRelease(manager as *const c_void);
+            drop(Box::from_raw(context));
+        });
+
+        match result.recv() {
+            Ok(Ok(run_loop)) => Ok(Capture { run_loop, thread }),
+            Ok(Err(e)) => {
+                let _ = thread.join();
+                Err(e)
+            }
+            Err(_) => Err(QuickscanError::command_failed("IOHIDManager", "capture thread exited")),
+        }
+    }
+}
+
+// A low-level keyboard hook sees every key before any window does, and needs
+// no window of its own; its thread only has to pump messages
+#[cfg(target_os = "windows")]
+mod platform {
+    use super::{emit, set1_code};
+    use crate::error::QuickscanError;
+    use std::sync::mpsc;
+    use std::sync::Mutex;
+    use std::thread::{self, JoinHandle};
+    use windows::core::PCWSTR;
+    use windows::Win32::Foundation::{HINSTANCE, LPARAM, LRESULT, WPARAM};
+    use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+    use windows::Win32::System::Threading::GetCurrentThreadId;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        CallNextHookEx, GetMessageW, PostThreadMessageW, SetWindowsHookExW, UnhookWindowsHookEx, HC_ACTION,
+        KBDLLHOOKSTRUCT, LLKHF_EXTENDED, MSG, WH_KEYBOARD_LL, WM_KEYDOWN, WM_KEYUP, WM_QUIT, WM_SYSKEYDOWN,
+        WM_SYSKEYUP,
+    };
+
+    // The hook procedure takes no context, so the app handle waits here
+    static HOOK_APP: Mutex<Option<tauri::AppHandle>> = Mutex::new(None);
+
+    pub struct Capture {
+        thread_id: u32,
+        thread: JoinHandle<()>,
+    }
+
+    impl Capture {
+        pub fn stop(self) {
+            let _ = unsafe { PostThreadMessageW(self.thread_id, WM_QUIT, WPARAM(0), LPARAM(0)) };
+            let _ = self.thread.join();
+            *HOOK_APP.lock().unwrap_or_else(|e| e.into_inner()) = None;
+        }
+    }
+
+    // Extended (E0-prefixed) scan codes are the right-hand modifiers, the
+    // arrow and navigation block and the Windows keys
+    fn scan_code(scan: u32, extended: bool) -> Option<&'static str> {
+        if !extended {
+            return set1_code(scan);
+        }
+        let code = match scan {
+            0x1c => "NumpadEnter",
+            0x1d => "ControlRight",
+            0x38 => "AltRight",
+            0x47 => "Home",
+            0x48 => "ArrowUp",
+            0x49 => "PageUp",
+            0x4b => "ArrowLeft",
+            0x4d => "ArrowRight",
+            0x4f => "End",
+            0x50 => "ArrowDown",
+            0x51 => "PageDown",
+            0x52 => "Insert",
+            0x53 => "Delete",
+            0x5b => "MetaLeft",
+            0x5c => "MetaRight",
+            0x5d => "ContextMenu",
+            _ => return None,
+        };
+        Some(code)
+    }
+
+    unsafe extern "system" fn hook(code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+        if code == HC_ACTION as i32 {
+            let event = &*(lparam.0 as *const KBDLLHOOKSTRUCT);
+            let pressed = match wparam.0 as u32 {
+                WM_KEYDOWN | WM_SYSKEYDOWN => Some(true),
+                WM_KEYUP | WM_SYSKEYUP => Some(false),
+                _ => None,
+            };
+            if let (Some(pressed), Some(app)) = (pressed, HOOK_APP.lock().unwrap_or_else(|e| e.into_inner()).as_ref()) {
+                let extended = event.flags.0 & LLKHF_EXTENDED.0 != 0;
+                let raw = event.scanCode | if extended { 0xe000 } else { 0 };
+                emit(app, scan_code(event.scanCode, extended), raw, pressed);
+            }
+        }
+        // Only watching: every key still reaches its window
+        CallNextHookEx(None, code, wparam, lparam)
+    }
+
+    pub fn start(app: tauri::AppHandle) -> Result<Capture, QuickscanError> {
+        *HOOK_APP.lock().unwrap_or_else(|e| e.into_inner()) = Some(app);
+        let (started, result) = mpsc::channel::<Result<u32, QuickscanError>>();
+        let thread = thread::spawn(move || unsafe {
+            let module = GetModuleHandleW(PCWSTR::null()).ok().map(HINSTANCE::from);
+            let hook = match SetWindowsHookExW(WH_KEYBOARD_LL, Some(hook), module, 0) {
+                Ok(hook) => hook,
+                Err(e) => {
+                    let _ = started.send(Err(QuickscanError::command_failed("SetWindowsHookEx", e.message())));
+                    return;
+                }
+            };
+            let _ = started.send(Ok(GetCurrentThreadId()));
+            let mut message = MSG::default();
+            // 0 for WM_QUIT, -1 on error
+            while GetMessageW(&mut message, None, 0, 0).0 > 0 {}
+            let _ = UnhookWindowsHookEx(hook);
+        });
+
+        match result.recv() {
+            Ok(Ok(thread_id)) => Ok(Capture { thread_id, thread }),
+            Ok(Err(e)) => {
+                let _ = thread.join();
+                Err(e)
+            }
+            Err(_) => Err(QuickscanError::command_failed("SetWindowsHookEx", "capture thread exited")),
+        }
+    }
+}
+
+// evdev: the keyboards' /dev/input/event* nodes, readable by root and the
+// input group
+#[cfg(target_os = "linux")]
+mod platform {
+    use super::{emit, proc_input_devices, set1_code, EV_REP};
+    use crate::error::QuickscanError;
+    use std::io::Read;
+    use std::os::unix::fs::OpenOptionsExt;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::thread::{self, JoinHandle};
+    use std::time::Duration;
+
+    const POLL_INTERVAL: Duration = Duration::from_millis(10);
+    const EV_KEY: u16 = 0x01;
+
+    pub struct Capture {
+        stop: Arc<AtomicBool>,
+        thread: JoinHandle<()>,
+    }
+
+    impl Capture {
+        pub fn stop(self) {
+            self.stop.store(true, Ordering::Relaxed);
+            let _ = self.thread.join();
+        }
+    }
+
+    fn evdev_code(code: u16) -> Option<&'static str> {
+        let code = match code {
+            0..=88 => return set1_code(code as u32),
+            97 => "ControlRight",
+            100 => "AltRight",
+            102 => "Home",
+            103 => "ArrowUp",
+            104 => "PageUp",
+            105 => "ArrowLeft",
+            106 => "ArrowRight",
+            107 => "End",
+            108 => "ArrowDown",
+            109 => "PageDown",
+            110 => "Insert",
+            111 => "Delete",
+            125 => "MetaLeft",
+            126 => "MetaRight",
+            127 => "ContextMenu",
+            464 => "Fn",
+            _ => return None,
+        };
+        Some(code)
+    }
+
+    pub fn start(app: tauri::AppHandle) -> Result<Capture, QuickscanError> {
+        let nodes: Vec<String> = proc_input_devices()
+            .into_iter()
+            .filter(|device| device.handlers.iter().any(|h| h == "kbd") && device.events & EV_REP != 0)
+            .filter_map(|device| device.handlers.into_iter().find(|h| h.starts_with("event")))
+            .map(|handler| format!("/dev/input/{}", handler))
+            .collect();
+
+        let mut files = vec![];
+        let mut last_error = None;
+        for node in &nodes {
+            match std::fs::OpenOptions::new().read(true).custom_flags(libc::O_NONBLOCK).open(node) {
+                Ok(file) => files.push(file),
+                Err(e) => last_error = Some(QuickscanError::io(node, e)),
+            }
+        }
+        if files.is_empty() {
+            return Err(last_error.unwrap_or_else(|| QuickscanError::command_failed("evdev", "no keyboard found")));
+        }
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread = {
+            let stop = stop.clone();
+            thread::spawn(move || {
+                let size = std::mem::size_of::<libc::input_event>();
+                let mut buffer = vec![0u8; size * 64];
+                while !stop.load(Ordering::Relaxed) {
+                    for file in &mut files {
+                        // WouldBlock: nothing new on this keyboard
+                        let Ok(read) = file.read(&mut buffer) else {
+                            continue;
+                        };
+                        for chunk in buffer[..read].chunks_exact(size) {
+                            let event = unsafe { std::ptr::read_unaligned(chunk.as_ptr() as *const libc::input_event) };
+                            // 1 press, 0 release, 2 auto-repeat
+                            if event.type_ == EV_KEY && event.value != 2 {
+                                emit(&app, evdev_code(event.code), event.code as u32, event.value == 1);
+                            }
+                        }
+                    }
+                    thread::sleep(POLL_INTERVAL);
+                }
+            })
+        };
+        Ok(Capture { stop, thread })
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+mod platform {
+    use crate::error::QuickscanError;
+
+    pub struct Capture;
+
+    impl Capture {
+        pub fn stop(self) {}
+    }
+
+    pub fn start(_app: tauri::AppHandle) -> Result<Capture, QuickscanError> {
+        Err(QuickscanError::unsupported("keyboard_test"))
+    }
+}
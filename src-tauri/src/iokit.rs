@@ -0,0 +1,129 @@
+// Direct IORegistry access, replacing screen-scraped `ioreg -l` text whose
+// layout shifts between macOS releases. Properties are converted to JSON so
+// callers can read them the same way they read system_profiler output.
+
+use core_foundation::array::CFArray;
+use core_foundation::base::{kCFAllocatorDefault, CFType, TCFType};
+use core_foundation::boolean::CFBoolean;
+use core_foundation::data::CFData;
+use core_foundation::dictionary::CFDictionary;
+use core_foundation::number::CFNumber;
+use core_foundation::string::CFString;
+use core_foundation_sys::dictionary::CFMutableDictionaryRef;
+use io_kit_sys::types::{io_iterator_t, io_registry_entry_t};
+use io_kit_sys::{
+    kIOMasterPortDefault, IOIteratorNext, IOObjectRelease, IORegistryEntryCreateCFProperties,
+    IOServiceGetMatchingService, IOServiceGetMatchingServices, IOServiceMatching,
+};
+use serde_json::{Map, Value};
+use std::ffi::{c_void, CString};
+
+const KERN_SUCCESS: i32 = 0;
+
+// All properties of the first service of the given IOKit class, e.g.
+// "AppleSmartBattery" or "IOPlatformExpertDevice"
+pub fn service_properties(class: &str) -> Option<Map<String, Value>> {
+    let matching = matching(class)?;
+    unsafe {
+        // IOServiceGetMatchingService consumes the matching dictionary
+        let service = IOServiceGetMatchingService(kIOMasterPortDefault, matching);
+        if service == 0 {
+            return None;
+        }
+        let properties = entry_properties(service);
+        IOObjectRelease(service);
+        properties
+    }
+}
+
+// Properties of every service of the given class, e.g. each "IOUSBHostDevice"
+pub fn all_service_properties(class: &str) -> Vec<Map<String, Value>> {
+    let Some(matching) = matching(class) else {
+        return vec![];
+    };
+    let mut services = vec![];
+    unsafe {
+        // Consumes the matching dictionary too
+        let mut iterator: io_iterator_t = 0;
+        if IOServiceGetMatchingServices(kIOMasterPortDefault, matching, &mut iterator) != KERN_SUCCESS {
+            return services;
+        }
+        loop {
+            let service = IOIteratorNext(iterator);
+            if service == 0 {
+                break;
+            }
+            services.extend(entry_properties(service));
+            IOObjectRelease(service);
+        }
+        IOObjectRelease(iterator);
+    }
+    services
+}
+
+fn matching(class: &str) -> Option<CFMutableDictionaryRef> {
+    let class = CString::new(class).ok()?;
+    let matching = unsafe { IOServiceMatching(class.as_ptr()) };
+    (!matching.is_null()).then_some(matching)
+}
+
+unsafe fn entry_properties(entry: io_registry_entry_t) -> Option<Map<String, Value>> {
+    let mut properties: CFMutableDictionaryRef = std::ptr::null_mut();
+    let result = IORegistryEntryCreateCFProperties(entry, &mut properties, kCFAllocatorDefault, 0);
+    if result != KERN_SUCCESS || properties.is_null() {
+        return None;
+    }
+
+    let properties = CFType::wrap_under_create_rule(properties as *const c_void);
+    match to_json(&properties) {
+        Value::Object(map) => Some(map),
+        _ => None,
+    }
+}
+
+pub fn string_property(properties: &Map<String, Value>, key: &str) -> Option<String> {
+    properties
+        .get(key)
+        .and_then(|v| v.as_str())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+fn to_json(value: &CFType) -> Value {
+    if let Some(string) = value.downcast::<CFString>() {
+        Value::String(string.to_string())
+    } else if let Some(boolean) = value.downcast::<CFBoolean>() {
+        Value::Bool(boolean.into())
+    } else if let Some(number) = value.downcast::<CFNumber>() {
+        number
+            .to_i64()
+            .map(Value::from)
+            .or_else(|| number.to_f64().map(Value::from))
+            .unwrap_or(Value::Null)
+    } else if let Some(data) = value.downcast::<CFData>() {
+        // Device tree strings (serials, model names) are NUL-terminated data
+        Value::String(String::from_utf8_lossy(data.bytes()).trim_end_matches('\0').to_string())
+    } else if let Some(array) = value.downcast::<CFArray<*const c_void>>() {
+        Value::Array(
+            array
+                .get_all_values()
+                .into_iter()
+                .map(|item| to_json(&unsafe { CFType::wrap_under_get_rule(item) }))
+                .collect(),
+        )
+    } else if let Some(dictionary) = value.downcast::<CFDictionary<*const c_void, *const c_void>>() {
+        let (keys, values) = dictionary.get_keys_and_values();
+        let map = keys
+            .into_iter()
+            .zip(values)
+            .map(|(key, value)| {
+                let key = unsafe { CFType::wrap_under_get_rule(key) };
+                let key = key.downcast::<CFString>().map(|k| k.to_string()).unwrap_or_default();
+                (key, to_json(&unsafe { CFType::wrap_under_get_rule(value) }))
+            })
+            .collect();
+        Value::Object(map)
+    } else {
+        Value::Null
+    }
+}
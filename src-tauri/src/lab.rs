@@ -0,0 +1,169 @@
+// The lab harness behind the `quickscan-lab` binary, built with the `lab`
+// feature. Maintainers run it on the machines of the hardware lab, one per
+// model and OS release, to build a corpus of what the system tools really
+// print: every external command's output and every WMI query's rows are kept
+// next to what the probes made of them, and the lot is posted to a collection
+// endpoint. A new OS release that changes a tool's output then shows up in the
+// corpus as a probe error or an empty field before a shop runs into it.
+//
+// The outputs are recorded as in evidence mode (evidence.rs).
+//
+// Unlike the headless scan it runs every probe that needs no window, the
+// installed OS's included, and doesn't ask for consent: whoever starts it on a
+// lab machine has agreed to its logs being read. The raw outputs carry
+// serials, account names and network names, so it belongs on lab machines
+// only.
+//
+//   quickscan-lab [--endpoint <url>] [--token <token>] [--output <file>]
+//
+// QUICKSCAN_LAB_ENDPOINT and QUICKSCAN_LAB_TOKEN stand in for the options.
+// Without an endpoint the corpus entry is only written out.
+
+use crate::evidence::{self, RawOutput};
+use crate::export::{installed_system, probe};
+use serde::Serialize;
+use serde_json::Value;
+use std::process::{Command, ExitCode};
+use std::time::Duration;
+use sysinfo::System;
+
+const USAGE: &str = "usage: quickscan-lab [--endpoint <url>] [--token <token>] [--output <file>]";
+// Bumped whenever a field of CorpusEntry changes meaning
+const SCHEMA_VERSION: u32 = 1;
+const UPLOAD_TIMEOUT: Duration = Duration::from_secs(120);
+
+#[derive(Serialize)]
+struct OsRelease {
+    name: Option<String>,
+    version: Option<String>,
+    kernel: Option<String>,
+    arch: &'static str,
+}
+
+#[derive(Serialize)]
+struct CorpusEntry {
+    schema_version: u32,
+    app_version: &'static str,
+    collected_at: String,
+    os: OsRelease,
+    parsed: Value, // each probe's result or error, as the headless report has them
+    raw: Vec<RawOutput>, // in the order they ran
+}
+
+// The headless report plus what it leaves out for describing the installed OS
+fn scan() -> Value {
+    let mut report = match crate::headless::scan(None) {
+        Value::Object(report) => report,
+        _ => serde_json::Map::new(),
+    };
+    installed_system(&mut report);
+    probe(&mut report, "crash_history", crate::crash_history::crash_history());
+    probe(&mut report, "rtc_battery", crate::rtc_battery::rtc_battery());
+    probe(&mut report, "power_events", crate::sleep::power_events());
+    Value::Object(report)
+}
+
+fn collect() -> Value {
+    evidence::start();
+    let parsed = scan();
+    let raw = evidence::take().unwrap_or_default();
+    let entry = CorpusEntry {
+        schema_version: SCHEMA_VERSION,
+        app_version: env!("CARGO_PKG_VERSION"),
+        collected_at: chrono::Local::now().to_rfc3339(),
+        os: OsRelease {
+            name: System::name(),
+            version: System::long_os_version().or_else(System::os_version),
+            kernel: System::kernel_version(),
+            arch: std::env::consts::ARCH,
+        },
+        parsed,
+        raw,
+    };
+    serde_json::to_value(entry).unwrap_or(Value::Null)
+}
+
+// Posted through curl, as the telemetry is; the token goes in a curl config
+// file rather than on the command line
+fn upload(endpoint: &str, token: Option<&str>, body: &str) -> Result<(), String> {
+    let temp = |extension: &str| std::env::temp_dir().join(format!("quickscan-lab-{}.{}", std::process::id(), extension));
+    let body_path = temp("json");
+    let config_path = temp("curlrc");
+
+    let quote = |value: &str| format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""));
+    let mut curl_config = vec![
+        format!("url = {}", quote(endpoint)),
+        format!("data-binary = {}", quote(&format!("@{}", body_path.to_string_lossy()))),
+        format!("header = {}", quote("Content-Type: application/json")),
+        format!("max-time = {}", UPLOAD_TIMEOUT.as_secs()),
+        "fail".to_string(),
+        "silent".to_string(),
+        "show-error".to_string(),
+    ];
+    if let Some(token) = token {
+        curl_config.push(format!("header = {}", quote(&format!("Authorization: Bearer {}", token))));
+    }
+
+    let result = crate::digest::write_private(&body_path, body)
+        .and_then(|()| crate::digest::write_private(&config_path, &(curl_config.join("\n") + "\n")))
+        .and_then(|()| {
+            let mut command = Command::new("curl");
+            command.arg("--config").arg(&config_path);
+            crate::process::run_cmd_with_timeout(&mut command, UPLOAD_TIMEOUT + Duration::from_secs(5)).map_err(|e| e.to_string())
+        })
+        .and_then(|output| {
+            if output.status.success() {
+                Ok(())
+            } else {
+                Err(format!("curl: {}", String::from_utf8_lossy(&output.stderr).trim()))
+            }
+        });
+    let _ = std::fs::remove_file(&body_path);
+    let _ = std::fs::remove_file(&config_path);
+    result
+}
+
+pub fn run() -> ExitCode {
+    let mut endpoint = std::env::var("QUICKSCAN_LAB_ENDPOINT").ok().filter(|url| !url.is_empty());
+    let mut token = std::env::var("QUICKSCAN_LAB_TOKEN").ok().filter(|token| !token.is_empty());
+    let mut output: Option<String> = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--endpoint" => endpoint = args.next(),
+            "--token" => token = args.next(),
+            "--output" | "-o" => output = args.next(),
+            "--help" | "-h" => {
+                println!("{}", USAGE);
+                return ExitCode::SUCCESS;
+            }
+            _ => {
+                eprintln!("{}", USAGE);
+                return ExitCode::from(2);
+            }
+        }
+    }
+    if endpoint.is_none() && output.is_none() {
+        eprintln!("{}", USAGE);
+        eprintln!("nowhere to send the corpus entry: give --endpoint or --output");
+        return ExitCode::from(2);
+    }
+
+    let content = serde_json::to_string_pretty(&collect()).unwrap_or_default();
+    // Written first, so an upload that fails still leaves the entry behind
+    if let Some(path) = &output {
+        if let Err(e) = std::fs::write(path, content.clone() + "\n") {
+            eprintln!("{}: {}", path, e);
+            return ExitCode::FAILURE;
+        }
+        eprintln!("corpus entry written to {}", path);
+    }
+    if let Some(endpoint) = &endpoint {
+        if let Err(e) = upload(endpoint, token.as_deref(), &content) {
+            eprintln!("{}: {}", endpoint, e);
+            return ExitCode::FAILURE;
+        }
+        eprintln!("corpus entry sent to {}", endpoint);
+    }
+    ExitCode::SUCCESS
+}
@@ -1,6 +1,93 @@
 use serde::{Deserialize, Serialize};
 use sysinfo::System;
+#[cfg(target_os = "macos")]
 use std::process::Command;
+use error::QuickscanError;
+use probe_scheduler::ProbeScheduler;
+use tauri::Manager;
+
+mod accounts;
+mod activation_lock;
+mod apple_overview;
+mod audio;
+mod battery_monitor;
+mod battery_pack;
+mod bluetooth;
+mod board;
+mod camera;
+mod clock;
+mod colorimeter;
+#[cfg(target_os = "macos")]
+mod component_manifest;
+mod component_serials;
+mod consent;
+mod crash_history;
+mod data_remnants;
+mod device_model;
+mod digest;
+mod disk_bench;
+mod display;
+mod encryption;
+mod energy;
+mod error;
+mod evidence;
+mod export;
+mod fingerprint;
+mod genuine_parts;
+mod gpu;
+mod grade;
+mod hardware_watch;
+#[cfg(feature = "headless")]
+pub mod headless;
+mod hooks;
+mod indicator_help;
+mod input_devices;
+#[cfg(target_os = "macos")]
+mod iokit;
+#[cfg(feature = "lab")]
+pub mod lab;
+mod locales;
+mod mdm;
+mod memory_modules;
+mod memory_test;
+mod network_adapters;
+mod os_install;
+mod panel_aging;
+mod part_history;
+mod pdf_report;
+mod pixel_defects;
+mod preferences;
+mod probe_scheduler;
+mod process;
+mod recommendations;
+mod region;
+mod rtc_battery;
+mod rules;
+mod scan_config;
+mod scripting;
+mod seal;
+mod security;
+#[cfg(target_os = "macos")]
+mod serial_decode;
+mod sleep;
+mod smart;
+#[cfg(target_os = "macos")]
+mod smc;
+mod speed_test;
+mod stress;
+mod summary;
+mod telemetry;
+mod thermal;
+mod touch_bar;
+mod usage_estimate;
+mod usb;
+mod watchdog;
+mod wifi;
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+mod wifi_oui;
+mod windows_activation;
+#[cfg(target_os = "windows")]
+mod wmi_provider;
 
 #[derive(Serialize, Deserialize)]
 pub struct CpuInfo {
@@ -33,6 +120,11 @@ pub struct BatteryInfo {
     pub current_capacity: u32,
     pub is_charging: bool,
     pub temperature: Option<f64>,
+    pub serial: Option<String>,
+    pub manufacturer: Option<String>, // the pack maker: "SMP", "LGC", "Celxpert"
+    pub device_name: Option<String>, // gauge or pack model: "bq20z451", "DELL 7FHHV88"
+    pub firmware_version: Option<String>, // gas gauge firmware; macOS only
+    pub manufacture_date: Option<String>, // YYYY-MM-DD
 }
 
 #[derive(Serialize, Deserialize)]
@@ -41,15 +133,27 @@ pub struct StorageHealth {
     pub smart_status: String,
     pub power_on_hours: Option<u64>,
     pub temperature: Option<f64>,
+    #[serde(default)]
+    pub smart: Option<smart::SmartAttributes>,
+    pub device: String, // "disk0" on macOS, the disk number on Windows
+    pub is_internal: bool,
+    pub is_boot: bool,
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct RefurbishmentCheck {
     pub is_refurbished: bool,
     pub confidence: String, // "high", "medium", "low"
+    #[serde(default)]
+    pub confidence_score: u32, // 0-100, the weighted indicators (scan_config.rs)
     pub indicators: Vec<RefurbishmentIndicator>,
     pub replaced_parts: Vec<String>,
     pub details: RefurbishmentDetails,
+    #[serde(default)]
+    pub recommendations: Vec<recommendations::Recommendation>,
+    // From custom scripts, applied by compute_device_grade
+    #[serde(default)]
+    pub grade_modifiers: Vec<scripting::GradeModifier>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -58,6 +162,10 @@ pub struct RefurbishmentIndicator {
     pub detected: bool,
     pub description: String,
     pub severity: String, // "info", "warning", "critical"
+    #[serde(default)]
+    pub help: Option<indicator_help::IndicatorHelp>,
+    #[serde(default)]
+    pub caveat: Option<String>, // why it's less certain than usual: "clock_unreliable"
 }
 
 #[derive(Serialize, Deserialize)]
@@ -82,7 +190,11 @@ pub struct SystemHardwareInfo {
 }
 
 #[tauri::command]
-fn get_hardware_info() -> SystemHardwareInfo {
+async fn get_hardware_info(app: tauri::AppHandle) -> SystemHardwareInfo {
+    app.state::<ProbeScheduler>().run(hardware_info).await
+}
+
+fn hardware_info() -> SystemHardwareInfo {
     let mut sys = System::new_all();
     sys.refresh_all();
 
@@ -123,48 +235,70 @@ fn get_hardware_info() -> SystemHardwareInfo {
 
 #[cfg(target_os = "macos")]
 fn get_serial_number() -> String {
-    let output = Command::new("ioreg")
-        .args(["-l"])
-        .output()
-        .ok();
-    
-    if let Some(out) = output {
-        let stdout = String::from_utf8_lossy(&out.stdout);
-        for line in stdout.lines() {
-            if line.contains("IOPlatformSerialNumber") {
-                if let Some(serial) = line.split('"').nth(3) {
-                    return serial.to_string();
-                }
-            }
-        }
-    }
-    "Unknown".to_string()
+    iokit::service_properties("IOPlatformExpertDevice")
+        .and_then(|platform| iokit::string_property(&platform, "IOPlatformSerialNumber"))
+        .unwrap_or_else(|| "Unknown".to_string())
+}
+
+#[cfg(target_os = "windows")]
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Win32Bios {
+    manufacturer: Option<String>,
+    serial_number: Option<String>,
+    release_date: Option<String>,
 }
 
 #[cfg(target_os = "windows")]
 fn get_serial_number() -> String {
-    let output = Command::new("wmic")
-        .args(["bios", "get", "serialnumber"])
-        .output()
-        .ok();
-    
-    if let Some(out) = output {
-        let stdout = String::from_utf8_lossy(&out.stdout);
-        let lines: Vec<&str> = stdout.lines().collect();
-        if lines.len() > 1 {
-            return lines[1].trim().to_string();
-        }
-    }
-    "Unknown".to_string()
+    wmi_provider::WmiProvider::global()
+        .query_first::<Win32Bios>(wmi_provider::CIMV2, "SELECT Manufacturer, SerialNumber, ReleaseDate FROM Win32_BIOS")
+        .and_then(|bios| bios.serial_number)
+        .map(|serial| serial.trim().to_string())
+        .filter(|serial| !serial.is_empty())
+        .unwrap_or_else(|| "Unknown".to_string())
+}
+
+// Readable by root only on most distributions; the headless build runs as root
+#[cfg(target_os = "linux")]
+fn get_serial_number() -> String {
+    std::fs::read_to_string("/sys/class/dmi/id/product_serial")
+        .ok()
+        .and_then(|serial| component_serials::clean(&serial))
+        .unwrap_or_else(|| "Unknown".to_string())
 }
 
-#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
 fn get_serial_number() -> String {
     "Unknown".to_string()
 }
 
+#[cfg(target_os = "macos")]
+fn get_wifi_mac_macos() -> Option<String> {
+    let output = process::run_cmd(Command::new("networksetup").args(["-listallhardwareports"]))
+        .ok()?;
+    
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut in_wifi_port = false;
+    for line in stdout.lines() {
+        if let Some(port) = line.strip_prefix("Hardware Port:") {
+            in_wifi_port = port.contains("Wi-Fi") || port.contains("AirPort");
+        } else if in_wifi_port {
+            if let Some(mac) = line.strip_prefix("Ethernet Address:") {
+                return Some(mac.trim().to_string());
+            }
+        }
+    }
+    None
+}
+
 #[tauri::command]
-fn get_battery_info() -> Option<BatteryInfo> {
+async fn get_battery_info(probes: tauri::State<'_, ProbeScheduler>) -> Result<Option<BatteryInfo>, QuickscanError> {
+    probes.run(battery_info).await
+}
+
+// Ok(None) means the machine has no battery
+fn battery_info() -> Result<Option<BatteryInfo>, QuickscanError> {
     #[cfg(target_os = "macos")]
     {
         get_battery_info_macos()
@@ -173,92 +307,221 @@ fn get_battery_info() -> Option<BatteryInfo> {
     {
         get_battery_info_windows()
     }
-    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    #[cfg(target_os = "linux")]
+    {
+        get_battery_info_linux()
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
     {
-        None
+        Err(QuickscanError::unsupported("battery"))
     }
 }
 
 #[cfg(target_os = "macos")]
-fn get_battery_info_macos() -> Option<BatteryInfo> {
-    let output = Command::new("system_profiler")
-        .args(["SPPowerDataType", "-json"])
-        .output()
-        .ok()?;
+fn get_battery_info_macos() -> Result<Option<BatteryInfo>, QuickscanError> {
+    let Some(battery) = iokit::service_properties("AppleSmartBattery") else {
+        return Ok(None);
+    };
+    let number = |key: &str| battery.get(key).and_then(|v| v.as_u64());
     
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let json: serde_json::Value = serde_json::from_str(&stdout).ok()?;
-    
-    let power_data = json.get("SPPowerDataType")?.as_array()?;
-    
-    // Find battery information section
-    let battery_info = power_data.iter()
-        .find(|item| item.get("_name").and_then(|n| n.as_str()) == Some("spbattery_information"))?;
-    
-    // Extract charge info
-    let charge_info = battery_info.get("sppower_battery_charge_info")?;
-    let is_charging = charge_info.get("sppower_battery_is_charging")
-        .and_then(|v| v.as_str())
-        .map(|s| s == "TRUE")
-        .unwrap_or(false);
-    let current_capacity = charge_info.get("sppower_battery_state_of_charge")
-        .and_then(|v| v.as_u64())
-        .unwrap_or(0) as u32;
-    
-    // Extract health info
-    let health_info = battery_info.get("sppower_battery_health_info")?;
-    let cycle_count = health_info.get("sppower_battery_cycle_count")
-        .and_then(|v| v.as_u64())
-        .unwrap_or(0) as u32;
-    
-    // Parse health percentage from string like "96%"
-    let health = health_info.get("sppower_battery_health_maximum_capacity")
-        .and_then(|v| v.as_str())
-        .and_then(|s| s.trim_end_matches('%').parse::<f64>().ok())
-        .unwrap_or(100.0);
-    
-    // Calculate max_capacity based on health percentage (assuming design_capacity as 100 units)
-    let design_capacity: u32 = 100;
-    let max_capacity = health as u32;
-    
-    Some(BatteryInfo {
+    // Apple silicon reports CurrentCapacity/MaxCapacity as percentages and the mAh values
+    // under AppleRaw*; Intel Macs only have the mAh values
+    let (current, full) = match (number("AppleRawCurrentCapacity"), number("AppleRawMaxCapacity")) {
+        (Some(current), Some(full)) => (current, full),
+        _ => (number("CurrentCapacity").unwrap_or(0), number("MaxCapacity").unwrap_or(0)),
+    };
+    let current_capacity = (current * 100).checked_div(full).map_or(0, |percent| percent.min(100) as u32);
+    
+    // Health is measured like macOS' "Maximum Capacity": full charge capacity over design capacity
+    let design_capacity = number("DesignCapacity").unwrap_or(0) as u32;
+    let max_capacity = number("NominalChargeCapacity").unwrap_or(full) as u32;
+    let health = if design_capacity > 0 && max_capacity > 0 {
+        (max_capacity as f64 / design_capacity as f64 * 100.0).min(100.0)
+    } else {
+        100.0
+    };
+    
+    // Apple silicon keeps the pack details under BatteryData
+    let battery_data = battery.get("BatteryData");
+    let detail = |key: &str| battery.get(key).or_else(|| battery_data?.get(key));
+    let text = |key: &str| detail(key).and_then(|v| v.as_str()).and_then(component_serials::clean);
+
+    Ok(Some(BatteryInfo {
         health,
-        cycle_count,
+        cycle_count: number("CycleCount").unwrap_or(0) as u32,
         design_capacity,
         max_capacity,
         current_capacity,
-        is_charging,
-        temperature: None, // SPPowerDataType doesn't provide temperature
-    })
+        is_charging: battery.get("IsCharging").and_then(|v| v.as_bool()).unwrap_or(false),
+        // Reported in hundredths of a degree Celsius
+        temperature: number("Temperature").map(|t| t as f64 / 100.0),
+        serial: text("Serial").or_else(|| text("BatterySerialNumber")),
+        manufacturer: text("Manufacturer"),
+        device_name: text("DeviceName"),
+        firmware_version: detail("GasGaugeFirmwareVersion").and_then(|v| v.as_u64()).map(|v| v.to_string()),
+        manufacture_date: detail("ManufactureDate").and_then(|v| v.as_u64()).and_then(battery_pack::sbs_date),
+    }))
 }
 
 #[cfg(target_os = "windows")]
-fn get_battery_info_windows() -> Option<BatteryInfo> {
-    let output = Command::new("powershell")
-        .args(["-Command", "Get-WmiObject -Class Win32_Battery | Select-Object EstimatedChargeRemaining, DesignCapacity, FullChargeCapacity, BatteryStatus | ConvertTo-Json"])
-        .output()
-        .ok()?;
+fn get_battery_info_windows() -> Result<Option<BatteryInfo>, QuickscanError> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct Win32Battery {
+        estimated_charge_remaining: Option<u16>,
+        battery_status: Option<u16>,
+        name: Option<String>,
+    }
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct BatteryStaticData {
+        designed_capacity: u32,
+        manufacture_name: Option<String>,
+        device_name: Option<String>,
+        serial_number: Option<String>,
+        manufacture_date: Option<String>, // CIM datetime, often blank
+    }
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct BatteryFullChargedCapacity {
+        full_charged_capacity: u32,
+    }
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct BatteryCycleCount {
+        cycle_count: u32,
+    }
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct BatteryStatus {
+        charging: bool,
+    }
     
-    let stdout = String::from_utf8_lossy(&output.stdout);
+    // Win32_Battery only has the charge level; capacities and cycle count live in root/wmi
+    let wmi = wmi_provider::WmiProvider::global();
+    let Some(battery) = wmi
+        .query::<Win32Battery>(wmi_provider::CIMV2, "SELECT EstimatedChargeRemaining, BatteryStatus, Name FROM Win32_Battery")?
+        .into_iter()
+        .next()
+    else {
+        return Ok(None);
+    };
+    
+    // Capacities are in mWh
+    let static_data = wmi.query_first::<BatteryStaticData>(
+        wmi_provider::WMI,
+        "SELECT DesignedCapacity, ManufactureName, DeviceName, SerialNumber, ManufactureDate FROM BatteryStaticData",
+    );
+    let design_capacity = static_data.as_ref().map(|b| b.designed_capacity).unwrap_or(0);
+    let max_capacity = wmi
+        .query_first::<BatteryFullChargedCapacity>(wmi_provider::WMI, "SELECT FullChargedCapacity FROM BatteryFullChargedCapacity")
+        .map(|b| b.full_charged_capacity)
+        .unwrap_or(0);
+    let cycle_count = wmi
+        .query_first::<BatteryCycleCount>(wmi_provider::WMI, "SELECT CycleCount FROM BatteryCycleCount")
+        .map(|b| b.cycle_count)
+        .unwrap_or(0);
+    let health = if design_capacity > 0 && max_capacity > 0 {
+        (max_capacity as f64 / design_capacity as f64 * 100.0).min(100.0)
+    } else {
+        100.0
+    };
     
-    // Parse basic info (simplified for Windows)
-    let current_capacity: u32 = 100;
-    let design_capacity: u32 = 100;
-    let max_capacity: u32 = 100;
+    // Win32_Battery BatteryStatus 6-9 are the charging states
+    let is_charging = wmi
+        .query_first::<BatteryStatus>(wmi_provider::WMI, "SELECT Charging FROM BatteryStatus")
+        .map(|b| b.charging)
+        .unwrap_or_else(|| matches!(battery.battery_status, Some(6..=9)));
     
-    Some(BatteryInfo {
-        health: 100.0,
-        cycle_count: 0, // Windows doesn't easily expose this
+    Ok(Some(BatteryInfo {
+        health,
+        cycle_count,
         design_capacity,
         max_capacity,
-        current_capacity,
-        is_charging: false,
+        current_capacity: battery.estimated_charge_remaining.unwrap_or(0) as u32,
+        is_charging,
         temperature: None,
-    })
+        serial: static_data.as_ref().and_then(|b| b.serial_number.as_deref()).and_then(component_serials::clean),
+        manufacturer: static_data.as_ref().and_then(|b| b.manufacture_name.as_deref()).and_then(component_serials::clean),
+        device_name: static_data
+            .as_ref()
+            .and_then(|b| b.device_name.as_deref())
+            .or(battery.name.as_deref())
+            .and_then(component_serials::clean),
+        firmware_version: None,
+        manufacture_date: static_data
+            .and_then(|b| b.manufacture_date)
+            .and_then(|date| wmi_provider::format_datetime(&date))
+            .map(|date| date[..10].to_string()),
+    }))
+}
+
+#[cfg(target_os = "linux")]
+fn get_battery_info_linux() -> Result<Option<BatteryInfo>, QuickscanError> {
+    let power_supply = "/sys/class/power_supply";
+    let Some(battery_dir) = std::fs::read_dir(power_supply)
+        .map_err(|e| QuickscanError::io(power_supply, e))?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            std::fs::read_to_string(path.join("type"))
+                .map(|t| t.trim() == "Battery")
+                .unwrap_or(false)
+        })
+        .min()
+    else {
+        return Ok(None);
+    };
+    
+    let read = |name: &str| std::fs::read_to_string(battery_dir.join(name)).ok().map(|s| s.trim().to_string());
+    let read_u64 = |name: &str| read(name).and_then(|s| s.parse::<u64>().ok());
+    
+    // Drivers report either energy (µWh) or charge (µAh); convert to mWh / mAh
+    let (design, full) = match (read_u64("energy_full_design"), read_u64("energy_full")) {
+        (Some(design), Some(full)) => (design, full),
+        _ => match (read_u64("charge_full_design"), read_u64("charge_full")) {
+            (Some(design), Some(full)) => (design, full),
+            _ => {
+                let source = battery_dir.to_string_lossy();
+                return Err(QuickscanError::parse(&source, "no energy_full or charge_full attributes"));
+            }
+        },
+    };
+    let design_capacity = (design / 1000) as u32;
+    let max_capacity = (full / 1000) as u32;
+    let health = if design > 0 {
+        (full as f64 / design as f64 * 100.0).min(100.0)
+    } else {
+        100.0
+    };
+    
+    Ok(Some(BatteryInfo {
+        health,
+        cycle_count: read_u64("cycle_count").unwrap_or(0) as u32,
+        design_capacity,
+        max_capacity,
+        current_capacity: read_u64("capacity").unwrap_or(0) as u32,
+        is_charging: read("status").as_deref() == Some("Charging"),
+        // temp is in tenths of a degree Celsius when the driver exposes it
+        temperature: read("temp").and_then(|s| s.parse::<f64>().ok()).map(|t| t / 10.0),
+        serial: read("serial_number").as_deref().and_then(component_serials::clean),
+        manufacturer: read("manufacturer").as_deref().and_then(component_serials::clean),
+        device_name: read("model_name").as_deref().and_then(component_serials::clean),
+        firmware_version: None,
+        // Only some drivers (the ACPI battery driver doesn't) expose the date
+        manufacture_date: match (read_u64("manufacture_year"), read_u64("manufacture_month"), read_u64("manufacture_day")) {
+            (Some(year), Some(month), Some(day)) => Some(format!("{:04}-{:02}-{:02}", year, month, day)),
+            _ => None,
+        },
+    }))
 }
 
 #[tauri::command]
-fn get_storage_health() -> Option<StorageHealth> {
+async fn get_storage_health(probes: tauri::State<'_, ProbeScheduler>) -> Result<Vec<StorageHealth>, QuickscanError> {
+    probes.run(storage_health).await
+}
+
+fn storage_health() -> Result<Vec<StorageHealth>, QuickscanError> {
     #[cfg(target_os = "macos")]
     {
         get_storage_health_macos()
@@ -267,107 +530,200 @@ fn get_storage_health() -> Option<StorageHealth> {
     {
         get_storage_health_windows()
     }
-    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    #[cfg(target_os = "linux")]
+    {
+        get_storage_health_linux()
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
     {
-        None
+        Err(QuickscanError::unsupported("storage_health"))
     }
 }
 
 #[cfg(target_os = "macos")]
-fn get_storage_health_macos() -> Option<StorageHealth> {
-    // Get disk model
-    let output = Command::new("system_profiler")
-        .args(["SPStorageDataType", "-json"])
-        .output()
-        .ok()?;
-    
+fn get_storage_health_macos() -> Result<Vec<StorageHealth>, QuickscanError> {
+    let boot_disk = boot_disk_macos();
+    physical_disks_macos()?
+        .into_iter()
+        .map(|(device, is_internal)| {
+            let info = process::run_cmd_checked(Command::new("diskutil").args(["info", &device]))?;
+            let info = String::from_utf8_lossy(&info.stdout);
+            let field = |name: &str| {
+                info.lines()
+                    .find_map(|line| line.trim().strip_prefix(name))
+                    .map(|value| value.trim_start_matches(':').trim().to_string())
+            };
+            
+            let smart = smart::read_macos(&device);
+            Ok(StorageHealth {
+                model: field("Device / Media Name").unwrap_or_else(|| "Unknown".to_string()),
+                // External enclosures usually don't pass SMART through
+                smart_status: field("SMART Status").unwrap_or_else(|| "Unknown".to_string()),
+                power_on_hours: smart.as_ref().and_then(|s| s.power_on_hours),
+                temperature: smart.as_ref().and_then(|s| s.temperature),
+                smart,
+                is_boot: boot_disk.as_deref() == Some(device.as_str()),
+                device,
+                is_internal,
+            })
+        })
+        .collect()
+}
+
+// Whole physical disks with whether they are internal, from headers like
+// "/dev/disk0 (internal, physical):"
+#[cfg(target_os = "macos")]
+fn physical_disks_macos() -> Result<Vec<(String, bool)>, QuickscanError> {
+    let output = process::run_cmd_checked(Command::new("diskutil").args(["list", "physical"]))?;
     let stdout = String::from_utf8_lossy(&output.stdout);
-    let mut model = "Unknown".to_string();
-    
-    // Simple parsing for device name
-    if let Ok(json) = serde_json::from_str::<serde_json::Value>(&stdout) {
-        if let Some(storage) = json.get("SPStorageDataType") {
-            if let Some(arr) = storage.as_array() {
-                if let Some(first) = arr.first() {
-                    if let Some(physical) = first.get("physical_drive") {
-                        if let Some(name) = physical.get("device_name") {
-                            model = name.as_str().unwrap_or("Unknown").to_string();
-                        }
-                    }
-                }
-            }
-        }
-    }
-    
-    // Get SMART status
-    let smart_output = Command::new("diskutil")
-        .args(["info", "disk0"])
-        .output()
-        .ok()?;
-    
-    let smart_stdout = String::from_utf8_lossy(&smart_output.stdout);
-    let mut smart_status = "Unknown".to_string();
-    
-    for line in smart_stdout.lines() {
-        if line.contains("SMART Status") {
-            smart_status = line.split(':').nth(1).unwrap_or("Unknown").trim().to_string();
-            break;
-        }
-    }
-    
-    Some(StorageHealth {
-        model,
-        smart_status,
-        power_on_hours: None,
-        temperature: None,
-    })
+    Ok(stdout
+        .lines()
+        .filter_map(|line| line.strip_prefix("/dev/"))
+        .filter_map(|line| line.split_once(" ("))
+        .map(|(device, kind)| (device.to_string(), kind.starts_with("internal")))
+        .collect())
+}
+
+// The boot volume lives on a synthesized APFS container; its physical store
+// (e.g. disk0s2) names the real disk
+#[cfg(target_os = "macos")]
+fn boot_disk_macos() -> Option<String> {
+    let output = process::run_cmd(Command::new("diskutil").args(["info", "/"])).ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let field = |name: &str| {
+        stdout
+            .lines()
+            .find_map(|line| line.trim().strip_prefix(name))
+            .map(|value| value.trim_start_matches(':').trim().to_string())
+    };
+    let store = field("APFS Physical Store").or_else(|| field("Part of Whole"))?;
+    // disk0s2 -> disk0
+    let digits = store.trim_start_matches("disk");
+    let number: String = digits.chars().take_while(|c| c.is_ascii_digit()).collect();
+    (!number.is_empty()).then(|| format!("disk{}", number))
 }
 
 #[cfg(target_os = "windows")]
-fn get_storage_health_windows() -> Option<StorageHealth> {
-    let output = Command::new("powershell")
-        .args(["-Command", "Get-PhysicalDisk | Select-Object FriendlyName, HealthStatus | ConvertTo-Json"])
-        .output()
-        .ok()?;
+fn get_storage_health_windows() -> Result<Vec<StorageHealth>, QuickscanError> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct PhysicalDisk {
+        device_id: String,
+        friendly_name: Option<String>,
+        health_status: Option<u16>,
+        bus_type: Option<u16>,
+    }
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct Disk {
+        number: u32,
+        is_boot: Option<bool>,
+    }
     
-    let stdout = String::from_utf8_lossy(&output.stdout);
+    let wmi = wmi_provider::WmiProvider::global();
+    let disks = wmi.query::<PhysicalDisk>(
+        wmi_provider::STORAGE,
+        "SELECT DeviceId, FriendlyName, HealthStatus, BusType FROM MSFT_PhysicalDisk",
+    )?;
+    // MSFT_Disk.Number matches MSFT_PhysicalDisk.DeviceId
+    let boot_disks: Vec<String> = wmi
+        .query::<Disk>(wmi_provider::STORAGE, "SELECT Number, IsBoot FROM MSFT_Disk")
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|disk| disk.is_boot == Some(true))
+        .map(|disk| disk.number.to_string())
+        .collect();
     
-    Some(StorageHealth {
-        model: "Unknown".to_string(),
-        smart_status: "Healthy".to_string(),
-        power_on_hours: None,
-        temperature: None,
-    })
+    let mut disks: Vec<StorageHealth> = disks
+        .into_iter()
+        .map(|disk| {
+            let smart_status = match disk.health_status {
+                Some(0) => "Verified",
+                Some(1) => "Warning",
+                Some(2) => "Failing",
+                _ => "Unknown",
+            };
+            let smart = smart::read_windows(&disk.device_id);
+            StorageHealth {
+                model: disk.friendly_name.unwrap_or_else(|| "Unknown".to_string()),
+                smart_status: smart_status.to_string(),
+                power_on_hours: smart.as_ref().and_then(|s| s.power_on_hours),
+                temperature: smart.as_ref().and_then(|s| s.temperature),
+                smart,
+                is_boot: boot_disks.contains(&disk.device_id),
+                // BusType 4 = 1394, 7 = USB, 12 = SD, 13 = MMC
+                is_internal: !matches!(disk.bus_type, Some(4 | 7 | 12 | 13)),
+                device: disk.device_id,
+            }
+        })
+        .collect();
+    disks.sort_by(|a, b| a.device.cmp(&b.device));
+    Ok(disks)
 }
 
-#[tauri::command]
-fn get_network_info() -> serde_json::Value {
-    #[cfg(target_os = "macos")]
-    {
-        let wifi = Command::new("networksetup")
-            .args(["-getairportpower", "en0"])
-            .output()
-            .map(|o| String::from_utf8_lossy(&o.stdout).contains("On"))
-            .unwrap_or(false);
-        
-        let bluetooth = Command::new("system_profiler")
-            .args(["SPBluetoothDataType"])
-            .output()
-            .map(|o| !o.stdout.is_empty())
-            .unwrap_or(false);
-        
-        serde_json::json!({
-            "wifi": { "available": true, "enabled": wifi },
-            "bluetooth": { "available": bluetooth, "enabled": bluetooth }
-        })
-    }
-    #[cfg(not(target_os = "macos"))]
-    {
-        serde_json::json!({
-            "wifi": { "available": true, "enabled": true },
-            "bluetooth": { "available": true, "enabled": true }
+// Whole disks from /sys/block; partitions, loop devices and device-mapper
+// volumes have no device/ link there or are virtual
+#[cfg(target_os = "linux")]
+fn get_storage_health_linux() -> Result<Vec<StorageHealth>, QuickscanError> {
+    let entries = std::fs::read_dir("/sys/block").map_err(|e| QuickscanError::io("/sys/block", e))?;
+    let boot_disk = boot_disk_linux();
+    let mut disks: Vec<StorageHealth> = entries
+        .flatten()
+        .filter(|entry| entry.path().join("device").exists())
+        .map(|entry| {
+            let device = entry.file_name().to_string_lossy().to_string();
+            let read = |name: &str| std::fs::read_to_string(entry.path().join(name)).ok().map(|s| s.trim().to_string());
+            // /sys/block/sdb -> ../devices/pci0000:00/0000:00:14.0/usb2/2-1/...
+            let on_usb = std::fs::canonicalize(entry.path()).is_ok_and(|path| path.to_string_lossy().contains("/usb"));
+            let (smart, passed) = smart::read_linux(&device);
+            StorageHealth {
+                model: read("device/model").filter(|model| !model.is_empty()).unwrap_or_else(|| "Unknown".to_string()),
+                smart_status: match passed {
+                    Some(true) => "Verified",
+                    Some(false) => "Failing",
+                    None => "Unknown",
+                }
+                .to_string(),
+                power_on_hours: smart.as_ref().and_then(|s| s.power_on_hours),
+                temperature: smart.as_ref().and_then(|s| s.temperature),
+                smart,
+                is_boot: boot_disk.as_deref() == Some(device.as_str()),
+                is_internal: !on_usb && read("removable").as_deref() != Some("1"),
+                device,
+            }
         })
-    }
+        .collect();
+    disks.sort_by(|a, b| a.device.cmp(&b.device));
+    Ok(disks)
+}
+
+// The disk holding the root filesystem: /dev/nvme0n1p2 -> nvme0n1. A live
+// system's root is an overlay in memory and has none.
+#[cfg(target_os = "linux")]
+fn boot_disk_linux() -> Option<String> {
+    let mounts = std::fs::read_to_string("/proc/mounts").ok()?;
+    let source = mounts.lines().find_map(|line| {
+        let mut fields = line.split_whitespace();
+        let source = fields.next()?;
+        (fields.next()? == "/").then(|| source.to_string())
+    })?;
+    let partition = source.strip_prefix("/dev/")?;
+    // /sys/class/block/nvme0n1p2 -> ../../devices/.../nvme0n1/nvme0n1p2
+    let path = std::fs::canonicalize(format!("/sys/class/block/{}", partition)).ok()?;
+    let parent = path.parent()?.file_name()?.to_string_lossy().to_string();
+    Some(if std::path::Path::new(&format!("/sys/block/{}", parent)).exists() { parent } else { partition.to_string() })
+}
+
+#[tauri::command]
+async fn get_network_info(probes: tauri::State<'_, ProbeScheduler>) -> Result<serde_json::Value, QuickscanError> {
+    probes.run(network_info).await
+}
+
+fn network_info() -> Result<serde_json::Value, QuickscanError> {
+    let wifi = wifi::wifi_info()?;
+    // Null rather than failing the whole step, e.g. without bluez installed
+    let bluetooth = bluetooth::bluetooth_info().ok();
+    Ok(serde_json::json!({ "wifi": wifi, "bluetooth": bluetooth }))
 }
 
 #[tauri::command]
@@ -376,43 +732,432 @@ fn greet(name: &str) -> String {
 }
 
 #[tauri::command]
-fn check_refurbishment() -> RefurbishmentCheck {
-    #[cfg(target_os = "macos")]
-    {
-        check_refurbishment_macos()
+async fn check_refurbishment(app: tauri::AppHandle, claimed_region: Option<String>) -> RefurbishmentCheck {
+    let probes = app.state::<ProbeScheduler>().inner().clone();
+    probes.run(move || refurbishment_check(&app, claimed_region.as_deref())).await
+}
+
+// `claimed_region` is the ISO 3166 code of where the seller says the machine was sold
+fn refurbishment_check(app: &tauri::AppHandle, claimed_region: Option<&str>) -> RefurbishmentCheck {
+    let mut check = hardware_refurbishment_check();
+    apply_bluetooth_pairings(&mut check);
+    apply_data_remnants(&mut check);
+    apply_signed_in_accounts(&mut check);
+    apply_recent_install(&mut check);
+    caveat_date_indicators(&mut check);
+    if let Some(claimed_region) = claimed_region.filter(|region| !region.trim().is_empty()) {
+        if let Ok(info) = region::region_info() {
+            apply_claimed_origin(&info, claimed_region, &mut check);
+        }
     }
-    #[cfg(target_os = "windows")]
-    {
-        check_refurbishment_windows()
+    apply_part_history(app, &mut check);
+    apply_custom_checks(app, &mut check);
+    score_refurbishment(&mut check);
+    for indicator in &mut check.indicators {
+        indicator.help = indicator_help::lookup(&indicator.name);
     }
+    apply_recommendations(app, &mut check);
+    check
+}
+
+// The platform's checks and those that look only at the hardware. The
+// headless build stops here: it runs from its own system, so the installed
+// one's pairings, install date and settings aren't what it sees.
+fn hardware_refurbishment_check() -> RefurbishmentCheck {
+    #[cfg(target_os = "macos")]
+    let mut check = check_refurbishment_macos();
+    #[cfg(target_os = "windows")]
+    let mut check = check_refurbishment_windows();
     #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let mut check = RefurbishmentCheck {
+        is_refurbished: false,
+        confidence: "low".to_string(),
+        confidence_score: 0,
+        indicators: vec![],
+        replaced_parts: vec![],
+        details: RefurbishmentDetails {
+            serial_manufacture_date: None,
+            os_install_date: None,
+            battery_manufacture_date: None,
+            storage_first_use_date: None,
+            date_mismatch: false,
+            refurb_program: None,
+        },
+        recommendations: vec![],
+        grade_modifiers: vec![],
+    };
+
+    apply_board_serials(&mut check);
+    apply_genuine_parts(&mut check);
+    apply_battery_age(&mut check);
+    apply_clock_check(&mut check);
+    score_refurbishment(&mut check);
+    check
+}
+
+// Devices the previous owner paired and nobody removed: the machine wasn't
+// wiped. Not a sign of refurbishment, so it stays at info.
+fn apply_bluetooth_pairings(check: &mut RefurbishmentCheck) {
+    let Ok(bluetooth) = bluetooth::bluetooth_info() else {
+        return;
+    };
+    // Whatever is connected right now is usually the shop's own test mouse or keyboard
+    let remnants: Vec<&str> =
+        bluetooth.paired_devices.iter().filter(|device| !device.connected).map(|device| device.name.as_str()).collect();
+    if remnants.is_empty() {
+        return;
+    }
+    check.indicators.push(RefurbishmentIndicator {
+        name: "bluetooth_pairings".to_string(),
+        detected: true,
+        description: format!("bluetooth_pairings:{}", remnants.join(", ")),
+        severity: "info".to_string(),
+        help: None,
+        caveat: None,
+    });
+}
+
+// Accounts, home folders, networks or browser data the previous owner left:
+// the machine wasn't wiped before the sale. Accounts and users are warnings.
+fn apply_data_remnants(check: &mut RefurbishmentCheck) {
+    let Ok(remnants) = data_remnants::data_remnants() else {
+        return;
+    };
+    if remnants.wiped {
+        return;
+    }
+    check.indicators.push(RefurbishmentIndicator {
+        name: "previous_owner_data".to_string(),
+        detected: true,
+        description: format!("previous_owner_data:{}", data_remnants::evidence(&remnants).join(", ")),
+        severity: data_remnants::severity(&remnants).to_string(),
+        help: None,
+        caveat: None,
+    });
+}
+
+// An account the machine is still signed in to: the seller's data keeps
+// syncing to and from a personal one, and Find My may come with it; a work
+// or school registration hands the machine back to the company after a
+// reset. Both are critical.
+fn apply_signed_in_accounts(check: &mut RefurbishmentCheck) {
+    let Ok(signed_in) = accounts::signed_in_accounts() else {
+        return;
+    };
+    for account in signed_in.accounts {
+        let name = if account.service == "work_school" { "work_account" } else { "personal_account" };
+        check.indicators.push(RefurbishmentIndicator {
+            name: name.to_string(),
+            detected: true,
+            description: format!("{}:{}", name, account.account),
+            severity: "critical".to_string(),
+            help: None,
+            caveat: None,
+        });
+    }
+}
+
+// A system installed from scratch shortly before the sale, which wipes the
+// previous owner's traces along with it. Sellers do that routinely, so info.
+fn apply_recent_install(check: &mut RefurbishmentCheck) {
+    let Some((date, days)) = os_install::os_install_history().ok().as_ref().and_then(os_install::latest_install) else {
+        return;
+    };
+    if days > os_install::RECENT_INSTALL_DAYS {
+        return;
+    }
+    check.indicators.push(RefurbishmentIndicator {
+        name: "recent_os_install".to_string(),
+        detected: true,
+        description: format!("recent_os_install:{}", date),
+        severity: "info".to_string(),
+        help: None,
+        caveat: None,
+    });
+}
+
+// A logic board whose serial doesn't go with the machine's: never programmed
+// after a replacement, or taken from another machine. Disagreeing serials are
+// critical.
+fn apply_board_serials(check: &mut RefurbishmentCheck) {
+    let Some(mismatch) = board::board_info().ok().and_then(|board| board::serial_mismatch(&board)) else {
+        return;
+    };
+    check.indicators.push(RefurbishmentIndicator {
+        name: "logic_board_mismatch".to_string(),
+        detected: true,
+        description: mismatch.description,
+        severity: mismatch.severity.to_string(),
+        help: None,
+        caveat: None,
+    });
+    if !check.replaced_parts.iter().any(|part| part == "logic_board") {
+        check.replaced_parts.push("logic_board".to_string());
+    }
+}
+
+// Storage, panel, battery or keyboard from another maker than the one the
+// machine shipped with: third_party_<part> with the maker or model it reports
+fn apply_genuine_parts(check: &mut RefurbishmentCheck) {
+    let third_party = genuine_parts::genuine_parts().into_iter().filter(|part| part.verdict == genuine_parts::Verdict::ThirdParty);
+    for part in third_party {
+        check.indicators.push(RefurbishmentIndicator {
+            name: format!("third_party_{}", part.part),
+            detected: true,
+            description: format!("third_party_{}:{}", part.part, part.evidence),
+            severity: "warning".to_string(),
+            help: None,
+            caveat: None,
+        });
+        if !check.replaced_parts.iter().any(|replaced| replaced == part.part) {
+            check.replaced_parts.push(part.part.to_string());
+        }
+    }
+}
+
+// apply_battery_age flags the battery as replaced, at info severity, when it was
+// made more than REPLACEMENT_MONTHS after the chassis.
+fn apply_battery_age(check: &mut RefurbishmentCheck) {
+    let Ok(Some(battery)) = battery_info() else {
+        return;
+    };
+    check.details.battery_manufacture_date = battery.manufacture_date.clone();
+    let (Some(battery_date), Some(chassis_date)) =
+        (battery.manufacture_date.as_deref(), check.details.serial_manufacture_date.as_deref())
+    else {
+        return;
+    };
+    let months = battery_pack::months_newer(battery_date, chassis_date);
+    if months.is_none_or(|months| months <= battery_pack::REPLACEMENT_MONTHS) {
+        return;
+    }
+    check.indicators.push(RefurbishmentIndicator {
+        name: "battery_newer_than_chassis".to_string(),
+        detected: true,
+        description: format!("battery_newer_than_chassis:{} / {}", battery_date, chassis_date),
+        severity: "info".to_string(),
+        help: None,
+        caveat: None,
+    });
+    check.details.date_mismatch = true;
+    if !check.replaced_parts.iter().any(|part| part == "battery") {
+        check.replaced_parts.push("battery".to_string());
+    }
+}
+
+// A system clock that can't be right: findings that compare dates go by it,
+// so they are marked and weigh half. The clock itself is info.
+fn apply_clock_check(check: &mut RefurbishmentCheck) {
+    let clock = clock::clock_check();
+    let Some(problem) = clock.problem else {
+        return;
+    };
+    check.indicators.push(RefurbishmentIndicator {
+        name: "clock_unreliable".to_string(),
+        detected: true,
+        description: format!("clock_unreliable_{}:{}", problem, clock.system_time),
+        severity: "info".to_string(),
+        help: None,
+        caveat: None,
+    });
+    caveat_date_indicators(check);
+}
+
+// Also called once the indicators of the installed system are in
+fn caveat_date_indicators(check: &mut RefurbishmentCheck) {
+    if !check.indicators.iter().any(|indicator| indicator.name == "clock_unreliable" && indicator.detected) {
+        return;
+    }
+    for indicator in &mut check.indicators {
+        if clock::DATE_INDICATORS.contains(&indicator.name.as_str()) {
+            indicator.caveat = Some("clock_unreliable".to_string());
+        }
+    }
+}
+
+// Language, region settings or the keyboard pointing at another market than
+// the one the seller claims: a grey import, or a keyboard from one
+fn apply_claimed_origin(info: &region::RegionInfo, claimed_region: &str, check: &mut RefurbishmentCheck) {
+    let Some(conflict) = region::origin_conflict(info, claimed_region) else {
+        return;
+    };
+    check.indicators.push(RefurbishmentIndicator {
+        name: "origin_mismatch".to_string(),
+        detected: true,
+        description: conflict.description,
+        severity: conflict.severity.to_string(),
+        help: None,
+        caveat: None,
+    });
+}
+
+// Flag parts whose serial changed since this machine was last scanned
+fn apply_part_history(app: &tauri::AppHandle, check: &mut RefurbishmentCheck) {
+    let device_serial = get_serial_number();
+    if device_serial == "Unknown" {
+        return;
+    }
+    let (Ok(data_dir), Ok(serials)) = (app.path().app_data_dir(), component_serials::component_serials()) else {
+        return;
+    };
+    // Without raw identifiers the history holds only the fingerprint and hashes
+    let history = if preferences::store_raw_identifiers(app) {
+        Ok((device_serial, serials))
+    } else {
+        fingerprint::load_salt(&data_dir).and_then(|salt| {
+            let key = fingerprint::device_fingerprint(&data_dir)?;
+            Ok((key, fingerprint::hash_serials(&salt, serials)))
+        })
+    };
+    let changes = match history
+        .and_then(|(key, serials)| part_history::changes_since_last_scan(&data_dir, &key, serials))
     {
-        RefurbishmentCheck {
-            is_refurbished: false,
-            confidence: "low".to_string(),
-            indicators: vec![],
-            replaced_parts: vec![],
-            details: RefurbishmentDetails {
-                serial_manufacture_date: None,
-                os_install_date: None,
-                battery_manufacture_date: None,
-                storage_first_use_date: None,
-                date_mismatch: false,
-                refurb_program: None,
-            },
+        Ok(changes) => changes,
+        Err(e) => {
+            check.indicators.push(RefurbishmentIndicator {
+                name: "part_history".to_string(),
+                detected: false,
+                description: format!("part_history_invalid:{}", e),
+                severity: "info".to_string(),
+                help: None,
+                caveat: None,
+            });
+            return;
+        }
+    };
+    if changes.is_empty() {
+        return;
+    }
+
+    for change in changes {
+        check.indicators.push(RefurbishmentIndicator {
+            name: "part_swapped".to_string(),
+            detected: true,
+            description: format!("part_swapped_{}:{} / {}", change.part, change.previous, change.current),
+            severity: "critical".to_string(),
+            help: None,
+            caveat: None,
+        });
+        if !check.replaced_parts.iter().any(|part| part == change.part) {
+            check.replaced_parts.push(change.part.to_string());
         }
     }
 }
 
+// Attach remediation steps for critical findings, following the shop's policy file
+fn apply_recommendations(app: &tauri::AppHandle, check: &mut RefurbishmentCheck) {
+    let Ok(config_dir) = app.path().app_config_dir() else {
+        return;
+    };
+    let policy = match recommendations::load_policy(&config_dir.join(recommendations::POLICY_FILE)) {
+        Ok(policy) => policy,
+        Err(e) => {
+            check.indicators.push(RefurbishmentIndicator {
+                name: "recommendation_policy".to_string(),
+                detected: false,
+                description: format!("recommendation_policy_invalid:{}", e),
+                severity: "info".to_string(),
+                help: None,
+                caveat: None,
+            });
+            recommendations::Policy::default()
+        }
+    };
+    check.recommendations = policy.recommend(&check.indicators);
+}
+
+// Evaluate user rules and scripts from the config directory against the collected report
+fn apply_custom_checks(app: &tauri::AppHandle, check: &mut RefurbishmentCheck) {
+    let Ok(config_dir) = app.path().app_config_dir() else {
+        return;
+    };
+    let rule_set = match rules::load_rules(&config_dir.join(rules::RULES_FILE)) {
+        Ok(rule_set) => rule_set,
+        Err(e) => {
+            check.indicators.push(RefurbishmentIndicator {
+                name: "custom_rules".to_string(),
+                detected: false,
+                description: format!("custom_rules_invalid:{}", e),
+                severity: "info".to_string(),
+                help: None,
+                caveat: None,
+            });
+            rules::RuleSet::default()
+        }
+    };
+    let scripts = scripting::load_scripts(&config_dir.join(scripting::SCRIPTS_DIR));
+    if rule_set.rules.is_empty() && scripts.is_empty() {
+        return;
+    }
+
+    // "storage" stays the boot disk so existing rules keep matching
+    let disks = storage_health().unwrap_or_default();
+    let report = serde_json::json!({
+        "hardware": hardware_info(),
+        "battery": battery_info().ok().flatten(),
+        "storage": disks.iter().find(|disk| disk.is_boot).or(disks.first()),
+        "disks": &disks,
+        "network": network_info().ok(),
+        "refurbishment": &check,
+    });
+
+    let mut findings = rule_set.evaluate(&report);
+    let outcome = scripting::run_scripts(&scripts, &report);
+    findings.extend(outcome.findings);
+    check.grade_modifiers.extend(outcome.grade_modifiers);
+    for failure in outcome.failures {
+        check.indicators.push(RefurbishmentIndicator {
+            name: "custom_script".to_string(),
+            detected: false,
+            description: failure,
+            severity: "info".to_string(),
+            help: None,
+            caveat: None,
+        });
+    }
+
+    for (indicator, replaced_part) in findings {
+        if let Some(part) = replaced_part {
+            if !check.replaced_parts.contains(&part) {
+                check.replaced_parts.push(part);
+            }
+        }
+        check.indicators.push(indicator);
+    }
+}
+
+// Sums the weights of the detected indicators into the confidence score and
+// its label. Whether the machine counts as refurbished follows from the score
+// alone, so every check only has to add its indicators.
+fn score_refurbishment(check: &mut RefurbishmentCheck) {
+    let weights = scan_config::current().confidence;
+    let total: u32 = check
+        .indicators
+        .iter()
+        .filter(|indicator| indicator.detected)
+        .map(|indicator| match indicator.caveat {
+            Some(_) => weights.weight(&indicator.name, &indicator.severity) / 2,
+            None => weights.weight(&indicator.name, &indicator.severity),
+        })
+        .sum();
+    let score = total.min(100);
+    let confidence = if score >= weights.high {
+        "high"
+    } else if score >= weights.medium {
+        "medium"
+    } else {
+        "low"
+    };
+    check.confidence_score = score;
+    check.confidence = confidence.to_string();
+    check.is_refurbished = score >= weights.refurbished;
+}
+
 #[cfg(target_os = "macos")]
 fn check_refurbishment_macos() -> RefurbishmentCheck {
     let mut indicators: Vec<RefurbishmentIndicator> = vec![];
     let mut replaced_parts: Vec<String> = vec![];
-    let mut is_refurbished = false;
-    
-    let mut serial_date: Option<String> = None;
     let mut os_install_date: Option<String> = None;
-    let mut battery_date: Option<String> = None;
     let mut refurb_program: Option<String> = None;
     
     // 1. Check serial number for refurbishment indicator
@@ -420,74 +1165,40 @@ fn check_refurbishment_macos() -> RefurbishmentCheck {
     if serial.len() >= 4 {
         // Apple refurbished devices often have serial starting with 'F' (certified refurbished)
         if serial.starts_with('F') {
-            is_refurbished = true;
             refurb_program = Some("Apple Certified Refurbished".to_string());
             indicators.push(RefurbishmentIndicator {
                 name: "serial_refurb".to_string(),
                 detected: true,
                 description: "serial_starts_with_f".to_string(),
                 severity: "info".to_string(),
+                help: None,
+                caveat: None,
             });
         }
-        
-        serial_date = Some(serial[..4].to_string());
     }
+    // None for 2021+ randomized serials, which carry no date
+    let serial_date = serial_decode::decode(&serial).manufacture_date();
     
-    // 2. Check for refurbishment flag in NVRAM/IORegistry
-    if let Ok(output) = Command::new("ioreg")
-        .args(["-l"])
-        .output()
-    {
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        
-        // Check for refurbishment indicators
-        if stdout.contains("refurbished") || stdout.contains("Refurbished") {
-            is_refurbished = true;
-            indicators.push(RefurbishmentIndicator {
-                name: "ioreg_refurb".to_string(),
-                detected: true,
-                description: "firmware_refurb_flag".to_string(),
-                severity: "info".to_string(),
-            });
-        }
-        
-        // Check battery manufacture date from serial number
-        // Battery serial format: F8Y201400XQQ1LTAH - extract manufacture info
-        for line in stdout.lines() {
-            if line.contains("\"Serial\"") && line.contains("AppleSmartBattery") {
-                if let Some(serial_part) = line.split('"').nth(3) {
-                    // Extract year/week from battery serial if available
-                    battery_date = Some(serial_part.to_string());
-                }
-            }
-        }
-        
-        // Also try to get battery serial from system_profiler
-        if battery_date.is_none() {
-            if let Ok(output) = Command::new("system_profiler")
-                .args(["SPPowerDataType", "-json"])
-                .output()
-            {
-                let power_stdout = String::from_utf8_lossy(&output.stdout);
-                if let Ok(json) = serde_json::from_str::<serde_json::Value>(&power_stdout) {
-                    if let Some(power_data) = json.get("SPPowerDataType").and_then(|v| v.as_array()) {
-                        for item in power_data {
-                            if let Some(model_info) = item.get("sppower_battery_model_info") {
-                                if let Some(serial) = model_info.get("sppower_battery_serial_number") {
-                                    battery_date = serial.as_str().map(|s| s.to_string());
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
+    // 2. Check for refurbishment flag in the platform device and NVRAM
+    let registry_text = ["IOPlatformExpertDevice", "IODTNVRAM"]
+        .iter()
+        .filter_map(|class| iokit::service_properties(class))
+        .map(|properties| serde_json::Value::Object(properties).to_string().to_lowercase())
+        .collect::<String>();
+    if registry_text.contains("refurbished") {
+        indicators.push(RefurbishmentIndicator {
+            name: "ioreg_refurb".to_string(),
+            detected: true,
+            description: "firmware_refurb_flag".to_string(),
+            severity: "info".to_string(),
+            help: None,
+            caveat: None,
+        });
     }
     
     // 3. Get OS install date
-    if let Ok(output) = Command::new("stat")
-        .args(["-f", "%SB", "/var/db/.AppleSetupDone"])
-        .output()
+    if let Ok(output) = process::run_cmd(Command::new("stat")
+        .args(["-f", "%SB", "/var/db/.AppleSetupDone"]))
     {
         let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
         if !stdout.is_empty() {
@@ -496,24 +1207,11 @@ fn check_refurbishment_macos() -> RefurbishmentCheck {
     }
     
     // 4. Check for enterprise management (MDM enrollment)
-    if let Ok(output) = Command::new("profiles")
-        .args(["status", "-type", "enrollment"])
-        .output()
-    {
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        
-        // Check for DEP (Device Enrollment Program) enrollment
-        let dep_enrolled = stdout.lines()
-            .any(|line| line.contains("Enrolled via DEP:") && line.contains("Yes"));
-        
-        // Check for MDM enrollment
-        let mdm_enrolled = stdout.lines()
-            .any(|line| line.contains("MDM enrollment:") && line.contains("Yes"));
-        
-        if dep_enrolled || mdm_enrolled {
-            let description = if dep_enrolled && mdm_enrolled {
+    if let Ok(mdm) = mdm::mdm_status() {
+        if mdm.dep_assigned || mdm.enrolled {
+            let description = if mdm.dep_assigned && mdm.enrolled {
                 "enterprise_dep_and_mdm".to_string()
-            } else if dep_enrolled {
+            } else if mdm.dep_assigned {
                 "enterprise_dep_enrolled".to_string()
             } else {
                 "enterprise_mdm_enrolled".to_string()
@@ -524,75 +1222,40 @@ fn check_refurbishment_macos() -> RefurbishmentCheck {
                 detected: true,
                 description,
                 severity: "warning".to_string(),
+                help: None,
+                caveat: None,
             });
         }
     }
     
-    // 5. Check for battery replacement - only flag if cycle count is suspiciously low for device age
-    // Skip this check as it causes false positives
+    // 5. Battery replacement is checked by apply_battery_age on every platform
     
-    // 6. Check storage health for replacement indicators
-    if let Ok(output) = Command::new("diskutil")
-        .args(["info", "disk0"])
-        .output()
-    {
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let mut is_internal = false;
-        let mut device_model = String::new();
-        
-        for line in stdout.lines() {
-            if line.contains("Device Location:") && line.contains("Internal") {
-                is_internal = true;
-            }
-            if line.contains("Device / Media Name:") {
-                device_model = line.split(':').nth(1).unwrap_or("").trim().to_string();
-            }
-        }
-        
-        // Check if internal SSD seems to be third-party
-        if is_internal && !device_model.is_empty() {
-            let apple_ssds = ["APPLE SSD", "Apple SSD", "AP", "Macintosh"];
-            let is_apple_ssd = apple_ssds.iter().any(|s| device_model.contains(s));
-            
-            if !is_apple_ssd {
-                indicators.push(RefurbishmentIndicator {
-                    name: "third_party_storage".to_string(),
-                    detected: true,
-                    description: format!("third_party_storage:{}", device_model),
-                    severity: "warning".to_string(),
-                });
-                replaced_parts.push("storage".to_string());
-            }
-        }
-    }
+    // 6. Storage, display, battery and keyboard are checked by apply_genuine_parts on every platform
     
-    // 7. Check display for replacement
-    if let Ok(output) = Command::new("system_profiler")
-        .args(["SPDisplaysDataType", "-json"])
-        .output()
+    // 7. Check GPU memory for replacement
+    if let Ok(output) = process::run_cmd(Command::new("system_profiler")
+        .args(["SPDisplaysDataType", "-json"]))
     {
         let stdout = String::from_utf8_lossy(&output.stdout);
         if let Ok(json) = serde_json::from_str::<serde_json::Value>(&stdout) {
             if let Some(displays) = json.get("SPDisplaysDataType") {
                 if let Some(arr) = displays.as_array() {
                     for display in arr {
-                        if let Some(vendor) = display.get("spdisplays_vendor") {
-                            let vendor_str = vendor.as_str().unwrap_or("");
-                            // Check for non-Apple display on internal
-                            if !vendor_str.contains("Apple") && !vendor_str.contains("APP") {
-                                if display.get("spdisplays_connection_type")
-                                    .and_then(|v| v.as_str())
-                                    .map(|s| s.contains("Internal"))
-                                    .unwrap_or(false)
-                                {
-                                    indicators.push(RefurbishmentIndicator {
-                                        name: "third_party_display".to_string(),
-                                        detected: true,
-                                        description: format!("third_party_display:{}", vendor_str),
-                                        severity: "warning".to_string(),
-                                    });
-                                    replaced_parts.push("display".to_string());
-                                }
+                        // Check discrete GPU memory against the smallest shipped configuration
+                        let gpu_model = display.get("sppci_model").and_then(|v| v.as_str()).unwrap_or("");
+                        let vram = display.get("spdisplays_vram")
+                            .and_then(|v| v.as_str())
+                            .and_then(gpu::parse_vram_mb);
+                        if let (Some(vram), Some(expected)) = (vram, gpu::expected_min_vram_mb(gpu_model)) {
+                            if vram < expected {
+                                indicators.push(RefurbishmentIndicator {
+                                    name: "gpu_vram_below_spec".to_string(),
+                                    detected: true,
+                                    description: format!("gpu_vram_below_spec:{} {}/{} MB", gpu_model, vram, expected),
+                                    severity: "warning".to_string(),
+                                    help: None,
+                                    caveat: None,
+                                });
                             }
                         }
                     }
@@ -600,99 +1263,291 @@ fn check_refurbishment_macos() -> RefurbishmentCheck {
             }
         }
     }
+
+    // 8. Check Wi-Fi module for replacement via MAC OUI and card vendor
+    let mut wifi_vendor: Option<&str> = None;
+    if let Some(mac) = get_wifi_mac_macos() {
+        if wifi_oui::is_locally_administered(&mac) {
+            indicators.push(RefurbishmentIndicator {
+                name: "wifi_mac_locally_administered".to_string(),
+                detected: true,
+                description: "wifi_mac_locally_administered".to_string(),
+                severity: "info".to_string(),
+                help: None,
+                caveat: None,
+            });
+        } else if let Some(vendor) = wifi_oui::oui_vendor(&mac) {
+            if !wifi_oui::MAC_VENDORS.contains(&vendor) {
+                wifi_vendor = Some(vendor);
+            }
+        }
+    }
+    if wifi_vendor.is_none() {
+        if let Ok(groups) = apple_overview::section("SPAirPortDataType") {
+            let interfaces = groups
+                .iter()
+                .filter_map(|item| item.get("spairport_airport_interfaces").and_then(|v| v.as_array()))
+                .flatten();
+            for interface in interfaces {
+                let vendor = interface.get("spairport_wireless_card_type")
+                    .and_then(|v| v.as_str())
+                    .and_then(wifi_oui::parse_card_vendor_id)
+                    .and_then(wifi_oui::pci_vendor);
+                if let Some(vendor) = vendor.filter(|v| !wifi_oui::MAC_VENDORS.contains(v)) {
+                    wifi_vendor = Some(vendor);
+                }
+            }
+        }
+    }
+    if let Some(vendor) = wifi_vendor {
+        indicators.push(RefurbishmentIndicator {
+            name: "third_party_wifi".to_string(),
+            detected: true,
+            description: format!("third_party_wifi:{}", vendor),
+            severity: "warning".to_string(),
+            help: None,
+            caveat: None,
+        });
+        replaced_parts.push("wifi".to_string());
+    }
     
-    // Calculate confidence based on indicators
-    let critical_count = indicators.iter().filter(|i| i.severity == "critical").count();
-    let warning_count = indicators.iter().filter(|i| i.severity == "warning").count();
+    // 9. Check for components this model shipped with that are no longer detected
+    if let Some(expected) = component_manifest::model_identifier()
+        .and_then(|model| component_manifest::expected_components(&model))
+    {
+        for component in component_manifest::missing_components(expected) {
+            indicators.push(RefurbishmentIndicator {
+                name: "component_missing".to_string(),
+                detected: true,
+                description: format!("component_missing:{}", component),
+                severity: "warning".to_string(),
+                help: None,
+                caveat: None,
+            });
+        }
+    }
     
-    let confidence = if critical_count > 0 || (warning_count >= 2) {
-        "high"
-    } else if warning_count > 0 || indicators.len() >= 2 {
-        "medium"
-    } else {
-        "low"
-    };
+    // 10. Check whether boot security has been lowered (SIP disabled, reduced Secure Boot)
+    if let Ok(security) = security::security_info() {
+        if security.sip_enabled == Some(false) {
+            indicators.push(RefurbishmentIndicator {
+                name: "sip_disabled".to_string(),
+                detected: true,
+                description: "sip_disabled".to_string(),
+                severity: "critical".to_string(),
+                help: None,
+                caveat: None,
+            });
+        }
+        if let Some(policy) = security.secure_boot_policy.filter(|policy| policy != "full") {
+            indicators.push(RefurbishmentIndicator {
+                name: "secure_boot_lowered".to_string(),
+                detected: true,
+                description: format!("secure_boot_lowered:{}", policy),
+                severity: "warning".to_string(),
+                help: None,
+                caveat: None,
+            });
+        }
+    }
     
-    // Determine date mismatch
-    let date_mismatch = battery_date.is_some() && os_install_date.is_some();
+    // 11. Check whether the Mac is still tied to an Apple ID
+    if let Ok(status) = activation_lock::activation_lock_status() {
+        if status.may_be_locked {
+            let description = if status.activation_lock_enabled == Some(true) {
+                "activation_lock_enabled"
+            } else {
+                "find_my_enabled"
+            };
+            indicators.push(RefurbishmentIndicator {
+                name: "activation_lock".to_string(),
+                detected: true,
+                description: description.to_string(),
+                severity: "critical".to_string(),
+                help: None,
+                caveat: None,
+            });
+        }
+    }
     
+    // Scored once the cross-platform checks have added theirs
     RefurbishmentCheck {
-        is_refurbished: is_refurbished || !replaced_parts.is_empty() || warning_count > 0,
-        confidence: confidence.to_string(),
+        is_refurbished: false,
+        confidence: "low".to_string(),
+        confidence_score: 0,
         indicators,
         replaced_parts,
         details: RefurbishmentDetails {
             serial_manufacture_date: serial_date,
             os_install_date,
-            battery_manufacture_date: battery_date,
+            battery_manufacture_date: None,
             storage_first_use_date: None,
-            date_mismatch,
+            date_mismatch: false,
             refurb_program,
         },
+        recommendations: vec![],
+        grade_modifiers: vec![],
     }
 }
 
 #[cfg(target_os = "windows")]
 fn check_refurbishment_windows() -> RefurbishmentCheck {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct OperatingSystem {
+        install_date: Option<String>,
+    }
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct NetAdapter {
+        permanent_address: Option<String>,
+        interface_description: Option<String>,
+    }
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct VideoController {
+        name: Option<String>,
+        adapter_ram: Option<u32>,
+    }
+    
+    let wmi = wmi_provider::WmiProvider::global();
     let mut indicators: Vec<RefurbishmentIndicator> = vec![];
-    let replaced_parts: Vec<String> = vec![];
-    let mut os_install_date: Option<String> = None;
+    let mut replaced_parts: Vec<String> = vec![];
     
     // 1. Check Windows install date
-    if let Ok(output) = Command::new("powershell")
-        .args(["-Command", "(Get-CimInstance Win32_OperatingSystem).InstallDate"])
-        .output()
-    {
-        let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
-        if !stdout.is_empty() {
-            os_install_date = Some(stdout);
-        }
-    }
+    let os_install_date = wmi
+        .query_first::<OperatingSystem>(wmi_provider::CIMV2, "SELECT InstallDate FROM Win32_OperatingSystem")
+        .and_then(|os| os.install_date)
+        .and_then(|date| wmi_provider::format_datetime(&date));
     
     // 2. Check BIOS for refurbishment info
-    if let Ok(output) = Command::new("powershell")
-        .args(["-Command", "Get-WmiObject Win32_BIOS | Select-Object Manufacturer,SerialNumber,ReleaseDate | ConvertTo-Json"])
-        .output()
-    {
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        if stdout.to_lowercase().contains("refurbished") || stdout.to_lowercase().contains("renewed") {
+    if let Some(bios) = wmi.query_first::<Win32Bios>(wmi_provider::CIMV2, "SELECT Manufacturer, SerialNumber, ReleaseDate FROM Win32_BIOS") {
+        let text = [bios.manufacturer, bios.serial_number, bios.release_date]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase();
+        if text.contains("refurbished") || text.contains("renewed") {
             indicators.push(RefurbishmentIndicator {
                 name: "bios_refurb".to_string(),
                 detected: true,
                 description: "bios_refurb_flag".to_string(),
                 severity: "info".to_string(),
+                help: None,
+                caveat: None,
             });
         }
     }
     
     // 3. Check for OEM info changes
-    if let Ok(output) = Command::new("powershell")
-        .args(["-Command", "Get-ItemProperty 'HKLM:\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\OEMInformation' 2>$null | ConvertTo-Json"])
-        .output()
-    {
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        if stdout.to_lowercase().contains("refurb") || stdout.to_lowercase().contains("renewed") {
+    let oem_key = "SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\OEMInformation";
+    let oem_info = ["Manufacturer", "Model", "SupportProvider", "SupportURL"]
+        .iter()
+        .filter_map(|value| wmi_provider::registry_string(oem_key, value))
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+    if oem_info.contains("refurb") || oem_info.contains("renewed") {
+        indicators.push(RefurbishmentIndicator {
+            name: "oem_refurb".to_string(),
+            detected: true,
+            description: "oem_refurb_flag".to_string(),
+            severity: "info".to_string(),
+            help: None,
+            caveat: None,
+        });
+    }
+    
+    // 4. Check the Wi-Fi adapter's MAC OUI against the reported module vendor
+    // (NdisPhysicalMedium 9 is native 802.11)
+    if let Some(adapter) = wmi.query_first::<NetAdapter>(
+        wmi_provider::STANDARD_CIMV2,
+        "SELECT PermanentAddress, InterfaceDescription FROM MSFT_NetAdapter WHERE NdisPhysicalMedium = 9 AND ConnectorPresent = TRUE",
+    ) {
+        let mac = adapter.permanent_address.unwrap_or_default();
+        let description = adapter.interface_description.unwrap_or_default();
+        if let Some(vendor) = wifi_oui::oui_vendor(&mac) {
+            if !wifi_oui::description_matches_vendor(vendor, &description) {
+                indicators.push(RefurbishmentIndicator {
+                    name: "wifi_vendor_mismatch".to_string(),
+                    detected: true,
+                    description: format!("wifi_vendor_mismatch:{} / {}", vendor, description),
+                    severity: "warning".to_string(),
+                    help: None,
+                    caveat: None,
+                });
+                replaced_parts.push("wifi".to_string());
+            }
+        }
+    }
+    
+    // 5. Check GPUs for fallback drivers and missing video memory
+    let adapters = wmi
+        .query::<VideoController>(wmi_provider::CIMV2, "SELECT Name, AdapterRAM FROM Win32_VideoController")
+        .unwrap_or_default();
+    for adapter in &adapters {
+        let name = adapter.name.as_deref().unwrap_or("");
+        if gpu::is_fallback_adapter(name) {
             indicators.push(RefurbishmentIndicator {
-                name: "oem_refurb".to_string(),
+                name: "gpu_generic_driver".to_string(),
                 detected: true,
-                description: "oem_refurb_flag".to_string(),
-                severity: "info".to_string(),
+                description: format!("gpu_generic_driver:{}", name),
+                severity: "warning".to_string(),
+                help: None,
+                caveat: None,
             });
+            continue;
+        }
+        
+        // AdapterRAM is a 32-bit field, so anything from 4GB up reads as ~4GB
+        let vram_mb = adapter.adapter_ram.map(|b| b as u64 / (1024 * 1024));
+        if let (Some(vram), Some(expected)) = (vram_mb, gpu::expected_min_vram_mb(name)) {
+            if vram < expected.min(4095) {
+                indicators.push(RefurbishmentIndicator {
+                    name: "gpu_vram_below_spec".to_string(),
+                    detected: true,
+                    description: format!("gpu_vram_below_spec:{} {}/{} MB", name, vram, expected),
+                    severity: "warning".to_string(),
+                    help: None,
+                    caveat: None,
+                });
+            }
         }
     }
     
-    let warning_count = indicators.iter().filter(|i| i.severity == "warning").count();
-    let confidence = if warning_count >= 2 {
-        "high"
-    } else if warning_count > 0 || indicators.len() >= 2 {
-        "medium"
-    } else {
-        "low"
-    };
+    // 6. Check whether Secure Boot has been turned off
+    if security::security_info().is_ok_and(|security| security.secure_boot_enabled == Some(false)) {
+        indicators.push(RefurbishmentIndicator {
+            name: "secure_boot_lowered".to_string(),
+            detected: true,
+            description: "secure_boot_disabled".to_string(),
+            severity: "warning".to_string(),
+            help: None,
+            caveat: None,
+        });
+    }
     
+    // 7. Check for a registered refurbisher's Windows license
+    let mut refurb_program: Option<String> = None;
+    let activation = windows_activation::windows_activation().ok();
+    if activation.as_ref().and_then(|a| a.channel.as_deref()) == Some("Refurbisher") {
+        refurb_program = Some("Microsoft Registered Refurbisher".to_string());
+        indicators.push(RefurbishmentIndicator {
+            name: "refurbisher_license".to_string(),
+            detected: true,
+            description: "refurbisher_license".to_string(),
+            severity: "info".to_string(),
+            help: None,
+            caveat: None,
+        });
+    }
+    
+    // Scored once the cross-platform checks have added theirs
     RefurbishmentCheck {
-        is_refurbished: !indicators.is_empty() || !replaced_parts.is_empty(),
-        confidence: confidence.to_string(),
+        is_refurbished: false,
+        confidence: "low".to_string(),
+        confidence_score: 0,
         indicators,
         replaced_parts,
         details: RefurbishmentDetails {
@@ -701,8 +1556,10 @@ fn check_refurbishment_windows() -> RefurbishmentCheck {
             battery_manufacture_date: None,
             storage_first_use_date: None,
             date_mismatch: false,
-            refurb_program: None,
+            refurb_program,
         },
+        recommendations: vec![],
+        grade_modifiers: vec![],
     }
 }
 
@@ -713,13 +1570,90 @@ pub fn run() {
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
+        .manage(ProbeScheduler::default())
+        .setup(|app| {
+            scan_config::init(app.handle());
+            digest::start_scheduler(app.handle().clone());
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             greet, 
             get_hardware_info, 
             get_battery_info, 
             get_storage_health,
+            disk_bench::run_disk_benchmark,
             get_network_info,
-            check_refurbishment
+            bluetooth::get_bluetooth_info,
+            network_adapters::get_network_adapters,
+            check_refurbishment,
+            activation_lock::check_activation_lock,
+            apple_overview::get_apple_system_overview,
+            encryption::check_disk_encryption,
+            energy::get_energy_report,
+            battery_monitor::start_battery_monitor,
+            battery_monitor::stop_battery_monitor,
+            audio::get_audio_devices,
+            audio::run_audio_test,
+            camera::get_camera_info,
+            camera::test_camera_capture,
+            input_devices::get_input_devices,
+            input_devices::start_keyboard_test,
+            input_devices::stop_keyboard_test,
+            touch_bar::get_touch_bar_info,
+            usb::get_usb_devices,
+            hardware_watch::watch_hardware_changes,
+            hardware_watch::stop_hardware_watch,
+            device_model::get_device_model,
+            component_serials::get_component_serials,
+            board::get_board_info,
+            genuine_parts::check_genuine_parts,
+            region::get_region_info,
+            mdm::check_mdm_status,
+            data_remnants::check_data_remnants,
+            accounts::get_signed_in_accounts,
+            security::get_security_info,
+            thermal::get_thermal_info,
+            stress::run_cpu_stress_test,
+            speed_test::run_network_speed_test,
+            speed_test::run_latency_test,
+            memory_test::run_memory_test,
+            memory_modules::get_memory_modules,
+            sleep::request_sleep,
+            sleep::check_sleep_history,
+            sleep::get_power_events,
+            crash_history::get_crash_history,
+            rtc_battery::get_rtc_battery,
+            clock::check_clock,
+            usage_estimate::get_usage_estimate,
+            windows_activation::check_windows_activation,
+            gpu::get_gpu_info,
+            display::get_display_info,
+            hooks::run_scan_hook,
+            digest::record_scan,
+            telemetry::export_scan_telemetry,
+            watchdog::get_watchdog_events,
+            watchdog::clear_watchdog_events,
+            preferences::get_preferences,
+            scan_config::get_scan_config,
+            scan_config::set_scan_config,
+            consent::check_consent,
+            consent::record_consent,
+            seal::seal_report,
+            seal::get_report_seals,
+            seal::verify_sealed_report,
+            locales::render_narrative,
+            summary::summarize_report,
+            grade::compute_device_grade,
+            export::export_report,
+            pdf_report::export_report_pdf,
+            evidence::start_evidence,
+            evidence::save_evidence,
+            evidence::get_evidence,
+            colorimeter::read_colorimeter,
+            colorimeter::compute_display_measurement,
+            panel_aging::get_panel_aging,
+            os_install::get_os_install_history,
+            pixel_defects::analyze_pixel_defects
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,137 @@
+// Community translations of the report narrative, as Fluent files in the
+// `locales` directory of the app config directory, one per language:
+//
+//   locales/de.ftl
+//   locales/pt-BR.ftl
+//
+// The shop's `locale` preference picks the file ("de-AT" falls back to
+// de.ftl). Message IDs are the built-in translation keys with dashes for
+// dots, and the arguments keep their names:
+//
+//   narrative-device = Dieses Gerät ist ein { $model } mit { $os }.
+//   narrative-componentsMissing = { $count ->
+//       [one] Eine Komponente fehlt.
+//      *[other] { $count } Komponenten fehlen.
+//   }
+//
+// A message the file lacks keeps its built-in text, so a partial translation
+// still produces a whole narrative.
+
+use crate::error::QuickscanError;
+use fluent_bundle::{FluentArgs, FluentBundle, FluentResource, FluentValue};
+use fluent_langneg::{negotiate_languages, NegotiationStrategy};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use tauri::Manager;
+use unic_langid::LanguageIdentifier;
+
+pub const LOCALES_DIR: &str = "locales";
+
+// One sentence of the narrative, as the frontend built it
+#[derive(Deserialize)]
+pub struct NarrativeMessage {
+    pub id: String, // built-in key, e.g. "narrative.battery"
+    #[serde(default)]
+    pub args: BTreeMap<String, NarrativeArg>,
+    pub fallback: String, // the built-in translation, used when the file lacks `id`
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum NarrativeArg {
+    Number(f64),
+    Text(String),
+    Message(NarrativeMessage),
+    List(Vec<NarrativeMessage>), // joined with ", "
+}
+
+#[derive(Serialize)]
+pub struct LocalizedNarrative {
+    pub locale: String, // the file that was used
+    pub paragraphs: Vec<String>,
+}
+
+// None when no file matches `locale`; the built-in narrative stands
+#[tauri::command]
+pub fn render_narrative(
+    app: tauri::AppHandle,
+    locale: String,
+    paragraphs: Vec<Vec<NarrativeMessage>>,
+) -> Result<Option<LocalizedNarrative>, QuickscanError> {
+    let config_dir = app.path().app_config_dir().map_err(|e| QuickscanError::command_failed(LOCALES_DIR, e))?;
+    let dir = config_dir.join(LOCALES_DIR);
+
+    let available: Vec<LanguageIdentifier> =
+        available_locales(&dir).iter().filter_map(|tag| tag.parse().ok()).collect();
+    let requested: LanguageIdentifier =
+        locale.parse().map_err(|_| QuickscanError::parse("locale", format!("\"{}\" is not a language tag", locale)))?;
+    let Some(matched) = negotiate_languages(&[requested], &available, None, NegotiationStrategy::Lookup).first().copied()
+    else {
+        return Ok(None);
+    };
+    let tag = matched.to_string();
+
+    let file = format!("{}.ftl", tag);
+    let source = std::fs::read_to_string(dir.join(&file)).map_err(|e| QuickscanError::io(&file, e))?;
+    // A file with mistakes is reported rather than half-applied
+    let resource = FluentResource::try_new(source.clone()).map_err(|(_, errors)| {
+        let error = &errors[0];
+        let line = source[..error.pos.start.min(source.len())].matches('\n').count() + 1;
+        QuickscanError::parse(&file, format!("line {}: {}", line, error))
+    })?;
+    let mut bundle = FluentBundle::new(vec![matched.clone()]);
+    // Unicode isolation marks around arguments show up as boxes in the PDF
+    bundle.set_use_isolating(false);
+    bundle
+        .add_resource(resource)
+        .map_err(|errors| QuickscanError::parse(&file, errors.iter().map(|e| e.to_string()).collect::<Vec<_>>().join("; ")))?;
+
+    let paragraphs = paragraphs
+        .iter()
+        .map(|sentences| sentences.iter().map(|message| format(&bundle, message)).collect::<Vec<_>>().join(" "))
+        .collect();
+    Ok(Some(LocalizedNarrative { locale: tag, paragraphs }))
+}
+
+// File stems that parse as language tags
+fn available_locales(dir: &Path) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return vec![];
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "ftl"))
+        .filter_map(|path| path.file_stem().map(|stem| stem.to_string_lossy().to_string()))
+        .filter(|stem| stem.parse::<LanguageIdentifier>().is_ok())
+        .collect()
+}
+
+fn format(bundle: &FluentBundle<FluentResource>, message: &NarrativeMessage) -> String {
+    let id = message.id.replace('.', "-");
+    let Some(pattern) = bundle.get_message(&id).and_then(|m| m.value()) else {
+        return message.fallback.clone();
+    };
+    let mut args = FluentArgs::new();
+    for (name, arg) in &message.args {
+        let value = match arg {
+            NarrativeArg::Number(n) => FluentValue::from(*n),
+            NarrativeArg::Text(text) => FluentValue::from(text.clone()),
+            NarrativeArg::Message(nested) => FluentValue::from(format(bundle, nested)),
+            NarrativeArg::List(items) => {
+                FluentValue::from(items.iter().map(|item| format(bundle, item)).collect::<Vec<_>>().join(", "))
+            }
+        };
+        args.set(name.as_str(), value);
+    }
+    // A reference to a missing variable or message still formats, with the
+    // name in braces; the built-in text is the better outcome
+    let mut errors = vec![];
+    let text = bundle.format_pattern(pattern, Some(&args), &mut errors).to_string();
+    if errors.is_empty() {
+        text
+    } else {
+        message.fallback.clone()
+    }
+}
@@ -0,0 +1,112 @@
+// Device management enrollment (`check_mdm_status`). A machine still assigned
+// to an organization through Apple Business Manager or Windows Autopilot
+// re-enrolls itself after a wipe, so a buyer needs to know before paying.
+
+use crate::error::QuickscanError;
+use crate::probe_scheduler::ProbeScheduler;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct MdmStatus {
+    pub enrolled: bool,
+    pub dep_assigned: bool, // Automated Device Enrollment on macOS, Autopilot on Windows
+    pub user_approved: Option<bool>, // macOS only
+    pub organization: Option<String>,
+    pub server_url: Option<String>,
+}
+
+#[tauri::command]
+pub async fn check_mdm_status(probes: tauri::State<'_, ProbeScheduler>) -> Result<MdmStatus, QuickscanError> {
+    probes.run(mdm_status).await
+}
+
+pub fn mdm_status() -> Result<MdmStatus, QuickscanError> {
+    #[cfg(target_os = "macos")]
+    {
+        mdm_status_macos()
+    }
+    #[cfg(target_os = "windows")]
+    {
+        Ok(mdm_status_windows())
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        Err(QuickscanError::unsupported("mdm_status"))
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn mdm_status_macos() -> Result<MdmStatus, QuickscanError> {
+    use std::process::Command;
+
+    // Enrolled via DEP: Yes
+    // MDM enrollment: Yes (User Approved)
+    // MDM server: https://mdm.example.com/mdm/server
+    let output = crate::process::run_cmd_checked(Command::new("profiles").args(["status", "-type", "enrollment"]))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let field = |name: &str| {
+        stdout
+            .lines()
+            .find_map(|line| line.trim().strip_prefix(name))
+            .map(|value| value.trim().to_string())
+    };
+    let enrollment = field("MDM enrollment:").unwrap_or_default();
+    let mut status = MdmStatus {
+        enrolled: enrollment.starts_with("Yes"),
+        dep_assigned: field("Enrolled via DEP:").is_some_and(|v| v.starts_with("Yes")),
+        user_approved: enrollment.starts_with("Yes").then(|| enrollment.contains("User Approved")),
+        organization: None,
+        server_url: field("MDM server:").filter(|v| !v.is_empty()),
+    };
+
+    // The activation record names the organization and shows an ABM assignment
+    // even before the machine enrolls. Reading it needs root, so this is best effort.
+    if let Ok(output) = crate::process::run_cmd(Command::new("profiles").args(["show", "-type", "enrollment"])) {
+        let record = String::from_utf8_lossy(&output.stdout);
+        let value = |key: &str| {
+            record.lines().find_map(|line| {
+                let (name, value) = line.split_once('=')?;
+                (name.trim() == key).then(|| value.trim().trim_end_matches(';').trim_matches('"').to_string())
+            })
+        };
+        if let Some(organization) = value("OrganizationName").filter(|v| !v.is_empty()) {
+            status.organization = Some(organization);
+            status.dep_assigned = true;
+        }
+        status.server_url = status.server_url.or_else(|| value("ConfigurationURL").filter(|v| !v.is_empty()));
+    }
+    Ok(status)
+}
+
+#[cfg(target_os = "windows")]
+fn mdm_status_windows() -> MdmStatus {
+    use crate::wmi_provider::{registry_string, registry_subkeys};
+
+    const ENROLLMENTS: &str = "SOFTWARE\\Microsoft\\Enrollments";
+    const TENANT_INFO: &str = "SYSTEM\\CurrentControlSet\\Control\\CloudDomainJoin\\TenantInfo";
+    const AUTOPILOT: &str = "SOFTWARE\\Microsoft\\Provisioning\\Diagnostics\\Autopilot";
+
+    let mut status = MdmStatus::default();
+
+    // One GUID subkey per enrollment; Windows also keeps bookkeeping subkeys
+    // (Context, Status, ...) there that have no ProviderID
+    for id in registry_subkeys(ENROLLMENTS) {
+        let key = format!("{}\\{}", ENROLLMENTS, id);
+        if registry_string(&key, "ProviderID").is_some_and(|p| !p.is_empty()) {
+            status.enrolled = true;
+            status.server_url = status.server_url.or_else(|| registry_string(&key, "DiscoveryServiceFullURL"));
+        }
+    }
+
+    // Entra ID (Azure AD) join records the tenant's display name
+    status.organization = registry_subkeys(TENANT_INFO)
+        .into_iter()
+        .find_map(|tenant| registry_string(&format!("{}\\{}", TENANT_INFO, tenant), "DisplayName"))
+        .filter(|name| !name.is_empty());
+
+    if let Some(domain) = registry_string(AUTOPILOT, "CloudAssignedTenantDomain").filter(|d| !d.is_empty()) {
+        status.dep_assigned = true;
+        status.organization = status.organization.or(Some(domain));
+    }
+    status
+}
@@ -0,0 +1,347 @@
+// The installed memory, module by module (`get_memory_modules`): size, type,
+// speed, maker and part number per slot, how many slots are free and whether
+// the memory is soldered. Tells a buyer whether 16 GB is two matched 8 GB
+// sticks, a mismatched pair, or fixed for the life of the machine.
+
+use crate::error::QuickscanError;
+use crate::probe_scheduler::ProbeScheduler;
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct MemoryModules {
+    pub total_bytes: u64,
+    pub slots_total: Option<u32>, // None where the firmware doesn't say
+    pub slots_used: u32,
+    pub soldered: Option<bool>,
+    pub upgradeable: Option<bool>,
+    pub ecc: Option<bool>,
+    pub modules: Vec<MemoryModule>, // installed ones only
+}
+
+#[derive(Serialize)]
+pub struct MemoryModule {
+    pub slot: String, // "DIMM A", "BANK 0/ChannelA-DIMM0"; "Built-in" on Apple silicon
+    pub size_bytes: u64,
+    pub kind: Option<String>, // "DDR4", "DDR5", "LPDDR5"
+    pub speed_mts: Option<u32>,
+    pub manufacturer: Option<String>,
+    pub part_number: Option<String>,
+    pub soldered: Option<bool>,
+}
+
+#[tauri::command]
+pub async fn get_memory_modules(probes: tauri::State<'_, ProbeScheduler>) -> Result<MemoryModules, QuickscanError> {
+    probes.run(memory_modules).await
+}
+
+pub fn memory_modules() -> Result<MemoryModules, QuickscanError> {
+    #[cfg(target_os = "macos")]
+    let modules = memory_modules_macos();
+    #[cfg(target_os = "windows")]
+    let modules = memory_modules_windows();
+    #[cfg(target_os = "linux")]
+    let modules = memory_modules_linux();
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    let modules = Err(QuickscanError::unsupported("memory_modules"));
+    modules
+}
+
+// Totals and the soldered flag from the per-module findings
+fn summarize(modules: Vec<MemoryModule>, slots_total: Option<u32>, ecc: Option<bool>) -> MemoryModules {
+    let soldered = if modules.iter().any(|m| m.soldered == Some(true)) {
+        Some(true)
+    } else if !modules.is_empty() && modules.iter().all(|m| m.soldered == Some(false)) {
+        Some(false)
+    } else {
+        None
+    };
+    let slots_used = modules.len() as u32;
+    MemoryModules {
+        total_bytes: modules.iter().map(|m| m.size_bytes).sum(),
+        slots_total,
+        slots_used,
+        soldered,
+        // A free slot or a socketed module can be upgraded
+        upgradeable: match soldered {
+            Some(true) => Some(slots_total.is_some_and(|total| total > slots_used)),
+            Some(false) => Some(true),
+            None => None,
+        },
+        ecc,
+        modules,
+    }
+}
+
+// LPDDR is always soldered to the board
+fn is_lpddr(kind: Option<&str>) -> bool {
+    kind.is_some_and(|kind| kind.starts_with("LPDDR"))
+}
+
+// "8 GB", "16384 MB"
+#[cfg(not(target_os = "windows"))]
+fn parse_size(text: &str) -> Option<u64> {
+    let mut parts = text.split_whitespace();
+    let value: u64 = parts.next()?.parse().ok()?;
+    match parts.next()? {
+        "TB" => Some(value << 40),
+        "GB" => Some(value << 30),
+        "MB" => Some(value << 20),
+        "kB" | "KB" => Some(value << 10),
+        _ => None,
+    }
+}
+
+// "2133 MHz", "3200 MT/s"
+#[cfg(not(target_os = "windows"))]
+fn parse_speed(text: &str) -> Option<u32> {
+    text.split_whitespace().next()?.parse().ok().filter(|&speed| speed > 0)
+}
+
+#[cfg(target_os = "macos")]
+fn memory_modules_macos() -> Result<MemoryModules, QuickscanError> {
+    // Apple silicon: [{"SPMemoryDataType": "16 GB", "dimm_manufacturer": "Hynix", "dimm_type": "LPDDR5"}]
+    // Intel: [{"_name": "Memory Slots", "global_ecc_state": "ecc_disabled", "is_memory_upgradeable": "No",
+    //   "_items": [{"_name": "BANK 0/ChannelA-DIMM0", "dimm_manufacturer": "0x80AD",
+    //   "dimm_part_number": "0x483943434E4E4E...", "dimm_size": "8 GB", "dimm_speed": "2133 MHz", "dimm_type": "LPDDR3"}]}]
+    let sections = crate::apple_overview::section("SPMemoryDataType")?;
+    let Some(memory) = sections.first() else {
+        return Err(QuickscanError::parse("system_profiler", "no SPMemoryDataType entry"));
+    };
+    let text = |value: &serde_json::Value, key: &str| {
+        value.get(key).and_then(|v| v.as_str()).map(|s| s.trim().to_string()).filter(|s| !s.is_empty() && s != "-")
+    };
+
+    let Some(slots) = memory.get("_items").and_then(|v| v.as_array()) else {
+        // Apple silicon: the memory sits in the chip package
+        let kind = text(memory, "dimm_type");
+        let module = MemoryModule {
+            slot: "Built-in".to_string(),
+            size_bytes: text(memory, "SPMemoryDataType").and_then(|s| parse_size(&s)).unwrap_or(0),
+            kind,
+            speed_mts: None,
+            manufacturer: text(memory, "dimm_manufacturer"),
+            part_number: None,
+            soldered: Some(true),
+        };
+        return Ok(summarize(vec![module], None, None));
+    };
+
+    let upgradeable = text(memory, "is_memory_upgradeable").map(|s| s == "Yes");
+    let modules = slots
+        .iter()
+        .filter_map(|slot| {
+            let size_bytes = text(slot, "dimm_size").and_then(|s| parse_size(&s))?; // "Empty" slots
+            let kind = text(slot, "dimm_type");
+            Some(MemoryModule {
+                slot: text(slot, "_name").unwrap_or_default(),
+                size_bytes,
+                speed_mts: text(slot, "dimm_speed").and_then(|s| parse_speed(&s)),
+                manufacturer: text(slot, "dimm_manufacturer").map(|m| jedec_manufacturer(&m)),
+                part_number: text(slot, "dimm_part_number").map(|p| decode_hex_ascii(&p)),
+                soldered: if upgradeable == Some(false) || is_lpddr(kind.as_deref()) {
+                    Some(true)
+                } else {
+                    upgradeable.map(|_| false)
+                },
+                kind,
+            })
+        })
+        .collect();
+    let ecc = text(memory, "global_ecc_state").map(|s| s == "ecc_enabled");
+    let mut result = summarize(modules, Some(slots.len() as u32), ecc);
+    result.upgradeable = upgradeable.or(result.upgradeable);
+    Ok(result)
+}
+
+// Intel Macs report JEDEC codes for some makers: "0x80AD"
+#[cfg(target_os = "macos")]
+fn jedec_manufacturer(code: &str) -> String {
+    const MANUFACTURERS: [(&str, &str); 6] = [
+        ("0x80AD", "SK Hynix"),
+        ("0x80CE", "Samsung"),
+        ("0x802C", "Micron"),
+        ("0x0198", "Kingston"),
+        ("0x859B", "Crucial"),
+        ("0x04CD", "G.Skill"),
+    ];
+    MANUFACTURERS
+        .iter()
+        .find(|(id, _)| id.eq_ignore_ascii_case(code))
+        .map(|(_, name)| name.to_string())
+        .unwrap_or_else(|| code.to_string())
+}
+
+// Part numbers come hex-encoded: "0x484D54..." is "HMT..."
+#[cfg(target_os = "macos")]
+fn decode_hex_ascii(text: &str) -> String {
+    let Some(hex) = text.strip_prefix("0x") else {
+        return text.to_string();
+    };
+    let bytes: Option<Vec<u8>> =
+        (0..hex.len() / 2).map(|i| u8::from_str_radix(hex.get(i * 2..i * 2 + 2)?, 16).ok()).collect();
+    match bytes.and_then(|bytes| String::from_utf8(bytes).ok()) {
+        Some(decoded) => decoded.trim_matches(|c: char| c == '\0' || c.is_whitespace()).to_string(),
+        None => text.to_string(),
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn memory_modules_windows() -> Result<MemoryModules, QuickscanError> {
+    use crate::wmi_provider::{WmiProvider, CIMV2};
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct PhysicalMemory {
+        capacity: Option<u64>,
+        speed: Option<u32>,
+        configured_clock_speed: Option<u32>,
+        #[serde(rename = "SMBIOSMemoryType")]
+        smbios_memory_type: Option<u32>,
+        manufacturer: Option<String>,
+        part_number: Option<String>,
+        device_locator: Option<String>,
+        bank_label: Option<String>,
+        form_factor: Option<u32>,
+        total_width: Option<u32>,
+        data_width: Option<u32>,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct PhysicalMemoryArray {
+        memory_devices: Option<u32>,
+        memory_error_correction: Option<u32>, // 3 none, 5 single-bit ECC, 6 multi-bit ECC, 7 CRC
+    }
+
+    let wmi = WmiProvider::global();
+    let rows = wmi.query::<PhysicalMemory>(
+        CIMV2,
+        "SELECT Capacity, Speed, ConfiguredClockSpeed, SMBIOSMemoryType, Manufacturer, PartNumber, DeviceLocator, \
+         BankLabel, FormFactor, TotalWidth, DataWidth FROM Win32_PhysicalMemory",
+    )?;
+    // Use = 3 is system memory, as opposed to video or flash
+    let arrays = wmi
+        .query::<PhysicalMemoryArray>(
+            CIMV2,
+            "SELECT MemoryDevices, MemoryErrorCorrection FROM Win32_PhysicalMemoryArray WHERE Use = 3",
+        )
+        .unwrap_or_default();
+
+    let clean = |value: Option<String>| value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty() && v != "Unknown");
+    // ECC modules carry 8 check bits on top of the 64 data bits
+    let mut ecc_modules = None;
+    let modules: Vec<MemoryModule> = rows
+        .into_iter()
+        .filter(|row| row.capacity.unwrap_or(0) > 0)
+        .map(|row| {
+            let kind = row.smbios_memory_type.and_then(memory_type).map(str::to_string);
+            if let (Some(total), Some(data)) = (row.total_width, row.data_width) {
+                ecc_modules = Some(ecc_modules.unwrap_or(true) && total > data);
+            }
+            // FormFactor 8 DIMM, 12 SODIMM; 9 TSOP, 14 SMD, 21 BGA and 22 FPBGA are chips on the board
+            let soldered = match row.form_factor {
+                _ if is_lpddr(kind.as_deref()) => Some(true),
+                Some(8 | 12) => Some(false),
+                Some(9 | 14 | 21 | 22) => Some(true),
+                _ => None,
+            };
+            let slot = [clean(row.bank_label), clean(row.device_locator)].into_iter().flatten().collect::<Vec<_>>();
+            MemoryModule {
+                slot: slot.join(" / "),
+                size_bytes: row.capacity.unwrap_or(0),
+                kind,
+                speed_mts: row.configured_clock_speed.or(row.speed).filter(|&speed| speed > 0),
+                manufacturer: clean(row.manufacturer),
+                part_number: clean(row.part_number),
+                soldered,
+            }
+        })
+        .collect();
+
+    let slots_total = arrays.iter().filter_map(|array| array.memory_devices).sum::<u32>();
+    let ecc = arrays
+        .iter()
+        .filter_map(|array| array.memory_error_correction)
+        .map(|correction| matches!(correction, 5..=7))
+        .reduce(|a, b| a || b)
+        .or(ecc_modules);
+    Ok(summarize(modules, (slots_total > 0).then_some(slots_total), ecc))
+}
+
+// SMBIOS memory device types
+#[cfg(target_os = "windows")]
+fn memory_type(code: u32) -> Option<&'static str> {
+    match code {
+        20 => Some("DDR"),
+        21 => Some("DDR2"),
+        24 => Some("DDR3"),
+        26 => Some("DDR4"),
+        27 => Some("LPDDR"),
+        28 => Some("LPDDR2"),
+        29 => Some("LPDDR3"),
+        30 => Some("LPDDR4"),
+        34 => Some("DDR5"),
+        35 => Some("LPDDR5"),
+        _ => None,
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn memory_modules_linux() -> Result<MemoryModules, QuickscanError> {
+    use std::process::Command;
+
+    // The DMI tables are root-only
+    let output = crate::process::run_cmd(Command::new("dmidecode").args(["--type", "16,17"])).map_err(|e| match e {
+        QuickscanError::CommandFailed { .. } => QuickscanError::unsupported("memory_modules (install dmidecode)"),
+        other => other,
+    })?;
+    if !output.status.success() {
+        return Err(QuickscanError::PermissionDenied {
+            source: "dmidecode".to_string(),
+            message: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        });
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    // Blocks start with a handle line, then a title: "Memory Device" or "Physical Memory Array"
+    let mut slots_total = 0;
+    let mut ecc = None;
+    let mut modules = vec![];
+    for block in stdout.split("\n\n") {
+        let field = |key: &str| {
+            block
+                .lines()
+                .find_map(|line| line.trim().strip_prefix(key)?.strip_prefix(':'))
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty() && !matches!(v.as_str(), "Unknown" | "Not Specified" | "None"))
+        };
+        if block.contains("Physical Memory Array") && field("Use").as_deref() == Some("System Memory") {
+            slots_total += field("Number Of Devices").and_then(|n| n.parse::<u32>().ok()).unwrap_or(0);
+            ecc = Some(ecc.unwrap_or(false) || field("Error Correction Type").is_some_and(|t| t.contains("ECC")));
+        } else if block.contains("Memory Device") {
+            // "No Module Installed" for an empty slot
+            let Some(size_bytes) = field("Size").and_then(|s| parse_size(&s)) else {
+                continue;
+            };
+            let kind = field("Type");
+            // "Row Of Chips" is memory soldered to the board
+            let soldered = match field("Form Factor").as_deref() {
+                _ if is_lpddr(kind.as_deref()) => Some(true),
+                Some("Row Of Chips") => Some(true),
+                Some("DIMM" | "SODIMM") => Some(false),
+                _ => None,
+            };
+            modules.push(MemoryModule {
+                slot: [field("Bank Locator"), field("Locator")].into_iter().flatten().collect::<Vec<_>>().join(" / "),
+                size_bytes,
+                speed_mts: field("Configured Memory Speed").or_else(|| field("Speed")).and_then(|s| parse_speed(&s)),
+                manufacturer: field("Manufacturer"),
+                part_number: field("Part Number"),
+                soldered,
+                kind,
+            });
+        }
+    }
+    Ok(summarize(modules, (slots_total > 0).then_some(slots_total), ecc))
+}
@@ -0,0 +1,175 @@
+// Memory test (`run_memory_test`), a lightweight memtest run from inside the
+// OS: fill a large buffer with solid patterns, moving inversions and each
+// word's own address, and read every word back. Flaky RAM is a common hidden
+// defect that no probe reports. Only the RAM the OS hands us gets tested, and
+// the reported addresses are virtual, so a clean run is not proof.
+
+use crate::error::QuickscanError;
+use serde::Serialize;
+use std::ptr::{read_volatile, write_volatile};
+use std::time::Instant;
+use sysinfo::System;
+use tauri::Emitter;
+
+pub const PROGRESS_EVENT: &str = "memory-test-progress";
+
+const MIN_SIZE_MB: u64 = 16;
+// Leave the rest free so the buffer is never paged out; that would test the disk
+const MAX_AVAILABLE_SHARE: u64 = 2; // 1/2 of available memory
+const MAX_PASSES: u32 = 10;
+// A bad module can fail millions of words; the first few tell the story
+const MAX_FAULTS: usize = 100;
+const SOLID_PATTERNS: [u64; 4] = [0, !0, 0x5555_5555_5555_5555, 0xAAAA_AAAA_AAAA_AAAA];
+// Solid patterns, moving inversions and address-in-address
+const STEPS_PER_PASS: u32 = SOLID_PATTERNS.len() as u32 + 2;
+
+#[derive(Serialize, Clone)]
+pub struct MemoryTestProgress {
+    pub pass: u32, // 1-based
+    pub passes: u32,
+    pub test: &'static str,
+    pub percent: f64, // of the whole run
+}
+
+// Hex strings: full 64-bit values don't survive a JavaScript number
+#[derive(Serialize)]
+pub struct MemoryFault {
+    pub address: String,
+    pub expected: String,
+    pub actual: String,
+    pub test: &'static str,
+}
+
+#[derive(Serialize)]
+pub struct MemoryTestResult {
+    pub size_mb: u64, // after clamping to what is free
+    pub passes: u32,
+    pub duration_secs: f64,
+    pub fault_count: u64, // every mismatch, including those past MAX_FAULTS
+    pub faults: Vec<MemoryFault>,
+}
+
+#[tauri::command]
+pub async fn run_memory_test(app: tauri::AppHandle, size_mb: u64, passes: u32) -> Result<MemoryTestResult, QuickscanError> {
+    crate::process::blocking(move || memory_test(&app, size_mb, passes)).await
+}
+
+struct Tester<'a> {
+    app: &'a tauri::AppHandle,
+    words: Vec<u64>,
+    pass: u32,
+    passes: u32,
+    step: u32,
+    fault_count: u64,
+    faults: Vec<MemoryFault>,
+}
+
+fn memory_test(app: &tauri::AppHandle, size_mb: u64, passes: u32) -> Result<MemoryTestResult, QuickscanError> {
+    let mut system = System::new();
+    system.refresh_memory();
+    let max_mb = (system.available_memory() / 1024 / 1024 / MAX_AVAILABLE_SHARE).max(MIN_SIZE_MB);
+    let size_mb = size_mb.clamp(MIN_SIZE_MB, max_mb);
+    let passes = passes.clamp(1, MAX_PASSES);
+
+    let len = (size_mb * 1024 * 1024 / 8) as usize;
+    let mut words = Vec::new();
+    words
+        .try_reserve_exact(len)
+        .map_err(|_| QuickscanError::ResourceLimit { source: "memory_test".to_string(), resource: "memory".to_string() })?;
+    words.resize(len, 0);
+
+    let started = Instant::now();
+    let mut tester = Tester { app, words, pass: 0, passes, step: 0, fault_count: 0, faults: vec![] };
+    for pass in 1..=passes {
+        tester.pass = pass;
+        for pattern in SOLID_PATTERNS {
+            tester.solid(pattern);
+        }
+        // A different pattern each pass catches bits stuck in either direction
+        tester.moving_inversions(0x0123_4567_89AB_CDEFu64.rotate_left(pass * 8));
+        tester.address_in_address();
+    }
+
+    Ok(MemoryTestResult {
+        size_mb,
+        passes,
+        duration_secs: (started.elapsed().as_secs_f64() * 10.0).round() / 10.0,
+        fault_count: tester.fault_count,
+        faults: tester.faults,
+    })
+}
+
+impl Tester<'_> {
+    // Volatile so the compiler can't skip reading back what it just wrote
+    fn write(&mut self, index: usize, value: u64) {
+        unsafe { write_volatile(self.words.as_mut_ptr().add(index), value) }
+    }
+
+    fn check(&mut self, index: usize, expected: u64, test: &'static str) {
+        let actual = unsafe { read_volatile(self.words.as_ptr().add(index)) };
+        if actual != expected {
+            self.fault_count += 1;
+            if self.faults.len() < MAX_FAULTS {
+                let address = self.words.as_ptr() as u64 + index as u64 * 8;
+                self.faults.push(MemoryFault {
+                    address: format!("{:#x}", address),
+                    expected: format!("{:#018x}", expected),
+                    actual: format!("{:#018x}", actual),
+                    test,
+                });
+            }
+        }
+    }
+
+    fn finish_step(&mut self, test: &'static str) {
+        self.step += 1;
+        let percent = self.step as f64 / (self.passes * STEPS_PER_PASS) as f64 * 100.0;
+        // The UI shows progress; a closed window just stops listening
+        let _ = self.app.emit(
+            PROGRESS_EVENT,
+            MemoryTestProgress { pass: self.pass, passes: self.passes, test, percent: percent.round() },
+        );
+    }
+
+    fn solid(&mut self, pattern: u64) {
+        for i in 0..self.words.len() {
+            self.write(i, pattern);
+        }
+        for i in 0..self.words.len() {
+            self.check(i, pattern, "solid");
+        }
+        self.finish_step("solid");
+    }
+
+    // Up then down through the buffer, checking each word and flipping it, so
+    // a write that disturbs a neighbour is caught on the way back
+    fn moving_inversions(&mut self, pattern: u64) {
+        for i in 0..self.words.len() {
+            self.write(i, pattern);
+        }
+        for i in 0..self.words.len() {
+            self.check(i, pattern, "inversion");
+            self.write(i, !pattern);
+        }
+        for i in (0..self.words.len()).rev() {
+            self.check(i, !pattern, "inversion");
+            self.write(i, pattern);
+        }
+        self.finish_step("inversion");
+    }
+
+    // Each word holds its own index, then the complement: catches address
+    // lines that alias two locations onto one
+    fn address_in_address(&mut self) {
+        for invert in [false, true] {
+            let value = |i: usize| if invert { !(i as u64) } else { i as u64 };
+            for i in 0..self.words.len() {
+                self.write(i, value(i));
+            }
+            for i in 0..self.words.len() {
+                self.check(i, value(i), "address");
+            }
+        }
+        self.finish_step("address");
+    }
+}
@@ -0,0 +1,277 @@
+// Every network interface with its MAC address and link state, for
+// `get_network_adapters`. A desktop or dock Ethernet port is only proven by
+// a link coming up on it, and the MACs identify the machine in fleet and
+// inventory reports. sysinfo supplies the interfaces, their MACs and
+// addresses; the OS adds the kind, link speed and driver.
+
+use crate::error::QuickscanError;
+use crate::probe_scheduler::ProbeScheduler;
+use serde::Serialize;
+use sysinfo::Networks;
+
+#[derive(Serialize)]
+pub struct NetworkAdapter {
+    pub name: String, // "en0", "eth0", "Ethernet 2"
+    pub description: Option<String>, // "Thunderbolt Ethernet Slot 1", "Intel(R) Ethernet Connection I219-V"
+    pub kind: &'static str, // "ethernet", "wifi", "wwan", "bluetooth", "virtual", "loopback" or "other"
+    pub mac: Option<String>,
+    pub up: bool, // link detected
+    pub speed_mbps: Option<u64>, // negotiated rate; None while down or where not reported
+    pub driver: Option<String>,
+    pub addresses: Vec<String>, // "192.168.1.20/24"
+}
+
+impl NetworkAdapter {
+    fn new(name: String) -> Self {
+        NetworkAdapter {
+            name,
+            description: None,
+            kind: "other",
+            mac: None,
+            up: false,
+            speed_mbps: None,
+            driver: None,
+            addresses: vec![],
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn get_network_adapters(probes: tauri::State<'_, ProbeScheduler>) -> Result<Vec<NetworkAdapter>, QuickscanError> {
+    probes.run(network_adapters).await
+}
+
+pub fn network_adapters() -> Result<Vec<NetworkAdapter>, QuickscanError> {
+    #[cfg(target_os = "macos")]
+    let mut adapters = adapters_macos();
+    #[cfg(target_os = "windows")]
+    let mut adapters = adapters_windows()?;
+    #[cfg(target_os = "linux")]
+    let mut adapters = adapters_linux();
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    let mut adapters: Vec<NetworkAdapter> = return Err(QuickscanError::unsupported("network_adapters"));
+
+    let networks = Networks::new_with_refreshed_list();
+    for (name, data) in networks.list() {
+        let index = match adapters.iter().position(|a| &a.name == name) {
+            Some(index) => index,
+            None => {
+                adapters.push(NetworkAdapter::new(name.clone()));
+                adapters.len() - 1
+            }
+        };
+        let adapter = &mut adapters[index];
+        let mac = data.mac_address();
+        if !mac.is_unspecified() {
+            adapter.mac = Some(mac.to_string());
+        }
+        adapter.addresses = data.ip_networks().iter().map(|n| format!("{}/{}", n.addr, n.prefix)).collect();
+    }
+
+    // Hardware ports first, then by name
+    adapters.sort_by(|a, b| {
+        let rank = |kind: &str| matches!(kind, "virtual" | "loopback" | "other") as u8;
+        rank(a.kind).cmp(&rank(b.kind)).then_with(|| a.name.cmp(&b.name))
+    });
+    Ok(adapters)
+}
+
+// "1000baseT", "10GbaseT", "2500Base-T" to Mb/s
+#[cfg(target_os = "macos")]
+fn media_speed(media: &str) -> Option<u64> {
+    let rate = &media[..media.to_ascii_lowercase().find("base")?];
+    match rate.strip_suffix('G') {
+        Some(gigabits) => gigabits.parse::<u64>().ok().map(|g| g * 1000),
+        None => rate.parse().ok(),
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn adapters_macos() -> Vec<NetworkAdapter> {
+    use crate::process;
+    use std::process::Command;
+
+    let mut adapters: Vec<NetworkAdapter> = Vec::new();
+
+    // Hardware ports name the physical interfaces:
+    //   Hardware Port: Thunderbolt Ethernet Slot 1
+    //   Device: en5
+    if let Ok(output) = process::run_cmd(Command::new("networksetup").arg("-listallhardwareports")) {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let mut port: Option<String> = None;
+        for line in stdout.lines() {
+            if let Some(name) = line.strip_prefix("Hardware Port:") {
+                port = Some(name.trim().to_string());
+            } else if let (Some(device), Some(port)) = (line.strip_prefix("Device:"), port.take()) {
+                let mut adapter = NetworkAdapter::new(device.trim().to_string());
+                adapter.kind = if port == "Wi-Fi" || port == "AirPort" {
+                    "wifi"
+                } else if port.contains("Bluetooth") {
+                    "bluetooth"
+                } else if port.contains("Bridge") || port.contains("VPN") {
+                    "virtual"
+                } else if port.contains("Ethernet") || port.contains("LAN") {
+                    "ethernet"
+                } else {
+                    "other" // "iPhone USB", "FireWire"
+                };
+                adapter.description = Some(port);
+                adapters.push(adapter);
+            }
+        }
+    }
+
+    // Link state and negotiated media from ifconfig:
+    //   en5: flags=8863<UP,BROADCAST,SMART,RUNNING,SIMPLEX,MULTICAST> mtu 1500
+    //   	media: autoselect (1000baseT <full-duplex>)
+    //   	status: active
+    let output = match process::run_cmd(Command::new("ifconfig").arg("-a")) {
+        Ok(output) => output,
+        Err(_) => return adapters,
+    };
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut current: Option<usize> = None;
+    for line in stdout.lines() {
+        if !line.starts_with(char::is_whitespace) {
+            let Some((name, rest)) = line.split_once(": flags=") else {
+                current = None;
+                continue;
+            };
+            let index = match adapters.iter().position(|a| a.name == name) {
+                Some(index) => index,
+                None => {
+                    let mut adapter = NetworkAdapter::new(name.to_string());
+                    adapter.kind = if name.starts_with("lo") { "loopback" } else { "virtual" }; // utun, awdl, bridge, llw
+                    adapters.push(adapter);
+                    adapters.len() - 1
+                }
+            };
+            // Interfaces without a status line (loopback, tunnels) are up when running
+            adapters[index].up = rest.contains("UP,") && rest.contains("RUNNING");
+            current = Some(index);
+        } else if let Some(index) = current {
+            let line = line.trim();
+            if let Some(status) = line.strip_prefix("status:") {
+                adapters[index].up = status.trim() == "active";
+            } else if let Some(media) = line.strip_prefix("media:") {
+                let selected = media.split('(').nth(1).unwrap_or(media).trim();
+                adapters[index].speed_mbps = media_speed(selected);
+            }
+        }
+    }
+    for adapter in &mut adapters {
+        if !adapter.up {
+            adapter.speed_mbps = None;
+        }
+    }
+    adapters
+}
+
+#[cfg(target_os = "windows")]
+fn adapters_windows() -> Result<Vec<NetworkAdapter>, QuickscanError> {
+    use crate::wmi_provider::{WmiProvider, STANDARD_CIMV2};
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct NetAdapter {
+        name: String,
+        interface_description: Option<String>,
+        permanent_address: Option<String>, // "A4BB6D1C2E3F"
+        speed: Option<u64>,                // bits per second
+        media_connect_state: Option<u32>,  // 1 connected, 2 disconnected
+        driver_name: Option<String>,       // "\SystemRoot\System32\drivers\e1d68x64.sys"
+        #[serde(rename = "Virtual")]
+        is_virtual: Option<bool>,
+        ndis_physical_medium: Option<u32>,
+        connector_present: Option<bool>,
+    }
+
+    let rows = WmiProvider::global().query::<NetAdapter>(
+        STANDARD_CIMV2,
+        "SELECT Name, InterfaceDescription, PermanentAddress, Speed, MediaConnectState, DriverName, Virtual, \
+         NdisPhysicalMedium, ConnectorPresent FROM MSFT_NetAdapter",
+    )?;
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            let mut adapter = NetworkAdapter::new(row.name);
+            // NDIS_PHYSICAL_MEDIUM: 1 wireless LAN, 8 wireless WAN, 9 native 802.11,
+            // 10 Bluetooth, 12 WiMAX, 14 802.3
+            adapter.kind = if row.is_virtual == Some(true) || row.connector_present == Some(false) {
+                "virtual"
+            } else {
+                match row.ndis_physical_medium {
+                    Some(1 | 9) => "wifi",
+                    Some(8 | 12) => "wwan",
+                    Some(10) => "bluetooth",
+                    Some(14) => "ethernet",
+                    _ => "other",
+                }
+            };
+            adapter.description = row.interface_description;
+            adapter.mac = row
+                .permanent_address
+                .filter(|mac| mac.len() == 12)
+                .map(|mac| mac.as_bytes().chunks(2).map(|pair| String::from_utf8_lossy(pair)).collect::<Vec<_>>().join(":"));
+            adapter.up = row.media_connect_state == Some(1);
+            adapter.speed_mbps = row.speed.filter(|_| adapter.up).map(|bps| bps / 1_000_000);
+            adapter.driver = row
+                .driver_name
+                .as_deref()
+                .and_then(|path| path.rsplit('\\').next())
+                .filter(|file| !file.is_empty())
+                .map(str::to_string);
+            adapter
+        })
+        .collect())
+}
+
+#[cfg(target_os = "linux")]
+fn adapters_linux() -> Vec<NetworkAdapter> {
+    use std::fs;
+    use std::path::Path;
+
+    let read = |path: &Path| fs::read_to_string(path).ok().map(|s| s.trim().to_string());
+    let Ok(entries) = fs::read_dir("/sys/class/net") else {
+        return vec![];
+    };
+    entries
+        .flatten()
+        .map(|entry| {
+            let dir = entry.path();
+            let mut adapter = NetworkAdapter::new(entry.file_name().to_string_lossy().to_string());
+            let devtype = read(&dir.join("uevent"))
+                .and_then(|uevent| uevent.lines().find_map(|l| l.strip_prefix("DEVTYPE=").map(str::to_string)));
+            // ARPHRD_LOOPBACK is 772; interfaces with no device behind them are software
+            adapter.kind = if read(&dir.join("type")).as_deref() == Some("772") {
+                "loopback"
+            } else if !dir.join("device").exists() {
+                "virtual"
+            } else if dir.join("wireless").exists() || dir.join("phy80211").exists() {
+                "wifi"
+            } else {
+                match devtype.as_deref() {
+                    Some("wwan") => "wwan",
+                    Some("bluetooth") => "bluetooth",
+                    Some(_) => "other",
+                    None if read(&dir.join("type")).as_deref() == Some("1") => "ethernet",
+                    None => "other",
+                }
+            };
+            // operstate is "unknown" for loopback and some drivers; carrier settles it
+            let operstate = read(&dir.join("operstate"));
+            adapter.up = operstate.as_deref() == Some("up")
+                || (operstate.as_deref() == Some("unknown") && read(&dir.join("carrier")).as_deref() == Some("1"));
+            // speed reads -1 or fails while the link is down
+            adapter.speed_mbps = read(&dir.join("speed"))
+                .and_then(|s| s.parse::<i64>().ok())
+                .filter(|&s| s > 0 && adapter.up)
+                .map(|s| s as u64);
+            adapter.driver = fs::read_link(dir.join("device/driver"))
+                .ok()
+                .and_then(|link| link.file_name().map(|f| f.to_string_lossy().to_string()));
+            adapter
+        })
+        .collect()
+}
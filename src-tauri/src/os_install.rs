@@ -0,0 +1,213 @@
+// When the operating system was installed, and how often since, for
+// `get_os_install_history`. A system installed from scratch a week before
+// the sale hides whatever the previous owner's setup would have shown (crash
+// history, pairings, management profiles), so `check_refurbishment` points a
+// recent install out. A clean install erases the records of earlier ones, so
+// "original" means the earliest install the disk still remembers.
+//
+// macOS: the Setup Assistant marks the end of a clean install by creating
+// /var/db/.AppleSetupDone. install.log records every run of the full OS
+// installer and softwareupdate's journal.plist every update it installed,
+// major upgrades included; an installer run on a day without an upgrade is
+// the same version installed again over itself (Recovery's "Reinstall
+// macOS").
+//
+// Windows: every in-place setup (feature upgrade, repair install, "Reset this
+// PC") moves the previous system's details to SYSTEM\Setup\Source OS
+// (Updated on …). One that kept the build is a reinstall. Windows.old holds
+// the previous system for about ten days after one.
+
+use crate::error::QuickscanError;
+use crate::probe_scheduler::ProbeScheduler;
+use chrono::NaiveDate;
+use serde::Serialize;
+
+#[derive(Serialize, Default)]
+pub struct OsInstallHistory {
+    pub original_install: Option<String>, // YYYY-MM-DD
+    pub last_clean_install: Option<String>, // set up from scratch, or reset
+    pub last_reinstall: Option<String>,
+    pub reinstall_count: u32, // the installed version installed again
+    pub upgrade_count: u32, // to a newer version
+    pub previous_install_kept: Option<bool>, // Windows.old; None on macOS
+}
+
+// An install this recent is worth asking the seller about
+pub const RECENT_INSTALL_DAYS: i64 = 14;
+
+#[tauri::command]
+pub async fn get_os_install_history(probes: tauri::State<'_, ProbeScheduler>) -> Result<OsInstallHistory, QuickscanError> {
+    probes.run(os_install_history).await
+}
+
+pub fn os_install_history() -> Result<OsInstallHistory, QuickscanError> {
+    #[cfg(target_os = "macos")]
+    {
+        Ok(macos::os_install_history())
+    }
+    #[cfg(target_os = "windows")]
+    {
+        Ok(win32::os_install_history())
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        Err(QuickscanError::unsupported("os_install_history"))
+    }
+}
+
+// The later of the last clean install and the last reinstall, with how many
+// days ago it was
+pub fn latest_install(history: &OsInstallHistory) -> Option<(String, i64)> {
+    let latest = history.last_clean_install.iter().chain(&history.last_reinstall).max()?;
+    let date = NaiveDate::parse_from_str(latest, "%Y-%m-%d").ok()?;
+    Some((latest.clone(), (chrono::Local::now().date_naive() - date).num_days()))
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn date_of(time: std::time::SystemTime) -> String {
+    chrono::DateTime::<chrono::Local>::from(time).format("%Y-%m-%d").to_string()
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use super::OsInstallHistory;
+    use crate::process;
+    use std::collections::BTreeSet;
+    use std::process::Command;
+
+    const INSTALL_LOG: &str = "/var/log/install.log";
+    const JOURNAL: &str = "/var/db/softwareupdate/journal.plist";
+    const SETUP_DONE: &str = "/var/db/.AppleSetupDone";
+
+    // `<key>name</key>` followed by a `<string>` or `<date>` in an XML plist dict
+    fn xml_value<'a>(dict: &'a str, key: &str) -> Option<&'a str> {
+        let rest = &dict[dict.find(&format!("<key>{}</key>", key))?..];
+        let value = &rest[rest.find("</key>")? + 6..];
+        let value = &value[value.find('>')? + 1..];
+        Some(value[..value.find('<')?].trim())
+    }
+
+    // Install dates of major macOS upgrades, and of any update
+    fn journal_dates() -> (BTreeSet<String>, Option<String>) {
+        let mut command = Command::new("plutil");
+        let Ok(output) = process::run_cmd(command.args(["-convert", "xml1", "-o", "-", JOURNAL])) else {
+            return (BTreeSet::new(), None);
+        };
+        let xml = String::from_utf8_lossy(&output.stdout);
+        let mut upgrades = BTreeSet::new();
+        let mut earliest: Option<String> = None;
+        for dict in xml.split("<dict>").skip(1) {
+            // "2023-10-02T08:14:55Z"
+            let Some(date) = xml_value(dict, "__installDate").and_then(|date| date.get(..10)) else {
+                continue;
+            };
+            if earliest.as_deref().is_none_or(|earliest| date < earliest) {
+                earliest = Some(date.to_string());
+            }
+            let title = xml_value(dict, "title").unwrap_or_default();
+            if title.starts_with("macOS") || title.starts_with("OS X") {
+                upgrades.insert(date.to_string());
+            }
+        }
+        (upgrades, earliest)
+    }
+
+    pub fn os_install_history() -> OsInstallHistory {
+        let last_clean_install =
+            std::fs::metadata(SETUP_DONE).and_then(|metadata| metadata.created()).ok().map(super::date_of);
+        let (upgrades, first_update) = journal_dates();
+
+        // "2024-01-10 09:31:05+01 MacBook-Pro OSInstaller[456]: ..."
+        let log = std::fs::read_to_string(INSTALL_LOG).unwrap_or_default();
+        let first_logged = log.lines().next().and_then(|line| line.get(..10)).map(str::to_string);
+        let installer_days: BTreeSet<String> = log
+            .lines()
+            .filter(|line| line.contains(" OSInstaller["))
+            .filter_map(|line| line.get(..10).map(str::to_string))
+            .collect();
+        // Runs after the clean install that weren't upgrades
+        let reinstalls: Vec<&String> = installer_days
+            .iter()
+            .filter(|day| last_clean_install.as_ref().is_some_and(|clean| *day > clean) && !upgrades.contains(*day))
+            .collect();
+
+        let original_install =
+            [&last_clean_install, &first_logged, &first_update].into_iter().flatten().min().cloned();
+        OsInstallHistory {
+            original_install,
+            last_reinstall: reinstalls.last().map(|day| day.to_string()),
+            reinstall_count: reinstalls.len() as u32,
+            upgrade_count: upgrades.len() as u32,
+            last_clean_install,
+            previous_install_kept: None,
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod win32 {
+    use super::OsInstallHistory;
+    use crate::wmi_provider::{registry_dword, registry_string, registry_subkeys};
+    use std::time::{Duration, UNIX_EPOCH};
+
+    const CURRENT_VERSION: &str = "SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion";
+
+    struct Install {
+        date: String,
+        build: Option<String>,
+    }
+
+    fn install(key: &str) -> Option<Install> {
+        // Seconds since 1970
+        let seconds = registry_dword(key, "InstallDate")?;
+        Some(Install {
+            date: super::date_of(UNIX_EPOCH + Duration::from_secs(u64::from(seconds))),
+            build: registry_string(key, "CurrentBuild"),
+        })
+    }
+
+    // Setup's log starts with the run that installed the current system:
+    // "2024-01-10 09:31:05, Info                  SP     ..."
+    fn setup_log_date(windows_dir: &str) -> Option<String> {
+        let log = std::fs::read(format!("{}\\Panther\\setupact.log", windows_dir)).ok()?;
+        let first = String::from_utf8_lossy(&log[..log.len().min(256)]).lines().next()?.to_string();
+        let date = first.get(..10)?;
+        date.bytes().all(|b| b.is_ascii_digit() || b == b'-').then(|| date.to_string())
+    }
+
+    pub fn os_install_history() -> OsInstallHistory {
+        let windows_dir = std::env::var("SystemRoot").unwrap_or_else(|_| "C:\\Windows".to_string());
+        let system_drive = std::env::var("SystemDrive").unwrap_or_else(|_| "C:".to_string());
+
+        let mut installs: Vec<Install> = registry_subkeys("SYSTEM\\Setup")
+            .into_iter()
+            .filter(|name| name.starts_with("Source OS"))
+            .filter_map(|name| install(&format!("SYSTEM\\Setup\\{}", name)))
+            .collect();
+        installs.sort_by(|a, b| a.date.cmp(&b.date));
+        let current = install(CURRENT_VERSION).or_else(|| {
+            Some(Install { date: setup_log_date(&windows_dir)?, build: registry_string(CURRENT_VERSION, "CurrentBuild") })
+        });
+        installs.extend(current);
+
+        let (mut reinstall_count, mut upgrade_count) = (0, 0);
+        let mut last_reinstall = None;
+        for pair in installs.windows(2) {
+            if pair[0].build.is_some() && pair[0].build == pair[1].build {
+                reinstall_count += 1;
+                last_reinstall = Some(pair[1].date.clone());
+            } else {
+                upgrade_count += 1;
+            }
+        }
+        let original_install = installs.first().map(|install| install.date.clone());
+        OsInstallHistory {
+            last_clean_install: last_reinstall.clone().or_else(|| original_install.clone()),
+            original_install,
+            last_reinstall,
+            reinstall_count,
+            upgrade_count,
+            previous_install_kept: Some(std::path::Path::new(&format!("{}\\Windows.old", system_drive)).exists()),
+        }
+    }
+}
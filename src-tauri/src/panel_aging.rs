@@ -0,0 +1,237 @@
+// Wear of OLED and mini-LED panels for `get_panel_aging`, from the screen
+// test. Both lose brightness with use: OLED emitters dim as they age, a
+// mini-LED backlight's LEDs more slowly. Two estimates of what is left go
+// into the panel's condition score, the lower one winning:
+//
+// - on-hours against the hours such a panel takes to fall to half
+//   brightness, where the panel reports them (DDC/CI usage time on monitors
+//   that support it; laptop panels don't)
+// - the full-screen white a colorimeter measured at full brightness against
+//   the panel's rated SDR brightness, where the rating is known
+//
+// LCDs with an ordinary backlight get no score; their backlight outlives the
+// machine.
+
+use crate::display::DisplayInfo;
+use crate::error::QuickscanError;
+use crate::probe_scheduler::ProbeScheduler;
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct PanelAging {
+    pub display: String,
+    pub technology: &'static str, // "oled", "mini_led" or "lcd"
+    pub panel_model: Option<String>, // part number from the EDID, "ATNA40YK04-0"
+    pub on_hours: Option<u64>,
+    pub firmware_version: Option<String>,
+    pub rated_brightness_nits: Option<f64>, // full-screen SDR white
+    pub measured_brightness_nits: Option<f64>,
+    pub brightness_percent: Option<f64>, // estimated brightness left, of new
+    pub score: Option<u8>, // panel condition, 0-100
+    pub rating: Option<&'static str>, // "good", "fair" or "worn"
+}
+
+// What the platform knows about the panel besides `DisplayInfo`
+#[derive(Default)]
+struct PanelDetails {
+    technology: Option<&'static str>,
+    panel_model: Option<String>,
+    on_hours: Option<u64>,
+    firmware_version: Option<String>,
+    rated_brightness_nits: Option<f64>,
+}
+
+// Hours to half brightness
+const OLED_HALF_LIFE_HOURS: f64 = 30_000.0;
+const MINI_LED_HALF_LIFE_HOURS: f64 = 50_000.0;
+
+const GOOD_PERCENT: f64 = 90.0;
+const FAIR_PERCENT: f64 = 75.0;
+
+// Part number prefixes of OLED laptop panels (Samsung Display's "ATNA…")
+const OLED_PANEL_PREFIXES: &[&str] = &["ATNA"];
+
+// Mini-LED Macs and their rated SDR brightness in nits
+#[cfg(target_os = "macos")]
+const MINI_LED_MACS: &[(&str, f64)] = &[
+    ("MacBookPro18,1", 500.0),
+    ("MacBookPro18,2", 500.0),
+    ("MacBookPro18,3", 500.0),
+    ("MacBookPro18,4", 500.0),
+    ("Mac14,5", 500.0),
+    ("Mac14,6", 500.0),
+    ("Mac14,9", 500.0),
+    ("Mac14,10", 500.0),
+    ("Mac15,3", 600.0),
+    ("Mac15,6", 600.0),
+    ("Mac15,7", 600.0),
+    ("Mac15,8", 600.0),
+    ("Mac15,9", 600.0),
+    ("Mac15,10", 600.0),
+    ("Mac15,11", 600.0),
+    ("Mac16,1", 1000.0),
+    ("Mac16,5", 1000.0),
+    ("Mac16,6", 1000.0),
+    ("Mac16,7", 1000.0),
+    ("Mac16,8", 1000.0),
+];
+
+// `measured_brightness_nits` is the white patch's luminance from `read_colorimeter`
+#[tauri::command]
+pub async fn get_panel_aging(probes: tauri::State<'_, ProbeScheduler>, measured_brightness_nits: Option<f64>) -> Result<Option<PanelAging>, QuickscanError> {
+    probes.run(move || panel_aging(measured_brightness_nits)).await
+}
+
+// The built-in panel, or the first monitor of a desktop; None without a display
+pub fn panel_aging(measured_brightness_nits: Option<f64>) -> Result<Option<PanelAging>, QuickscanError> {
+    let displays = crate::display::display_info()?;
+    let Some(display) = displays.iter().find(|d| d.is_internal).or(displays.first()) else {
+        return Ok(None);
+    };
+    let details = panel_details(display);
+    let technology = details.technology.unwrap_or_else(|| {
+        let oled = details.panel_model.as_deref().is_some_and(|model| {
+            OLED_PANEL_PREFIXES.iter().any(|prefix| model.starts_with(prefix)) || model.contains("OLED")
+        });
+        if oled || display.name.contains("OLED") { "oled" } else { "lcd" }
+    });
+
+    let half_life = match technology {
+        "oled" => Some(OLED_HALF_LIFE_HOURS),
+        "mini_led" => Some(MINI_LED_HALF_LIFE_HOURS),
+        _ => None,
+    };
+    let measured_brightness_nits = measured_brightness_nits.filter(|nits| *nits > 0.0);
+    let brightness_percent = half_life.and_then(|half_life| {
+        let by_hours = details.on_hours.map(|hours| (100.0 - 50.0 * hours as f64 / half_life).max(0.0));
+        let by_measurement = measured_brightness_nits
+            .zip(details.rated_brightness_nits)
+            .map(|(measured, rated)| (measured / rated * 100.0).min(100.0));
+        match (by_hours, by_measurement) {
+            (Some(hours), Some(measured)) => Some(hours.min(measured)),
+            (hours, measured) => hours.or(measured),
+        }
+    });
+    let score = brightness_percent.map(|percent| percent.round() as u8);
+    let rating = brightness_percent.map(|percent| match percent {
+        p if p >= GOOD_PERCENT => "good",
+        p if p >= FAIR_PERCENT => "fair",
+        _ => "worn",
+    });
+
+    Ok(Some(PanelAging {
+        display: display.name.clone(),
+        technology,
+        panel_model: details.panel_model,
+        on_hours: details.on_hours,
+        firmware_version: details.firmware_version,
+        rated_brightness_nits: details.rated_brightness_nits,
+        measured_brightness_nits,
+        brightness_percent,
+        score,
+        rating,
+    }))
+}
+
+// Apple publishes the panel of every Mac, so the model identifier tells
+// mini-LED from LCD; the panels report nothing about their use
+#[cfg(target_os = "macos")]
+fn panel_details(display: &DisplayInfo) -> PanelDetails {
+    if !display.is_internal {
+        if display.name.contains("Pro Display XDR") {
+            return PanelDetails { technology: Some("mini_led"), rated_brightness_nits: Some(500.0), ..Default::default() };
+        }
+        return PanelDetails::default();
+    }
+    let identifier = crate::device_model::device_model().map(|model| model.identifier).unwrap_or_default();
+    match MINI_LED_MACS.iter().find(|(model, _)| *model == identifier) {
+        Some((_, rated)) => PanelDetails {
+            technology: Some("mini_led"),
+            rated_brightness_nits: Some(*rated),
+            ..Default::default()
+        },
+        None => PanelDetails { technology: Some("lcd"), ..Default::default() },
+    }
+}
+
+// Windows keeps each monitor's EDID in the registry under its instance name.
+// Reading DDC/CI usage time would need a physical monitor handle per display,
+// so on-hours stay unknown here.
+#[cfg(target_os = "windows")]
+fn panel_details(display: &DisplayInfo) -> PanelDetails {
+    use crate::wmi_provider::{self, WmiProvider, WMI};
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct MonitorConnection {
+        instance_name: String,
+        video_output_technology: u32,
+    }
+
+    let connections: Vec<MonitorConnection> = WmiProvider::global()
+        .query(WMI, "SELECT InstanceName, VideoOutputTechnology FROM WmiMonitorConnectionParams")
+        .unwrap_or_default();
+    // LVDS, eDP, UDI embedded and "internal", as in display_info
+    let connection = connections.iter().find(|connection| {
+        matches!(connection.video_output_technology, 6 | 11 | 13 | 0x8000_0000) == display.is_internal
+    });
+    // "DISPLAY\SDC4161\4&2a5f9c2&0&UID8388688_0"; the registry key drops the "_0"
+    let edid = connection.and_then(|connection| {
+        let device = connection.instance_name.rsplit_once('_').map_or(connection.instance_name.as_str(), |(d, _)| d);
+        let key = format!("SYSTEM\\CurrentControlSet\\Enum\\{}\\Device Parameters", device);
+        wmi_provider::registry_binary(&key, "EDID")
+    });
+    PanelDetails {
+        panel_model: edid.as_deref().and_then(crate::display::edid_panel_model),
+        ..Default::default()
+    }
+}
+
+// The EDID comes from the DRM connector; monitors (not laptop panels) answer
+// DDC/CI, which ddcutil reads when it is installed
+#[cfg(target_os = "linux")]
+fn panel_details(display: &DisplayInfo) -> PanelDetails {
+    let connectors = std::fs::read_dir("/sys/class/drm").map(|entries| {
+        let mut paths: Vec<_> = entries.flatten().map(|entry| entry.path()).collect();
+        paths.sort();
+        paths
+    });
+    let connector = connectors.unwrap_or_default().into_iter().find(|path| {
+        let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        let internal = ["-eDP-", "-LVDS-", "-DSI-"].iter().any(|kind| name.contains(kind));
+        internal == display.is_internal
+            && std::fs::read_to_string(path.join("status")).is_ok_and(|status| status.trim() == "connected")
+    });
+    let edid = connector.and_then(|connector| std::fs::read(connector.join("edid")).ok());
+
+    let (on_hours, firmware_version) = if display.is_internal {
+        (None, None)
+    } else {
+        // "VCP code 0xc0 (Display usage time): Usage time (hours) = 1234 (0x0004d2) mh=0xff, ..."
+        let on_hours = ddcutil_value("C0")
+            .and_then(|value| value.split_once("= ")?.1.split_whitespace().next()?.parse().ok());
+        // "VCP code 0xc9 (Display firmware level): 0.16"
+        (on_hours, ddcutil_value("C9"))
+    };
+    PanelDetails {
+        panel_model: edid.as_deref().and_then(crate::display::edid_panel_model),
+        on_hours,
+        firmware_version,
+        ..Default::default()
+    }
+}
+
+// What follows "): " in `ddcutil getvcp <code>`
+#[cfg(target_os = "linux")]
+fn ddcutil_value(code: &str) -> Option<String> {
+    let output = crate::process::run_cmd(std::process::Command::new("ddcutil").args(["getvcp", code])).ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let value = stdout.lines().find(|line| line.starts_with("VCP code"))?.split_once("): ")?.1.trim().to_string();
+    (!value.is_empty()).then_some(value)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+fn panel_details(_display: &DisplayInfo) -> PanelDetails {
+    PanelDetails::default()
+}
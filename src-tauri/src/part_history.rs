@@ -0,0 +1,90 @@
+// Component serials from earlier scans, keyed by the device serial (or its
+// fingerprint, see fingerprint.rs), in `part_history.json` in the app data
+// directory. When the same machine is
+// scanned again (a customer return, a trade-in re-check) its parts are
+// compared against the previous scan; a battery or SSD that changed in
+// between is what return fraud looks like.
+//
+// { "C02XL0GXJGH5": { "scanned_at": 1760601600, "serials": { "battery": "...", ... } } }
+
+use crate::component_serials::{ComponentSerials, StorageSerial};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+pub const HISTORY_FILE: &str = "part_history.json";
+
+#[derive(Serialize, Deserialize)]
+struct ScanRecord {
+    scanned_at: u64, // Unix seconds
+    serials: ComponentSerials,
+}
+
+pub struct PartChange {
+    pub part: &'static str, // as in `RefurbishmentCheck::replaced_parts`
+    pub previous: String,
+    pub current: String,
+}
+
+// Compares against the last scan of `device_key`, then records `current` as
+// the new last scan. The first scan of a machine finds nothing.
+pub fn changes_since_last_scan(
+    data_dir: &Path,
+    device_key: &str,
+    current: ComponentSerials,
+) -> Result<Vec<PartChange>, String> {
+    let path = data_dir.join(HISTORY_FILE);
+    // A history that can't be read is left alone rather than overwritten
+    let mut history: BTreeMap<String, ScanRecord> = match std::fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content).map_err(|e| e.to_string())?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+        Err(e) => return Err(e.to_string()),
+    };
+
+    let changes = history
+        .get(device_key)
+        .map(|record| compare(&record.serials, &current))
+        .unwrap_or_default();
+
+    let scanned_at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    history.insert(device_key.to_string(), ScanRecord { scanned_at, serials: current });
+    std::fs::create_dir_all(data_dir).map_err(|e| e.to_string())?;
+    let content = serde_json::to_string_pretty(&history).map_err(|e| e.to_string())?;
+    std::fs::write(&path, content).map_err(|e| e.to_string())?;
+    Ok(changes)
+}
+
+// A serial that couldn't be read on either scan (root-only on Linux, a
+// battery that stopped answering) is not a swap
+fn compare(previous: &ComponentSerials, current: &ComponentSerials) -> Vec<PartChange> {
+    let mut changes = vec![];
+    let parts = [
+        ("logic_board", &previous.logic_board, &current.logic_board),
+        ("battery", &previous.battery, &current.battery),
+        ("display", &previous.display, &current.display),
+        ("wifi", &previous.wifi_mac, &current.wifi_mac),
+    ];
+    for (part, previous, current) in parts {
+        if let (Some(previous), Some(current)) = (previous, current) {
+            if !previous.eq_ignore_ascii_case(current) {
+                changes.push(PartChange { part, previous: previous.clone(), current: current.clone() });
+            }
+        }
+    }
+
+    // An external disk coming or going is not a swap; a disk that disappeared
+    // while another one appeared is
+    let missing_from = |list: &[StorageSerial], disk: &StorageSerial| !list.iter().any(|d| d.serial == disk.serial);
+    let describe = |disks: Vec<&StorageSerial>| {
+        disks.iter().map(|d| format!("{} {}", d.model, d.serial).trim().to_string()).collect::<Vec<_>>().join(", ")
+    };
+    let removed: Vec<&StorageSerial> = previous.storage.iter().filter(|d| missing_from(&current.storage, d)).collect();
+    let added: Vec<&StorageSerial> = current.storage.iter().filter(|d| missing_from(&previous.storage, d)).collect();
+    if !removed.is_empty() && !added.is_empty() {
+        changes.push(PartChange { part: "storage", previous: describe(removed), current: describe(added) });
+    }
+    changes
+}
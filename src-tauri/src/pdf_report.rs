@@ -0,0 +1,527 @@
+// The printable certificate a shop hands the buyer (`export_report_pdf`),
+// rendered in the backend instead of screenshotting the report page. It runs
+// the full scan of export.rs and lays out the hardware summary, battery,
+// storage, the grade and the refurbishment verdict on A4, headed with the
+// shop's name and logo from the "branding" section of preferences.json;
+// the options the frontend passes take precedence.
+//
+// Chinese needs a TrueType font with CJK glyphs: the branding's font_path, or
+// one of the system fonts below. Without one the certificate falls back to
+// English and PDF's built-in Helvetica.
+
+use crate::error::QuickscanError;
+use crate::preferences::{self, Branding};
+use crate::probe_scheduler::ProbeScheduler;
+use printpdf::{BuiltinFont, Color, Image, ImageTransform, IndirectFontRef, Mm, PdfDocument, PdfDocumentReference, PdfLayerReference, Rgb};
+use serde::Deserialize;
+use serde_json::Value;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use tauri::Manager;
+
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+const MARGIN: f32 = 18.0;
+const LOGO_HEIGHT: f32 = 18.0;
+// Points to millimetres
+const PT: f32 = 0.3528;
+
+// .ttc collections can't be embedded, so only single-font files
+const SYSTEM_FONTS: &[&str] = &[
+    "/System/Library/Fonts/Supplemental/Arial Unicode.ttf",
+    "/Library/Fonts/Arial Unicode.ttf",
+    "C:\\Windows\\Fonts\\simhei.ttf",
+    "C:\\Windows\\Fonts\\simkai.ttf",
+    "/usr/share/fonts/truetype/droid/DroidSansFallbackFull.ttf",
+];
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct PdfOptions {
+    pub shop_name: Option<String>,
+    pub logo_path: Option<PathBuf>,
+    pub font_path: Option<PathBuf>,
+    pub language: Option<String>, // "zh…" for Chinese, English otherwise
+}
+
+struct Labels {
+    title: &'static str,
+    issued_by: &'static str,
+    scanned: &'static str,
+    machine: &'static str,
+    model: &'static str,
+    serial: &'static str,
+    system: &'static str,
+    processor: &'static str,
+    memory: &'static str,
+    grade: &'static str,
+    score: &'static str,
+    categories: [(&'static str, &'static str); 4], // grade.rs's category ids
+    confidence_levels: [(&'static str, &'static str); 3],
+    battery: &'static str,
+    no_battery: &'static str,
+    health: &'static str,
+    cycles: &'static str,
+    storage: &'static str,
+    power_on: &'static str,
+    wear: &'static str,
+    refurbishment: &'static str,
+    refurbished: &'static str,
+    not_refurbished: &'static str,
+    confidence: &'static str,
+    replaced_parts: &'static str,
+    findings: &'static str,
+    severities: [(&'static str, &'static str); 3],
+    // The indicator descriptions' keys, as in refurbishment.indicatorDesc of
+    // the frontend's translations
+    finding_texts: &'static [(&'static str, &'static str)],
+    unavailable: &'static str,
+    footer: &'static str,
+}
+
+const EN: Labels = Labels {
+    title: "Device Inspection Certificate",
+    issued_by: "Issued by",
+    scanned: "Inspected",
+    machine: "Machine",
+    model: "Model",
+    serial: "Serial number",
+    system: "System",
+    processor: "Processor",
+    memory: "Memory",
+    grade: "Grade",
+    score: "Score",
+    categories: [
+        ("battery", "Battery"),
+        ("storage", "Storage"),
+        ("cosmetic_proxy", "Wear and tear"),
+        ("hardware_integrity", "Hardware integrity"),
+    ],
+    confidence_levels: [("high", "high"), ("medium", "medium"), ("low", "low")],
+    battery: "Battery",
+    no_battery: "No battery",
+    health: "Health",
+    cycles: "Cycles",
+    storage: "Storage",
+    power_on: "Power-on hours",
+    wear: "Wear",
+    refurbishment: "Refurbishment",
+    refurbished: "Signs of refurbishment found",
+    not_refurbished: "No signs of refurbishment",
+    confidence: "Confidence",
+    replaced_parts: "Replaced parts",
+    findings: "Findings",
+    severities: [("info", "Info"), ("warning", "Warning"), ("critical", "Critical")],
+    finding_texts: &[
+        ("serial_starts_with_f", "Serial number starts with F, indicating Apple Certified Refurbished"),
+        ("firmware_refurb_flag", "Refurbishment flag found in system firmware"),
+        ("enterprise_managed_device", "Device was enterprise managed, may be a retired device"),
+        ("enterprise_dep_enrolled", "Device enrolled in DEP (Device Enrollment Program), likely enterprise device"),
+        ("enterprise_mdm_enrolled", "Device enrolled in MDM (Mobile Device Management), likely enterprise device"),
+        ("enterprise_dep_and_mdm", "Device enrolled in both DEP and MDM, confirmed enterprise managed"),
+        ("third_party_storage", "Non-original storage device detected"),
+        ("third_party_display", "Non-original display detected"),
+        ("bios_refurb_flag", "Refurbishment flag found in BIOS"),
+        ("oem_refurb_flag", "Refurbishment flag found in OEM information"),
+        ("custom_rules_invalid", "Custom rules file could not be loaded"),
+        ("custom_script_failed", "Custom check script failed"),
+        ("third_party_wifi", "Non-original Wi-Fi module detected"),
+        ("wifi_vendor_mismatch", "Wi-Fi MAC address vendor does not match the adapter"),
+        ("wifi_mac_locally_administered", "Wi-Fi MAC address is software-assigned, not the factory address"),
+        ("gpu_vram_below_spec", "GPU reports less video memory than this model shipped with"),
+        ("gpu_generic_driver", "GPU is running a generic fallback display driver"),
+        ("component_missing", "Expected component not detected"),
+        ("sip_disabled", "System Integrity Protection is disabled"),
+        ("secure_boot_lowered", "Secure Boot policy has been lowered"),
+        ("secure_boot_disabled", "Secure Boot is turned off"),
+        ("activation_lock_enabled", "Activation Lock is enabled; the Mac is tied to an Apple ID"),
+        ("find_my_enabled", "Find My Mac is turned on; the Mac may be activation-locked after a wipe"),
+        ("recommendation_policy_invalid", "Recommendation policy file could not be read"),
+        ("refurbisher_license", "Windows is licensed through a registered refurbisher"),
+        ("part_swapped_logic_board", "Logic board changed since the last scan (last scan / now)"),
+        ("part_swapped_battery", "Battery changed since the last scan (last scan / now)"),
+        ("part_swapped_storage", "Storage changed since the last scan (last scan / now)"),
+        ("part_swapped_display", "Display changed since the last scan (last scan / now)"),
+        ("part_swapped_wifi", "Wi-Fi card changed since the last scan (last scan / now)"),
+        ("part_history_invalid", "Scan history could not be read; parts were not compared with the last scan"),
+        ("bluetooth_pairings", "Bluetooth devices from a previous owner are still paired"),
+        ("board_serial_unprogrammed", "Logic board has no system serial number; it was replaced and never programmed"),
+        ("nvram_serial_mismatch", "Serial number in NVRAM does not match the system serial number"),
+        ("chassis_serial_mismatch", "System serial number does not match the chassis serial number"),
+        ("board_service_tag_mismatch", "Logic board was made for a different service tag than this machine's"),
+        ("keyboard_hardware_region", "Built-in keyboard layout does not match the claimed country of sale (claimed / keyboard)"),
+        ("os_region_mismatch", "Regional settings point at a different country than claimed (claimed / settings)"),
+        ("keyboard_layout_region", "Installed keyboard layouts belong to a different market than claimed (claimed / layouts)"),
+        ("third_party_battery", "Battery pack from a maker that does not supply laptop manufacturers"),
+        ("third_party_keyboard", "Non-original keyboard detected"),
+        ("battery_newer_than_chassis", "Battery was made more than a year after the machine (battery / machine)"),
+        ("recent_os_install", "Operating system freshly installed"),
+        ("previous_owner_data", "Data from a previous owner is still on the machine"),
+        ("personal_account", "Still signed in to a personal account"),
+        ("work_account", "Still tied to a work or school directory"),
+        ("clock_unreliable_epoch", "System clock reads a date before 2000; it was reset"),
+        ("clock_unreliable_before_firmware", "System clock reads a date before this machine's firmware was released"),
+        ("clock_unreliable_y2038", "System clock reads a date past January 2038"),
+        ("clock_unreliable_ntp_offset", "System clock is more than a day off from internet time"),
+    ],
+    unavailable: "Not available",
+    footer: "Generated by QuickScan",
+};
+
+const ZH: Labels = Labels {
+    title: "设备检测证书",
+    issued_by: "出具方",
+    scanned: "检测时间",
+    machine: "机器信息",
+    model: "型号",
+    serial: "序列号",
+    system: "系统",
+    processor: "处理器",
+    memory: "内存",
+    grade: "成色等级",
+    score: "评分",
+    categories: [("battery", "电池"), ("storage", "硬盘"), ("cosmetic_proxy", "使用痕迹"), ("hardware_integrity", "硬件完整性")],
+    confidence_levels: [("high", "高"), ("medium", "中"), ("low", "低")],
+    battery: "电池",
+    no_battery: "无电池",
+    health: "健康度",
+    cycles: "循环次数",
+    storage: "硬盘",
+    power_on: "通电时长（小时）",
+    wear: "磨损",
+    refurbishment: "翻新检测",
+    refurbished: "发现翻新迹象",
+    not_refurbished: "未发现翻新迹象",
+    confidence: "置信度",
+    replaced_parts: "更换部件",
+    findings: "检测发现",
+    severities: [("info", "信息"), ("warning", "警告"), ("critical", "严重")],
+    finding_texts: &[
+        ("serial_starts_with_f", "序列号以 F 开头，表示 Apple 官方翻新机"),
+        ("firmware_refurb_flag", "系统固件中发现翻新标记"),
+        ("enterprise_managed_device", "设备曾被企业管理，可能是退役设备"),
+        ("enterprise_dep_enrolled", "设备已加入 DEP（设备注册计划），可能是企业设备"),
+        ("enterprise_mdm_enrolled", "设备已加入 MDM（移动设备管理），可能是企业设备"),
+        ("enterprise_dep_and_mdm", "设备同时加入 DEP 和 MDM，确认为企业管理设备"),
+        ("third_party_storage", "检测到非原装存储设备"),
+        ("third_party_display", "检测到非原装显示屏"),
+        ("bios_refurb_flag", "BIOS 中发现翻新标记"),
+        ("oem_refurb_flag", "OEM 信息中发现翻新标记"),
+        ("custom_rules_invalid", "自定义规则文件加载失败"),
+        ("custom_script_failed", "自定义检测脚本运行失败"),
+        ("third_party_wifi", "检测到非原装无线网卡"),
+        ("wifi_vendor_mismatch", "无线网卡 MAC 地址厂商与网卡型号不符"),
+        ("wifi_mac_locally_administered", "无线网卡 MAC 地址为软件设置，并非出厂地址"),
+        ("gpu_vram_below_spec", "GPU 报告的显存低于该型号的出厂配置"),
+        ("gpu_generic_driver", "GPU 正在使用通用备用显示驱动"),
+        ("component_missing", "未检测到该型号应有的组件"),
+        ("sip_disabled", "系统完整性保护（SIP）已关闭"),
+        ("secure_boot_lowered", "安全启动策略已被降低"),
+        ("secure_boot_disabled", "安全启动已关闭"),
+        ("activation_lock_enabled", "激活锁已开启，此 Mac 绑定了 Apple ID"),
+        ("find_my_enabled", "“查找我的 Mac”已开启，抹掉后可能被激活锁锁定"),
+        ("recommendation_policy_invalid", "无法读取处置建议策略文件"),
+        ("refurbisher_license", "Windows 通过注册翻新商授权"),
+        ("part_swapped_logic_board", "主板自上次检测后已更换（上次 / 现在）"),
+        ("part_swapped_battery", "电池自上次检测后已更换（上次 / 现在）"),
+        ("part_swapped_storage", "存储设备自上次检测后已更换（上次 / 现在）"),
+        ("part_swapped_display", "屏幕自上次检测后已更换（上次 / 现在）"),
+        ("part_swapped_wifi", "Wi-Fi 网卡自上次检测后已更换（上次 / 现在）"),
+        ("part_history_invalid", "无法读取检测历史，未与上次检测对比部件"),
+        ("bluetooth_pairings", "仍保留着前任机主配对的蓝牙设备"),
+        ("board_serial_unprogrammed", "主板没有写入整机序列号，更换后未重新编程"),
+        ("nvram_serial_mismatch", "NVRAM 中的序列号与整机序列号不一致"),
+        ("chassis_serial_mismatch", "整机序列号与机箱序列号不一致"),
+        ("board_service_tag_mismatch", "主板所属的服务编号与本机不一致"),
+        ("keyboard_hardware_region", "内置键盘布局与声称的销售国家不符（声称 / 键盘）"),
+        ("os_region_mismatch", "地区设置指向与声称不同的国家（声称 / 设置）"),
+        ("keyboard_layout_region", "已安装的键盘布局属于与声称不同的市场（声称 / 布局）"),
+        ("third_party_battery", "电池组来自不为笔记本厂商供货的制造商"),
+        ("third_party_keyboard", "检测到非原装键盘"),
+        ("battery_newer_than_chassis", "电池生产日期比整机晚一年以上（电池 / 整机）"),
+        ("recent_os_install", "操作系统为新近安装"),
+        ("previous_owner_data", "机器上仍有前任用户的数据"),
+        ("personal_account", "仍登录着个人账户"),
+        ("work_account", "仍绑定工作或学校目录"),
+        ("clock_unreliable_epoch", "系统时钟显示 2000 年以前的日期，已被重置"),
+        ("clock_unreliable_before_firmware", "系统时钟显示的日期早于本机固件发布日期"),
+        ("clock_unreliable_y2038", "系统时钟显示的日期晚于 2038 年 1 月"),
+        ("clock_unreliable_ntp_offset", "系统时钟与网络时间相差超过一天"),
+    ],
+    unavailable: "无法读取",
+    footer: "由秒验 QuickScan 生成",
+};
+
+#[tauri::command]
+pub async fn export_report_pdf(app: tauri::AppHandle, probes: tauri::State<'_, ProbeScheduler>, path: PathBuf, options: PdfOptions) -> Result<(), QuickscanError> {
+    probes.run(move || {
+        let branding = app
+            .path()
+            .app_config_dir()
+            .ok()
+            .and_then(|dir| preferences::load_preferences(&dir.join(preferences::PREFERENCES_FILE)).ok())
+            .map(|preferences| preferences.branding)
+            .unwrap_or_default();
+        let branding = Branding {
+            shop_name: options.shop_name.or(branding.shop_name).filter(|name| !name.trim().is_empty()),
+            logo_path: options.logo_path.or(branding.logo_path),
+            font_path: options.font_path.or(branding.font_path),
+        };
+        let chinese = options.language.is_some_and(|language| language.starts_with("zh"));
+        let document = serde_json::to_value(crate::export::collect(&app)).map_err(|e| QuickscanError::parse("report", e))?;
+        render(&document, &branding, chinese, &path).map_err(|e| QuickscanError::command_failed(&path.display().to_string(), e))
+    })
+    .await
+}
+
+// Lays out text top to bottom, starting a new page when it runs out of room
+struct Writer {
+    doc: PdfDocumentReference,
+    layer: PdfLayerReference,
+    font: IndirectFontRef,
+    bold: IndirectFontRef,
+    wide: bool, // the font has CJK glyphs, which are as wide as they are tall
+    y: f32,
+}
+
+impl Writer {
+    fn ensure(&mut self, height: f32) {
+        if self.y - height >= MARGIN {
+            return;
+        }
+        let (page, layer) = self.doc.add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Layer 1");
+        self.layer = self.doc.get_page(page).get_layer(layer);
+        self.y = PAGE_HEIGHT - MARGIN;
+    }
+
+    fn text(&mut self, text: &str, size: f32, bold: bool, color: (f32, f32, f32)) {
+        let line_height = size * PT * 1.5;
+        let font = if bold { self.bold.clone() } else { self.font.clone() };
+        for line in wrap(text, size, self.wide) {
+            self.ensure(line_height);
+            self.y -= line_height;
+            self.layer.set_fill_color(Color::Rgb(Rgb::new(color.0, color.1, color.2, None)));
+            self.layer.use_text(line, size, Mm(MARGIN), Mm(self.y), &font);
+        }
+    }
+
+    fn heading(&mut self, text: &str) {
+        self.y -= 4.0;
+        self.ensure(14.0);
+        self.text(text, 13.0, true, (0.12, 0.16, 0.22));
+    }
+
+    fn row(&mut self, label: &str, value: &str) {
+        self.text(&format!("{}: {}", label, value), 10.0, false, (0.22, 0.25, 0.32));
+    }
+}
+
+// Breaks a line to the text width, counting a CJK character as a full em and
+// anything else as about half of one
+fn wrap(text: &str, size: f32, wide: bool) -> Vec<String> {
+    let max_ems = (PAGE_WIDTH - 2.0 * MARGIN) / (size * PT);
+    let mut lines = vec![];
+    let mut line = String::new();
+    let mut ems = 0.0;
+    for c in text.chars() {
+        let width = if wide && !c.is_ascii() { 1.0 } else { 0.55 };
+        if ems + width > max_ems && !line.is_empty() {
+            lines.push(std::mem::take(&mut line));
+            ems = 0.0;
+        }
+        line.push(c);
+        ems += width;
+    }
+    lines.push(line);
+    lines
+}
+
+fn load_font(doc: &PdfDocumentReference, branding: &Branding) -> Option<IndirectFontRef> {
+    let configured = branding.font_path.iter().map(PathBuf::as_path);
+    let system = SYSTEM_FONTS.iter().map(Path::new);
+    configured
+        .chain(system)
+        .filter(|path| path.exists())
+        .find_map(|path| File::open(path).ok().and_then(|file| doc.add_external_font(file).ok()))
+}
+
+fn render(document: &Value, branding: &Branding, chinese: bool, path: &Path) -> Result<(), String> {
+    let (doc, page, layer) = PdfDocument::new(EN.title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Layer 1");
+    let layer = doc.get_page(page).get_layer(layer);
+
+    let external = if chinese { load_font(&doc, branding) } else { None };
+    let wide = external.is_some();
+    let labels = if wide { &ZH } else { &EN };
+    let (font, bold) = match external {
+        Some(font) => (font.clone(), font),
+        None => (
+            doc.add_builtin_font(BuiltinFont::Helvetica).map_err(|e| e.to_string())?,
+            doc.add_builtin_font(BuiltinFont::HelveticaBold).map_err(|e| e.to_string())?,
+        ),
+    };
+    let mut writer = Writer { doc, layer, font, bold, wide, y: PAGE_HEIGHT - MARGIN };
+
+    // A logo that can't be read leaves the header to the shop's name
+    if let Some(logo) = branding.logo_path.as_ref().and_then(|logo| printpdf::image_crate::open(logo).ok()) {
+        let height_px = logo.height().max(1) as f32;
+        // At 300 dpi a pixel is 25.4 / 300 mm
+        let scale = LOGO_HEIGHT / (height_px * 25.4 / 300.0);
+        writer.y -= LOGO_HEIGHT;
+        Image::from_dynamic_image(&logo).add_to_layer(
+            writer.layer.clone(),
+            ImageTransform {
+                translate_x: Some(Mm(MARGIN)),
+                translate_y: Some(Mm(writer.y)),
+                scale_x: Some(scale),
+                scale_y: Some(scale),
+                dpi: Some(300.0),
+                ..Default::default()
+            },
+        );
+        writer.y -= 2.0;
+    }
+    if let Some(shop_name) = &branding.shop_name {
+        writer.text(shop_name, 16.0, true, (0.12, 0.16, 0.22));
+    }
+    writer.text(labels.title, 20.0, true, (0.15, 0.39, 0.92));
+    writer.row(labels.scanned, document["scanned_at"].as_str().unwrap_or_default());
+    if let Some(shop_name) = &branding.shop_name {
+        writer.row(labels.issued_by, shop_name);
+    }
+
+    let checks = &document["checks"];
+    machine(&mut writer, labels, document, checks);
+    grade(&mut writer, labels, checks);
+    battery(&mut writer, labels, &checks["battery"]);
+    storage(&mut writer, labels, &checks["storage"]);
+    refurbishment(&mut writer, labels, &checks["refurbishment"]);
+
+    writer.y -= 6.0;
+    let footer = format!("{} {}", labels.footer, document["app_version"].as_str().unwrap_or_default());
+    writer.text(&footer, 8.0, false, (0.42, 0.45, 0.5));
+
+    let file = File::create(path).map_err(|e| e.to_string())?;
+    writer.doc.save(&mut BufWriter::new(file)).map_err(|e| e.to_string())
+}
+
+fn translate(pairs: &[(&'static str, &'static str)], id: &str) -> String {
+    pairs.iter().find(|(key, _)| *key == id).map_or(id.to_string(), |(_, text)| text.to_string())
+}
+
+// "key:value" descriptions keep their value after the translated key; only
+// the first colon separates them (MAC addresses hold more)
+fn finding(labels: &Labels, description: &str) -> String {
+    match description.split_once(':') {
+        Some((key, value)) => format!("{}: {}", translate(labels.finding_texts, key), value),
+        None => translate(labels.finding_texts, description),
+    }
+}
+
+fn failed(check: &Value) -> bool {
+    check.is_null() || check.get("error").is_some()
+}
+
+fn machine(writer: &mut Writer, labels: &Labels, document: &Value, checks: &Value) {
+    writer.heading(labels.machine);
+    let model = &checks["model"];
+    let name = model["marketing_name"].as_str().or(model["identifier"].as_str()).unwrap_or(labels.unavailable);
+    writer.row(labels.model, name);
+    writer.row(labels.serial, document["machine"]["serial_number"].as_str().unwrap_or(labels.unavailable));
+    let hardware = &checks["hardware"];
+    if !failed(hardware) {
+        let system = format!("{} {}", hardware["os_name"].as_str().unwrap_or_default(), hardware["os_version"].as_str().unwrap_or_default());
+        writer.row(labels.system, system.trim());
+        writer.row(labels.processor, hardware["cpu"]["model"].as_str().unwrap_or(labels.unavailable));
+        if let Some(total) = hardware["memory"]["total"].as_u64() {
+            writer.row(labels.memory, &format!("{:.0} GB", total as f64 / 1_073_741_824.0));
+        }
+    }
+}
+
+fn grade(writer: &mut Writer, labels: &Labels, checks: &Value) {
+    let grade = crate::grade::compute_device_grade(crate::grade::GradeInput::from_checks(checks));
+    let Ok(grade) = serde_json::to_value(grade) else {
+        return;
+    };
+    writer.heading(labels.grade);
+    let letter = grade["grade"].as_str().unwrap_or_default();
+    writer.text(&format!("{}   {} {}/100", letter, labels.score, grade["score"]), 16.0, true, (0.12, 0.16, 0.22));
+    for category in grade["categories"].as_array().into_iter().flatten() {
+        let name = translate(&labels.categories, category["category"].as_str().unwrap_or_default());
+        writer.row(&name, &format!("{}/100", category["score"]));
+    }
+}
+
+fn battery(writer: &mut Writer, labels: &Labels, battery: &Value) {
+    writer.heading(labels.battery);
+    if battery.is_null() {
+        writer.row(labels.battery, labels.no_battery);
+        return;
+    }
+    if failed(battery) {
+        writer.row(labels.battery, labels.unavailable);
+        return;
+    }
+    if let Some(health) = battery["health"].as_f64() {
+        writer.row(labels.health, &format!("{:.0}%", health));
+    }
+    if let Some(cycles) = battery["cycle_count"].as_u64() {
+        writer.row(labels.cycles, &cycles.to_string());
+    }
+}
+
+fn storage(writer: &mut Writer, labels: &Labels, storage: &Value) {
+    writer.heading(labels.storage);
+    let Some(disks) = storage.as_array().filter(|disks| !disks.is_empty()) else {
+        writer.row(labels.storage, labels.unavailable);
+        return;
+    };
+    for disk in disks {
+        let mut line = format!("{} · SMART {}", disk["model"].as_str().unwrap_or_default(), disk["smart_status"].as_str().unwrap_or_default());
+        if let Some(hours) = disk["power_on_hours"].as_u64() {
+            line.push_str(&format!(" · {} {}", labels.power_on, hours));
+        }
+        if let Some(used) = disk["smart"]["percentage_used"].as_u64() {
+            line.push_str(&format!(" · {} {}%", labels.wear, used));
+        }
+        writer.text(&line, 10.0, false, (0.22, 0.25, 0.32));
+    }
+}
+
+fn refurbishment(writer: &mut Writer, labels: &Labels, refurbishment: &Value) {
+    writer.heading(labels.refurbishment);
+    if failed(refurbishment) {
+        writer.row(labels.refurbishment, labels.unavailable);
+        return;
+    }
+    let (verdict, color) = if refurbishment["is_refurbished"].as_bool() == Some(true) {
+        (labels.refurbished, (0.85, 0.47, 0.02))
+    } else {
+        (labels.not_refurbished, (0.09, 0.64, 0.29))
+    };
+    writer.text(verdict, 12.0, true, color);
+    let level = translate(&labels.confidence_levels, refurbishment["confidence"].as_str().unwrap_or_default());
+    let confidence = format!("{} ({}/100)", level, refurbishment["confidence_score"]);
+    writer.row(labels.confidence, &confidence);
+    let parts: Vec<&str> = refurbishment["replaced_parts"].as_array().into_iter().flatten().filter_map(Value::as_str).collect();
+    if !parts.is_empty() {
+        writer.row(labels.replaced_parts, &parts.join(", "));
+    }
+    let findings: Vec<String> = refurbishment["indicators"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|indicator| indicator["detected"].as_bool() == Some(true))
+        .map(|indicator| {
+            let severity = translate(&labels.severities, indicator["severity"].as_str().unwrap_or_default());
+            format!("[{}] {}", severity, finding(labels, indicator["description"].as_str().unwrap_or_default()))
+        })
+        .collect();
+    if !findings.is_empty() {
+        writer.text(labels.findings, 10.0, true, (0.22, 0.25, 0.32));
+        for finding in findings {
+            writer.text(&finding, 9.0, false, (0.22, 0.25, 0.32));
+        }
+    }
+}
@@ -0,0 +1,154 @@
+// Assisted dead/stuck pixel detection from a photo of the screen test pattern.
+// One machine shows a solid color full screen, another camera or phone takes a
+// photo, and the photo is analyzed here. Small spots that differ sharply from an
+// otherwise uniform neighborhood are returned as candidates for the technician
+// to confirm on the real screen.
+
+use crate::error::QuickscanError;
+use image::imageops::FilterType;
+use image::ImageError;
+use serde::Serialize;
+use std::collections::VecDeque;
+
+// Larger photos are scaled down first; a stuck pixel still spans a few photo pixels
+const MAX_DIMENSION: u32 = 3000;
+const WINDOW_RADIUS: u32 = 15;
+const MIN_DEVIATION: f64 = 40.0;
+const MAX_NEIGHBORHOOD_STD_DEV: f64 = 18.0;
+const MAX_BLOB_PIXELS: usize = 400;
+const MAX_CANDIDATES: usize = 50;
+
+#[derive(Serialize)]
+pub struct PixelDefectCandidate {
+    pub x: u32,
+    pub y: u32,
+    pub relative_x: f64,
+    pub relative_y: f64,
+    pub size_px: usize,
+    pub deviation: f64, // positive = brighter than surroundings (stuck), negative = darker (dead)
+}
+
+#[derive(Serialize)]
+pub struct PixelDefectAnalysis {
+    pub width: u32,
+    pub height: u32,
+    pub candidates: Vec<PixelDefectCandidate>,
+}
+
+#[tauri::command]
+pub async fn analyze_pixel_defects(image_path: String) -> Result<PixelDefectAnalysis, QuickscanError> {
+    crate::process::blocking(move || analyze(&image_path)).await
+}
+
+fn analyze(image_path: &str) -> Result<PixelDefectAnalysis, QuickscanError> {
+    // A file that can't be read is an I/O error; one that can but isn't a
+    // photo we can decode is a parse error
+    let image = image::open(image_path).map_err(|e| match e {
+        ImageError::IoError(e) => QuickscanError::io(image_path, e),
+        other => QuickscanError::parse(image_path, other),
+    })?;
+    let image = if image.width().max(image.height()) > MAX_DIMENSION {
+        image.resize(MAX_DIMENSION, MAX_DIMENSION, FilterType::Triangle)
+    } else {
+        image
+    };
+    let gray = image.to_luma8();
+    let (width, height) = gray.dimensions();
+    let deviations = local_deviations(gray.as_raw(), width as usize, height as usize);
+
+    let mut candidates = find_blobs(&deviations, width as usize, height as usize);
+    candidates.sort_by(|a, b| b.deviation.abs().total_cmp(&a.deviation.abs()));
+    candidates.truncate(MAX_CANDIDATES);
+
+    Ok(PixelDefectAnalysis { width, height, candidates })
+}
+
+// Deviation of each pixel from its neighborhood mean, or None where the
+// neighborhood isn't uniform enough to judge (edges, bezels, reflections).
+fn local_deviations(pixels: &[u8], width: usize, height: usize) -> Vec<Option<f64>> {
+    let stride = width + 1;
+    let mut sum = vec![0u64; stride * (height + 1)];
+    let mut sum_sq = vec![0u64; stride * (height + 1)];
+    for y in 0..height {
+        let (mut row_sum, mut row_sq) = (0u64, 0u64);
+        for x in 0..width {
+            let v = pixels[y * width + x] as u64;
+            row_sum += v;
+            row_sq += v * v;
+            sum[(y + 1) * stride + x + 1] = sum[y * stride + x + 1] + row_sum;
+            sum_sq[(y + 1) * stride + x + 1] = sum_sq[y * stride + x + 1] + row_sq;
+        }
+    }
+    let window = |table: &[u64], x0: usize, y0: usize, x1: usize, y1: usize| {
+        (table[y1 * stride + x1] + table[y0 * stride + x0] - table[y0 * stride + x1] - table[y1 * stride + x0]) as f64
+    };
+
+    let r = WINDOW_RADIUS as usize;
+    let mut deviations = vec![None; width * height];
+    if width <= 2 * r || height <= 2 * r {
+        return deviations;
+    }
+    for y in r..height - r {
+        for x in r..width - r {
+            let (x0, y0, x1, y1) = (x - r, y - r, x + r + 1, y + r + 1);
+            let count = ((2 * r + 1) * (2 * r + 1)) as f64;
+            let mean = window(&sum, x0, y0, x1, y1) / count;
+            let variance = (window(&sum_sq, x0, y0, x1, y1) / count - mean * mean).max(0.0);
+            if variance.sqrt() <= MAX_NEIGHBORHOOD_STD_DEV {
+                deviations[y * width + x] = Some(pixels[y * width + x] as f64 - mean);
+            }
+        }
+    }
+    deviations
+}
+
+fn find_blobs(deviations: &[Option<f64>], width: usize, height: usize) -> Vec<PixelDefectCandidate> {
+    let flagged = |i: usize| deviations[i].is_some_and(|d| d.abs() >= MIN_DEVIATION);
+    let mut visited = vec![false; deviations.len()];
+    let mut candidates = vec![];
+
+    for start in 0..deviations.len() {
+        if visited[start] || !flagged(start) {
+            continue;
+        }
+        let mut queue = VecDeque::from([start]);
+        visited[start] = true;
+        let (mut count, mut sum_x, mut sum_y, mut peak) = (0usize, 0usize, 0usize, 0.0f64);
+
+        while let Some(i) = queue.pop_front() {
+            let (x, y) = (i % width, i / width);
+            count += 1;
+            sum_x += x;
+            sum_y += y;
+            let d = deviations[i].unwrap_or(0.0);
+            if d.abs() > peak.abs() {
+                peak = d;
+            }
+            let neighbors = [
+                (x > 0).then(|| i - 1),
+                (x + 1 < width).then(|| i + 1),
+                (y > 0).then(|| i - width),
+                (y + 1 < height).then(|| i + width),
+            ];
+            for n in neighbors.into_iter().flatten() {
+                if !visited[n] && flagged(n) {
+                    visited[n] = true;
+                    queue.push_back(n);
+                }
+            }
+        }
+
+        if count <= MAX_BLOB_PIXELS {
+            let (cx, cy) = (sum_x / count, sum_y / count);
+            candidates.push(PixelDefectCandidate {
+                x: cx as u32,
+                y: cy as u32,
+                relative_x: cx as f64 / width as f64,
+                relative_y: cy as f64 / height as f64,
+                size_px: count,
+                deviation: peak,
+            });
+        }
+    }
+    candidates
+}
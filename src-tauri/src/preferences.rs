@@ -0,0 +1,109 @@
+// Display units, number locale and what gets kept on disk, set per shop in
+// `preferences.json` in the app config directory:
+//
+// { "byte_units": "decimal", "temperature": "fahrenheit", "locale": "de-DE", "store_raw_identifiers": false,
+//   "evidence_mode": true, "branding": { "shop_name": "Example Refurb", "logo_path": "/path/to/logo.png" } }
+//
+// Only formatted output follows the units; the report JSON keeps raw bytes and
+// degrees Celsius so exports from different shops stay comparable.
+
+use crate::error::QuickscanError;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tauri::Manager;
+
+pub const PREFERENCES_FILE: &str = "preferences.json";
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ByteUnits {
+    #[default]
+    Binary, // 1024-based, GiB
+    Decimal, // 1000-based, GB, as drive makers label them
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TemperatureUnit {
+    #[default]
+    Celsius,
+    Fahrenheit,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Preferences {
+    #[serde(default)]
+    pub byte_units: ByteUnits,
+    #[serde(default)]
+    pub temperature: TemperatureUnit,
+    #[serde(default)]
+    pub locale: Option<String>, // BCP 47 tag; the UI language when unset
+    // When false the part history and scan log key machines by a hashed
+    // fingerprint instead of their serials (see fingerprint.rs)
+    #[serde(default = "default_store_raw_identifiers")]
+    pub store_raw_identifiers: bool,
+    // Keep every tool's raw output with the scan (see evidence.rs)
+    #[serde(default)]
+    pub evidence_mode: bool,
+    #[serde(default)]
+    pub branding: Branding,
+}
+
+// What the PDF certificate is headed with (see pdf_report.rs)
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct Branding {
+    pub shop_name: Option<String>,
+    pub logo_path: Option<PathBuf>, // PNG or JPEG
+    pub font_path: Option<PathBuf>, // a TrueType font with CJK glyphs; a system one is looked for otherwise
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Preferences {
+            byte_units: ByteUnits::default(),
+            temperature: TemperatureUnit::default(),
+            locale: None,
+            store_raw_identifiers: true,
+            evidence_mode: false,
+            branding: Branding::default(),
+        }
+    }
+}
+
+fn default_store_raw_identifiers() -> bool {
+    true
+}
+
+pub fn load_preferences(path: &Path) -> Result<Preferences, QuickscanError> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Preferences::default()),
+        Err(e) => return Err(QuickscanError::io(PREFERENCES_FILE, e)),
+    };
+    serde_json::from_str(&content).map_err(|e| QuickscanError::parse(PREFERENCES_FILE, e))
+}
+
+#[tauri::command]
+pub fn get_preferences(app: tauri::AppHandle) -> Result<Preferences, QuickscanError> {
+    let config_dir = app.path().app_config_dir().map_err(|e| QuickscanError::command_failed(PREFERENCES_FILE, e))?;
+    load_preferences(&config_dir.join(PREFERENCES_FILE))
+}
+
+// Unreadable preferences keep raw identifiers, as before the setting existed
+pub fn store_raw_identifiers(app: &tauri::AppHandle) -> bool {
+    app.path()
+        .app_config_dir()
+        .ok()
+        .and_then(|dir| load_preferences(&dir.join(PREFERENCES_FILE)).ok())
+        .is_none_or(|preferences| preferences.store_raw_identifiers)
+}
+
+// Unreadable preferences record no evidence
+pub fn evidence_mode(app: &tauri::AppHandle) -> bool {
+    app.path()
+        .app_config_dir()
+        .ok()
+        .and_then(|dir| load_preferences(&dir.join(PREFERENCES_FILE)).ok())
+        .is_some_and(|preferences| preferences.evidence_mode)
+}
@@ -0,0 +1,182 @@
+// Keeps probes from piling up when the UI fires several expensive checks at
+// once, say an operator clicking through the report while the scan is still
+// running:
+//
+// - at most MAX_PROCESSES probes run at a time; the rest wait their turn in
+//   the order they asked
+// - a probe asking for the exact command line another probe is already
+//   running waits for that run and gets a copy of its output, rather than
+//   starting a second system_profiler
+//
+// The scheduler is Tauri managed state. A probe command takes it as
+// `State<'_, ProbeScheduler>` and runs its work with `probes.run(...)`, which
+// makes it the current scheduler of the blocking thread; process::run_probe,
+// several calls further down, queues on whichever scheduler is current. The
+// headless and lab binaries have no app to manage it and scan one probe at
+// a time, so their probes run unqueued.
+//
+// Only process::run_probe (and run_cmd) goes through here. Hooks, uploads,
+// playback and colorimeter readings have side effects or wait on the
+// operator, so they run on their own and never take a probe's slot. Windows
+// probes go through WMI, whose single worker thread already queues them:
+// commands take the same state there, but nothing waits on it.
+#![cfg_attr(target_os = "windows", allow(dead_code))]
+
+use crate::error::QuickscanError;
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::process::Output;
+use std::sync::{Arc, Condvar, Mutex};
+
+const MAX_PROCESSES: usize = 3;
+
+thread_local! {
+    static CURRENT: RefCell<Option<ProbeScheduler>> = const { RefCell::new(None) };
+}
+
+#[derive(Default)]
+struct Slots {
+    running: usize,
+    queue: VecDeque<u64>, // tickets of those waiting, oldest first
+    next_ticket: u64,
+}
+
+// One run's output, for whoever asked for the same command line meanwhile
+#[derive(Default)]
+struct Shared {
+    result: Mutex<Option<Result<Output, QuickscanError>>>,
+    done: Condvar,
+}
+
+// A handle; clones share the same slots and in-flight runs
+#[derive(Clone, Default)]
+pub struct ProbeScheduler {
+    queue: Arc<Queue>,
+}
+
+#[derive(Default)]
+struct Queue {
+    slots: Mutex<Slots>,
+    freed: Condvar,
+    in_flight: Mutex<HashMap<String, Arc<Shared>>>,
+}
+
+// Frees the slot when the process is done with it
+struct Slot<'a> {
+    scheduler: &'a Queue,
+}
+
+impl Drop for Slot<'_> {
+    fn drop(&mut self) {
+        self.scheduler.slots.lock().unwrap().running -= 1;
+        self.scheduler.freed.notify_all();
+    }
+}
+
+// Publishes the run's output and takes it off the in-flight list, also when
+// the run panicked, so nobody waits on it forever
+struct Leader<'a> {
+    scheduler: &'a Queue,
+    key: String,
+    shared: Arc<Shared>,
+}
+
+impl Drop for Leader<'_> {
+    fn drop(&mut self) {
+        self.scheduler.in_flight.lock().unwrap().remove(&self.key);
+        let mut result = self.shared.result.lock().unwrap();
+        if result.is_none() {
+            *result = Some(Err(QuickscanError::command_failed(&self.key, "the run panicked")));
+        }
+        self.shared.done.notify_all();
+    }
+}
+
+// Puts the thread's previous scheduler back, also when the work panicked
+struct Entered(Option<ProbeScheduler>);
+
+impl Drop for Entered {
+    fn drop(&mut self) {
+        CURRENT.with(|current| *current.borrow_mut() = self.0.take());
+    }
+}
+
+impl ProbeScheduler {
+    // Runs a command's work on the blocking pool with this scheduler current
+    pub async fn run<T, F>(&self, work: F) -> T
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let scheduler = self.clone();
+        crate::process::blocking(move || scheduler.enter(work)).await
+    }
+
+    // Makes this the current scheduler while `work` runs, for work that
+    // starts a thread of its own (monitors, watches)
+    pub fn enter<T>(self, work: impl FnOnce() -> T) -> T {
+        let _entered = Entered(CURRENT.with(|current| current.replace(Some(self))));
+        work()
+    }
+
+    pub fn current() -> Option<ProbeScheduler> {
+        CURRENT.with(|current| current.borrow().clone())
+    }
+
+    // Runs `run` in one of the slots, or copies the output of a run of the
+    // same command line already in flight
+    pub fn probe(&self, key: String, run: impl FnOnce() -> Result<Output, QuickscanError>) -> Result<Output, QuickscanError> {
+        let queue = &self.queue;
+        queue.shared(key, || {
+            let _slot = queue.slot();
+            run()
+        })
+    }
+}
+
+impl Queue {
+    // Waits until a process may start and it's this caller's turn
+    fn slot(&self) -> Slot<'_> {
+        let mut slots = self.slots.lock().unwrap();
+        let ticket = slots.next_ticket;
+        slots.next_ticket += 1;
+        slots.queue.push_back(ticket);
+        while slots.running >= MAX_PROCESSES || slots.queue.front() != Some(&ticket) {
+            slots = self.freed.wait(slots).unwrap();
+        }
+        slots.queue.pop_front();
+        slots.running += 1;
+        drop(slots);
+        // The next in line may fit as well
+        self.freed.notify_all();
+        Slot { scheduler: self }
+    }
+
+    // Runs `run` unless a run for the same key is in flight, in which case
+    // its output is waited for and copied
+    fn shared(&self, key: String, run: impl FnOnce() -> Result<Output, QuickscanError>) -> Result<Output, QuickscanError> {
+        let (shared, leading) = {
+            let mut in_flight = self.in_flight.lock().unwrap();
+            match in_flight.get(&key) {
+                Some(shared) => (shared.clone(), false),
+                None => {
+                    let shared = Arc::new(Shared::default());
+                    in_flight.insert(key.clone(), shared.clone());
+                    (shared, true)
+                }
+            }
+        };
+        if !leading {
+            let mut result = shared.result.lock().unwrap();
+            while result.is_none() {
+                result = shared.done.wait(result).unwrap();
+            }
+            return result.clone().unwrap_or_else(|| Err(QuickscanError::command_failed(&key, "no output")));
+        }
+
+        let leader = Leader { scheduler: self, key, shared };
+        let result = run();
+        *leader.shared.result.lock().unwrap() = Some(result.clone());
+        result
+    }
+}
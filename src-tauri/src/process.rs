@@ -0,0 +1,125 @@
+// External commands with a deadline. system_profiler and friends can hang for
+// 10s+ on a busy or failing machine; a stuck child is killed and reported as
+// a timeout instead of holding up the scan. The watchdog does the same for a
+// child that runs away with memory or CPU.
+
+use crate::error::QuickscanError;
+#[cfg(not(target_os = "windows"))]
+use crate::probe_scheduler::ProbeScheduler;
+use crate::watchdog::Watchdog;
+use std::io::Read;
+use std::process::{Command, Output, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// Windows probes go through WMI; only hooks spawn processes there
+#[cfg(not(target_os = "windows"))]
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(15);
+
+const POLL_INTERVAL: Duration = Duration::from_millis(25);
+
+#[cfg(not(target_os = "windows"))]
+pub fn run_cmd(command: &mut Command) -> Result<Output, QuickscanError> {
+    run_probe(command, DEFAULT_TIMEOUT)
+}
+
+// Like run_cmd_with_timeout, but waits for one of the probes' slots, and a
+// probe asking for a command line that is already running gets a copy of
+// that run's output (see probe_scheduler.rs). Only for commands that just
+// read something. Outside a command's ProbeScheduler::run it runs unqueued.
+#[cfg(not(target_os = "windows"))]
+pub fn run_probe(command: &mut Command, timeout: Duration) -> Result<Output, QuickscanError> {
+    let Some(scheduler) = ProbeScheduler::current() else {
+        return run_cmd_with_timeout(command, timeout);
+    };
+    let key = format!("{:?}", command);
+    scheduler.probe(key, || run_cmd_with_timeout(command, timeout))
+}
+
+// Like run_cmd, but a non-zero exit is an error carrying the tool's stderr
+#[cfg(target_os = "macos")]
+pub fn run_cmd_checked(command: &mut Command) -> Result<Output, QuickscanError> {
+    let output = run_cmd(command)?;
+    if output.status.success() {
+        return Ok(output);
+    }
+    let program = command.get_program().to_string_lossy().to_string();
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    let lower = stderr.to_lowercase();
+    if lower.contains("not permitted") || lower.contains("permission denied") || lower.contains("must be run as root") {
+        Err(QuickscanError::PermissionDenied { source: program, message: stderr })
+    } else {
+        Err(QuickscanError::CommandFailed { source: program, message: format!("{} {}", output.status, stderr) })
+    }
+}
+
+// Like Command::output(), but kills the child once `timeout` has passed.
+// Commands with side effects or that wait on the operator (hooks, uploads,
+// playback, a colorimeter reading) call this directly: they start right away
+// and don't hold up the probes.
+pub fn run_cmd_with_timeout(command: &mut Command, timeout: Duration) -> Result<Output, QuickscanError> {
+    let started = Instant::now();
+    let result = run_with_timeout(command, timeout);
+    // Kept when the scan is collecting evidence, or for the lab's corpus
+    crate::evidence::record_command(command, started.elapsed(), &result);
+    result
+}
+
+fn run_with_timeout(command: &mut Command, timeout: Duration) -> Result<Output, QuickscanError> {
+    let program = command.get_program().to_string_lossy().to_string();
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| QuickscanError::io(&program, e))?;
+
+    // Drain the pipes while waiting so a chatty child can't block on a full pipe
+    let stdout = child.stdout.take().map(drain);
+    let stderr = child.stderr.take().map(drain);
+
+    let deadline = Instant::now() + timeout;
+    let mut watchdog = Watchdog::new(&program, child.id());
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() < deadline => {
+                if let Some(event) = watchdog.check() {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Err(QuickscanError::ResourceLimit { source: program, resource: event.resource });
+                }
+                thread::sleep(POLL_INTERVAL);
+            }
+            Ok(None) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(QuickscanError::Timeout { source: program, seconds: timeout.as_secs() });
+            }
+            Err(e) => return Err(QuickscanError::io(&program, e)),
+        }
+    };
+
+    let collect = |reader: Option<thread::JoinHandle<Vec<u8>>>| reader.and_then(|r| r.join().ok()).unwrap_or_default();
+    Ok(Output { status, stdout: collect(stdout), stderr: collect(stderr) })
+}
+
+fn drain(mut pipe: impl Read + Send + 'static) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buffer = Vec::new();
+        let _ = pipe.read_to_end(&mut buffer);
+        buffer
+    })
+}
+
+// Runs probe code on tokio's blocking pool so async commands keep the IPC
+// thread free while system tools do their work
+pub async fn blocking<T, F>(probe: F) -> T
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(probe)
+        .await
+        .unwrap_or_else(|e| std::panic::resume_unwind(e.into_panic()))
+}
@@ -0,0 +1,92 @@
+// Remediation guidance for critical integrity findings. Built-in
+// recommendations can be disabled or replaced per shop through
+// recommendations.json in the app config directory:
+//
+// {
+//   "disabled": ["parts_repairing"],
+//   "recommendations": [
+//     {
+//       "id": "dfu_restore",
+//       "title": "Send to bench 2 for a DFU restore",
+//       "steps": ["Tag the device", "Hand it to the bench 2 technician"],
+//       "triggers": ["sip_disabled"]
+//     }
+//   ]
+// }
+//
+// A custom entry with the same id as a built-in one replaces it. Titles and
+// steps are i18n keys for the built-ins; shops can use plain text. Built-ins
+// only apply on the platforms their steps are written for: the same
+// indicator needs a DFU restore on a Mac and a firmware reset on a PC.
+
+use crate::RefurbishmentIndicator;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+pub const POLICY_FILE: &str = "recommendations.json";
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Recommendation {
+    pub id: String,
+    pub title: String,
+    pub steps: Vec<String>,
+    #[serde(default)]
+    pub triggers: Vec<String>, // indicator names that call for this recommendation
+}
+
+#[derive(Deserialize, Default)]
+pub struct Policy {
+    #[serde(default)]
+    pub disabled: Vec<String>,
+    #[serde(default)]
+    pub recommendations: Vec<Recommendation>,
+}
+
+// `platforms` are std::env::consts::OS values
+fn builtin(id: &str, step_count: usize, triggers: &[&str], platforms: &[&str]) -> Option<Recommendation> {
+    platforms.contains(&std::env::consts::OS).then(|| Recommendation {
+        id: id.to_string(),
+        title: format!("recommendations.{}.title", id),
+        steps: (1..=step_count).map(|n| format!("recommendations.{}.step{}", id, n)).collect(),
+        triggers: triggers.iter().map(|t| t.to_string()).collect(),
+    })
+}
+
+fn builtin_recommendations() -> Vec<Recommendation> {
+    [
+        builtin("dfu_restore", 5, &["sip_disabled", "secure_boot_lowered"], &["macos"]),
+        builtin("parts_repairing", 4, &["part_swapped", "logic_board_mismatch"], &["macos"]),
+        builtin("secure_boot_reset", 4, &["secure_boot_lowered"], &["windows", "linux"]),
+        builtin("oem_parts_repair", 4, &["part_swapped", "logic_board_mismatch"], &["windows", "linux"]),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+pub fn load_policy(path: &Path) -> Result<Policy, String> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Policy::default()),
+        Err(e) => return Err(e.to_string()),
+    };
+    serde_json::from_str(&content).map_err(|e| e.to_string())
+}
+
+impl Policy {
+    pub fn recommend(&self, indicators: &[RefurbishmentIndicator]) -> Vec<Recommendation> {
+        let mut available = builtin_recommendations();
+        available.retain(|r| !self.recommendations.iter().any(|custom| custom.id == r.id));
+        available.extend(self.recommendations.iter().cloned());
+
+        available
+            .into_iter()
+            .filter(|r| !self.disabled.contains(&r.id))
+            .filter(|r| {
+                indicators
+                    .iter()
+                    .any(|i| i.detected && r.triggers.contains(&i.name))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,280 @@
+// Language, regional settings and keyboard layouts for `get_region_info`, and
+// where they say the machine was first sold. Settings are easy to change, so
+// they only add an info indicator when they disagree with the origin the
+// seller claims; the built-in keyboard's physical layout can't be changed
+// without swapping the top case, so a JIS keyboard on a "US" machine is a
+// warning.
+
+use crate::error::QuickscanError;
+use crate::probe_scheduler::ProbeScheduler;
+use serde::Serialize;
+
+#[derive(Serialize, Default)]
+pub struct RegionInfo {
+    pub display_language: Option<String>, // BCP 47, "ja-JP"
+    pub region: Option<String>, // ISO 3166 code from the regional settings, "JP"
+    pub keyboard_layouts: Vec<String>, // enabled layouts and input methods as the OS names them: "U.S.", "Japanese", "ja-JP", "jp"
+    pub physical_layout: Option<&'static str>, // built-in keyboard, see input_devices::physical_layout
+    pub likely_region: Option<String>, // where the evidence points, the physical layout first
+}
+
+#[tauri::command]
+pub async fn get_region_info(probes: tauri::State<'_, ProbeScheduler>) -> Result<RegionInfo, QuickscanError> {
+    probes.run(region_info).await
+}
+
+pub fn region_info() -> Result<RegionInfo, QuickscanError> {
+    #[cfg(target_os = "macos")]
+    {
+        Ok(with_hardware_evidence(macos::region_info()))
+    }
+    #[cfg(target_os = "windows")]
+    {
+        Ok(with_hardware_evidence(win32::region_info()))
+    }
+    #[cfg(target_os = "linux")]
+    {
+        Ok(with_hardware_evidence(linux::region_info()))
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        Err(QuickscanError::unsupported("region_info"))
+    }
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+fn with_hardware_evidence(mut info: RegionInfo) -> RegionInfo {
+    info.physical_layout = crate::input_devices::physical_layout();
+    info.likely_region = match info.physical_layout {
+        Some("jis") => Some("JP".to_string()),
+        _ => info.region.clone().or_else(|| info.display_language.as_deref().and_then(language_region)),
+    };
+    info
+}
+
+// What the hardware alone says, for a scan run from another system whose
+// settings aren't the machine's (the headless build)
+#[cfg(feature = "headless")]
+pub fn hardware_region_info() -> RegionInfo {
+    #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+    {
+        with_hardware_evidence(RegionInfo::default())
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        RegionInfo::default()
+    }
+}
+
+pub struct OriginConflict {
+    pub description: String, // indicator description, e.g. "keyboard_hardware_region:US / JIS"
+    pub severity: &'static str,
+}
+
+// Layouts only one market uses, matched by the names each OS gives them:
+// macOS layout or input mode, Windows language tag, X11 layout
+const LAYOUT_REGIONS: &[(&str, &str)] = &[
+    ("Japanese", "JP"),
+    ("ja-JP", "JP"),
+    ("jp", "JP"),
+    ("Korean", "KR"),
+    ("ko-KR", "KR"),
+    ("kr", "KR"),
+    ("British", "GB"),
+    ("en-GB", "GB"),
+    ("gb", "GB"),
+    ("German", "DE"),
+    ("de-DE", "DE"),
+    ("de", "DE"),
+    ("French", "FR"),
+    ("fr-FR", "FR"),
+    ("fr", "FR"),
+    ("U.S.", "US"),
+    ("en-US", "US"),
+    ("us", "US"),
+];
+
+// Markets whose machines ship with one physical layout. Others (Canada,
+// Switzerland…) get more than one, so nothing is compared there.
+fn expected_physical_layout(region: &str) -> Option<&'static str> {
+    match region {
+        "JP" => Some("jis"),
+        "US" | "CN" | "TW" | "KR" | "AU" => Some("ansi"),
+        "GB" | "IE" | "DE" | "AT" | "FR" | "BE" | "NL" | "ES" | "PT" | "IT" | "SE" | "NO" | "DK" | "FI" => Some("iso"),
+        _ => None,
+    }
+}
+
+fn layout_region(layout: &str) -> Option<&'static str> {
+    // macOS input modes come with a variant: "Korean.2SetKorean"
+    LAYOUT_REGIONS
+        .iter()
+        .find(|(name, _)| layout == *name || layout.strip_prefix(name).is_some_and(|rest| rest.starts_with('.')))
+        .map(|(_, region)| *region)
+}
+
+// "ja-JP" -> "JP"; "zh-Hant-TW" -> "TW"
+fn language_region(tag: &str) -> Option<String> {
+    tag.split(['-', '_'])
+        .skip(1)
+        .find(|part| part.len() == 2 && part.bytes().all(|b| b.is_ascii_alphabetic()))
+        .map(str::to_uppercase)
+}
+
+// The strongest sign that the machine was first sold somewhere other than
+// `claimed` (an ISO 3166 code); None when nothing contradicts it
+pub fn origin_conflict(info: &RegionInfo, claimed: &str) -> Option<OriginConflict> {
+    let claimed = claimed.trim().to_uppercase();
+    if let (Some(physical), Some(expected)) = (info.physical_layout, expected_physical_layout(&claimed)) {
+        if physical != expected {
+            return Some(OriginConflict {
+                description: format!("keyboard_hardware_region:{} / {}", claimed, physical.to_uppercase()),
+                severity: "warning",
+            });
+        }
+    }
+    let setting = info.region.clone().or_else(|| info.display_language.as_deref().and_then(language_region));
+    if let Some(setting) = setting.filter(|setting| *setting != claimed) {
+        return Some(OriginConflict {
+            description: format!("os_region_mismatch:{} / {}", claimed, setting),
+            severity: "info",
+        });
+    }
+    // Only layouts tied to one market count, and only when none of them is the claimed one
+    let regional: Vec<&String> =
+        info.keyboard_layouts.iter().filter(|layout| layout_region(layout).is_some()).collect();
+    if !regional.is_empty() && regional.iter().all(|layout| layout_region(layout) != Some(claimed.as_str())) {
+        let layouts: Vec<&str> = regional.iter().map(|layout| layout.as_str()).collect();
+        return Some(OriginConflict {
+            description: format!("keyboard_layout_region:{} / {}", claimed, layouts.join(", ")),
+            severity: "info",
+        });
+    }
+    None
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use super::RegionInfo;
+    use crate::process;
+    use std::process::Command;
+
+    fn defaults_read(domain: &str, key: &str) -> Option<String> {
+        let output = process::run_cmd(Command::new("defaults").args(["read", domain, key])).ok()?;
+        output.status.success().then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    // `name = value;` lines of an old-style plist, quotes removed
+    fn plist_values<'a>(plist: &'a str, key: &'a str) -> impl Iterator<Item = String> + 'a {
+        plist.lines().filter_map(move |line| {
+            let (name, value) = line.trim().split_once(" = ")?;
+            (name.trim_matches('"') == key).then(|| value.trim_end_matches(';').trim_matches('"').to_string())
+        })
+    }
+
+    pub fn region_info() -> RegionInfo {
+        // ("ja-JP", "en-US"), most preferred first
+        let display_language = defaults_read("-g", "AppleLanguages").and_then(|languages| {
+            languages
+                .lines()
+                .map(|line| line.trim().trim_end_matches(',').trim_matches('"'))
+                .find(|l| !l.is_empty() && *l != "(")
+                .map(str::to_string)
+        });
+        // "ja_JP", or "en_JP@rg=uszzzz" when the region was set apart from the language
+        let region = defaults_read("-g", "AppleLocale").and_then(|locale| {
+            let locale = locale.trim();
+            match locale.split_once("@rg=") {
+                Some((_, rg)) => rg.get(..2).map(str::to_uppercase),
+                None => super::language_region(locale),
+            }
+        });
+
+        let sources = defaults_read("com.apple.HIToolbox", "AppleEnabledInputSources").unwrap_or_default();
+        let mut keyboard_layouts: Vec<String> = plist_values(&sources, "KeyboardLayout Name")
+            .chain(
+                plist_values(&sources, "Input Mode")
+                    .map(|mode| mode.trim_start_matches("com.apple.inputmethod.").to_string()),
+            )
+            .collect();
+        keyboard_layouts.dedup();
+
+        RegionInfo { display_language, region, keyboard_layouts, ..Default::default() }
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod win32 {
+    use super::RegionInfo;
+    use windows::Win32::Globalization::{GetUserDefaultGeoName, GetUserDefaultUILanguage, LCIDToLocaleName};
+    use windows::Win32::UI::Input::KeyboardAndMouse::{GetKeyboardLayoutList, HKL};
+
+    // LOCALE_NAME_MAX_LENGTH
+    const NAME_LENGTH: usize = 85;
+
+    fn utf16(buffer: &[u16], len: i32) -> Option<String> {
+        // The length counts the terminating null
+        let len = usize::try_from(len).ok()?.checked_sub(1)?;
+        Some(String::from_utf16_lossy(&buffer[..len])).filter(|s| !s.is_empty())
+    }
+
+    fn locale_name(lcid: u32) -> Option<String> {
+        let mut buffer = [0u16; NAME_LENGTH];
+        let len = unsafe { LCIDToLocaleName(lcid, Some(&mut buffer), 0) };
+        utf16(&buffer, len)
+    }
+
+    pub fn region_info() -> RegionInfo {
+        let display_language = locale_name(u32::from(unsafe { GetUserDefaultUILanguage() }));
+        let mut buffer = [0u16; NAME_LENGTH];
+        let len = unsafe { GetUserDefaultGeoName(&mut buffer) };
+        // Numeric UN M.49 codes ("419") stand for regions larger than a country
+        let region = utf16(&buffer, len).filter(|geo| geo.len() == 2);
+
+        // The low word of a layout handle is its input language
+        let count = unsafe { GetKeyboardLayoutList(None) };
+        let mut layouts = vec![HKL::default(); usize::try_from(count).unwrap_or(0)];
+        let count = unsafe { GetKeyboardLayoutList(Some(&mut layouts)) };
+        layouts.truncate(usize::try_from(count).unwrap_or(0));
+        let mut keyboard_layouts: Vec<String> =
+            layouts.iter().filter_map(|layout| locale_name((layout.0 as usize & 0xFFFF) as u32)).collect();
+        keyboard_layouts.dedup();
+
+        RegionInfo { display_language, region, keyboard_layouts, ..Default::default() }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::RegionInfo;
+    use crate::process;
+    use std::process::Command;
+
+    pub fn region_info() -> RegionInfo {
+        // "   System Locale: LANG=ja_JP.UTF-8" / "       X11 Layout: jp,us"
+        let status = process::run_cmd(Command::new("localectl").arg("status"))
+            .map(|output| String::from_utf8_lossy(&output.stdout).into_owned())
+            .unwrap_or_default();
+        let field = |name: &str| {
+            status
+                .lines()
+                .find_map(|line| line.trim().strip_prefix(name)?.strip_prefix(':').map(|v| v.trim().to_string()))
+        };
+
+        // "ja_JP.UTF-8" -> "ja-JP"
+        let display_language = std::env::var("LANG")
+            .ok()
+            .or_else(|| field("System Locale")?.strip_prefix("LANG=").map(str::to_string))
+            .map(|lang| lang.split(['.', '@']).next().unwrap_or_default().replace('_', "-"))
+            .filter(|lang| !lang.is_empty() && lang != "C" && lang != "POSIX");
+        let region = display_language.as_deref().and_then(super::language_region);
+        let keyboard_layouts = field("X11 Layout")
+            .or_else(|| field("VC Keymap"))
+            .map(|layouts| {
+                layouts.split(',').map(|layout| layout.trim().to_string()).filter(|l| !l.is_empty()).collect()
+            })
+            .unwrap_or_default();
+
+        RegionInfo { display_language, region, keyboard_layouts, ..Default::default() }
+    }
+}
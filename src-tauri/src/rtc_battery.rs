@@ -0,0 +1,173 @@
+// Signs of a flat clock battery (the RTC or "CMOS" coin cell) for
+// `get_rtc_battery`. The cell keeps the hardware clock running while the
+// machine is unplugged, and lasts five to ten years; once it is flat the clock
+// starts over from the firmware's default date every time power is cut, and the
+// system sets it right again once it reaches a time server. It is a cheap fix,
+// but the buyer only notices through certificate errors and a wrong date after
+// every cold start.
+//
+// The system logs those corrections: Windows logs each change of the system
+// time (Kernel-General 1) with the time before and after it, and the macOS
+// power log has its entries written with the wrong date until the clock is
+// corrected. A clock that read a date before the firmware was even released
+// was reset; a large jump now and then may be someone setting it by hand, so
+// those only count once they come back.
+
+use crate::error::QuickscanError;
+use crate::probe_scheduler::ProbeScheduler;
+use serde::Serialize;
+
+// An NTP correction is seconds; a clock off by this much lost its time
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+const RESET_MIN_DAYS: i64 = 30;
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+const REPEATED_RESETS: usize = 2;
+
+#[derive(Serialize)]
+pub struct ClockReset {
+    pub at: String, // when the clock was set right, "YYYY-MM-DD HH:MM:SS" local time
+    pub clock_read: String, // what it read before
+    pub before_firmware: bool, // earlier than the machine could have been running
+}
+
+#[derive(Serialize)]
+pub struct RtcBattery {
+    pub resets: Vec<ClockReset>, // newest first
+    pub earliest_possible: Option<String>, // YYYY-MM-DD: the firmware's release date, or the model's year
+    pub likely_flat: bool,
+}
+
+#[tauri::command]
+pub async fn get_rtc_battery(app: tauri::AppHandle, probes: tauri::State<'_, ProbeScheduler>) -> Result<RtcBattery, QuickscanError> {
+    crate::consent::require(&app, crate::consent::ConsentScope::Logs)?;
+    probes.run(rtc_battery).await
+}
+
+// YYYY-MM-DD: the machine can't have been running on its firmware before it
+// was released
+pub fn earliest_possible() -> Option<String> {
+    crate::board::board_info().ok().and_then(|board| board.firmware_date).or_else(|| {
+        let year = crate::device_model::device_model().ok()?.release_year?;
+        Some(format!("{}-01-01", year))
+    })
+}
+
+pub fn rtc_battery() -> Result<RtcBattery, QuickscanError> {
+    let earliest_possible = earliest_possible();
+
+    #[cfg(target_os = "macos")]
+    {
+        macos::resets().map(|resets| summarize(resets, earliest_possible))
+    }
+    #[cfg(target_os = "windows")]
+    {
+        win32::resets().map(|resets| summarize(resets, earliest_possible))
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        let _ = earliest_possible;
+        Err(QuickscanError::unsupported("rtc_battery"))
+    }
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn parse(at: &str) -> Option<chrono::NaiveDateTime> {
+    chrono::NaiveDateTime::parse_from_str(at.get(..19)?, "%Y-%m-%d %H:%M:%S").ok()
+}
+
+// `resets` holds (when it was corrected, what the clock read) pairs
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn summarize(resets: Vec<(String, String)>, earliest_possible: Option<String>) -> RtcBattery {
+    let mut resets: Vec<ClockReset> = resets
+        .into_iter()
+        .filter(|(at, clock_read)| match (parse(at), parse(clock_read)) {
+            (Some(at), Some(read)) => (at - read).num_days() >= RESET_MIN_DAYS,
+            _ => false,
+        })
+        .map(|(at, clock_read)| ClockReset {
+            before_firmware: earliest_possible.as_deref().is_some_and(|earliest| clock_read.as_str() < earliest),
+            at,
+            clock_read,
+        })
+        .collect();
+    resets.sort_by(|a, b| b.at.cmp(&a.at));
+    resets.dedup_by(|a, b| a.at == b.at);
+
+    let likely_flat = resets.len() >= REPEATED_RESETS || resets.iter().any(|reset| reset.before_firmware);
+    RtcBattery { resets, earliest_possible, likely_flat }
+}
+
+// pmset keeps the power log with each entry stamped by the clock of the
+// moment, so a boot on a reset clock leaves a run of entries dated years back
+#[cfg(target_os = "macos")]
+mod macos {
+    use super::{parse, RESET_MIN_DAYS};
+    use crate::error::QuickscanError;
+    use crate::process;
+    use std::process::Command;
+
+    pub fn resets() -> Result<Vec<(String, String)>, QuickscanError> {
+        // 2024-05-01 10:00:00 +0200 Wake      Wake from Deep Idle [CDNVA] : due to EC.LidOpen/Lid Open
+        let output = process::run_cmd_checked(Command::new("pmset").args(["-g", "log"]))?;
+        let log = String::from_utf8_lossy(&output.stdout);
+
+        let mut resets = vec![];
+        let mut latest: Option<chrono::NaiveDateTime> = None;
+        // The first entry of the run written on the wrong date
+        let mut wrong_since: Option<String> = None;
+        for line in log.lines() {
+            let Some(time) = parse(line) else {
+                continue;
+            };
+            let stamp = line[..19].to_string();
+            let behind = latest.is_some_and(|latest| (latest - time).num_days() >= RESET_MIN_DAYS);
+            if behind {
+                wrong_since.get_or_insert(stamp);
+                continue;
+            }
+            // Back on the right date: the clock has been corrected
+            if let Some(clock_read) = wrong_since.take() {
+                resets.push((stamp, clock_read));
+            }
+            latest = Some(latest.map_or(time, |latest| latest.max(time)));
+        }
+        Ok(resets)
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod win32 {
+    use crate::error::QuickscanError;
+    use crate::wmi_provider::{WmiProvider, CIMV2};
+    use chrono::{DateTime, Local};
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct LogEvent {
+        insertion_strings: Option<Vec<String>>,
+    }
+
+    // "2024-05-01T08:00:00.500000000Z" -> local "2024-05-01 10:00:00"
+    fn local(time: &str) -> Option<String> {
+        let time = DateTime::parse_from_rfc3339(time.trim()).ok()?;
+        Some(time.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S").to_string())
+    }
+
+    pub fn resets() -> Result<Vec<(String, String)>, QuickscanError> {
+        // "The system time has changed to <NewTime> from <OldTime>": both in
+        // UTC, new first
+        let events = WmiProvider::global().query::<LogEvent>(
+            CIMV2,
+            "SELECT InsertionStrings FROM Win32_NTLogEvent WHERE Logfile = 'System' \
+             AND SourceName = 'Microsoft-Windows-Kernel-General' AND EventCode = 1",
+        )?;
+        Ok(events
+            .into_iter()
+            .filter_map(|event| {
+                let strings = event.insertion_strings?;
+                Some((local(strings.first()?)?, local(strings.get(1)?)?))
+            })
+            .collect())
+    }
+}
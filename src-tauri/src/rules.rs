@@ -0,0 +1,184 @@
+// User-defined refurbishment rules, loaded from `rules.json` in the app config
+// directory. Each rule is a condition over report fields (dotted paths such as
+// "hardware.hostname" or "battery.cycle_count") that adds an indicator when it
+// matches, e.g.
+//
+// { "rules": [{
+//     "name": "district_asset",
+//     "description": "Hostname matches a school district asset name",
+//     "severity": "warning",
+//     "when": { "field": "hardware.hostname", "op": "contains", "value": "district", "ignore_case": true }
+// }] }
+//
+// Conditions can be combined with { "all": [...] }, { "any": [...] } and { "not": {...} }.
+
+use serde::Deserialize;
+use serde_json::Value;
+use std::path::Path;
+
+use crate::RefurbishmentIndicator;
+
+pub const RULES_FILE: &str = "rules.json";
+
+#[derive(Deserialize, Default)]
+pub struct RuleSet {
+    #[serde(default)]
+    pub rules: Vec<Rule>,
+}
+
+#[derive(Deserialize)]
+pub struct Rule {
+    pub name: String,
+    pub description: String,
+    #[serde(default = "default_severity")]
+    pub severity: String,
+    pub replaced_part: Option<String>,
+    pub when: Condition,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum Condition {
+    All { all: Vec<Condition> },
+    Any { any: Vec<Condition> },
+    Not { not: Box<Condition> },
+    Field {
+        field: String,
+        op: Operator,
+        #[serde(default)]
+        value: Value,
+        #[serde(default)]
+        ignore_case: bool,
+    },
+}
+
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum Operator {
+    Exists,
+    Equals,
+    NotEquals,
+    Contains,
+    StartsWith,
+    EndsWith,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+}
+
+fn default_severity() -> String {
+    "warning".to_string()
+}
+
+pub fn load_rules(path: &Path) -> Result<RuleSet, String> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(RuleSet::default()),
+        Err(e) => return Err(e.to_string()),
+    };
+    serde_json::from_str(&content).map_err(|e| e.to_string())
+}
+
+impl RuleSet {
+    pub fn evaluate(&self, report: &Value) -> Vec<(RefurbishmentIndicator, Option<String>)> {
+        self.rules
+            .iter()
+            .filter(|rule| rule.when.matches(report))
+            .map(|rule| {
+                let indicator = RefurbishmentIndicator {
+                    name: format!("rule:{}", rule.name),
+                    detected: true,
+                    description: rule.description.clone(),
+                    severity: rule.severity.clone(),
+                    help: None,
+                    caveat: None,
+                };
+                (indicator, rule.replaced_part.clone())
+            })
+            .collect()
+    }
+}
+
+impl Condition {
+    fn matches(&self, report: &Value) -> bool {
+        match self {
+            Condition::All { all } => all.iter().all(|c| c.matches(report)),
+            Condition::Any { any } => any.iter().any(|c| c.matches(report)),
+            Condition::Not { not } => !not.matches(report),
+            Condition::Field { field, op, value, ignore_case } => {
+                let actual = lookup(report, field);
+                compare(actual, *op, value, *ignore_case)
+            }
+        }
+    }
+}
+
+// Resolve a dotted path; numeric segments index into arrays ("disks.0.kind").
+fn lookup<'a>(report: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(report, |current, segment| match current {
+        Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+        _ => current.get(segment),
+    })
+}
+
+fn compare(actual: Option<&Value>, op: Operator, expected: &Value, ignore_case: bool) -> bool {
+    let actual = match actual {
+        Some(Value::Null) | None => return false,
+        Some(v) => v,
+    };
+
+    match op {
+        Operator::Exists => true,
+        Operator::Equals => values_equal(actual, expected, ignore_case),
+        Operator::NotEquals => !values_equal(actual, expected, ignore_case),
+        Operator::Contains if actual.is_array() => actual
+            .as_array()
+            .is_some_and(|items| items.iter().any(|item| values_equal(item, expected, ignore_case))),
+        Operator::Contains | Operator::StartsWith | Operator::EndsWith => {
+            let (Some(haystack), Some(needle)) = (as_text(actual), as_text(expected)) else {
+                return false;
+            };
+            let (haystack, needle) = if ignore_case {
+                (haystack.to_lowercase(), needle.to_lowercase())
+            } else {
+                (haystack, needle)
+            };
+            match op {
+                Operator::Contains => haystack.contains(&needle),
+                Operator::StartsWith => haystack.starts_with(&needle),
+                _ => haystack.ends_with(&needle),
+            }
+        }
+        Operator::Gt | Operator::Gte | Operator::Lt | Operator::Lte => {
+            let (Some(a), Some(b)) = (actual.as_f64(), expected.as_f64()) else {
+                return false;
+            };
+            match op {
+                Operator::Gt => a > b,
+                Operator::Gte => a >= b,
+                Operator::Lt => a < b,
+                _ => a <= b,
+            }
+        }
+    }
+}
+
+fn values_equal(actual: &Value, expected: &Value, ignore_case: bool) -> bool {
+    match (actual.as_f64(), expected.as_f64()) {
+        (Some(a), Some(b)) => a == b,
+        _ => match (actual.as_str(), expected.as_str()) {
+            (Some(a), Some(b)) if ignore_case => a.to_lowercase() == b.to_lowercase(),
+            _ => actual == expected,
+        },
+    }
+}
+
+fn as_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
@@ -0,0 +1,212 @@
+// The thresholds a shop grades by, in `scan_config.json` in the app config
+// directory. Every field is optional; a file only lists what it changes:
+//
+// { "battery": { "good_percent": 85, "cycle_limit": 1000 },
+//   "grade": { "a": 92, "weights": { "cosmetic_proxy": 30, "hardware_integrity": 20 } },
+//   "confidence": { "warning": 20, "indicators": { "secure_boot_lowered": 5 } } }
+//
+// The config is read at startup and kept in memory; `set_scan_config` writes
+// the file and swaps it in, so the next check uses it without a restart.
+
+use crate::error::QuickscanError;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Mutex;
+use tauri::Manager;
+
+pub const SCAN_CONFIG_FILE: &str = "scan_config.json";
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ScanConfig {
+    pub battery: BatteryThresholds,
+    pub storage: StorageThresholds,
+    pub grade: GradeThresholds,
+    pub confidence: ConfidenceWeights,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct BatteryThresholds {
+    pub good_percent: f64, // health at or above passes
+    pub fair_percent: f64, // at or above is a warning, below fails
+    pub wear_free_percent: f64, // what a battery loses before the grade counts it
+    pub cycle_warning: u32, // cycles above which the report warns
+    pub cycle_limit: u32, // above which it fails
+}
+
+impl Default for BatteryThresholds {
+    fn default() -> Self {
+        BatteryThresholds { good_percent: 80.0, fair_percent: 60.0, wear_free_percent: 95.0, cycle_warning: 500, cycle_limit: 800 }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct StorageThresholds {
+    // Drive-reported wear ("Percentage Used") from which the grade deducts a
+    // little, and from which it deducts a lot
+    pub wear_warning_percent: f64,
+    pub wear_limit_percent: f64,
+}
+
+impl Default for StorageThresholds {
+    fn default() -> Self {
+        StorageThresholds { wear_warning_percent: 50.0, wear_limit_percent: 80.0 }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct GradeThresholds {
+    // Score at or above which each grade starts
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub weights: GradeWeights,
+}
+
+impl Default for GradeThresholds {
+    fn default() -> Self {
+        GradeThresholds { a: 90.0, b: 75.0, c: 60.0, weights: GradeWeights::default() }
+    }
+}
+
+// Relative weights; they needn't add up to 100
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct GradeWeights {
+    pub battery: u32,
+    pub storage: u32,
+    pub cosmetic_proxy: u32,
+    pub hardware_integrity: u32,
+}
+
+impl Default for GradeWeights {
+    fn default() -> Self {
+        GradeWeights { battery: 25, storage: 25, cosmetic_proxy: 20, hardware_integrity: 30 }
+    }
+}
+
+// What each detected indicator adds to the refurbishment confidence score,
+// which is capped at 100. Indicators not listed under `indicators` weigh by
+// their severity, so a lone info-level finding no longer calls the machine
+// refurbished.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ConfidenceWeights {
+    pub info: u32,
+    pub warning: u32,
+    pub critical: u32,
+    pub indicators: BTreeMap<String, u32>, // by indicator name, over the built-in ones
+    // Score at or above which the confidence is high or medium, and the
+    // machine counts as refurbished
+    pub high: u32,
+    pub medium: u32,
+    pub refurbished: u32,
+}
+
+// The markers a refurbisher sets on purpose settle it on their own. The rest
+// of the zeros are about the owner or the setup, not about parts: a signed-in
+// account or a lowered boot policy is serious for the buyer, but says nothing
+// about whether the machine was refurbished. A wrong clock only says how far
+// to trust the others.
+const INDICATOR_WEIGHTS: &[(&str, u32)] = &[
+    ("serial_refurb", 100),
+    ("ioreg_refurb", 100),
+    ("refurbisher_license", 100),
+    ("personal_account", 0),
+    ("work_account", 0),
+    ("activation_lock", 0),
+    ("enterprise_managed", 0),
+    ("sip_disabled", 0),
+    ("secure_boot_lowered", 0),
+    ("gpu_generic_driver", 0),
+    ("bluetooth_pairings", 0),
+    ("previous_owner_data", 0),
+    ("recent_os_install", 0),
+    ("origin_mismatch", 0),
+    ("wifi_mac_locally_administered", 0),
+    ("clock_unreliable", 0),
+];
+
+impl Default for ConfidenceWeights {
+    fn default() -> Self {
+        ConfidenceWeights {
+            info: 10,
+            warning: 30,
+            critical: 60,
+            indicators: BTreeMap::new(),
+            high: 60,
+            medium: 30,
+            refurbished: 30,
+        }
+    }
+}
+
+impl ConfidenceWeights {
+    pub fn weight(&self, name: &str, severity: &str) -> u32 {
+        if let Some(weight) = self.indicators.get(name) {
+            return *weight;
+        }
+        if let Some((_, weight)) = INDICATOR_WEIGHTS.iter().find(|(indicator, _)| *indicator == name) {
+            return *weight;
+        }
+        match severity {
+            "critical" => self.critical,
+            "warning" => self.warning,
+            _ => self.info,
+        }
+    }
+}
+
+// The config in use; None until startup has read it
+static CONFIG: Mutex<Option<ScanConfig>> = Mutex::new(None);
+
+pub fn load_scan_config(path: &Path) -> Result<ScanConfig, QuickscanError> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(ScanConfig::default()),
+        Err(e) => return Err(QuickscanError::io(SCAN_CONFIG_FILE, e)),
+    };
+    serde_json::from_str(&content).map_err(|e| QuickscanError::parse(SCAN_CONFIG_FILE, e))
+}
+
+// The thresholds in use. The headless build has no config directory and
+// grades by the defaults.
+pub fn current() -> ScanConfig {
+    CONFIG.lock().unwrap().clone().unwrap_or_default()
+}
+
+// Called at startup. A file that can't be read leaves the defaults in place;
+// get_scan_config reports why.
+pub fn init(app: &tauri::AppHandle) {
+    if let Ok(config) = read(app) {
+        *CONFIG.lock().unwrap() = Some(config);
+    }
+}
+
+fn read(app: &tauri::AppHandle) -> Result<ScanConfig, QuickscanError> {
+    let config_dir = app.path().app_config_dir().map_err(|e| QuickscanError::command_failed(SCAN_CONFIG_FILE, e))?;
+    load_scan_config(&config_dir.join(SCAN_CONFIG_FILE))
+}
+
+// The file as it is now, and what the checks will use from here on
+#[tauri::command]
+pub fn get_scan_config(app: tauri::AppHandle) -> Result<ScanConfig, QuickscanError> {
+    let config = read(&app)?;
+    *CONFIG.lock().unwrap() = Some(config.clone());
+    Ok(config)
+}
+
+#[tauri::command]
+pub fn set_scan_config(app: tauri::AppHandle, config: ScanConfig) -> Result<(), QuickscanError> {
+    let config_dir = app.path().app_config_dir().map_err(|e| QuickscanError::command_failed(SCAN_CONFIG_FILE, e))?;
+    let io = |e| QuickscanError::io(SCAN_CONFIG_FILE, e);
+    std::fs::create_dir_all(&config_dir).map_err(io)?;
+    let content = serde_json::to_string_pretty(&config).map_err(|e| QuickscanError::parse(SCAN_CONFIG_FILE, e))?;
+    std::fs::write(config_dir.join(SCAN_CONFIG_FILE), content).map_err(io)?;
+    *CONFIG.lock().unwrap() = Some(config);
+    Ok(())
+}
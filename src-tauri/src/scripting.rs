@@ -0,0 +1,187 @@
+// Custom checks written as Rhai scripts, loaded from the `scripts` folder in the
+// app config directory. Each script sees the collected report as the `report`
+// constant and can raise indicators with:
+//
+//   flag(name, description)                  // warning
+//   flag(name, description, severity)        // "info", "warning" or "critical"
+//   flag_replaced(name, description, part)   // warning that also marks a replaced part
+//
+// and change the device grade (grade.rs) with:
+//
+//   adjust_grade(category, points, reason)       // points off "battery", "storage",
+//                                                // "cosmetic_proxy" or "hardware_integrity"
+//   adjust_grade(category, points, cap, reason)  // and the best grade it can get, "A" to "D"
+//
+// Scripts run in a sandboxed engine: no module imports, no host access and a
+// bounded number of operations.
+
+use rhai::{Engine, EvalAltResult, Scope};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::cell::RefCell;
+use std::path::Path;
+use std::rc::Rc;
+
+use crate::RefurbishmentIndicator;
+
+pub const SCRIPTS_DIR: &str = "scripts";
+
+const MAX_OPERATIONS: u64 = 1_000_000;
+pub const GRADE_CATEGORIES: [&str; 4] = ["battery", "storage", "cosmetic_proxy", "hardware_integrity"];
+const GRADE_CAPS: [&str; 4] = ["A", "B", "C", "D"];
+
+pub struct Script {
+    pub name: String,
+    pub source: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GradeModifier {
+    pub script: String,
+    pub category: String,
+    pub points: u32, // taken off the category's 100
+    pub cap: Option<String>,
+    pub reason: String,
+}
+
+#[derive(Default)]
+pub struct ScriptOutcome {
+    pub findings: Vec<(RefurbishmentIndicator, Option<String>)>,
+    pub grade_modifiers: Vec<GradeModifier>,
+    pub failures: Vec<String>,
+}
+
+pub fn load_scripts(dir: &Path) -> Vec<Script> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return vec![];
+    };
+
+    let mut scripts: Vec<Script> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|e| e.to_str()) == Some("rhai"))
+        .filter_map(|path| {
+            let source = std::fs::read_to_string(&path).ok()?;
+            let name = path.file_stem()?.to_string_lossy().to_string();
+            Some(Script { name, source })
+        })
+        .collect();
+    scripts.sort_by(|a, b| a.name.cmp(&b.name));
+    scripts
+}
+
+pub fn run_scripts(scripts: &[Script], report: &Value) -> ScriptOutcome {
+    let mut outcome = ScriptOutcome::default();
+    let report = match rhai::serde::to_dynamic(report) {
+        Ok(report) => report,
+        Err(_) => return outcome,
+    };
+
+    for script in scripts {
+        let findings = Rc::new(RefCell::new(vec![]));
+        let modifiers = Rc::new(RefCell::new(vec![]));
+        let engine = sandboxed_engine(&script.name, findings.clone(), modifiers.clone());
+        let mut scope = Scope::new();
+        scope.push_constant("report", report.clone());
+
+        // A script that fails part way contributes nothing
+        match engine.run_with_scope(&mut scope, &script.source) {
+            Ok(()) => {
+                outcome.findings.extend(findings.take());
+                outcome.grade_modifiers.extend(modifiers.take());
+            }
+            Err(e) => outcome.failures.push(describe_failure(&script.name, &e)),
+        }
+    }
+    outcome
+}
+
+type Findings = Rc<RefCell<Vec<(RefurbishmentIndicator, Option<String>)>>>;
+type Modifiers = Rc<RefCell<Vec<GradeModifier>>>;
+
+fn sandboxed_engine(script_name: &str, findings: Findings, modifiers: Modifiers) -> Engine {
+    let mut engine = Engine::new();
+    engine.set_module_resolver(rhai::module_resolvers::DummyModuleResolver::new());
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.set_max_call_levels(32);
+    engine.set_max_expr_depths(64, 32);
+    engine.set_max_string_size(64 * 1024);
+    engine.set_max_array_size(10_000);
+    engine.set_max_map_size(10_000);
+    engine.on_print(|_| {});
+    engine.on_debug(|_, _, _| {});
+
+    let prefix = format!("script:{}", script_name);
+    let flag = {
+        let findings = findings.clone();
+        let prefix = prefix.clone();
+        move |name: &str, description: &str, severity: &str, part: Option<String>| {
+            findings.borrow_mut().push((
+                RefurbishmentIndicator {
+                    name: format!("{}:{}", prefix, name),
+                    detected: true,
+                    description: description.to_string(),
+                    severity: severity.to_string(),
+                    help: None,
+                    caveat: None,
+                },
+                part,
+            ));
+        }
+    };
+
+    let f = flag.clone();
+    engine.register_fn("flag", move |name: &str, description: &str| {
+        f(name, description, "warning", None)
+    });
+    let f = flag.clone();
+    engine.register_fn("flag", move |name: &str, description: &str, severity: &str| {
+        let severity = match severity {
+            "info" | "warning" | "critical" => severity,
+            _ => "warning",
+        };
+        f(name, description, severity, None)
+    });
+    engine.register_fn("flag_replaced", move |name: &str, description: &str, part: &str| {
+        flag(name, description, "warning", Some(part.to_string()))
+    });
+
+    // An unknown category or grade stops the script, so a typo shows up as a
+    // failed script rather than a grade that silently didn't change
+    let adjust = {
+        let script = script_name.to_string();
+        move |category: &str, points: i64, cap: Option<&str>, reason: &str| -> Result<(), Box<EvalAltResult>> {
+            if !GRADE_CATEGORIES.contains(&category) {
+                return Err(format!("unknown grade category '{}'", category).into());
+            }
+            if cap.is_some_and(|cap| !GRADE_CAPS.contains(&cap)) {
+                return Err(format!("unknown grade '{}'", cap.unwrap_or_default()).into());
+            }
+            modifiers.borrow_mut().push(GradeModifier {
+                script: script.clone(),
+                category: category.to_string(),
+                points: points.clamp(0, 100) as u32,
+                cap: cap.map(str::to_string),
+                reason: reason.to_string(),
+            });
+            Ok(())
+        }
+    };
+    let a = adjust.clone();
+    engine.register_fn("adjust_grade", move |category: &str, points: i64, reason: &str| {
+        a(category, points, None, reason)
+    });
+    engine.register_fn("adjust_grade", move |category: &str, points: i64, cap: &str, reason: &str| {
+        adjust(category, points, Some(cap), reason)
+    });
+
+    engine
+}
+
+fn describe_failure(script_name: &str, error: &EvalAltResult) -> String {
+    let position = error.position();
+    match position.line() {
+        Some(line) => format!("custom_script_failed:{} (line {})", script_name, line),
+        None => format!("custom_script_failed:{}", script_name),
+    }
+}
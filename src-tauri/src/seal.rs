@@ -0,0 +1,189 @@
+// Sealed report versions, for reports that end up as evidence in a
+// marketplace dispute. `seal_report` writes the report, with the operator's
+// annotations, to sealed_reports/<report id>/v<N>.json in the app data
+// directory and never touches that file again: a later change is sealed as
+// the next version, which names the hash of the one it supersedes. Every seal
+// is appended to the audit log, so a copy edited after the fact no longer
+// matches a hash the log recorded (`verify_sealed_report`).
+//
+// The hash is a SHA-256 over the canonical JSON (keys sorted, no whitespace)
+// of the seal's fields and the report, so it doesn't depend on how the
+// frontend happened to order the report's keys.
+
+use crate::consent::{self, AUDIT_LOG_FILE};
+use crate::error::QuickscanError;
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tauri::Manager;
+
+pub const SEALED_DIR: &str = "sealed_reports";
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Seal {
+    pub report_id: String,
+    pub version: u32, // from 1
+    pub sealed_at: i64, // Unix seconds
+    pub supersedes: Option<String>, // hash of the previous version
+    pub hash: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SealedReport {
+    seal: Seal,
+    report: Value,
+}
+
+#[derive(Serialize)]
+struct AuditEntry<'a> {
+    at: i64,
+    event: &'static str, // "report_sealed"
+    report_id: &'a str,
+    version: u32,
+    hash: &'a str,
+}
+
+#[derive(Serialize)]
+pub struct SealVerification {
+    pub seal: Seal,
+    pub intact: bool, // the report still hashes to the seal's hash
+    pub in_audit_log: bool, // this station's audit log recorded the hash
+}
+
+// Seals the report as its next version. Sealing an unchanged report again
+// returns the existing seal instead of a new version.
+#[tauri::command]
+pub fn seal_report(app: tauri::AppHandle, report: Value) -> Result<Seal, QuickscanError> {
+    let report_id = report
+        .get("id")
+        .and_then(|id| id.as_str())
+        .ok_or_else(|| QuickscanError::parse("report", "report has no id"))?
+        .to_string();
+    // The id becomes a directory name
+    if report_id.is_empty() || !report_id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err(QuickscanError::parse("report", format!("invalid report id: {}", report_id)));
+    }
+    let dir = sealed_dir(&app)?.join(&report_id);
+    std::fs::create_dir_all(&dir).map_err(|e| QuickscanError::io(SEALED_DIR, e))?;
+
+    let previous = read_versions(&dir)?.pop();
+    if let Some(previous) = &previous {
+        if canonical(&previous.report) == canonical(&report) {
+            return Ok(previous.seal.clone());
+        }
+    }
+
+    let mut seal = Seal {
+        report_id,
+        version: previous.as_ref().map_or(1, |p| p.seal.version + 1),
+        sealed_at: Local::now().timestamp(),
+        supersedes: previous.map(|p| p.seal.hash),
+        hash: String::new(),
+    };
+    seal.hash = hash(&seal, &report);
+
+    // create_new: an existing version is never overwritten
+    let path = dir.join(format!("v{}.json", seal.version));
+    let content =
+        serde_json::to_string_pretty(&SealedReport { seal: seal.clone(), report }).map_err(|e| QuickscanError::parse("report", e))?;
+    let io = |e| QuickscanError::io(SEALED_DIR, e);
+    let mut file = std::fs::OpenOptions::new().write(true).create_new(true).open(&path).map_err(io)?;
+    file.write_all(content.as_bytes()).map_err(io)?;
+    drop(file);
+    let mut permissions = std::fs::metadata(&path).map_err(io)?.permissions();
+    permissions.set_readonly(true);
+    std::fs::set_permissions(&path, permissions).map_err(io)?;
+
+    let entry = AuditEntry {
+        at: seal.sealed_at,
+        event: "report_sealed",
+        report_id: &seal.report_id,
+        version: seal.version,
+        hash: &seal.hash,
+    };
+    let data_dir = app.path().app_data_dir().map_err(|e| QuickscanError::command_failed(AUDIT_LOG_FILE, e))?;
+    consent::append_audit(&data_dir, &entry).map_err(|e| QuickscanError::command_failed(AUDIT_LOG_FILE, e))?;
+    Ok(seal)
+}
+
+// Every sealed version of a report, oldest first
+#[tauri::command]
+pub fn get_report_seals(app: tauri::AppHandle, report_id: String) -> Result<Vec<Seal>, QuickscanError> {
+    let dir = sealed_dir(&app)?.join(&report_id);
+    Ok(read_versions(&dir)?.into_iter().map(|sealed| sealed.seal).collect())
+}
+
+// Checks a sealed report file, e.g. one a buyer or a marketplace sent back
+#[tauri::command]
+pub fn verify_sealed_report(app: tauri::AppHandle, path: PathBuf) -> Result<SealVerification, QuickscanError> {
+    let sealed = read_sealed(&path)?;
+    let intact = hash(&sealed.seal, &sealed.report) == sealed.seal.hash;
+    let data_dir = app.path().app_data_dir().map_err(|e| QuickscanError::command_failed(AUDIT_LOG_FILE, e))?;
+    let in_audit_log = std::fs::read_to_string(data_dir.join(AUDIT_LOG_FILE))
+        .map(|log| {
+            log.lines().filter_map(|line| serde_json::from_str::<Value>(line).ok()).any(|entry| {
+                entry["event"] == "report_sealed" && entry["hash"] == sealed.seal.hash.as_str()
+            })
+        })
+        .unwrap_or(false);
+    Ok(SealVerification { seal: sealed.seal, intact, in_audit_log })
+}
+
+fn sealed_dir(app: &tauri::AppHandle) -> Result<PathBuf, QuickscanError> {
+    let data_dir = app.path().app_data_dir().map_err(|e| QuickscanError::command_failed(SEALED_DIR, e))?;
+    Ok(data_dir.join(SEALED_DIR))
+}
+
+fn read_sealed(path: &Path) -> Result<SealedReport, QuickscanError> {
+    let source = path.display().to_string();
+    let content = std::fs::read_to_string(path).map_err(|e| QuickscanError::io(&source, e))?;
+    serde_json::from_str(&content).map_err(|e| QuickscanError::parse(&source, e))
+}
+
+// Sorted by version; none for a report that was never sealed
+fn read_versions(dir: &Path) -> Result<Vec<SealedReport>, QuickscanError> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(QuickscanError::io(SEALED_DIR, e)),
+    };
+    let mut versions = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .map(|path| read_sealed(&path))
+        .collect::<Result<Vec<_>, _>>()?;
+    versions.sort_by_key(|sealed| sealed.seal.version);
+    Ok(versions)
+}
+
+// Over everything in the seal but the hash itself
+fn hash(seal: &Seal, report: &Value) -> String {
+    let sealed = json!({
+        "report_id": seal.report_id,
+        "version": seal.version,
+        "sealed_at": seal.sealed_at,
+        "supersedes": seal.supersedes,
+        "report": report,
+    });
+    Sha256::digest(canonical(&sealed).as_bytes()).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn canonical(value: &Value) -> String {
+    match value {
+        Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            let fields: Vec<String> = keys
+                .into_iter()
+                .map(|key| format!("{}:{}", Value::String(key.clone()), canonical(&map[key])))
+                .collect();
+            format!("{{{}}}", fields.join(","))
+        }
+        Value::Array(items) => format!("[{}]", items.iter().map(canonical).collect::<Vec<_>>().join(",")),
+        other => other.to_string(),
+    }
+}
@@ -0,0 +1,154 @@
+// Firmware security posture (`get_security_info`): SIP, Secure Boot, TPM and
+// the Mac security chip. Each setting is read on its own, so one that needs
+// more privileges than the app has just comes back as None.
+
+use crate::error::QuickscanError;
+use crate::probe_scheduler::ProbeScheduler;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct SecurityInfo {
+    pub sip_enabled: Option<bool>, // macOS only
+    pub secure_boot_enabled: Option<bool>,
+    pub secure_boot_policy: Option<String>, // Macs: "full", "reduced" or "permissive"
+    pub tpm_present: Option<bool>,
+    pub tpm_version: Option<String>,
+    pub security_chip: Option<String>, // "T2" or "Apple silicon"
+}
+
+#[tauri::command]
+pub async fn get_security_info(probes: tauri::State<'_, ProbeScheduler>) -> Result<SecurityInfo, QuickscanError> {
+    probes.run(security_info).await
+}
+
+pub fn security_info() -> Result<SecurityInfo, QuickscanError> {
+    #[cfg(target_os = "macos")]
+    {
+        Ok(security_info_macos())
+    }
+    #[cfg(target_os = "windows")]
+    {
+        Ok(security_info_windows())
+    }
+    #[cfg(target_os = "linux")]
+    {
+        Ok(security_info_linux())
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        Err(QuickscanError::unsupported("security_info"))
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn security_info_macos() -> SecurityInfo {
+    use std::process::Command;
+
+    let stdout = |program: &str, args: &[&str]| {
+        crate::process::run_cmd(Command::new(program).args(args))
+            .ok()
+            .map(|output| String::from_utf8_lossy(&output.stdout).to_string())
+    };
+
+    // "System Integrity Protection status: enabled." A custom configuration
+    // (some protections off) reads as "unknown" and counts as disabled.
+    let sip_enabled = stdout("csrutil", &["status"])
+        .filter(|s| s.contains("System Integrity Protection status"))
+        .map(|s| s.contains("status: enabled"));
+
+    let apple_silicon = stdout("sysctl", &["-n", "hw.optional.arm64"]).is_some_and(|s| s.trim() == "1");
+    let has_t2 = !apple_silicon
+        && stdout("system_profiler", &["SPiBridgeDataType"]).is_some_and(|s| s.contains("T2"));
+
+    let secure_boot_policy = if apple_silicon {
+        // "Security Mode: Full (2)" / "Reduced (1)" / "Permissive (0)"
+        stdout("bputil", &["-d"]).and_then(|s| {
+            let mode = s.lines().find_map(|line| line.trim().strip_prefix("Security Mode:"))?;
+            mode.split_whitespace().next().map(|m| m.to_lowercase())
+        })
+    } else if has_t2 {
+        // T2 keeps the Startup Security Utility setting in NVRAM: %02 full, %01 medium, %00 none
+        stdout("nvram", &["94b73556-2197-4702-82a8-3e1337dafbfb:AppleSecureBootPolicy"]).and_then(|s| {
+            match s.split_whitespace().last()? {
+                "%02" => Some("full".to_string()),
+                "%01" => Some("reduced".to_string()),
+                "%00" => Some("permissive".to_string()),
+                _ => None,
+            }
+        })
+    } else {
+        None
+    };
+
+    SecurityInfo {
+        sip_enabled,
+        secure_boot_enabled: secure_boot_policy.as_deref().map(|policy| policy != "permissive"),
+        secure_boot_policy,
+        // The Secure Enclave does the TPM's job on Macs
+        tpm_present: None,
+        tpm_version: None,
+        security_chip: if apple_silicon {
+            Some("Apple silicon".to_string())
+        } else if has_t2 {
+            Some("T2".to_string())
+        } else {
+            None
+        },
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn security_info_windows() -> SecurityInfo {
+    use crate::wmi_provider::{registry_dword, WmiProvider, TPM};
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct Tpm {
+        spec_version: Option<String>,
+    }
+
+    // Missing on legacy BIOS boots, where Secure Boot can't be on
+    let secure_boot_enabled = registry_dword("SYSTEM\\CurrentControlSet\\Control\\SecureBoot\\State", "UEFISecureBootEnabled")
+        .map(|enabled| enabled == 1)
+        .or(Some(false));
+
+    // Win32_Tpm needs an elevated process; leave TPM unknown otherwise
+    let (tpm_present, tpm_version) = match WmiProvider::global().query::<Tpm>(TPM, "SELECT SpecVersion FROM Win32_Tpm") {
+        // SpecVersion looks like "2.0, 0, 1.38"
+        Ok(tpms) => match tpms.into_iter().next() {
+            Some(tpm) => (Some(true), tpm.spec_version.and_then(|v| v.split(',').next().map(|v| v.trim().to_string()))),
+            None => (Some(false), None),
+        },
+        Err(_) => (None, None),
+    };
+
+    SecurityInfo {
+        sip_enabled: None,
+        secure_boot_enabled,
+        secure_boot_policy: None,
+        tpm_present,
+        tpm_version,
+        security_chip: None,
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn security_info_linux() -> SecurityInfo {
+    // efivars entries start with 4 attribute bytes; the fifth is the value
+    let secure_boot_enabled = std::fs::read("/sys/firmware/efi/efivars/SecureBoot-8be4df61-93ca-11d2-aa0d-00e098032b8c")
+        .ok()
+        .and_then(|data| data.get(4).map(|value| *value == 1))
+        .or_else(|| std::path::Path::new("/sys/firmware/efi").exists().then_some(false));
+    let tpm_version = std::fs::read_to_string("/sys/class/tpm/tpm0/tpm_version_major")
+        .ok()
+        .map(|major| format!("{}.0", major.trim()));
+
+    SecurityInfo {
+        sip_enabled: None,
+        secure_boot_enabled,
+        secure_boot_policy: None,
+        tpm_present: Some(std::path::Path::new("/sys/class/tpm/tpm0").exists()),
+        tpm_version,
+        security_chip: None,
+    }
+}
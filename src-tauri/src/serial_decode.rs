@@ -0,0 +1,127 @@
+// Manufacture plant and week encoded in Apple serial numbers. Before 2021
+// serials were either 11 characters (PPYWWSSSCCC: plant, year digit, two-digit
+// week) or 12 characters (PPPYWSSSCCCC: plant, half-year code, week code).
+// Machines released from 2021 on get 10 random characters that carry no
+// manufacture information.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SerialFormat {
+    Legacy11,
+    Legacy12,
+    Randomized,
+    Unknown,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DecodedSerial {
+    pub format: SerialFormat,
+    pub plant: Option<String>,
+    pub year: Option<u16>,
+    pub week: Option<u8>,
+}
+
+// Year code: two letters per year (first/second half), repeating every decade
+const YEAR_CODES: &str = "CDFGHJKLMNPQRSTVWXYZ";
+// Week within the half year
+const WEEK_CODES: &str = "123456789CDFGHJKLMNPQRTVWXY";
+
+// Location prefixes; matched by the longest key the serial starts with
+const PLANTS: &[(&str, &str)] = &[
+    ("C0", "Quanta Computer, China"),
+    ("C3", "Foxconn, Shenzhen, China"),
+    ("C7", "Pentragon, Shanghai, China"),
+    ("CK", "Cork, Ireland"),
+    ("CY", "Korea"),
+    ("DL", "Foxconn, China"),
+    ("DM", "Foxconn, China"),
+    ("DN", "Foxconn, Chengdu, China"),
+    ("EE", "Taiwan"),
+    ("F1", "Foxconn, Zhengzhou, China"),
+    ("F2", "Foxconn, Zhengzhou, China"),
+    ("F7", "China"),
+    ("FC", "Fountain, Colorado, USA"),
+    ("FK", "Foxconn, Zhengzhou, China"),
+    ("G8", "USA"),
+    ("MB", "Malaysia"),
+    ("PT", "Korea"),
+    ("QP", "USA"),
+    ("QT", "Quanta Computer, Taiwan"),
+    ("RM", "Refurbished/remanufactured"),
+    ("RN", "Mexico"),
+    ("SG", "Singapore"),
+    ("UV", "Taiwan"),
+    ("VM", "Foxconn, Pardubice, Czech Republic"),
+    ("W8", "Shanghai, China"),
+    ("WQ", "China"),
+    ("XA", "USA"),
+    ("XB", "USA"),
+    ("YM", "Foxconn, China"),
+    ("1C", "China"),
+    ("4H", "China"),
+    ("7J", "Foxconn, China"),
+];
+
+pub fn decode(serial: &str) -> DecodedSerial {
+    let serial = serial.trim().to_uppercase();
+    let undecoded = |format| DecodedSerial { format, plant: None, year: None, week: None };
+    if !serial.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return undecoded(SerialFormat::Unknown);
+    }
+
+    match serial.len() {
+        10 => undecoded(SerialFormat::Randomized),
+        11 => {
+            let digit = serial[2..3].parse::<u16>().ok();
+            let week = serial[3..5].parse::<u8>().ok().filter(|w| (1..=53).contains(w));
+            DecodedSerial {
+                format: SerialFormat::Legacy11,
+                plant: plant_name(&serial),
+                // The 11-character format ran until 2012
+                year: digit.map(|d| if d <= 2 { 2010 + d } else { 2000 + d }),
+                week,
+            }
+        }
+        12 => {
+            let half = YEAR_CODES.find(&serial[3..4]);
+            let week = WEEK_CODES.find(&serial[4..5]).map(|w| w as u8 + 1);
+            DecodedSerial {
+                format: SerialFormat::Legacy12,
+                plant: plant_name(&serial),
+                // C and D mean either 2010 or 2020; a 2010 Mac can't run a
+                // macOS this app supports, so they read as 2020
+                year: half.map(|h| if h < 2 { 2020 } else { 2010 + h as u16 / 2 }),
+                week: half.zip(week).map(|(h, w)| if h % 2 == 1 { w + 26 } else { w }),
+            }
+        }
+        _ => undecoded(SerialFormat::Unknown),
+    }
+}
+
+fn plant_name(serial: &str) -> Option<String> {
+    PLANTS
+        .iter()
+        .filter(|(prefix, _)| serial.starts_with(prefix))
+        .max_by_key(|(prefix, _)| prefix.len())
+        .map(|(_, name)| name.to_string())
+}
+
+impl DecodedSerial {
+    // First day of the manufacture week as YYYY-MM-DD
+    pub fn manufacture_date(&self) -> Option<String> {
+        let year = self.year?;
+        let week = self.week?;
+        let leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+        let month_days = [31, if leap { 29 } else { 28 }, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+        let mut day = ((week as u16 - 1) * 7).min(if leap { 365 } else { 364 });
+        for (month, days) in month_days.iter().enumerate() {
+            if day < *days {
+                return Some(format!("{:04}-{:02}-{:02}", year, month + 1, day + 1));
+            }
+            day -= days;
+        }
+        None
+    }
+}
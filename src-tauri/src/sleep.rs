@@ -0,0 +1,411 @@
+// Guided sleep test (`request_sleep`, then `check_sleep_history` once the
+// machine is back) and a summary of the last week of power events
+// (`get_power_events`). Broken sleep is a common used-laptop defect: the lid
+// closes and the machine wakes straight back up, something other than the
+// user keeps waking it, or the battery is flat by morning. The power logs say
+// when it slept, what woke it and, on macOS, how much charge it lost.
+
+use crate::error::QuickscanError;
+use crate::probe_scheduler::ProbeScheduler;
+use serde::Serialize;
+
+// Shorter than this and the machine most likely bounced straight back
+const INSTANT_WAKE_SECS: u64 = 15;
+const HISTORY_DAYS: u64 = 7;
+// A sleeping laptop loses about 1% an hour at most; shorter sleeps round the
+// charge too coarsely to tell
+const DRAIN_PERCENT_PER_HOUR: f64 = 3.0;
+const MIN_DRAIN_SLEEP_SECS: u64 = 3600;
+// Chronic rather than a one-off: enough incidents, and for wakes, most of them
+const CHRONIC_WAKES: u32 = 10;
+const CHRONIC_DRAIN_INCIDENTS: u32 = 3;
+
+#[derive(Serialize)]
+pub struct SleepCycle {
+    pub slept_at: u64, // seconds since the epoch
+    pub woke_at: u64,
+    pub wake_reason: Option<String>,
+    pub dark_wake: bool, // macOS maintenance wake with the display off
+    pub charge_at_sleep: Option<u8>, // percent; macOS only
+    pub charge_at_wake: Option<u8>,
+}
+
+// What the platform's power log holds since a point in time
+struct PowerLog {
+    cycles: Vec<SleepCycle>,
+    failed_sleeps: u32, // sleeps or resumes the log records as failed
+}
+
+#[derive(Serialize)]
+pub struct WakeSource {
+    pub reason: String,
+    pub count: u32,
+}
+
+#[derive(Serialize)]
+pub struct PowerEventSummary {
+    pub days: u64,
+    pub sleeps: u32,
+    pub unexpected_wakes: u32,
+    pub wake_sources: Vec<WakeSource>, // behind the unexpected wakes, most frequent first
+    pub failed_sleeps: u32,
+    pub drain_incidents: u32, // sleeps that lost more than DRAIN_PERCENT_PER_HOUR
+    pub worst_drain_percent_per_hour: Option<f64>,
+    pub chronic_wakes: bool,
+    pub chronic_drain: bool,
+}
+
+#[derive(Serialize)]
+pub struct SleepReport {
+    pub slept: bool,
+    pub seconds_asleep: Option<u64>, // first full sleep since the test started
+    pub wake_reason: Option<String>,
+    pub instant_wake: bool,
+    pub abnormal_wakes: Vec<String>, // wake sources in the window other than the user
+    pub cycles: Vec<SleepCycle>,
+}
+
+#[tauri::command]
+pub async fn request_sleep() -> Result<(), QuickscanError> {
+    crate::process::blocking(sleep_now).await
+}
+
+#[tauri::command]
+pub async fn check_sleep_history(app: tauri::AppHandle, probes: tauri::State<'_, ProbeScheduler>, since_ms: u64) -> Result<SleepReport, QuickscanError> {
+    crate::consent::require(&app, crate::consent::ConsentScope::Logs)?;
+    probes.run(move || power_log(since_ms / 1000).map(|log| report(log.cycles))).await
+}
+
+#[tauri::command]
+pub async fn get_power_events(app: tauri::AppHandle, probes: tauri::State<'_, ProbeScheduler>) -> Result<PowerEventSummary, QuickscanError> {
+    crate::consent::require(&app, crate::consent::ConsentScope::Logs)?;
+    probes.run(power_events).await
+}
+
+pub fn power_events() -> Result<PowerEventSummary, QuickscanError> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    power_log(now.saturating_sub(HISTORY_DAYS * 86400)).map(summarize)
+}
+
+fn sleep_now() -> Result<(), QuickscanError> {
+    #[cfg(target_os = "macos")]
+    {
+        let output =
+            crate::process::run_cmd_with_timeout(std::process::Command::new("pmset").arg("sleepnow"), crate::process::DEFAULT_TIMEOUT)?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(QuickscanError::command_failed("pmset", String::from_utf8_lossy(&output.stderr).trim()))
+        }
+    }
+    #[cfg(target_os = "windows")]
+    {
+        // Sleep rather than hibernate, and let wake timers through so they show up
+        let suspended = unsafe { windows::Win32::System::Power::SetSuspendState(false, false, false) };
+        if suspended {
+            Ok(())
+        } else {
+            Err(QuickscanError::command_failed("SetSuspendState", std::io::Error::last_os_error()))
+        }
+    }
+    #[cfg(target_os = "linux")]
+    {
+        let output =
+            crate::process::run_cmd_with_timeout(std::process::Command::new("systemctl").arg("suspend"), crate::process::DEFAULT_TIMEOUT)?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(QuickscanError::command_failed("systemctl", String::from_utf8_lossy(&output.stderr).trim()))
+        }
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        Err(QuickscanError::unsupported("sleep_test"))
+    }
+}
+
+fn power_log(since: u64) -> Result<PowerLog, QuickscanError> {
+    #[cfg(target_os = "macos")]
+    {
+        power_log_macos(since)
+    }
+    #[cfg(target_os = "windows")]
+    {
+        power_log_windows(since)
+    }
+    #[cfg(target_os = "linux")]
+    {
+        power_log_linux(since)
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        let _ = since;
+        Err(QuickscanError::unsupported("sleep_test"))
+    }
+}
+
+fn report(cycles: Vec<SleepCycle>) -> SleepReport {
+    let first = cycles.iter().find(|cycle| !cycle.dark_wake);
+    let seconds_asleep = first.map(|cycle| cycle.woke_at.saturating_sub(cycle.slept_at));
+    let mut abnormal_wakes: Vec<String> = cycles
+        .iter()
+        .filter(|cycle| !cycle.dark_wake)
+        .filter_map(|cycle| cycle.wake_reason.clone())
+        .filter(|reason| !is_user_wake(reason))
+        .collect();
+    abnormal_wakes.dedup();
+
+    SleepReport {
+        slept: first.is_some(),
+        seconds_asleep,
+        wake_reason: first.and_then(|cycle| cycle.wake_reason.clone()),
+        instant_wake: seconds_asleep.is_some_and(|secs| secs < INSTANT_WAKE_SECS),
+        abnormal_wakes,
+        cycles,
+    }
+}
+
+fn summarize(log: PowerLog) -> PowerEventSummary {
+    let wakes: Vec<&SleepCycle> = log.cycles.iter().filter(|cycle| !cycle.dark_wake).collect();
+    let mut wake_sources: Vec<WakeSource> = vec![];
+    for reason in wakes.iter().filter_map(|cycle| cycle.wake_reason.as_deref()).filter(|r| !is_user_wake(r)) {
+        match wake_sources.iter_mut().find(|source| source.reason == reason) {
+            Some(source) => source.count += 1,
+            None => wake_sources.push(WakeSource { reason: reason.to_string(), count: 1 }),
+        }
+    }
+    wake_sources.sort_by_key(|source| std::cmp::Reverse(source.count));
+    let unexpected_wakes: u32 = wake_sources.iter().map(|source| source.count).sum();
+
+    // Percent lost per hour for each long enough sleep on battery
+    let drains: Vec<f64> = log
+        .cycles
+        .iter()
+        .filter(|cycle| cycle.woke_at.saturating_sub(cycle.slept_at) >= MIN_DRAIN_SLEEP_SECS)
+        .filter_map(|cycle| {
+            let lost = cycle.charge_at_sleep?.checked_sub(cycle.charge_at_wake?)?;
+            let hours = (cycle.woke_at - cycle.slept_at) as f64 / 3600.0;
+            Some(lost as f64 / hours)
+        })
+        .collect();
+    let drain_incidents = drains.iter().filter(|rate| **rate > DRAIN_PERCENT_PER_HOUR).count() as u32;
+
+    PowerEventSummary {
+        days: HISTORY_DAYS,
+        sleeps: wakes.len() as u32,
+        unexpected_wakes,
+        wake_sources,
+        failed_sleeps: log.failed_sleeps,
+        drain_incidents,
+        worst_drain_percent_per_hour: drains.into_iter().reduce(f64::max).map(|rate| (rate * 10.0).round() / 10.0),
+        chronic_wakes: unexpected_wakes >= CHRONIC_WAKES && unexpected_wakes * 2 >= wakes.len() as u32,
+        chronic_drain: drain_incidents >= CHRONIC_DRAIN_INCIDENTS,
+    }
+}
+
+// Lid, power button and input devices; anything else (network, USB, timers,
+// unidentified sources) woke the machine without being asked to
+fn is_user_wake(reason: &str) -> bool {
+    let reason = reason.to_lowercase();
+    ["lid", "power button", "pwrbtn", "powerbutton", "keyboard", "hid", "user", "mouse", "trackpad", "input device"]
+        .iter()
+        .any(|source| reason.contains(source))
+}
+
+// Days since 1970-01-01 for a Gregorian date (Howard Hinnant's days_from_civil)
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = if year >= 0 { year } else { year - 399 } / 400;
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+// "2024-05-01 10:00:00" or "2024-05-01T10:00:00" -> seconds since the epoch,
+// before applying any UTC offset
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn parse_timestamp(text: &str) -> Option<i64> {
+    let number = |range: std::ops::Range<usize>| text.get(range)?.parse::<i64>().ok();
+    let days = days_from_civil(number(0..4)?, number(5..7)?, number(8..10)?);
+    Some(days * 86400 + number(11..13)? * 3600 + number(14..16)? * 60 + number(17..19)?)
+}
+
+#[cfg(target_os = "macos")]
+fn power_log_macos(since: u64) -> Result<PowerLog, QuickscanError> {
+    // 2024-05-01 10:00:00 +0200 Sleep     Entering Sleep state due to 'Software Sleep pid=85': Using Batt (Charge:80%) 45 secs
+    // 2024-05-01 10:05:00 +0200 Wake      Wake from Deep Idle [CDNVA] : due to EC.LidOpen/Lid Open Using BATT (Charge:79%)
+    // 2024-05-01 10:02:00 +0200 DarkWake  DarkWake from Deep Idle [CDN] : due to RTC/Maintenance Using BATT
+    // 2024-05-01 09:00:00 +0200 Failure   Sleep Wake failure in EFI
+    let output = crate::process::run_cmd_checked(std::process::Command::new("pmset").args(["-g", "log"]))?;
+    let log = String::from_utf8_lossy(&output.stdout);
+
+    let charge = |message: &str| {
+        let (_, rest) = message.split_once("Charge:")?;
+        rest.trim_start().split(|c: char| !c.is_ascii_digit()).next()?.parse::<u8>().ok()
+    };
+
+    let mut cycles = vec![];
+    let mut failed_sleeps = 0;
+    let mut asleep_since: Option<(u64, Option<u8>)> = None;
+    for line in log.lines() {
+        let Some(local) = parse_timestamp(line) else {
+            continue;
+        };
+        // "+0200" -> seconds east of UTC
+        let offset = line.get(20..25).and_then(|zone| {
+            let sign = if zone.starts_with('-') { -1 } else { 1 };
+            Some(sign * (zone.get(1..3)?.parse::<i64>().ok()? * 3600 + zone.get(3..5)?.parse::<i64>().ok()? * 60))
+        });
+        let timestamp = (local - offset.unwrap_or(0)).max(0) as u64;
+        if timestamp < since {
+            continue;
+        }
+
+        let message = line.get(25..).unwrap_or("");
+        match message.split_whitespace().next() {
+            Some("Sleep") => asleep_since = Some((timestamp, charge(message))),
+            Some("Failure") => failed_sleeps += 1,
+            Some(kind @ ("Wake" | "DarkWake")) => {
+                let Some((slept_at, charge_at_sleep)) = asleep_since.take() else {
+                    continue;
+                };
+                let wake_reason = message.split_once("due to ").map(|(_, reason)| {
+                    reason.split(" Using ").next().unwrap_or(reason).trim().to_string()
+                });
+                cycles.push(SleepCycle {
+                    slept_at,
+                    woke_at: timestamp,
+                    wake_reason,
+                    dark_wake: kind == "DarkWake",
+                    charge_at_sleep,
+                    charge_at_wake: charge(message),
+                });
+            }
+            _ => {}
+        }
+    }
+    Ok(PowerLog { cycles, failed_sleeps })
+}
+
+#[cfg(target_os = "windows")]
+fn power_log_windows(since: u64) -> Result<PowerLog, QuickscanError> {
+    use crate::wmi_provider::{WmiProvider, CIMV2};
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct LogEvent {
+        message: Option<String>,
+        insertion_strings: Option<Vec<String>>,
+    }
+
+    let wmi = WmiProvider::global();
+    let after = crate::wmi_provider::wmi_datetime(since);
+
+    // Power-Troubleshooter event 1 is written on every resume:
+    //   Sleep Time: 2024-05-01T08:00:00.1234567Z
+    //   Wake Time: 2024-05-01T08:05:00.1234567Z
+    //   Wake Source: Device -USB Composite Device
+    let events = wmi.query::<LogEvent>(
+        CIMV2,
+        &format!(
+            "SELECT Message FROM Win32_NTLogEvent WHERE Logfile = 'System' AND SourceName = 'Microsoft-Windows-Power-Troubleshooter' \
+             AND EventCode = 1 AND TimeGenerated >= '{}'",
+            after
+        ),
+    )?;
+
+    let mut cycles: Vec<SleepCycle> = events
+        .into_iter()
+        .filter_map(|event| {
+            // The timestamps are wrapped in left-to-right marks
+            let message: String = event.message?.chars().filter(|c| c.is_ascii()).collect();
+            let field = |name: &str| {
+                message
+                    .lines()
+                    .find_map(|line| line.trim().strip_prefix(name))
+                    .map(|value| value.trim().to_string())
+            };
+            Some(SleepCycle {
+                slept_at: parse_timestamp(&field("Sleep Time:")?)?.max(0) as u64,
+                woke_at: parse_timestamp(&field("Wake Time:")?)?.max(0) as u64,
+                wake_reason: field("Wake Source:").filter(|s| !s.is_empty()),
+                dark_wake: false,
+                charge_at_sleep: None,
+                charge_at_wake: None,
+            })
+        })
+        .collect();
+    cycles.sort_by_key(|cycle| cycle.slept_at);
+
+    // Kernel-Power 41 is an unclean restart; its sixth field, SleepInProgress,
+    // is non-zero when the machine died asleep or failed to resume
+    let failed_sleeps = wmi
+        .query::<LogEvent>(
+            CIMV2,
+            &format!(
+                "SELECT InsertionStrings FROM Win32_NTLogEvent WHERE Logfile = 'System' AND SourceName = 'Microsoft-Windows-Kernel-Power' \
+                 AND EventCode = 41 AND TimeGenerated >= '{}'",
+                after
+            ),
+        )
+        .unwrap_or_default()
+        .iter()
+        .filter(|event| {
+            let sleep_in_progress = event.insertion_strings.as_ref().and_then(|strings| strings.get(5));
+            sleep_in_progress.is_some_and(|value| value.trim() != "0" && value.trim() != "false")
+        })
+        .count() as u32;
+
+    Ok(PowerLog { cycles, failed_sleeps })
+}
+
+#[cfg(target_os = "linux")]
+fn power_log_linux(since: u64) -> Result<PowerLog, QuickscanError> {
+    use std::process::Command;
+
+    // 1714557600.123456 host kernel: PM: suspend entry (deep)
+    // 1714557900.654321 host kernel: PM: suspend exit
+    // 1714557600.234567 host kernel: PM: Some devices failed to suspend, or early wake event detected
+    let output = crate::process::run_cmd(
+        Command::new("journalctl").args(["-k", "-o", "short-unix", "--no-pager", "--since", &format!("@{}", since)]),
+    )?;
+    let log = String::from_utf8_lossy(&output.stdout);
+
+    let mut cycles = vec![];
+    let mut failed_sleeps = 0;
+    let mut asleep_since: Option<u64> = None;
+    for line in log.lines() {
+        let Some(timestamp) = line.split(['.', ' ']).next().and_then(|s| s.parse::<u64>().ok()) else {
+            continue;
+        };
+        if line.contains("PM: suspend entry") {
+            asleep_since = Some(timestamp);
+        } else if line.contains("PM: suspend exit") {
+            if let Some(slept_at) = asleep_since.take() {
+                cycles.push(SleepCycle {
+                    slept_at,
+                    woke_at: timestamp,
+                    wake_reason: None,
+                    dark_wake: false,
+                    charge_at_sleep: None,
+                    charge_at_wake: None,
+                });
+            }
+        } else if line.contains("failed to suspend") || line.contains("Freezing of tasks failed") {
+            failed_sleeps += 1;
+            // The exit that follows closes an aborted attempt, not a sleep
+            asleep_since = None;
+        }
+    }
+    // The kernel only keeps the interrupt behind the most recent wake
+    if let (Some(last), Ok(irq)) = (cycles.last_mut(), std::fs::read_to_string("/sys/power/pm_wakeup_irq")) {
+        last.wake_reason = Some(format!("IRQ {}", irq.trim()));
+    }
+    Ok(PowerLog { cycles, failed_sleeps })
+}
@@ -0,0 +1,192 @@
+// SMART attributes behind the pass/fail status. macOS has no built-in reader
+// for the NVMe health log, so it goes through smartctl (smartmontools) when
+// installed, as Linux does; Windows reads the storage reliability counters and the raw ATA
+// attribute table from WMI.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct SmartAttributes {
+    pub power_on_hours: Option<u64>,
+    pub power_cycles: Option<u64>,
+    pub reallocated_sectors: Option<u64>,
+    pub percentage_used: Option<u64>, // drive's own wear estimate; can exceed 100
+    pub total_bytes_written: Option<u64>,
+    pub temperature: Option<f64>,
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+impl SmartAttributes {
+    fn is_empty(&self) -> bool {
+        self.power_on_hours.is_none()
+            && self.power_cycles.is_none()
+            && self.reallocated_sectors.is_none()
+            && self.percentage_used.is_none()
+            && self.total_bytes_written.is_none()
+            && self.temperature.is_none()
+    }
+}
+
+// ATA attribute ids
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+const ATA_REALLOCATED_SECTORS: u64 = 5;
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+const ATA_POWER_ON_HOURS: u64 = 9;
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+const ATA_POWER_CYCLES: u64 = 12;
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+const ATA_TEMPERATURE: u64 = 194;
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+const ATA_LBAS_WRITTEN: u64 = 241;
+// Wear_Leveling_Count, SSD_Life_Left, Media_Wearout_Indicator: normalized value counts down from 100
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+const ATA_WEAR_REMAINING: &[u64] = &[177, 231, 233];
+
+// Homebrew installs outside the PATH an app launched from Finder gets
+#[cfg(target_os = "macos")]
+const SMARTCTL_PATHS: &[&str] = &["/opt/homebrew/sbin/smartctl", "/usr/local/sbin/smartctl", "smartctl"];
+
+#[cfg(target_os = "macos")]
+pub fn read_macos(disk: &str) -> Option<SmartAttributes> {
+    let device = format!("/dev/{}", disk);
+    // smartctl's exit status is a bitmask of findings, so only the JSON matters
+    let output = SMARTCTL_PATHS.iter().find_map(|path| {
+        crate::process::run_cmd(std::process::Command::new(path).args(["-a", "-j", &device])).ok()
+    })?;
+    let json = serde_json::from_slice::<serde_json::Value>(&output.stdout).ok()?;
+    let attributes = parse_smartctl(&json);
+    (!attributes.is_empty()).then_some(attributes)
+}
+
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn parse_smartctl(json: &serde_json::Value) -> SmartAttributes {
+    let number = |pointer: &str| json.pointer(pointer).and_then(|v| v.as_u64());
+    let mut attributes = SmartAttributes {
+        power_on_hours: number("/power_on_time/hours"),
+        power_cycles: number("/power_cycle_count"),
+        temperature: number("/temperature/current").map(|t| t as f64),
+        ..Default::default()
+    };
+
+    if let Some(log) = json.get("nvme_smart_health_information_log") {
+        let field = |key: &str| log.get(key).and_then(|v| v.as_u64());
+        attributes.percentage_used = field("percentage_used");
+        // NVMe data units are 1000 512-byte blocks
+        attributes.total_bytes_written = field("data_units_written").map(|units| units * 512_000);
+        attributes.power_on_hours = attributes.power_on_hours.or(field("power_on_hours"));
+        attributes.power_cycles = attributes.power_cycles.or(field("power_cycles"));
+    }
+
+    let table = json.pointer("/ata_smart_attributes/table").and_then(|v| v.as_array());
+    for attribute in table.into_iter().flatten() {
+        let Some(id) = attribute.get("id").and_then(|v| v.as_u64()) else {
+            continue;
+        };
+        let raw = attribute.pointer("/raw/value").and_then(|v| v.as_u64());
+        let value = attribute.get("value").and_then(|v| v.as_u64());
+        apply_ata_attribute(&mut attributes, id, value, raw);
+    }
+    attributes
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+fn apply_ata_attribute(attributes: &mut SmartAttributes, id: u64, value: Option<u64>, raw: Option<u64>) {
+    match id {
+        ATA_REALLOCATED_SECTORS => attributes.reallocated_sectors = raw,
+        ATA_POWER_ON_HOURS => attributes.power_on_hours = attributes.power_on_hours.or(raw),
+        ATA_POWER_CYCLES => attributes.power_cycles = attributes.power_cycles.or(raw),
+        // Only the low byte is the current temperature
+        ATA_TEMPERATURE => attributes.temperature = attributes.temperature.or(raw.map(|r| (r & 0xff) as f64)),
+        ATA_LBAS_WRITTEN => attributes.total_bytes_written = raw.map(|lbas| lbas * 512),
+        id if ATA_WEAR_REMAINING.contains(&id) => {
+            attributes.percentage_used = value.map(|remaining| 100u64.saturating_sub(remaining));
+        }
+        _ => {}
+    }
+}
+
+// The attributes and smartctl's overall verdict; needs root
+#[cfg(target_os = "linux")]
+pub fn read_linux(disk: &str) -> (Option<SmartAttributes>, Option<bool>) {
+    let device = format!("/dev/{}", disk);
+    let Ok(output) = crate::process::run_cmd(std::process::Command::new("smartctl").args(["-a", "-j", &device])) else {
+        return (None, None);
+    };
+    let Ok(json) = serde_json::from_slice::<serde_json::Value>(&output.stdout) else {
+        return (None, None);
+    };
+    let attributes = parse_smartctl(&json);
+    let passed = json.pointer("/smart_status/passed").and_then(|v| v.as_bool());
+    ((!attributes.is_empty()).then_some(attributes), passed)
+}
+
+#[cfg(target_os = "windows")]
+pub fn read_windows(device_id: &str) -> Option<SmartAttributes> {
+    use crate::wmi_provider::{WmiProvider, CIMV2, STORAGE, WMI};
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct ReliabilityCounter {
+        power_on_hours: Option<u32>,
+        start_stop_cycle_count: Option<u32>,
+        temperature: Option<u8>,
+        wear: Option<u8>,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct DiskDrive {
+        #[serde(rename = "PNPDeviceID")]
+        pnp_device_id: String,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct AtaSmartData {
+        instance_name: String,
+        vendor_specific: Vec<u8>,
+    }
+
+    let provider = WmiProvider::global();
+    let mut attributes = SmartAttributes::default();
+
+    // Same data as Get-StorageReliabilityCounter; needs an elevated process
+    if let Some(counter) = provider.query_first::<ReliabilityCounter>(
+        STORAGE,
+        &format!(
+            "SELECT PowerOnHours, StartStopCycleCount, Temperature, Wear FROM MSFT_StorageReliabilityCounter WHERE DeviceId = '{}'",
+            device_id
+        ),
+    ) {
+        attributes.power_on_hours = counter.power_on_hours.map(u64::from);
+        attributes.power_cycles = counter.start_stop_cycle_count.map(u64::from);
+        attributes.temperature = counter.temperature.filter(|t| *t > 0).map(f64::from);
+        attributes.percentage_used = counter.wear.map(u64::from);
+    }
+
+    // SATA drives also expose the raw attribute table: a 2-byte header, then
+    // 12-byte entries of id, flags (2), value, worst, raw (6), reserved.
+    // Instances are named after the drive's PnP id with a "_0" suffix.
+    let pnp_device_id = device_id.parse::<u32>().ok().and_then(|index| {
+        provider.query_first::<DiskDrive>(CIMV2, &format!("SELECT PNPDeviceID FROM Win32_DiskDrive WHERE Index = {}", index))
+    });
+    let smart = pnp_device_id.and_then(|drive| {
+        let prefix = drive.pnp_device_id.to_lowercase();
+        provider
+            .query::<AtaSmartData>(WMI, "SELECT InstanceName, VendorSpecific FROM MSStorageDriver_ATAPISmartData")
+            .ok()?
+            .into_iter()
+            .find(|data| data.instance_name.to_lowercase().starts_with(&prefix))
+    });
+    if let Some(smart) = smart {
+        for entry in smart.vendor_specific.get(2..).unwrap_or_default().chunks_exact(12) {
+            if entry[0] == 0 {
+                continue;
+            }
+            let raw = entry[5..11].iter().rev().fold(0u64, |acc, b| (acc << 8) | *b as u64);
+            apply_ata_attribute(&mut attributes, entry[0] as u64, Some(entry[3] as u64), Some(raw));
+        }
+    }
+
+    (!attributes.is_empty()).then_some(attributes)
+}
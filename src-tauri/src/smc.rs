@@ -0,0 +1,133 @@
+// System Management Controller reads for fan and temperature keys. There is
+// no public API for these; this is the AppleSMC user client protocol that fan
+// utilities have relied on since the Intel Macs, and Apple silicon keeps it.
+
+use io_kit_sys::{
+    io_connect_t, kIOMasterPortDefault, IOConnectCallStructMethod, IOObjectRelease, IOServiceClose,
+    IOServiceGetMatchingService, IOServiceMatching, IOServiceOpen,
+};
+use std::ffi::c_void;
+
+const KERN_SUCCESS: i32 = 0;
+const KERNEL_INDEX_SMC: u32 = 2;
+const CMD_READ_BYTES: u8 = 5;
+const CMD_READ_KEYINFO: u8 = 9;
+
+// Mirrors SMCKeyData_t; the kernel checks the size, so the layout has to match
+#[repr(C)]
+#[derive(Default, Clone, Copy)]
+struct KeyDataVersion {
+    major: u8,
+    minor: u8,
+    build: u8,
+    reserved: u8,
+    release: u16,
+}
+
+#[repr(C)]
+#[derive(Default, Clone, Copy)]
+struct KeyDataPowerLimits {
+    version: u16,
+    length: u16,
+    cpu_limit: u32,
+    gpu_limit: u32,
+    memory_limit: u32,
+}
+
+#[repr(C)]
+#[derive(Default, Clone, Copy)]
+struct KeyInfo {
+    data_size: u32,
+    data_type: u32,
+    data_attributes: u8,
+}
+
+#[repr(C)]
+#[derive(Default, Clone, Copy)]
+struct KeyData {
+    key: u32,
+    version: KeyDataVersion,
+    power_limits: KeyDataPowerLimits,
+    key_info: KeyInfo,
+    result: u8,
+    status: u8,
+    data8: u8,
+    data32: u32,
+    bytes: [u8; 32],
+}
+
+pub struct Smc {
+    connection: io_connect_t,
+}
+
+impl Smc {
+    pub fn open() -> Option<Smc> {
+        unsafe {
+            // IOServiceGetMatchingService consumes the matching dictionary
+            let matching = IOServiceMatching(c"AppleSMC".as_ptr());
+            if matching.is_null() {
+                return None;
+            }
+            let service = IOServiceGetMatchingService(kIOMasterPortDefault, matching);
+            if service == 0 {
+                return None;
+            }
+            let mut connection: io_connect_t = 0;
+            let result = IOServiceOpen(service, mach2::traps::mach_task_self(), 0, &mut connection);
+            IOObjectRelease(service);
+            (result == KERN_SUCCESS).then_some(Smc { connection })
+        }
+    }
+
+    // Reads a four-character key such as "TC0P" and decodes it by the data
+    // type the SMC reports for it. Keys a model doesn't have come back as None.
+    pub fn read(&self, key: &str) -> Option<f64> {
+        let key = match key.as_bytes() {
+            &[a, b, c, d] => u32::from_be_bytes([a, b, c, d]),
+            _ => return None,
+        };
+        let info = self
+            .call(&KeyData { key, data8: CMD_READ_KEYINFO, ..Default::default() })?
+            .key_info;
+        let data = self.call(&KeyData { key, key_info: info, data8: CMD_READ_BYTES, ..Default::default() })?;
+        let size = (info.data_size as usize).min(data.bytes.len());
+        decode(&info.data_type.to_be_bytes(), &data.bytes[..size])
+    }
+
+    fn call(&self, input: &KeyData) -> Option<KeyData> {
+        let mut output = KeyData::default();
+        let mut output_size = std::mem::size_of::<KeyData>();
+        let result = unsafe {
+            IOConnectCallStructMethod(
+                self.connection,
+                KERNEL_INDEX_SMC,
+                input as *const KeyData as *const c_void,
+                std::mem::size_of::<KeyData>(),
+                &mut output as *mut KeyData as *mut c_void,
+                &mut output_size,
+            )
+        };
+        (result == KERN_SUCCESS && output.result == 0).then_some(output)
+    }
+}
+
+impl Drop for Smc {
+    fn drop(&mut self) {
+        unsafe {
+            IOServiceClose(self.connection);
+        }
+    }
+}
+
+// Intel Macs use big-endian fixed point (sp78 for temperatures, fpe2 for fan
+// speeds); Apple silicon uses little-endian floats for both
+fn decode(data_type: &[u8; 4], bytes: &[u8]) -> Option<f64> {
+    match data_type {
+        b"sp78" => Some(i16::from_be_bytes(bytes.get(..2)?.try_into().ok()?) as f64 / 256.0),
+        b"fpe2" => Some(u16::from_be_bytes(bytes.get(..2)?.try_into().ok()?) as f64 / 4.0),
+        b"flt " => Some(f32::from_le_bytes(bytes.get(..4)?.try_into().ok()?) as f64),
+        b"ui8 " => bytes.first().map(|b| *b as f64),
+        b"ui16" => Some(u16::from_be_bytes(bytes.get(..2)?.try_into().ok()?) as f64),
+        _ => None,
+    }
+}
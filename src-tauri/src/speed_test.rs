@@ -0,0 +1,325 @@
+// Internet throughput (`run_network_speed_test`) and latency
+// (`run_latency_test`) for the certification report, so shops don't need a
+// separate speed test tool. Endpoints come from `network_test.json` in the app
+// config directory, with Cloudflare's public speed test as the default:
+//
+// {
+//   "download_url": "https://speed.cloudflare.com/__down?bytes=1000000000",
+//   "upload_url": "https://speed.cloudflare.com/__up",
+//   "duration_secs": 10,
+//   "latency_hosts": ["1.1.1.1:443", "8.8.8.8:443", "9.9.9.9:443"]
+// }
+//
+// Transfers go through curl, as the digest mail does, with the bytes counted
+// here so progress can be reported while they run. Latency is the TCP
+// handshake time: ICMP needs raw sockets, and ping's output is localized on
+// Windows.
+
+use crate::error::QuickscanError;
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{Emitter, Manager};
+
+pub const CONFIG_FILE: &str = "network_test.json";
+pub const PROGRESS_EVENT: &str = "network-test-progress";
+
+const MIN_DURATION_SECS: u64 = 3;
+const MAX_DURATION_SECS: u64 = 60;
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(500);
+const CHUNK_SIZE: usize = 64 * 1024;
+// Connecting and the server's response, on top of the transfer itself
+const CURL_GRACE_SECS: u64 = 15;
+const LATENCY_SAMPLES: usize = 10;
+const LATENCY_TIMEOUT: Duration = Duration::from_secs(2);
+const LATENCY_INTERVAL: Duration = Duration::from_millis(200);
+
+#[derive(Deserialize)]
+pub struct NetworkTestConfig {
+    #[serde(default = "default_download_url")]
+    pub download_url: String,
+    #[serde(default = "default_upload_url")]
+    pub upload_url: String,
+    #[serde(default = "default_duration_secs")]
+    pub duration_secs: u64, // per direction
+    #[serde(default = "default_latency_hosts")]
+    pub latency_hosts: Vec<String>, // "host:port"; port 443 when left out
+}
+
+impl Default for NetworkTestConfig {
+    fn default() -> Self {
+        NetworkTestConfig {
+            download_url: default_download_url(),
+            upload_url: default_upload_url(),
+            duration_secs: default_duration_secs(),
+            latency_hosts: default_latency_hosts(),
+        }
+    }
+}
+
+fn default_download_url() -> String {
+    "https://speed.cloudflare.com/__down?bytes=1000000000".to_string()
+}
+
+fn default_upload_url() -> String {
+    "https://speed.cloudflare.com/__up".to_string()
+}
+
+fn default_duration_secs() -> u64 {
+    10
+}
+
+fn default_latency_hosts() -> Vec<String> {
+    ["1.1.1.1:443", "8.8.8.8:443", "9.9.9.9:443"].map(str::to_string).to_vec()
+}
+
+#[derive(Serialize, Clone)]
+pub struct SpeedProgress {
+    pub phase: &'static str, // "download", "upload" or "latency"
+    pub elapsed_secs: f64,
+    pub mbps: Option<f64>, // so far; None during the latency test
+}
+
+#[derive(Serialize)]
+pub struct SpeedTestResult {
+    pub download_url: String,
+    pub upload_url: String,
+    pub download_mbps: f64,
+    pub upload_mbps: f64,
+    pub downloaded_bytes: u64,
+    pub uploaded_bytes: u64,
+}
+
+#[derive(Serialize)]
+pub struct LatencyResult {
+    pub hosts: Vec<HostLatency>,
+    pub latency_ms: Option<f64>, // median of the reachable hosts' averages
+    pub jitter_ms: Option<f64>,
+}
+
+#[derive(Serialize)]
+pub struct HostLatency {
+    pub host: String,
+    pub sent: usize,
+    pub received: usize,
+    pub min_ms: Option<f64>,
+    pub avg_ms: Option<f64>,
+    pub max_ms: Option<f64>,
+    pub jitter_ms: Option<f64>, // mean difference between consecutive samples
+}
+
+pub fn load_config(path: &Path) -> Result<NetworkTestConfig, String> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(NetworkTestConfig::default()),
+        Err(e) => return Err(e.to_string()),
+    };
+    serde_json::from_str(&content).map_err(|e| e.to_string())
+}
+
+fn config(app: &tauri::AppHandle) -> Result<NetworkTestConfig, QuickscanError> {
+    let config_dir = app.path().app_config_dir().map_err(|e| QuickscanError::command_failed(CONFIG_FILE, e))?;
+    load_config(&config_dir.join(CONFIG_FILE)).map_err(|e| QuickscanError::parse(CONFIG_FILE, e))
+}
+
+#[tauri::command]
+pub async fn run_network_speed_test(app: tauri::AppHandle) -> Result<SpeedTestResult, QuickscanError> {
+    crate::process::blocking(move || {
+        let config = config(&app)?;
+        let duration = Duration::from_secs(config.duration_secs.clamp(MIN_DURATION_SECS, MAX_DURATION_SECS));
+        let (downloaded_bytes, download_secs) = download(&app, &config.download_url, duration)?;
+        let (uploaded_bytes, upload_secs) = upload(&app, &config.upload_url, duration)?;
+        Ok(SpeedTestResult {
+            download_mbps: mbps(downloaded_bytes, download_secs),
+            upload_mbps: mbps(uploaded_bytes, upload_secs),
+            download_url: config.download_url,
+            upload_url: config.upload_url,
+            downloaded_bytes,
+            uploaded_bytes,
+        })
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn run_latency_test(app: tauri::AppHandle) -> Result<LatencyResult, QuickscanError> {
+    crate::process::blocking(move || {
+        let config = config(&app)?;
+        let started = Instant::now();
+        let hosts: Vec<HostLatency> = config
+            .latency_hosts
+            .iter()
+            .map(|host| {
+                let _ = app.emit(
+                    PROGRESS_EVENT,
+                    SpeedProgress { phase: "latency", elapsed_secs: started.elapsed().as_secs_f64(), mbps: None },
+                );
+                host_latency(host)
+            })
+            .collect();
+
+        let mut averages: Vec<f64> = hosts.iter().filter_map(|h| h.avg_ms).collect();
+        averages.sort_by(f64::total_cmp);
+        let jitters: Vec<f64> = hosts.iter().filter_map(|h| h.jitter_ms).collect();
+        Ok(LatencyResult {
+            latency_ms: averages.get(averages.len() / 2).copied(),
+            jitter_ms: (!jitters.is_empty()).then(|| jitters.iter().sum::<f64>() / jitters.len() as f64),
+            hosts,
+        })
+    })
+    .await
+}
+
+fn mbps(bytes: u64, secs: f64) -> f64 {
+    if secs > 0.0 {
+        bytes as f64 * 8.0 / secs / 1e6
+    } else {
+        0.0
+    }
+}
+
+fn curl(url: &str, duration: Duration) -> Command {
+    let mut command = Command::new("curl");
+    command
+        .args(["--silent", "--show-error", "--fail", "--location", "--max-time"])
+        .arg((duration.as_secs() + CURL_GRACE_SECS).to_string())
+        .arg(url);
+    command
+}
+
+fn curl_error(stderr: Option<impl Read>) -> String {
+    let mut message = String::new();
+    if let Some(mut stderr) = stderr {
+        let _ = stderr.read_to_string(&mut message);
+    }
+    format!("curl: {}", message.trim())
+}
+
+// Bytes received and seconds taken, timed from the first byte so connecting
+// doesn't count against the throughput
+fn download(app: &tauri::AppHandle, url: &str, duration: Duration) -> Result<(u64, f64), QuickscanError> {
+    let mut child = curl(url, duration)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| QuickscanError::io("curl", e))?;
+    let mut stdout = child.stdout.take().ok_or_else(|| QuickscanError::command_failed("curl", "no output"))?;
+
+    let mut buffer = vec![0u8; CHUNK_SIZE];
+    let mut bytes = 0u64;
+    let mut started: Option<Instant> = None;
+    let mut last_progress = Instant::now();
+    loop {
+        let read = match stdout.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(e) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(QuickscanError::io("curl", e));
+            }
+        };
+        let started = *started.get_or_insert_with(Instant::now);
+        bytes += read as u64;
+        if last_progress.elapsed() >= PROGRESS_INTERVAL {
+            last_progress = Instant::now();
+            let elapsed = started.elapsed().as_secs_f64();
+            let _ = app.emit(PROGRESS_EVENT, SpeedProgress { phase: "download", elapsed_secs: elapsed, mbps: Some(mbps(bytes, elapsed)) });
+        }
+        if started.elapsed() >= duration {
+            break;
+        }
+    }
+    let elapsed = started.map(|s| s.elapsed().as_secs_f64()).unwrap_or(0.0);
+    // Cut short on purpose once the time is up; only a failure before that counts
+    let _ = child.kill();
+    let status = child.wait().map_err(|e| QuickscanError::io("curl", e))?;
+    if bytes == 0 || (elapsed < duration.as_secs_f64() && !status.success()) {
+        return Err(QuickscanError::command_failed("network_speed_test", curl_error(child.stderr.take())));
+    }
+    Ok((bytes, elapsed))
+}
+
+// Streams incompressible data to curl's stdin, which sends it as a chunked POST
+fn upload(app: &tauri::AppHandle, url: &str, duration: Duration) -> Result<(u64, f64), QuickscanError> {
+    let mut child = curl(url, duration)
+        .args(["--request", "POST", "--upload-file", "-", "--header", "Content-Type: application/octet-stream"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| QuickscanError::io("curl", e))?;
+    let mut stdin = child.stdin.take().ok_or_else(|| QuickscanError::command_failed("curl", "no input"))?;
+
+    // xorshift, so compression along the way can't inflate the result
+    let mut x: u64 = 0x9e37_79b9_7f4a_7c15;
+    let chunk: Vec<u8> = (0..CHUNK_SIZE / 8)
+        .flat_map(|_| {
+            x ^= x << 13;
+            x ^= x >> 7;
+            x ^= x << 17;
+            x.to_le_bytes()
+        })
+        .collect();
+
+    let started = Instant::now();
+    let mut last_progress = started;
+    let mut bytes = 0u64;
+    while started.elapsed() < duration {
+        // A closed pipe means curl gave up; its exit status says why
+        if stdin.write_all(&chunk).is_err() {
+            break;
+        }
+        bytes += chunk.len() as u64;
+        if last_progress.elapsed() >= PROGRESS_INTERVAL {
+            last_progress = Instant::now();
+            let elapsed = started.elapsed().as_secs_f64();
+            let _ = app.emit(PROGRESS_EVENT, SpeedProgress { phase: "upload", elapsed_secs: elapsed, mbps: Some(mbps(bytes, elapsed)) });
+        }
+    }
+    let elapsed = started.elapsed().as_secs_f64();
+    drop(stdin);
+    let status = child.wait().map_err(|e| QuickscanError::io("curl", e))?;
+    if !status.success() {
+        return Err(QuickscanError::command_failed("network_speed_test", curl_error(child.stderr.take())));
+    }
+    Ok((bytes, elapsed))
+}
+
+fn host_latency(host: &str) -> HostLatency {
+    let target = if host.rsplit_once(':').is_some_and(|(_, port)| port.parse::<u16>().is_ok()) {
+        host.to_string()
+    } else {
+        format!("{}:443", host)
+    };
+    let address = target.to_socket_addrs().ok().and_then(|mut addresses| addresses.next());
+
+    let mut samples: Vec<f64> = vec![];
+    if let Some(address) = address {
+        for _ in 0..LATENCY_SAMPLES {
+            let started = Instant::now();
+            if TcpStream::connect_timeout(&address, LATENCY_TIMEOUT).is_ok() {
+                samples.push(started.elapsed().as_secs_f64() * 1000.0);
+            }
+            thread::sleep(LATENCY_INTERVAL);
+        }
+    }
+
+    let jitter = (samples.len() > 1).then(|| {
+        samples.windows(2).map(|pair| (pair[1] - pair[0]).abs()).sum::<f64>() / (samples.len() - 1) as f64
+    });
+    HostLatency {
+        host: host.to_string(),
+        sent: LATENCY_SAMPLES,
+        received: samples.len(),
+        min_ms: samples.iter().copied().reduce(f64::min),
+        avg_ms: (!samples.is_empty()).then(|| samples.iter().sum::<f64>() / samples.len() as f64),
+        max_ms: samples.iter().copied().reduce(f64::max),
+        jitter_ms: jitter,
+    }
+}
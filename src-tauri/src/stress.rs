@@ -0,0 +1,144 @@
+// CPU stress test (`run_cpu_stress_test`). Every core spins on integer work
+// while clock, temperature and throughput are sampled once a second. A
+// machine with dried thermal paste or failing cooling looks fine at idle but
+// loses speed within the first minute of full load.
+
+use crate::error::QuickscanError;
+use crate::probe_scheduler::ProbeScheduler;
+use serde::Serialize;
+use std::hint::black_box;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+use sysinfo::System;
+use tauri::Emitter;
+
+pub const PROGRESS_EVENT: &str = "cpu-stress-progress";
+
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+const MIN_DURATION_SECS: u64 = 10;
+const MAX_DURATION_SECS: u64 = 600;
+// Turbo budgets running out is normal; only a drop below this share of the
+// peak while the CPU is this hot counts as thermal throttling
+const THROTTLE_RATIO: f64 = 0.85;
+const THROTTLE_CELSIUS: f64 = 90.0;
+// Iterations between counter updates, small enough to stop promptly
+const BATCH: u64 = 1 << 16;
+
+#[derive(Serialize, Clone)]
+pub struct StressSample {
+    pub elapsed_secs: f64,
+    pub frequency_mhz: Option<u64>, // average over cores; None where the OS doesn't report it
+    pub temperature: Option<f64>, // CPU sensor, degrees Celsius
+    pub throughput: f64, // million iterations per second, all cores together
+}
+
+#[derive(Serialize)]
+pub struct StressTestResult {
+    pub duration_secs: u64,
+    pub threads: usize,
+    pub peak_mhz: Option<u64>,
+    pub sustained_mhz: Option<u64>, // average over the last third of the run
+    pub peak_temperature: Option<f64>,
+    pub throttle_onset_secs: Option<f64>,
+    pub score: u32, // share of peak throughput still held in the last third, 0-100
+    pub samples: Vec<StressSample>,
+}
+
+#[tauri::command]
+pub async fn run_cpu_stress_test(app: tauri::AppHandle, probes: tauri::State<'_, ProbeScheduler>, duration_secs: u64) -> Result<StressTestResult, QuickscanError> {
+    probes.run(move || Ok(stress_test(&app, duration_secs))).await
+}
+
+fn stress_test(app: &tauri::AppHandle, duration_secs: u64) -> StressTestResult {
+    let duration_secs = duration_secs.clamp(MIN_DURATION_SECS, MAX_DURATION_SECS);
+    let threads = thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+    let stop = Arc::new(AtomicBool::new(false));
+    let iterations = Arc::new(AtomicU64::new(0));
+    let workers: Vec<_> = (0..threads as u64)
+        .map(|seed| {
+            let stop = stop.clone();
+            let iterations = iterations.clone();
+            thread::spawn(move || spin(seed + 1, &stop, &iterations))
+        })
+        .collect();
+
+    let mut system = System::new();
+    let started = Instant::now();
+    let mut last = (started, 0);
+    let mut samples = vec![];
+    while started.elapsed() < Duration::from_secs(duration_secs) {
+        thread::sleep(SAMPLE_INTERVAL);
+        let now = Instant::now();
+        let count = iterations.load(Ordering::Relaxed);
+        system.refresh_cpu_frequency();
+        let frequencies: Vec<u64> = system.cpus().iter().map(|cpu| cpu.frequency()).filter(|f| *f > 0).collect();
+
+        let sample = StressSample {
+            elapsed_secs: now.duration_since(started).as_secs_f64(),
+            frequency_mhz: (!frequencies.is_empty()).then(|| frequencies.iter().sum::<u64>() / frequencies.len() as u64),
+            temperature: cpu_temperature(),
+            throughput: (count - last.1) as f64 / now.duration_since(last.0).as_secs_f64() / 1e6,
+        };
+        last = (now, count);
+        // The UI charts these live; a closed window just stops listening
+        let _ = app.emit(PROGRESS_EVENT, sample.clone());
+        samples.push(sample);
+    }
+
+    stop.store(true, Ordering::Relaxed);
+    for worker in workers {
+        let _ = worker.join();
+    }
+    summarize(duration_secs, threads, samples)
+}
+
+// xorshift keeps the integer units busy and can't be folded away
+fn spin(seed: u64, stop: &AtomicBool, iterations: &AtomicU64) {
+    let mut x = seed;
+    while !stop.load(Ordering::Relaxed) {
+        for _ in 0..BATCH {
+            x ^= x << 13;
+            x ^= x >> 7;
+            x ^= x << 17;
+        }
+        black_box(x);
+        iterations.fetch_add(BATCH, Ordering::Relaxed);
+    }
+}
+
+fn cpu_temperature() -> Option<f64> {
+    crate::thermal::thermal_info()
+        .ok()?
+        .sensors
+        .into_iter()
+        .find(|sensor| sensor.name == "cpu")
+        .map(|sensor| sensor.celsius)
+}
+
+fn summarize(duration_secs: u64, threads: usize, samples: Vec<StressSample>) -> StressTestResult {
+    let average = |values: Vec<f64>| (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64);
+    let tail = &samples[samples.len() * 2 / 3..];
+
+    let peak = samples.iter().map(|s| s.throughput).fold(0.0, f64::max);
+    let sustained = average(tail.iter().map(|s| s.throughput).collect()).unwrap_or(0.0);
+    let peak_index = samples.iter().position(|s| s.throughput == peak).unwrap_or(0);
+    // Without a CPU sensor a drop can't be told apart from a turbo budget running out
+    let throttle_onset_secs = samples[peak_index..]
+        .iter()
+        .find(|s| s.throughput < peak * THROTTLE_RATIO && s.temperature.is_some_and(|t| t >= THROTTLE_CELSIUS))
+        .map(|s| s.elapsed_secs);
+
+    StressTestResult {
+        duration_secs,
+        threads,
+        peak_mhz: samples.iter().filter_map(|s| s.frequency_mhz).max(),
+        sustained_mhz: average(tail.iter().filter_map(|s| s.frequency_mhz.map(|f| f as f64)).collect())
+            .map(|f| f.round() as u64),
+        peak_temperature: samples.iter().filter_map(|s| s.temperature).reduce(f64::max),
+        throttle_onset_secs,
+        score: if peak > 0.0 { (sustained / peak * 100.0).round().min(100.0) as u32 } else { 0 },
+        samples,
+    }
+}
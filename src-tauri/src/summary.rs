@@ -0,0 +1,638 @@
+// Screen-reader summaries of a finished report: one entry per section with a
+// short label, a value and a status sentence. The verdicts (which figure
+// stands for the section, what counts as a warning) are decided here so the
+// accessible view can't drift from the visual one; the frontend only
+// translates the keys and formats the typed values with the shop's units.
+//
+// Labels and sentences are translation keys from the frontend's i18n files,
+// e.g. for a worn battery:
+//
+//   { "section": "battery", "status": "warning",
+//     "label": { "id": "detection.categories.battery" },
+//     "value": { "kind": "percent", "value": 72 },
+//     "sentence": { "id": "a11y.battery.health", "args": {
+//         "health": { "kind": "percent", "value": 72 },
+//         "cycles": { "kind": "count", "value": 612 },
+//         "rating": { "kind": "message", "value": "battery.rating.fair" } } } }
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+const CPU_HOT_CELSIUS: f64 = 90.0;
+const WEAK_SIGNAL_DBM: i32 = -75;
+
+#[derive(Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SummaryStatus {
+    Passed,
+    Warning,
+    Failed,
+    Skipped,
+    Pending, // interactive test not run
+    Info,    // nothing to judge, e.g. a display
+}
+
+#[derive(Serialize)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+pub enum SummaryValue {
+    Text(String),
+    Count(u64),
+    Percent(f64),
+    Bytes(u64),
+    Celsius(f64),
+    Dbm(i32),
+    Ratio(u64, u64),      // "78/80"
+    Message(&'static str), // a translation key, e.g. "battery.rating.good"
+}
+
+#[derive(Serialize)]
+pub struct SummaryText {
+    pub id: String,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub args: BTreeMap<&'static str, SummaryValue>,
+}
+
+#[derive(Serialize)]
+pub struct SectionSummary {
+    pub section: String, // "battery", "display.0", "interactive.keyboard"
+    pub status: SummaryStatus,
+    pub label: SummaryText,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<SummaryValue>,
+    pub sentence: SummaryText,
+}
+
+// The parts of the frontend's DetectionReport the summaries read. Reports
+// saved by older versions lack some sections; those come out empty.
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ReportInput {
+    overall_score: f64,
+    summary: Counts,
+    hardware: Hardware,
+    battery: Battery,
+    storage: Storage,
+    thermal: Option<Thermal>,
+    network: Network,
+    displays: Vec<Display>,
+    refurbishment: Option<Refurbishment>,
+    interactive: Interactive,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct Counts {
+    passed: u64,
+    warning: u64,
+    failed: u64,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct Hardware {
+    cpu: Cpu,
+    memory: Memory,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct Cpu {
+    model: String,
+    cores: u64,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct Memory {
+    total: u64,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct Battery {
+    health: f64,
+    cycle_count: u64,
+    design_capacity: f64,
+    rating: String,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct Storage {
+    model: String,
+    capacity: u64,
+    smart_status: String,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct Thermal {
+    fans: Vec<Fan>,
+    sensors: Vec<Sensor>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct Fan {
+    stalled: bool,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct Sensor {
+    name: String,
+    celsius: f64,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct Network {
+    wifi: Wifi,
+    bluetooth: Bluetooth,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct Wifi {
+    available: bool,
+    generation: Option<String>,
+    connection: Option<WifiLink>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct WifiLink {
+    rssi_dbm: Option<i32>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct Bluetooth {
+    available: bool,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct Display {
+    name: String,
+    vendor: String,
+    native_resolution: Option<String>,
+    connection_type: String,
+    is_internal: bool,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct Refurbishment {
+    is_refurbished: bool,
+    confidence: String,
+    replaced_parts: Vec<String>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct Interactive {
+    screen: ScreenTest,
+    keyboard: KeyboardTest,
+    trackpad: TrackpadTest,
+    touch_bar: Option<TouchBarTest>,
+    camera: WorkingTest,
+    microphone: WorkingTest,
+    speaker: SpeakerTest,
+    ports: Option<PortTest>,
+    sleep: Option<SleepTest>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct ScreenTest {
+    tested: bool,
+    skipped: bool,
+    has_dead_pixel: bool,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct KeyboardTest {
+    tested: bool,
+    skipped: bool,
+    tested_keys: Vec<String>,
+    total_keys: u64,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct TrackpadTest {
+    tested: bool,
+    skipped: bool,
+    click_working: bool,
+    drag_working: bool,
+    gesture_working: bool,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct TouchBarTest {
+    tested: bool,
+    skipped: bool,
+    display_present: bool,
+    bridge_responding: bool,
+    tapped_keys: Vec<String>,
+    missed_keys: Vec<String>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct WorkingTest {
+    tested: bool,
+    skipped: bool,
+    working: bool,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct SpeakerTest {
+    tested: bool,
+    skipped: bool,
+    left_channel: bool,
+    right_channel: bool,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct PortTest {
+    tested: bool,
+    skipped: bool,
+    ports: Vec<serde_json::Value>,
+    dead_port_reported: bool,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct SleepTest {
+    tested: bool,
+    skipped: bool,
+    slept: bool,
+    instant_wake: bool,
+    abnormal_wakes: Vec<String>,
+}
+
+#[tauri::command]
+pub fn summarize_report(report: ReportInput) -> Vec<SectionSummary> {
+    let mut sections = vec![
+        overall(&report),
+        hardware(&report.hardware),
+        battery(&report.battery),
+        storage(&report.storage),
+    ];
+    if let Some(thermal) = report.thermal.as_ref().filter(|t| !t.fans.is_empty() || !t.sensors.is_empty()) {
+        sections.push(thermal_summary(thermal));
+    }
+    sections.push(network(&report.network));
+    sections.extend(report.displays.iter().enumerate().map(|(index, panel)| display(index, panel)));
+    if let Some(refurb) = &report.refurbishment {
+        sections.push(refurbishment(refurb));
+    }
+    sections.extend(interactive(&report.interactive));
+    sections
+}
+
+fn text(id: &str) -> SummaryText {
+    SummaryText { id: id.to_string(), args: BTreeMap::new() }
+}
+
+fn text_with<const N: usize>(id: &str, args: [(&'static str, SummaryValue); N]) -> SummaryText {
+    SummaryText { id: id.to_string(), args: args.into_iter().collect() }
+}
+
+fn section(
+    section: &str,
+    label: &str,
+    status: SummaryStatus,
+    value: Option<SummaryValue>,
+    sentence: SummaryText,
+) -> SectionSummary {
+    SectionSummary { section: section.to_string(), status, label: text(label), value, sentence }
+}
+
+fn overall(report: &ReportInput) -> SectionSummary {
+    let counts = &report.summary;
+    let status = if counts.failed > 0 {
+        SummaryStatus::Failed
+    } else if counts.warning > 0 {
+        SummaryStatus::Warning
+    } else {
+        SummaryStatus::Passed
+    };
+    section(
+        "overall",
+        "report.overallScore",
+        status,
+        Some(SummaryValue::Ratio(report.overall_score.round().max(0.0) as u64, 100)),
+        text_with(
+            "a11y.overall",
+            [
+                ("passed", SummaryValue::Count(counts.passed)),
+                ("warning", SummaryValue::Count(counts.warning)),
+                ("failed", SummaryValue::Count(counts.failed)),
+            ],
+        ),
+    )
+}
+
+fn hardware(hardware: &Hardware) -> SectionSummary {
+    section(
+        "hardware",
+        "detection.categories.hardware",
+        SummaryStatus::Passed,
+        Some(SummaryValue::Text(hardware.cpu.model.clone())),
+        text_with(
+            "a11y.hardware",
+            [
+                ("cpu", SummaryValue::Text(hardware.cpu.model.clone())),
+                ("cores", SummaryValue::Count(hardware.cpu.cores)),
+                ("memory", SummaryValue::Bytes(hardware.memory.total)),
+            ],
+        ),
+    )
+}
+
+fn battery(battery: &Battery) -> SectionSummary {
+    // Desktops report a zero design capacity
+    if battery.design_capacity <= 0.0 {
+        return section("battery", "detection.categories.battery", SummaryStatus::Info, None, text("a11y.battery.none"));
+    }
+    // The shop's thresholds, as the issue list in ReportPage uses them
+    let thresholds = crate::scan_config::current().battery;
+    let status = if battery.health >= thresholds.good_percent {
+        SummaryStatus::Passed
+    } else if battery.health >= thresholds.fair_percent {
+        SummaryStatus::Warning
+    } else {
+        SummaryStatus::Failed
+    };
+    let rating = match battery.rating.as_str() {
+        "excellent" => "battery.rating.excellent",
+        "good" => "battery.rating.good",
+        "fair" => "battery.rating.fair",
+        _ => "battery.rating.poor",
+    };
+    let health = battery.health.round();
+    section(
+        "battery",
+        "detection.categories.battery",
+        status,
+        Some(SummaryValue::Percent(health)),
+        text_with(
+            "a11y.battery.health",
+            [
+                ("health", SummaryValue::Percent(health)),
+                ("cycles", SummaryValue::Count(battery.cycle_count)),
+                ("rating", SummaryValue::Message(rating)),
+            ],
+        ),
+    )
+}
+
+fn storage(storage: &Storage) -> SectionSummary {
+    let healthy = storage.smart_status == "healthy";
+    let sentence = if healthy {
+        text_with(
+            "a11y.storage.healthy",
+            [("model", SummaryValue::Text(storage.model.clone())), ("capacity", SummaryValue::Bytes(storage.capacity))],
+        )
+    } else {
+        text_with(
+            "a11y.storage.unhealthy",
+            [("model", SummaryValue::Text(storage.model.clone())), ("status", SummaryValue::Text(storage.smart_status.clone()))],
+        )
+    };
+    section(
+        "storage",
+        "detection.categories.storage",
+        if healthy { SummaryStatus::Passed } else { SummaryStatus::Warning },
+        Some(SummaryValue::Bytes(storage.capacity)),
+        sentence,
+    )
+}
+
+fn thermal_summary(thermal: &Thermal) -> SectionSummary {
+    let hottest = thermal.sensors.iter().map(|s| s.celsius).fold(None, |max: Option<f64>, c| Some(max.map_or(c, |m| m.max(c))));
+    let cpu = thermal.sensors.iter().find(|s| s.name == "cpu").map(|s| s.celsius);
+    let stalled = thermal.fans.iter().filter(|f| f.stalled).count() as u64;
+
+    let (status, sentence) = if stalled > 0 {
+        (SummaryStatus::Failed, text_with("a11y.thermal.stalledFans", [("count", SummaryValue::Count(stalled))]))
+    } else if let Some(celsius) = cpu.filter(|c| *c >= CPU_HOT_CELSIUS) {
+        (SummaryStatus::Warning, text_with("a11y.thermal.cpuHot", [("temperature", SummaryValue::Celsius(celsius))]))
+    } else if let Some(celsius) = hottest {
+        (SummaryStatus::Passed, text_with("a11y.thermal.normal", [("temperature", SummaryValue::Celsius(celsius))]))
+    } else {
+        (SummaryStatus::Passed, text_with("a11y.thermal.fans", [("count", SummaryValue::Count(thermal.fans.len() as u64))]))
+    };
+    section("thermal", "thermal.title", status, hottest.map(SummaryValue::Celsius), sentence)
+}
+
+fn network(network: &Network) -> SectionSummary {
+    let wifi = &network.wifi;
+    let rssi = wifi.connection.as_ref().and_then(|link| link.rssi_dbm);
+    let bluetooth = SummaryValue::Message(if network.bluetooth.available { "a11y.present" } else { "a11y.absent" });
+
+    let (status, sentence) = if !wifi.available {
+        (SummaryStatus::Failed, text_with("a11y.network.noWifi", [("bluetooth", bluetooth)]))
+    } else if let Some(dbm) = rssi {
+        let status = if dbm <= WEAK_SIGNAL_DBM { SummaryStatus::Warning } else { SummaryStatus::Passed };
+        let id = if status == SummaryStatus::Warning { "a11y.network.weakSignal" } else { "a11y.network.connected" };
+        (status, text_with(id, [("rssi", SummaryValue::Dbm(dbm)), ("bluetooth", bluetooth)]))
+    } else {
+        (SummaryStatus::Passed, text_with("a11y.network.wifi", [("bluetooth", bluetooth)]))
+    };
+    let value = match (rssi, &wifi.generation) {
+        (Some(dbm), _) => Some(SummaryValue::Dbm(dbm)),
+        (None, Some(generation)) => Some(SummaryValue::Text(generation.clone())),
+        (None, None) => None,
+    };
+    section("network", "detection.categories.network", status, value, sentence)
+}
+
+fn display(index: usize, panel: &Display) -> SectionSummary {
+    let name = if panel.name.is_empty() { panel.vendor.clone() } else { panel.name.clone() };
+    let kind = SummaryValue::Message(if panel.is_internal { "display.internal" } else { "display.external" });
+    let sentence = match &panel.native_resolution {
+        Some(resolution) => text_with(
+            "a11y.display.resolution",
+            [
+                ("name", SummaryValue::Text(name)),
+                ("kind", kind),
+                ("resolution", SummaryValue::Text(resolution.clone())),
+                ("connection", SummaryValue::Text(panel.connection_type.clone())),
+            ],
+        ),
+        None => text_with(
+            "a11y.display.panel",
+            [
+                ("name", SummaryValue::Text(name)),
+                ("kind", kind),
+                ("connection", SummaryValue::Text(panel.connection_type.clone())),
+            ],
+        ),
+    };
+    section(
+        &format!("display.{}", index),
+        "display.title",
+        SummaryStatus::Info,
+        panel.native_resolution.clone().map(SummaryValue::Text),
+        sentence,
+    )
+}
+
+fn refurbishment(refurb: &Refurbishment) -> SectionSummary {
+    let parts = SummaryValue::Count(refurb.replaced_parts.len() as u64);
+    let (status, value, sentence) = if refurb.is_refurbished {
+        let confidence = match refurb.confidence.as_str() {
+            "high" => "refurbishment.confidence.high",
+            "medium" => "refurbishment.confidence.medium",
+            _ => "refurbishment.confidence.low",
+        };
+        (
+            SummaryStatus::Warning,
+            "refurbishment.detected",
+            text_with("a11y.refurbishment.detected", [("confidence", SummaryValue::Message(confidence)), ("parts", parts)]),
+        )
+    } else {
+        (
+            SummaryStatus::Passed,
+            "refurbishment.notDetected",
+            text_with("a11y.refurbishment.notDetected", [("parts", parts)]),
+        )
+    };
+    section(
+        "refurbishment",
+        "detection.categories.refurbishment",
+        status,
+        Some(SummaryValue::Message(value)),
+        sentence,
+    )
+}
+
+fn interactive(tests: &Interactive) -> Vec<SectionSummary> {
+    let mut sections = vec![];
+
+    sections.push(interactive_test("screen", tests.screen.tested, tests.screen.skipped, || {
+        if tests.screen.has_dead_pixel {
+            (SummaryStatus::Warning, None, text("a11y.screen.deadPixels"))
+        } else {
+            (SummaryStatus::Passed, None, text("a11y.screen.clean"))
+        }
+    }));
+
+    let keyboard = &tests.keyboard;
+    sections.push(interactive_test("keyboard", keyboard.tested, keyboard.skipped, || {
+        let tested = keyboard.tested_keys.len() as u64;
+        let total = keyboard.total_keys;
+        let value = Some(SummaryValue::Ratio(tested, total));
+        let args = [("tested", SummaryValue::Count(tested)), ("total", SummaryValue::Count(total))];
+        if tested < total {
+            (SummaryStatus::Warning, value, text_with("a11y.keyboard.partial", args))
+        } else {
+            (SummaryStatus::Passed, value, text_with("a11y.keyboard.complete", args))
+        }
+    }));
+
+    let trackpad = &tests.trackpad;
+    sections.push(interactive_test("trackpad", trackpad.tested, trackpad.skipped, || {
+        let failed: u64 = [trackpad.click_working, trackpad.drag_working, trackpad.gesture_working]
+            .iter()
+            .filter(|working| !**working)
+            .count() as u64;
+        if failed > 0 {
+            (SummaryStatus::Warning, None, text_with("a11y.trackpad.issues", [("count", SummaryValue::Count(failed))]))
+        } else {
+            (SummaryStatus::Passed, None, text("a11y.trackpad.working"))
+        }
+    }));
+
+    if let Some(touch_bar) = &tests.touch_bar {
+        // A bar the bridge doesn't expose fails whether or not it was tapped
+        let dead = !touch_bar.display_present || !touch_bar.bridge_responding;
+        sections.push(interactive_test("touchBar", touch_bar.tested || dead, touch_bar.skipped && !dead, || {
+            let tapped = touch_bar.tapped_keys.len() as u64;
+            let total = tapped + touch_bar.missed_keys.len() as u64;
+            let value = Some(SummaryValue::Ratio(tapped, total));
+            let args = [("tapped", SummaryValue::Count(tapped)), ("total", SummaryValue::Count(total))];
+            if dead {
+                (SummaryStatus::Failed, None, text("a11y.touchBar.dead"))
+            } else if tapped == 0 {
+                (SummaryStatus::Failed, value, text_with("a11y.touchBar.partial", args))
+            } else if tapped < total {
+                (SummaryStatus::Warning, value, text_with("a11y.touchBar.partial", args))
+            } else {
+                (SummaryStatus::Passed, value, text_with("a11y.touchBar.complete", args))
+            }
+        }));
+    }
+
+    for (name, test) in [("camera", &tests.camera), ("microphone", &tests.microphone)] {
+        sections.push(interactive_test(name, test.tested, test.skipped, || {
+            if test.working {
+                (SummaryStatus::Passed, None, text(&format!("a11y.{}.working", name)))
+            } else {
+                (SummaryStatus::Failed, None, text(&format!("a11y.{}.notWorking", name)))
+            }
+        }));
+    }
+
+    let speaker = &tests.speaker;
+    sections.push(interactive_test("speaker", speaker.tested, speaker.skipped, || {
+        match (speaker.left_channel, speaker.right_channel) {
+            (true, true) => (SummaryStatus::Passed, None, text("a11y.speaker.working")),
+            (false, false) => (SummaryStatus::Warning, None, text("a11y.speaker.silent")),
+            (left, _) => {
+                let channel = if left { "speaker.rightChannel" } else { "speaker.leftChannel" };
+                (SummaryStatus::Warning, None, text_with("a11y.speaker.channel", [("channel", SummaryValue::Message(channel))]))
+            }
+        }
+    }));
+
+    if let Some(ports) = &tests.ports {
+        sections.push(interactive_test("ports", ports.tested, ports.skipped, || {
+            let count = ports.ports.len() as u64;
+            if ports.dead_port_reported {
+                (SummaryStatus::Failed, None, text_with("a11y.ports.dead", [("count", SummaryValue::Count(count))]))
+            } else {
+                (SummaryStatus::Passed, Some(SummaryValue::Count(count)), text_with("a11y.ports.working", [("count", SummaryValue::Count(count))]))
+            }
+        }));
+    }
+
+    if let Some(sleep) = &tests.sleep {
+        sections.push(interactive_test("sleep", sleep.tested, sleep.skipped, || {
+            if !sleep.slept {
+                (SummaryStatus::Failed, None, text("a11y.sleep.didNotSleep"))
+            } else if sleep.instant_wake || !sleep.abnormal_wakes.is_empty() {
+                (SummaryStatus::Warning, None, text("a11y.sleep.abnormalWake"))
+            } else {
+                (SummaryStatus::Passed, None, text("a11y.sleep.normal"))
+            }
+        }));
+    }
+
+    sections
+}
+
+// Skipped and not-yet-run tests share their wording across tests
+fn interactive_test(
+    name: &str,
+    tested: bool,
+    skipped: bool,
+    verdict: impl FnOnce() -> (SummaryStatus, Option<SummaryValue>, SummaryText),
+) -> SectionSummary {
+    let (status, value, sentence) = if skipped {
+        (SummaryStatus::Skipped, None, text("a11y.interactive.skipped"))
+    } else if !tested {
+        (SummaryStatus::Pending, None, text("a11y.interactive.notTested"))
+    } else {
+        verdict()
+    };
+    section(&format!("interactive.{}", name), &format!("detection.categories.{}", name), status, value, sentence)
+}
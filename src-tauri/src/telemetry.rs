@@ -0,0 +1,373 @@
+// Optional OpenTelemetry export of each finished scan, for fleets that watch
+// their scan stations from an existing collector. A scan becomes one trace (a
+// "scan" span with a child span per check) plus the metrics
+// `quickscan.check.duration`, `quickscan.check.failures` and `quickscan.scans`.
+//
+// Off unless an OTLP endpoint is set. Configured with the standard variables:
+//   OTEL_EXPORTER_OTLP_ENDPOINT          base URL; /v1/traces and /v1/metrics are appended
+//   OTEL_EXPORTER_OTLP_TRACES_ENDPOINT   full URL for traces (likewise _METRICS_)
+//   OTEL_EXPORTER_OTLP_HEADERS           "api-key=secret,tenant=shop1" (likewise _TRACES_, _METRICS_)
+//   OTEL_EXPORTER_OTLP_TIMEOUT           milliseconds, default 10000 (likewise _TRACES_, _METRICS_)
+//   OTEL_EXPORTER_OTLP_PROTOCOL          only "http/json" (likewise _TRACES_, _METRICS_)
+//   OTEL_SERVICE_NAME, OTEL_RESOURCE_ATTRIBUTES
+//   OTEL_TRACES_EXPORTER / OTEL_METRICS_EXPORTER = "none", OTEL_SDK_DISABLED = "true"
+//
+// OTLP over HTTP with JSON bodies is sent through curl, as the digest mail is,
+// so no gRPC or protobuf stack ships with the app.
+
+use crate::error::QuickscanError;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::process::Command;
+use std::time::Duration;
+use sysinfo::System;
+
+const DEFAULT_TIMEOUT_MS: u64 = 10_000;
+// The SDKs' default histogram buckets, in milliseconds
+const DURATION_BOUNDS_MS: [f64; 15] =
+    [0.0, 5.0, 10.0, 25.0, 50.0, 75.0, 100.0, 250.0, 500.0, 750.0, 1000.0, 2500.0, 5000.0, 7500.0, 10000.0];
+
+const SPAN_KIND_INTERNAL: u8 = 1;
+const STATUS_OK: u8 = 1;
+const STATUS_ERROR: u8 = 2;
+const TEMPORALITY_DELTA: u8 = 1;
+
+#[derive(Deserialize)]
+pub struct ScanTelemetry {
+    pub report_id: String,
+    pub started_at_ms: u64, // Unix milliseconds
+    pub ended_at_ms: u64,
+    pub checks: Vec<CheckTiming>,
+}
+
+#[derive(Deserialize)]
+pub struct CheckTiming {
+    pub id: String, // detection step, e.g. "battery", "keyboard"
+    pub status: String, // "passed", "warning", "failed" or "skipped"
+    pub started_at_ms: u64,
+    pub ended_at_ms: u64,
+    #[serde(default)]
+    pub detail: Option<String>, // what the step showed, e.g. why it failed
+}
+
+// Where one signal goes
+struct Exporter {
+    url: String,
+    headers: Vec<(String, String)>,
+    timeout: Duration,
+}
+
+// Ok when nothing is configured; an Err names the signal that couldn't be sent
+#[tauri::command]
+pub async fn export_scan_telemetry(scan: ScanTelemetry) -> Result<(), QuickscanError> {
+    crate::process::blocking(move || export(&scan)).await
+}
+
+fn export(scan: &ScanTelemetry) -> Result<(), QuickscanError> {
+    if env("OTEL_SDK_DISABLED").is_some_and(|v| v.eq_ignore_ascii_case("true")) {
+        return Ok(());
+    }
+    let resource = resource();
+    let mut errors = vec![];
+    for (signal, exporter_var) in [("traces", "OTEL_TRACES_EXPORTER"), ("metrics", "OTEL_METRICS_EXPORTER")] {
+        if env(exporter_var).is_some_and(|v| v.eq_ignore_ascii_case("none")) {
+            continue;
+        }
+        let exporter = match exporter(signal) {
+            Ok(Some(exporter)) => exporter,
+            Ok(None) => continue,
+            Err(e) => {
+                errors.push(e);
+                continue;
+            }
+        };
+        let body = if signal == "traces" { traces(scan, &resource) } else { metrics(scan, &resource) };
+        if let Err(e) = post(signal, &exporter, &body) {
+            errors.push(e);
+        }
+    }
+    match errors.len() {
+        0 => Ok(()),
+        1 => Err(errors.remove(0)),
+        _ => Err(QuickscanError::command_failed("OTLP", errors.iter().map(|e| e.to_string()).collect::<Vec<_>>().join("; "))),
+    }
+}
+
+// Unset and empty are the same to the OpenTelemetry SDKs
+fn env(name: &str) -> Option<String> {
+    std::env::var(name).ok().map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+// The signal's own variable wins over the general one
+fn signal_env(signal: &str, suffix: &str) -> Option<String> {
+    env(&format!("OTEL_EXPORTER_OTLP_{}_{}", signal.to_uppercase(), suffix))
+        .or_else(|| env(&format!("OTEL_EXPORTER_OTLP_{}", suffix)))
+}
+
+fn exporter(signal: &str) -> Result<Option<Exporter>, QuickscanError> {
+    let source = format!("OTLP {}", signal);
+    let url = match env(&format!("OTEL_EXPORTER_OTLP_{}_ENDPOINT", signal.to_uppercase())) {
+        Some(url) => url,
+        None => match env("OTEL_EXPORTER_OTLP_ENDPOINT") {
+            Some(base) => format!("{}/v1/{}", base.trim_end_matches('/'), signal),
+            None => return Ok(None),
+        },
+    };
+    if let Some(protocol) = signal_env(signal, "PROTOCOL") {
+        if protocol != "http/json" {
+            return Err(QuickscanError::command_failed(&source, format!("protocol \"{}\" is not supported, set http/json", protocol)));
+        }
+    }
+    let headers = signal_env(signal, "HEADERS").map(|list| key_values(&list)).unwrap_or_default();
+    let timeout_ms = match signal_env(signal, "TIMEOUT") {
+        Some(ms) => ms.parse().map_err(|_| QuickscanError::parse(&source, format!("timeout \"{}\" is not a number of milliseconds", ms)))?,
+        None => DEFAULT_TIMEOUT_MS,
+    };
+    Ok(Some(Exporter { url, headers, timeout: Duration::from_millis(timeout_ms) }))
+}
+
+// "key1=value1,key2=value2" with percent-encoded values, as in
+// OTEL_EXPORTER_OTLP_HEADERS and OTEL_RESOURCE_ATTRIBUTES
+fn key_values(list: &str) -> Vec<(String, String)> {
+    list.split(',')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(key, value)| (key.trim().to_string(), percent_decode(value.trim())))
+        .filter(|(key, _)| !key.is_empty())
+        .collect()
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes.get(i + 1..i + 3).and_then(|h| std::str::from_utf8(h).ok()).and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn attribute(key: &str, value: Value) -> Value {
+    let value = match value {
+        Value::Bool(b) => json!({ "boolValue": b }),
+        Value::Number(n) if n.is_i64() || n.is_u64() => json!({ "intValue": n.to_string() }),
+        Value::Number(n) => json!({ "doubleValue": n }),
+        other => json!({ "stringValue": other.as_str().map(|s| s.to_string()).unwrap_or_else(|| other.to_string()) }),
+    };
+    json!({ "key": key, "value": value })
+}
+
+// OTEL_SERVICE_NAME beats a service.name in OTEL_RESOURCE_ATTRIBUTES
+fn resource() -> Value {
+    let mut attributes = key_values(&env("OTEL_RESOURCE_ATTRIBUTES").unwrap_or_default());
+    let mut set = |key: &str, value: String, overwrite: bool| match attributes.iter_mut().find(|(k, _)| k == key) {
+        Some(existing) if overwrite => existing.1 = value,
+        Some(_) => {}
+        None => attributes.push((key.to_string(), value)),
+    };
+    set("service.name", env("OTEL_SERVICE_NAME").unwrap_or_else(|| "quickscan".to_string()), env("OTEL_SERVICE_NAME").is_some());
+    set("service.version", env!("CARGO_PKG_VERSION").to_string(), false);
+    if let Some(host) = System::host_name() {
+        set("host.name", host, false);
+    }
+    set("os.type", std::env::consts::OS.to_string(), false);
+    json!({ "attributes": attributes.into_iter().map(|(k, v)| attribute(&k, Value::String(v))).collect::<Vec<_>>() })
+}
+
+fn scope() -> Value {
+    json!({ "name": "quickscan", "version": env!("CARGO_PKG_VERSION") })
+}
+
+// Random enough to keep traces apart; the std hasher is seeded per process
+fn random_hex(bytes: usize) -> String {
+    (0..bytes.div_ceil(8))
+        .map(|_| format!("{:016x}", RandomState::new().build_hasher().finish()))
+        .collect::<String>()[..bytes * 2]
+        .to_string()
+}
+
+fn nanos(ms: u64) -> String {
+    (ms as u128 * 1_000_000).to_string()
+}
+
+fn traces(scan: &ScanTelemetry, resource: &Value) -> Value {
+    let trace_id = random_hex(16);
+    let scan_span_id = random_hex(8);
+    let failed: Vec<&str> = scan.checks.iter().filter(|c| c.status == "failed").map(|c| c.id.as_str()).collect();
+
+    let mut spans = vec![json!({
+        "traceId": trace_id,
+        "spanId": scan_span_id,
+        "name": "scan",
+        "kind": SPAN_KIND_INTERNAL,
+        "startTimeUnixNano": nanos(scan.started_at_ms),
+        "endTimeUnixNano": nanos(scan.ended_at_ms),
+        "attributes": [
+            attribute("quickscan.report_id", json!(scan.report_id)),
+            attribute("quickscan.checks", json!(scan.checks.len())),
+            attribute("quickscan.checks.failed", json!(failed.len())),
+        ],
+        "status": if failed.is_empty() {
+            json!({ "code": STATUS_OK })
+        } else {
+            json!({ "code": STATUS_ERROR, "message": format!("failed: {}", failed.join(", ")) })
+        },
+    })];
+    for check in &scan.checks {
+        let mut attributes = vec![
+            attribute("quickscan.check", json!(check.id)),
+            attribute("quickscan.check.status", json!(check.status)),
+        ];
+        if let Some(detail) = &check.detail {
+            attributes.push(attribute("quickscan.check.detail", json!(detail)));
+        }
+        // A warning is a finding about the machine, not a failed span
+        let status = if check.status == "failed" {
+            json!({ "code": STATUS_ERROR, "message": check.detail.clone().unwrap_or_default() })
+        } else {
+            json!({ "code": STATUS_OK })
+        };
+        spans.push(json!({
+            "traceId": trace_id,
+            "spanId": random_hex(8),
+            "parentSpanId": scan_span_id,
+            "name": format!("check {}", check.id),
+            "kind": SPAN_KIND_INTERNAL,
+            "startTimeUnixNano": nanos(check.started_at_ms),
+            "endTimeUnixNano": nanos(check.ended_at_ms),
+            "attributes": attributes,
+            "status": status,
+        }));
+    }
+
+    json!({ "resourceSpans": [{ "resource": resource, "scopeSpans": [{ "scope": scope(), "spans": spans }] }] })
+}
+
+// Delta temporality: each scan reports only itself, and the backend sums them
+fn metrics(scan: &ScanTelemetry, resource: &Value) -> Value {
+    let start = nanos(scan.started_at_ms);
+    let end = nanos(scan.ended_at_ms);
+
+    let durations: Vec<Value> = scan
+        .checks
+        .iter()
+        .map(|check| {
+            let duration = check.ended_at_ms.saturating_sub(check.started_at_ms) as f64;
+            let bucket = DURATION_BOUNDS_MS.iter().position(|&bound| duration <= bound).unwrap_or(DURATION_BOUNDS_MS.len());
+            let buckets: Vec<String> = (0..=DURATION_BOUNDS_MS.len()).map(|i| if i == bucket { "1" } else { "0" }.to_string()).collect();
+            json!({
+                "attributes": [
+                    attribute("quickscan.check", json!(check.id)),
+                    attribute("quickscan.check.status", json!(check.status)),
+                ],
+                "startTimeUnixNano": start,
+                "timeUnixNano": end,
+                "count": "1",
+                "sum": duration,
+                "min": duration,
+                "max": duration,
+                "bucketCounts": buckets,
+                "explicitBounds": DURATION_BOUNDS_MS,
+            })
+        })
+        .collect();
+
+    let failures: Vec<Value> = scan
+        .checks
+        .iter()
+        .filter(|check| check.status == "failed")
+        .map(|check| {
+            json!({
+                "attributes": [attribute("quickscan.check", json!(check.id))],
+                "startTimeUnixNano": start,
+                "timeUnixNano": end,
+                "asInt": "1",
+            })
+        })
+        .collect();
+
+    let held_back = scan.checks.iter().any(|check| check.status == "failed");
+    let metrics = json!([
+        {
+            "name": "quickscan.check.duration",
+            "description": "Time each check of a scan took, including time waiting on the operator",
+            "unit": "ms",
+            "histogram": { "aggregationTemporality": TEMPORALITY_DELTA, "dataPoints": durations },
+        },
+        {
+            "name": "quickscan.check.failures",
+            "description": "Checks that failed",
+            "unit": "{failure}",
+            "sum": { "aggregationTemporality": TEMPORALITY_DELTA, "isMonotonic": true, "dataPoints": failures },
+        },
+        {
+            "name": "quickscan.scans",
+            "description": "Finished scans",
+            "unit": "{scan}",
+            "sum": {
+                "aggregationTemporality": TEMPORALITY_DELTA,
+                "isMonotonic": true,
+                "dataPoints": [{
+                    "attributes": [attribute("quickscan.scan.failed", json!(held_back))],
+                    "startTimeUnixNano": start,
+                    "timeUnixNano": end,
+                    "asInt": "1",
+                }],
+            },
+        },
+    ]);
+
+    json!({ "resourceMetrics": [{ "resource": resource, "scopeMetrics": [{ "scope": scope(), "metrics": metrics }] }] })
+}
+
+// Headers often carry an API key, so they go in a curl config file rather
+// than on the command line
+fn post(signal: &str, exporter: &Exporter, body: &Value) -> Result<(), QuickscanError> {
+    let source = format!("OTLP {}", signal);
+    let temp = |extension: &str| std::env::temp_dir().join(format!("quickscan-otlp-{}-{}.{}", std::process::id(), signal, extension));
+    let body_path = temp("json");
+    let config_path = temp("curlrc");
+
+    let quote = |value: &str| format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""));
+    let mut curl_config = vec![
+        format!("url = {}", quote(&exporter.url)),
+        format!("data-binary = {}", quote(&format!("@{}", body_path.to_string_lossy()))),
+        format!("header = {}", quote("Content-Type: application/json")),
+        format!("max-time = {}", exporter.timeout.as_secs_f64()),
+        "fail".to_string(),
+        "silent".to_string(),
+        "show-error".to_string(),
+    ];
+    curl_config.extend(exporter.headers.iter().map(|(key, value)| format!("header = {}", quote(&format!("{}: {}", key, value)))));
+
+    let result = crate::digest::write_private(&body_path, &body.to_string())
+        .and_then(|()| crate::digest::write_private(&config_path, &(curl_config.join("\n") + "\n")))
+        .map_err(|e| QuickscanError::command_failed(&source, e))
+        .and_then(|()| {
+            let mut command = Command::new("curl");
+            command.arg("--config").arg(&config_path);
+            // curl's own max-time ends it first; this only catches a hung curl
+            crate::process::run_cmd_with_timeout(&mut command, exporter.timeout + Duration::from_secs(5))
+        })
+        .and_then(|output| {
+            if output.status.success() {
+                Ok(())
+            } else {
+                Err(QuickscanError::command_failed(&source, format!("curl: {}", String::from_utf8_lossy(&output.stderr).trim())))
+            }
+        });
+    let _ = std::fs::remove_file(&body_path);
+    let _ = std::fs::remove_file(&config_path);
+    result
+}
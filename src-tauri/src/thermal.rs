@@ -0,0 +1,216 @@
+// Fans and temperature sensors (`get_thermal_info`). A fan that no longer
+// spins or a CPU that runs hot at idle is one of the commonest reasons to turn
+// down a used laptop, and neither shows up anywhere else in the scan.
+
+use crate::error::QuickscanError;
+use crate::probe_scheduler::ProbeScheduler;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct FanReading {
+    pub index: u32,
+    pub current_rpm: u32,
+    pub min_rpm: Option<u32>, // Windows: the range seen since the monitor started, not controller limits
+    pub max_rpm: Option<u32>,
+    pub stalled: bool, // stopped although the controller asks for a minimum speed
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct TemperatureSensor {
+    pub name: String, // "cpu", "gpu", "ssd", "battery" or "system"
+    pub celsius: f64,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct ThermalInfo {
+    pub fans: Vec<FanReading>,
+    pub sensors: Vec<TemperatureSensor>, // hottest reading per sensor name
+}
+
+#[tauri::command]
+pub async fn get_thermal_info(probes: tauri::State<'_, ProbeScheduler>) -> Result<ThermalInfo, QuickscanError> {
+    probes.run(thermal_info).await
+}
+
+pub fn thermal_info() -> Result<ThermalInfo, QuickscanError> {
+    #[cfg(target_os = "macos")]
+    {
+        thermal_info_macos()
+    }
+    #[cfg(target_os = "windows")]
+    {
+        thermal_info_windows()
+    }
+    #[cfg(target_os = "linux")]
+    {
+        Ok(thermal_info_linux())
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        Err(QuickscanError::unsupported("thermal_info"))
+    }
+}
+
+impl ThermalInfo {
+    // Absent sensors read as 0 or large negative values on every platform
+    fn add_sensor(&mut self, name: &str, celsius: f64) {
+        if !(1.0..150.0).contains(&celsius) {
+            return;
+        }
+        match self.sensors.iter_mut().find(|s| s.name == name) {
+            Some(sensor) => sensor.celsius = sensor.celsius.max(celsius),
+            None => self.sensors.push(TemperatureSensor { name: name.to_string(), celsius }),
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn thermal_info_macos() -> Result<ThermalInfo, QuickscanError> {
+    use crate::smc::Smc;
+
+    // Intel keys first, then Apple silicon; models only have some of them
+    const SENSOR_KEYS: &[(&str, &[&str])] = &[
+        ("cpu", &["TC0P", "TC0D", "TC0E", "TC0F", "Tp09", "Tp0T", "Tp01", "Tp05", "Tp0D"]),
+        ("gpu", &["TG0P", "TG0D", "Tg05", "Tg0D", "Tg0f"]),
+        ("ssd", &["TH0a", "TH0A", "TH0x", "TH0b"]),
+        ("battery", &["TB0T", "TB1T", "TB2T"]),
+    ];
+
+    let smc = Smc::open().ok_or_else(|| QuickscanError::command_failed("AppleSMC", "could not open the SMC"))?;
+    let mut info = ThermalInfo::default();
+
+    let fan_count = smc.read("FNum").unwrap_or(0.0) as u32;
+    for index in 0..fan_count {
+        let read = |suffix: &str| smc.read(&format!("F{}{}", index, suffix)).map(|rpm| rpm.round() as u32);
+        let current_rpm = read("Ac").unwrap_or(0);
+        let min_rpm = read("Mn");
+        info.fans.push(FanReading {
+            index,
+            current_rpm,
+            min_rpm,
+            max_rpm: read("Mx"),
+            // Apple silicon fans stop completely at low load
+            stalled: !cfg!(target_arch = "aarch64") && current_rpm == 0 && min_rpm.is_some_and(|min| min > 0),
+        });
+    }
+
+    for (name, keys) in SENSOR_KEYS {
+        if let Some(celsius) = keys.iter().filter_map(|key| smc.read(key)).find(|c| (1.0..150.0).contains(c)) {
+            info.add_sensor(name, celsius);
+        }
+    }
+    Ok(info)
+}
+
+#[cfg(target_os = "windows")]
+fn thermal_info_windows() -> Result<ThermalInfo, QuickscanError> {
+    use crate::wmi_provider::{WmiProvider, LIBRE_HARDWARE_MONITOR, OPEN_HARDWARE_MONITOR, WMI};
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct Sensor {
+        identifier: Option<String>, // "/intelcpu/0/temperature/0", "/lpc/nct6798d/fan/1", ...
+        sensor_type: Option<String>,
+        value: Option<f32>,
+        min: Option<f32>,
+        max: Option<f32>,
+    }
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct ThermalZone {
+        current_temperature: Option<u32>, // tenths of a kelvin
+    }
+
+    // Windows has no fan or sensor API of its own; (Libre|Open)HardwareMonitor
+    // publish theirs over WMI while they are running
+    let wmi = WmiProvider::global();
+    let sensors = [LIBRE_HARDWARE_MONITOR, OPEN_HARDWARE_MONITOR].into_iter().find_map(|namespace| {
+        wmi.query::<Sensor>(
+            namespace,
+            "SELECT Identifier, SensorType, Value, Min, Max FROM Sensor WHERE SensorType = 'Fan' OR SensorType = 'Temperature'",
+        )
+        .ok()
+        .filter(|sensors| !sensors.is_empty())
+    });
+
+    let mut info = ThermalInfo::default();
+    if let Some(sensors) = sensors {
+        for sensor in sensors {
+            let identifier = sensor.identifier.unwrap_or_default().to_lowercase();
+            let value = sensor.value.unwrap_or(0.0);
+            if sensor.sensor_type.as_deref() == Some("Fan") {
+                info.fans.push(FanReading {
+                    index: info.fans.len() as u32,
+                    current_rpm: value.round() as u32,
+                    min_rpm: sensor.min.map(|rpm| rpm.round() as u32),
+                    max_rpm: sensor.max.map(|rpm| rpm.round() as u32),
+                    stalled: false,
+                });
+                continue;
+            }
+            let name = if identifier.contains("cpu") {
+                "cpu"
+            } else if identifier.contains("gpu") {
+                "gpu"
+            } else if identifier.contains("hdd") || identifier.contains("nvme") || identifier.contains("ssd") {
+                "ssd"
+            } else if identifier.contains("battery") {
+                "battery"
+            } else {
+                "system"
+            };
+            info.add_sensor(name, value as f64);
+        }
+        return Ok(info);
+    }
+
+    // ACPI thermal zones are the fallback; they need an elevated process and
+    // usually track the CPU package or the chassis
+    for zone in wmi.query::<ThermalZone>(WMI, "SELECT CurrentTemperature FROM MSAcpi_ThermalZoneTemperature")? {
+        if let Some(tenths_kelvin) = zone.current_temperature {
+            info.add_sensor("system", tenths_kelvin as f64 / 10.0 - 273.15);
+        }
+    }
+    Ok(info)
+}
+
+#[cfg(target_os = "linux")]
+fn thermal_info_linux() -> ThermalInfo {
+    let mut info = ThermalInfo::default();
+    let Ok(entries) = std::fs::read_dir("/sys/class/hwmon") else {
+        return info;
+    };
+    let mut devices: Vec<_> = entries.flatten().map(|entry| entry.path()).collect();
+    devices.sort();
+
+    for device in devices {
+        let read = |file: &str| std::fs::read_to_string(device.join(file)).ok().and_then(|s| s.trim().parse::<i64>().ok());
+        let driver = std::fs::read_to_string(device.join("name")).unwrap_or_default();
+        let name = match driver.trim() {
+            "coretemp" | "k10temp" | "zenpower" | "cpu_thermal" => "cpu",
+            "amdgpu" | "nouveau" | "radeon" | "i915" | "xe" => "gpu",
+            "nvme" | "drivetemp" => "ssd",
+            driver if driver.starts_with("BAT") || driver == "battery" => "battery",
+            _ => "system",
+        };
+
+        // temp1_input, fan1_input, ... are numbered from 1 with possible gaps
+        for n in 1..=32 {
+            if let Some(millidegrees) = read(&format!("temp{}_input", n)) {
+                info.add_sensor(name, millidegrees as f64 / 1000.0);
+            }
+            if let Some(rpm) = read(&format!("fan{}_input", n)) {
+                let min_rpm = read(&format!("fan{}_min", n)).map(|rpm| rpm as u32);
+                info.fans.push(FanReading {
+                    index: info.fans.len() as u32,
+                    current_rpm: rpm as u32,
+                    min_rpm,
+                    max_rpm: read(&format!("fan{}_max", n)).map(|rpm| rpm as u32),
+                    stalled: read(&format!("fan{}_alarm", n)) == Some(1)
+                        || (rpm == 0 && min_rpm.is_some_and(|min| min > 0)),
+                });
+            }
+        }
+    }
+    info
+}
@@ -0,0 +1,89 @@
+// Touch Bar presence and health for `get_touch_bar_info`. A dead Touch Bar
+// costs as much as a keyboard to replace and a seller can hide it by turning
+// the brightness down, so the check doesn't trust what the user can see:
+// the bridge chip (T1 or T2) has to answer, its USB bus has to expose the
+// Touch Bar display, and TouchBarServer has to be drawing on it. The guided
+// tap test in the UI then proves the digitizer with fn + Esc/F1–F12.
+
+use crate::error::QuickscanError;
+use crate::probe_scheduler::ProbeScheduler;
+use serde::Serialize;
+
+#[derive(Serialize, Default)]
+pub struct TouchBarInfo {
+    pub expected: Option<bool>, // from the model manifest; None for models it doesn't list
+    pub display_present: bool, // the bridge exposes the Touch Bar display
+    pub backlight_present: bool,
+    pub bridge: Option<String>, // "Apple T2 Security Chip"
+    pub bridge_firmware: Option<String>,
+    pub bridge_responding: bool, // answered system_profiler in time
+    pub server_running: bool, // TouchBarServer is drawing the keys
+}
+
+#[tauri::command]
+pub async fn get_touch_bar_info(probes: tauri::State<'_, ProbeScheduler>) -> Result<TouchBarInfo, QuickscanError> {
+    probes.run(touch_bar_info).await
+}
+
+pub fn touch_bar_info() -> Result<TouchBarInfo, QuickscanError> {
+    #[cfg(target_os = "macos")]
+    {
+        Ok(macos::touch_bar_info())
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        Err(QuickscanError::unsupported("touch_bar"))
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use super::TouchBarInfo;
+    use crate::{component_manifest, iokit, process};
+    use std::process::Command;
+    use std::time::Duration;
+
+    // A healthy bridge answers in a second or two; a hung one never does
+    const BRIDGE_TIMEOUT: Duration = Duration::from_secs(15);
+
+    pub fn touch_bar_info() -> TouchBarInfo {
+        let mut info = TouchBarInfo {
+            expected: component_manifest::model_identifier()
+                .and_then(|model| component_manifest::expected_components(&model))
+                .map(|expected| expected.touch_bar),
+            ..Default::default()
+        };
+
+        // The Touch Bar hangs off the bridge's internal USB bus: "Touch Bar
+        // Display" and "Touch Bar Backlight" on T2 Macs, "iBridge Display" on T1
+        for device in iokit::all_service_properties("IOUSBHostDevice") {
+            let Some(product) = iokit::string_property(&device, "USB Product Name") else {
+                continue;
+            };
+            if product == "Touch Bar Display" || product == "iBridge Display" {
+                info.display_present = true;
+            } else if product == "Touch Bar Backlight" {
+                info.backlight_present = true;
+            }
+        }
+
+        // {"SPiBridgeDataType": [{"ibridge_model_name": "Apple T2 Security Chip", "ibridge_build": "20P420"}]}
+        let mut command = Command::new("system_profiler");
+        command.args(["SPiBridgeDataType", "-json"]);
+        if let Ok(output) = process::run_probe(&mut command, BRIDGE_TIMEOUT) {
+            let bridge = serde_json::from_slice::<serde_json::Value>(&output.stdout)
+                .ok()
+                .and_then(|json| json.get("SPiBridgeDataType")?.as_array()?.first().cloned());
+            if let Some(bridge) = bridge {
+                let text = |key: &str| bridge.get(key).and_then(|v| v.as_str()).map(str::to_string);
+                info.bridge = text("ibridge_model_name");
+                info.bridge_firmware = text("ibridge_build");
+                info.bridge_responding = info.bridge.is_some();
+            }
+        }
+
+        info.server_running = process::run_cmd(Command::new("pgrep").args(["-x", "TouchBarServer"]))
+            .is_ok_and(|output| output.status.success());
+        info
+    }
+}
@@ -0,0 +1,96 @@
+// How much the machine has been used against how old it is, for
+// `get_usage_estimate`. A three-year-old laptop with 200 hours on it and one
+// with 15,000 are not the same purchase, whatever the listing says.
+//
+// Age comes from the earliest date the machine gives for itself: the week in
+// an Apple serial, the battery's manufacture date, the first OS install the
+// install logs remember, the model's release year. Only the serial's is
+// exact; a replaced battery or a clean install only ever makes the others
+// later, so the earliest of them is the closest to the truth. Use comes from the boot drive's power-on hours and the battery's
+// cycle count. Either part may have been replaced, which resets its count, so
+// the higher of the two is taken.
+
+use crate::error::QuickscanError;
+use crate::probe_scheduler::ProbeScheduler;
+use chrono::NaiveDate;
+use serde::Serialize;
+
+// Hours of use behind one battery cycle; most use is on the charger, so this
+// is a floor rather than an estimate of its own
+const HOURS_PER_CYCLE: f64 = 5.0;
+const LIGHT_HOURS_PER_DAY: f64 = 2.0;
+const HEAVY_HOURS_PER_DAY: f64 = 8.0;
+
+#[derive(Serialize, Default)]
+pub struct UsageEstimate {
+    pub first_seen: Option<String>, // YYYY-MM-DD
+    pub first_seen_source: Option<&'static str>, // "serial", "battery", "os_install" or "model_year"
+    pub calendar_age_days: Option<i64>,
+    pub power_on_hours: Option<u64>, // boot drive
+    pub battery_cycles: Option<u32>,
+    pub estimated_usage_hours: Option<u64>,
+    pub hours_per_day: Option<f64>,
+    // "light", "typical", "heavy", or "exceeds_age" when the hours don't fit
+    // in the machine's age (a drive from an older machine, or a wrong date)
+    pub usage: Option<&'static str>,
+}
+
+#[tauri::command]
+pub async fn get_usage_estimate(probes: tauri::State<'_, ProbeScheduler>) -> Result<UsageEstimate, QuickscanError> {
+    probes.run(usage_estimate).await
+}
+
+fn first_seen(battery: Option<&crate::BatteryInfo>) -> Option<(String, &'static str)> {
+    // Only Apple serials carry a date, and only before 2021; it is the one exact date
+    #[cfg(target_os = "macos")]
+    if let Some(date) = crate::serial_decode::decode(&crate::get_serial_number()).manufacture_date() {
+        return Some((date, "serial"));
+    }
+    let mut dates: Vec<(String, &'static str)> = vec![];
+    dates.extend(battery.and_then(|battery| battery.manufacture_date.clone()).map(|date| (date, "battery")));
+    if let Ok(history) = crate::os_install::os_install_history() {
+        dates.extend(history.original_install.map(|date| (date, "os_install")));
+    }
+    // Mid-year, so a model released late in the year isn't aged by months
+    if let Some(year) = crate::device_model::device_model().ok().and_then(|model| model.release_year) {
+        dates.push((format!("{}-07-01", year), "model_year"));
+    }
+    dates.into_iter().min()
+}
+
+pub fn usage_estimate() -> Result<UsageEstimate, QuickscanError> {
+    let mut estimate = UsageEstimate::default();
+    let battery = crate::battery_info().ok().flatten();
+
+    if let Some((date, source)) = first_seen(battery.as_ref()) {
+        let today = chrono::Local::now().date_naive();
+        estimate.calendar_age_days =
+            NaiveDate::parse_from_str(&date, "%Y-%m-%d").ok().map(|date| (today - date).num_days().max(0));
+        estimate.first_seen = Some(date);
+        estimate.first_seen_source = Some(source);
+    }
+
+    let disks = crate::storage_health().unwrap_or_default();
+    let boot = disks.iter().find(|disk| disk.is_boot).or_else(|| disks.iter().find(|disk| disk.is_internal));
+    estimate.power_on_hours = boot.and_then(|disk| disk.power_on_hours);
+    // Desktops have no battery; a reported pack with no cycles says nothing
+    estimate.battery_cycles = battery.map(|battery| battery.cycle_count).filter(|cycles| *cycles > 0);
+
+    let from_cycles = estimate.battery_cycles.map(|cycles| (f64::from(cycles) * HOURS_PER_CYCLE) as u64);
+    estimate.estimated_usage_hours = estimate.power_on_hours.into_iter().chain(from_cycles).max();
+
+    if let (Some(hours), Some(days)) = (estimate.estimated_usage_hours, estimate.calendar_age_days.filter(|days| *days > 0)) {
+        let per_day = hours as f64 / days as f64;
+        estimate.hours_per_day = Some((per_day * 10.0).round() / 10.0);
+        estimate.usage = Some(if per_day > 24.0 {
+            "exceeds_age"
+        } else if per_day >= HEAVY_HOURS_PER_DAY {
+            "heavy"
+        } else if per_day < LIGHT_HOURS_PER_DAY {
+            "light"
+        } else {
+            "typical"
+        });
+    }
+    Ok(estimate)
+}
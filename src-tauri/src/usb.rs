@@ -0,0 +1,216 @@
+// USB and Thunderbolt devices (`get_usb_devices`). Dead ports are a common
+// used-laptop defect; plugging a test device into each port in turn while
+// `watch_hardware_changes` runs shows which ones still work and at what
+// speed.
+
+use crate::error::QuickscanError;
+use crate::probe_scheduler::ProbeScheduler;
+use serde::Serialize;
+
+#[derive(Serialize, Clone)]
+pub struct UsbDevice {
+    pub id: String, // stable while the device stays plugged in
+    pub kind: &'static str, // "usb" or "thunderbolt"
+    pub name: String,
+    pub vendor: Option<String>,
+    pub vendor_id: Option<u16>,
+    pub product_id: Option<u16>,
+    pub speed_mbps: Option<f64>, // negotiated, not the port's maximum
+    pub port: Option<String>, // the machine's own port it hangs off, through any hubs
+}
+
+#[tauri::command]
+pub async fn get_usb_devices(probes: tauri::State<'_, ProbeScheduler>) -> Result<Vec<UsbDevice>, QuickscanError> {
+    probes.run(usb_devices).await
+}
+
+pub fn usb_devices() -> Result<Vec<UsbDevice>, QuickscanError> {
+    #[cfg(target_os = "macos")]
+    {
+        Ok(usb_devices_macos())
+    }
+    #[cfg(target_os = "windows")]
+    {
+        usb_devices_windows()
+    }
+    #[cfg(target_os = "linux")]
+    {
+        Ok(usb_devices_linux())
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        Err(QuickscanError::unsupported("usb_devices"))
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn usb_devices_macos() -> Vec<UsbDevice> {
+    use crate::iokit;
+
+    let mut devices = vec![];
+    for device in iokit::all_service_properties("IOUSBHostDevice") {
+        let number = |key: &str| device.get(key).and_then(|v| v.as_i64());
+        // 0xBBPPPPPP: the bus, then one nibble per hop from the root port
+        let Some(location) = number("locationID") else {
+            continue;
+        };
+        let ports = location & 0x00ff_ffff;
+        if ports == 0 {
+            continue; // a root hub, not a device
+        }
+        let root_port = (ports >> 20) & 0xf;
+        let name = iokit::string_property(&device, "USB Product Name")
+            .or_else(|| iokit::string_property(&device, "kUSBProductString"))
+            .unwrap_or_else(|| "USB device".to_string());
+        devices.push(UsbDevice {
+            id: number("sessionID").unwrap_or(location).to_string(),
+            kind: "usb",
+            name,
+            vendor: iokit::string_property(&device, "USB Vendor Name")
+                .or_else(|| iokit::string_property(&device, "kUSBVendorString")),
+            vendor_id: number("idVendor").map(|id| id as u16),
+            product_id: number("idProduct").map(|id| id as u16),
+            speed_mbps: number("Device Speed").and_then(|speed| match speed {
+                0 => Some(1.5),
+                1 => Some(12.0),
+                2 => Some(480.0),
+                3 => Some(5000.0),
+                4 => Some(10000.0),
+                5 => Some(20000.0),
+                _ => None,
+            }),
+            port: Some(format!("{}-{}", location >> 24, root_port)),
+        });
+    }
+
+    // Every Thunderbolt controller shows up as a switch; route 0 is the Mac's own
+    for switch in iokit::all_service_properties("IOThunderboltSwitch") {
+        let number = |key: &str| switch.get(key).and_then(|v| v.as_i64());
+        let route = number("Route String").unwrap_or(0);
+        if route == 0 {
+            continue;
+        }
+        devices.push(UsbDevice {
+            id: number("UID").unwrap_or(route).to_string(),
+            kind: "thunderbolt",
+            name: iokit::string_property(&switch, "Device Model Name").unwrap_or_else(|| "Thunderbolt device".to_string()),
+            vendor: iokit::string_property(&switch, "Device Vendor Name"),
+            vendor_id: number("Vendor ID").map(|id| id as u16),
+            product_id: number("Device ID").map(|id| id as u16),
+            speed_mbps: None,
+            // The lowest byte of the route is the port on the Mac
+            port: Some(format!("tb-{}", route & 0xff)),
+        });
+    }
+    devices
+}
+
+#[cfg(target_os = "windows")]
+fn usb_devices_windows() -> Result<Vec<UsbDevice>, QuickscanError> {
+    use crate::wmi_provider::{WmiProvider, CIMV2};
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct PnpEntity {
+        #[serde(rename = "DeviceID")]
+        device_id: String,
+        name: Option<String>,
+        manufacturer: Option<String>,
+    }
+
+    // WMI has neither the negotiated speed nor the port; USB4 and Thunderbolt
+    // devices appear here through their USB side
+    let entities = WmiProvider::global()
+        .query::<PnpEntity>(CIMV2, "SELECT DeviceID, Name, Manufacturer FROM Win32_PnPEntity WHERE DeviceID LIKE 'USB%'")?;
+    let devices = entities
+        .into_iter()
+        .filter_map(|entity| {
+            // "USB\VID_046D&PID_C52B\5&2F4A3B1&0&2"; composite devices list
+            // each interface again with "&MI_nn"
+            let upper = entity.device_id.to_uppercase();
+            if upper.contains("&MI_") {
+                return None;
+            }
+            let id_after = |tag: &str| {
+                let start = upper.find(tag)? + tag.len();
+                u16::from_str_radix(upper.get(start..start + 4)?, 16).ok()
+            };
+            let vendor_id = id_after("VID_")?;
+            Some(UsbDevice {
+                id: entity.device_id.clone(),
+                kind: "usb",
+                name: entity.name.unwrap_or_else(|| "USB device".to_string()),
+                // "(Standard USB Host Controller)" and friends name no vendor
+                vendor: entity.manufacturer.filter(|m| !m.starts_with('(')),
+                vendor_id: Some(vendor_id),
+                product_id: id_after("PID_"),
+                speed_mbps: None,
+                port: None,
+            })
+        })
+        .collect();
+    Ok(devices)
+}
+
+#[cfg(target_os = "linux")]
+fn usb_devices_linux() -> Vec<UsbDevice> {
+    use std::path::Path;
+
+    let read = |path: &Path, name: &str| {
+        std::fs::read_to_string(path.join(name)).ok().map(|s| s.trim().to_string()).filter(|s| !s.is_empty())
+    };
+    let hex = |value: Option<String>| value.and_then(|v| u16::from_str_radix(&v, 16).ok());
+    let entries = |dir: &str| {
+        let mut paths: Vec<_> = std::fs::read_dir(dir)
+            .map(|entries| entries.flatten().map(|entry| entry.path()).collect())
+            .unwrap_or_default();
+        paths.sort();
+        paths
+    };
+
+    let mut devices = vec![];
+    // "1-2.3" is bus 1, root port 2, port 3 of the hub behind it; root hubs
+    // are "usb1" and interfaces "1-2.3:1.0"
+    for path in entries("/sys/bus/usb/devices") {
+        let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        if name.starts_with("usb") || name.contains(':') {
+            continue;
+        }
+        let Some(vendor_id) = hex(read(&path, "idVendor")) else {
+            continue;
+        };
+        devices.push(UsbDevice {
+            id: format!("{}:{}", name, read(&path, "devnum").unwrap_or_default()),
+            kind: "usb",
+            name: read(&path, "product").unwrap_or_else(|| "USB device".to_string()),
+            vendor: read(&path, "manufacturer"),
+            vendor_id: Some(vendor_id),
+            product_id: hex(read(&path, "idProduct")),
+            speed_mbps: read(&path, "speed").and_then(|s| s.parse().ok()),
+            port: name.split('.').next().map(|root| root.to_string()),
+        });
+    }
+
+    // "0-0" is the host controller, "0-1" a device on its first port, "0-0:1.1" a service
+    for path in entries("/sys/bus/thunderbolt/devices") {
+        let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        if !name.contains('-') || name.contains(':') || name.ends_with("-0") {
+            continue;
+        }
+        devices.push(UsbDevice {
+            id: read(&path, "unique_id").unwrap_or_else(|| name.clone()),
+            kind: "thunderbolt",
+            name: read(&path, "device_name").unwrap_or_else(|| "Thunderbolt device".to_string()),
+            vendor: read(&path, "vendor_name"),
+            vendor_id: read(&path, "vendor").and_then(|v| u16::from_str_radix(v.trim_start_matches("0x"), 16).ok()),
+            product_id: read(&path, "device").and_then(|v| u16::from_str_radix(v.trim_start_matches("0x"), 16).ok()),
+            // "20.0 Gb/s", per lane on older kernels
+            speed_mbps: read(&path, "rx_speed")
+                .and_then(|s| s.split_whitespace().next()?.parse::<f64>().ok())
+                .map(|gbps| gbps * 1000.0),
+            port: Some(format!("tb-{}", name.split('.').next().unwrap_or(&name))),
+        });
+    }
+    devices
+}
@@ -0,0 +1,114 @@
+// Resource ceilings for child processes. A runaway system_profiler can take
+// all RAM or every core on a marginal machine; run_cmd kills the offender and
+// the event is kept, since a tool blowing up like that says something about
+// the machine too.
+
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+
+const CHECK_INTERVAL: Duration = Duration::from_millis(500);
+// Share of physical memory a single tool may use
+const MEMORY_CEILING_DIVISOR: u64 = 4;
+// Share of all cores, sustained for CPU_GRACE; short bursts are normal
+const CPU_CEILING_PERCENT: f32 = 90.0;
+const CPU_GRACE: Duration = Duration::from_secs(5);
+
+static EVENTS: Mutex<Vec<WatchdogEvent>> = Mutex::new(Vec::new());
+
+#[derive(Serialize, Clone)]
+pub struct WatchdogEvent {
+    pub program: String,
+    pub resource: String, // "memory" (bytes) or "cpu" (percent of one core)
+    pub observed: u64,
+    pub limit: u64,
+    pub elapsed_ms: u64, // since the child was started
+}
+
+pub struct Watchdog {
+    program: String,
+    pid: Pid,
+    system: System,
+    memory_limit: u64,
+    cpu_limit: f32,
+    started: Instant,
+    last_check: Instant,
+    over_cpu_since: Option<Instant>,
+}
+
+impl Watchdog {
+    pub fn new(program: &str, pid: u32) -> Self {
+        let mut system = System::new();
+        system.refresh_memory();
+        let cores = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+        let now = Instant::now();
+        Watchdog {
+            program: program.to_string(),
+            pid: Pid::from_u32(pid),
+            memory_limit: system.total_memory() / MEMORY_CEILING_DIVISOR,
+            // sysinfo reports 100% per busy core
+            cpu_limit: CPU_CEILING_PERCENT * cores as f32,
+            system,
+            started: now,
+            last_check: now,
+            over_cpu_since: None,
+        }
+    }
+
+    // Called from the wait loop; returns the event once a ceiling is crossed.
+    // The caller kills the child.
+    pub fn check(&mut self) -> Option<WatchdogEvent> {
+        let now = Instant::now();
+        if now.duration_since(self.last_check) < CHECK_INTERVAL {
+            return None;
+        }
+        self.last_check = now;
+        self.system.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&[self.pid]),
+            true,
+            ProcessRefreshKind::new().with_memory().with_cpu(),
+        );
+        let process = self.system.process(self.pid)?;
+
+        let memory = process.memory();
+        if self.memory_limit > 0 && memory > self.memory_limit {
+            return Some(self.record("memory", memory, self.memory_limit));
+        }
+
+        let cpu = process.cpu_usage();
+        if cpu <= self.cpu_limit {
+            self.over_cpu_since = None;
+            return None;
+        }
+        let since = *self.over_cpu_since.get_or_insert(now);
+        (now.duration_since(since) >= CPU_GRACE).then(|| self.record("cpu", cpu as u64, self.cpu_limit as u64))
+    }
+
+    fn record(&self, resource: &str, observed: u64, limit: u64) -> WatchdogEvent {
+        let event = WatchdogEvent {
+            program: self.program.clone(),
+            resource: resource.to_string(),
+            observed,
+            limit,
+            elapsed_ms: self.started.elapsed().as_millis() as u64,
+        };
+        if let Ok(mut events) = EVENTS.lock() {
+            events.push(event.clone());
+        }
+        event
+    }
+}
+
+#[tauri::command]
+pub fn get_watchdog_events() -> Vec<WatchdogEvent> {
+    EVENTS.lock().map(|events| events.clone()).unwrap_or_default()
+}
+
+// Called when a scan starts, so a report only lists the tools its own scan killed
+#[tauri::command]
+pub fn clear_watchdog_events() {
+    if let Ok(mut events) = EVENTS.lock() {
+        events.clear();
+    }
+}
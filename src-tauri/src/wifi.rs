@@ -0,0 +1,491 @@
+// The Wi-Fi adapter and its current link, for `get_network_info`. A weak
+// signal next to the access point usually means an antenna cable pinched or
+// left unplugged during a screen or lid swap, since the antennas run
+// through the display hinge on most laptops.
+
+use crate::error::QuickscanError;
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct WifiInfo {
+    pub available: bool, // an adapter is present
+    pub enabled: bool,   // and its radio is on
+    pub interface: Option<String>,
+    pub adapter: Option<String>,
+    pub standards: Vec<String>, // "802.11a", ..., "802.11ax"
+    pub generation: Option<&'static str>, // newest supported: "Wi-Fi 4" up to "Wi-Fi 7"
+    pub supports_6ghz: Option<bool>,
+    pub connection: Option<WifiConnection>,
+}
+
+#[derive(Serialize)]
+pub struct WifiConnection {
+    pub ssid: Option<String>, // None where the OS hides it
+    pub phy_mode: Option<String>, // "802.11ax"
+    pub rssi_dbm: Option<i32>,
+    pub noise_dbm: Option<i32>,
+    pub channel: Option<u32>,
+    pub band: Option<&'static str>, // "2.4GHz", "5GHz" or "6GHz"
+    pub channel_width_mhz: Option<u32>,
+    pub tx_rate_mbps: Option<f64>,
+}
+
+impl WifiInfo {
+    fn none() -> Self {
+        WifiInfo {
+            available: false,
+            enabled: false,
+            interface: None,
+            adapter: None,
+            standards: vec![],
+            generation: None,
+            supports_6ghz: None,
+            connection: None,
+        }
+    }
+}
+
+pub fn wifi_info() -> Result<WifiInfo, QuickscanError> {
+    #[cfg(target_os = "macos")]
+    let mut info = wifi_info_macos()?;
+    #[cfg(target_os = "windows")]
+    let mut info = wifi_info_windows()?;
+    #[cfg(target_os = "linux")]
+    let mut info = wifi_info_linux()?;
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    let mut info: WifiInfo = return Err(QuickscanError::unsupported("wifi_info"));
+
+    // Connected on 6 GHz settles it where the adapter's bands aren't listed
+    if info.connection.as_ref().and_then(|c| c.band) == Some("6GHz") {
+        info.supports_6ghz = Some(true);
+    }
+    info.generation = generation(&info.standards, info.supports_6ghz);
+    Ok(info)
+}
+
+fn generation(standards: &[String], supports_6ghz: Option<bool>) -> Option<&'static str> {
+    let has = |standard: &str| standards.iter().any(|s| s == standard);
+    if has("802.11be") {
+        Some("Wi-Fi 7")
+    } else if has("802.11ax") {
+        Some(if supports_6ghz == Some(true) { "Wi-Fi 6E" } else { "Wi-Fi 6" })
+    } else if has("802.11ac") {
+        Some("Wi-Fi 5")
+    } else if has("802.11n") {
+        Some("Wi-Fi 4")
+    } else {
+        None
+    }
+}
+
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+fn band(frequency_mhz: u32) -> Option<&'static str> {
+    match frequency_mhz {
+        2400..=2500 => Some("2.4GHz"),
+        5150..=5924 => Some("5GHz"),
+        5925..=7125 => Some("6GHz"),
+        _ => None,
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn wifi_info_macos() -> Result<WifiInfo, QuickscanError> {
+    // Shared with the rest of the scan, so the link is as of the overview run
+    // {"SPAirPortDataType": [{"spairport_airport_interfaces": [{"_name": "en0",
+    //   "spairport_card_type": "Wi-Fi  (0x14E4, 0x4387)", "spairport_supported_phymodes": "802.11 a/b/g/n/ac/ax",
+    //   "spairport_supported_channels": ["1 (2GHz)", ..., "1 (6GHz)"],
+    //   "spairport_status_information": "spairport_status_connected",
+    //   "spairport_current_network_information": {"_name": "Shop", "spairport_network_channel": "149 (5GHz, 80MHz)",
+    //     "spairport_network_phymode": "802.11ax", "spairport_network_rate": 1200,
+    //     "spairport_signal_noise": "-54 dBm / -94 dBm"}}]}]}
+    let groups = crate::apple_overview::section("SPAirPortDataType")?;
+    // awdl0 and llw0 are listed too, without a card type
+    let adapter = groups
+        .iter()
+        .filter_map(|group| group.get("spairport_airport_interfaces")?.as_array())
+        .flatten()
+        .find(|interface| interface.get("spairport_card_type").is_some());
+    let Some(adapter) = adapter else {
+        return Ok(WifiInfo::none());
+    };
+    let field = |value: &serde_json::Value, key: &str| value.get(key).and_then(|v| v.as_str()).map(|s| s.trim().to_string());
+
+    let interface = field(adapter, "_name");
+    let standards = field(adapter, "spairport_supported_phymodes")
+        .map(|modes| {
+            modes
+                .trim_start_matches("802.11")
+                .split('/')
+                .map(|mode| format!("802.11{}", mode.trim()))
+                .collect()
+        })
+        .unwrap_or_default();
+    // Older releases list bare channel numbers without the band
+    let supports_6ghz = adapter.get("spairport_supported_channels").and_then(|v| v.as_array()).and_then(|channels| {
+        let labelled: Vec<&str> = channels.iter().filter_map(|c| c.as_str()).collect();
+        (!labelled.is_empty()).then(|| labelled.iter().any(|c| c.contains("6GHz")))
+    });
+    let status = field(adapter, "spairport_status_information").unwrap_or_default();
+
+    let connection = adapter.get("spairport_current_network_information").map(|network| {
+        // "149 (5GHz, 80MHz)"
+        let channel_field = network.get("spairport_network_channel").map(|v| match v {
+            serde_json::Value::Number(n) => n.to_string(),
+            other => other.as_str().unwrap_or_default().to_string(),
+        });
+        let channel_field = channel_field.unwrap_or_default();
+        let channel = channel_field.split_whitespace().next().and_then(|c| c.parse().ok());
+        let details = channel_field.split_once('(').map(|(_, rest)| rest.trim_end_matches(')')).unwrap_or_default();
+        let band = details.split(',').map(|s| s.trim()).find_map(|part| match part {
+            "2GHz" => Some("2.4GHz"),
+            "5GHz" => Some("5GHz"),
+            "6GHz" => Some("6GHz"),
+            _ => None,
+        });
+        let channel_width_mhz = details.split(',').find_map(|part| part.trim().strip_suffix("MHz")?.parse().ok());
+        // "-54 dBm / -94 dBm"
+        let signal_noise = field(network, "spairport_signal_noise").unwrap_or_default();
+        let mut levels = signal_noise.split('/').map(|part| part.trim().trim_end_matches("dBm").trim().parse::<i32>().ok());
+        let (rssi_dbm, noise_dbm) = (levels.next().flatten(), levels.next().flatten());
+        // Since macOS 14.4 the SSID needs Location Services access
+        let ssid = field(network, "_name")
+            .filter(|ssid| ssid != "<redacted>")
+            .or_else(|| interface.as_deref().and_then(ssid_from_ipconfig));
+        WifiConnection {
+            ssid,
+            phy_mode: field(network, "spairport_network_phymode"),
+            rssi_dbm,
+            noise_dbm,
+            channel,
+            band,
+            channel_width_mhz,
+            tx_rate_mbps: network.get("spairport_network_rate").and_then(|v| v.as_f64()),
+        }
+    });
+
+    Ok(WifiInfo {
+        available: true,
+        enabled: status != "spairport_status_off",
+        interface,
+        adapter: field(adapter, "spairport_card_type").map(|card| card.split_whitespace().collect::<Vec<_>>().join(" ")),
+        standards,
+        generation: None,
+        supports_6ghz,
+        connection,
+    })
+}
+
+// "  SSID : Shop" in `ipconfig getsummary en0`, also redacted on recent releases
+#[cfg(target_os = "macos")]
+fn ssid_from_ipconfig(interface: &str) -> Option<String> {
+    let output = crate::process::run_cmd(std::process::Command::new("ipconfig").args(["getsummary", interface])).ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("SSID : "))
+        .map(|ssid| ssid.to_string())
+        .filter(|ssid| ssid != "<redacted>")
+}
+
+// The native WLAN API: netsh prints the same figures, but with labels in the
+// system language
+#[cfg(target_os = "windows")]
+fn wifi_info_windows() -> Result<WifiInfo, QuickscanError> {
+    use std::ffi::c_void;
+    use windows::Win32::Foundation::{ERROR_SUCCESS, HANDLE};
+    use windows::Win32::NetworkManagement::WiFi::*;
+
+    const SOURCE: &str = "wlanapi";
+
+    struct Client(HANDLE);
+    impl Drop for Client {
+        fn drop(&mut self) {
+            unsafe { WlanCloseHandle(self.0, None) };
+        }
+    }
+
+    // The API allocates every result; WlanFreeMemory releases it
+    struct Allocated<T>(*mut T);
+    impl<T> Drop for Allocated<T> {
+        fn drop(&mut self) {
+            if !self.0.is_null() {
+                unsafe { WlanFreeMemory(self.0 as *const c_void) };
+            }
+        }
+    }
+
+    fn phy_standard(phy: DOT11_PHY_TYPE) -> Option<&'static str> {
+        let standards = [
+            (dot11_phy_type_hrdsss, "802.11b"),
+            (dot11_phy_type_ofdm, "802.11a"),
+            (dot11_phy_type_erp, "802.11g"),
+            (dot11_phy_type_ht, "802.11n"),
+            (dot11_phy_type_vht, "802.11ac"),
+            (dot11_phy_type_he, "802.11ax"),
+            (dot11_phy_type_eht, "802.11be"),
+        ];
+        standards.iter().find(|(p, _)| *p == phy).map(|(_, standard)| *standard)
+    }
+
+    unsafe {
+        let mut version = 0u32;
+        let mut handle = HANDLE::default();
+        // The WLAN AutoConfig service isn't running on machines without Wi-Fi
+        if WlanOpenHandle(2, None, &mut version, &mut handle) != ERROR_SUCCESS.0 {
+            return Ok(WifiInfo::none());
+        }
+        let client = Client(handle);
+
+        let mut list: *mut WLAN_INTERFACE_INFO_LIST = std::ptr::null_mut();
+        let status = WlanEnumInterfaces(client.0, None, &mut list);
+        let list = Allocated(list);
+        if status != ERROR_SUCCESS.0 {
+            return Err(QuickscanError::command_failed(SOURCE, format!("WlanEnumInterfaces failed ({})", status)));
+        }
+        if (*list.0).dwNumberOfItems == 0 {
+            return Ok(WifiInfo::none());
+        }
+        let interface = &*(*list.0).InterfaceInfo.as_ptr();
+        let guid = interface.InterfaceGuid;
+        let description = &interface.strInterfaceDescription;
+        let length = description.iter().position(|&c| c == 0).unwrap_or(description.len());
+        let adapter = String::from_utf16_lossy(&description[..length]);
+
+        // Opcode results come back as a pointer to a struct of the opcode's type
+        let query = |opcode: WLAN_INTF_OPCODE| -> Option<Allocated<c_void>> {
+            let mut size = 0u32;
+            let mut data: *mut c_void = std::ptr::null_mut();
+            let status = WlanQueryInterface(client.0, &guid, opcode, None, &mut size, &mut data, None);
+            let data = Allocated(data);
+            (status == ERROR_SUCCESS.0 && !data.0.is_null()).then_some(data)
+        };
+
+        let enabled = query(wlan_intf_opcode_radio_state)
+            .map(|data| {
+                let radio = &*(data.0 as *const WLAN_RADIO_STATE);
+                radio.PhyRadioState[..(radio.dwNumberOfPhys as usize).min(radio.PhyRadioState.len())].iter().any(|phy| {
+                    phy.dot11SoftwareRadioState == dot11_radio_state_on && phy.dot11HardwareRadioState == dot11_radio_state_on
+                })
+            })
+            .unwrap_or(true);
+
+        let mut capability: *mut WLAN_INTERFACE_CAPABILITY = std::ptr::null_mut();
+        let status = WlanGetInterfaceCapability(client.0, &guid, None, &mut capability);
+        let capability = Allocated(capability);
+        let mut standards: Vec<String> = vec![];
+        if status == ERROR_SUCCESS.0 {
+            let capability = &*capability.0;
+            let count = (capability.dwNumberOfSupportedPhys as usize).min(capability.dot11PhyTypes.len());
+            for phy in &capability.dot11PhyTypes[..count] {
+                if let Some(standard) = phy_standard(*phy) {
+                    if !standards.iter().any(|s| s == standard) {
+                        standards.push(standard.to_string());
+                    }
+                }
+            }
+        }
+
+        let connection = if interface.isState == wlan_interface_state_connected {
+            query(wlan_intf_opcode_current_connection).map(|data| {
+                let attributes = &(*(data.0 as *const WLAN_CONNECTION_ATTRIBUTES)).wlanAssociationAttributes;
+                let ssid = &attributes.dot11Ssid;
+                let ssid = String::from_utf8_lossy(&ssid.ucSSID[..(ssid.uSSIDLength as usize).min(ssid.ucSSID.len())]).to_string();
+                let rssi_dbm = query(wlan_intf_opcode_rssi).map(|data| *(data.0 as *const i32));
+                let channel = query(wlan_intf_opcode_channel_number).map(|data| *(data.0 as *const u32));
+
+                // The band comes from the access point's entry in the scan list
+                let mut bss_list: *mut WLAN_BSS_LIST = std::ptr::null_mut();
+                let status = WlanGetNetworkBssList(client.0, &guid, None, dot11_BSS_type_any, false, None, &mut bss_list);
+                let bss_list = Allocated(bss_list);
+                let band = (status == ERROR_SUCCESS.0)
+                    .then(|| {
+                        let entries = std::slice::from_raw_parts((*bss_list.0).wlanBssEntries.as_ptr(), (*bss_list.0).dwNumberOfItems as usize);
+                        entries.iter().find(|entry| entry.dot11Bssid == attributes.dot11Bssid).and_then(|entry| band(entry.ulChCenterFrequency / 1000))
+                    })
+                    .flatten();
+
+                WifiConnection {
+                    ssid: (!ssid.is_empty()).then_some(ssid),
+                    phy_mode: phy_standard(attributes.dot11PhyType).map(|s| s.to_string()),
+                    rssi_dbm,
+                    noise_dbm: None,
+                    channel,
+                    band,
+                    channel_width_mhz: None,
+                    tx_rate_mbps: Some(attributes.ulTxRate as f64 / 1000.0), // kbps
+                }
+            })
+        } else {
+            None
+        };
+
+        Ok(WifiInfo {
+            available: true,
+            enabled,
+            interface: None,
+            adapter: Some(adapter),
+            standards,
+            generation: None,
+            supports_6ghz: None,
+            connection,
+        })
+    }
+}
+
+// sysfs for the adapter and its rfkill switch, `iw` for what the radio can
+// do and the current link, /proc/net/wireless for the noise floor
+#[cfg(target_os = "linux")]
+fn wifi_info_linux() -> Result<WifiInfo, QuickscanError> {
+    use std::path::Path;
+    use std::process::Command;
+
+    let interface = std::fs::read_dir("/sys/class/net")
+        .map_err(|e| QuickscanError::io("/sys/class/net", e))?
+        .flatten()
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| Path::new("/sys/class/net").join(name).join("phy80211").exists())
+        .min();
+    let Some(interface) = interface else {
+        return Ok(WifiInfo::none());
+    };
+    let net = Path::new("/sys/class/net").join(&interface);
+    let read = |path: &Path| std::fs::read_to_string(path).ok().map(|s| s.trim().to_string());
+
+    // "iwlwifi (8086:2725)"; USB adapters have no PCI IDs
+    let driver = std::fs::read_link(net.join("device/driver"))
+        .ok()
+        .and_then(|path| path.file_name().map(|name| name.to_string_lossy().to_string()));
+    let pci_id = read(&net.join("device/vendor"))
+        .zip(read(&net.join("device/device")))
+        .map(|(vendor, device)| format!("{}:{}", vendor.trim_start_matches("0x"), device.trim_start_matches("0x")));
+    let adapter = match (driver, pci_id) {
+        (Some(driver), Some(id)) => Some(format!("{} ({})", driver, id)),
+        (driver, id) => driver.or(id),
+    };
+
+    // Blocked by either the hardware switch or software
+    let enabled = std::fs::read_dir(net.join("phy80211"))
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|entry| entry.file_name().to_string_lossy().starts_with("rfkill"))
+                .all(|entry| read(&entry.path().join("soft")).as_deref() != Some("1") && read(&entry.path().join("hard")).as_deref() != Some("1"))
+        })
+        .unwrap_or(true);
+
+    let missing_tool = |e: QuickscanError| match e {
+        QuickscanError::CommandFailed { .. } => QuickscanError::unsupported("wifi_info (install iw)"),
+        other => other,
+    };
+    let phy = read(&net.join("phy80211/name")).unwrap_or_default();
+    let output = crate::process::run_cmd(Command::new("iw").args(["phy", &phy, "info"])).map_err(missing_tool)?;
+    let (standards, supports_6ghz) = parse_iw_phy(&String::from_utf8_lossy(&output.stdout));
+
+    let output = crate::process::run_cmd(Command::new("iw").args(["dev", &interface, "link"])).map_err(missing_tool)?;
+    let mut connection = parse_iw_link(&String::from_utf8_lossy(&output.stdout));
+    if let Some(connection) = connection.as_mut() {
+        connection.noise_dbm = proc_net_wireless_noise(&interface);
+    }
+
+    Ok(WifiInfo {
+        available: true,
+        enabled,
+        interface: Some(interface),
+        adapter,
+        standards,
+        generation: None,
+        supports_6ghz: Some(supports_6ghz),
+        connection,
+    })
+}
+
+// Band 1 is 2.4 GHz, band 2 5 GHz and band 4 6 GHz; each lists its
+// capabilities and frequencies:
+//   Band 2:
+//       VHT Capabilities (0x039071f6):
+//       HE Iftypes: managed
+//       Frequencies:
+//           * 5180.0 MHz [36] (22.0 dBm)
+#[cfg(target_os = "linux")]
+fn parse_iw_phy(output: &str) -> (Vec<String>, bool) {
+    let mut standards = vec![];
+    let mut supports_6ghz = false;
+    let mut add = |standard: &str| {
+        if !standards.iter().any(|s| s == standard) {
+            standards.push(standard.to_string());
+        }
+    };
+    for line in output.lines().map(|line| line.trim()) {
+        match line {
+            "Band 1:" => {
+                add("802.11b");
+                add("802.11g");
+            }
+            "Band 2:" => add("802.11a"),
+            _ if line.starts_with("HT Capabilities") => add("802.11n"),
+            _ if line.starts_with("VHT Capabilities") => add("802.11ac"),
+            _ if line.starts_with("HE Iftypes") => add("802.11ax"),
+            _ if line.starts_with("EHT Iftypes") => add("802.11be"),
+            _ => {
+                let frequency = line.strip_prefix("* ").and_then(|rest| rest.split_whitespace().next()).and_then(|f| f.parse::<f64>().ok());
+                if frequency.is_some_and(|f| f >= 5925.0) {
+                    supports_6ghz = true;
+                }
+            }
+        }
+    }
+    (standards, supports_6ghz)
+}
+
+// Connected to aa:bb:cc:dd:ee:ff (on wlan0)
+//     SSID: Shop
+//     freq: 5180
+//     signal: -52 dBm
+//     tx bitrate: 780.0 MBit/s VHT-MCS 8 80MHz short GI VHT-NSS 2
+#[cfg(target_os = "linux")]
+fn parse_iw_link(output: &str) -> Option<WifiConnection> {
+    if !output.starts_with("Connected") {
+        return None;
+    }
+    let field = |key: &str| output.lines().find_map(|line| line.trim().strip_prefix(key)).map(|value| value.trim());
+    let frequency = field("freq:").and_then(|f| f.parse::<f64>().ok()).map(|f| f as u32);
+    let channel = frequency.and_then(|f| match f {
+        2484 => Some(14),
+        2412..=2472 => Some((f - 2407) / 5),
+        5150..=5924 => Some((f - 5000) / 5),
+        5935 => Some(2),
+        5950..=7125 => Some((f - 5950) / 5),
+        _ => None,
+    });
+    let tx = field("tx bitrate:").unwrap_or_default();
+    let phy_mode = if tx.contains("EHT-MCS") {
+        Some("802.11be")
+    } else if tx.contains("HE-MCS") {
+        Some("802.11ax")
+    } else if tx.contains("VHT-MCS") {
+        Some("802.11ac")
+    } else if tx.contains("MCS") {
+        Some("802.11n")
+    } else {
+        None
+    };
+    Some(WifiConnection {
+        ssid: field("SSID:").map(|s| s.to_string()),
+        phy_mode: phy_mode.map(|s| s.to_string()),
+        rssi_dbm: field("signal:").and_then(|s| s.split_whitespace().next()?.parse().ok()),
+        noise_dbm: None,
+        channel,
+        band: frequency.and_then(band),
+        channel_width_mhz: tx.split_whitespace().find_map(|part| part.strip_suffix("MHz")?.parse().ok()),
+        tx_rate_mbps: tx.split_whitespace().next().and_then(|rate| rate.parse().ok()),
+    })
+}
+
+// "wlp0s20f3: 0000   58.  -52.  -256 ..."; drivers without a noise reading
+// report -256 or 0
+#[cfg(target_os = "linux")]
+fn proc_net_wireless_noise(interface: &str) -> Option<i32> {
+    let content = std::fs::read_to_string("/proc/net/wireless").ok()?;
+    let line = content.lines().find(|line| line.trim_start().starts_with(&format!("{}:", interface)))?;
+    let noise = line.split_whitespace().nth(4)?.trim_end_matches('.').parse::<i32>().ok()?;
+    (noise != -256 && noise != 0).then_some(noise)
+}
@@ -0,0 +1,99 @@
+// MAC OUI and PCI vendor lookups for Wi-Fi modules. The tables only list
+// well-known prefixes; anything else is treated as unknown rather than
+// suspicious.
+
+const OUI_VENDORS: &[(&str, &str)] = &[
+    // Apple
+    ("000393", "Apple"),
+    ("000A27", "Apple"),
+    ("000A95", "Apple"),
+    ("000D93", "Apple"),
+    ("0017F2", "Apple"),
+    ("001B63", "Apple"),
+    ("001EC2", "Apple"),
+    ("002500", "Apple"),
+    ("0026BB", "Apple"),
+    ("28CFE9", "Apple"),
+    // Broadcom
+    ("001018", "Broadcom"),
+    ("00904C", "Broadcom"),
+    // Intel
+    ("001B77", "Intel"),
+    ("001F3B", "Intel"),
+    ("00216A", "Intel"),
+    ("0024D7", "Intel"),
+    ("3CA9F4", "Intel"),
+    ("7C5CF8", "Intel"),
+    ("A44E31", "Intel"),
+    // Qualcomm Atheros
+    ("00037F", "Qualcomm Atheros"),
+    // Realtek
+    ("00E04C", "Realtek"),
+    // MediaTek / Ralink
+    ("000CE7", "MediaTek"),
+    ("000C43", "MediaTek"),
+];
+
+#[cfg(target_os = "macos")]
+const PCI_VENDORS: &[(u16, &str)] = &[
+    (0x14E4, "Broadcom"),
+    (0x106B, "Apple"),
+    (0x8086, "Intel"),
+    (0x168C, "Qualcomm Atheros"),
+    (0x17CB, "Qualcomm"),
+    (0x10EC, "Realtek"),
+    (0x14C3, "MediaTek"),
+];
+
+#[cfg(target_os = "macos")]
+pub const MAC_VENDORS: &[&str] = &["Apple", "Broadcom"];
+
+fn normalize(mac: &str) -> Option<String> {
+    let hex: String = mac.chars().filter(|c| c.is_ascii_hexdigit()).collect();
+    (hex.len() == 12).then(|| hex.to_uppercase())
+}
+
+pub fn oui_vendor(mac: &str) -> Option<&'static str> {
+    let mac = normalize(mac)?;
+    OUI_VENDORS
+        .iter()
+        .find(|(oui, _)| mac.starts_with(oui))
+        .map(|(_, vendor)| *vendor)
+}
+
+// Set when the MAC was assigned in software (spoofed or randomized) rather than burned in
+#[cfg(target_os = "macos")]
+pub fn is_locally_administered(mac: &str) -> bool {
+    normalize(mac)
+        .and_then(|mac| u8::from_str_radix(&mac[..2], 16).ok())
+        .is_some_and(|first| first & 0x02 != 0)
+}
+
+#[cfg(target_os = "macos")]
+pub fn pci_vendor(vendor_id: u16) -> Option<&'static str> {
+    PCI_VENDORS.iter().find(|(id, _)| *id == vendor_id).map(|(_, vendor)| *vendor)
+}
+
+// system_profiler reports the card type as "Wi-Fi  (0x14E4, 0x4388)"
+#[cfg(target_os = "macos")]
+pub fn parse_card_vendor_id(card_type: &str) -> Option<u16> {
+    let inner = card_type.split('(').nth(1)?;
+    let vendor = inner.split(',').next()?.trim();
+    u16::from_str_radix(vendor.trim_start_matches("0x").trim_start_matches("0X"), 16).ok()
+}
+
+// Driver descriptions name the chip maker, sometimes under a brand name
+#[cfg(target_os = "windows")]
+pub fn description_matches_vendor(vendor: &str, description: &str) -> bool {
+    let aliases: &[&str] = match vendor {
+        "Intel" => &["intel", "killer"],
+        "Qualcomm Atheros" => &["qualcomm", "atheros", "killer"],
+        "MediaTek" => &["mediatek", "ralink"],
+        "Broadcom" => &["broadcom"],
+        "Realtek" => &["realtek"],
+        "Apple" => &["apple", "broadcom"],
+        _ => return true,
+    };
+    let description = description.to_lowercase();
+    aliases.iter().any(|alias| description.contains(alias))
+}
@@ -0,0 +1,112 @@
+// Windows license and activation (`check_windows_activation`). What
+// `slmgr /dlv` prints, read from SoftwareLicensingProduct instead so there is
+// no script host output to parse.
+
+use crate::error::QuickscanError;
+use crate::probe_scheduler::ProbeScheduler;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct WindowsActivation {
+    pub activated: bool,
+    pub status: String, // "licensed", "grace", "notification" or "unlicensed"
+    pub edition: Option<String>,
+    pub channel: Option<String>, // "OEM", "Retail", "Volume" or "Refurbisher"
+    pub partial_product_key: Option<String>,
+    pub tied_to_motherboard: Option<bool>, // OEM and refurbisher licenses stay with the first machine
+}
+
+#[tauri::command]
+pub async fn check_windows_activation(probes: tauri::State<'_, ProbeScheduler>) -> Result<WindowsActivation, QuickscanError> {
+    probes.run(windows_activation).await
+}
+
+pub fn windows_activation() -> Result<WindowsActivation, QuickscanError> {
+    #[cfg(target_os = "windows")]
+    {
+        windows_activation_windows()
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        Err(QuickscanError::unsupported("windows_activation"))
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn windows_activation_windows() -> Result<WindowsActivation, QuickscanError> {
+    use crate::wmi_provider::{WmiProvider, CIMV2};
+
+    // Application ID of Windows itself; Office and others share the class
+    const WINDOWS_APPLICATION_ID: &str = "55c92734-d682-4d71-983e-d6ec3f16059f";
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct LicensingProduct {
+        name: Option<String>,
+        description: Option<String>,
+        license_status: Option<u32>,
+        partial_product_key: Option<String>,
+        product_key_channel: Option<String>,
+    }
+
+    // Only the installed key has a PartialProductKey; the class lists every edition
+    let product = WmiProvider::global()
+        .query::<LicensingProduct>(
+            CIMV2,
+            &format!(
+                "SELECT Name, Description, LicenseStatus, PartialProductKey, ProductKeyChannel FROM SoftwareLicensingProduct \
+                 WHERE ApplicationID = '{}' AND PartialProductKey IS NOT NULL",
+                WINDOWS_APPLICATION_ID
+            ),
+        )?
+        .into_iter()
+        .next();
+    let Some(product) = product else {
+        return Ok(WindowsActivation {
+            status: "unlicensed".to_string(),
+            ..Default::default()
+        });
+    };
+
+    // 1 licensed; 2, 3 and 6 are the out-of-box, out-of-tolerance and extended
+    // grace periods; 4 non-genuine grace and 5 notification mode
+    let status = match product.license_status.unwrap_or(0) {
+        1 => "licensed",
+        2 | 3 | 4 | 6 => "grace",
+        5 => "notification",
+        _ => "unlicensed",
+    };
+    let channel = license_channel(
+        product.product_key_channel.as_deref().unwrap_or(""),
+        product.description.as_deref().unwrap_or(""),
+        product.name.as_deref().unwrap_or(""),
+    );
+
+    Ok(WindowsActivation {
+        activated: status == "licensed",
+        status: status.to_string(),
+        edition: product.name,
+        tied_to_motherboard: channel.map(|channel| matches!(channel, "OEM" | "Refurbisher")),
+        channel: channel.map(str::to_string),
+        partial_product_key: product.partial_product_key,
+    })
+}
+
+// ProductKeyChannel is "OEM:DM", "OEM:NONSLP", "Retail", "Volume:MAK", ... and
+// the description ends in e.g. "OEM_DM channel". Registered refurbisher keys
+// carry a MAR marker, or "Refurbished PCs" in the edition name.
+#[cfg(target_os = "windows")]
+fn license_channel(key_channel: &str, description: &str, name: &str) -> Option<&'static str> {
+    let text = format!("{} {} {}", key_channel, description, name).to_uppercase();
+    if text.contains("REFURB") || text.contains("_MAR") || text.contains(":MAR") {
+        Some("Refurbisher")
+    } else if text.contains("OEM") {
+        Some("OEM")
+    } else if text.contains("VOLUME") {
+        Some("Volume")
+    } else if text.contains("RETAIL") {
+        Some("Retail")
+    } else {
+        None
+    }
+}
@@ -0,0 +1,266 @@
+// Shared WMI access for the Windows checks. Spawning powershell or wmic costs
+// 1-3s per query and wmic is gone on recent Windows builds, so queries go
+// through the wmi crate instead.
+//
+// COM connections can't move between threads and the main thread is already
+// initialized for the webview, so a single worker thread owns COM and one
+// connection per namespace; callers send it queries and wait for the rows.
+
+use crate::error::QuickscanError;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::sync::mpsc;
+use std::sync::OnceLock;
+use std::thread;
+use windows::core::{HSTRING, PWSTR};
+use windows::Win32::System::Registry::{
+    RegCloseKey, RegEnumKeyExW, RegGetValueW, RegOpenKeyExW, HKEY, HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE, KEY_READ,
+    RRF_RT_REG_BINARY, RRF_RT_REG_DWORD, RRF_RT_REG_SZ,
+};
+use wmi::{COMLibrary, WMIConnection, WMIError};
+
+pub const CIMV2: &str = "ROOT\\CIMV2";
+pub const WMI: &str = "ROOT\\WMI";
+pub const STORAGE: &str = "ROOT\\Microsoft\\Windows\\Storage";
+pub const STANDARD_CIMV2: &str = "ROOT\\StandardCimv2";
+pub const VOLUME_ENCRYPTION: &str = "ROOT\\CIMV2\\Security\\MicrosoftVolumeEncryption";
+pub const TPM: &str = "ROOT\\CIMV2\\Security\\MicrosoftTpm";
+pub const LIBRE_HARDWARE_MONITOR: &str = "ROOT\\LibreHardwareMonitor";
+pub const OPEN_HARDWARE_MONITOR: &str = "ROOT\\OpenHardwareMonitor";
+
+type Job = Box<dyn FnOnce(&mut Connections) + Send>;
+
+struct Connections {
+    com: COMLibrary,
+    by_namespace: HashMap<&'static str, WMIConnection>,
+}
+
+impl Connections {
+    fn get(&mut self, namespace: &'static str) -> Result<&WMIConnection, QuickscanError> {
+        if !self.by_namespace.contains_key(namespace) {
+            let connection =
+                WMIConnection::with_namespace_path(namespace, self.com).map_err(|e| to_error(namespace, e))?;
+            self.by_namespace.insert(namespace, connection);
+        }
+        Ok(&self.by_namespace[namespace])
+    }
+}
+
+pub struct WmiProvider {
+    jobs: mpsc::Sender<Job>,
+}
+
+impl WmiProvider {
+    pub fn global() -> &'static WmiProvider {
+        static PROVIDER: OnceLock<WmiProvider> = OnceLock::new();
+        PROVIDER.get_or_init(WmiProvider::start)
+    }
+
+    fn start() -> WmiProvider {
+        let (jobs, receiver) = mpsc::channel::<Job>();
+        thread::spawn(move || {
+            let com = match COMLibrary::new() {
+                Ok(com) => com,
+                Err(_) => return, // queued and future jobs fail as CommandFailed
+            };
+            let mut connections = Connections { com, by_namespace: HashMap::new() };
+            for job in receiver {
+                job(&mut connections);
+            }
+        });
+        WmiProvider { jobs }
+    }
+
+    // Runs a WQL query and deserializes each row; field names follow the WMI
+    // property names, so result structs use #[serde(rename_all = "PascalCase")].
+    pub fn query<T>(&self, namespace: &'static str, wql: &str) -> Result<Vec<T>, QuickscanError>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let (reply, result) = mpsc::channel();
+        let wql = wql.to_string();
+        let job: Job = Box::new(move |connections| {
+            record_raw(connections, namespace, &wql);
+            let rows = connections
+                .get(namespace)
+                .and_then(|connection| connection.raw_query::<T>(&wql).map_err(|e| to_error(namespace, e)));
+            let _ = reply.send(rows);
+        });
+        let unavailable = || QuickscanError::command_failed("wmi", "COM could not be initialized");
+        self.jobs.send(job).map_err(|_| unavailable())?;
+        result.recv().map_err(|_| unavailable())?
+    }
+
+    pub fn query_first<T>(&self, namespace: &'static str, wql: &str) -> Option<T>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        self.query(namespace, wql).ok()?.into_iter().next()
+    }
+}
+
+// Evidence and the lab's corpus keep every property of the rows, not just
+// those the probe asked for, so a renamed or retyped one shows up
+fn record_raw(connections: &mut Connections, namespace: &'static str, wql: &str) {
+    if !crate::evidence::recording() {
+        return;
+    }
+    let started = std::time::Instant::now();
+    let rows = connections.get(namespace).and_then(|connection| {
+        let rows = connection.raw_query::<HashMap<String, wmi::Variant>>(wql).map_err(|e| to_error(namespace, e))?;
+        serde_json::to_value(rows).map_err(|e| QuickscanError::parse(namespace, e))
+    });
+    crate::evidence::record_wmi(namespace, wql, started.elapsed(), rows);
+}
+
+fn to_error(namespace: &str, error: WMIError) -> QuickscanError {
+    match error {
+        // WBEM_E_ACCESS_DENIED and E_ACCESSDENIED; some classes need an elevated process
+        WMIError::HResultError { hres } if hres == 0x8004_1003_u32 as i32 || hres == 0x8007_0005_u32 as i32 => {
+            QuickscanError::PermissionDenied { source: namespace.to_string(), message: error.to_string() }
+        }
+        WMIError::SerdeError(message) => QuickscanError::parse(namespace, message),
+        other => QuickscanError::command_failed(namespace, other),
+    }
+}
+
+// WMI datetimes look like "20230102101500.000000+060"
+pub fn format_datetime(value: &str) -> Option<String> {
+    let digits = value.get(..14).filter(|d| d.bytes().all(|b| b.is_ascii_digit()))?;
+    Some(format!(
+        "{}-{}-{} {}:{}:{}",
+        &digits[..4],
+        &digits[4..6],
+        &digits[6..8],
+        &digits[8..10],
+        &digits[10..12],
+        &digits[12..14]
+    ))
+}
+
+// Seconds since the epoch -> WMI datetime "yyyymmddHHMMSS.ffffff+UUU" in UTC
+// (civil_from_days, the inverse of days_from_civil)
+pub fn wmi_datetime(secs: u64) -> String {
+    let z = (secs / 86400) as i64 + 719468;
+    let era = if z >= 0 { z } else { z - 146096 } / 146097;
+    let day_of_era = z - era * 146097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    let seconds = secs % 86400;
+    format!(
+        "{:04}{:02}{:02}{:02}{:02}{:02}.000000+000",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
+
+// Some checks need values that only live in the registry, not in WMI
+pub fn registry_string(subkey: &str, value: &str) -> Option<String> {
+    hive_string(HKEY_LOCAL_MACHINE, subkey, value)
+}
+
+// The same under HKEY_CURRENT_USER, for what belongs to the signed-in user
+pub fn user_registry_string(subkey: &str, value: &str) -> Option<String> {
+    hive_string(HKEY_CURRENT_USER, subkey, value)
+}
+
+fn hive_string(hive: HKEY, subkey: &str, value: &str) -> Option<String> {
+    let subkey = HSTRING::from(subkey);
+    let value = HSTRING::from(value);
+    let mut size: u32 = 0;
+    unsafe {
+        RegGetValueW(hive, &subkey, &value, RRF_RT_REG_SZ, None, None, Some(&mut size)).ok().ok()?;
+        let mut buffer = vec![0u16; (size as usize).div_ceil(2)];
+        RegGetValueW(hive, &subkey, &value, RRF_RT_REG_SZ, None, Some(buffer.as_mut_ptr().cast()), Some(&mut size))
+            .ok()
+            .ok()?;
+        let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+        Some(String::from_utf16_lossy(&buffer[..len]))
+    }
+}
+
+pub fn registry_binary(subkey: &str, value: &str) -> Option<Vec<u8>> {
+    let subkey = HSTRING::from(subkey);
+    let value = HSTRING::from(value);
+    let mut size: u32 = 0;
+    unsafe {
+        RegGetValueW(HKEY_LOCAL_MACHINE, &subkey, &value, RRF_RT_REG_BINARY, None, None, Some(&mut size))
+            .ok()
+            .ok()?;
+        let mut buffer = vec![0u8; size as usize];
+        RegGetValueW(
+            HKEY_LOCAL_MACHINE,
+            &subkey,
+            &value,
+            RRF_RT_REG_BINARY,
+            None,
+            Some(buffer.as_mut_ptr().cast()),
+            Some(&mut size),
+        )
+        .ok()
+        .ok()?;
+        buffer.truncate(size as usize);
+        Some(buffer)
+    }
+}
+
+pub fn registry_dword(subkey: &str, value: &str) -> Option<u32> {
+    let subkey = HSTRING::from(subkey);
+    let value = HSTRING::from(value);
+    let mut data: u32 = 0;
+    let mut size = std::mem::size_of::<u32>() as u32;
+    unsafe {
+        RegGetValueW(
+            HKEY_LOCAL_MACHINE,
+            &subkey,
+            &value,
+            RRF_RT_REG_DWORD,
+            None,
+            Some((&mut data as *mut u32).cast()),
+            Some(&mut size),
+        )
+        .ok()
+        .ok()?;
+    }
+    Some(data)
+}
+
+// Names of the subkeys directly under an HKLM key; empty if it doesn't exist
+pub fn registry_subkeys(subkey: &str) -> Vec<String> {
+    hive_subkeys(HKEY_LOCAL_MACHINE, subkey)
+}
+
+pub fn user_registry_subkeys(subkey: &str) -> Vec<String> {
+    hive_subkeys(HKEY_CURRENT_USER, subkey)
+}
+
+fn hive_subkeys(hive: HKEY, subkey: &str) -> Vec<String> {
+    let subkey = HSTRING::from(subkey);
+    let mut names = vec![];
+    unsafe {
+        let mut key = HKEY::default();
+        if RegOpenKeyExW(hive, &subkey, None, KEY_READ, &mut key).is_err() {
+            return names;
+        }
+        // Key names are at most 255 characters
+        let mut buffer = [0u16; 256];
+        for index in 0.. {
+            let mut len = buffer.len() as u32;
+            let result = RegEnumKeyExW(key, index, Some(PWSTR(buffer.as_mut_ptr())), &mut len, None, None, None, None);
+            if result.is_err() {
+                break;
+            }
+            names.push(String::from_utf16_lossy(&buffer[..len as usize]));
+        }
+        let _ = RegCloseKey(key);
+    }
+    names
+}
@@ -3,17 +3,21 @@ import { Header } from './components/common';
 import { HomePage } from './components/HomePage';
 import { DetectionPage } from './components/detection/DetectionPage';
 import { ReportPage } from './components/report/ReportPage';
+import { PartsPage } from './components/parts/PartsPage';
+import { DevicesPage } from './components/devices/DevicesPage';
 import type { DetectionReport } from './types';
 import './i18n';
 import './styles/global.css';
 
-type Page = 'home' | 'detection' | 'report';
+type Page = 'home' | 'detection' | 'report' | 'parts' | 'devices';
 
 function App() {
   const [currentPage, setCurrentPage] = useState<Page>('home');
   const [report, setReport] = useState<DetectionReport | null>(null);
+  const [claimedRegion, setClaimedRegion] = useState<string | null>(null);
 
-  const handleStartDetection = () => {
+  const handleStartDetection = (region: string | null) => {
+    setClaimedRegion(region);
     setCurrentPage('detection');
   };
 
@@ -32,14 +36,24 @@ function App() {
       <Header />
       <main>
         {currentPage === 'home' && (
-          <HomePage onStartDetection={handleStartDetection} />
+          <HomePage
+            onStartDetection={handleStartDetection}
+            onCheckParts={() => setCurrentPage('parts')}
+            onWatchDevices={() => setCurrentPage('devices')}
+          />
         )}
         {currentPage === 'detection' && (
-          <DetectionPage onComplete={handleDetectionComplete} onBack={handleBackToHome} />
+          <DetectionPage claimedRegion={claimedRegion} onComplete={handleDetectionComplete} onBack={handleBackToHome} />
         )}
         {currentPage === 'report' && report && (
           <ReportPage report={report} onBack={handleBackToHome} />
         )}
+        {currentPage === 'parts' && (
+          <PartsPage onBack={handleBackToHome} />
+        )}
+        {currentPage === 'devices' && (
+          <DevicesPage onBack={handleBackToHome} />
+        )}
       </main>
     </div>
   );
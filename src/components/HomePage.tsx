@@ -1,12 +1,17 @@
+import { useState } from 'react';
 import { useTranslation } from 'react-i18next';
 import { Cpu, Battery, HardDrive, Monitor } from 'lucide-react';
 
 interface HomePageProps {
-  onStartDetection: () => void;
+  onStartDetection: (claimedRegion: string | null) => void;
+  onCheckParts: () => void;
+  onWatchDevices: () => void;
 }
 
-export function HomePage({ onStartDetection }: HomePageProps) {
+export function HomePage({ onStartDetection, onCheckParts, onWatchDevices }: HomePageProps) {
   const { t } = useTranslation();
+  // Where the seller says the machine was bought; checked against its language and keyboard
+  const [claimedRegion, setClaimedRegion] = useState('');
 
   const features = [
     { icon: Cpu, key: 'hardware' },
@@ -23,9 +28,32 @@ export function HomePage({ onStartDetection }: HomePageProps) {
           <p style={{ fontSize: '18px', color: 'var(--color-text-secondary)', marginBottom: '32px' }}>
             {t('home.description')}
           </p>
-          <button className="btn btn-primary" onClick={onStartDetection} style={{ fontSize: '18px', padding: '16px 48px' }}>
+          <div style={{ marginBottom: '24px' }}>
+            <label style={{ color: 'var(--color-text-secondary)', fontSize: '14px' }}>
+              {t('home.claimedRegion')}{' '}
+              <input
+                value={claimedRegion}
+                onChange={e => setClaimedRegion(e.target.value.replace(/[^a-zA-Z]/g, '').slice(0, 2).toUpperCase())}
+                placeholder={t('home.claimedRegionPlaceholder')}
+                style={{ width: '64px', padding: '6px 8px', borderRadius: '8px', border: '1px solid var(--color-border)', textAlign: 'center' }}
+              />
+            </label>
+          </div>
+          <button
+            className="btn btn-primary"
+            onClick={() => onStartDetection(claimedRegion.length === 2 ? claimedRegion : null)}
+            style={{ fontSize: '18px', padding: '16px 48px' }}
+          >
             {t('home.startButton')}
           </button>
+          <div style={{ marginTop: '16px', display: 'flex', gap: '12px', justifyContent: 'center', flexWrap: 'wrap' }}>
+            <button className="btn btn-secondary" onClick={onCheckParts}>
+              {t('home.partsButton')}
+            </button>
+            <button className="btn btn-secondary" onClick={onWatchDevices}>
+              {t('home.devicesButton')}
+            </button>
+          </div>
         </div>
       </section>
 
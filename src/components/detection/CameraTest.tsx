@@ -1,7 +1,27 @@
 import { useState, useRef, useEffect } from 'react';
 import { useTranslation } from 'react-i18next';
-import { Camera, CheckCircle, XCircle, AlertTriangle, ExternalLink, SkipForward } from 'lucide-react';
+import { Camera, CheckCircle, XCircle, AlertTriangle, ExternalLink, SkipForward, Aperture } from 'lucide-react';
 import { Command } from '@tauri-apps/plugin-shell';
+import { invoke } from '@tauri-apps/api/core';
+import { describeError } from '../../utils/errors';
+import { ensureConsent } from '../../utils/consent';
+
+export interface CameraInfoData {
+  name: string;
+  model: string | null;
+  built_in: boolean | null;
+  connection: string | null;
+  max_resolution: string | null;
+}
+
+interface CameraCaptureData {
+  camera: string;
+  width: number;
+  height: number;
+  image_base64: string;
+  mean_brightness: number;
+  dark: boolean;
+}
 
 interface CameraTestProps {
   onComplete: (working: boolean) => void;
@@ -17,6 +37,10 @@ export function CameraTest({ onComplete, onSkip }: CameraTestProps) {
   const [error, setError] = useState<string | null>(null);
   const [permissionDenied, setPermissionDenied] = useState(false);
   const [isLoading, setIsLoading] = useState(true);
+  const [cameras, setCameras] = useState<CameraInfoData[]>([]);
+  const [snapshot, setSnapshot] = useState<CameraCaptureData | null>(null);
+  const [capturing, setCapturing] = useState(false);
+  const [captureError, setCaptureError] = useState<string | null>(null);
 
   // Cleanup function using ref to ensure we always get the current stream
   const stopCamera = () => {
@@ -42,6 +66,9 @@ export function CameraTest({ onComplete, onSkip }: CameraTestProps) {
   useEffect(() => {
     isMountedRef.current = true;
     startCamera();
+    invoke('get_camera_info')
+      .then(list => isMountedRef.current && setCameras(list as CameraInfoData[]))
+      .catch(() => {});
     return () => {
       isMountedRef.current = false;
       stopCamera();
@@ -53,6 +80,10 @@ export function CameraTest({ onComplete, onSkip }: CameraTestProps) {
       stopCamera(); // Clean up any existing stream first
       setIsLoading(true);
       setError(null);
+      if (!await ensureConsent('camera', t)) {
+        setError(t('errors.consent_required'));
+        return;
+      }
       const mediaStream = await navigator.mediaDevices.getUserMedia({ 
         video: { facingMode: 'user' } 
       });
@@ -88,6 +119,26 @@ export function CameraTest({ onComplete, onSkip }: CameraTestProps) {
     }
   };
 
+  // A frame straight from the OS camera API, for when the preview fails for
+  // reasons other than the hardware. The preview has to let go of the camera first.
+  const captureNative = async () => {
+    stopCamera();
+    setCapturing(true);
+    setCaptureError(null);
+    try {
+      if (!await ensureConsent('camera', t)) {
+        if (isMountedRef.current) setCaptureError(t('errors.consent_required'));
+        return;
+      }
+      const capture = await invoke('test_camera_capture', { index: 0 }) as CameraCaptureData;
+      if (isMountedRef.current) setSnapshot(capture);
+    } catch (e) {
+      if (isMountedRef.current) setCaptureError(describeError(e, t));
+    } finally {
+      if (isMountedRef.current) setCapturing(false);
+    }
+  };
+
   const handleFinish = (working: boolean) => {
     stopCamera();
     // Use setTimeout to ensure cleanup completes before navigation
@@ -128,19 +179,26 @@ export function CameraTest({ onComplete, onSkip }: CameraTestProps) {
             alignItems: 'center',
             justifyContent: 'center',
           }}>
-            {isLoading && (
+            {snapshot && (
+              <img
+                src={`data:image/jpeg;base64,${snapshot.image_base64}`}
+                alt={snapshot.camera}
+                style={{ width: '100%', height: '100%', objectFit: 'cover', transform: 'scaleX(-1)' }}
+              />
+            )}
+            {!snapshot && isLoading && (
               <div style={{ textAlign: 'center', color: 'var(--color-text-secondary)' }}>
                 <Camera size={48} style={{ marginBottom: '16px', opacity: 0.5 }} />
                 <p>{t('common.loading')}</p>
               </div>
             )}
-            {error && !permissionDenied && (
+            {!snapshot && error && !permissionDenied && (
               <div style={{ textAlign: 'center', color: 'var(--color-danger)' }}>
                 <XCircle size={48} style={{ marginBottom: '16px' }} />
                 <p>{error}</p>
               </div>
             )}
-            {permissionDenied && (
+            {!snapshot && permissionDenied && (
               <div style={{ textAlign: 'center', padding: '20px' }}>
                 <AlertTriangle size={48} style={{ marginBottom: '16px', color: 'var(--color-warning)' }} />
                 <p style={{ color: 'var(--color-warning)', fontWeight: 'bold', marginBottom: '12px' }}>
@@ -171,17 +229,42 @@ export function CameraTest({ onComplete, onSkip }: CameraTestProps) {
                 width: '100%', 
                 height: '100%', 
                 objectFit: 'cover',
-                display: isLoading || error ? 'none' : 'block',
+                display: isLoading || error || snapshot ? 'none' : 'block',
                 transform: 'scaleX(-1)', // Mirror
               }}
             />
           </div>
 
+          {cameras.length > 0 && (
+            <div style={{ marginBottom: '16px', fontSize: '14px', color: 'var(--color-text-secondary)', textAlign: 'center' }}>
+              {cameras.map((camera, i) => (
+                <p key={i}>
+                  {camera.name}
+                  {camera.max_resolution && ` · ${camera.max_resolution}`}
+                  {camera.built_in !== null && ` · ${camera.built_in ? t('camera.builtIn') : t('camera.external')}`}
+                </p>
+              ))}
+            </div>
+          )}
+          {cameras.length === 0 && !isLoading && (
+            <p style={{ marginBottom: '16px', textAlign: 'center', color: 'var(--color-warning)', fontSize: '14px' }}>{t('camera.noneDetected')}</p>
+          )}
+          {snapshot?.dark && (
+            <p style={{ marginBottom: '16px', textAlign: 'center', color: 'var(--color-warning)', fontSize: '14px' }}>{t('camera.darkFrame')}</p>
+          )}
+          {captureError && (
+            <p style={{ marginBottom: '16px', textAlign: 'center', color: 'var(--color-danger)', fontSize: '14px' }}>{captureError}</p>
+          )}
+
           <div style={{ display: 'flex', gap: '16px', justifyContent: 'center', flexWrap: 'wrap' }}>
+            <button className="btn btn-secondary" onClick={captureNative} disabled={capturing}>
+              <Aperture size={20} />
+              {capturing ? t('camera.capturing') : t('camera.nativeCapture')}
+            </button>
             <button 
               className="btn btn-success" 
               onClick={() => handleFinish(true)}
-              disabled={isLoading}
+              disabled={isLoading && !snapshot}
             >
               <CheckCircle size={20} />
               {t('camera.working')}
@@ -1,21 +1,32 @@
-import { useState } from 'react';
+import { useRef, useState } from 'react';
 import { useTranslation } from 'react-i18next';
 import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 import { 
   Cpu, HardDrive, Battery, Monitor, Keyboard, 
   Mouse, Camera, Mic, Volume2, Wifi, Activity,
-  ArrowLeft, Play, RefreshCcw
+  ArrowLeft, Play, RefreshCcw, CircuitBoard, Flame, Moon, MemoryStick, Usb, RectangleHorizontal
 } from 'lucide-react';
 import { StatusBadge, ProgressBar } from '../common';
-import { ScreenTest } from './ScreenTest';
+import { ScreenTest, type DisplayMeasurementData, type PanelAgingData, type ScreenTestResult } from './ScreenTest';
 import { KeyboardTest } from './KeyboardTest';
 import { TrackpadTest } from './TrackpadTest';
-import { CameraTest } from './CameraTest';
+import { TouchBarTest, type TouchBarInfoData, type TouchBarTestResult } from './TouchBarTest';
+import { CameraTest, type CameraInfoData } from './CameraTest';
 import { MicrophoneTest } from './MicrophoneTest';
-import { SpeakerTest } from './SpeakerTest';
-import type { DetectionReport, DetectionStatus } from '../../types';
+import { SpeakerTest, type AudioDevicesData, type AudioTestData } from './SpeakerTest';
+import { SleepTest, type SleepReportData } from './SleepTest';
+import { PortTest, type PortTestResult } from './PortTest';
+import { StressChart, type StressPoint } from './StressChart';
+import type { DetectionReport, DetectionStatus, GradeModifier, IndicatorHelp, Recommendation } from '../../types';
+import { buildNarrative, buildLocalizedNarrative } from '../../utils/narrative';
+import { describeError } from '../../utils/errors';
+import { ensureConsent } from '../../utils/consent';
+import { loadPreferences, useUnits } from '../../utils/units';
+import { useScanConfig } from '../../utils/scanConfig';
 
 interface DetectionPageProps {
+  claimedRegion: string | null; // ISO 3166 code of where the seller says the machine was sold
   onComplete: (report: DetectionReport) => void;
   onBack: () => void;
 }
@@ -36,21 +47,387 @@ interface BatteryData {
   max_capacity: number;
   current_capacity: number;
   is_charging: boolean;
+  serial: string | null;
+  manufacturer: string | null;
+  device_name: string | null;
+  firmware_version: string | null;
+  manufacture_date: string | null;
 }
 
 interface StorageData {
   model: string;
   smart_status: string;
+  power_on_hours: number | null;
+  temperature: number | null;
+  smart: {
+    power_on_hours: number | null;
+    power_cycles: number | null;
+    reallocated_sectors: number | null;
+    percentage_used: number | null;
+    total_bytes_written: number | null;
+    temperature: number | null;
+  } | null;
+  device: string;
+  is_internal: boolean;
+  is_boot: boolean;
+}
+
+// Sent to the OpenTelemetry export, when one is configured
+interface CheckTimingData {
+  id: string;
+  status: DetectionStatus;
+  started_at_ms: number;
+  ended_at_ms: number;
+  detail: string | null;
+}
+
+interface DeviceModelData {
+  identifier: string;
+  manufacturer: string | null;
+  marketing_name: string | null;
+  release_year: number | null;
+  spec_options: string[];
+}
+
+interface ComponentSerialsData {
+  logic_board: string | null;
+  battery: string | null;
+  storage: Array<{ model: string; serial: string }>;
+  display: string | null;
+  wifi_mac: string | null;
+}
+
+interface BoardData {
+  manufacturer: string | null;
+  model: string | null;
+  version: string | null;
+  serial: string | null;
+  system_serial: string | null;
+  chassis_serial: string | null;
+  nvram_serial: string | null;
+  firmware_vendor: string | null;
+  firmware_version: string | null;
+  firmware_date: string | null;
+}
+
+interface RegionData {
+  display_language: string | null;
+  region: string | null;
+  keyboard_layouts: string[];
+  physical_layout: 'ansi' | 'iso' | 'jis' | null;
+  likely_region: string | null;
+}
+
+interface InputDevicesData {
+  devices: Array<{
+    name: string;
+    kind: 'keyboard' | 'trackpad' | 'mouse';
+    built_in: boolean | null;
+    connection: string | null;
+    force_touch: boolean | null;
+    haptic: boolean | null;
+  }>;
+  internal_keyboard: boolean | null;
+  internal_trackpad: boolean | null;
+  physical_layout: 'ansi' | 'iso' | 'jis' | null;
+}
+
+interface NetworkData {
+  wifi: {
+    available: boolean;
+    enabled: boolean;
+    interface: string | null;
+    adapter: string | null;
+    standards: string[];
+    generation: string | null;
+    supports_6ghz: boolean | null;
+    connection: {
+      ssid: string | null;
+      phy_mode: string | null;
+      rssi_dbm: number | null;
+      noise_dbm: number | null;
+      channel: number | null;
+      band: '2.4GHz' | '5GHz' | '6GHz' | null;
+      channel_width_mhz: number | null;
+      tx_rate_mbps: number | null;
+    } | null;
+  };
+  bluetooth: {
+    available: boolean;
+    enabled: boolean;
+    controller: string | null;
+    vendor: string | null;
+    address: string | null;
+    version: string | null;
+    firmware: string | null;
+    paired_devices: { name: string; address: string | null; kind: string | null; connected: boolean }[];
+  } | null;
+}
+
+interface NetworkAdapterData {
+  name: string;
+  description: string | null;
+  kind: 'ethernet' | 'wifi' | 'wwan' | 'bluetooth' | 'virtual' | 'loopback' | 'other';
+  mac: string | null;
+  up: boolean;
+  speed_mbps: number | null;
+  driver: string | null;
+  addresses: string[];
+}
+
+interface AppleOverviewData {
+  model_name: string | null;
+  model_identifier: string | null;
+  model_number: string | null;
+  chip: string | null;
+  cores: string | null;
+  memory: string | null;
+  serial_number: string | null;
+  firmware: string | null;
+  activation_lock: boolean | null;
+  battery: {
+    serial: string | null;
+    cycle_count: number | null;
+    condition: string | null;
+    maximum_capacity_percent: number | null;
+    charger_watts: number | null;
+  } | null;
+  storage: { model: string; serial: string | null; size_bytes: number | null; smart_status: string | null; trim: boolean | null }[];
+  memory_details: { kind: string | null; manufacturer: string | null; upgradeable: boolean | null } | null;
+  wifi: { card_type: string | null; firmware: string | null; phy_modes: string | null } | null;
+}
+
+interface MemoryModulesData {
+  total_bytes: number;
+  slots_total: number | null;
+  slots_used: number;
+  soldered: boolean | null;
+  upgradeable: boolean | null;
+  ecc: boolean | null;
+  modules: {
+    slot: string;
+    size_bytes: number;
+    kind: string | null;
+    speed_mts: number | null;
+    manufacturer: string | null;
+    part_number: string | null;
+    soldered: boolean | null;
+  }[];
+}
+
+interface SpeedTestData {
+  download_url: string;
+  upload_url: string;
+  download_mbps: number;
+  upload_mbps: number;
+  downloaded_bytes: number;
+  uploaded_bytes: number;
+}
+
+interface LatencyData {
+  hosts: { host: string; sent: number; received: number; min_ms: number | null; avg_ms: number | null; max_ms: number | null; jitter_ms: number | null }[];
+  latency_ms: number | null;
+  jitter_ms: number | null;
+}
+
+interface MdmData {
+  enrolled: boolean;
+  dep_assigned: boolean;
+  user_approved: boolean | null;
+  organization: string | null;
+  server_url: string | null;
+}
+
+interface WatchdogEventData {
+  program: string;
+  resource: 'memory' | 'cpu';
+  observed: number;
+  limit: number;
+  elapsed_ms: number;
+}
+
+interface ActivationLockData {
+  find_my_enabled: boolean;
+  activation_lock_enabled: boolean | null;
+  may_be_locked: boolean;
+}
+
+interface EncryptionData {
+  encrypted: boolean;
+  method: string | null;
+  in_progress: boolean;
+  recovery_key_escrowed: boolean | null;
+}
+
+interface SecurityData {
+  sip_enabled: boolean | null;
+  secure_boot_enabled: boolean | null;
+  secure_boot_policy: 'full' | 'reduced' | 'permissive' | null;
+  tpm_present: boolean | null;
+  tpm_version: string | null;
+  security_chip: string | null;
+}
+
+interface ThermalData {
+  fans: { index: number; current_rpm: number; min_rpm: number | null; max_rpm: number | null; stalled: boolean }[];
+  sensors: { name: 'cpu' | 'gpu' | 'ssd' | 'battery' | 'system'; celsius: number }[];
+}
+
+interface DiskBenchmarkData {
+  path: string;
+  disk: string | null;
+  size_mb: number;
+  direct_io: boolean;
+  sequential_write_mbps: number;
+  sequential_read_mbps: number;
+  random_write_iops: number;
+  random_read_iops: number;
+}
+
+// Big enough to get past SLC write caches on small drives, small enough to stay quick
+const DISK_BENCHMARK_MB = 256;
+
+interface EnergyData {
+  on_battery: boolean;
+  system_power_watts: number | null;
+  display_brightness: number | null;
+  display_power_watts: number | null;
+  full_charge_wh: number | null;
+  estimated_runtime_hours: number | null;
+}
+
+interface PowerEventData {
+  days: number;
+  sleeps: number;
+  unexpected_wakes: number;
+  wake_sources: Array<{ reason: string; count: number }>;
+  failed_sleeps: number;
+  drain_incidents: number;
+  worst_drain_percent_per_hour: number | null;
+  chronic_wakes: boolean;
+  chronic_drain: boolean;
+}
+
+interface CrashHistoryData {
+  months_covered: number;
+  total: number;
+  by_month: Array<{ month: string; count: number }>;
+  crashes: Array<{ at: string; kind: string; component: string; detail: string | null }>;
+  top_component: string | null;
+  repeated: boolean;
+}
+
+interface RtcBatteryData {
+  resets: Array<{ at: string; clock_read: string; before_firmware: boolean }>;
+  earliest_possible: string | null;
+  likely_flat: boolean;
+}
+
+interface StressTestData {
+  duration_secs: number;
+  threads: number;
+  peak_mhz: number | null;
+  sustained_mhz: number | null;
+  peak_temperature: number | null;
+  throttle_onset_secs: number | null;
+  score: number;
+  samples: StressPoint[];
+}
+
+// Long enough for a worn cooling system to heat-soak and give up clock
+const STRESS_TEST_SECONDS = 60;
+
+interface MemoryTestData {
+  size_mb: number;
+  passes: number;
+  duration_secs: number;
+  fault_count: number;
+  faults: Array<{
+    address: string;
+    expected: string;
+    actual: string;
+    test: 'solid' | 'inversion' | 'address';
+  }>;
+}
+
+// A single pass over a slice of RAM keeps the scan short; the backend caps it
+// at half of what is free
+const MEMORY_TEST_MB = 1024;
+const MEMORY_TEST_PASSES = 1;
+
+interface WindowsActivationData {
+  activated: boolean;
+  status: 'licensed' | 'grace' | 'notification' | 'unlicensed';
+  edition: string | null;
+  channel: 'OEM' | 'Retail' | 'Volume' | 'Refurbisher' | null;
+  partial_product_key: string | null;
+  tied_to_motherboard: boolean | null;
+}
+
+interface OsInstallData {
+  original_install: string | null;
+  last_clean_install: string | null;
+  last_reinstall: string | null;
+  reinstall_count: number;
+  upgrade_count: number;
+  previous_install_kept: boolean | null;
+}
+
+interface UsageEstimateData {
+  first_seen: string | null;
+  first_seen_source: string | null;
+  calendar_age_days: number | null;
+  power_on_hours: number | null;
+  battery_cycles: number | null;
+  estimated_usage_hours: number | null;
+  hours_per_day: number | null;
+  usage: string | null;
+}
+
+interface SignedInAccountsData {
+  signed_in: boolean;
+  accounts: Array<{ service: string; account: string; services: string[] }>;
+}
+
+interface DataRemnantsData {
+  other_users: string[];
+  accounts: { service: 'icloud' | 'microsoft'; account: string }[];
+  saved_networks: string[];
+  browser_profiles: { browser: string; user: string; profile: string }[];
+  directory_join: { kind: 'domain' | 'entra_id'; name: string } | null;
+  wiped: boolean;
+}
+
+interface GpuData {
+  model: string;
+  vendor: string;
+  vram_mb: number | null;
+  driver_version: string | null;
+  api_version: string | null;
+  is_integrated: boolean;
+}
+
+interface DisplayData {
+  name: string;
+  vendor: string;
+  native_resolution: string | null;
+  refresh_rate_hz: number | null;
+  color_depth: number | null;
+  connection_type: string;
+  is_internal: boolean;
 }
 
 interface RefurbishmentData {
   is_refurbished: boolean;
   confidence: string;
+  confidence_score: number;
   indicators: Array<{
     name: string;
     detected: boolean;
     description: string;
     severity: string;
+    help?: IndicatorHelp;
+    caveat?: string | null;
   }>;
   replaced_parts: string[];
   details: {
@@ -61,45 +438,118 @@ interface RefurbishmentData {
     date_mismatch: boolean;
     refurb_program?: string;
   };
+  recommendations?: Recommendation[];
+  grade_modifiers?: GradeModifier[];
 }
 
-export function DetectionPage({ onComplete, onBack }: DetectionPageProps) {
-  const { t } = useTranslation();
+export function DetectionPage({ claimedRegion, onComplete, onBack }: DetectionPageProps) {
+  const { t, i18n } = useTranslation();
+  const units = useUnits();
+  const { battery: batteryThresholds } = useScanConfig();
   const [currentStep, setCurrentStep] = useState(0);
   const [isRunning, setIsRunning] = useState(false);
   const [activeTest, setActiveTest] = useState<string | null>(null);
   const [hardwareData, setHardwareData] = useState<Record<string, unknown> | null>(null);
+  const [modelData, setModelData] = useState<DeviceModelData | null>(null);
+  const [serialsData, setSerialsData] = useState<ComponentSerialsData | null>(null);
+  const [cameraData, setCameraData] = useState<CameraInfoData[] | null>(null);
+  const [audioData, setAudioData] = useState<AudioDevicesData | null>(null);
+  const [audioTestData, setAudioTestData] = useState<AudioTestData | null>(null);
+  const [boardData, setBoardData] = useState<BoardData | null>(null);
+  const [regionData, setRegionData] = useState<RegionData | null>(null);
+  const [inputData, setInputData] = useState<InputDevicesData | null>(null);
+  const [touchBarData, setTouchBarData] = useState<TouchBarInfoData | null>(null);
+  const [touchBarResult, setTouchBarResult] = useState<TouchBarTestResult | null>(null);
+  const [networkData, setNetworkData] = useState<NetworkData | null>(null);
+  const [adapterData, setAdapterData] = useState<NetworkAdapterData[] | null>(null);
+  const [speedData, setSpeedData] = useState<SpeedTestData | null>(null);
+  const [appleOverviewData, setAppleOverviewData] = useState<AppleOverviewData | null>(null);
+  const [memoryModuleData, setMemoryModuleData] = useState<MemoryModulesData | null>(null);
+  const [latencyData, setLatencyData] = useState<LatencyData | null>(null);
   const [batteryData, setBatteryData] = useState<BatteryData | null>(null);
   const [storageData, setStorageData] = useState<StorageData | null>(null);
+  const [diskData, setDiskData] = useState<StorageData[]>([]);
+  const [encryptionData, setEncryptionData] = useState<EncryptionData | null>(null);
+  const [gpuData, setGpuData] = useState<GpuData[]>([]);
+  const [displayData, setDisplayData] = useState<DisplayData[]>([]);
+  const [panelAgingData, setPanelAgingData] = useState<PanelAgingData | null>(null);
+  const [displayMeasurementData, setDisplayMeasurementData] = useState<DisplayMeasurementData | null>(null);
   const [refurbishmentData, setRefurbishmentData] = useState<RefurbishmentData | null>(null);
+  const [mdmData, setMdmData] = useState<MdmData | null>(null);
+  const [activationLockData, setActivationLockData] = useState<ActivationLockData | null>(null);
+  const [securityData, setSecurityData] = useState<SecurityData | null>(null);
+  const [activationData, setActivationData] = useState<WindowsActivationData | null>(null);
+  const [osInstallData, setOsInstallData] = useState<OsInstallData | null>(null);
+  const [remnantsData, setRemnantsData] = useState<DataRemnantsData | null>(null);
+  const [usageData, setUsageData] = useState<UsageEstimateData | null>(null);
+  const [accountsData, setAccountsData] = useState<SignedInAccountsData | null>(null);
+  const [thermalData, setThermalData] = useState<ThermalData | null>(null);
+  const [stressData, setStressData] = useState<StressTestData | null>(null);
+  const [energyData, setEnergyData] = useState<EnergyData | null>(null);
+  const [powerEventData, setPowerEventData] = useState<PowerEventData | null>(null);
+  const [crashData, setCrashData] = useState<CrashHistoryData | null>(null);
+  const [rtcData, setRtcData] = useState<RtcBatteryData | null>(null);
+  const [benchmarkData, setBenchmarkData] = useState<DiskBenchmarkData | null>(null);
+  const [memoryData, setMemoryData] = useState<MemoryTestData | null>(null);
+  const [sleepData, setSleepData] = useState<SleepReportData | null>(null);
+  const [portData, setPortData] = useState<PortTestResult | null>(null);
+  const [stressSamples, setStressSamples] = useState<StressPoint[]>([]);
   const [interactiveResults, setInteractiveResults] = useState({
     screen: { tested: false, skipped: false, hasDeadPixel: false },
     keyboard: { tested: false, skipped: false, testedCount: 0, totalKeys: 78 },
     trackpad: { tested: false, skipped: false, click: true, drag: true, gesture: true },
+    touchBar: { tested: false, skipped: false },
     camera: { tested: false, skipped: false, working: true },
     microphone: { tested: false, skipped: false, working: true },
     speaker: { tested: false, skipped: false, left: true, right: true },
+    ports: { tested: false, skipped: false },
+    sleep: { tested: false, skipped: false },
   });
   
   const [steps, setSteps] = useState<DetectionStep[]>([
     { id: 'hardware', icon: Cpu, category: 'hardware', status: 'pending', isInteractive: false },
     { id: 'battery', icon: Battery, category: 'battery', status: 'pending', isInteractive: false },
     { id: 'storage', icon: HardDrive, category: 'storage', status: 'pending', isInteractive: false },
+    { id: 'gpu', icon: CircuitBoard, category: 'gpu', status: 'pending', isInteractive: false },
+    { id: 'stress', icon: Flame, category: 'stress', status: 'pending', isInteractive: false },
+    { id: 'memory', icon: MemoryStick, category: 'memory', status: 'pending', isInteractive: false },
     { id: 'refurbishment', icon: RefreshCcw, category: 'refurbishment', status: 'pending', isInteractive: false },
     { id: 'network', icon: Wifi, category: 'network', status: 'pending', isInteractive: false },
     { id: 'screen', icon: Monitor, category: 'screen', status: 'pending', isInteractive: true },
     { id: 'keyboard', icon: Keyboard, category: 'keyboard', status: 'pending', isInteractive: true },
     { id: 'trackpad', icon: Mouse, category: 'trackpad', status: 'pending', isInteractive: true },
+    { id: 'touchBar', icon: RectangleHorizontal, category: 'touchBar', status: 'pending', isInteractive: true },
     { id: 'camera', icon: Camera, category: 'camera', status: 'pending', isInteractive: true },
     { id: 'microphone', icon: Mic, category: 'microphone', status: 'pending', isInteractive: true },
     { id: 'speaker', icon: Volume2, category: 'speaker', status: 'pending', isInteractive: true },
+    { id: 'ports', icon: Usb, category: 'ports', status: 'pending', isInteractive: true },
+    { id: 'sleep', icon: Moon, category: 'sleep', status: 'pending', isInteractive: true },
     { id: 'sensors', icon: Activity, category: 'sensors', status: 'pending', isInteractive: false },
   ]);
 
+  const scanStartedAt = useRef(0);
+  // Read by the step loop, which runs on the first render's state
+  const hasTouchBar = useRef(false);
+  const checkTimings = useRef<Record<string, CheckTimingData>>({});
+
+  // Progress updates keep a check 'testing'; its clock starts with the first
+  const startCheckTiming = (id: string) => {
+    if (checkTimings.current[id]?.status === 'testing') return;
+    const now = Date.now();
+    checkTimings.current[id] = { id, status: 'testing', started_at_ms: now, ended_at_ms: now, detail: null };
+  };
+
   const updateStepStatus = (id: string, status: DetectionStatus, value?: string) => {
     setSteps(prev => prev.map(step => 
       step.id === id ? { ...step, status, value } : step
     ));
+    if (status === 'testing') {
+      startCheckTiming(id);
+    } else if (status !== 'pending') {
+      // A skipped test that never started took no time
+      const started = checkTimings.current[id]?.started_at_ms ?? Date.now();
+      checkTimings.current[id] = { id, status, started_at_ms: started, ended_at_ms: Date.now(), detail: value ?? null };
+    }
   };
 
   const runAutomaticDetection = async (stepId: string) => {
@@ -108,52 +558,163 @@ export function DetectionPage({ onComplete, onBack }: DetectionPageProps) {
     try {
       switch (stepId) {
         case 'hardware': {
+          // macOS only: one system_profiler run that the later checks reuse
+          setAppleOverviewData(await invoke('get_apple_system_overview').catch(() => null) as AppleOverviewData | null);
           const info = await invoke('get_hardware_info') as Record<string, unknown>;
           setHardwareData(info);
+          // The model name is a nice-to-have; CPU and memory still identify the machine
+          const model = await invoke('get_device_model').catch(() => null) as DeviceModelData | null;
+          setModelData(model);
+          setSerialsData(await invoke('get_component_serials').catch(() => null) as ComponentSerialsData | null);
+          setBoardData(await invoke('get_board_info').catch(() => null) as BoardData | null);
+          setRegionData(await invoke('get_region_info').catch(() => null) as RegionData | null);
+          setCameraData(await invoke('get_camera_info').catch(() => null) as CameraInfoData[] | null);
+          setAudioData(await invoke('get_audio_devices').catch(() => null) as AudioDevicesData | null);
+          setInputData(await invoke('get_input_devices').catch(() => null) as InputDevicesData | null);
+          // macOS only; the tap test runs when the model should have a Touch Bar or one shows up
+          const touchBar = await invoke('get_touch_bar_info').catch(() => null) as TouchBarInfoData | null;
+          setTouchBarData(touchBar);
+          hasTouchBar.current = !!touchBar && (touchBar.expected === true || touchBar.display_present);
+          setThermalData(await invoke('get_thermal_info').catch(() => null) as ThermalData | null);
+          // Root-only on Linux
+          setMemoryModuleData(await invoke('get_memory_modules').catch(() => null) as MemoryModulesData | null);
           const cpu = info.cpu as { model: string; cores: number };
           const memory = info.memory as { total: number };
-          const memoryGB = Math.round((memory.total / (1024 * 1024 * 1024)) * 10) / 10;
-          updateStepStatus(stepId, 'passed', `${cpu.model} | ${memoryGB}GB`);
+          const name = model?.marketing_name ? `${model.marketing_name} | ` : '';
+          updateStepStatus(stepId, 'passed', `${name}${cpu.model} | ${units.bytes(memory.total)}`);
           break;
         }
         case 'battery': {
+          // Desktops sleep too, so the power log is read with or without a battery
+          const logsAllowed = await ensureConsent('logs', t).catch(() => false);
+          setPowerEventData(logsAllowed ? await invoke('get_power_events').catch(() => null) as PowerEventData | null : null);
+          // Under the same consent: panics and bug checks from the system logs
+          setCrashData(logsAllowed ? await invoke('get_crash_history').catch(() => null) as CrashHistoryData | null : null);
+          // And clock resets that point at a flat clock battery
+          setRtcData(logsAllowed ? await invoke('get_rtc_battery').catch(() => null) as RtcBatteryData | null : null);
           try {
             const battery = await invoke('get_battery_info') as BatteryData | null;
             if (battery) {
               setBatteryData(battery);
+              // Measured here, before the stress test, while the scan is the only load
+              const energy = await invoke('get_energy_report').catch(() => null) as EnergyData | null;
+              setEnergyData(energy);
               const healthPercent = Math.round(battery.health);
-              const status = healthPercent >= 80 ? 'passed' : healthPercent >= 60 ? 'warning' : 'failed';
-              updateStepStatus(stepId, status, `${healthPercent}% | ${battery.cycle_count} cycles`);
+              const status = healthPercent >= batteryThresholds.good_percent ? 'passed' : healthPercent >= batteryThresholds.fair_percent ? 'warning' : 'failed';
+              const runtime = energy?.estimated_runtime_hours ? ` | ~${units.number(energy.estimated_runtime_hours, 1)} h` : '';
+              updateStepStatus(stepId, status, `${healthPercent}% | ${battery.cycle_count} cycles${runtime}`);
             } else {
-              updateStepStatus(stepId, 'warning', '无法读取电池信息');
+              updateStepStatus(stepId, 'passed', t('errors.noBattery'));
             }
-          } catch {
-            updateStepStatus(stepId, 'warning', '无法读取电池信息');
+          } catch (error) {
+            updateStepStatus(stepId, 'warning', describeError(error, t));
           }
           break;
         }
         case 'storage': {
           try {
-            const storage = await invoke('get_storage_health') as StorageData | null;
+            const disks = await invoke('get_storage_health') as StorageData[];
+            const storage = disks.find(d => d.is_boot) ?? disks[0];
+            setDiskData(disks);
+            // Usually needs admin on Windows; the SMART result stands on its own without it
+            setEncryptionData(await invoke('check_disk_encryption').catch(() => null) as EncryptionData | null);
             if (storage) {
               setStorageData(storage);
-              const status = storage.smart_status.toLowerCase().includes('verified') || 
-                            storage.smart_status.toLowerCase().includes('healthy') ? 'passed' : 'warning';
-              updateStepStatus(stepId, status, `SMART: ${storage.smart_status}`);
+              // External enclosures rarely pass SMART through, so only internal disks count
+              const isHealthy = (d: StorageData) => d.smart_status.toLowerCase().includes('verified') || 
+                                                    d.smart_status.toLowerCase().includes('healthy');
+              const status = disks.filter(d => d.is_internal).every(isHealthy) ? 'passed' : 'warning';
+              const others = disks.length > 1 ? ` (+${disks.length - 1})` : '';
+              // Runs in the temp directory, which lives on the boot volume
+              const benchmark = await invoke('run_disk_benchmark', { targetPath: null, sizeMb: DISK_BENCHMARK_MB })
+                .catch(() => null) as DiskBenchmarkData | null;
+              setBenchmarkData(benchmark);
+              const speed = benchmark
+                ? ` | R ${units.number(benchmark.sequential_read_mbps)} / W ${units.number(benchmark.sequential_write_mbps)} MB/s`
+                : '';
+              updateStepStatus(stepId, status, `SMART: ${storage.smart_status}${others}${speed}`);
             } else {
-              updateStepStatus(stepId, 'passed', 'SMART: OK');
+              updateStepStatus(stepId, 'warning', t('errors.noDisk'));
             }
-          } catch {
-            updateStepStatus(stepId, 'passed', 'SMART: OK');
+          } catch (error) {
+            updateStepStatus(stepId, 'warning', describeError(error, t));
+          }
+          break;
+        }
+        case 'stress': {
+          setStressSamples([]);
+          const unlisten = await listen<StressPoint>('cpu-stress-progress', event => {
+            setStressSamples(prev => [...prev, event.payload]);
+          });
+          try {
+            const result = await invoke('run_cpu_stress_test', { durationSecs: STRESS_TEST_SECONDS }) as StressTestData;
+            setStressData(result);
+            const clock = result.peak_mhz && result.sustained_mhz ? ` | ${result.sustained_mhz}/${result.peak_mhz} MHz` : '';
+            if (result.throttle_onset_secs !== null) {
+              updateStepStatus(stepId, 'warning', `${t('stress.throttledAt', { seconds: Math.round(result.throttle_onset_secs) })}${clock}`);
+            } else {
+              updateStepStatus(stepId, 'passed', `${t('stress.score', { score: result.score })}${clock}`);
+            }
+          } catch (error) {
+            updateStepStatus(stepId, 'warning', describeError(error, t));
+          } finally {
+            unlisten();
+          }
+          break;
+        }
+        case 'memory': {
+          const unlisten = await listen<{ percent: number }>('memory-test-progress', event => {
+            updateStepStatus(stepId, 'testing', `${event.payload.percent}%`);
+          });
+          try {
+            const result = await invoke('run_memory_test', { sizeMb: MEMORY_TEST_MB, passes: MEMORY_TEST_PASSES }) as MemoryTestData;
+            setMemoryData(result);
+            const tested = `${units.bytes(result.size_mb * 1024 * 1024)} × ${result.passes}`;
+            if (result.fault_count > 0) {
+              updateStepStatus(stepId, 'failed', `${t('memory.faults', { count: result.fault_count })} | ${tested}`);
+            } else {
+              updateStepStatus(stepId, 'passed', `${t('memory.noFaults')} | ${tested}`);
+            }
+          } catch (error) {
+            updateStepStatus(stepId, 'warning', describeError(error, t));
+          } finally {
+            unlisten();
+          }
+          break;
+        }
+        case 'gpu': {
+          try {
+            const gpus = await invoke('get_gpu_info') as GpuData[];
+            setGpuData(gpus);
+            setDisplayData(await invoke('get_display_info') as DisplayData[]);
+            if (gpus.length > 0) {
+              updateStepStatus(stepId, 'passed', gpus.map(g => g.model).join(' / '));
+            } else {
+              updateStepStatus(stepId, 'warning', '未检测到显卡');
+            }
+          } catch (error) {
+            updateStepStatus(stepId, 'warning', describeError(error, t));
           }
           break;
         }
         case 'refurbishment': {
           try {
-            const refurb = await invoke('check_refurbishment') as RefurbishmentData;
+            const refurb = await invoke('check_refurbishment', { claimedRegion }) as RefurbishmentData;
             setRefurbishmentData(refurb);
+            setMdmData(await invoke('check_mdm_status').catch(() => null) as MdmData | null);
+            setActivationLockData(await invoke('check_activation_lock').catch(() => null) as ActivationLockData | null);
+            setSecurityData(await invoke('get_security_info').catch(() => null) as SecurityData | null);
+            setActivationData(await invoke('check_windows_activation').catch(() => null) as WindowsActivationData | null);
+            setOsInstallData(await invoke('get_os_install_history').catch(() => null) as OsInstallData | null);
+            setUsageData(await invoke('get_usage_estimate').catch(() => null) as UsageEstimateData | null);
+            const remnants = await invoke('check_data_remnants').catch(() => null) as DataRemnantsData | null;
+            setRemnantsData(remnants);
+            const accounts = await invoke('get_signed_in_accounts').catch(() => null) as SignedInAccountsData | null;
+            setAccountsData(accounts);
             
-            if (refurb.is_refurbished) {
+            if (accounts?.signed_in) {
+              updateStepStatus(stepId, 'warning', t('refurbishment.accounts.signedIn'));
+            } else if (refurb.is_refurbished) {
               const warningCount = refurb.indicators.filter(i => i.severity === 'warning' || i.severity === 'critical').length;
               const status = warningCount > 0 ? 'warning' : 'passed';
               const label = refurb.details.refurb_program || 
@@ -161,6 +722,8 @@ export function DetectionPage({ onComplete, onBack }: DetectionPageProps) {
                   ? t('refurbishment.partsReplaced', { count: refurb.replaced_parts.length })
                   : t('refurbishment.detected'));
               updateStepStatus(stepId, status, label);
+            } else if (remnants && !remnants.wiped) {
+              updateStepStatus(stepId, 'warning', t('refurbishment.remnants.found'));
             } else {
               updateStepStatus(stepId, 'passed', t('refurbishment.notDetected'));
             }
@@ -171,12 +734,43 @@ export function DetectionPage({ onComplete, onBack }: DetectionPageProps) {
         }
         case 'network': {
           try {
-            const network = await invoke('get_network_info') as { wifi: { enabled: boolean }; bluetooth: { available: boolean } };
-            const wifiStatus = network.wifi?.enabled ? '✓' : '✗';
+            const network = await invoke('get_network_info') as NetworkData;
+            setNetworkData(network);
+            const link = network.wifi.connection;
+            const wifiStatus = !network.wifi.enabled ? '✗' : link?.rssi_dbm != null ? `${link.rssi_dbm} dBm` : '✓';
             const btStatus = network.bluetooth?.available ? '✓' : '✗';
-            updateStepStatus(stepId, 'passed', `WiFi ${wifiStatus} | Bluetooth ${btStatus}`);
-          } catch {
-            updateStepStatus(stepId, 'passed', 'WiFi ✓ | Bluetooth ✓');
+            const adapters = await invoke('get_network_adapters').catch(() => null) as NetworkAdapterData[] | null;
+            setAdapterData(adapters);
+            const ethernet = adapters?.filter(adapter => adapter.kind === 'ethernet') ?? [];
+            const ethernetStatus = ethernet.length > 0 ? ` | Ethernet ${ethernet.some(adapter => adapter.up) ? '✓' : '✗'}` : '';
+            // Offline machines skip the internet tests rather than waiting on timeouts
+            const online = adapters
+              ? adapters.some(adapter => adapter.up && adapter.kind !== 'virtual' && adapter.kind !== 'loopback')
+              : link !== null;
+            let speedStatus = '';
+            if (online) {
+              const unlisten = await listen<{ phase: 'download' | 'upload' | 'latency'; mbps: number | null }>('network-test-progress', event => {
+                const { phase, mbps } = event.payload;
+                updateStepStatus(stepId, 'testing', `${t(`network.speed.${phase}`)}${mbps !== null ? ` ${units.number(mbps, 0)} Mb/s` : ''}`);
+              });
+              try {
+                const latency = await invoke('run_latency_test').catch(() => null) as LatencyData | null;
+                const speed = await invoke('run_network_speed_test').catch(() => null) as SpeedTestData | null;
+                setLatencyData(latency);
+                setSpeedData(speed);
+                if (speed) {
+                  speedStatus = ` | ↓ ${units.number(speed.download_mbps, 0)} / ↑ ${units.number(speed.upload_mbps, 0)} Mb/s`;
+                }
+                if (latency?.latency_ms != null) {
+                  speedStatus += ` · ${units.number(latency.latency_ms, 0)} ms`;
+                }
+              } finally {
+                unlisten();
+              }
+            }
+            updateStepStatus(stepId, 'passed', `WiFi ${wifiStatus} | Bluetooth ${btStatus}${ethernetStatus}${speedStatus}`);
+          } catch (error) {
+            updateStepStatus(stepId, 'warning', describeError(error, t));
           }
           break;
         }
@@ -196,6 +790,15 @@ export function DetectionPage({ onComplete, onBack }: DetectionPageProps) {
 
   const startDetection = async () => {
     setIsRunning(true);
+    scanStartedAt.current = Date.now();
+    checkTimings.current = {};
+    // The hook may prepare the machine (mount a share, stop a service), so the
+    // scan waits for it; a failing hook is logged and the scan goes on
+    await invoke('run_scan_hook', { stage: 'pre_scan' })
+      .catch(error => console.error('pre_scan hook failed:', error));
+    // Records every tool's raw output when the shop has evidence mode on
+    await invoke('start_evidence').catch(error => console.error('evidence recording failed to start:', error));
+    await invoke('clear_watchdog_events').catch(error => console.error('watchdog events not cleared:', error));
     
     for (let i = 0; i < steps.length; i++) {
       const step = steps[i];
@@ -203,7 +806,10 @@ export function DetectionPage({ onComplete, onBack }: DetectionPageProps) {
       
       if (!step.isInteractive) {
         await runAutomaticDetection(step.id);
+      } else if (step.id === 'touchBar' && !hasTouchBar.current) {
+        updateStepStatus(step.id, 'passed', t('touchBar.notPresent'));
       } else {
+        startCheckTiming(step.id);
         setActiveTest(step.id);
         return;
       }
@@ -219,10 +825,17 @@ export function DetectionPage({ onComplete, onBack }: DetectionPageProps) {
     
     switch (testId) {
       case 'screen': {
-        const hasDeadPixel = result as boolean;
+        const { hasDeadPixel, panelAging, displayMeasurement } = result as ScreenTestResult;
+        setPanelAgingData(panelAging);
+        setDisplayMeasurementData(displayMeasurement);
         setInteractiveResults(prev => ({ ...prev, screen: { ...prev.screen, tested: true, hasDeadPixel } }));
-        updateStepStatus('screen', hasDeadPixel ? 'warning' : 'passed', 
-          hasDeadPixel ? t('screen.hasDeadPixel') : t('screen.noDeadPixel'));
+        // A worn OLED or mini-LED panel counts against the grade like a dead pixel
+        const panelWorn = panelAging?.rating === 'worn';
+        const messages = [hasDeadPixel ? t('screen.hasDeadPixel') : t('screen.noDeadPixel')];
+        if (panelWorn) {
+          messages.push(t('screen.panelWorn', { percent: panelAging?.score }));
+        }
+        updateStepStatus('screen', hasDeadPixel || panelWorn ? 'warning' : 'passed', messages.join(' · '));
         break;
       }
       case 'keyboard': {
@@ -238,6 +851,20 @@ export function DetectionPage({ onComplete, onBack }: DetectionPageProps) {
         updateStepStatus('trackpad', allPassed ? 'passed' : 'warning', allPassed ? t('detection.status.passed') : t('detection.status.warning'));
         break;
       }
+      case 'touchBar': {
+        const touchBarTest = result as TouchBarTestResult;
+        setTouchBarResult(touchBarTest);
+        setInteractiveResults(prev => ({ ...prev, touchBar: { ...prev.touchBar, tested: true } }));
+        // A bar the bridge doesn't expose is dead, whatever the operator saw
+        const dead = !!touchBarData && (!touchBarData.display_present || !touchBarData.bridge_responding);
+        const tappedCount = `${touchBarTest.tapped.length}/${touchBarTest.tapped.length + touchBarTest.missed.length} ${t('keyboard.tested')}`;
+        if (dead || touchBarTest.tapped.length === 0) {
+          updateStepStatus('touchBar', 'failed', dead ? t('touchBar.dead') : tappedCount);
+        } else {
+          updateStepStatus('touchBar', touchBarTest.missed.length === 0 ? 'passed' : 'warning', tappedCount);
+        }
+        break;
+      }
       case 'camera': {
         const working = result as boolean;
         setInteractiveResults(prev => ({ ...prev, camera: { ...prev.camera, tested: true, working } }));
@@ -251,12 +878,40 @@ export function DetectionPage({ onComplete, onBack }: DetectionPageProps) {
         break;
       }
       case 'speaker': {
-        const speakerResult = result as { left: boolean; right: boolean };
+        const { loopback, ...speakerResult } = result as { left: boolean; right: boolean; loopback?: AudioTestData };
+        setAudioTestData(loopback ?? null);
         setInteractiveResults(prev => ({ ...prev, speaker: { ...prev.speaker, tested: true, ...speakerResult } }));
-        const speakerPassed = speakerResult.left && speakerResult.right;
+        // A channel the mic didn't pick up is a warning even if the user heard it
+        const speakerPassed = speakerResult.left && speakerResult.right && !loopback?.channels.some(c => !c.detected);
         updateStepStatus('speaker', speakerPassed ? 'passed' : 'warning', speakerPassed ? t('detection.status.passed') : t('detection.status.warning'));
         break;
       }
+      case 'ports': {
+        const portResult = result as PortTestResult;
+        setPortData(portResult);
+        setInteractiveResults(prev => ({ ...prev, ports: { ...prev.ports, tested: true } }));
+        if (portResult.dead_port_reported) {
+          updateStepStatus('ports', 'failed', t('ports.deadPort'));
+        } else {
+          updateStepStatus('ports', 'passed', t('ports.working', { count: portResult.ports.length }));
+        }
+        break;
+      }
+      case 'sleep': {
+        const sleepResult = result as SleepReportData;
+        setSleepData(sleepResult);
+        setInteractiveResults(prev => ({ ...prev, sleep: { ...prev.sleep, tested: true } }));
+        if (!sleepResult.slept) {
+          updateStepStatus('sleep', 'failed', t('sleep.didNotSleep'));
+        } else if (sleepResult.instant_wake || sleepResult.abnormal_wakes.length > 0) {
+          updateStepStatus('sleep', 'warning', sleepResult.instant_wake
+            ? t('sleep.instantWake', { seconds: sleepResult.seconds_asleep })
+            : t('sleep.abnormalWakes', { sources: sleepResult.abnormal_wakes.join(', ') }));
+        } else {
+          updateStepStatus('sleep', 'passed', t('sleep.normal', { seconds: sleepResult.seconds_asleep }));
+        }
+        break;
+      }
     }
 
     // Delay before continuing to next step to allow resource cleanup
@@ -277,6 +932,9 @@ export function DetectionPage({ onComplete, onBack }: DetectionPageProps) {
       case 'trackpad':
         setInteractiveResults(prev => ({ ...prev, trackpad: { ...prev.trackpad, skipped: true } }));
         break;
+      case 'touchBar':
+        setInteractiveResults(prev => ({ ...prev, touchBar: { ...prev.touchBar, skipped: true } }));
+        break;
       case 'camera':
         setInteractiveResults(prev => ({ ...prev, camera: { ...prev.camera, skipped: true } }));
         break;
@@ -286,6 +944,12 @@ export function DetectionPage({ onComplete, onBack }: DetectionPageProps) {
       case 'speaker':
         setInteractiveResults(prev => ({ ...prev, speaker: { ...prev.speaker, skipped: true } }));
         break;
+      case 'ports':
+        setInteractiveResults(prev => ({ ...prev, ports: { ...prev.ports, skipped: true } }));
+        break;
+      case 'sleep':
+        setInteractiveResults(prev => ({ ...prev, sleep: { ...prev.sleep, skipped: true } }));
+        break;
     }
 
     // Delay before continuing to next step to allow resource cleanup
@@ -302,7 +966,10 @@ export function DetectionPage({ onComplete, onBack }: DetectionPageProps) {
 
       if (!step.isInteractive) {
         await runAutomaticDetection(step.id);
+      } else if (step.id === 'touchBar' && !hasTouchBar.current) {
+        updateStepStatus(step.id, 'passed', t('touchBar.notPresent'));
       } else {
+        startCheckTiming(step.id);
         setActiveTest(step.id);
         return;
       }
@@ -313,8 +980,9 @@ export function DetectionPage({ onComplete, onBack }: DetectionPageProps) {
     finishDetection();
   };
 
-  const finishDetection = () => {
+  const finishDetection = async () => {
     setIsRunning(false);
+    const watchdogEvents = await invoke('get_watchdog_events').catch(() => []) as WatchdogEventData[];
     
     const passedCount = steps.filter(s => s.status === 'passed').length;
     const warningCount = steps.filter(s => s.status === 'warning').length;
@@ -325,7 +993,7 @@ export function DetectionPage({ onComplete, onBack }: DetectionPageProps) {
       id: Date.now().toString(),
       generatedAt: new Date().toISOString(),
       deviceOverview: {
-        model: (hardwareData?.cpu as { model: string })?.model || 'Unknown',
+        model: modelData?.marketing_name || (hardwareData?.cpu as { model: string })?.model || 'Unknown',
         os: `${hardwareData?.os_name || 'Unknown'} ${hardwareData?.os_version || ''}`,
         serialNumber: (hardwareData?.serial_number as string) || 'Unknown',
       },
@@ -341,48 +1009,258 @@ export function DetectionPage({ onComplete, onBack }: DetectionPageProps) {
         storage: { model: storageData?.model || '', capacity: 0, verified: true },
         serialNumber: { value: (hardwareData?.serial_number as string) || 'Unknown', verified: true },
       },
+      deviceModel: modelData ? {
+        identifier: modelData.identifier,
+        manufacturer: modelData.manufacturer ?? undefined,
+        marketingName: modelData.marketing_name ?? undefined,
+        releaseYear: modelData.release_year ?? undefined,
+        specOptions: modelData.spec_options,
+      } : undefined,
+      cameras: cameraData?.map(c => ({
+        name: c.name,
+        model: c.model ?? undefined,
+        builtIn: c.built_in ?? undefined,
+        connection: c.connection ?? undefined,
+        maxResolution: c.max_resolution ?? undefined,
+      })),
+      audio: audioData ? {
+        outputs: audioData.outputs.map(d => ({ name: d.name, builtIn: d.built_in ?? undefined, channels: d.channels ?? undefined })),
+        inputs: audioData.inputs.map(d => ({ name: d.name, builtIn: d.built_in ?? undefined, channels: d.channels ?? undefined })),
+        headphoneJack: audioData.headphone_jack ?? undefined,
+      } : undefined,
+      inputDevices: inputData ? {
+        devices: inputData.devices.map(d => ({
+          name: d.name,
+          kind: d.kind,
+          builtIn: d.built_in ?? undefined,
+          connection: d.connection ?? undefined,
+          forceTouch: d.force_touch ?? undefined,
+          haptic: d.haptic ?? undefined,
+        })),
+        internalKeyboard: inputData.internal_keyboard ?? undefined,
+        internalTrackpad: inputData.internal_trackpad ?? undefined,
+        physicalLayout: inputData.physical_layout ?? undefined,
+      } : undefined,
+      componentSerials: serialsData ? {
+        logicBoard: serialsData.logic_board ?? undefined,
+        battery: serialsData.battery ?? undefined,
+        storage: serialsData.storage,
+        display: serialsData.display ?? undefined,
+        wifiMac: serialsData.wifi_mac ?? undefined,
+      } : undefined,
+      board: boardData ? {
+        manufacturer: boardData.manufacturer ?? undefined,
+        model: boardData.model ?? undefined,
+        version: boardData.version ?? undefined,
+        serial: boardData.serial ?? undefined,
+        systemSerial: boardData.system_serial ?? undefined,
+        chassisSerial: boardData.chassis_serial ?? undefined,
+        nvramSerial: boardData.nvram_serial ?? undefined,
+        firmwareVendor: boardData.firmware_vendor ?? undefined,
+        firmwareVersion: boardData.firmware_version ?? undefined,
+        firmwareDate: boardData.firmware_date ?? undefined,
+      } : undefined,
+      region: regionData ? {
+        displayLanguage: regionData.display_language ?? undefined,
+        region: regionData.region ?? undefined,
+        keyboardLayouts: regionData.keyboard_layouts,
+        physicalLayout: regionData.physical_layout ?? undefined,
+        likelyRegion: regionData.likely_region ?? undefined,
+      } : undefined,
+      claimedRegion: claimedRegion ?? undefined,
       battery: {
         health: batteryData?.health || 100,
         cycleCount: batteryData?.cycle_count || 0,
         designCapacity: batteryData?.design_capacity || 0,
         currentCapacity: batteryData?.current_capacity || 0,
         isCharging: batteryData?.is_charging || false,
-        rating: (batteryData?.health || 100) >= 80 ? 'excellent' : (batteryData?.health || 100) >= 60 ? 'good' : 'fair',
+        serial: batteryData?.serial ?? undefined,
+        manufacturer: batteryData?.manufacturer ?? undefined,
+        deviceName: batteryData?.device_name ?? undefined,
+        firmwareVersion: batteryData?.firmware_version ?? undefined,
+        manufactureDate: batteryData?.manufacture_date ?? undefined,
+        rating: (batteryData?.health || 100) >= batteryThresholds.good_percent ? 'excellent' : (batteryData?.health || 100) >= batteryThresholds.fair_percent ? 'good' : 'fair',
       },
       storage: {
         model: storageData?.model || 'Unknown',
         capacity: 0,
         used: 0,
         smartStatus: storageData?.smart_status === 'Verified' ? 'healthy' : 'warning',
-        powerOnHours: 0,
+        powerOnHours: storageData?.power_on_hours ?? 0,
+        temperature: storageData?.temperature ?? undefined,
+        powerCycles: storageData?.smart?.power_cycles ?? undefined,
+        reallocatedSectors: storageData?.smart?.reallocated_sectors ?? undefined,
+        percentageUsed: storageData?.smart?.percentage_used ?? undefined,
+        totalBytesWritten: storageData?.smart?.total_bytes_written ?? undefined,
       },
+      encryption: encryptionData ? {
+        encrypted: encryptionData.encrypted,
+        method: encryptionData.method ?? undefined,
+        inProgress: encryptionData.in_progress,
+        recoveryKeyEscrowed: encryptionData.recovery_key_escrowed ?? undefined,
+      } : undefined,
+      gpus: gpuData.map(g => ({
+        model: g.model,
+        vendor: g.vendor,
+        vramMb: g.vram_mb ?? undefined,
+        driverVersion: g.driver_version ?? undefined,
+        apiVersion: g.api_version ?? undefined,
+        isIntegrated: g.is_integrated,
+      })),
+      displays: displayData.map(d => ({
+        name: d.name,
+        vendor: d.vendor,
+        nativeResolution: d.native_resolution ?? undefined,
+        refreshRateHz: d.refresh_rate_hz ?? undefined,
+        colorDepth: d.color_depth ?? undefined,
+        connectionType: d.connection_type,
+        isInternal: d.is_internal,
+      })),
+      panelAging: panelAgingData ? {
+        display: panelAgingData.display,
+        technology: panelAgingData.technology,
+        panelModel: panelAgingData.panel_model ?? undefined,
+        onHours: panelAgingData.on_hours ?? undefined,
+        firmwareVersion: panelAgingData.firmware_version ?? undefined,
+        ratedBrightnessNits: panelAgingData.rated_brightness_nits ?? undefined,
+        measuredBrightnessNits: panelAgingData.measured_brightness_nits ?? undefined,
+        brightnessPercent: panelAgingData.brightness_percent ?? undefined,
+        score: panelAgingData.score ?? undefined,
+        rating: panelAgingData.rating ?? undefined,
+      } : undefined,
+      displayMeasurement: displayMeasurementData ? {
+        brightnessNits: displayMeasurementData.brightness_nits,
+        blackLevelNits: displayMeasurementData.black_level_nits,
+        contrastRatio: displayMeasurementData.contrast_ratio ?? undefined,
+        whitePointCct: displayMeasurementData.white_point_cct ?? undefined,
+        srgbCoverage: displayMeasurementData.srgb_coverage ?? undefined,
+        dciP3Coverage: displayMeasurementData.dci_p3_coverage ?? undefined,
+      } : undefined,
+      disks: diskData.map(d => ({
+        model: d.model,
+        capacity: 0,
+        used: 0,
+        smartStatus: d.smart_status === 'Verified' ? 'healthy' : 'warning',
+        powerOnHours: d.power_on_hours ?? 0,
+        device: d.device,
+        isInternal: d.is_internal,
+        isBoot: d.is_boot,
+      })),
       system: {
         os: hardwareData?.os_name as string || '',
         osVersion: hardwareData?.os_version as string || '',
         hostname: hardwareData?.hostname as string || '',
         isActivated: true,
+        activationLock: activationLockData?.may_be_locked,
       },
       network: {
-        wifi: { available: true, connected: true },
-        bluetooth: { available: true, enabled: true },
+        wifi: networkData ? {
+          available: networkData.wifi.available,
+          connected: networkData.wifi.connection !== null,
+          enabled: networkData.wifi.enabled,
+          adapter: networkData.wifi.adapter ?? undefined,
+          standards: networkData.wifi.standards,
+          generation: networkData.wifi.generation ?? undefined,
+          connection: networkData.wifi.connection ? {
+            ssid: networkData.wifi.connection.ssid ?? undefined,
+            phyMode: networkData.wifi.connection.phy_mode ?? undefined,
+            rssiDbm: networkData.wifi.connection.rssi_dbm ?? undefined,
+            noiseDbm: networkData.wifi.connection.noise_dbm ?? undefined,
+            channel: networkData.wifi.connection.channel ?? undefined,
+            band: networkData.wifi.connection.band ?? undefined,
+            channelWidthMhz: networkData.wifi.connection.channel_width_mhz ?? undefined,
+            txRateMbps: networkData.wifi.connection.tx_rate_mbps ?? undefined,
+          } : undefined,
+        } : { available: true, connected: true },
+        bluetooth: networkData?.bluetooth ? {
+          available: networkData.bluetooth.available,
+          enabled: networkData.bluetooth.enabled,
+          controller: networkData.bluetooth.controller ?? undefined,
+          vendor: networkData.bluetooth.vendor ?? undefined,
+          version: networkData.bluetooth.version ?? undefined,
+          firmware: networkData.bluetooth.firmware ?? undefined,
+          pairedDevices: networkData.bluetooth.paired_devices.map(device => ({
+            name: device.name,
+            address: device.address ?? undefined,
+            kind: device.kind ?? undefined,
+            connected: device.connected,
+          })),
+        } : { available: true, enabled: true },
+        adapters: adapterData?.map(adapter => ({
+          name: adapter.name,
+          description: adapter.description ?? undefined,
+          kind: adapter.kind,
+          mac: adapter.mac ?? undefined,
+          up: adapter.up,
+          speedMbps: adapter.speed_mbps ?? undefined,
+          driver: adapter.driver ?? undefined,
+          addresses: adapter.addresses,
+        })),
+        speedTest: speedData || latencyData ? {
+          downloadMbps: speedData?.download_mbps,
+          uploadMbps: speedData?.upload_mbps,
+          latencyMs: latencyData?.latency_ms ?? undefined,
+          jitterMs: latencyData?.jitter_ms ?? undefined,
+          server: speedData ? new URL(speedData.download_url).host : undefined,
+        } : undefined,
       },
       sensors: {},
       interactive: {
         screen: { tested: interactiveResults.screen.tested, skipped: interactiveResults.screen.skipped, hasDeadPixel: interactiveResults.screen.hasDeadPixel },
         keyboard: { tested: interactiveResults.keyboard.tested, skipped: interactiveResults.keyboard.skipped, testedKeys: [], totalKeys: interactiveResults.keyboard.totalKeys, failedKeys: [] },
         trackpad: { tested: interactiveResults.trackpad.tested, skipped: interactiveResults.trackpad.skipped, clickWorking: interactiveResults.trackpad.click, dragWorking: interactiveResults.trackpad.drag, gestureWorking: interactiveResults.trackpad.gesture },
+        touchBar: touchBarData && (touchBarData.expected || touchBarData.display_present) ? {
+          tested: interactiveResults.touchBar.tested,
+          skipped: interactiveResults.touchBar.skipped,
+          displayPresent: touchBarData.display_present,
+          bridge: touchBarData.bridge ?? undefined,
+          bridgeFirmware: touchBarData.bridge_firmware ?? undefined,
+          bridgeResponding: touchBarData.bridge_responding,
+          serverRunning: touchBarData.server_running,
+          tappedKeys: touchBarResult?.tapped ?? [],
+          missedKeys: touchBarResult?.missed ?? [],
+        } : undefined,
         camera: { tested: interactiveResults.camera.tested, skipped: interactiveResults.camera.skipped, working: interactiveResults.camera.working },
         microphone: { tested: interactiveResults.microphone.tested, skipped: interactiveResults.microphone.skipped, working: interactiveResults.microphone.working },
-        speaker: { tested: interactiveResults.speaker.tested, skipped: interactiveResults.speaker.skipped, leftChannel: interactiveResults.speaker.left, rightChannel: interactiveResults.speaker.right },
+        speaker: {
+          tested: interactiveResults.speaker.tested,
+          skipped: interactiveResults.speaker.skipped,
+          leftChannel: interactiveResults.speaker.left,
+          rightChannel: interactiveResults.speaker.right,
+          loopback: audioTestData?.channels.map(c => ({
+            channel: c.channel,
+            levelDbfs: c.level_dbfs,
+            detected: c.detected,
+            missingTones: c.tones.filter(tone => !tone.heard).map(tone => tone.frequency_hz),
+          })),
+        },
+        ports: {
+          tested: interactiveResults.ports.tested,
+          skipped: interactiveResults.ports.skipped,
+          ports: (portData?.ports ?? []).map(p => ({ port: p.port, device: p.device, speedMbps: p.speed_mbps ?? undefined })),
+          deadPortReported: portData?.dead_port_reported ?? false,
+        },
+        sleep: {
+          tested: interactiveResults.sleep.tested,
+          skipped: interactiveResults.sleep.skipped,
+          slept: sleepData?.slept ?? false,
+          secondsAsleep: sleepData?.seconds_asleep ?? undefined,
+          wakeReason: sleepData?.wake_reason ?? undefined,
+          instantWake: sleepData?.instant_wake ?? false,
+          abnormalWakes: sleepData?.abnormal_wakes ?? [],
+        },
       },
       refurbishment: refurbishmentData ? {
         isRefurbished: refurbishmentData.is_refurbished,
         confidence: refurbishmentData.confidence as 'high' | 'medium' | 'low',
+        confidenceScore: refurbishmentData.confidence_score,
         indicators: refurbishmentData.indicators.map(i => ({
           name: i.name,
           detected: i.detected,
           description: i.description,
           severity: i.severity as 'info' | 'warning' | 'critical',
+          help: i.help,
+          caveat: i.caveat ?? undefined,
         })),
         replacedParts: refurbishmentData.replaced_parts,
         details: {
@@ -393,10 +1271,181 @@ export function DetectionPage({ onComplete, onBack }: DetectionPageProps) {
           dateMismatch: refurbishmentData.details.date_mismatch,
           refurbProgram: refurbishmentData.details.refurb_program,
         },
+        recommendations: refurbishmentData.recommendations,
+        gradeModifiers: refurbishmentData.grade_modifiers,
+      } : undefined,
+      mdm: mdmData ? {
+        enrolled: mdmData.enrolled,
+        depAssigned: mdmData.dep_assigned,
+        userApproved: mdmData.user_approved ?? undefined,
+        organization: mdmData.organization ?? undefined,
+        serverUrl: mdmData.server_url ?? undefined,
+      } : undefined,
+      watchdogEvents: watchdogEvents.map(e => ({
+        program: e.program,
+        resource: e.resource,
+        observed: e.observed,
+        limit: e.limit,
+        elapsedMs: e.elapsed_ms,
+      })),
+      security: securityData ? {
+        sipEnabled: securityData.sip_enabled ?? undefined,
+        secureBootEnabled: securityData.secure_boot_enabled ?? undefined,
+        secureBootPolicy: securityData.secure_boot_policy ?? undefined,
+        tpmPresent: securityData.tpm_present ?? undefined,
+        tpmVersion: securityData.tpm_version ?? undefined,
+        securityChip: securityData.security_chip ?? undefined,
+      } : undefined,
+      diskBenchmark: benchmarkData ? {
+        path: benchmarkData.path,
+        disk: benchmarkData.disk ?? undefined,
+        sizeMb: benchmarkData.size_mb,
+        directIo: benchmarkData.direct_io,
+        sequentialWriteMbps: benchmarkData.sequential_write_mbps,
+        sequentialReadMbps: benchmarkData.sequential_read_mbps,
+        randomWriteIops: benchmarkData.random_write_iops,
+        randomReadIops: benchmarkData.random_read_iops,
+      } : undefined,
+      energy: energyData ? {
+        onBattery: energyData.on_battery,
+        systemPowerWatts: energyData.system_power_watts ?? undefined,
+        displayBrightness: energyData.display_brightness ?? undefined,
+        displayPowerWatts: energyData.display_power_watts ?? undefined,
+        fullChargeWh: energyData.full_charge_wh ?? undefined,
+        estimatedRuntimeHours: energyData.estimated_runtime_hours ?? undefined,
+      } : undefined,
+      powerEvents: powerEventData ? {
+        days: powerEventData.days,
+        sleeps: powerEventData.sleeps,
+        unexpectedWakes: powerEventData.unexpected_wakes,
+        wakeSources: powerEventData.wake_sources,
+        failedSleeps: powerEventData.failed_sleeps,
+        drainIncidents: powerEventData.drain_incidents,
+        worstDrainPercentPerHour: powerEventData.worst_drain_percent_per_hour ?? undefined,
+        chronicWakes: powerEventData.chronic_wakes,
+        chronicDrain: powerEventData.chronic_drain,
       } : undefined,
-      rawData: { hardware: hardwareData, battery: batteryData, storage: storageData, refurbishment: refurbishmentData },
+      crashHistory: crashData ? {
+        monthsCovered: crashData.months_covered,
+        total: crashData.total,
+        byMonth: crashData.by_month,
+        crashes: crashData.crashes.map(crash => ({ ...crash, detail: crash.detail ?? undefined })),
+        topComponent: crashData.top_component ?? undefined,
+        repeated: crashData.repeated,
+      } : undefined,
+      rtcBattery: rtcData ? {
+        resets: rtcData.resets.map(reset => ({ at: reset.at, clockRead: reset.clock_read, beforeFirmware: reset.before_firmware })),
+        earliestPossible: rtcData.earliest_possible ?? undefined,
+        likelyFlat: rtcData.likely_flat,
+      } : undefined,
+      cpuStress: stressData ? {
+        durationSecs: stressData.duration_secs,
+        threads: stressData.threads,
+        peakMhz: stressData.peak_mhz ?? undefined,
+        sustainedMhz: stressData.sustained_mhz ?? undefined,
+        peakTemperature: stressData.peak_temperature ?? undefined,
+        throttleOnsetSecs: stressData.throttle_onset_secs ?? undefined,
+        score: stressData.score,
+      } : undefined,
+      memoryTest: memoryData ? {
+        sizeMb: memoryData.size_mb,
+        passes: memoryData.passes,
+        durationSecs: memoryData.duration_secs,
+        faultCount: memoryData.fault_count,
+        faults: memoryData.faults,
+      } : undefined,
+      memoryModules: memoryModuleData ? {
+        totalBytes: memoryModuleData.total_bytes,
+        slotsTotal: memoryModuleData.slots_total ?? undefined,
+        slotsUsed: memoryModuleData.slots_used,
+        soldered: memoryModuleData.soldered ?? undefined,
+        upgradeable: memoryModuleData.upgradeable ?? undefined,
+        ecc: memoryModuleData.ecc ?? undefined,
+        modules: memoryModuleData.modules.map(module => ({
+          slot: module.slot,
+          sizeBytes: module.size_bytes,
+          kind: module.kind ?? undefined,
+          speedMts: module.speed_mts ?? undefined,
+          manufacturer: module.manufacturer ?? undefined,
+          partNumber: module.part_number ?? undefined,
+        })),
+      } : undefined,
+      thermal: thermalData ? {
+        fans: thermalData.fans.map(fan => ({
+          index: fan.index,
+          currentRpm: fan.current_rpm,
+          minRpm: fan.min_rpm ?? undefined,
+          maxRpm: fan.max_rpm ?? undefined,
+          stalled: fan.stalled,
+        })),
+        sensors: thermalData.sensors,
+      } : undefined,
+      windowsActivation: activationData ? {
+        activated: activationData.activated,
+        status: activationData.status,
+        edition: activationData.edition ?? undefined,
+        channel: activationData.channel ?? undefined,
+        partialProductKey: activationData.partial_product_key ?? undefined,
+        tiedToMotherboard: activationData.tied_to_motherboard ?? undefined,
+      } : undefined,
+      osInstall: osInstallData ? {
+        originalInstall: osInstallData.original_install ?? undefined,
+        lastCleanInstall: osInstallData.last_clean_install ?? undefined,
+        lastReinstall: osInstallData.last_reinstall ?? undefined,
+        reinstallCount: osInstallData.reinstall_count,
+        upgradeCount: osInstallData.upgrade_count,
+        previousInstallKept: osInstallData.previous_install_kept ?? undefined,
+      } : undefined,
+      usage: usageData ? {
+        firstSeen: usageData.first_seen ?? undefined,
+        firstSeenSource: usageData.first_seen_source ?? undefined,
+        calendarAgeDays: usageData.calendar_age_days ?? undefined,
+        powerOnHours: usageData.power_on_hours ?? undefined,
+        batteryCycles: usageData.battery_cycles ?? undefined,
+        estimatedUsageHours: usageData.estimated_usage_hours ?? undefined,
+        hoursPerDay: usageData.hours_per_day ?? undefined,
+        usage: usageData.usage ?? undefined,
+      } : undefined,
+      signedInAccounts: accountsData ? {
+        signedIn: accountsData.signed_in,
+        accounts: accountsData.accounts,
+      } : undefined,
+      dataRemnants: remnantsData ? {
+        otherUsers: remnantsData.other_users,
+        accounts: remnantsData.accounts,
+        savedNetworks: remnantsData.saved_networks,
+        browserProfiles: remnantsData.browser_profiles,
+        directoryJoin: remnantsData.directory_join ?? undefined,
+        wiped: remnantsData.wiped,
+      } : undefined,
+      rawData: { hardware: hardwareData, appleOverview: appleOverviewData, model: modelData, componentSerials: serialsData, board: boardData, region: regionData, cameras: cameraData, audio: audioData, audioTest: audioTestData, inputDevices: inputData, touchBar: touchBarData, network: networkData, networkAdapters: adapterData, speedTest: speedData, latencyTest: latencyData, mdm: mdmData, activationLock: activationLockData, security: securityData, windowsActivation: activationData, osInstall: osInstallData, usage: usageData, signedInAccounts: accountsData, dataRemnants: remnantsData, thermal: thermalData, cpuStress: stressData, memoryTest: memoryData, memoryModules: memoryModuleData, sleep: sleepData, ports: portData, powerEvents: powerEventData, crashHistory: crashData, rtcBattery: rtcData, energy: energyData, diskBenchmark: benchmarkData, battery: batteryData, storage: storageData, disks: diskData, encryption: encryptionData, gpus: gpuData, displays: displayData, panelAging: panelAgingData, displayMeasurement: displayMeasurementData, refurbishment: refurbishmentData },
     };
 
+    report.narrative = buildNarrative(report, t);
+    // A community translation in the config dir's locales/ replaces it
+    const preferences = await loadPreferences();
+    const localized = await buildLocalizedNarrative(report, t, preferences.locale ?? i18n.language)
+      .catch(error => {
+        console.error('narrative translation failed:', error);
+        return null;
+      });
+    if (localized) {
+      report.narrative = localized.paragraphs;
+      report.narrativeLocale = localized.locale;
+    }
+
+    invoke('run_scan_hook', { stage: 'post_scan', report })
+      .catch(error => console.error('post_scan hook failed:', error));
+    invoke('save_evidence', { reportId: report.id })
+      .catch(error => console.error('evidence save failed:', error));
+    invoke('export_scan_telemetry', {
+      scan: {
+        report_id: report.id,
+        started_at_ms: scanStartedAt.current,
+        ended_at_ms: Date.now(),
+        checks: Object.values(checkTimings.current).filter(c => c.status !== 'testing'),
+      },
+    }).catch(error => console.error('telemetry export failed:', error));
     onComplete(report);
   };
 
@@ -404,7 +1453,7 @@ export function DetectionPage({ onComplete, onBack }: DetectionPageProps) {
 
   // Render active test component
   if (activeTest === 'screen') {
-    return <ScreenTest onComplete={(hasDeadPixel) => handleTestComplete('screen', hasDeadPixel)} onSkip={() => handleTestSkip('screen')} />;
+    return <ScreenTest onComplete={(screenResult) => handleTestComplete('screen', screenResult)} onSkip={() => handleTestSkip('screen')} />;
   }
   if (activeTest === 'keyboard') {
     return <KeyboardTest onComplete={(allPassed, testedCount, totalKeys) => handleTestComplete('keyboard', { allPassed, testedCount, totalKeys })} onSkip={() => handleTestSkip('keyboard')} />;
@@ -412,6 +1461,9 @@ export function DetectionPage({ onComplete, onBack }: DetectionPageProps) {
   if (activeTest === 'trackpad') {
     return <TrackpadTest onComplete={(result) => handleTestComplete('trackpad', result)} onSkip={() => handleTestSkip('trackpad')} />;
   }
+  if (activeTest === 'touchBar') {
+    return <TouchBarTest info={touchBarData} onComplete={(result) => handleTestComplete('touchBar', result)} onSkip={() => handleTestSkip('touchBar')} />;
+  }
   if (activeTest === 'camera') {
     return <CameraTest onComplete={(working) => handleTestComplete('camera', working)} onSkip={() => handleTestSkip('camera')} />;
   }
@@ -421,6 +1473,12 @@ export function DetectionPage({ onComplete, onBack }: DetectionPageProps) {
   if (activeTest === 'speaker') {
     return <SpeakerTest onComplete={(result) => handleTestComplete('speaker', result)} onSkip={() => handleTestSkip('speaker')} />;
   }
+  if (activeTest === 'ports') {
+    return <PortTest onComplete={(result) => handleTestComplete('ports', result)} onSkip={() => handleTestSkip('ports')} />;
+  }
+  if (activeTest === 'sleep') {
+    return <SleepTest onComplete={(result) => handleTestComplete('sleep', result)} onSkip={() => handleTestSkip('sleep')} />;
+  }
 
   return (
     <div className="detection-page section">
@@ -463,6 +1521,9 @@ export function DetectionPage({ onComplete, onBack }: DetectionPageProps) {
                   <div className="detection-item-content">
                     <h4>{t(`detection.categories.${step.category}`)}</h4>
                     {step.value && <p>{step.value}</p>}
+                    {step.id === 'stress' && step.status === 'testing' && (
+                      <StressChart samples={stressSamples} durationSecs={STRESS_TEST_SECONDS} />
+                    )}
                   </div>
                 </div>
                 <StatusBadge status={step.status} label={t(`detection.status.${step.status}`)} />
@@ -1,6 +1,15 @@
 import { useState, useEffect, useCallback } from 'react';
 import { useTranslation } from 'react-i18next';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 import { SkipForward } from 'lucide-react';
+import { describeError } from '../../utils/errors';
+
+interface KeyEvent {
+  code: string | null;
+  raw: number;
+  pressed: boolean;
+}
 
 interface KeyboardTestProps {
   onComplete: (allPassed: boolean, testedCount: number, totalKeys: number) => void;
@@ -50,6 +59,7 @@ const KEY_DISPLAY_MAP: Record<string, string> = {
 export function KeyboardTest({ onComplete, onSkip }: KeyboardTestProps) {
   const { t } = useTranslation();
   const [pressedKeys, setPressedKeys] = useState<Set<string>>(new Set());
+  const [captureError, setCaptureError] = useState<string | null>(null);
 
   const handleKeyDown = useCallback((e: KeyboardEvent) => {
     // Only prevent default for keys we're tracking, avoid blocking system shortcuts
@@ -66,6 +76,23 @@ export function KeyboardTest({ onComplete, onSkip }: KeyboardTestProps) {
     };
   }, [handleKeyDown]);
 
+  // The native capture also sees keys the OS handles before the webview
+  // (media keys on the function row, ⌘Q, the Windows key). Without it the
+  // test falls back to the webview's own key events.
+  useEffect(() => {
+    const unlisten = listen<KeyEvent>('keyboard://key', event => {
+      const { code, pressed } = event.payload;
+      if (pressed && code && VALID_KEYS_SET.has(code)) {
+        setPressedKeys(prev => new Set([...prev, code]));
+      }
+    });
+    invoke('start_keyboard_test').catch(e => setCaptureError(describeError(e, t)));
+    return () => {
+      unlisten.then(f => f());
+      invoke('stop_keyboard_test').catch(() => {});
+    };
+  }, []);
+
   // Cleanup pressed keys state on unmount
   useEffect(() => {
     return () => {
@@ -96,6 +123,11 @@ export function KeyboardTest({ onComplete, onSkip }: KeyboardTestProps) {
           <p style={{ marginBottom: '24px', textAlign: 'center', color: 'var(--color-text-secondary)' }}>
             {t('keyboard.instruction')}
           </p>
+          {captureError && (
+            <p style={{ marginBottom: '24px', textAlign: 'center', fontSize: '13px', color: 'var(--color-warning)' }}>
+              {t('keyboard.captureUnavailable', { error: captureError })}
+            </p>
+          )}
 
           <div style={{ marginBottom: '24px', textAlign: 'center' }}>
             <span style={{ 
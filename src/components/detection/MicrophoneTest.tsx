@@ -2,6 +2,7 @@ import { useState, useRef, useEffect } from 'react';
 import { useTranslation } from 'react-i18next';
 import { Mic, Square, Play, CheckCircle, XCircle, AlertTriangle, ExternalLink, SkipForward } from 'lucide-react';
 import { Command } from '@tauri-apps/plugin-shell';
+import { ensureConsent } from '../../utils/consent';
 
 interface MicrophoneTestProps {
   onComplete: (working: boolean) => void;
@@ -82,6 +83,10 @@ export function MicrophoneTest({ onComplete, onSkip }: MicrophoneTestProps) {
       setError(null);
       setPermissionDenied(false);
       setAudioBlob(null);
+      if (!await ensureConsent('microphone', t)) {
+        setError(t('errors.consent_required'));
+        return;
+      }
       const stream = await navigator.mediaDevices.getUserMedia({ audio: true });
       streamRef.current = stream;
       
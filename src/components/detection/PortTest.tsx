@@ -0,0 +1,130 @@
+import { useState, useEffect } from 'react';
+import { useTranslation } from 'react-i18next';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { Usb, CheckCircle, XCircle, SkipForward } from 'lucide-react';
+import { describeError } from '../../utils/errors';
+import { useUnits } from '../../utils/units';
+
+export interface HardwareDevice {
+  id: string;
+  category: 'usb' | 'thunderbolt' | 'display' | 'storage';
+  name: string;
+  vendor: string | null;
+  port: string | null;
+  speed_mbps: number | null;
+  resolution: string | null;
+  capacity_bytes: number | null;
+  removable: boolean | null;
+}
+
+export interface HardwareChange {
+  events: Array<{ action: 'added' | 'removed'; device: HardwareDevice; at: string }>;
+  devices: HardwareDevice[];
+}
+
+export interface PortTestResult {
+  ports: Array<{ port: string; device: string; speed_mbps: number | null }>;
+  dead_port_reported: boolean;
+}
+
+interface PortTestProps {
+  onComplete: (result: PortTestResult) => void;
+  onSkip: () => void;
+}
+
+// Only plug-ins seen during the test count: a device that was already
+// connected says nothing about the port it sits in
+export function PortTest({ onComplete, onSkip }: PortTestProps) {
+  const { t } = useTranslation();
+  const units = useUnits();
+  const [ports, setPorts] = useState<PortTestResult['ports']>([]);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    // Without a port (Windows), each plug-in is counted on its own
+    const unlisten = listen<HardwareChange>('hardware://changed', event => {
+      const added = event.payload.events
+        .filter(e => e.action === 'added' && (e.device.category === 'usb' || e.device.category === 'thunderbolt'))
+        .map(e => e.device);
+      setPorts(prev => {
+        const next = [...prev];
+        for (const device of added) {
+          const port = device.port ?? device.id;
+          const entry = { port, device: device.vendor ? `${device.vendor} ${device.name}` : device.name, speed_mbps: device.speed_mbps };
+          const index = next.findIndex(p => p.port === port);
+          if (index >= 0) next[index] = entry;
+          else next.push(entry);
+        }
+        return next;
+      });
+    });
+    invoke('watch_hardware_changes').catch(e => setError(describeError(e, t)));
+    return () => {
+      unlisten.then(f => f());
+      invoke('stop_hardware_watch').catch(() => {});
+    };
+  }, []);
+
+  const speed = (mbps: number | null) =>
+    mbps === null ? '' : mbps >= 1000 ? ` · ${units.number(mbps / 1000, 0)} Gb/s` : ` · ${units.number(mbps, 0)} Mb/s`;
+
+  return (
+    <div className="port-test section">
+      <div className="container">
+        <div className="card" style={{ maxWidth: '500px', margin: '0 auto', padding: '32px' }}>
+          <h2 style={{ marginBottom: '8px', textAlign: 'center' }}>{t('ports.title')}</h2>
+          <p style={{ marginBottom: '24px', textAlign: 'center', color: 'var(--color-text-secondary)' }}>
+            {t('ports.instruction')}
+          </p>
+
+          <div style={{ display: 'flex', justifyContent: 'center', marginBottom: '24px' }}>
+            <div style={{
+              width: '100px',
+              height: '100px',
+              borderRadius: '50%',
+              display: 'flex',
+              alignItems: 'center',
+              justifyContent: 'center',
+              backgroundColor: ports.length > 0 ? 'var(--color-success)' : 'var(--color-background)',
+              color: ports.length > 0 ? 'white' : 'var(--color-text)',
+            }}>
+              <Usb size={40} />
+            </div>
+          </div>
+
+          {error && (
+            <p style={{ marginBottom: '16px', textAlign: 'center', color: 'var(--color-warning)' }}>{error}</p>
+          )}
+          {ports.length === 0 ? (
+            <p style={{ marginBottom: '24px', textAlign: 'center' }}>{t('ports.waiting')}</p>
+          ) : (
+            <div style={{ marginBottom: '24px', fontSize: '14px' }}>
+              <p style={{ fontWeight: 500 }}>{t('ports.working', { count: ports.length })}</p>
+              {ports.map(p => (
+                <p key={p.port} style={{ color: 'var(--color-text-secondary)' }}>
+                  {p.device}{speed(p.speed_mbps)}
+                </p>
+              ))}
+            </div>
+          )}
+
+          <div style={{ display: 'flex', gap: '16px', justifyContent: 'center', flexWrap: 'wrap' }}>
+            <button className="btn btn-success" onClick={() => onComplete({ ports, dead_port_reported: false })} disabled={ports.length === 0}>
+              <CheckCircle size={20} />
+              {t('ports.allWork')}
+            </button>
+            <button className="btn btn-danger" onClick={() => onComplete({ ports, dead_port_reported: true })}>
+              <XCircle size={20} />
+              {t('ports.deadPort')}
+            </button>
+            <button className="btn btn-secondary" onClick={onSkip}>
+              <SkipForward size={20} />
+              {t('common.skip')}
+            </button>
+          </div>
+        </div>
+      </div>
+    </div>
+  );
+}
@@ -1,9 +1,63 @@
 import { useState } from 'react';
 import { useTranslation } from 'react-i18next';
+import { invoke } from '@tauri-apps/api/core';
+import { open } from '@tauri-apps/plugin-dialog';
 import { SkipForward } from 'lucide-react';
+import { describeError } from '../../utils/errors';
+import type { QuickscanError } from '../../types';
+
+export interface PanelAgingData {
+  display: string;
+  technology: 'oled' | 'mini_led' | 'lcd';
+  panel_model: string | null;
+  on_hours: number | null;
+  firmware_version: string | null;
+  rated_brightness_nits: number | null;
+  measured_brightness_nits: number | null;
+  brightness_percent: number | null;
+  score: number | null;
+  rating: 'good' | 'fair' | 'worn' | null;
+}
+
+interface PixelDefectCandidate {
+  relative_x: number; // 0-1 across the photo
+  relative_y: number;
+  size_px: number;
+  deviation: number; // positive = brighter than surroundings (stuck), negative = darker (dead)
+}
+
+interface PixelDefectAnalysis {
+  width: number;
+  height: number;
+  candidates: PixelDefectCandidate[];
+}
+
+interface ColorimeterReading {
+  x: number;
+  y: number;
+  z: number;
+  luminance: number; // cd/m²
+  chromaticity_x: number;
+  chromaticity_y: number;
+}
+
+export interface DisplayMeasurementData {
+  brightness_nits: number;
+  black_level_nits: number;
+  contrast_ratio: number | null;
+  white_point_cct: number | null;
+  srgb_coverage: number | null; // percent
+  dci_p3_coverage: number | null;
+}
+
+export interface ScreenTestResult {
+  hasDeadPixel: boolean;
+  panelAging: PanelAgingData | null;
+  displayMeasurement: DisplayMeasurementData | null; // needs at least the white and black readings
+}
 
 interface ScreenTestProps {
-  onComplete: (hasDeadPixel: boolean) => void;
+  onComplete: (result: ScreenTestResult) => void;
   onSkip: () => void;
 }
 
@@ -19,11 +73,20 @@ export function ScreenTest({ onComplete, onSkip }: ScreenTestProps) {
   const { t } = useTranslation();
   const [currentColorIndex, setCurrentColorIndex] = useState(0);
   const [isFullscreen, setIsFullscreen] = useState(false);
+  // Colorimeter readings by patch, when one is attached
+  const [readings, setReadings] = useState<Record<string, ColorimeterReading>>({});
+  const [measuring, setMeasuring] = useState(false);
+  const [measureError, setMeasureError] = useState<string | null>(null);
+  // Spots found in a photo of the test pattern, for the technician to confirm
+  const [photoAnalysis, setPhotoAnalysis] = useState<PixelDefectAnalysis | null>(null);
+  const [analyzing, setAnalyzing] = useState(false);
+  const [photoError, setPhotoError] = useState<string | null>(null);
 
   const currentColor = COLORS[currentColorIndex];
   const isLastColor = currentColorIndex === COLORS.length - 1;
 
   const handleNextColor = () => {
+    setMeasureError(null);
     if (isLastColor) {
       setIsFullscreen(false);
     } else {
@@ -31,10 +94,57 @@ export function ScreenTest({ onComplete, onSkip }: ScreenTestProps) {
     }
   };
 
-  const handleFinish = (hasDeadPixel: boolean) => {
-    onComplete(hasDeadPixel);
+  const measurePatch = async (event: React.MouseEvent) => {
+    event.stopPropagation();
+    setMeasuring(true);
+    setMeasureError(null);
+    try {
+      const reading = await invoke<ColorimeterReading>('read_colorimeter');
+      setReadings(prev => ({ ...prev, [currentColor.key]: reading }));
+    } catch (e) {
+      const error = e as QuickscanError;
+      if (error?.kind === 'unsupported') {
+        setMeasureError(t('screen.colorimeter.spotread_not_found'));
+      } else if (error?.kind === 'timeout') {
+        setMeasureError(t('screen.colorimeter.colorimeter_timeout'));
+      } else if (error?.kind === 'command_failed' && error.source === 'spotread') {
+        setMeasureError(`${t('screen.colorimeter.colorimeter_read_failed')}: ${error.message}`);
+      } else {
+        setMeasureError(describeError(e, t));
+      }
+    }
+    setMeasuring(false);
+  };
+
+  const analyzePhoto = async () => {
+    const imagePath = await open({ filters: [{ name: 'Images', extensions: ['jpg', 'jpeg', 'png', 'heic', 'webp'] }] });
+    if (!imagePath) return;
+    setAnalyzing(true);
+    setPhotoError(null);
+    try {
+      setPhotoAnalysis(await invoke<PixelDefectAnalysis>('analyze_pixel_defects', { imagePath }));
+    } catch (e) {
+      setPhotoAnalysis(null);
+      setPhotoError(describeError(e, t));
+    }
+    setAnalyzing(false);
+  };
+
+  // Panel wear is estimated with the white reading; contrast needs black as
+  // well, and gamut coverage the three primaries
+  const handleFinish = async (hasDeadPixel: boolean) => {
+    const { white, black, red, green, blue } = readings;
+    const panelAging = await invoke('get_panel_aging', { measuredBrightnessNits: white?.luminance ?? null })
+      .catch(() => null) as PanelAgingData | null;
+    const displayMeasurement = white && black
+      ? await invoke<DisplayMeasurementData>('compute_display_measurement', { readings: { white, black, red, green, blue } })
+        .catch(() => null)
+      : null;
+    onComplete({ hasDeadPixel, panelAging, displayMeasurement });
   };
 
+  const currentReading = readings[currentColor.key];
+
   if (isFullscreen) {
     return (
       <div 
@@ -67,6 +177,21 @@ export function ScreenTest({ onComplete, onSkip }: ScreenTestProps) {
           }}
         >
           <p style={{ marginBottom: '8px' }}>{t(`screen.colors.${currentColor.key}`)}</p>
+          <div style={{ marginBottom: '8px' }}>
+            <p style={{ fontSize: '14px', opacity: 0.8, marginBottom: '8px' }}>{t('screen.colorimeter.hint')}</p>
+            <button className="btn btn-secondary" onClick={measurePatch} disabled={measuring}>
+              {measuring ? t('screen.colorimeter.measuring') : t('screen.colorimeter.measure')}
+            </button>
+            {currentReading && (
+              <p style={{ fontSize: '14px', marginTop: '8px' }}>
+                {t('screen.colorimeter.result', {
+                  color: t(`screen.colors.${currentColor.key}`),
+                  nits: currentReading.luminance < 10 ? currentReading.luminance.toFixed(2) : Math.round(currentReading.luminance),
+                })}
+              </p>
+            )}
+            {measureError && <p style={{ fontSize: '14px', marginTop: '8px' }}>{measureError}</p>}
+          </div>
           <p style={{ fontSize: '14px', opacity: 0.8 }}>
             {isLastColor ? t('screen.finish') : t('screen.nextColor')} (Click)
           </p>
@@ -107,6 +232,31 @@ export function ScreenTest({ onComplete, onSkip }: ScreenTestProps) {
             {t('home.startButton')}
           </button>
 
+          <div style={{ marginBottom: '24px' }}>
+            <p style={{ fontSize: '14px', color: 'var(--color-text-secondary)', marginBottom: '8px' }}>{t('screen.photo.hint')}</p>
+            <button className="btn btn-secondary" onClick={analyzePhoto} disabled={analyzing}>
+              {analyzing ? t('screen.photo.analyzing') : t('screen.photo.analyze')}
+            </button>
+            {photoAnalysis && (
+              <div style={{ fontSize: '14px', marginTop: '8px' }}>
+                <p>
+                  {photoAnalysis.candidates.length > 0
+                    ? t('screen.photo.candidates', { count: photoAnalysis.candidates.length })
+                    : t('screen.photo.noCandidates')}
+                </p>
+                {photoAnalysis.candidates.slice(0, 5).map((candidate, index) => (
+                  <p key={index} style={{ color: 'var(--color-text-secondary)' }}>
+                    {t(candidate.deviation > 0 ? 'screen.photo.brightSpot' : 'screen.photo.darkSpot', {
+                      x: Math.round(candidate.relative_x * 100),
+                      y: Math.round(candidate.relative_y * 100),
+                    })}
+                  </p>
+                ))}
+              </div>
+            )}
+            {photoError && <p style={{ fontSize: '14px', marginTop: '8px' }}>{photoError}</p>}
+          </div>
+
           <div style={{ display: 'flex', gap: '16px', justifyContent: 'center', flexWrap: 'wrap' }}>
             <button className="btn btn-success" onClick={() => handleFinish(false)}>
               {t('screen.noDeadPixel')}
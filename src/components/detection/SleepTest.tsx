@@ -0,0 +1,166 @@
+import { useState, useEffect, useRef } from 'react';
+import { useTranslation } from 'react-i18next';
+import { invoke } from '@tauri-apps/api/core';
+import { Moon, CheckCircle, SkipForward, RefreshCcw } from 'lucide-react';
+import { describeError } from '../../utils/errors';
+import { ensureConsent } from '../../utils/consent';
+
+export interface SleepReportData {
+  slept: boolean;
+  seconds_asleep: number | null;
+  wake_reason: string | null;
+  instant_wake: boolean;
+  abnormal_wakes: string[];
+  cycles: Array<{
+    slept_at: number;
+    woke_at: number;
+    wake_reason: string | null;
+    dark_wake: boolean;
+  }>;
+}
+
+interface SleepTestProps {
+  onComplete: (result: SleepReportData) => void;
+  onSkip: () => void;
+}
+
+// A timer that falls this far behind means the machine was suspended
+const RESUME_GAP_MS = 10000;
+// The power logs are written a moment after resume
+const LOG_SETTLE_MS = 3000;
+
+export function SleepTest({ onComplete, onSkip }: SleepTestProps) {
+  const { t } = useTranslation();
+  const [phase, setPhase] = useState<'ready' | 'sleeping' | 'checking' | 'done'>('ready');
+  const [error, setError] = useState<string | null>(null);
+  const [result, setResult] = useState<SleepReportData | null>(null);
+  const startedAt = useRef(0);
+
+  const checkHistory = async () => {
+    setPhase('checking');
+    try {
+      if (!await ensureConsent('logs', t)) {
+        setError(t('errors.consent_required'));
+        setPhase('done');
+        return;
+      }
+      const report = await invoke('check_sleep_history', { sinceMs: startedAt.current }) as SleepReportData;
+      setResult(report);
+    } catch (e) {
+      setError(describeError(e, t));
+    }
+    setPhase('done');
+  };
+
+  // Notice the resume by the gap in a one-second timer
+  useEffect(() => {
+    if (phase !== 'sleeping') return;
+    let lastTick = Date.now();
+    const timer = setInterval(() => {
+      const now = Date.now();
+      if (now - lastTick > RESUME_GAP_MS) {
+        clearInterval(timer);
+        setTimeout(checkHistory, LOG_SETTLE_MS);
+      }
+      lastTick = now;
+    }, 1000);
+    return () => clearInterval(timer);
+  }, [phase]);
+
+  const startSleep = async () => {
+    setError(null);
+    setResult(null);
+    startedAt.current = Date.now();
+    setPhase('sleeping');
+    // If the app may not suspend the machine, closing the lid works just as well
+    await invoke('request_sleep').catch(e => setError(describeError(e, t)));
+  };
+
+  const verdict = !result ? null
+    : !result.slept ? t('sleep.didNotSleep')
+    : result.instant_wake ? t('sleep.instantWake', { seconds: result.seconds_asleep })
+    : result.abnormal_wakes.length > 0 ? t('sleep.abnormalWakes', { sources: result.abnormal_wakes.join(', ') })
+    : t('sleep.normal', { seconds: result.seconds_asleep });
+
+  return (
+    <div className="sleep-test section">
+      <div className="container">
+        <div className="card" style={{ maxWidth: '500px', margin: '0 auto', padding: '32px' }}>
+          <h2 style={{ marginBottom: '8px', textAlign: 'center' }}>{t('sleep.title')}</h2>
+          <p style={{ marginBottom: '24px', textAlign: 'center', color: 'var(--color-text-secondary)' }}>
+            {t('sleep.instruction')}
+          </p>
+
+          <div style={{ display: 'flex', justifyContent: 'center', marginBottom: '24px' }}>
+            <div style={{
+              width: '100px',
+              height: '100px',
+              borderRadius: '50%',
+              display: 'flex',
+              alignItems: 'center',
+              justifyContent: 'center',
+              backgroundColor: phase === 'sleeping' || phase === 'checking' ? 'var(--color-primary)' : 'var(--color-background)',
+              color: phase === 'sleeping' || phase === 'checking' ? 'white' : 'var(--color-text)',
+              animation: phase === 'checking' ? 'pulse 0.5s ease infinite' : 'none',
+            }}>
+              <Moon size={40} />
+            </div>
+          </div>
+
+          {phase === 'sleeping' && (
+            <p style={{ marginBottom: '16px', textAlign: 'center' }}>{t('sleep.waiting')}</p>
+          )}
+          {phase === 'checking' && (
+            <p style={{ marginBottom: '16px', textAlign: 'center' }}>{t('sleep.checking')}</p>
+          )}
+          {error && (
+            <p style={{ marginBottom: '16px', textAlign: 'center', color: 'var(--color-warning)' }}>{error}</p>
+          )}
+          {verdict && (
+            <div style={{ marginBottom: '24px', textAlign: 'center' }}>
+              <p>{verdict}</p>
+              {result?.wake_reason && (
+                <p style={{ fontSize: '14px', color: 'var(--color-text-secondary)' }}>
+                  {t('sleep.wakeReason')}: {result.wake_reason}
+                </p>
+              )}
+            </div>
+          )}
+
+          <div style={{ display: 'flex', gap: '16px', justifyContent: 'center', flexWrap: 'wrap' }}>
+            {phase === 'ready' && (
+              <button className="btn btn-primary" onClick={startSleep}>
+                <Moon size={20} />
+                {t('sleep.start')}
+              </button>
+            )}
+            {phase === 'sleeping' && (
+              <button className="btn btn-secondary" onClick={checkHistory}>
+                <RefreshCcw size={20} />
+                {t('sleep.checkNow')}
+              </button>
+            )}
+            {phase === 'done' && (
+              <>
+                <button className="btn btn-secondary" onClick={startSleep}>
+                  <RefreshCcw size={20} />
+                  {t('sleep.retry')}
+                </button>
+                {result && (
+                  <button className="btn btn-success" onClick={() => onComplete(result)}>
+                    <CheckCircle size={20} />
+                    {t('common.next')}
+                  </button>
+                )}
+              </>
+            )}
+            <button className="btn btn-secondary" onClick={onSkip}>
+              <SkipForward size={20} />
+              {t('common.skip')}
+            </button>
+          </div>
+        </div>
+      </div>
+    </div>
+  );
+}
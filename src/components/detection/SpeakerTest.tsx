@@ -1,9 +1,34 @@
 import { useState, useRef } from 'react';
 import { useTranslation } from 'react-i18next';
-import { Volume2, CheckCircle, XCircle, SkipForward } from 'lucide-react';
+import { invoke } from '@tauri-apps/api/core';
+import { Volume2, CheckCircle, XCircle, SkipForward, AudioWaveform } from 'lucide-react';
+import { describeError } from '../../utils/errors';
+import { ensureConsent } from '../../utils/consent';
+import { useUnits } from '../../utils/units';
+
+export interface AudioDeviceData {
+  name: string;
+  built_in: boolean | null;
+  channels: number | null;
+}
+
+export interface AudioDevicesData {
+  outputs: AudioDeviceData[];
+  inputs: AudioDeviceData[];
+  headphone_jack: boolean | null;
+}
+
+export interface AudioTestData {
+  channels: Array<{
+    channel: 'left' | 'right';
+    tones: Array<{ frequency_hz: number; level_dbfs: number; noise_dbfs: number; heard: boolean }>;
+    level_dbfs: number;
+    detected: boolean;
+  }>;
+}
 
 interface SpeakerTestProps {
-  onComplete: (result: { left: boolean; right: boolean }) => void;
+  onComplete: (result: { left: boolean; right: boolean; loopback?: AudioTestData }) => void;
   onSkip: () => void;
 }
 
@@ -13,6 +38,28 @@ export function SpeakerTest({ onComplete, onSkip }: SpeakerTestProps) {
   const [rightTested, setRightTested] = useState(false);
   const [currentChannel, setCurrentChannel] = useState<'left' | 'right' | null>(null);
   const audioContextRef = useRef<AudioContext | null>(null);
+  const units = useUnits();
+  const [loopback, setLoopback] = useState<AudioTestData | null>(null);
+  const [loopbackRunning, setLoopbackRunning] = useState(false);
+  const [loopbackError, setLoopbackError] = useState<string | null>(null);
+
+  // Plays tones on each channel and listens for them on the built-in mic,
+  // which catches a dead channel or mic without relying on the user's ears
+  const runLoopback = async () => {
+    setLoopbackRunning(true);
+    setLoopbackError(null);
+    try {
+      if (!await ensureConsent('microphone', t)) {
+        setLoopbackError(t('errors.consent_required'));
+        return;
+      }
+      setLoopback(await invoke('run_audio_test') as AudioTestData);
+    } catch (e) {
+      setLoopbackError(describeError(e, t));
+    } finally {
+      setLoopbackRunning(false);
+    }
+  };
 
   const playTestSound = (channel: 'left' | 'right') => {
     setCurrentChannel(channel);
@@ -133,17 +180,41 @@ export function SpeakerTest({ onComplete, onSkip }: SpeakerTestProps) {
             </div>
           </div>
 
+          {loopback && (
+            <div style={{ marginBottom: '16px', fontSize: '14px', textAlign: 'center' }}>
+              {loopback.channels.map(channel => {
+                const missing = channel.tones.filter(tone => !tone.heard).map(tone => `${tone.frequency_hz} Hz`);
+                return (
+                  <p key={channel.channel} style={{ color: channel.detected ? 'var(--color-success)' : 'var(--color-danger)' }}>
+                    {channel.channel === 'left' ? t('speaker.leftChannel') : t('speaker.rightChannel')}
+                    {': '}
+                    {channel.detected ? t('speaker.loopback.heard') : t('speaker.loopback.notHeard')}
+                    {` · ${units.number(channel.level_dbfs, 1)} dBFS`}
+                    {missing.length > 0 && ` · ${t('speaker.loopback.missingTones', { tones: missing.join(', ') })}`}
+                  </p>
+                );
+              })}
+            </div>
+          )}
+          {loopbackError && (
+            <p style={{ marginBottom: '16px', textAlign: 'center', color: 'var(--color-danger)', fontSize: '14px' }}>{loopbackError}</p>
+          )}
+
           <div style={{ display: 'flex', gap: '16px', justifyContent: 'center', flexWrap: 'wrap' }}>
+            <button className="btn btn-secondary" onClick={runLoopback} disabled={loopbackRunning || currentChannel !== null}>
+              <AudioWaveform size={20} />
+              {loopbackRunning ? t('speaker.loopback.running') : t('speaker.loopback.run')}
+            </button>
             <button 
               className="btn btn-success" 
-              onClick={() => onComplete({ left: true, right: true })}
+              onClick={() => onComplete({ left: true, right: true, loopback: loopback ?? undefined })}
             >
               <CheckCircle size={20} />
               {t('speaker.bothNormal')}
             </button>
             <button 
               className="btn btn-danger" 
-              onClick={() => onComplete({ left: leftTested, right: rightTested })}
+              onClick={() => onComplete({ left: leftTested, right: rightTested, loopback: loopback ?? undefined })}
             >
               <XCircle size={20} />
               {t('speaker.hasIssue')}
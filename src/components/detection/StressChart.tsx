@@ -0,0 +1,47 @@
+import { useTranslation } from 'react-i18next';
+
+export interface StressPoint {
+  elapsed_secs: number;
+  frequency_mhz: number | null;
+  temperature: number | null;
+  throughput: number;
+}
+
+interface StressChartProps {
+  samples: StressPoint[];
+  durationSecs: number;
+}
+
+const WIDTH = 320;
+const HEIGHT = 80;
+
+// Live throughput and CPU temperature while the stress test runs. Both lines
+// are scaled to their own maximum; the shape is what shows throttling.
+export function StressChart({ samples, durationSecs }: StressChartProps) {
+  const { t } = useTranslation();
+  if (samples.length < 2) return null;
+
+  const line = (values: (number | null)[]) => {
+    const max = Math.max(...values.map(v => v ?? 0));
+    if (max <= 0) return '';
+    return samples
+      .map((sample, i) => values[i] === null ? null : `${(sample.elapsed_secs / durationSecs) * WIDTH},${HEIGHT - (values[i]! / max) * (HEIGHT - 4)}`)
+      .filter(Boolean)
+      .join(' ');
+  };
+  const throughput = line(samples.map(s => s.throughput));
+  const temperature = line(samples.map(s => s.temperature));
+
+  return (
+    <div style={{ marginTop: '8px' }}>
+      <svg width={WIDTH} height={HEIGHT} style={{ border: '1px solid var(--color-border)', borderRadius: '4px' }}>
+        <polyline points={throughput} fill="none" stroke="var(--color-primary)" strokeWidth={2} />
+        {temperature && <polyline points={temperature} fill="none" stroke="var(--color-warning)" strokeWidth={1.5} />}
+      </svg>
+      <div style={{ fontSize: '12px', color: 'var(--color-text-secondary)' }}>
+        <span style={{ color: 'var(--color-primary)' }}>━</span> {t('stress.throughput')}{' '}
+        <span style={{ color: 'var(--color-warning)' }}>━</span> {t('stress.temperature')}
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,163 @@
+import { useState, useEffect, useCallback } from 'react';
+import { useTranslation } from 'react-i18next';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { CheckCircle, XCircle, SkipForward } from 'lucide-react';
+import { describeError } from '../../utils/errors';
+
+export interface TouchBarInfoData {
+  expected: boolean | null;
+  display_present: boolean;
+  backlight_present: boolean;
+  bridge: string | null;
+  bridge_firmware: string | null;
+  bridge_responding: boolean;
+  server_running: boolean;
+}
+
+export interface TouchBarTestResult {
+  tapped: string[];
+  missed: string[];
+}
+
+interface KeyEvent {
+  code: string | null;
+  raw: number;
+  pressed: boolean;
+}
+
+interface TouchBarTestProps {
+  info: TouchBarInfoData | null;
+  onComplete: (result: TouchBarTestResult) => void;
+  onSkip: () => void;
+}
+
+// Held with fn, the Touch Bar shows Esc and F1–F12, which arrive as ordinary key events
+const TOUCH_BAR_KEYS = ['Escape', 'F1', 'F2', 'F3', 'F4', 'F5', 'F6', 'F7', 'F8', 'F9', 'F10', 'F11', 'F12'];
+const TOUCH_BAR_KEYS_SET = new Set(TOUCH_BAR_KEYS);
+
+export function TouchBarTest({ info, onComplete, onSkip }: TouchBarTestProps) {
+  const { t } = useTranslation();
+  const [tapped, setTapped] = useState<Set<string>>(new Set());
+  const [captureError, setCaptureError] = useState<string | null>(null);
+
+  const handleKeyDown = useCallback((e: KeyboardEvent) => {
+    if (TOUCH_BAR_KEYS_SET.has(e.code)) {
+      e.preventDefault();
+      setTapped(prev => new Set([...prev, e.code]));
+    }
+  }, []);
+
+  useEffect(() => {
+    window.addEventListener('keydown', handleKeyDown);
+    return () => {
+      window.removeEventListener('keydown', handleKeyDown);
+    };
+  }, [handleKeyDown]);
+
+  // Same native capture as the keyboard test, so keys macOS keeps for itself still count
+  useEffect(() => {
+    const unlisten = listen<KeyEvent>('keyboard://key', event => {
+      const { code, pressed } = event.payload;
+      if (pressed && code && TOUCH_BAR_KEYS_SET.has(code)) {
+        setTapped(prev => new Set([...prev, code]));
+      }
+    });
+    invoke('start_keyboard_test').catch(e => setCaptureError(describeError(e, t)));
+    return () => {
+      unlisten.then(f => f());
+      invoke('stop_keyboard_test').catch(() => {});
+    };
+  }, []);
+
+  const handleFinish = () => {
+    onComplete({
+      tapped: TOUCH_BAR_KEYS.filter(key => tapped.has(key)),
+      missed: TOUCH_BAR_KEYS.filter(key => !tapped.has(key)),
+    });
+  };
+
+  const checks = info ? [
+    { label: info.bridge ?? t('touchBar.bridge'), ok: info.bridge_responding },
+    { label: t('touchBar.display'), ok: info.display_present },
+    { label: t('touchBar.server'), ok: info.server_running },
+  ] : [];
+
+  return (
+    <div className="touch-bar-test section">
+      <div className="container">
+        <div className="card" style={{ maxWidth: '900px', margin: '0 auto', padding: '32px' }}>
+          <h2 style={{ marginBottom: '8px', textAlign: 'center' }}>{t('touchBar.title')}</h2>
+          <p style={{ marginBottom: '24px', textAlign: 'center', color: 'var(--color-text-secondary)' }}>
+            {t('touchBar.instruction')}
+          </p>
+          {captureError && (
+            <p style={{ marginBottom: '24px', textAlign: 'center', fontSize: '13px', color: 'var(--color-warning)' }}>
+              {t('keyboard.captureUnavailable', { error: captureError })}
+            </p>
+          )}
+
+          {checks.length > 0 && (
+            <div style={{ display: 'flex', gap: '24px', justifyContent: 'center', marginBottom: '24px', fontSize: '14px' }}>
+              {checks.map(check => (
+                <span key={check.label} style={{ display: 'flex', alignItems: 'center', gap: '6px', color: check.ok ? 'var(--color-success)' : 'var(--color-danger)' }}>
+                  {check.ok ? <CheckCircle size={16} /> : <XCircle size={16} />}
+                  {check.label}
+                </span>
+              ))}
+            </div>
+          )}
+
+          <div style={{ marginBottom: '24px', textAlign: 'center' }}>
+            <span style={{
+              fontSize: '24px',
+              fontWeight: 'bold',
+              color: tapped.size === TOUCH_BAR_KEYS.length ? 'var(--color-success)' : 'var(--color-primary)'
+            }}>
+              {tapped.size} / {TOUCH_BAR_KEYS.length}
+            </span>
+            <span style={{ marginLeft: '8px', color: 'var(--color-text-secondary)' }}>
+              {t('keyboard.tested')}
+            </span>
+          </div>
+
+          <div style={{ display: 'flex', gap: '4px', justifyContent: 'center', marginBottom: '32px', padding: '8px', borderRadius: '8px', backgroundColor: '#111' }}>
+            {TOUCH_BAR_KEYS.map(key => {
+              const isTapped = tapped.has(key);
+              return (
+                <div
+                  key={key}
+                  style={{
+                    width: key === 'Escape' ? '56px' : '48px',
+                    height: '32px',
+                    display: 'flex',
+                    alignItems: 'center',
+                    justifyContent: 'center',
+                    borderRadius: '6px',
+                    fontSize: '12px',
+                    fontWeight: 500,
+                    transition: 'all 0.15s ease',
+                    backgroundColor: isTapped ? 'var(--color-success)' : '#333',
+                    color: 'white',
+                  }}
+                >
+                  {key === 'Escape' ? 'esc' : key}
+                </div>
+              );
+            })}
+          </div>
+
+          <div style={{ display: 'flex', gap: '16px', justifyContent: 'center' }}>
+            <button className="btn btn-primary" onClick={handleFinish}>
+              {t('screen.finish')}
+            </button>
+            <button className="btn btn-secondary" onClick={onSkip}>
+              <SkipForward size={20} />
+              {t('common.skip')}
+            </button>
+          </div>
+        </div>
+      </div>
+    </div>
+  );
+}
@@ -5,3 +5,7 @@ export { TrackpadTest } from './TrackpadTest';
 export { CameraTest } from './CameraTest';
 export { MicrophoneTest } from './MicrophoneTest';
 export { SpeakerTest } from './SpeakerTest';
+export { StressChart } from './StressChart';
+export { SleepTest } from './SleepTest';
+export { PortTest } from './PortTest';
+export { TouchBarTest } from './TouchBarTest';
@@ -0,0 +1,121 @@
+import { useEffect, useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { ArrowLeft, Usb, Zap, Monitor, HardDrive } from 'lucide-react';
+import { describeError } from '../../utils/errors';
+import { useUnits } from '../../utils/units';
+import type { HardwareChange, HardwareDevice } from '../detection/PortTest';
+
+interface DevicesPageProps {
+  onBack: () => void;
+}
+
+type HardwareEvent = HardwareChange['events'][number];
+
+const CATEGORIES: Array<{ category: HardwareDevice['category']; icon: typeof Usb }> = [
+  { category: 'usb', icon: Usb },
+  { category: 'thunderbolt', icon: Zap },
+  { category: 'display', icon: Monitor },
+  { category: 'storage', icon: HardDrive },
+];
+
+// Most recent first; older ones scroll off
+const MAX_EVENTS = 50;
+
+// Plug something in and see it appear: what is connected now, and each
+// device that came or went while the page was open
+export function DevicesPage({ onBack }: DevicesPageProps) {
+  const { t } = useTranslation();
+  const units = useUnits();
+  const [devices, setDevices] = useState<HardwareDevice[] | null>(null);
+  const [events, setEvents] = useState<HardwareEvent[]>([]);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    const unlisten = listen<HardwareChange>('hardware://changed', event => {
+      setDevices(event.payload.devices);
+      setEvents(prev => [...event.payload.events.slice().reverse(), ...prev].slice(0, MAX_EVENTS));
+    });
+    invoke('watch_hardware_changes')
+      .then(result => setDevices(result as HardwareDevice[]))
+      .catch(e => setError(describeError(e, t)));
+    return () => {
+      unlisten.then(f => f());
+      invoke('stop_hardware_watch').catch(() => {});
+    };
+  }, []);
+
+  const recentlyAdded = new Set(events.filter(e => e.action === 'added').map(e => e.device.id));
+  const detail = (device: HardwareDevice) => [
+    device.vendor,
+    device.speed_mbps !== null && (device.speed_mbps >= 1000
+      ? `${units.number(device.speed_mbps / 1000, 0)} Gb/s`
+      : `${units.number(device.speed_mbps, 0)} Mb/s`),
+    device.resolution,
+    device.capacity_bytes !== null && units.bytes(device.capacity_bytes),
+    device.removable && t('devices.removable'),
+    device.port && t('devices.port', { port: device.port }),
+  ].filter(Boolean).join(' · ');
+
+  return (
+    <div className="devices-page section">
+      <div className="container">
+        <div style={{ display: 'flex', alignItems: 'center', gap: '16px', marginBottom: '24px' }}>
+          <button className="btn btn-secondary" onClick={onBack} style={{ padding: '8px 16px' }}>
+            <ArrowLeft size={20} />
+          </button>
+          <h1 className="section-title" style={{ margin: 0 }}>{t('devices.title')}</h1>
+        </div>
+        <p style={{ color: 'var(--color-text-secondary)', marginBottom: '24px' }}>{t('devices.description')}</p>
+
+        {error && <div className="card" style={{ color: 'var(--color-danger)' }}>{error}</div>}
+        {!error && !devices && <div className="card">{t('devices.loading')}</div>}
+        {devices && (
+          <div className="grid grid-2" style={{ marginBottom: '24px' }}>
+            {CATEGORIES.map(({ category, icon: Icon }) => {
+              const connected = devices.filter(d => d.category === category);
+              return (
+                <div key={category} className="card">
+                  <div style={{ display: 'flex', alignItems: 'center', gap: '8px', fontWeight: 500, marginBottom: '8px' }}>
+                    <Icon size={18} />
+                    {t(`devices.categories.${category}`)}
+                  </div>
+                  {connected.length === 0 ? (
+                    <div style={{ fontSize: '13px', color: 'var(--color-text-secondary)' }}>{t('devices.none')}</div>
+                  ) : connected.map(device => (
+                    <div key={device.id} style={{ padding: '6px 0', fontSize: '13px' }}>
+                      <div style={{ fontWeight: 500, color: recentlyAdded.has(device.id) ? 'var(--color-success)' : undefined }}>
+                        {device.name}
+                      </div>
+                      <div style={{ color: 'var(--color-text-secondary)' }}>{detail(device)}</div>
+                    </div>
+                  ))}
+                </div>
+              );
+            })}
+          </div>
+        )}
+
+        {devices && (
+          <div className="card">
+            <div style={{ fontWeight: 500, marginBottom: '8px' }}>{t('devices.events')}</div>
+            {events.length === 0 ? (
+              <div style={{ fontSize: '13px', color: 'var(--color-text-secondary)' }}>{t('devices.waiting')}</div>
+            ) : events.map((event, index) => (
+              <div key={`${event.at}-${event.device.id}-${index}`} style={{ display: 'flex', gap: '12px', padding: '4px 0', fontSize: '13px' }}>
+                <span style={{ color: 'var(--color-text-secondary)', fontVariantNumeric: 'tabular-nums' }}>
+                  {new Date(event.at).toLocaleTimeString()}
+                </span>
+                <span style={{ color: event.action === 'added' ? 'var(--color-success)' : 'var(--color-warning)' }}>
+                  {t(`devices.actions.${event.action}`)}
+                </span>
+                <span>{t(`devices.categories.${event.device.category}`)} · {event.device.name}</span>
+              </div>
+            ))}
+          </div>
+        )}
+      </div>
+    </div>
+  );
+}
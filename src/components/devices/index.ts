@@ -0,0 +1 @@
+export { DevicesPage } from './DevicesPage';
@@ -0,0 +1,76 @@
+import { useEffect, useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { invoke } from '@tauri-apps/api/core';
+import { ArrowLeft } from 'lucide-react';
+import { StatusBadge } from '../common';
+import { describeError } from '../../utils/errors';
+import type { DetectionStatus } from '../../types';
+
+interface PartVerdict {
+  part: 'storage' | 'display' | 'battery' | 'keyboard';
+  verdict: 'genuine' | 'third_party' | 'unknown';
+  evidence: string;
+}
+
+interface PartsPageProps {
+  onBack: () => void;
+}
+
+const VERDICT_STATUS: Record<PartVerdict['verdict'], DetectionStatus> = {
+  genuine: 'passed',
+  third_party: 'warning',
+  unknown: 'skipped',
+};
+
+// The same verdicts that turn into third_party_* indicators in the
+// refurbishment check, with what each part reports about itself
+export function PartsPage({ onBack }: PartsPageProps) {
+  const { t } = useTranslation();
+  const [parts, setParts] = useState<PartVerdict[] | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke('check_genuine_parts')
+      .then(result => setParts(result as PartVerdict[]))
+      .catch(e => setError(describeError(e, t)));
+  }, [t]);
+
+  return (
+    <div className="parts-page section">
+      <div className="container">
+        <div style={{ display: 'flex', alignItems: 'center', gap: '16px', marginBottom: '24px' }}>
+          <button className="btn btn-secondary" onClick={onBack} style={{ padding: '8px 16px' }}>
+            <ArrowLeft size={20} />
+          </button>
+          <h1 className="section-title" style={{ margin: 0 }}>{t('parts.title')}</h1>
+        </div>
+        <p style={{ color: 'var(--color-text-secondary)', marginBottom: '24px' }}>{t('parts.description')}</p>
+
+        {error && <div className="card" style={{ color: 'var(--color-danger)' }}>{error}</div>}
+        {!error && !parts && <div className="card">{t('parts.checking')}</div>}
+        {parts && parts.length === 0 && <div className="card">{t('parts.none')}</div>}
+        {parts && parts.length > 0 && (
+          <div className="card">
+            {parts.map((part, index) => (
+              <div
+                key={`${part.part}-${index}`}
+                style={{ display: 'flex', alignItems: 'center', justifyContent: 'space-between', padding: '12px 0', borderTop: index > 0 ? '1px solid var(--color-border)' : undefined }}
+              >
+                <div>
+                  <div style={{ fontWeight: 500 }}>{t(`refurbishment.parts.${part.part}`)}</div>
+                  <div style={{ fontSize: '12px', color: 'var(--color-text-secondary)' }}>
+                    {part.evidence || t('parts.noEvidence')}
+                  </div>
+                </div>
+                <StatusBadge status={VERDICT_STATUS[part.verdict]} label={t(`parts.verdicts.${part.verdict}`)} />
+              </div>
+            ))}
+            {parts.some(part => part.verdict === 'unknown') && (
+              <p style={{ fontSize: '12px', color: 'var(--color-text-secondary)', marginTop: '12px' }}>{t('parts.unknownHint')}</p>
+            )}
+          </div>
+        )}
+      </div>
+    </div>
+  );
+}
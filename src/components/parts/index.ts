@@ -0,0 +1 @@
+export { PartsPage } from './PartsPage';
@@ -0,0 +1,72 @@
+import { useEffect, useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import type { TFunction } from 'i18next';
+import { invoke } from '@tauri-apps/api/core';
+import type { DetectionReport, SectionSummary, SummaryText, SummaryValue } from '../../types';
+import { useUnits, type UnitFormatter } from '../../utils/units';
+
+interface AccessibleSummaryProps {
+  report: DetectionReport;
+}
+
+function renderValue(value: SummaryValue, t: TFunction, units: UnitFormatter): string {
+  switch (value.kind) {
+    case 'text': return value.value;
+    case 'count': return units.number(value.value);
+    case 'percent': return `${units.number(value.value)}%`;
+    case 'bytes': return units.bytes(value.value);
+    case 'celsius': return units.temperature(value.value);
+    case 'dbm': return `${units.number(value.value)} dBm`;
+    case 'ratio': return `${units.number(value.value[0])}/${units.number(value.value[1])}`;
+    case 'message': return t(value.value);
+  }
+}
+
+function renderText(text: SummaryText, t: TFunction, units: UnitFormatter): string {
+  const args: Record<string, string> = {};
+  for (const [name, value] of Object.entries(text.args ?? {})) {
+    args[name] = renderValue(value, t, units);
+  }
+  return t(text.id, args);
+}
+
+// The report as one flat list of sections, each stating its status in words
+// so nothing depends on colour or icons. Rendered in high contrast.
+export function AccessibleSummary({ report }: AccessibleSummaryProps) {
+  const { t } = useTranslation();
+  const units = useUnits();
+  const [summaries, setSummaries] = useState<SectionSummary[] | null>(null);
+  const [error, setError] = useState(false);
+
+  useEffect(() => {
+    invoke('summarize_report', { report })
+      .then(result => setSummaries(result as SectionSummary[]))
+      .catch(e => {
+        console.error('Failed to summarize report:', e);
+        setError(true);
+      });
+  }, [report]);
+
+  return (
+    <section className="accessible-report" aria-labelledby="accessible-report-title">
+      <h2 id="accessible-report-title">{t('a11y.title', { model: report.deviceOverview.model })}</h2>
+      <p>{report.deviceOverview.os} · {t('hardware.serialNumber')}: {report.deviceOverview.serialNumber}</p>
+      {error && <p role="alert">{t('a11y.unavailable')}</p>}
+      {!summaries && !error && <p aria-live="polite">{t('common.loading')}</p>}
+      {summaries && (
+        <ul>
+          {summaries.map(summary => (
+            <li key={summary.section} className={`accessible-item accessible-${summary.status}`}>
+              <h3>{renderText(summary.label, t, units)}</h3>
+              <p>
+                <strong className="accessible-status">{t(`a11y.status.${summary.status}`)}</strong>
+                {summary.value && ` · ${renderValue(summary.value, t, units)}`}
+              </p>
+              <p>{renderText(summary.sentence, t, units)}</p>
+            </li>
+          ))}
+        </ul>
+      )}
+    </section>
+  );
+}
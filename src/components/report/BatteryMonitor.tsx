@@ -0,0 +1,112 @@
+import { useEffect, useRef, useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+import { Play, Square } from 'lucide-react';
+import { describeError } from '../../utils/errors';
+import { useUnits } from '../../utils/units';
+
+interface BatterySample {
+  elapsed_secs: number;
+  charge_percent: number | null;
+  voltage: number | null;
+  amperage: number | null;
+  temperature: number | null;
+  remaining_wh: number | null;
+  full_charge_wh: number | null;
+}
+
+interface BatteryMonitorResult {
+  duration_secs: number;
+  discharge_watts: number | null;
+  charge_watts: number | null;
+  percent_per_hour: number | null;
+  estimated_runtime_hours: number | null;
+  samples: BatterySample[];
+}
+
+const SAMPLE_INTERVAL_SECS = 5;
+
+// Measures real drain (or charge) rate over a few minutes of use, which the
+// static health percentage can't show. The buttons stay out of the PDF; the
+// result lands in it.
+export function BatteryMonitor() {
+  const { t } = useTranslation();
+  const units = useUnits();
+  const [running, setRunning] = useState(false);
+  const [latest, setLatest] = useState<BatterySample | null>(null);
+  const [result, setResult] = useState<BatteryMonitorResult | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const unlisten = useRef<UnlistenFn | null>(null);
+
+  // Leaving the report stops the measurement
+  useEffect(() => () => {
+    unlisten.current?.();
+    invoke('stop_battery_monitor').catch(() => {});
+  }, []);
+
+  const start = async () => {
+    setError(null);
+    setResult(null);
+    unlisten.current = await listen<BatterySample>('battery://sample', event => setLatest(event.payload));
+    try {
+      const first = await invoke('start_battery_monitor', { intervalSecs: SAMPLE_INTERVAL_SECS }) as BatterySample | null;
+      if (!first) {
+        setError(t('errors.noBattery'));
+        unlisten.current();
+        return;
+      }
+      setLatest(first);
+      setRunning(true);
+    } catch (e) {
+      setError(describeError(e, t));
+      unlisten.current();
+    }
+  };
+
+  const stop = async () => {
+    unlisten.current?.();
+    setRunning(false);
+    try {
+      setResult(await invoke('stop_battery_monitor') as BatteryMonitorResult);
+    } catch (e) {
+      setError(describeError(e, t));
+    }
+  };
+
+  const watts = latest?.voltage != null && latest.amperage != null ? latest.voltage * latest.amperage : null;
+
+  return (
+    <div style={{ marginTop: '8px' }}>
+      {!running ? (
+        <button className="btn btn-secondary" onClick={start} data-html2canvas-ignore style={{ padding: '4px 12px', fontSize: '13px' }}>
+          <Play size={14} />
+          {t('battery.monitor.start')}
+        </button>
+      ) : (
+        <>
+          <p>
+            {t('battery.monitor.live', { seconds: Math.round(latest?.elapsed_secs ?? 0) })}
+            {latest?.charge_percent != null && ` · ${units.number(latest.charge_percent, 1)}%`}
+            {watts !== null && ` · ${units.number(watts, 1)} W`}
+            {latest?.temperature != null && ` · ${units.temperature(latest.temperature)}`}
+          </p>
+          <button className="btn btn-secondary" onClick={stop} data-html2canvas-ignore style={{ padding: '4px 12px', fontSize: '13px' }}>
+            <Square size={14} />
+            {t('battery.monitor.stop')}
+          </button>
+        </>
+      )}
+      {result && (
+        <p>
+          {result.discharge_watts !== null && t('battery.monitor.discharge', { watts: units.number(result.discharge_watts, 1) })}
+          {result.charge_watts !== null && ` ${t('battery.monitor.charge', { watts: units.number(result.charge_watts, 1) })}`}
+          {result.estimated_runtime_hours !== null && ` · ${t('battery.monitor.runtime', { hours: units.number(result.estimated_runtime_hours, 1) })}`}
+          {result.percent_per_hour !== null && ` · ${units.number(result.percent_per_hour, 1)}%/h`}
+          {result.discharge_watts === null && result.charge_watts === null && t('battery.monitor.tooShort')}
+        </p>
+      )}
+      {error && <p style={{ color: 'var(--color-warning)' }}>{error}</p>}
+    </div>
+  );
+}
@@ -1,22 +1,42 @@
-import { useRef, useState } from 'react';
+import { useEffect, useRef, useState } from 'react';
 import { useTranslation } from 'react-i18next';
 import { jsPDF } from 'jspdf';
 import html2canvas from 'html2canvas';
+import { invoke } from '@tauri-apps/api/core';
 import { save } from '@tauri-apps/plugin-dialog';
 import { writeFile } from '@tauri-apps/plugin-fs';
 import { 
   Download, ArrowLeft, CheckCircle, AlertCircle, XCircle,
   Cpu, Battery, HardDrive, Monitor, Keyboard, Wifi, Mouse, Camera, Mic, Volume2,
-  Info, RefreshCcw
+  Info, RefreshCcw, FileText, Thermometer, Moon, Usb, Eye, RectangleHorizontal, Lock, PenLine, Trash2
 } from 'lucide-react';
 import { ScoreCircle } from '../common';
-import type { DetectionReport } from '../../types';
+import { BatteryMonitor } from './BatteryMonitor';
+import { AccessibleSummary } from './AccessibleSummary';
+import type { DetectionReport, DeviceGrade, ReportAnnotation, ReportSeal } from '../../types';
+import { buildNarrative } from '../../utils/narrative';
+import { buildListingDraft, listingDraftToCsv } from '../../utils/listingDraft';
+import { useUnits } from '../../utils/units';
+import { useScanConfig } from '../../utils/scanConfig';
+import { describeError } from '../../utils/errors';
 
 interface ReportPageProps {
   report: DetectionReport;
   onBack: () => void;
 }
 
+// A CPU this hot while the scan is all that's running points at the cooling
+const CPU_HOT_CELSIUS = 90;
+
+// What seal_report returns
+interface SealData {
+  report_id: string;
+  version: number;
+  sealed_at: number; // Unix seconds
+  supersedes: string | null;
+  hash: string;
+}
+
 interface IssueItem {
   category: string;
   icon: typeof Cpu;
@@ -30,16 +50,31 @@ interface IssueItem {
 export function ReportPage({ report, onBack }: ReportPageProps) {
   const { t, i18n } = useTranslation();
   const reportRef = useRef<HTMLDivElement>(null);
-  const [showDownloadSuccess, setShowDownloadSuccess] = useState(false);
+  const [successMessage, setSuccessMessage] = useState<string | null>(null);
   const [downloadError, setDownloadError] = useState<string | null>(null);
   const [isDownloading, setIsDownloading] = useState(false);
+  const [isExportingFull, setIsExportingFull] = useState(false);
+  const [isExportingCertificate, setIsExportingCertificate] = useState(false);
+  const [accessibleView, setAccessibleView] = useState(() => localStorage.getItem('accessibleReport') === 'true');
+  const [annotations, setAnnotations] = useState<ReportAnnotation[]>(report.annotations ?? []);
+  const [annotationDraft, setAnnotationDraft] = useState('');
+  // The latest seal, and whether the report still matches it. Editing a
+  // sealed report starts its next version; the sealed one stays on disk.
+  const [seal, setSeal] = useState<ReportSeal | null>(null);
+  const [isSealed, setIsSealed] = useState(false);
+  const [grade, setGrade] = useState<DeviceGrade | null>(null);
   const isZh = i18n.language === 'zh';
+  const units = useUnits();
+  const { battery: batteryThresholds } = useScanConfig();
 
   // Translate indicator description from key
   const translateIndicatorDesc = (desc: string): string => {
     // Check if it contains a colon (e.g., "third_party_storage:SAMSUNG SSD")
     if (desc.includes(':')) {
-      const [key, value] = desc.split(':');
+      // Only the first colon separates the key; values can hold more (MAC addresses)
+      const index = desc.indexOf(':');
+      const key = desc.slice(0, index);
+      const value = desc.slice(index + 1);
       const translatedKey = t(`refurbishment.indicatorDesc.${key}`, { defaultValue: '' });
       if (translatedKey) {
         return `${translatedKey}: ${value}`;
@@ -50,6 +85,12 @@ export function ReportPage({ report, onBack }: ReportPageProps) {
     return translated || desc;
   };
 
+  // One help entry per distinct topic, so repeated indicators aren't explained twice
+  const indicatorHelp = (report.refurbishment?.indicators ?? [])
+    .filter(i => i.detected && i.help)
+    .map(i => i.help!)
+    .filter((help, idx, all) => all.findIndex(h => h.explanation === help.explanation) === idx);
+
   // Translate part name
   const translatePartName = (part: string): string => {
     const translated = t(`refurbishment.parts.${part}`, { defaultValue: '' });
@@ -68,15 +109,15 @@ export function ReportPage({ report, onBack }: ReportPageProps) {
     const issues: IssueItem[] = [];
 
     // Battery issues
-    if (report.battery.health < 80) {
+    if (report.battery.health < batteryThresholds.good_percent) {
       issues.push({
         category: 'battery',
         icon: Battery,
-        level: report.battery.health < 60 ? 'failed' : 'warning',
+        level: report.battery.health < batteryThresholds.fair_percent ? 'failed' : 'warning',
         title: isZh ? '电池健康度偏低' : 'Low Battery Health',
         description: isZh 
-          ? `当前电池健康度为 ${Math.round(report.battery.health)}%，低于 80% 的良好标准。`
-          : `Current battery health is ${Math.round(report.battery.health)}%, below the 80% good standard.`,
+          ? `当前电池健康度为 ${Math.round(report.battery.health)}%，低于 ${batteryThresholds.good_percent}% 的良好标准。`
+          : `Current battery health is ${Math.round(report.battery.health)}%, below the ${batteryThresholds.good_percent}% good standard.`,
         suggestion: isZh 
           ? '建议：电池续航可能不如新机，考虑更换电池或在价格上适当议价。'
           : 'Suggestion: Battery life may be shorter than new. Consider battery replacement or price negotiation.',
@@ -84,11 +125,11 @@ export function ReportPage({ report, onBack }: ReportPageProps) {
       });
     }
 
-    if (report.battery.cycleCount > 500) {
+    if (report.battery.cycleCount > batteryThresholds.cycle_warning) {
       issues.push({
         category: 'battery',
         icon: Battery,
-        level: report.battery.cycleCount > 800 ? 'failed' : 'warning',
+        level: report.battery.cycleCount > batteryThresholds.cycle_limit ? 'failed' : 'warning',
         title: isZh ? '电池循环次数较高' : 'High Battery Cycle Count',
         description: isZh
           ? `当前循环次数为 ${report.battery.cycleCount} 次。macOS 设计寿命为 1000 次，Windows 通常 300-500 次。`
@@ -100,6 +141,118 @@ export function ReportPage({ report, onBack }: ReportPageProps) {
       });
     }
 
+    const power = report.powerEvents;
+    if (power?.chronicDrain) {
+      issues.push({
+        category: 'battery',
+        icon: Battery,
+        level: 'warning',
+        title: isZh ? '睡眠时耗电过快' : 'Battery Drains During Sleep',
+        description: isZh
+          ? `最近 ${power.days} 天有 ${power.drainIncidents} 次睡眠每小时掉电超过 3%，最严重时达到 ${units.number(power.worstDrainPercentPerHour ?? 0, 1)}%/小时。`
+          : `${power.drainIncidents} sleeps in the last ${power.days} days lost more than 3% an hour, up to ${units.number(power.worstDrainPercentPerHour ?? 0, 1)}%/h.`,
+        suggestion: isZh
+          ? '建议：合盖过夜后电量明显下降，可能是后台唤醒、网卡或主板漏电导致，购买前请向卖家确认。'
+          : 'Suggestion: The battery will be noticeably lower after a night closed. Background wakes, a network card or a board leak are the usual causes; ask the seller about it.',
+        data: `${units.number(power.worstDrainPercentPerHour ?? 0, 1)}%/h`,
+      });
+    }
+    if (power?.chronicWakes) {
+      issues.push({
+        category: 'sleep',
+        icon: Moon,
+        level: 'warning',
+        title: isZh ? '频繁意外唤醒' : 'Frequent Unexpected Wakes',
+        description: isZh
+          ? `最近 ${power.days} 天 ${power.sleeps} 次睡眠中有 ${power.unexpectedWakes} 次并非由用户唤醒。`
+          : `${power.unexpectedWakes} of ${power.sleeps} sleeps in the last ${power.days} days ended without the user waking the machine.`,
+        suggestion: isZh
+          ? '建议：检查网络唤醒和外接设备设置；若无法消除，可能是硬件故障。'
+          : 'Suggestion: Check wake-on-network and attached devices. If the wakes persist, suspect a hardware fault.',
+        data: power.wakeSources.slice(0, 3).map(source => `${source.reason} ×${source.count}`).join(', '),
+      });
+    }
+    if (power && power.failedSleeps > 0) {
+      issues.push({
+        category: 'sleep',
+        icon: Moon,
+        level: 'warning',
+        title: isZh ? '睡眠或唤醒失败' : 'Failed Sleep or Resume',
+        description: isZh
+          ? `最近 ${power.days} 天的电源日志记录了 ${power.failedSleeps} 次睡眠或唤醒失败。`
+          : `The power log records ${power.failedSleeps} failed sleep or resume attempt(s) in the last ${power.days} days.`,
+        suggestion: isZh
+          ? '建议：偶发一次问题不大；反复出现通常与驱动、固件或主板有关。'
+          : 'Suggestion: A single failure is harmless. Repeated ones usually point to drivers, firmware or the board.',
+        data: `${power.failedSleeps}`,
+      });
+    }
+
+    const crashes = report.crashHistory;
+    if (crashes?.repeated) {
+      const component = crashes.topComponent ? t(`crashes.components.${crashes.topComponent}`) : undefined;
+      issues.push({
+        category: 'crashes',
+        icon: AlertCircle,
+        level: 'warning',
+        title: isZh ? '系统反复崩溃' : 'Repeated System Crashes',
+        description: isZh
+          ? `最近 ${crashes.monthsCovered} 个月系统崩溃（内核错误或蓝屏）${crashes.total} 次${component ? `，多数与${component}有关` : ''}。`
+          : `The system crashed (kernel panic or blue screen) ${crashes.total} times in the last ${crashes.monthsCovered} months${component ? ` (mostly ${component})` : ''}.`,
+        suggestion: isZh
+          ? '建议：反复崩溃往往是显卡、内存或硬盘即将故障的征兆，单次检测未必能发现。请向卖家询问，并在购买前长时间试用。'
+          : 'Suggestion: Repeated crashes often come from a failing GPU, memory or SSD that a one-off check misses. Ask the seller about them and use the machine for longer before buying.',
+        data: crashes.crashes[0]?.at.slice(0, 10),
+      });
+    }
+
+    const rtc = report.rtcBattery;
+    if (rtc?.likelyFlat) {
+      const latest = rtc.resets[0];
+      issues.push({
+        category: 'rtc',
+        icon: Battery,
+        level: 'warning',
+        title: isZh ? '主板时钟电池可能没电' : 'Clock Battery Likely Flat',
+        description: isZh
+          ? `系统时钟重置过 ${rtc.resets.length} 次${latest ? `，最近一次开机时显示为 ${latest.clockRead.slice(0, 10)}` : ''}。主板上的纽扣电池没电后，每次断电时钟都会回到出厂日期。`
+          : `The system clock was reset ${rtc.resets.length} time(s)${latest ? `, most recently reading ${latest.clockRead.slice(0, 10)} at boot` : ''}. Once the coin cell on the board is flat, the clock goes back to the firmware's date every time power is cut.`,
+        suggestion: isZh
+          ? '建议：更换主板纽扣电池（CR2032 等）成本很低，但不换会在每次冷启动后出现证书错误和日期错误。'
+          : 'Suggestion: Replacing the coin cell (usually a CR2032) is cheap, but without it every cold start brings certificate errors and a wrong date.',
+        data: latest?.at.slice(0, 10),
+      });
+    }
+
+    const usage = report.usage;
+    if (usage?.usage === 'heavy' || usage?.usage === 'exceeds_age') {
+      const years = units.number((usage.calendarAgeDays ?? 0) / 365, 1);
+      const hours = units.number(usage.estimatedUsageHours ?? 0);
+      issues.push({
+        category: 'usage',
+        icon: Info,
+        level: 'warning',
+        title: usage.usage === 'heavy'
+          ? (isZh ? '使用强度高' : 'Heavily Used')
+          : (isZh ? '使用时长与机龄不符' : 'More Use Than the Machine\'s Age Allows'),
+        description: usage.usage === 'heavy'
+          ? (isZh
+            ? `这台约 ${years} 年的机器已使用约 ${hours} 小时，平均每天 ${units.number(usage.hoursPerDay ?? 0, 1)} 小时，远高于一般个人使用。`
+            : `About ${hours} hours of use in roughly ${years} years, ${units.number(usage.hoursPerDay ?? 0, 1)} hours a day on average, far more than typical personal use.`)
+          : (isZh
+            ? `硬盘或电池记录的约 ${hours} 小时使用时长超过了机器自身日期（${usage.firstSeen}）所允许的范围。`
+            : `The drive or battery records about ${hours} hours of use, more than fits since the earliest date the machine gives (${usage.firstSeen}).`),
+        suggestion: usage.usage === 'heavy'
+          ? (isZh
+            ? '建议：可能曾作为办公机、常开主机或租赁设备使用，风扇、键盘、电池和硬盘磨损会更明显，议价时可作为参考。'
+            : 'Suggestion: It may have been an office machine, always-on box or rental. Expect more wear on fans, keyboard, battery and storage, and factor it into the price.')
+          : (isZh
+            ? '建议：硬盘可能来自另一台更旧的机器，或系统日期被改动过，请向卖家确认。'
+            : 'Suggestion: The drive may come from an older machine, or the dates were changed. Ask the seller.'),
+        data: usage.hoursPerDay !== undefined ? `${units.number(usage.hoursPerDay, 1)} h/day` : `${hours} h`,
+      });
+    }
+
     // Storage issues
     if (report.storage.smartStatus !== 'healthy') {
       issues.push({
@@ -117,6 +270,71 @@ export function ReportPage({ report, onBack }: ReportPageProps) {
       });
     }
 
+    // Thermal issues
+    const stalledFans = report.thermal?.fans.filter(fan => fan.stalled) ?? [];
+    if (stalledFans.length > 0) {
+      issues.push({
+        category: 'thermal',
+        icon: Thermometer,
+        level: 'failed',
+        title: isZh ? '风扇不转' : 'Fan Not Spinning',
+        description: isZh
+          ? `${stalledFans.length} 个风扇转速为 0，但系统要求其保持最低转速。`
+          : `${stalledFans.length} fan(s) report 0 RPM although the system asks for a minimum speed.`,
+        suggestion: isZh
+          ? '风扇可能已损坏或被灰尘卡住，长期使用会导致过热降频，建议要求维修或降价。'
+          : 'The fan may be dead or clogged; the machine will overheat and throttle. Ask for a repair or a lower price.',
+        data: stalledFans.map(fan => `#${fan.index + 1}`).join(', '),
+      });
+    }
+    if (report.cpuStress?.throttleOnsetSecs !== undefined) {
+      const seconds = Math.round(report.cpuStress.throttleOnsetSecs);
+      issues.push({
+        category: 'thermal',
+        icon: Thermometer,
+        level: 'warning',
+        title: isZh ? 'CPU 满载降频' : 'CPU Throttles Under Load',
+        description: isZh
+          ? `压力测试第 ${seconds} 秒起 CPU 因过热降频，最终只保持峰值性能的 ${report.cpuStress.score}%。`
+          : `The CPU started throttling from heat ${seconds}s into the stress test and held only ${report.cpuStress.score}% of its peak performance.`,
+        suggestion: isZh
+          ? '空闲时看不出问题，但长时间高负载会明显变慢。通常是硅脂老化或风扇故障，建议维修后再购买或压价。'
+          : 'It looks fine at idle but slows down under sustained load, usually from dried thermal paste or a weak fan. Get it serviced or negotiate the price.',
+        data: `${report.cpuStress.score}/100`,
+      });
+    }
+    if (report.memoryTest && report.memoryTest.faultCount > 0) {
+      issues.push({
+        category: 'memory',
+        icon: Cpu,
+        level: 'failed',
+        title: isZh ? '内存校验错误' : 'Memory Errors',
+        description: isZh
+          ? `内存测试中有 ${report.memoryTest.faultCount} 处读回的数据与写入的不一致。`
+          : `${report.memoryTest.faultCount} word(s) read back differently from what the memory test wrote.`,
+        suggestion: isZh
+          ? '内存不稳定会导致随机崩溃和数据损坏。板载内存无法单独更换，建议放弃购买或要求维修。'
+          : 'Unstable RAM causes random crashes and corrupted files. Soldered memory cannot be swapped on its own; walk away or ask for a repair.',
+        data: report.memoryTest.faults.slice(0, 3).map(fault => fault.address).join(', '),
+      });
+    }
+    const cpuSensor = report.thermal?.sensors.find(sensor => sensor.name === 'cpu');
+    if (cpuSensor && cpuSensor.celsius >= CPU_HOT_CELSIUS) {
+      issues.push({
+        category: 'thermal',
+        icon: Thermometer,
+        level: 'warning',
+        title: isZh ? 'CPU 温度过高' : 'CPU Running Hot',
+        description: isZh
+          ? `检测期间 CPU 温度达到 ${units.temperature(cpuSensor.celsius)}，轻负载下不应如此之高。`
+          : `The CPU reached ${units.temperature(cpuSensor.celsius)} during the scan, which is too hot for a light load.`,
+        suggestion: isZh
+          ? '可能是散热硅脂老化或散热器积灰，建议清灰并更换硅脂后复测。'
+          : 'Usually dried thermal paste or a dusty heatsink; have it cleaned and repasted, then test again.',
+        data: units.temperature(cpuSensor.celsius),
+      });
+    }
+
     // Screen issues
     if (report.interactive.screen.hasDeadPixel) {
       issues.push({
@@ -133,6 +351,38 @@ export function ReportPage({ report, onBack }: ReportPageProps) {
       });
     }
 
+    // A laptop (it has a battery) whose own keyboard or trackpad doesn't show
+    // up has a dead part or a disconnected cable, whatever an external one does
+    const isLaptop = report.battery.designCapacity > 0;
+    if (isLaptop && report.inputDevices?.internalKeyboard === false) {
+      issues.push({
+        category: 'keyboard',
+        icon: Keyboard,
+        level: 'failed',
+        title: isZh ? '未检测到内置键盘' : 'Built-in Keyboard Not Detected',
+        description: isZh
+          ? '系统中找不到内置键盘，键盘可能已损坏或排线未连接。外接键盘不能代替它。'
+          : 'The system does not see a built-in keyboard. It may be dead or its cable disconnected; an external keyboard does not count.',
+        suggestion: isZh
+          ? '建议：检查键盘排线，维修后再购买。'
+          : 'Suggestion: Have the keyboard cable checked and repaired before purchase.',
+      });
+    }
+    if (isLaptop && report.inputDevices?.internalTrackpad === false) {
+      issues.push({
+        category: 'trackpad',
+        icon: Mouse,
+        level: 'failed',
+        title: isZh ? '未检测到内置触控板' : 'Built-in Trackpad Not Detected',
+        description: isZh
+          ? '系统中找不到内置触控板，触控板可能已损坏或排线未连接。'
+          : 'The system does not see a built-in trackpad. It may be dead or its cable disconnected.',
+        suggestion: isZh
+          ? '建议：检查触控板排线，维修后再购买。'
+          : 'Suggestion: Have the trackpad cable checked and repaired before purchase.',
+      });
+    }
+
     // Keyboard issues
     const keyboardTestedRatio = report.interactive.keyboard.totalKeys > 0 
       ? (report.interactive.keyboard.testedKeys?.length || 0) / report.interactive.keyboard.totalKeys 
@@ -175,6 +425,36 @@ export function ReportPage({ report, onBack }: ReportPageProps) {
       }
     }
 
+    // Touch Bar issues; the bridge check counts even when the tap test was skipped
+    const touchBar = report.interactive.touchBar;
+    if (touchBar && (!touchBar.displayPresent || !touchBar.bridgeResponding)) {
+      issues.push({
+        category: 'touchBar',
+        icon: RectangleHorizontal,
+        level: 'failed',
+        title: isZh ? 'Touch Bar 无响应' : 'Touch Bar Not Responding',
+        description: isZh
+          ? '该机型应配有 Touch Bar，但系统未检测到其显示控制器' + (touchBar.bridgeResponding ? '。' : '，安全芯片也没有响应。')
+          : 'This model should have a Touch Bar, but the system does not see its display controller' + (touchBar.bridgeResponding ? '.' : ', and the security chip did not respond.'),
+        suggestion: isZh
+          ? '建议：更换 Touch Bar 费用接近更换键盘，卖家常会调暗亮度来掩盖，请在议价时考虑。'
+          : 'Suggestion: A Touch Bar costs about as much as a keyboard to replace, and sellers often hide a dead one by dimming it; factor it into the price.',
+      });
+    } else if (touchBar?.tested && touchBar.missedKeys.length > 0) {
+      issues.push({
+        category: 'touchBar',
+        icon: RectangleHorizontal,
+        level: touchBar.tappedKeys.length === 0 ? 'failed' : 'warning',
+        title: isZh ? 'Touch Bar 部分区域无响应' : 'Touch Bar Dead Zones',
+        description: isZh
+          ? `以下按键点击无响应：${touchBar.missedKeys.join('、')}`
+          : `These keys did not respond to a tap: ${touchBar.missedKeys.join(', ')}`,
+        suggestion: isZh
+          ? '建议：按住 fn 重新点击确认；仍无响应说明触摸层损坏，需要更换 Touch Bar。'
+          : 'Suggestion: Hold fn and tap them again. If they still do not respond, the digitizer is damaged and the Touch Bar needs replacing.',
+      });
+    }
+
     // Camera issues
     if (report.interactive.camera.tested && !report.interactive.camera.working) {
       issues.push({
@@ -182,7 +462,9 @@ export function ReportPage({ report, onBack }: ReportPageProps) {
         icon: Camera,
         level: 'failed',
         title: isZh ? '摄像头无法工作' : 'Camera Not Working',
-        description: isZh
+        description: report.cameras?.length === 0
+          ? (isZh ? '系统中没有检测到摄像头，常见于更换屏幕后摄像头排线未接回。' : 'No camera is detected at all. This often means its cable was not reconnected after a screen replacement.')
+          : isZh
           ? '摄像头检测失败，无法正常获取画面。'
           : 'Camera test failed. Unable to capture video.',
         suggestion: isZh
@@ -207,7 +489,42 @@ export function ReportPage({ report, onBack }: ReportPageProps) {
       });
     }
 
+    if (report.audio && report.audio.inputs.length === 0) {
+      issues.push({
+        category: 'microphone',
+        icon: Mic,
+        level: 'failed',
+        title: isZh ? '未检测到麦克风' : 'No Microphone Detected',
+        description: isZh
+          ? '系统中没有任何音频输入设备，内置麦克风可能已损坏或排线未连接。'
+          : 'The system reports no audio input at all. The built-in microphone may be dead or its cable disconnected.',
+        suggestion: isZh
+          ? '建议：检查麦克风排线，维修后再购买。'
+          : 'Suggestion: Have the microphone cable checked and repaired before purchase.',
+      });
+    }
+
     // Speaker issues
+    const silentChannels = (report.interactive.speaker.loopback ?? []).filter(c => !c.detected);
+    if (silentChannels.length > 0) {
+      const names = silentChannels.map(c => c.channel === 'left' ? (isZh ? '左声道' : 'left channel') : (isZh ? '右声道' : 'right channel'));
+      issues.push({
+        category: 'speaker',
+        icon: Volume2,
+        level: 'warning',
+        title: isZh ? '麦克风未收到扬声器声音' : 'Speaker Not Heard by Microphone',
+        description: silentChannels.length === report.interactive.speaker.loopback!.length
+          ? (isZh
+            ? '两个声道播放的测试音都没有被麦克风录到，扬声器或麦克风其中之一可能有故障。'
+            : 'The microphone picked up neither channel\'s test tones. Either the speakers or the microphone is faulty.')
+          : (isZh
+            ? `以下声道播放的测试音没有被麦克风录到：${names.join('、')}`
+            : `The microphone did not pick up the test tones from: ${names.join(', ')}`),
+        suggestion: isZh
+          ? '建议：拔掉耳机并调高音量后重新测试，仍然失败说明扬声器损坏。'
+          : 'Suggestion: Unplug headphones, turn the volume up and test again. If it still fails, the speaker is damaged.',
+      });
+    }
     if (report.interactive.speaker.tested) {
       const speakerIssues = [];
       if (!report.interactive.speaker.leftChannel) speakerIssues.push(isZh ? '左声道' : 'left channel');
@@ -229,6 +546,87 @@ export function ReportPage({ report, onBack }: ReportPageProps) {
       }
     }
 
+    // Wi-Fi issues
+    const wifi = report.network.wifi;
+    if (!wifi.available) {
+      issues.push({
+        category: 'network',
+        icon: Wifi,
+        level: 'failed',
+        title: isZh ? '未检测到无线网卡' : 'No Wi-Fi Adapter Detected',
+        description: isZh
+          ? '系统中没有任何无线网卡，网卡可能已损坏、被拆除或驱动缺失。'
+          : 'The system reports no Wi-Fi adapter. The card may be dead, removed, or missing its driver.',
+        suggestion: isZh
+          ? '建议：确认网卡是否在位及驱动是否安装，维修后再购买。'
+          : 'Suggestion: Check that the card is fitted and its driver installed before purchase.',
+      });
+    }
+    // Measured next to the access point, a healthy laptop is well above -70 dBm
+    const rssi = wifi.connection?.rssiDbm;
+    if (rssi !== undefined && rssi <= -75) {
+      const snr = wifi.connection?.noiseDbm !== undefined ? rssi - wifi.connection.noiseDbm : undefined;
+      issues.push({
+        category: 'network',
+        icon: Wifi,
+        level: 'warning',
+        title: isZh ? 'Wi-Fi 信号弱' : 'Weak Wi-Fi Signal',
+        description: isZh
+          ? `信号强度 ${rssi} dBm${snr !== undefined ? `，信噪比 ${snr} dB` : ''}。如果检测时离路由器不远，天线排线可能在换屏时被压伤或未插好。`
+          : `Signal strength is ${rssi} dBm${snr !== undefined ? ` with a ${snr} dB signal-to-noise ratio` : ''}. If the scan ran near the access point, an antenna cable may have been pinched or left unplugged during a screen swap.`,
+        suggestion: isZh
+          ? '建议：靠近路由器重新检测；信号仍然很弱则需检查屏幕转轴处的天线排线。'
+          : 'Suggestion: Rescan next to the access point. If the signal stays weak, have the antenna cables in the display hinge checked.',
+      });
+    }
+
+    // Port issues
+    if (report.interactive.ports?.tested && report.interactive.ports.deadPortReported) {
+      const working = report.interactive.ports.ports.length;
+      issues.push({
+        category: 'ports',
+        icon: Usb,
+        level: 'failed',
+        title: isZh ? '接口无响应' : 'Dead Port',
+        description: isZh
+          ? `插入测试设备时至少有一个接口没有检测到设备（${working} 个接口正常）。`
+          : `At least one port did not detect the test device (${working} port(s) worked).`,
+        suggestion: isZh
+          ? '建议：换一个已知正常的设备或数据线再试；如仍无响应，多为接口或主板损坏，维修费用较高，请在议价时考虑。'
+          : 'Suggestion: Retry with a known-good device and cable. If the port stays dead, the port or board is damaged, which is costly to repair; factor it into the price.',
+      });
+    }
+
+    // Sleep issues
+    const sleep = report.interactive.sleep;
+    if (sleep?.tested && !sleep.slept) {
+      issues.push({
+        category: 'sleep',
+        icon: Moon,
+        level: 'failed',
+        title: isZh ? '无法进入睡眠' : 'Sleep Failed',
+        description: isZh
+          ? '请求睡眠后，电源日志中没有找到睡眠记录。'
+          : 'The power log shows no sleep after sleep was requested.',
+        suggestion: isZh
+          ? '建议：无法睡眠会在合盖时耗尽电量甚至发热，常见于主板或固件问题，购买前请让卖家说明。'
+          : 'Suggestion: A machine that cannot sleep drains and heats up in a closed bag. This usually points to a board or firmware problem; ask the seller about it.',
+      });
+    } else if (sleep?.tested && (sleep.instantWake || sleep.abnormalWakes.length > 0)) {
+      issues.push({
+        category: 'sleep',
+        icon: Moon,
+        level: 'warning',
+        title: isZh ? '睡眠异常唤醒' : 'Abnormal Wake From Sleep',
+        description: sleep.instantWake
+          ? (isZh ? `进入睡眠后 ${sleep.secondsAsleep} 秒即被唤醒。` : `The machine woke ${sleep.secondsAsleep}s after going to sleep.`)
+          : (isZh ? `唤醒来源：${sleep.abnormalWakes.join('、')}` : `Woken by: ${sleep.abnormalWakes.join(', ')}`),
+        suggestion: isZh
+          ? '建议：断开外接设备后重试；如仍异常，可能是网卡、USB 控制器或主板故障。'
+          : 'Suggestion: Retry with all accessories unplugged. If it persists, a network card, USB controller or the board may be at fault.',
+      });
+    }
+
     // Refurbishment issues
     if (report.refurbishment?.isRefurbished) {
       const refurb = report.refurbishment;
@@ -290,6 +688,144 @@ export function ReportPage({ report, onBack }: ReportPageProps) {
   const warnings = issues.filter(i => i.level === 'warning');
   const failures = issues.filter(i => i.level === 'failed');
 
+  // Log the scan once for the daily digest; the ref keeps a re-run effect from logging it twice
+  const recordedRef = useRef(false);
+  useEffect(() => {
+    if (recordedRef.current) return;
+    recordedRef.current = true;
+    const serial = report.deviceOverview.serialNumber;
+    invoke('record_scan', {
+      entry: {
+        report_id: report.id,
+        device: report.deviceOverview.model,
+        serial: serial && serial !== 'Unknown' ? serial : null,
+        failures: [...new Set(failures.map(i => i.category))],
+        warnings: warnings.length,
+      },
+    }).catch(error => console.error('Failed to record scan:', error));
+  }, []);
+
+  // Graded in the backend so the same report always gets the same grade
+  useEffect(() => {
+    invoke<DeviceGrade>('compute_device_grade', { report })
+      .then(setGrade)
+      .catch(error => console.error('Failed to grade device:', error));
+  }, [report]);
+
+  // Export a listing draft as JSON or CSV depending on the chosen extension
+  const exportListingDraft = async () => {
+    try {
+      const filePath = await save({
+        defaultPath: `QuickScan_Listing_${report.id}.json`,
+        filters: [
+          { name: 'JSON', extensions: ['json'] },
+          { name: 'CSV', extensions: ['csv'] },
+        ],
+      });
+      if (!filePath) return;
+
+      const draft = buildListingDraft(report, t, units, isSealed && seal ? seal : undefined);
+      const content = filePath.toLowerCase().endsWith('.csv')
+        ? listingDraftToCsv(draft)
+        : JSON.stringify(draft, null, 2);
+      await writeFile(filePath, new TextEncoder().encode(content));
+      invoke('run_scan_hook', { stage: 'post_export', reportPath: filePath })
+        .catch(hookError => console.error('post_export hook failed:', hookError));
+
+      setSuccessMessage(t('report.listingExported'));
+      setTimeout(() => setSuccessMessage(null), 3000);
+    } catch (error: unknown) {
+      console.error('Failed to export listing draft:', error);
+      const errorMessage = error instanceof Error ? error.message : String(error);
+      setDownloadError(isZh ? `导出失败: ${errorMessage}` : `Export failed: ${errorMessage}`);
+      setTimeout(() => setDownloadError(null), 8000);
+    }
+  };
+
+  // The backend's full scan for inventory systems; it re-runs every check, so
+  // it can take as long as the scan did
+  const exportFullReport = async () => {
+    try {
+      const filePath = await save({
+        defaultPath: `QuickScan_Full_${report.id}.json`,
+        filters: [{ name: 'JSON', extensions: ['json'] }],
+      });
+      if (!filePath) return;
+
+      setIsExportingFull(true);
+      const content = await invoke<string>('export_report', { format: 'json' });
+      await writeFile(filePath, new TextEncoder().encode(content));
+      invoke('run_scan_hook', { stage: 'post_export', reportPath: filePath })
+        .catch(hookError => console.error('post_export hook failed:', hookError));
+
+      setSuccessMessage(t('report.fullReportExported'));
+      setTimeout(() => setSuccessMessage(null), 3000);
+    } catch (error: unknown) {
+      console.error('Failed to export full report:', error);
+      const errorMessage = describeError(error, t);
+      setDownloadError(isZh ? `导出失败: ${errorMessage}` : `Export failed: ${errorMessage}`);
+      setTimeout(() => setDownloadError(null), 8000);
+    } finally {
+      setIsExportingFull(false);
+    }
+  };
+
+  // A printable certificate rendered by the backend from a fresh full scan,
+  // headed with the shop's branding from preferences.json
+  const exportCertificate = async () => {
+    try {
+      const filePath = await save({
+        defaultPath: `QuickScan_Certificate_${report.id}.pdf`,
+        filters: [{ name: 'PDF', extensions: ['pdf'] }],
+      });
+      if (!filePath) return;
+
+      setIsExportingCertificate(true);
+      await invoke('export_report_pdf', { path: filePath, options: { language: i18n.language } });
+      invoke('run_scan_hook', { stage: 'post_export', reportPath: filePath })
+        .catch(hookError => console.error('post_export hook failed:', hookError));
+
+      setSuccessMessage(t('report.certificateExported'));
+      setTimeout(() => setSuccessMessage(null), 3000);
+    } catch (error: unknown) {
+      console.error('Failed to export certificate:', error);
+      const errorMessage = describeError(error, t);
+      setDownloadError(isZh ? `导出失败: ${errorMessage}` : `Export failed: ${errorMessage}`);
+      setTimeout(() => setDownloadError(null), 8000);
+    } finally {
+      setIsExportingCertificate(false);
+    }
+  };
+
+  const addAnnotation = () => {
+    const text = annotationDraft.trim();
+    if (!text) return;
+    setAnnotations(prev => [...prev, { at: new Date().toISOString(), text }]);
+    setAnnotationDraft('');
+  };
+
+  // Seals the report with its annotations as an immutable version; the hash
+  // goes into the audit log and into every export made while it's sealed
+  const sealReport = async () => {
+    try {
+      const sealed = await invoke<SealData>('seal_report', { report: { ...report, annotations } });
+      setSeal({
+        reportId: sealed.report_id,
+        version: sealed.version,
+        sealedAt: new Date(sealed.sealed_at * 1000).toISOString(),
+        supersedes: sealed.supersedes,
+        hash: sealed.hash,
+      });
+      setIsSealed(true);
+      setSuccessMessage(t('report.seal.sealed', { version: sealed.version }));
+      setTimeout(() => setSuccessMessage(null), 3000);
+    } catch (error: unknown) {
+      console.error('Failed to seal report:', error);
+      setDownloadError(t('report.seal.failed', { error: describeError(error, t) }));
+      setTimeout(() => setDownloadError(null), 8000);
+    }
+  };
+
   const downloadPDF = async () => {
     if (!reportRef.current) return;
 
@@ -316,6 +852,9 @@ export function ReportPage({ report, onBack }: ReportPageProps) {
 
       const imgData = canvas.toDataURL('image/png');
       const pdf = new jsPDF('p', 'mm', 'a4');
+      if (isSealed && seal) {
+        pdf.setProperties({ subject: `QuickScan report ${seal.reportId} v${seal.version}`, keywords: `quickscan-seal:${seal.hash}` });
+      }
       const pdfWidth = pdf.internal.pageSize.getWidth();
       const pdfHeight = (canvas.height * pdfWidth) / canvas.width;
 
@@ -338,10 +877,12 @@ export function ReportPage({ report, onBack }: ReportPageProps) {
       
       // writeFile expects path as string and data as Uint8Array
       await writeFile(filePath, pdfBytes);
+      invoke('run_scan_hook', { stage: 'post_export', reportPath: filePath })
+        .catch(hookError => console.error('post_export hook failed:', hookError));
       
       // Show download success notification
-      setShowDownloadSuccess(true);
-      setTimeout(() => setShowDownloadSuccess(false), 3000);
+      setSuccessMessage(t('report.downloadComplete'));
+      setTimeout(() => setSuccessMessage(null), 3000);
     } catch (error: unknown) {
       console.error('Failed to download PDF:', error);
       const errorMessage = error instanceof Error ? error.message : String(error);
@@ -352,21 +893,18 @@ export function ReportPage({ report, onBack }: ReportPageProps) {
     }
   };
 
-  const formatDate = (isoString: string) => {
-    return new Date(isoString).toLocaleString();
+  const toggleAccessibleView = () => {
+    localStorage.setItem('accessibleReport', String(!accessibleView));
+    setAccessibleView(!accessibleView);
   };
 
-  const formatBytes = (bytes: number) => {
-    if (bytes === 0) return '0 B';
-    const k = 1024;
-    const sizes = ['B', 'KB', 'MB', 'GB', 'TB'];
-    const i = Math.floor(Math.log(bytes) / Math.log(k));
-    return parseFloat((bytes / Math.pow(k, i)).toFixed(2)) + ' ' + sizes[i];
+  const formatDate = (isoString: string) => {
+    return new Date(isoString).toLocaleString();
   };
 
   const getBatteryStatus = () => {
-    if (report.battery.health >= 80) return 'passed';
-    if (report.battery.health >= 60) return 'warning';
+    if (report.battery.health >= batteryThresholds.good_percent) return 'passed';
+    if (report.battery.health >= batteryThresholds.fair_percent) return 'warning';
     return 'failed';
   };
 
@@ -374,10 +912,17 @@ export function ReportPage({ report, onBack }: ReportPageProps) {
     return report.storage.smartStatus === 'healthy' ? 'passed' : 'warning';
   };
 
+  const getThermalStatus = () => {
+    if (report.thermal?.fans.some(fan => fan.stalled)) return 'failed';
+    const cpu = report.thermal?.sensors.find(sensor => sensor.name === 'cpu');
+    if (cpu && cpu.celsius >= CPU_HOT_CELSIUS) return 'warning';
+    return 'passed';
+  };
+
   return (
     <div className="report-page section">
       {/* Download Success Notification */}
-      {showDownloadSuccess && (
+      {successMessage && (
         <div style={{
           position: 'fixed',
           top: '20px',
@@ -395,7 +940,7 @@ export function ReportPage({ report, onBack }: ReportPageProps) {
           animation: 'fadeIn 0.3s ease-out',
         }}>
           <CheckCircle size={20} />
-          {t('report.downloadComplete')}
+          {successMessage}
         </div>
       )}
       {/* Download Error Notification */}
@@ -428,12 +973,47 @@ export function ReportPage({ report, onBack }: ReportPageProps) {
             </button>
             <h1 className="section-title" style={{ margin: 0 }}>{t('report.title')}</h1>
           </div>
-          <button className="btn btn-primary" onClick={downloadPDF} disabled={isDownloading}>
-            <Download size={20} />
-            {isDownloading ? (isZh ? '下载中...' : 'Downloading...') : t('report.downloadPdf')}
-          </button>
+          <div style={{ display: 'flex', gap: '12px' }}>
+            <button className="btn btn-secondary" onClick={toggleAccessibleView} aria-pressed={accessibleView}>
+              <Eye size={20} />
+              {t('a11y.toggle')}
+            </button>
+            {isSealed ? (
+              <button className="btn btn-secondary" onClick={() => setIsSealed(false)}>
+                <PenLine size={20} />
+                {t('report.seal.supersede')}
+              </button>
+            ) : (
+              <button className="btn btn-secondary" onClick={sealReport}>
+                <Lock size={20} />
+                {t('report.seal.seal')}
+              </button>
+            )}
+            <button className="btn btn-secondary" onClick={exportListingDraft}>
+              <FileText size={20} />
+              {t('report.exportListing')}
+            </button>
+            <button className="btn btn-secondary" onClick={exportFullReport} disabled={isExportingFull}>
+              <FileText size={20} />
+              {isExportingFull ? t('report.exportingFull') : t('report.exportFull')}
+            </button>
+            <button className="btn btn-secondary" onClick={exportCertificate} disabled={isExportingCertificate}>
+              <FileText size={20} />
+              {isExportingCertificate ? t('report.exportingCertificate') : t('report.exportCertificate')}
+            </button>
+            <button className="btn btn-primary" onClick={downloadPDF} disabled={isDownloading}>
+              <Download size={20} />
+              {isDownloading ? (isZh ? '下载中...' : 'Downloading...') : t('report.downloadPdf')}
+            </button>
+          </div>
         </div>
 
+        {/* The PDF is taken from whichever view is showing */}
+        {accessibleView ? (
+          <div ref={reportRef} className="high-contrast">
+            <AccessibleSummary report={report} />
+          </div>
+        ) : (
         <div ref={reportRef} style={{ backgroundColor: 'var(--color-surface)', padding: '32px', borderRadius: '12px' }}>
           {/* Header */}
           <div style={{ display: 'flex', alignItems: 'center', justifyContent: 'space-between', borderBottom: '1px solid var(--color-border)', paddingBottom: '24px', marginBottom: '24px' }}>
@@ -454,7 +1034,7 @@ export function ReportPage({ report, onBack }: ReportPageProps) {
               <h3 style={{ marginBottom: '16px' }}>{t('report.deviceOverview')}</h3>
               <div style={{ display: 'flex', flexDirection: 'column', gap: '8px' }}>
                 <div style={{ display: 'flex', justifyContent: 'space-between' }}>
-                  <span style={{ color: 'var(--color-text-secondary)' }}>{t('hardware.cpu')}</span>
+                  <span style={{ color: 'var(--color-text-secondary)' }}>{report.deviceModel?.marketingName ? t('hardware.model') : t('hardware.cpu')}</span>
                   <span>{report.deviceOverview.model}</span>
                 </div>
                 <div style={{ display: 'flex', justifyContent: 'space-between' }}>
@@ -487,6 +1067,55 @@ export function ReportPage({ report, onBack }: ReportPageProps) {
             </div>
           </div>
 
+          {/* Device grade */}
+          {grade && (
+            <div className="card" style={{ boxShadow: 'none', border: '1px solid var(--color-border)', marginBottom: '32px' }}>
+              <div style={{ display: 'flex', alignItems: 'center', gap: '16px', marginBottom: '16px' }}>
+                <span style={{ fontSize: '40px', fontWeight: 700, lineHeight: 1 }}>{grade.grade}</span>
+                <div>
+                  <h3 style={{ margin: 0 }}>{t('grade.title')}</h3>
+                  <span style={{ color: 'var(--color-text-secondary)' }}>{t('grade.score', { score: grade.score })}</span>
+                </div>
+              </div>
+              <div style={{ display: 'grid', gridTemplateColumns: `repeat(${grade.categories.length}, 1fr)`, gap: '12px', marginBottom: grade.rules.length > 0 ? '16px' : 0 }}>
+                {grade.categories.map(category => (
+                  <div key={category.category} style={{ display: 'flex', flexDirection: 'column', gap: '4px' }}>
+                    <span style={{ color: 'var(--color-text-secondary)', fontSize: '13px' }}>{t(`grade.categories.${category.category}`)}</span>
+                    <span style={{ fontWeight: 600 }}>{category.score}/100</span>
+                    <span style={{ color: 'var(--color-text-secondary)', fontSize: '12px' }}>{t('grade.weight', { weight: category.weight })}</span>
+                  </div>
+                ))}
+              </div>
+              {grade.rules.length > 0 && (
+                <ul style={{ margin: 0, paddingLeft: '20px', fontSize: '14px' }}>
+                  {grade.rules.map((rule, idx) => (
+                    <li key={idx}>
+                      {[
+                        t(`grade.rules.${rule.id}`),
+                        rule.detail && (rule.id.endsWith('_indicator') ? translateIndicatorDesc(rule.detail) : rule.detail),
+                        rule.points > 0 && t('grade.points', { points: rule.points }),
+                        rule.cap && t('grade.cap', { grade: rule.cap }),
+                      ].filter(Boolean).join(' · ')}
+                    </li>
+                  ))}
+                </ul>
+              )}
+            </div>
+          )}
+
+          {/* Plain-language summary */}
+          <div className="card" style={{ boxShadow: 'none', border: '1px solid var(--color-border)', marginBottom: '32px' }}>
+            <h3 style={{ marginBottom: '16px' }}>{t('report.summary')}</h3>
+            {(report.narrativeLocale && report.narrative ? report.narrative : buildNarrative(report, t)).map((paragraph, idx) => (
+              <p key={idx} style={{ margin: '0 0 8px 0', lineHeight: 1.6 }}>{paragraph}</p>
+            ))}
+            {report.narrativeLocale && (
+              <p style={{ margin: 0, fontSize: '12px', color: 'var(--color-text-secondary)' }}>
+                {t('report.narrativeTranslation', { locale: report.narrativeLocale })}
+              </p>
+            )}
+          </div>
+
           {/* Issues Section - Only show if there are issues */}
           {issues.length > 0 && (
             <>
@@ -575,8 +1204,111 @@ export function ReportPage({ report, onBack }: ReportPageProps) {
                 <span className="status-badge status-passed" style={{ marginLeft: 'auto' }}>✓ {t('detection.status.passed')}</span>
               </div>
               <div style={{ fontSize: '14px', display: 'flex', flexDirection: 'column', gap: '4px' }}>
+                {report.deviceModel && (
+                  <p>{t('hardware.model')}: {report.deviceModel.marketingName ?? report.deviceModel.identifier}
+                    {report.deviceModel.marketingName && report.deviceModel.marketingName !== report.deviceModel.identifier && ` (${report.deviceModel.identifier})`}</p>
+                )}
                 <p>{t('hardware.cpu')}: {report.hardware.cpu.model} ({report.hardware.cpu.cores} cores)</p>
-                <p>{t('hardware.memory')}: {formatBytes(report.hardware.memory.total)}</p>
+                <p>{t('hardware.memory')}: {units.bytes(report.hardware.memory.total)}</p>
+                {report.memoryModules && report.memoryModules.modules.length > 0 && (() => {
+                  const memory = report.memoryModules;
+                  const kinds = [...new Set(memory.modules.map(module => module.kind).filter(Boolean))].join('/');
+                  const makers = new Set(memory.modules.map(module => module.manufacturer).filter(Boolean));
+                  return (
+                    <>
+                      <p style={{ fontSize: '12px', color: 'var(--color-text-secondary)' }}>
+                        {[
+                          memory.modules.map(module => units.bytes(module.sizeBytes)).join(' + '),
+                          kinds,
+                          memory.modules[0].speedMts !== undefined && `${units.number(memory.modules[0].speedMts)} MT/s`,
+                          memory.slotsTotal !== undefined && t('memory.slots', { used: memory.slotsUsed, total: memory.slotsTotal }),
+                          memory.soldered && t('memory.soldered'),
+                          memory.upgradeable !== undefined && t(memory.upgradeable ? 'memory.upgradeable' : 'memory.notUpgradeable'),
+                          memory.ecc && 'ECC',
+                        ].filter(Boolean).join(' · ')}
+                      </p>
+                      {makers.size > 1 && (
+                        <p style={{ fontSize: '12px', color: 'var(--color-warning)' }}>
+                          {t('memory.mixed')}: {memory.modules.map(module => [module.manufacturer, module.partNumber].filter(Boolean).join(' ')).join(', ')}
+                        </p>
+                      )}
+                    </>
+                  );
+                })()}
+                {report.cpuStress && (
+                  <p>
+                    {t('stress.title')}: {t('stress.score', { score: report.cpuStress.score })}
+                    {report.cpuStress.peakMhz !== undefined && report.cpuStress.sustainedMhz !== undefined && ` · ${units.number(report.cpuStress.sustainedMhz)}/${units.number(report.cpuStress.peakMhz)} MHz`}
+                    {report.cpuStress.peakTemperature !== undefined && ` · ${units.temperature(report.cpuStress.peakTemperature)}`}
+                    {report.cpuStress.throttleOnsetSecs !== undefined && ` · ${t('stress.throttledAt', { seconds: Math.round(report.cpuStress.throttleOnsetSecs) })}`}
+                  </p>
+                )}
+                {report.memoryTest && (
+                  <p style={{ color: report.memoryTest.faultCount > 0 ? 'var(--color-danger)' : undefined }}>
+                    {t('memory.title')}: {report.memoryTest.faultCount > 0 ? t('memory.faults', { count: report.memoryTest.faultCount }) : t('memory.noFaults')}
+                    {` · ${units.bytes(report.memoryTest.sizeMb * 1024 * 1024)} × ${report.memoryTest.passes}`}
+                  </p>
+                )}
+                {report.crashHistory && (
+                  <p style={{ color: report.crashHistory.repeated ? 'var(--color-warning)' : undefined }}>
+                    {t('crashes.title')}: {[
+                      report.crashHistory.total > 0
+                        ? t('crashes.summary', { total: report.crashHistory.total, months: report.crashHistory.monthsCovered })
+                        : t('crashes.none', { months: report.crashHistory.monthsCovered }),
+                      report.crashHistory.topComponent && t('crashes.mostly', { component: t(`crashes.components.${report.crashHistory.topComponent}`) }),
+                      report.crashHistory.crashes[0] && t('crashes.latest', { date: report.crashHistory.crashes[0].at.slice(0, 10) }),
+                    ].filter(Boolean).join(' · ')}
+                  </p>
+                )}
+                {report.rtcBattery && (
+                  <p style={{ color: report.rtcBattery.likelyFlat ? 'var(--color-warning)' : undefined }}>
+                    {t('rtc.title')}: {report.rtcBattery.resets.length > 0
+                      ? [
+                          t('rtc.resets', { count: report.rtcBattery.resets.length }),
+                          t('rtc.latest', { date: report.rtcBattery.resets[0].at.slice(0, 10), read: report.rtcBattery.resets[0].clockRead.slice(0, 10) }),
+                          report.rtcBattery.likelyFlat && t('rtc.likelyFlat'),
+                        ].filter(Boolean).join(' · ')
+                      : t('rtc.none')}
+                  </p>
+                )}
+                {report.board && (report.board.model || report.board.firmwareVersion) && (
+                  <p style={{ fontSize: '12px', color: 'var(--color-text-secondary)' }}>
+                    {t('hardware.board')}: {[
+                      [report.board.manufacturer, report.board.model, report.board.version].filter(Boolean).join(' '),
+                      report.board.firmwareVersion && `${t('hardware.firmware')} ${report.board.firmwareVersion}${report.board.firmwareDate ? ` (${report.board.firmwareDate})` : ''}`,
+                    ].filter(Boolean).join(' · ')}
+                  </p>
+                )}
+                {report.inputDevices?.physicalLayout && (
+                  <p style={{ fontSize: '12px', color: 'var(--color-text-secondary)' }}>
+                    {t('hardware.keyboardLayout')}: {t(`hardware.physicalLayouts.${report.inputDevices.physicalLayout}`)}
+                  </p>
+                )}
+                {report.region && (report.region.displayLanguage || report.region.region) && (
+                  <p style={{ fontSize: '12px', color: 'var(--color-text-secondary)' }}>
+                    {t('hardware.region')}: {[
+                      report.region.displayLanguage,
+                      report.region.region,
+                      report.region.keyboardLayouts.length > 0 && `${t('hardware.keyboardLayouts')} ${report.region.keyboardLayouts.join(', ')}`,
+                      report.claimedRegion && t('hardware.claimedRegion', { region: report.claimedRegion }),
+                    ].filter(Boolean).join(' · ')}
+                  </p>
+                )}
+                {report.deviceModel && report.deviceModel.specOptions.length > 0 && (
+                  <p style={{ color: 'var(--color-text-secondary)' }}>{t('hardware.specOptions')}: {report.deviceModel.specOptions.join(', ')}</p>
+                )}
+                {report.componentSerials && (
+                  <div style={{ marginTop: '8px', fontSize: '12px', color: 'var(--color-text-secondary)' }}>
+                    <p style={{ fontWeight: 600 }}>{t('serials.title')}</p>
+                    {report.componentSerials.logicBoard && <p>{t('serials.logicBoard')}: {report.componentSerials.logicBoard}</p>}
+                    {report.componentSerials.battery && <p>{t('serials.battery')}: {report.componentSerials.battery}</p>}
+                    {report.componentSerials.storage.map(disk => (
+                      <p key={disk.serial}>{t('serials.storage')}: {disk.serial}{disk.model && ` (${disk.model})`}</p>
+                    ))}
+                    {report.componentSerials.display && <p>{t('serials.display')}: {report.componentSerials.display}</p>}
+                    {report.componentSerials.wifiMac && <p>{t('serials.wifiMac')}: {report.componentSerials.wifiMac}</p>}
+                  </div>
+                )}
               </div>
             </div>
 
@@ -591,8 +1323,34 @@ export function ReportPage({ report, onBack }: ReportPageProps) {
               </div>
               <div style={{ fontSize: '14px', display: 'flex', flexDirection: 'column', gap: '4px' }}>
                 <p>{t('battery.health')}: {Math.round(report.battery.health)}%</p>
-                <p>{t('battery.cycleCount')}: {report.battery.cycleCount}</p>
+                <p>{t('battery.cycleCount')}: {units.number(report.battery.cycleCount)}</p>
+                {(report.battery.manufacturer || report.battery.serial) && (
+                  <p style={{ fontSize: '12px', color: 'var(--color-text-secondary)' }}>
+                    {[
+                      [report.battery.manufacturer, report.battery.deviceName].filter(Boolean).join(' '),
+                      report.battery.serial && `${t('hardware.serialNumber')} ${report.battery.serial}`,
+                      report.battery.firmwareVersion && `${t('hardware.firmware')} ${report.battery.firmwareVersion}`,
+                      report.battery.manufactureDate && t('battery.manufactured', { date: report.battery.manufactureDate }),
+                    ].filter(Boolean).join(' · ')}
+                  </p>
+                )}
+                {report.energy?.estimatedRuntimeHours !== undefined && (
+                  <p>
+                    {t('battery.energy.runtime', { hours: units.number(report.energy.estimatedRuntimeHours, 1) })}
+                    {report.energy.systemPowerWatts !== undefined && ` · ${t('battery.energy.draw', { watts: units.number(report.energy.systemPowerWatts, 1) })}`}
+                  </p>
+                )}
+                {report.energy && report.energy.estimatedRuntimeHours === undefined && report.energy.fullChargeWh !== undefined && (
+                  <p style={{ fontSize: '12px', color: 'var(--color-text-secondary)' }}>{t('battery.energy.unplugHint')}</p>
+                )}
+                {report.powerEvents && report.powerEvents.sleeps > 0 && (
+                  <p>
+                    {t('battery.sleepLog.summary', { days: report.powerEvents.days, sleeps: report.powerEvents.sleeps, wakes: report.powerEvents.unexpectedWakes })}
+                    {report.powerEvents.worstDrainPercentPerHour !== undefined && ` · ${t('battery.sleepLog.drain', { rate: units.number(report.powerEvents.worstDrainPercentPerHour, 1) })}`}
+                  </p>
+                )}
                 <p style={{ fontSize: '12px', color: 'var(--color-text-secondary)' }}>{t('battery.reference.cycle')}</p>
+                <BatteryMonitor />
               </div>
             </div>
 
@@ -607,10 +1365,61 @@ export function ReportPage({ report, onBack }: ReportPageProps) {
               </div>
               <div style={{ fontSize: '14px', display: 'flex', flexDirection: 'column', gap: '4px' }}>
                 <p>{t('storage.smart')}: {report.storage.smartStatus}</p>
-                {report.storage.powerOnHours > 0 && <p>{t('storage.powerOnHours')}: {report.storage.powerOnHours} h</p>}
+                {report.storage.powerOnHours > 0 && <p>{t('storage.powerOnHours')}: {units.number(report.storage.powerOnHours)} h</p>}
+                {report.storage.powerCycles !== undefined && <p>{t('storage.powerCycles')}: {report.storage.powerCycles}</p>}
+                {report.storage.percentageUsed !== undefined && <p>{t('storage.percentageUsed')}: {report.storage.percentageUsed}%</p>}
+                {report.storage.totalBytesWritten !== undefined && <p>{t('storage.totalBytesWritten')}: {units.bytes(report.storage.totalBytesWritten)}</p>}
+                {report.storage.reallocatedSectors !== undefined && <p>{t('storage.reallocatedSectors')}: {report.storage.reallocatedSectors}</p>}
+                {report.storage.temperature !== undefined && <p>{t('storage.temperature')}: {units.temperature(report.storage.temperature)}</p>}
+                {report.diskBenchmark && (
+                  <p>
+                    {t('storage.benchmark.title')}{report.diskBenchmark.disk && ` (${report.diskBenchmark.disk})`}:{' '}
+                    {t('storage.benchmark.sequential', { read: units.number(report.diskBenchmark.sequentialReadMbps), write: units.number(report.diskBenchmark.sequentialWriteMbps) })}
+                    {' · '}
+                    {t('storage.benchmark.random', { read: units.number(report.diskBenchmark.randomReadIops), write: units.number(report.diskBenchmark.randomWriteIops) })}
+                    {!report.diskBenchmark.directIo && ` · ${t('storage.benchmark.cached')}`}
+                  </p>
+                )}
+                {report.encryption && (
+                  <p>
+                    {t('storage.encryption')}: {report.encryption.encrypted ? report.encryption.method : t('storage.notEncrypted')}
+                    {report.encryption.inProgress && ` (${t('storage.encryptionInProgress')})`}
+                    {report.encryption.recoveryKeyEscrowed !== undefined && ` · ${t('storage.recoveryKey')}: ${report.encryption.recoveryKeyEscrowed ? '✓' : '✗'}`}
+                  </p>
+                )}
+                {report.disks?.filter(disk => !disk.isBoot).map(disk => (
+                  <p key={disk.device} style={{ fontSize: '12px', color: 'var(--color-text-secondary)' }}>
+                    {disk.device} ({t(disk.isInternal ? 'display.internal' : 'display.external')}): {disk.model} · {t('storage.smart')}: {t(`storage.${disk.smartStatus}`)}
+                  </p>
+                ))}
               </div>
             </div>
 
+            {/* Thermal */}
+            {report.thermal && (report.thermal.fans.length > 0 || report.thermal.sensors.length > 0) && (
+              <div className="card" style={{ boxShadow: 'none', border: '1px solid var(--color-border)' }}>
+                <div style={{ display: 'flex', alignItems: 'center', gap: '8px', marginBottom: '16px' }}>
+                  <Thermometer size={20} />
+                  <h4 style={{ margin: 0 }}>{t('thermal.title')}</h4>
+                  <span className={`status-badge status-${getThermalStatus()}`} style={{ marginLeft: 'auto' }}>
+                    {getThermalStatus() === 'passed' ? '✓' : getThermalStatus() === 'warning' ? '⚠' : '✗'} {t(`detection.status.${getThermalStatus()}`)}
+                  </span>
+                </div>
+                <div style={{ fontSize: '14px', display: 'flex', flexDirection: 'column', gap: '4px' }}>
+                  {report.thermal.sensors.map(sensor => (
+                    <p key={sensor.name}>{t(`thermal.sensors.${sensor.name}`)}: {units.temperature(sensor.celsius)}</p>
+                  ))}
+                  {report.thermal.fans.map(fan => (
+                    <p key={fan.index}>
+                      {t('thermal.fan', { index: fan.index + 1 })}: {units.number(fan.currentRpm)} RPM
+                      {fan.minRpm !== undefined && fan.maxRpm !== undefined && ` (${units.number(fan.minRpm)}–${units.number(fan.maxRpm)})`}
+                      {fan.stalled && ` · ${t('thermal.stalled')}`}
+                    </p>
+                  ))}
+                </div>
+              </div>
+            )}
+
             {/* Network */}
             <div className="card" style={{ boxShadow: 'none', border: '1px solid var(--color-border)' }}>
               <div style={{ display: 'flex', alignItems: 'center', gap: '8px', marginBottom: '16px' }}>
@@ -619,10 +1428,144 @@ export function ReportPage({ report, onBack }: ReportPageProps) {
                 <span className="status-badge status-passed" style={{ marginLeft: 'auto' }}>✓ {t('detection.status.passed')}</span>
               </div>
               <div style={{ fontSize: '14px', display: 'flex', flexDirection: 'column', gap: '4px' }}>
-                <p>WiFi: {report.network.wifi.available ? '✓' : '✗'}</p>
-                <p>Bluetooth: {report.network.bluetooth.available ? '✓' : '✗'}</p>
+                <p>WiFi: {report.network.wifi.available ? '✓' : '✗'}{report.network.wifi.generation && ` · ${report.network.wifi.generation}`}</p>
+                {report.network.wifi.adapter && (
+                  <p style={{ fontSize: '12px', color: 'var(--color-text-secondary)' }}>{report.network.wifi.adapter}</p>
+                )}
+                {report.network.wifi.connection && (() => {
+                  const link = report.network.wifi.connection;
+                  return (
+                    <p style={{ fontSize: '12px', color: 'var(--color-text-secondary)' }}>
+                      {[
+                        link.ssid,
+                        link.band && link.channel !== undefined ? `${link.band} ${t('network.channel', { channel: link.channel })}` : link.band,
+                        link.rssiDbm !== undefined && `${link.rssiDbm} dBm${link.noiseDbm !== undefined ? ` / ${link.noiseDbm} dBm` : ''}`,
+                        link.txRateMbps !== undefined && `${units.number(link.txRateMbps, 0)} Mb/s`,
+                      ].filter(Boolean).join(' · ')}
+                    </p>
+                  );
+                })()}
+                <p>
+                  Bluetooth: {report.network.bluetooth.available ? '✓' : '✗'}
+                  {report.network.bluetooth.version && ` · ${t('network.bluetooth.version', { version: report.network.bluetooth.version })}`}
+                  {report.network.bluetooth.available && !report.network.bluetooth.enabled && ` · ${t('network.bluetooth.off')}`}
+                </p>
+                {report.network.bluetooth.controller && (
+                  <p style={{ fontSize: '12px', color: 'var(--color-text-secondary)' }}>
+                    {[report.network.bluetooth.vendor, report.network.bluetooth.controller, report.network.bluetooth.firmware && `${t('network.bluetooth.firmware')} ${report.network.bluetooth.firmware}`].filter(Boolean).join(' · ')}
+                  </p>
+                )}
+                {report.network.bluetooth.pairedDevices && report.network.bluetooth.pairedDevices.length > 0 && (
+                  <p style={{ fontSize: '12px', color: 'var(--color-text-secondary)' }}>
+                    {t('network.bluetooth.paired', { count: report.network.bluetooth.pairedDevices.length })}:{' '}
+                    {report.network.bluetooth.pairedDevices.map(device => `${device.name}${device.kind ? ` (${device.kind})` : ''}${device.connected ? ' ✓' : ''}`).join(', ')}
+                  </p>
+                )}
+                {report.network.speedTest && (
+                  <p>
+                    {t('network.speed.internet')}:{' '}
+                    {[
+                      report.network.speedTest.downloadMbps !== undefined && `↓ ${units.number(report.network.speedTest.downloadMbps, 0)} Mb/s`,
+                      report.network.speedTest.uploadMbps !== undefined && `↑ ${units.number(report.network.speedTest.uploadMbps, 0)} Mb/s`,
+                      report.network.speedTest.latencyMs !== undefined && `${t('network.speed.latency')} ${units.number(report.network.speedTest.latencyMs, 0)} ms`,
+                      report.network.speedTest.jitterMs !== undefined && `${t('network.speed.jitter')} ${units.number(report.network.speedTest.jitterMs, 1)} ms`,
+                    ].filter(Boolean).join(' · ')}
+                    {report.network.speedTest.server && (
+                      <span style={{ fontSize: '12px', color: 'var(--color-text-secondary)' }}> ({report.network.speedTest.server})</span>
+                    )}
+                  </p>
+                )}
+                {report.network.adapters?.filter(adapter => adapter.kind !== 'virtual' && adapter.kind !== 'loopback').map(adapter => (
+                  <div key={adapter.name} style={{ marginTop: '4px' }}>
+                    <p>
+                      {t(`network.adapters.kind.${adapter.kind}`)}: {adapter.name}
+                      {' · '}{adapter.up ? t('network.adapters.up') : t('network.adapters.down')}
+                      {adapter.speedMbps !== undefined && ` · ${units.number(adapter.speedMbps)} Mb/s`}
+                    </p>
+                    <p style={{ fontSize: '12px', color: 'var(--color-text-secondary)' }}>
+                      {[adapter.description, adapter.mac && `MAC ${adapter.mac}`, adapter.driver && `${t('network.adapters.driver')} ${adapter.driver}`].filter(Boolean).join(' · ')}
+                    </p>
+                  </div>
+                ))}
               </div>
             </div>
+
+            {/* Displays */}
+            {report.displays && report.displays.length > 0 && (
+              <div className="card" style={{ boxShadow: 'none', border: '1px solid var(--color-border)' }}>
+                <div style={{ display: 'flex', alignItems: 'center', gap: '8px', marginBottom: '16px' }}>
+                  <Monitor size={20} />
+                  <h4 style={{ margin: 0 }}>{t('display.title')}</h4>
+                </div>
+                <div style={{ fontSize: '14px', display: 'flex', flexDirection: 'column', gap: '8px' }}>
+                  {report.displays.map((panel, index) => (
+                    <div key={index}>
+                      <p style={{ fontWeight: 500 }}>
+                        {panel.name || panel.vendor} ({t(panel.isInternal ? 'display.internal' : 'display.external')})
+                      </p>
+                      <p>{t('display.vendor')}: {panel.vendor || '-'} · {t('display.connection')}: {panel.connectionType}</p>
+                      {panel.nativeResolution && (
+                        <p>
+                          {t('display.nativeResolution')}: {panel.nativeResolution}
+                          {panel.refreshRateHz ? ` @ ${Math.round(panel.refreshRateHz)} Hz` : ''}
+                          {panel.colorDepth ? ` · ${panel.colorDepth}-bit` : ''}
+                        </p>
+                      )}
+                    </div>
+                  ))}
+                  {report.panelAging && report.panelAging.technology !== 'lcd' && (
+                    <div>
+                      <p>
+                        {t('display.panelCondition')}: {report.panelAging.rating
+                          ? `${t(`display.panelRatings.${report.panelAging.rating}`)} (${report.panelAging.score}/100)`
+                          : t('display.panelNotScored')}
+                      </p>
+                      <p style={{ fontSize: '12px', color: 'var(--color-text-secondary)' }}>
+                        {[
+                          t(`display.technologies.${report.panelAging.technology}`),
+                          report.panelAging.panelModel,
+                          report.panelAging.onHours !== undefined && t('display.onHours', { hours: report.panelAging.onHours }),
+                          report.panelAging.measuredBrightnessNits !== undefined && t('display.measuredBrightness', {
+                            measured: Math.round(report.panelAging.measuredBrightnessNits),
+                            rated: report.panelAging.ratedBrightnessNits ?? '-',
+                          }),
+                          report.panelAging.firmwareVersion && `${t('display.firmware')} ${report.panelAging.firmwareVersion}`,
+                        ].filter(Boolean).join(' · ')}
+                      </p>
+                    </div>
+                  )}
+                  {report.displayMeasurement && (
+                    <p>
+                      {t('display.displayMeasurement')}: {[
+                        report.displayMeasurement.contrastRatio !== undefined && t('display.contrastRatio', { ratio: Math.round(report.displayMeasurement.contrastRatio) }),
+                        t('display.blackLevel', { nits: report.displayMeasurement.blackLevelNits.toFixed(2) }),
+                        report.displayMeasurement.whitePointCct !== undefined && t('display.whitePoint', { cct: Math.round(report.displayMeasurement.whitePointCct) }),
+                        report.displayMeasurement.srgbCoverage !== undefined && t('display.gamutCoverage', { gamut: 'sRGB', percent: Math.round(report.displayMeasurement.srgbCoverage) }),
+                        report.displayMeasurement.dciP3Coverage !== undefined && t('display.gamutCoverage', { gamut: 'DCI-P3', percent: Math.round(report.displayMeasurement.dciP3Coverage) }),
+                      ].filter(Boolean).join(' · ')}
+                    </p>
+                  )}
+                </div>
+              </div>
+            )}
+
+            {/* System tools killed by the watchdog */}
+            {report.watchdogEvents && report.watchdogEvents.length > 0 && (
+              <div className="card" style={{ boxShadow: 'none', border: '1px solid var(--color-border)' }}>
+                <div style={{ display: 'flex', alignItems: 'center', gap: '8px', marginBottom: '16px' }}>
+                  <AlertCircle size={20} />
+                  <h4 style={{ margin: 0 }}>{t('watchdog.title')}</h4>
+                </div>
+                <div style={{ fontSize: '14px', display: 'flex', flexDirection: 'column', gap: '4px' }}>
+                  {report.watchdogEvents.map((event, index) => (
+                    <p key={index}>
+                      {t('watchdog.event', { program: event.program, resource: t(`watchdog.resources.${event.resource}`) })}
+                      {' '}({event.resource === 'memory' ? `${units.bytes(event.observed)} / ${units.bytes(event.limit)}` : `${event.observed}% / ${event.limit}%`})
+                    </p>
+                  ))}
+                </div>
+              </div>
+            )}
           </div>
 
           {/* Refurbishment Check Results */}
@@ -643,6 +1586,9 @@ export function ReportPage({ report, onBack }: ReportPageProps) {
                   {report.refurbishment.isRefurbished && (
                     <div style={{ marginBottom: '12px', padding: '8px 12px', backgroundColor: '#FFFBEB', borderRadius: '6px' }}>
                       <span style={{ color: '#92400E' }}>{t('refurbishment.confidence.' + report.refurbishment.confidence)}</span>
+                      {report.refurbishment.confidenceScore !== undefined && (
+                        <span style={{ color: '#92400E', marginLeft: '8px' }}>({t('refurbishment.confidenceScore', { score: report.refurbishment.confidenceScore })})</span>
+                      )}
                     </div>
                   )}
                   
@@ -653,6 +1599,139 @@ export function ReportPage({ report, onBack }: ReportPageProps) {
                     </div>
                   )}
                   
+                  {/* Firmware security */}
+                  {report.security && (
+                    <div style={{ marginBottom: '12px' }}>
+                      <strong>{t('refurbishment.security.title')}:</strong>{' '}
+                      {[
+                        report.security.sipEnabled !== undefined && `SIP ${report.security.sipEnabled ? '✓' : '✗'}`,
+                        report.security.secureBootPolicy
+                          ? `${t('refurbishment.security.secureBoot')}: ${t(`refurbishment.security.policy.${report.security.secureBootPolicy}`)}`
+                          : report.security.secureBootEnabled !== undefined && `${t('refurbishment.security.secureBoot')} ${report.security.secureBootEnabled ? '✓' : '✗'}`,
+                        report.security.tpmPresent !== undefined && (report.security.tpmPresent ? `TPM ${report.security.tpmVersion ?? ''}`.trim() : `TPM ✗`),
+                        report.security.securityChip,
+                      ].filter(Boolean).join(' · ')}
+                    </div>
+                  )}
+                  
+                  {/* OS install history */}
+                  {report.osInstall && (report.osInstall.originalInstall || report.osInstall.lastCleanInstall) && (
+                    <div style={{ marginBottom: '12px' }}>
+                      <strong>{t('refurbishment.osInstall.title')}:</strong>{' '}
+                      {[
+                        report.osInstall.originalInstall && t('refurbishment.osInstall.original', { date: report.osInstall.originalInstall }),
+                        report.osInstall.lastCleanInstall && report.osInstall.lastCleanInstall !== report.osInstall.originalInstall
+                          && t('refurbishment.osInstall.lastClean', { date: report.osInstall.lastCleanInstall }),
+                        t('refurbishment.osInstall.reinstalls', { count: report.osInstall.reinstallCount }),
+                        report.osInstall.upgradeCount > 0 && t('refurbishment.osInstall.upgrades', { count: report.osInstall.upgradeCount }),
+                        report.osInstall.previousInstallKept && t('refurbishment.osInstall.previousKept'),
+                      ].filter(Boolean).join(' · ')}
+                    </div>
+                  )}
+
+                  {/* Usage against age */}
+                  {report.usage?.estimatedUsageHours !== undefined && (
+                    <div style={{ marginBottom: '12px' }}>
+                      <strong>{t('refurbishment.usage.title')}:</strong>{' '}
+                      {[
+                        t('refurbishment.usage.hours', { hours: units.number(report.usage.estimatedUsageHours) }),
+                        report.usage.calendarAgeDays !== undefined && report.usage.firstSeen
+                          && t('refurbishment.usage.age', { years: units.number(report.usage.calendarAgeDays / 365, 1), date: report.usage.firstSeen, source: t(`refurbishment.usage.sources.${report.usage.firstSeenSource}`) }),
+                        report.usage.hoursPerDay !== undefined && t('refurbishment.usage.perDay', { hours: units.number(report.usage.hoursPerDay, 1) }),
+                        report.usage.usage && t(`refurbishment.usage.levels.${report.usage.usage}`),
+                      ].filter(Boolean).join(' · ')}
+                      {(report.usage.powerOnHours !== undefined || report.usage.batteryCycles !== undefined) && (
+                        <p style={{ fontSize: '12px', color: 'var(--color-text-secondary)', margin: '4px 0 0' }}>
+                          {[
+                            report.usage.powerOnHours !== undefined && t('refurbishment.usage.powerOn', { hours: units.number(report.usage.powerOnHours) }),
+                            report.usage.batteryCycles !== undefined && t('refurbishment.usage.cycles', { cycles: report.usage.batteryCycles }),
+                          ].filter(Boolean).join(' · ')}
+                        </p>
+                      )}
+                    </div>
+                  )}
+
+                  {/* Personal accounts still signed in */}
+                  {report.signedInAccounts && (
+                    <div style={{ marginBottom: '12px' }}>
+                      <strong>{t('refurbishment.accounts.title')}:</strong>{' '}
+                      {report.signedInAccounts.signedIn ? (
+                        <ul style={{ margin: '8px 0 0 20px', padding: 0 }}>
+                          {report.signedInAccounts.accounts.map(account => (
+                            <li key={`${account.service}:${account.account}`} style={{ color: 'var(--color-danger)' }}>
+                              {t(`refurbishment.accounts.service.${account.service}`)}: {account.account}
+                              {account.services.length > 0 && ` · ${account.services.map(service => t(`refurbishment.accounts.services.${service}`, { defaultValue: service })).join(', ')}`}
+                            </li>
+                          ))}
+                        </ul>
+                      ) : (
+                        <span style={{ color: 'var(--color-success)' }}>{t('refurbishment.accounts.none')}</span>
+                      )}
+                    </div>
+                  )}
+
+                  {/* Previous owner data */}
+                  {report.dataRemnants && (
+                    <div style={{ marginBottom: '12px' }}>
+                      <strong>{t('refurbishment.remnants.title')}:</strong>{' '}
+                      {report.dataRemnants.wiped ? (
+                        <span style={{ color: 'var(--color-success)' }}>{t('refurbishment.remnants.wiped')}</span>
+                      ) : (
+                        <ul style={{ margin: '8px 0 0 20px', padding: 0 }}>
+                          {report.dataRemnants.otherUsers.length > 0 && (
+                            <li>{t('refurbishment.remnants.users')}: {report.dataRemnants.otherUsers.join(', ')}</li>
+                          )}
+                          {report.dataRemnants.accounts.map(account => (
+                            <li key={account.account}>{t(`refurbishment.remnants.service.${account.service}`)}: {account.account}</li>
+                          ))}
+                          {report.dataRemnants.directoryJoin && (
+                            <li>{t(`refurbishment.remnants.directory.${report.dataRemnants.directoryJoin.kind}`)}: {report.dataRemnants.directoryJoin.name}</li>
+                          )}
+                          {report.dataRemnants.savedNetworks.length > 0 && (
+                            <li>{t('refurbishment.remnants.networks')}: {report.dataRemnants.savedNetworks.join(', ')}</li>
+                          )}
+                          {report.dataRemnants.browserProfiles.length > 0 && (
+                            <li>
+                              {t('refurbishment.remnants.browsers')}:{' '}
+                              {report.dataRemnants.browserProfiles.map(p => `${p.browser} (${p.user}, ${p.profile})`).join(', ')}
+                            </li>
+                          )}
+                        </ul>
+                      )}
+                    </div>
+                  )}
+
+                  {/* Windows license */}
+                  {report.windowsActivation && (
+                    <div style={{ marginBottom: '12px' }}>
+                      <strong>{t('refurbishment.license.title')}:</strong>{' '}
+                      {[
+                        t(`refurbishment.license.status.${report.windowsActivation.status}`),
+                        report.windowsActivation.channel && t(`refurbishment.license.channel.${report.windowsActivation.channel}`),
+                        report.windowsActivation.partialProductKey && `*****-${report.windowsActivation.partialProductKey}`,
+                        report.windowsActivation.tiedToMotherboard && t('refurbishment.license.tiedToMotherboard'),
+                      ].filter(Boolean).join(' · ')}
+                      {report.windowsActivation.edition && (
+                        <div style={{ color: 'var(--color-text-secondary)' }}>{report.windowsActivation.edition}</div>
+                      )}
+                    </div>
+                  )}
+                  
+                  {/* Device management */}
+                  {report.mdm && (report.mdm.enrolled || report.mdm.depAssigned) && (
+                    <div style={{ marginBottom: '12px' }}>
+                      <strong>{t('refurbishment.mdm.title')}:</strong>{' '}
+                      {[
+                        report.mdm.enrolled && t('refurbishment.mdm.enrolled'),
+                        report.mdm.depAssigned && t('refurbishment.mdm.depAssigned'),
+                        report.mdm.organization && t('refurbishment.mdm.organization', { name: report.mdm.organization }),
+                      ].filter(Boolean).join(' · ')}
+                      {report.mdm.serverUrl && (
+                        <div style={{ color: 'var(--color-text-secondary)', wordBreak: 'break-all' }}>{report.mdm.serverUrl}</div>
+                      )}
+                    </div>
+                  )}
+                  
                   {/* Replaced Parts */}
                   {report.refurbishment.replacedParts.length > 0 && (
                     <div style={{ marginBottom: '12px' }}>
@@ -683,7 +1762,53 @@ export function ReportPage({ report, onBack }: ReportPageProps) {
                             {indicator.severity === 'critical' ? <XCircle size={16} color="var(--color-danger)" /> : 
                              indicator.severity === 'warning' ? <AlertCircle size={16} color="var(--color-warning)" /> :
                              <Info size={16} color="var(--color-primary)" />}
-                            <span>{translateIndicatorDesc(indicator.description)}</span>
+                            <span>
+                              {translateIndicatorDesc(indicator.description)}
+                              {indicator.caveat && (
+                                <span style={{ display: 'block', fontSize: '12px', color: 'var(--color-text-secondary)' }}>
+                                  {t(`refurbishment.caveat.${indicator.caveat}`, { defaultValue: indicator.caveat })}
+                                </span>
+                              )}
+                            </span>
+                          </div>
+                        ))}
+                      </div>
+                    </div>
+                  )}
+                  
+                  {/* What does this mean - plain-language help per indicator */}
+                  {indicatorHelp.length > 0 && (
+                    <div style={{ marginBottom: '12px' }}>
+                      <strong>{t('refurbishment.whatDoesThisMean')}</strong>
+                      <div style={{ display: 'flex', flexDirection: 'column', gap: '8px', marginTop: '8px' }}>
+                        {indicatorHelp.map((help, idx) => (
+                          <div key={idx} style={{ padding: '8px 12px', backgroundColor: 'var(--color-background)', borderRadius: '6px', fontSize: '13px' }}>
+                            <p style={{ margin: '0 0 4px' }}>{t(help.explanation)}</p>
+                            <p style={{ margin: '0 0 4px', color: 'var(--color-text-secondary)' }}>
+                              <strong>{t('refurbishment.helpCauses')}:</strong> {t(help.causes)}
+                            </p>
+                            <p style={{ margin: 0, color: 'var(--color-text-secondary)' }}>
+                              <strong>{t('refurbishment.helpAction')}:</strong> {t(help.action)}
+                            </p>
+                          </div>
+                        ))}
+                      </div>
+                    </div>
+                  )}
+                  
+                  {/* Recommended remediation for critical findings */}
+                  {(report.refurbishment.recommendations ?? []).length > 0 && (
+                    <div style={{ marginBottom: '12px' }}>
+                      <strong>{t('refurbishment.recommendedActions')}</strong>
+                      <div style={{ display: 'flex', flexDirection: 'column', gap: '8px', marginTop: '8px' }}>
+                        {report.refurbishment.recommendations!.map(recommendation => (
+                          <div key={recommendation.id} style={{ padding: '8px 12px', backgroundColor: '#FEF2F2', borderRadius: '6px', fontSize: '13px' }}>
+                            <p style={{ margin: '0 0 4px', fontWeight: 'bold' }}>{t(recommendation.title)}</p>
+                            <ol style={{ margin: 0, paddingLeft: '20px' }}>
+                              {recommendation.steps.map((step, idx) => (
+                                <li key={idx}>{t(step)}</li>
+                              ))}
+                            </ol>
                           </div>
                         ))}
                       </div>
@@ -769,6 +1894,22 @@ export function ReportPage({ report, onBack }: ReportPageProps) {
                 {report.interactive.keyboard.skipped ? t('detection.status.skipped') : report.interactive.keyboard.tested ? t('detection.status.passed') : t('detection.status.pending')}
               </p>
             </div>
+            {report.interactive.touchBar && (
+              <div style={{ padding: '12px', border: '1px solid var(--color-border)', borderRadius: '8px', textAlign: 'center' }}>
+                <RectangleHorizontal size={24} style={{ marginBottom: '8px' }} />
+                <p style={{ margin: 0, fontWeight: 500 }}>{t('detection.categories.touchBar')}</p>
+                <p style={{ margin: '4px 0 0', fontSize: '14px', color: !report.interactive.touchBar.displayPresent || !report.interactive.touchBar.bridgeResponding ? 'var(--color-danger)' : report.interactive.touchBar.skipped ? '#7C3AED' : report.interactive.touchBar.missedKeys.length > 0 ? 'var(--color-warning)' : 'var(--color-success)' }}>
+                  {!report.interactive.touchBar.displayPresent || !report.interactive.touchBar.bridgeResponding ? t('touchBar.dead')
+                    : report.interactive.touchBar.skipped ? t('detection.status.skipped')
+                    : `${report.interactive.touchBar.tappedKeys.length}/${report.interactive.touchBar.tappedKeys.length + report.interactive.touchBar.missedKeys.length} ${t('keyboard.tested')}`}
+                </p>
+                {report.interactive.touchBar.bridge && (
+                  <p style={{ margin: '4px 0 0', fontSize: '12px', color: 'var(--color-text-secondary)' }}>
+                    {report.interactive.touchBar.bridge}{report.interactive.touchBar.bridgeFirmware ? ` (${report.interactive.touchBar.bridgeFirmware})` : ''}
+                  </p>
+                )}
+              </div>
+            )}
             <div style={{ padding: '12px', border: '1px solid var(--color-border)', borderRadius: '8px', textAlign: 'center' }}>
               <Mouse size={24} style={{ marginBottom: '8px' }} />
               <p style={{ margin: 0, fontWeight: 500 }}>{t('detection.categories.trackpad')}</p>
@@ -777,6 +1918,9 @@ export function ReportPage({ report, onBack }: ReportPageProps) {
                   ? t('detection.status.passed') 
                   : t('detection.status.warning')}
               </p>
+              {report.inputDevices?.devices.some(d => d.kind === 'trackpad' && d.forceTouch) && (
+                <p style={{ margin: '4px 0 0', fontSize: '12px', color: 'var(--color-text-secondary)' }}>{t('trackpad.forceTouch')}</p>
+              )}
             </div>
             <div style={{ padding: '12px', border: '1px solid var(--color-border)', borderRadius: '8px', textAlign: 'center' }}>
               <Camera size={24} style={{ marginBottom: '8px' }} />
@@ -784,6 +1928,11 @@ export function ReportPage({ report, onBack }: ReportPageProps) {
               <p style={{ margin: '4px 0 0', fontSize: '14px', color: report.interactive.camera.skipped ? '#7C3AED' : report.interactive.camera.working ? 'var(--color-success)' : 'var(--color-danger)' }}>
                 {report.interactive.camera.skipped ? t('detection.status.skipped') : report.interactive.camera.working ? t('camera.working') : t('camera.notWorking')}
               </p>
+              {report.cameras?.map((camera, i) => (
+                <p key={i} style={{ margin: '4px 0 0', fontSize: '12px', color: 'var(--color-text-secondary)' }}>
+                  {camera.name}{camera.maxResolution && ` · ${camera.maxResolution}`}
+                </p>
+              ))}
             </div>
             <div style={{ padding: '12px', border: '1px solid var(--color-border)', borderRadius: '8px', textAlign: 'center' }}>
               <Mic size={24} style={{ marginBottom: '8px' }} />
@@ -800,12 +1949,43 @@ export function ReportPage({ report, onBack }: ReportPageProps) {
                   ? t('detection.status.passed') 
                   : `${!report.interactive.speaker.leftChannel ? (isZh ? '左声道异常' : 'Left issue') : ''} ${!report.interactive.speaker.rightChannel ? (isZh ? '右声道异常' : 'Right issue') : ''}`}
               </p>
+              {report.interactive.speaker.loopback?.map(c => (
+                <p key={c.channel} style={{ margin: '4px 0 0', fontSize: '12px', color: c.detected ? 'var(--color-text-secondary)' : 'var(--color-warning)' }}>
+                  {c.channel === 'left' ? t('speaker.leftChannel') : t('speaker.rightChannel')}: {c.detected ? t('speaker.loopback.heard') : t('speaker.loopback.notHeard')} · {units.number(c.levelDbfs, 1)} dBFS
+                </p>
+              ))}
+              {report.audio?.headphoneJack && (
+                <p style={{ margin: '4px 0 0', fontSize: '12px', color: 'var(--color-text-secondary)' }}>{t('speaker.headphonesConnected')}</p>
+              )}
             </div>
+            {report.interactive.ports && (
+              <div style={{ padding: '12px', border: '1px solid var(--color-border)', borderRadius: '8px', textAlign: 'center' }}>
+                <Usb size={24} style={{ marginBottom: '8px' }} />
+                <p style={{ margin: 0, fontWeight: 500 }}>{t('detection.categories.ports')}</p>
+                <p style={{ margin: '4px 0 0', fontSize: '14px', color: report.interactive.ports.skipped ? '#7C3AED' : report.interactive.ports.deadPortReported ? 'var(--color-danger)' : 'var(--color-success)' }}>
+                  {report.interactive.ports.skipped ? t('detection.status.skipped')
+                    : report.interactive.ports.deadPortReported ? t('ports.deadPort')
+                    : t('ports.working', { count: report.interactive.ports.ports.length })}
+                </p>
+              </div>
+            )}
+            {report.interactive.sleep && (
+              <div style={{ padding: '12px', border: '1px solid var(--color-border)', borderRadius: '8px', textAlign: 'center' }}>
+                <Moon size={24} style={{ marginBottom: '8px' }} />
+                <p style={{ margin: 0, fontWeight: 500 }}>{t('detection.categories.sleep')}</p>
+                <p style={{ margin: '4px 0 0', fontSize: '14px', color: report.interactive.sleep.skipped ? '#7C3AED' : !report.interactive.sleep.slept ? 'var(--color-danger)' : report.interactive.sleep.instantWake || report.interactive.sleep.abnormalWakes.length > 0 ? 'var(--color-warning)' : 'var(--color-success)' }}>
+                  {report.interactive.sleep.skipped ? t('detection.status.skipped')
+                    : !report.interactive.sleep.slept ? t('sleep.didNotSleep')
+                    : report.interactive.sleep.instantWake || report.interactive.sleep.abnormalWakes.length > 0 ? t('detection.status.warning')
+                    : t('detection.status.passed')}
+                </p>
+              </div>
+            )}
           </div>
 
           {/* Skipped Tests Summary */}
-          {(report.interactive.screen.skipped || report.interactive.keyboard.skipped || report.interactive.trackpad.skipped || 
-            report.interactive.camera.skipped || report.interactive.microphone.skipped || report.interactive.speaker.skipped) && (
+          {(report.interactive.screen.skipped || report.interactive.keyboard.skipped || report.interactive.trackpad.skipped || report.interactive.touchBar?.skipped || 
+            report.interactive.camera.skipped || report.interactive.microphone.skipped || report.interactive.speaker.skipped || report.interactive.ports?.skipped || report.interactive.sleep?.skipped) && (
             <div style={{ 
               backgroundColor: '#F3E8FF', 
               padding: '16px', 
@@ -819,14 +1999,57 @@ export function ReportPage({ report, onBack }: ReportPageProps) {
                   report.interactive.screen.skipped && t('detection.categories.screen'),
                   report.interactive.keyboard.skipped && t('detection.categories.keyboard'),
                   report.interactive.trackpad.skipped && t('detection.categories.trackpad'),
+                  report.interactive.touchBar?.skipped && t('detection.categories.touchBar'),
                   report.interactive.camera.skipped && t('detection.categories.camera'),
                   report.interactive.microphone.skipped && t('detection.categories.microphone'),
                   report.interactive.speaker.skipped && t('detection.categories.speaker'),
+                  report.interactive.ports?.skipped && t('detection.categories.ports'),
+                  report.interactive.sleep?.skipped && t('detection.categories.sleep'),
                 ].filter(Boolean).join('、')}
               </p>
             </div>
           )}
 
+          {/* Annotations; the editing controls stay out of the PDF */}
+          {(annotations.length > 0 || !isSealed) && (
+            <div className="card" style={{ boxShadow: 'none', border: '1px solid var(--color-border)', marginBottom: '24px' }}>
+              <h3 style={{ marginBottom: '16px' }}>{t('report.annotations.title')}</h3>
+              {annotations.map((annotation, index) => (
+                <div key={annotation.at} style={{ display: 'flex', justifyContent: 'space-between', gap: '12px', marginBottom: '8px' }}>
+                  <div>
+                    <p style={{ margin: 0, whiteSpace: 'pre-wrap' }}>{annotation.text}</p>
+                    <span style={{ fontSize: '12px', color: 'var(--color-text-secondary)' }}>{formatDate(annotation.at)}</span>
+                  </div>
+                  {!isSealed && (
+                    <button
+                      className="btn btn-secondary"
+                      data-html2canvas-ignore
+                      style={{ padding: '4px 8px' }}
+                      onClick={() => setAnnotations(prev => prev.filter((_, i) => i !== index))}
+                      aria-label={t('report.annotations.remove')}
+                    >
+                      <Trash2 size={16} />
+                    </button>
+                  )}
+                </div>
+              ))}
+              {!isSealed && (
+                <div data-html2canvas-ignore style={{ display: 'flex', gap: '12px', alignItems: 'flex-start' }}>
+                  <textarea
+                    value={annotationDraft}
+                    onChange={e => setAnnotationDraft(e.target.value)}
+                    placeholder={t('report.annotations.placeholder')}
+                    rows={2}
+                    style={{ flex: 1, padding: '8px', borderRadius: '8px', border: '1px solid var(--color-border)', font: 'inherit' }}
+                  />
+                  <button className="btn btn-secondary" onClick={addAnnotation} disabled={!annotationDraft.trim()}>
+                    {t('report.annotations.add')}
+                  </button>
+                </div>
+              )}
+            </div>
+          )}
+
           {/* Disclaimer */}
           <div style={{ 
             backgroundColor: 'var(--color-background)', 
@@ -843,8 +2066,16 @@ export function ReportPage({ report, onBack }: ReportPageProps) {
           {/* Footer */}
           <div style={{ marginTop: '24px', textAlign: 'center', color: 'var(--color-text-secondary)', fontSize: '12px' }}>
             <p>{t('app.name')} v0.1.0 | QuickScan</p>
+            {seal && (
+              <p style={{ wordBreak: 'break-all' }}>
+                {isSealed
+                  ? t('report.seal.footer', { version: seal.version, date: formatDate(seal.sealedAt), hash: seal.hash })
+                  : t('report.seal.editing', { version: seal.version })}
+              </p>
+            )}
           </div>
         </div>
+        )}
       </div>
     </div>
   );
@@ -1 +1,3 @@
 export { ReportPage } from './ReportPage';
+export { BatteryMonitor } from './BatteryMonitor';
+export { AccessibleSummary } from './AccessibleSummary';
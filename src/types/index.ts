@@ -38,6 +38,11 @@ export interface BatteryInfo {
   designCapacity: number;
   currentCapacity: number;
   isCharging: boolean;
+  serial?: string;
+  manufacturer?: string; // the pack maker: "SMP", "LGC", "Celxpert"
+  deviceName?: string;
+  firmwareVersion?: string; // gas gauge firmware; macOS only
+  manufactureDate?: string; // YYYY-MM-DD
   rating: 'excellent' | 'good' | 'fair' | 'poor';
 }
 
@@ -48,6 +53,323 @@ export interface StorageInfo {
   smartStatus: 'healthy' | 'warning' | 'failing';
   powerOnHours: number;
   temperature?: number;
+  powerCycles?: number;
+  reallocatedSectors?: number;
+  percentageUsed?: number; // drive-reported wear, can exceed 100
+  totalBytesWritten?: number;
+  device?: string;
+  isInternal?: boolean;
+  isBoot?: boolean;
+}
+
+export interface DeviceModelInfo {
+  identifier: string; // e.g. MacBookPro18,3
+  manufacturer?: string;
+  marketingName?: string;
+  releaseYear?: number;
+  specOptions: string[];
+}
+
+export interface CameraInfo {
+  name: string;
+  model?: string;
+  builtIn?: boolean;
+  connection?: string; // "built-in", "usb", "thunderbolt", ...
+  maxResolution?: string;
+}
+
+export interface AudioDevice {
+  name: string;
+  builtIn?: boolean;
+  channels?: number;
+}
+
+export interface AudioInfo {
+  outputs: AudioDevice[];
+  inputs: AudioDevice[];
+  headphoneJack?: boolean; // true with headphones plugged in; undefined where the OS doesn't report it
+}
+
+export interface InputDevice {
+  name: string;
+  kind: 'keyboard' | 'trackpad' | 'mouse';
+  builtIn?: boolean;
+  connection?: string; // "spi", "i2c", "ps2", "usb", "bluetooth"
+  forceTouch?: boolean;
+  haptic?: boolean;
+}
+
+export interface InputDevicesInfo {
+  devices: InputDevice[];
+  internalKeyboard?: boolean; // undefined where built-in devices can't be told apart
+  internalTrackpad?: boolean;
+  physicalLayout?: 'ansi' | 'iso' | 'jis'; // built-in keyboard
+}
+
+// For parts traceability; a later scan of the same machine shows what was swapped
+export interface ComponentSerials {
+  logicBoard?: string;
+  battery?: string;
+  storage: { model: string; serial: string }[];
+  display?: string; // internal panel
+  wifiMac?: string;
+}
+
+export interface BoardInfo {
+  manufacturer?: string;
+  model?: string; // board name, or the board ID on Macs
+  version?: string;
+  serial?: string;
+  systemSerial?: string; // the machine's serial, as the firmware reports it
+  chassisSerial?: string;
+  nvramSerial?: string; // Intel Macs
+  firmwareVendor?: string;
+  firmwareVersion?: string; // BIOS / UEFI version, or the Mac's boot ROM
+  firmwareDate?: string; // YYYY-MM-DD
+}
+
+export interface RegionInfo {
+  displayLanguage?: string; // BCP 47, "ja-JP"
+  region?: string; // ISO 3166 code from the regional settings
+  keyboardLayouts: string[];
+  physicalLayout?: 'ansi' | 'iso' | 'jis'; // built-in keyboard
+  likelyRegion?: string; // where the evidence points
+}
+
+export interface MdmInfo {
+  enrolled: boolean;
+  depAssigned: boolean; // Apple Business Manager / Windows Autopilot
+  userApproved?: boolean;
+  organization?: string;
+  serverUrl?: string;
+}
+
+export interface EncryptionInfo {
+  encrypted: boolean;
+  method?: string; // FileVault, BitLocker or LUKS
+  inProgress: boolean;
+  recoveryKeyEscrowed?: boolean;
+}
+
+export interface SecurityInfo {
+  sipEnabled?: boolean;
+  secureBootEnabled?: boolean;
+  secureBootPolicy?: 'full' | 'reduced' | 'permissive';
+  tpmPresent?: boolean;
+  tpmVersion?: string;
+  securityChip?: string; // T2 or Apple silicon
+}
+
+export interface FanReading {
+  index: number;
+  currentRpm: number;
+  minRpm?: number;
+  maxRpm?: number;
+  stalled: boolean;
+}
+
+export interface DiskBenchmarkResult {
+  path: string;
+  disk?: string;
+  sizeMb: number;
+  directIo: boolean; // false: the filesystem refused uncached I/O, so reads may be inflated
+  sequentialWriteMbps: number;
+  sequentialReadMbps: number;
+  randomWriteIops: number;
+  randomReadIops: number;
+}
+
+export interface EnergyInfo {
+  onBattery: boolean;
+  systemPowerWatts?: number;
+  displayBrightness?: number; // 0-1
+  displayPowerWatts?: number;
+  fullChargeWh?: number;
+  estimatedRuntimeHours?: number; // at standard brightness
+}
+
+export interface PowerEventSummary {
+  days: number;
+  sleeps: number;
+  unexpectedWakes: number;
+  wakeSources: { reason: string; count: number }[]; // behind the unexpected wakes
+  failedSleeps: number;
+  drainIncidents: number;
+  worstDrainPercentPerHour?: number;
+  chronicWakes: boolean;
+  chronicDrain: boolean;
+}
+
+export interface CrashRecord {
+  at: string; // "YYYY-MM-DD HH:MM:SS", local time
+  kind: string; // "panic", "bug_check" or "hardware_error"
+  component: string; // "gpu", "kernel", "driver", "memory", "cpu", "storage", "pcie" or "unknown"
+  detail?: string; // panic string, stop code or WHEA component
+}
+
+export interface CrashHistory {
+  monthsCovered: number;
+  total: number;
+  byMonth: { month: string; count: number }[]; // oldest first
+  crashes: CrashRecord[]; // newest first
+  topComponent?: string;
+  repeated: boolean;
+}
+
+export interface RtcBatteryInfo {
+  resets: { at: string; clockRead: string; beforeFirmware: boolean }[]; // newest first
+  earliestPossible?: string; // firmware release date or model year
+  likelyFlat: boolean;
+}
+
+export interface CpuStressResult {
+  durationSecs: number;
+  threads: number;
+  peakMhz?: number;
+  sustainedMhz?: number;
+  peakTemperature?: number;
+  throttleOnsetSecs?: number;
+  score: number; // share of peak throughput held to the end, 0-100
+}
+
+export interface MemoryTestResult {
+  sizeMb: number;
+  passes: number;
+  durationSecs: number;
+  faultCount: number;
+  faults: { address: string; expected: string; actual: string; test: 'solid' | 'inversion' | 'address' }[]; // first 100
+}
+
+export interface MemoryModules {
+  totalBytes: number;
+  slotsTotal?: number;
+  slotsUsed: number;
+  soldered?: boolean;
+  upgradeable?: boolean;
+  ecc?: boolean;
+  modules: {
+    slot: string;
+    sizeBytes: number;
+    kind?: string; // "DDR4", "LPDDR5"
+    speedMts?: number;
+    manufacturer?: string;
+    partNumber?: string;
+  }[];
+}
+
+export interface ThermalInfo {
+  fans: FanReading[];
+  sensors: { name: 'cpu' | 'gpu' | 'ssd' | 'battery' | 'system'; celsius: number }[];
+}
+
+export interface WindowsActivationInfo {
+  activated: boolean;
+  status: 'licensed' | 'grace' | 'notification' | 'unlicensed';
+  edition?: string;
+  channel?: 'OEM' | 'Retail' | 'Volume' | 'Refurbisher';
+  partialProductKey?: string;
+  tiedToMotherboard?: boolean;
+}
+
+// Dates are YYYY-MM-DD
+export interface OsInstallInfo {
+  originalInstall?: string;
+  lastCleanInstall?: string;
+  lastReinstall?: string;
+  reinstallCount: number;
+  upgradeCount: number;
+  previousInstallKept?: boolean; // Windows.old
+}
+
+export interface UsageEstimateInfo {
+  firstSeen?: string; // YYYY-MM-DD
+  firstSeenSource?: string; // "serial", "battery", "os_install" or "model_year"
+  calendarAgeDays?: number;
+  powerOnHours?: number; // boot drive
+  batteryCycles?: number;
+  estimatedUsageHours?: number;
+  hoursPerDay?: number;
+  usage?: string; // "light", "typical", "heavy" or "exceeds_age"
+}
+
+export interface SignedInAccountsInfo {
+  signedIn: boolean;
+  accounts: {
+    service: string; // "apple_id", "microsoft" or "work_school"
+    account: string; // masked; a work account without an address is its organisation
+    services: string[]; // syncing with it: "find_my", "icloud_drive"…
+  }[];
+}
+
+export interface DataRemnantsInfo {
+  otherUsers: string[]; // home folders besides the tester's
+  accounts: { service: 'icloud' | 'microsoft'; account: string }[];
+  savedNetworks: string[];
+  browserProfiles: { browser: string; user: string; profile: string }[];
+  directoryJoin?: { kind: 'domain' | 'entra_id'; name: string };
+  wiped: boolean;
+}
+
+export interface GpuInfo {
+  model: string;
+  vendor: string;
+  vramMb?: number;
+  driverVersion?: string;
+  apiVersion?: string;
+  isIntegrated: boolean;
+}
+
+export interface DisplayInfo {
+  name: string;
+  vendor: string;
+  nativeResolution?: string;
+  refreshRateHz?: number;
+  colorDepth?: number; // bits per pixel
+  connectionType: string;
+  isInternal: boolean;
+}
+
+// Wear of the built-in (or only) panel, scored for OLED and mini-LED
+export interface PanelAgingInfo {
+  display: string;
+  technology: 'oled' | 'mini_led' | 'lcd';
+  panelModel?: string;
+  onHours?: number;
+  firmwareVersion?: string;
+  ratedBrightnessNits?: number; // full-screen SDR white
+  measuredBrightnessNits?: number; // colorimeter, white patch of the screen test
+  brightnessPercent?: number; // estimated brightness left, of new
+  score?: number; // panel condition, 0-100
+  rating?: 'good' | 'fair' | 'worn';
+}
+
+// What a colorimeter measured over the screen test patches
+export interface DisplayMeasurementInfo {
+  brightnessNits: number; // white patch
+  blackLevelNits: number;
+  contrastRatio?: number;
+  whitePointCct?: number; // kelvin
+  srgbCoverage?: number; // percent, with the red, green and blue patches
+  dciP3Coverage?: number;
+}
+
+// Error payload of a failed probe command
+export type QuickscanError =
+  | { kind: 'command_failed'; source: string; message: string }
+  | { kind: 'parse_error'; source: string; message: string }
+  | { kind: 'unsupported'; feature: string }
+  | { kind: 'permission_denied'; source: string; message: string }
+  | { kind: 'timeout'; source: string; seconds: number }
+  | { kind: 'resource_limit'; source: string; resource: 'memory' | 'cpu' }
+  | { kind: 'consent_required'; scope: 'camera' | 'microphone' | 'logs' };
+
+// A system tool the watchdog killed for using too much memory or CPU
+export interface WatchdogEvent {
+  program: string;
+  resource: 'memory' | 'cpu';
+  observed: number; // bytes, or percent of one core
+  limit: number;
+  elapsedMs: number;
 }
 
 export interface SystemInfo {
@@ -63,6 +385,7 @@ export interface SystemInfo {
 export interface RefurbishmentInfo {
   isRefurbished: boolean;
   confidence: 'high' | 'medium' | 'low';
+  confidenceScore?: number; // 0-100, absent in reports saved before it was scored
   indicators: RefurbishmentIndicator[];
   replacedParts: string[];
   details: {
@@ -73,6 +396,24 @@ export interface RefurbishmentInfo {
     dateMismatch: boolean;
     refurbProgram?: string;
   };
+  recommendations?: Recommendation[];
+  gradeModifiers?: GradeModifier[];
+}
+
+// A deduction or cap a custom script adds to the device grade
+export interface GradeModifier {
+  script: string;
+  category: 'battery' | 'storage' | 'cosmetic_proxy' | 'hardware_integrity';
+  points: number;
+  cap: 'A' | 'B' | 'C' | 'D' | null;
+  reason: string;
+}
+
+export interface Recommendation {
+  id: string;
+  title: string;
+  steps: string[];
+  triggers: string[];
 }
 
 export interface RefurbishmentIndicator {
@@ -80,19 +421,74 @@ export interface RefurbishmentIndicator {
   detected: boolean;
   description: string;
   severity: 'info' | 'warning' | 'critical';
+  help?: IndicatorHelp;
+  caveat?: string; // why it's less certain than usual: "clock_unreliable"
+}
+
+export interface IndicatorHelp {
+  explanation: string;
+  causes: string;
+  action: string;
+}
+
+export interface WifiConnection {
+  ssid?: string; // hidden by macOS without Location Services access
+  phyMode?: string; // "802.11ax"
+  rssiDbm?: number;
+  noiseDbm?: number;
+  channel?: number;
+  band?: '2.4GHz' | '5GHz' | '6GHz';
+  channelWidthMhz?: number;
+  txRateMbps?: number;
 }
 
 export interface NetworkInfo {
   wifi: {
     available: boolean;
     connected: boolean;
+    enabled?: boolean;
+    adapter?: string;
+    standards?: string[]; // "802.11a", ..., "802.11ax"
+    generation?: string; // "Wi-Fi 6E"
+    connection?: WifiConnection;
   };
   bluetooth: {
     available: boolean;
     enabled: boolean;
+    controller?: string; // chipset or adapter name
+    vendor?: string;
+    version?: string; // core spec, "5.3"
+    firmware?: string;
+    pairedDevices?: PairedBluetoothDevice[]; // left over from a previous owner unless the shop paired them
+  };
+  adapters?: NetworkAdapter[];
+  speedTest?: {
+    downloadMbps?: number;
+    uploadMbps?: number;
+    latencyMs?: number; // TCP handshake, median over the test hosts
+    jitterMs?: number;
+    server?: string; // host the throughput was measured against
   };
 }
 
+export interface NetworkAdapter {
+  name: string; // "en0", "eth0", "Ethernet 2"
+  description?: string;
+  kind: 'ethernet' | 'wifi' | 'wwan' | 'bluetooth' | 'virtual' | 'loopback' | 'other';
+  mac?: string;
+  up: boolean; // link detected
+  speedMbps?: number;
+  driver?: string;
+  addresses: string[];
+}
+
+export interface PairedBluetoothDevice {
+  name: string;
+  address?: string;
+  kind?: string; // as the OS describes it
+  connected: boolean;
+}
+
 export interface SensorInfo {
   ambientLight?: boolean;
   accelerometer?: boolean;
@@ -121,6 +517,18 @@ export interface InteractiveTestResult {
     dragWorking: boolean;
     gestureWorking: boolean;
   };
+  // Only on Macs that have, or should have, a Touch Bar
+  touchBar?: {
+    tested: boolean;
+    skipped?: boolean;
+    displayPresent: boolean; // the bridge exposes the Touch Bar display
+    bridge?: string; // "Apple T2 Security Chip"
+    bridgeFirmware?: string;
+    bridgeResponding: boolean;
+    serverRunning: boolean;
+    tappedKeys: string[]; // KeyboardEvent.code, "Escape", "F1"…
+    missedKeys: string[];
+  };
   camera: {
     tested: boolean;
     skipped?: boolean;
@@ -137,9 +545,68 @@ export interface InteractiveTestResult {
     skipped?: boolean;
     leftChannel: boolean;
     rightChannel: boolean;
+    // Tones played per channel and listened for on the built-in mic
+    loopback?: { channel: 'left' | 'right'; levelDbfs: number; detected: boolean; missingTones: number[] }[];
+  };
+  ports?: {
+    tested: boolean;
+    skipped?: boolean;
+    ports: { port: string; device: string; speedMbps?: number }[]; // one per port a plug-in was seen on
+    deadPortReported: boolean;
+  };
+  sleep?: {
+    tested: boolean;
+    skipped?: boolean;
+    slept: boolean;
+    secondsAsleep?: number;
+    wakeReason?: string;
+    instantWake: boolean;
+    abnormalWakes: string[];
   };
 }
 
+// One report section in plain words, for the accessible view (see summary.rs)
+export type SummaryValue =
+  | { kind: 'text'; value: string }
+  | { kind: 'count'; value: number }
+  | { kind: 'percent'; value: number }
+  | { kind: 'bytes'; value: number }
+  | { kind: 'celsius'; value: number }
+  | { kind: 'dbm'; value: number }
+  | { kind: 'ratio'; value: [number, number] }
+  | { kind: 'message'; value: string }; // translation key
+
+export interface SummaryText {
+  id: string; // translation key
+  args?: Record<string, SummaryValue>;
+}
+
+export interface SectionSummary {
+  section: string; // "battery", "display.0", "interactive.keyboard"
+  status: 'passed' | 'warning' | 'failed' | 'skipped' | 'pending' | 'info';
+  label: SummaryText;
+  value?: SummaryValue;
+  sentence: SummaryText;
+}
+
+// Letter grade of a report, from compute_device_grade
+export type GradeCategory = 'battery' | 'storage' | 'cosmetic_proxy' | 'hardware_integrity';
+
+export interface DeviceGrade {
+  grade: 'A' | 'B' | 'C' | 'D';
+  score: number; // 0-100
+  categories: { category: GradeCategory; score: number; weight: number }[]; // battery left out without one
+  rules: GradeRule[];
+}
+
+export interface GradeRule {
+  id: string; // "battery_wear", "storage_smart_failing"…
+  category: GradeCategory;
+  points: number; // taken off the category's 100
+  cap?: 'A' | 'B' | 'C' | 'D'; // the best grade the machine can still get
+  detail?: string; // "72%", or an indicator's description
+}
+
 export interface DetectionReport {
   id: string;
   generatedAt: string;
@@ -155,12 +622,61 @@ export interface DetectionReport {
     failed: number;
   };
   hardware: HardwareInfo;
+  deviceModel?: DeviceModelInfo;
+  componentSerials?: ComponentSerials;
+  board?: BoardInfo;
+  region?: RegionInfo;
+  claimedRegion?: string; // where the seller says the machine was sold
   battery: BatteryInfo;
   storage: StorageInfo;
+  disks?: StorageInfo[]; // every physical disk; `storage` is the boot disk
+  encryption?: EncryptionInfo; // system volume
+  gpus?: GpuInfo[];
+  displays?: DisplayInfo[];
+  panelAging?: PanelAgingInfo;
+  displayMeasurement?: DisplayMeasurementInfo;
+  cameras?: CameraInfo[]; // undefined when enumeration failed, empty when none was found
+  audio?: AudioInfo;
+  inputDevices?: InputDevicesInfo;
   system: SystemInfo;
   network: NetworkInfo;
   sensors: SensorInfo;
   interactive: InteractiveTestResult;
   refurbishment?: RefurbishmentInfo;
+  mdm?: MdmInfo;
+  security?: SecurityInfo;
+  windowsActivation?: WindowsActivationInfo;
+  osInstall?: OsInstallInfo;
+  usage?: UsageEstimateInfo;
+  signedInAccounts?: SignedInAccountsInfo;
+  dataRemnants?: DataRemnantsInfo;
+  thermal?: ThermalInfo;
+  cpuStress?: CpuStressResult;
+  memoryTest?: MemoryTestResult;
+  memoryModules?: MemoryModules;
+  energy?: EnergyInfo;
+  powerEvents?: PowerEventSummary;
+  crashHistory?: CrashHistory;
+  rtcBattery?: RtcBatteryInfo;
+  diskBenchmark?: DiskBenchmarkResult;
+  watchdogEvents?: WatchdogEvent[];
+  narrative?: string[];
+  narrativeLocale?: string; // set when a shop's Fluent file wrote the narrative
+  annotations?: ReportAnnotation[];
   rawData: Record<string, unknown>;
 }
+
+// The operator's notes on a report, sealed along with it
+export interface ReportAnnotation {
+  at: string; // ISO timestamp
+  text: string;
+}
+
+// A sealed, immutable version of a report (seal.rs)
+export interface ReportSeal {
+  reportId: string;
+  version: number;
+  sealedAt: string;
+  supersedes: string | null; // hash of the version it replaced
+  hash: string;
+}
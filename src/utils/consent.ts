@@ -0,0 +1,23 @@
+import type { TFunction } from 'i18next';
+import { invoke } from '@tauri-apps/api/core';
+import { ask } from '@tauri-apps/plugin-dialog';
+
+// Checks that see private things need the operator's yes once per session,
+// unless the shop pre-approved them (see consent.rs)
+export type ConsentScope = 'camera' | 'microphone' | 'logs';
+
+// Asks when the backend hasn't been given consent yet; the answer goes to the
+// audit log. False when the operator declined.
+export async function ensureConsent(scope: ConsentScope, t: TFunction): Promise<boolean> {
+  if (await invoke('check_consent', { scope })) {
+    return true;
+  }
+  const granted = await ask(t(`consent.${scope}`), {
+    title: t('consent.title'),
+    kind: 'warning',
+    okLabel: t('consent.allow'),
+    cancelLabel: t('consent.deny'),
+  });
+  await invoke('record_consent', { scope, granted });
+  return granted;
+}
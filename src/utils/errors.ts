@@ -0,0 +1,18 @@
+import type { TFunction } from 'i18next';
+import type { QuickscanError } from '../types';
+
+function isQuickscanError(error: unknown): error is QuickscanError {
+  return typeof error === 'object' && error !== null && 'kind' in error;
+}
+
+// Short, actionable text for a failed probe: retry, run as admin, or accept
+// that this platform can't report it
+export function describeError(error: unknown, t: TFunction): string {
+  if (!isQuickscanError(error)) {
+    return t('errors.command_failed');
+  }
+  return t(`errors.${error.kind}`, {
+    seconds: 'seconds' in error ? error.seconds : undefined,
+    resource: 'resource' in error ? t(`watchdog.resources.${error.resource}`) : undefined,
+  });
+}
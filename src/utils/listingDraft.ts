@@ -0,0 +1,63 @@
+import type { TFunction } from 'i18next';
+import type { DetectionReport, ReportSeal } from '../types';
+import { buildNarrative } from './narrative';
+import type { UnitFormatter } from './units';
+
+// Listing draft for bulk-listing tools (eBay, Swappa, ...). The shop still
+// reviews and prices it; this only saves retyping what the scan already knows.
+export interface ListingDraft {
+  title: string;
+  condition: string;
+  specs: string[];
+  photos: string[];
+  seal?: string; // "v2 <hash>", so a dispute can be matched to the sealed report
+}
+
+// RAM is sold in binary sizes ("16GB" means 16 GiB) whatever the byte unit preference
+const GB = 1024 * 1024 * 1024;
+
+export function buildListingDraft(report: DetectionReport, t: TFunction, units: UnitFormatter, seal?: ReportSeal): ListingDraft {
+  const memoryGb = Math.round(report.hardware.memory.total / GB);
+  const storageModel = report.storage.model !== 'Unknown' ? report.storage.model : '';
+
+  const titleParts = [report.deviceOverview.model];
+  if (memoryGb > 0) titleParts.push(t('listing.memory', { size: memoryGb }));
+  if (storageModel) titleParts.push(storageModel);
+
+  const specs = [
+    t('listing.spec.cpu', { model: report.hardware.cpu.model, cores: report.hardware.cpu.cores }),
+    t('listing.spec.os', { os: report.deviceOverview.os }),
+  ];
+  if (memoryGb > 0) specs.push(t('listing.spec.memory', { size: memoryGb }));
+  if (storageModel) specs.push(t('listing.spec.storage', { model: storageModel }));
+  // Buyers filter by layout, and listings often get it wrong
+  const layout = report.inputDevices?.physicalLayout;
+  if (layout) specs.push(t('listing.spec.keyboard', { layout: t(`hardware.physicalLayouts.${layout}`) }));
+  if (report.battery.designCapacity > 0) {
+    specs.push(t('listing.spec.battery', { health: Math.round(report.battery.health), cycles: units.number(report.battery.cycleCount) }));
+  }
+  for (const part of report.refurbishment?.replacedParts ?? []) {
+    specs.push(t('listing.spec.replacedPart', { part: t(`refurbishment.parts.${part}`, { defaultValue: part }) }));
+  }
+
+  const interactive = report.interactive;
+  const photos = [interactive.screen.screenshot, interactive.keyboard.screenshot, interactive.camera.screenshot]
+    .filter((photo): photo is string => !!photo);
+
+  return {
+    title: titleParts.join(' · '),
+    condition: (report.narrativeLocale && report.narrative ? report.narrative : buildNarrative(report, t)).join('\n\n'),
+    specs,
+    photos,
+    seal: seal ? `v${seal.version} ${seal.hash}` : undefined,
+  };
+}
+
+// One row per draft; multi-value fields are joined with " | " as most
+// bulk-listing templates expect a single cell
+export function listingDraftToCsv(draft: ListingDraft): string {
+  const escape = (value: string) => `"${value.replace(/"/g, '""')}"`;
+  const header = ['title', 'condition', 'specs', 'photos', 'seal'].join(',');
+  const row = [draft.title, draft.condition, draft.specs.join(' | '), draft.photos.join(' | '), draft.seal ?? ''].map(escape).join(',');
+  return `${header}\n${row}\n`;
+}
@@ -0,0 +1,133 @@
+import type { TFunction } from 'i18next';
+import { invoke } from '@tauri-apps/api/core';
+import type { DetectionReport } from '../types';
+
+// One sentence: a translation key and its arguments. Kept as data so a shop's
+// Fluent translation (see locales.rs) can render the same sentences.
+export interface NarrativeMessage {
+  id: string;
+  args?: Record<string, NarrativeArg>;
+  defaultValue?: string; // when even the built-in key is missing
+  lowercase?: boolean; // built-in text only; a Fluent file chooses its own casing
+}
+
+type NarrativeArg = string | number | NarrativeMessage | NarrativeMessage[];
+
+export interface LocalizedNarrative {
+  locale: string; // the Fluent file that was used
+  paragraphs: string[];
+}
+
+// Turns the scan results into a few plain-language paragraphs for buyers who
+// don't want to read the indicator list.
+function narrativeMessages(report: DetectionReport): NarrativeMessage[][] {
+  const refurb = report.refurbishment;
+  const indicators = (refurb?.indicators ?? []).filter(i => i.detected);
+  const paragraphs: NarrativeMessage[][] = [];
+
+  // History: what the device is and what it has been through
+  const history: NarrativeMessage[] = [
+    { id: 'narrative.device', args: { model: report.deviceOverview.model, os: report.deviceOverview.os } },
+  ];
+  if (refurb?.details.osInstallDate) {
+    history.push({ id: 'narrative.osInstalled', args: { date: refurb.details.osInstallDate } });
+  }
+  if (refurb?.details.refurbProgram) {
+    history.push({ id: 'narrative.refurbProgram', args: { program: refurb.details.refurbProgram } });
+  }
+  if (indicators.some(i => i.name === 'enterprise_managed')) {
+    history.push({ id: 'narrative.enterpriseManaged' });
+  }
+  if (report.system.activationLock) {
+    history.push({ id: 'narrative.activationLock' });
+  }
+  paragraphs.push(history);
+
+  // Condition: battery and parts
+  const condition: NarrativeMessage[] = [];
+  if (report.battery.designCapacity > 0) {
+    condition.push({
+      id: 'narrative.battery',
+      args: {
+        health: Math.round(report.battery.health),
+        cycles: report.battery.cycleCount,
+        rating: { id: `battery.rating.${report.battery.rating}`, lowercase: true },
+      },
+    });
+  }
+  const replacedParts = refurb?.replacedParts ?? [];
+  if (replacedParts.length > 0) {
+    const parts = replacedParts.map(part => ({ id: `refurbishment.parts.${part}`, defaultValue: part }));
+    condition.push({ id: 'narrative.partsReplaced', args: { parts } });
+  } else if (refurb) {
+    condition.push({ id: 'narrative.noPartsReplaced' });
+  }
+  const missingCount = indicators.filter(i => i.name === 'component_missing').length;
+  if (missingCount > 0) {
+    condition.push({ id: 'narrative.componentsMissing', args: { count: missingCount } });
+  }
+  if (condition.length > 0) {
+    paragraphs.push(condition);
+  }
+
+  // Verdict
+  const verdict: NarrativeMessage[] = [
+    {
+      id: 'narrative.score',
+      args: {
+        score: report.overallScore,
+        passed: report.summary.passed,
+        warning: report.summary.warning,
+        failed: report.summary.failed,
+      },
+    },
+  ];
+  if (refurb?.isRefurbished) {
+    verdict.push({ id: 'narrative.refurbished', args: { confidence: { id: `refurbishment.confidence.${refurb.confidence}` } } });
+  }
+  const criticalCount = indicators.filter(i => i.severity === 'critical').length;
+  if (criticalCount > 0) {
+    verdict.push({ id: 'narrative.critical', args: { count: criticalCount } });
+  }
+  paragraphs.push(verdict);
+
+  return paragraphs;
+}
+
+function renderMessage(message: NarrativeMessage, t: TFunction): string {
+  const args: Record<string, string | number> = {};
+  for (const [name, arg] of Object.entries(message.args ?? {})) {
+    args[name] = typeof arg === 'string' || typeof arg === 'number'
+      ? arg
+      : Array.isArray(arg) ? arg.map(item => renderMessage(item, t)).join(', ') : renderMessage(arg, t);
+  }
+  const text = message.defaultValue !== undefined
+    ? t(message.id, { ...args, defaultValue: message.defaultValue })
+    : t(message.id, args);
+  return message.lowercase ? text.toLowerCase() : text;
+}
+
+export function buildNarrative(report: DetectionReport, t: TFunction): string[] {
+  return narrativeMessages(report).map(sentences => sentences.map(message => renderMessage(message, t)).join(' '));
+}
+
+// The narrative from the shop's Fluent file for `locale`, with the built-in
+// text for any sentence the file doesn't translate. Null without a file.
+export async function buildLocalizedNarrative(
+  report: DetectionReport,
+  t: TFunction,
+  locale: string,
+): Promise<LocalizedNarrative | null> {
+  type WireMessage = { id: string; args: Record<string, string | number | WireMessage | WireMessage[]>; fallback: string };
+  const toWire = (message: NarrativeMessage): WireMessage => {
+    const args: WireMessage['args'] = {};
+    for (const [name, arg] of Object.entries(message.args ?? {})) {
+      args[name] = typeof arg === 'string' || typeof arg === 'number'
+        ? arg
+        : Array.isArray(arg) ? arg.map(toWire) : toWire(arg);
+    }
+    return { id: message.id, args, fallback: renderMessage(message, t) };
+  };
+  const paragraphs = narrativeMessages(report).map(sentences => sentences.map(toWire));
+  return await invoke('render_narrative', { locale, paragraphs }) as LocalizedNarrative | null;
+}
@@ -0,0 +1,37 @@
+import { useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+
+// The shop's grading thresholds from scan_config.json (see scan_config.rs).
+// Only the parts the frontend judges by itself are listed here.
+export interface BatteryThresholds {
+  good_percent: number;
+  fair_percent: number;
+  cycle_warning: number;
+  cycle_limit: number;
+}
+
+export interface ScanConfig {
+  battery: BatteryThresholds;
+}
+
+export const DEFAULT_SCAN_CONFIG: ScanConfig = {
+  battery: { good_percent: 80, fair_percent: 60, cycle_warning: 500, cycle_limit: 800 },
+};
+
+// A broken config is reported by get_scan_config; the scan carries on with the defaults
+export async function loadScanConfig(): Promise<ScanConfig> {
+  try {
+    return await invoke('get_scan_config') as ScanConfig;
+  } catch (error) {
+    console.error('Failed to load scan config:', error);
+    return DEFAULT_SCAN_CONFIG;
+  }
+}
+
+export function useScanConfig(): ScanConfig {
+  const [config, setConfig] = useState(DEFAULT_SCAN_CONFIG);
+  useEffect(() => {
+    loadScanConfig().then(setConfig);
+  }, []);
+  return config;
+}
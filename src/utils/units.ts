@@ -0,0 +1,67 @@
+import { useEffect, useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { invoke } from '@tauri-apps/api/core';
+
+// Display units from the shop's preferences.json (see preferences.rs). Only
+// what's shown or exported as text is converted; the report JSON keeps raw
+// bytes and degrees Celsius.
+export interface UnitPreferences {
+  byte_units: 'binary' | 'decimal';
+  temperature: 'celsius' | 'fahrenheit';
+  locale: string | null;
+}
+
+export interface UnitFormatter {
+  bytes: (bytes: number) => string;
+  temperature: (celsius: number) => string;
+  number: (value: number, maximumFractionDigits?: number) => string;
+}
+
+export const DEFAULT_PREFERENCES: UnitPreferences = {
+  byte_units: 'binary',
+  temperature: 'celsius',
+  locale: null,
+};
+
+const BINARY_UNITS = ['B', 'KiB', 'MiB', 'GiB', 'TiB'];
+const DECIMAL_UNITS = ['B', 'KB', 'MB', 'GB', 'TB'];
+
+export function createFormatter(preferences: UnitPreferences, language: string): UnitFormatter {
+  const locale = preferences.locale ?? language;
+  const number = (value: number, maximumFractionDigits = 0) =>
+    new Intl.NumberFormat(locale, { maximumFractionDigits }).format(value);
+
+  return {
+    number,
+    bytes: (bytes) => {
+      const decimal = preferences.byte_units === 'decimal';
+      const k = decimal ? 1000 : 1024;
+      const sizes = decimal ? DECIMAL_UNITS : BINARY_UNITS;
+      const i = bytes > 0 ? Math.min(Math.floor(Math.log(bytes) / Math.log(k)), sizes.length - 1) : 0;
+      return `${number(bytes / Math.pow(k, i), 2)} ${sizes[i]}`;
+    },
+    temperature: (celsius) =>
+      preferences.temperature === 'fahrenheit'
+        ? `${number((celsius * 9) / 5 + 32)}°F`
+        : `${number(celsius)}°C`,
+  };
+}
+
+// A missing or broken preferences file falls back to the defaults rather than
+// holding up the scan
+export async function loadPreferences(): Promise<UnitPreferences> {
+  try {
+    return await invoke('get_preferences') as UnitPreferences;
+  } catch {
+    return DEFAULT_PREFERENCES;
+  }
+}
+
+export function useUnits(): UnitFormatter {
+  const { i18n } = useTranslation();
+  const [preferences, setPreferences] = useState(DEFAULT_PREFERENCES);
+  useEffect(() => {
+    loadPreferences().then(setPreferences);
+  }, []);
+  return createFormatter(preferences, i18n.language);
+}